@@ -0,0 +1,59 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+pub enum Cmd {
+    #[clap(about = "Show audit log entries, starting with the most recent one")]
+    Show(ShowArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct ShowArgs {
+    #[clap(
+        long,
+        help = "Only show calls of this principal, e.g. external-user/admins/jdoe"
+    )]
+    pub principal: Option<String>,
+
+    #[clap(long, help = "Only show calls which targeted the object with this ID")]
+    pub object_id: Option<String>,
+
+    #[clap(long, help = "Only show calls of this RPC, e.g. SetMaintenance")]
+    pub rpc_name: Option<String>,
+
+    #[clap(
+        long,
+        help = "Only show calls at or after this time (RFC 3339, e.g. 2026-03-01T10:00:00Z)"
+    )]
+    pub since: Option<DateTime<Utc>>,
+
+    #[clap(
+        long,
+        help = "Only show calls at or before this time (RFC 3339, e.g. 2026-03-01T10:00:00Z)"
+    )]
+    pub until: Option<DateTime<Utc>>,
+
+    #[clap(
+        long,
+        default_value_t = 100,
+        help = "Maximum amount of entries to show"
+    )]
+    pub limit: u32,
+}
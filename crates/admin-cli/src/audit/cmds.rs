@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge as rpc;
+use prettytable::{Cell, Row, Table};
+
+use super::args::ShowArgs;
+use crate::rpc::ApiClient;
+
+pub async fn show(
+    args: ShowArgs,
+    format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let entries = api_client
+        .0
+        .find_audit_log_entries(rpc::FindAuditLogEntriesRequest {
+            principal: args.principal,
+            object_id: args.object_id,
+            rpc_name: args.rpc_name,
+            start_time: args.since.map(Into::into),
+            end_time: args.until.map(Into::into),
+            limit: Some(args.limit),
+        })
+        .await?;
+
+    match format {
+        OutputFormat::AsciiTable => {
+            entries_to_table(&entries).printstd();
+        }
+        OutputFormat::Csv => {
+            println!("timestamp,principal,rpc_name,object_ids,outcome,error");
+            for entry in &entries.entries {
+                println!(
+                    "{},{},{},{},{},{}",
+                    timestamp(entry),
+                    entry.principal,
+                    entry.rpc_name,
+                    entry.object_ids.join(" "),
+                    entry.outcome().as_str_name(),
+                    entry.error.as_deref().unwrap_or_default()
+                )
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?)
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&entries)?)
+        }
+    }
+
+    Ok(())
+}
+
+fn timestamp(entry: &rpc::AuditLogEntry) -> String {
+    entry
+        .timestamp
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
+
+// entries_to_table converts the AuditLogEntries response into
+// a pretty ASCII table. The recorded requests are only part of the
+// JSON and YAML output, since they are too large for a table.
+fn entries_to_table(entries: &rpc::AuditLogEntries) -> Table {
+    let mut table = Table::new();
+
+    table.set_titles(Row::new(vec![
+        Cell::new("Time"),
+        Cell::new("Principal"),
+        Cell::new("RPC"),
+        Cell::new("Objects"),
+        Cell::new("Outcome"),
+        Cell::new("Error"),
+    ]));
+
+    for entry in &entries.entries {
+        table.add_row(Row::new(vec![
+            Cell::new(&timestamp(entry)),
+            Cell::new(&entry.principal),
+            Cell::new(&entry.rpc_name),
+            Cell::new(&entry.object_ids.join("\n")),
+            Cell::new(entry.outcome().as_str_name()),
+            Cell::new(entry.error.as_deref().unwrap_or_default()),
+        ]));
+    }
+
+    table
+}
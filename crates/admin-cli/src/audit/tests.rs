@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+
+use clap::{CommandFactory, Parser};
+
+use super::args::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_show_no_args ensures show parses with no arguments,
+// and applies the default limit.
+#[test]
+fn parse_show_no_args() {
+    let cmd = Cmd::try_parse_from(["audit", "show"]).expect("should parse show");
+
+    match cmd {
+        Cmd::Show(args) => {
+            assert!(args.principal.is_none());
+            assert!(args.object_id.is_none());
+            assert!(args.since.is_none());
+            assert_eq!(args.limit, 100);
+        }
+    }
+}
+
+// parse_show_with_filters ensures show parses all filters.
+#[test]
+fn parse_show_with_filters() {
+    let cmd = Cmd::try_parse_from([
+        "audit",
+        "show",
+        "--principal",
+        "external-user/admins/jdoe",
+        "--object-id",
+        "fm100htjtiaehv1n5vh67tbmqq4eabcjdng40f7jupsadbedhruh6rag1l0",
+        "--rpc-name",
+        "SetMaintenance",
+        "--since",
+        "2026-03-01T10:00:00Z",
+        "--until",
+        "2026-03-02T10:00:00Z",
+        "--limit",
+        "10",
+    ])
+    .expect("should parse show with filters");
+
+    match cmd {
+        Cmd::Show(args) => {
+            assert_eq!(args.principal.as_deref(), Some("external-user/admins/jdoe"));
+            assert_eq!(args.rpc_name.as_deref(), Some("SetMaintenance"));
+            assert_eq!(
+                args.since.unwrap().to_rfc3339(),
+                "2026-03-01T10:00:00+00:00"
+            );
+            assert!(args.until.unwrap() > args.since.unwrap());
+            assert_eq!(args.limit, 10);
+        }
+    }
+}
+
+// parse_show_invalid_time ensures show rejects times
+// which are not in RFC 3339 format.
+#[test]
+fn parse_show_invalid_time() {
+    let result = Cmd::try_parse_from(["audit", "show", "--since", "yesterday"]);
+
+    assert!(result.is_err(), "should fail with invalid time");
+}
@@ -19,7 +19,7 @@ use rpc::admin_cli::OutputFormat;
 
 use crate::cfg::measurement;
 use crate::{
-    bmc_machine, boot_override, credential, devenv, domain, dpa, dpu, dpu_remediation,
+    audit, bmc_machine, boot_override, credential, devenv, domain, dpa, dpu, dpu_remediation,
     expected_machines, expected_power_shelf, expected_switch, extension_service, firmware,
     generate_shell_complete, host, ib_partition, instance, instance_type, inventory, ip, jump,
     machine, machine_interfaces, machine_validation, managed_host, mlx, network_devices,
@@ -186,6 +186,8 @@ pub enum CliCommand {
     Credential(credential::Cmd),
     #[clap(about = "Route server handling", subcommand)]
     RouteServer(route_server::Cmd),
+    #[clap(about = "Audit log of mutating RPCs", subcommand)]
+    Audit(audit::Cmd),
     #[clap(about = "Site explorer functions", subcommand)]
     SiteExplorer(site_explorer::Cmd),
     #[clap(
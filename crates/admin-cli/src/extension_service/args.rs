@@ -38,6 +38,10 @@ pub enum Cmd {
 pub enum ExtensionServiceType {
     #[value(alias = "k8s")]
     KubernetesPod = 0, // Kubernetes pod service type
+    #[value(alias = "containerd")]
+    ContainerdContainer = 1, // Plain containerd container service type
+    #[value(alias = "systemd")]
+    SystemdUnit = 2, // Systemd unit service type
 }
 
 impl From<ExtensionServiceType> for i32 {
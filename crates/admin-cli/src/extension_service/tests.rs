@@ -287,5 +287,23 @@ fn extension_service_type_value_enum() {
         ExtensionServiceType::from_str("k8s", false),
         Ok(ExtensionServiceType::KubernetesPod)
     ));
+    assert!(matches!(
+        ExtensionServiceType::from_str("containerd-container", false),
+        Ok(ExtensionServiceType::ContainerdContainer)
+    ));
+    // "containerd" is an alias for ContainerdContainer
+    assert!(matches!(
+        ExtensionServiceType::from_str("containerd", false),
+        Ok(ExtensionServiceType::ContainerdContainer)
+    ));
+    assert!(matches!(
+        ExtensionServiceType::from_str("systemd-unit", false),
+        Ok(ExtensionServiceType::SystemdUnit)
+    ));
+    // "systemd" is an alias for SystemdUnit
+    assert!(matches!(
+        ExtensionServiceType::from_str("systemd", false),
+        Ok(ExtensionServiceType::SystemdUnit)
+    ));
     assert!(ExtensionServiceType::from_str("invalid", false).is_err());
 }
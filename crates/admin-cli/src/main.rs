@@ -38,6 +38,7 @@ use crate::cfg::runtime::{RuntimeConfig, RuntimeContext};
 use crate::rpc::ApiClient;
 
 mod async_write;
+mod audit;
 mod bmc_machine;
 mod boot_override;
 mod cfg;
@@ -198,6 +199,7 @@ async fn main() -> color_eyre::Result<()> {
 
     // Command to talk to Carbide API.
     match command {
+        CliCommand::Audit(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::BmcMachine(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::BootOverride(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Credential(cmd) => cmd.dispatch(ctx).await?,
@@ -29,10 +29,11 @@
  *  - `report list all`: List high level info about all reports.
  *  - `report list machine`: List all reports for a given machine.
  *  - `report match``
+ *  - `report diff`: Diff a report's event log against a bundle's.
 */
 
 use carbide_uuid::machine::MachineId;
-use carbide_uuid::measured_boot::MeasurementReportId;
+use carbide_uuid::measured_boot::{MeasurementBundleId, MeasurementReportId};
 use clap::Parser;
 use measured_boot::pcr::{PcrRegisterValue, PcrSet, parse_pcr_index_input};
 
@@ -83,6 +84,12 @@ pub enum CmdReport {
         visible_alias = "m"
     )]
     Match(Match),
+
+    #[clap(
+        about = "Replay a report's event log and diff it against a bundle's, event by event.",
+        visible_alias = "x"
+    )]
+    Diff(Diff),
 }
 
 /// Create is used for creating reports, which really
@@ -204,3 +211,15 @@ pub struct Match {
     #[arg(value_parser = parse_pcr_register_values)]
     pub values: Vec<PcrRegisterValue>,
 }
+
+/// Diff is used for replaying the event log of a report, and diffing
+/// it against the event log of a bundle. If no bundle is provided, the
+/// bundle the report matched is used, or the closest bundle otherwise.
+#[derive(Parser, Debug)]
+pub struct Diff {
+    #[clap(help = "The report ID to diff.")]
+    pub report_id: MeasurementReportId,
+
+    #[clap(long, help = "The bundle ID to diff the report against.")]
+    pub bundle_id: Option<MeasurementBundleId>,
+}
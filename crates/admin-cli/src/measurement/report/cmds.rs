@@ -21,20 +21,21 @@
 
 use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, ToTable, cli_output};
 use ::rpc::protos::measured_boot::{
-    CreateMeasurementReportRequest, DeleteMeasurementReportRequest, ListMeasurementReportRequest,
-    MatchMeasurementReportRequest, PromoteMeasurementReportRequest, RevokeMeasurementReportRequest,
-    ShowMeasurementReportForIdRequest, ShowMeasurementReportsForMachineRequest,
-    list_measurement_report_request,
+    CreateMeasurementReportRequest, DeleteMeasurementReportRequest, DiffMeasurementReportRequest,
+    ListMeasurementReportRequest, MatchMeasurementReportRequest, PromoteMeasurementReportRequest,
+    RevokeMeasurementReportRequest, ShowMeasurementReportForIdRequest,
+    ShowMeasurementReportsForMachineRequest, list_measurement_report_request,
 };
 use measured_boot::bundle::MeasurementBundle;
+use measured_boot::eventlog::MeasurementReportDiff;
 use measured_boot::records::MeasurementReportRecord;
 use measured_boot::report::MeasurementReport;
 use serde::Serialize;
 
 use crate::measurement::global;
 use crate::measurement::report::args::{
-    CmdReport, Create, Delete, List, ListMachines, Match, Promote, Revoke, ShowFor, ShowForId,
-    ShowForMachine,
+    CmdReport, Create, Delete, Diff, List, ListMachines, Match, Promote, Revoke, ShowFor,
+    ShowForId, ShowForMachine,
 };
 use crate::rpc::ApiClient;
 
@@ -117,6 +118,13 @@ pub async fn dispatch(
                 ::rpc::admin_cli::Destination::Stdout(),
             )?;
         }
+        CmdReport::Diff(local_args) => {
+            cli_output(
+                diff(cli.grpc_conn, local_args).await?,
+                &cli.args.format,
+                ::rpc::admin_cli::Destination::Stdout(),
+            )?;
+        }
     }
     Ok(())
 }
@@ -335,6 +343,23 @@ pub async fn match_values(
     ))
 }
 
+/// diff replays the event log of a report, and diffs it against
+/// the event log of a bundle, event by event.
+///
+/// `report diff <report-id> [--bundle-id <bundle-id>]`
+pub async fn diff(grpc_conn: &ApiClient, diff: Diff) -> CarbideCliResult<MeasurementReportDiff> {
+    // Request.
+    let request = DiffMeasurementReportRequest {
+        report_id: Some(diff.report_id),
+        bundle_id: diff.bundle_id,
+    };
+
+    // Response.
+    let response = grpc_conn.0.diff_measurement_report(request).await?;
+
+    Ok(MeasurementReportDiff::from(response))
+}
+
 /// MeasurementReportRecordList just implements a newtype pattern
 /// for a Vec<MeasurementReportRecord> so the ToTable trait can
 /// be leveraged (since we don't define Vec).
@@ -373,6 +398,14 @@ impl ToTable for MeasurementReportList {
             details_table.add_row(prettytable::row!["report_id", report.report_id]);
             details_table.add_row(prettytable::row!["machine_id", report.machine_id]);
             details_table.add_row(prettytable::row!["created_ts", report.ts]);
+            details_table.add_row(prettytable::row![
+                "event_log_replay",
+                report
+                    .event_log_replay
+                    .as_ref()
+                    .map(|replay| replay.to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            ]);
             let mut values_table = prettytable::Table::new();
             values_table.add_row(prettytable::row!["pcr_register", "value"]);
             for value_record in report.values.iter() {
@@ -14,6 +14,8 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::net::IpAddr;
+
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::vpc::VpcId;
 use clap::Parser;
@@ -53,6 +55,12 @@ pub enum Cmd {
         visible_alias = "r"
     )]
     Detach(DetachNetworkSecurityGroup),
+
+    #[clap(
+        about = "Explain which network security group rule applies to a flow of an instance interface",
+        visible_alias = "e"
+    )]
+    Explain(ExplainNetworkSecurityGroupFlow),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -214,3 +222,78 @@ pub struct DetachNetworkSecurityGroup {
     )]
     pub instance_id: Option<InstanceId>,
 }
+
+#[derive(Parser, Debug, Clone)]
+pub struct ExplainNetworkSecurityGroupFlow {
+    #[clap(short = 'i', long, help = "Instance ID the flow belongs to")]
+    pub instance_id: InstanceId,
+
+    #[clap(
+        short = 'f',
+        long,
+        help = "Optional, virtual function ID of the instance interface - the physical interface is used if unset"
+    )]
+    pub virtual_function_id: Option<u32>,
+
+    #[clap(
+        short = 'd',
+        long,
+        help = "Direction of the flow from the perspective of the instance"
+    )]
+    #[arg(value_enum)]
+    pub direction: FlowDirection,
+
+    #[clap(short = 'p', long, help = "Protocol of the flow")]
+    #[arg(value_enum)]
+    pub protocol: FlowProtocol,
+
+    #[clap(long, help = "Source IP of the flow")]
+    pub src_ip: IpAddr,
+
+    #[clap(long, help = "Destination IP of the flow")]
+    pub dst_ip: IpAddr,
+
+    #[clap(long, help = "Source port of the flow, required for TCP and UDP")]
+    pub src_port: Option<u32>,
+
+    #[clap(long, help = "Destination port of the flow, required for TCP and UDP")]
+    pub dst_port: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum FlowDirection {
+    Ingress,
+    Egress,
+}
+
+impl From<FlowDirection> for rpc::forge::NetworkSecurityGroupRuleDirection {
+    fn from(d: FlowDirection) -> Self {
+        match d {
+            FlowDirection::Ingress => {
+                rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress
+            }
+            FlowDirection::Egress => {
+                rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionEgress
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum FlowProtocol {
+    Tcp,
+    Udp,
+    Icmp,
+    Icmp6,
+}
+
+impl From<FlowProtocol> for rpc::forge::NetworkSecurityGroupRuleProtocol {
+    fn from(p: FlowProtocol) -> Self {
+        match p {
+            FlowProtocol::Tcp => rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+            FlowProtocol::Udp => rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoUdp,
+            FlowProtocol::Icmp => rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp,
+            FlowProtocol::Icmp6 => rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp6,
+        }
+    }
+}
@@ -23,8 +23,8 @@ use prettytable::{Table, row};
 
 use super::args::{
     AttachNetworkSecurityGroup, CreateNetworkSecurityGroup, DeleteNetworkSecurityGroup,
-    DetachNetworkSecurityGroup, ShowNetworkSecurityGroup, ShowNetworkSecurityGroupAttachments,
-    UpdateNetworkSecurityGroup,
+    DetachNetworkSecurityGroup, ExplainNetworkSecurityGroupFlow, ShowNetworkSecurityGroup,
+    ShowNetworkSecurityGroupAttachments, UpdateNetworkSecurityGroup,
 };
 use crate::rpc::ApiClient;
 
@@ -563,3 +563,62 @@ pub async fn detach(
 
     Ok(())
 }
+
+/// Evaluates a synthetic flow of an instance interface against the
+/// network security group rules that apply to it and shows the verdict.
+pub async fn explain(
+    args: ExplainNetworkSecurityGroupFlow,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let explanation = api_client
+        .0
+        .explain_network_security_group_flow(forgerpc::ExplainNetworkSecurityGroupFlowRequest {
+            instance_id: Some(args.instance_id),
+            virtual_function_id: args.virtual_function_id,
+            direction: forgerpc::NetworkSecurityGroupRuleDirection::from(args.direction).into(),
+            protocol: forgerpc::NetworkSecurityGroupRuleProtocol::from(args.protocol).into(),
+            src_ip: args.src_ip.to_string(),
+            dst_ip: args.dst_ip.to_string(),
+            src_port: args.src_port,
+            dst_port: args.dst_port,
+        })
+        .await?;
+
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&explanation).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut table = Box::new(Table::new());
+
+    table.add_row(row!["Action", explanation.action().as_str_name()]);
+    table.add_row(row!["Decided By", explanation.rule_source().as_str_name()]);
+    table.add_row(row![
+        "Network Security Group Source",
+        explanation.network_security_group_source().as_str_name()
+    ]);
+    table.add_row(row![
+        "Network Security Group ID",
+        explanation.network_security_group_id()
+    ]);
+    table.add_row(row![
+        "Network Security Group Version",
+        explanation.network_security_group_version()
+    ]);
+    table.add_row(row!["Stateful Egress", explanation.stateful_egress]);
+    table.add_row(row![
+        "Matched Rule",
+        match explanation.matched_rule.as_ref() {
+            None => "-".to_string(),
+            Some(rule) => serde_json::to_string_pretty(rule).map_err(CarbideCliError::JsonError)?,
+        }
+    ]);
+
+    table.printstd();
+
+    Ok(())
+}
@@ -48,6 +48,7 @@ impl Dispatch for Cmd {
             }
             Cmd::Attach(args) => cmds::attach(args, &ctx.api_client).await,
             Cmd::Detach(args) => cmds::detach(args, &ctx.api_client).await,
+            Cmd::Explain(args) => cmds::explain(args, ctx.config.format, &ctx.api_client).await,
         }
     }
 }
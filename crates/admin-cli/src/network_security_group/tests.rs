@@ -222,6 +222,58 @@ fn parse_detach() {
     }
 }
 
+// parse_explain ensures explain parses a flow.
+#[test]
+fn parse_explain() {
+    let cmd = Cmd::try_parse_from([
+        "network-security-group",
+        "explain",
+        "--instance-id",
+        "8c6a4ef0-1a2b-4c3d-9e8f-0123456789ab",
+        "--direction",
+        "ingress",
+        "--protocol",
+        "tcp",
+        "--src-ip",
+        "192.0.2.10",
+        "--dst-ip",
+        "198.51.100.5",
+        "--dst-port",
+        "22",
+    ])
+    .expect("should parse explain");
+
+    match cmd {
+        Cmd::Explain(args) => {
+            assert_eq!(args.direction, FlowDirection::Ingress);
+            assert_eq!(args.protocol, FlowProtocol::Tcp);
+            assert_eq!(args.dst_port, Some(22));
+            assert!(args.src_port.is_none());
+            assert!(args.virtual_function_id.is_none());
+        }
+        _ => panic!("expected Explain variant"),
+    }
+}
+
+// parse_explain_missing_protocol_fails ensures explain
+// fails without a protocol.
+#[test]
+fn parse_explain_missing_protocol_fails() {
+    let result = Cmd::try_parse_from([
+        "network-security-group",
+        "explain",
+        "--instance-id",
+        "8c6a4ef0-1a2b-4c3d-9e8f-0123456789ab",
+        "--direction",
+        "egress",
+        "--src-ip",
+        "192.0.2.10",
+        "--dst-ip",
+        "198.51.100.5",
+    ]);
+    assert!(result.is_err(), "should fail without --protocol");
+}
+
 // parse_create_missing_required_fails ensures create
 // fails without tenant org ID.
 #[test]
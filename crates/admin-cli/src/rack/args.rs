@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use carbide_uuid::rack::RackId;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -25,6 +26,10 @@ pub enum Cmd {
     List,
     #[clap(about = "Delete the rack")]
     Delete(DeleteRack),
+    #[clap(
+        about = "Power on, off or reset all devices of the rack in order: power shelves, NVLink switches, then compute trays (reversed for power off)"
+    )]
+    Power(PowerRack),
 }
 
 #[derive(Parser, Debug)]
@@ -40,3 +45,35 @@ pub struct DeleteRack {
     )]
     pub identifier: String,
 }
+
+#[derive(Parser, Debug)]
+pub struct PowerRack {
+    #[clap(help = "Rack ID to power sequence")]
+    pub rack_id: RackId,
+
+    #[clap(value_enum, help = "Power action to sequence")]
+    pub action: RackPowerAction,
+
+    #[clap(
+        long,
+        help = "Number of compute trays which are powered on or off at the same time"
+    )]
+    pub compute_wave_size: Option<u32>,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum RackPowerAction {
+    On,
+    Off,
+    Reset,
+}
+
+impl From<RackPowerAction> for rpc::forge::RackPowerAction {
+    fn from(action: RackPowerAction) -> Self {
+        match action {
+            RackPowerAction::On => rpc::forge::RackPowerAction::On,
+            RackPowerAction::Off => rpc::forge::RackPowerAction::Off,
+            RackPowerAction::Reset => rpc::forge::RackPowerAction::Reset,
+        }
+    }
+}
@@ -19,7 +19,7 @@ use color_eyre::Result;
 use prettytable::{Cell, Row, Table};
 use rpc::admin_cli::OutputFormat;
 
-use super::args::{DeleteRack, ShowRack};
+use super::args::{DeleteRack, PowerRack, ShowRack};
 use crate::rpc::ApiClient;
 
 pub async fn show_rack(api_client: &ApiClient, show_opts: ShowRack) -> Result<()> {
@@ -57,6 +57,49 @@ pub async fn show_rack(api_client: &ApiClient, show_opts: ShowRack) -> Result<()
             println!("  {}", ps_id);
         }
         println!("Current NVLink Switches");
+        if let Some(upgrade) = r.firmware_upgrade {
+            println!(
+                "Firmware Upgrade: {} ({}), max unavailable {}",
+                upgrade.firmware_id, upgrade.firmware_type, upgrade.max_unavailable
+            );
+            for device in upgrade.devices {
+                println!(
+                    "  {} {}: {}{}",
+                    device.device_type,
+                    device.device_id,
+                    device.status,
+                    device
+                        .message
+                        .map(|message| format!(" ({message})"))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        if let Some(sequence) = r.power_sequence {
+            let progress = if let Some(error) = &sequence.error {
+                format!("failed: {error}")
+            } else if sequence.completed_at.is_some() {
+                "completed".to_string()
+            } else if sequence.started_at.is_some() {
+                format!(
+                    "step {}/{}",
+                    (sequence.current_step as usize + 1).min(sequence.steps.len()),
+                    sequence.steps.len()
+                )
+            } else {
+                "pending".to_string()
+            };
+            println!("Power Sequence: {} ({})", sequence.action, progress);
+            for (index, step) in sequence.steps.iter().enumerate() {
+                println!(
+                    "  {}. {} {}: {}",
+                    index + 1,
+                    step.target,
+                    step.device_type,
+                    step.bmc_mac_addresses.join(", ")
+                );
+            }
+        }
     }
     Ok(())
 }
@@ -133,3 +176,19 @@ pub async fn delete_rack(api_client: &ApiClient, delete_opts: DeleteRack) -> Res
     api_client.0.delete_rack(query).await?;
     Ok(())
 }
+
+pub async fn power_rack(api_client: &ApiClient, power_opts: PowerRack) -> Result<()> {
+    let action: rpc::forge::RackPowerAction = power_opts.action.into();
+    let query = rpc::forge::SetRackPowerStateRequest {
+        rack_id: Some(power_opts.rack_id),
+        action: action as i32,
+        compute_wave_size: power_opts.compute_wave_size,
+    };
+    api_client.0.set_rack_power_state(query).await?;
+    println!(
+        "Scheduled power sequence {} for rack {}. Use `rack show` to follow its progress.",
+        action.as_str_name(),
+        power_opts.rack_id
+    );
+    Ok(())
+}
@@ -33,6 +33,7 @@ impl Dispatch for Cmd {
             Cmd::Show(show_opts) => cmds::show_rack(&ctx.api_client, show_opts).await?,
             Cmd::List => cmds::list_racks(&ctx.api_client).await?,
             Cmd::Delete(delete_opts) => cmds::delete_rack(&ctx.api_client, delete_opts).await?,
+            Cmd::Power(power_opts) => cmds::power_rack(&ctx.api_client, power_opts).await?,
         }
         Ok(())
     }
@@ -100,3 +100,39 @@ fn parse_delete_missing_identifier_fails() {
     let result = Cmd::try_parse_from(["rack", "delete"]);
     assert!(result.is_err(), "should fail without identifier");
 }
+
+// parse_power ensures power parses with rack ID, action and
+// compute wave size.
+#[test]
+fn parse_power() {
+    let cmd = Cmd::try_parse_from([
+        "rack",
+        "power",
+        "ps100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg",
+        "reset",
+        "--compute-wave-size",
+        "2",
+    ])
+    .expect("should parse power");
+
+    match cmd {
+        Cmd::Power(args) => {
+            assert!(matches!(args.action, RackPowerAction::Reset));
+            assert_eq!(args.compute_wave_size, Some(2));
+        }
+        _ => panic!("expected Power variant"),
+    }
+}
+
+// parse_power_invalid_action_fails ensures power fails
+// with an unknown action.
+#[test]
+fn parse_power_invalid_action_fails() {
+    let result = Cmd::try_parse_from([
+        "rack",
+        "power",
+        "ps100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg",
+        "cycle",
+    ]);
+    assert!(result.is_err(), "should fail with unknown action");
+}
@@ -74,4 +74,10 @@ pub struct Apply {
 
     #[clap(help = "Firmware type: dev or prod", value_parser = ["dev", "prod"])]
     pub firmware_type: String,
+
+    #[clap(
+        long,
+        help = "Maximum number of compute trays which are upgraded at the same time"
+    )]
+    pub max_unavailable: Option<u32>,
 }
@@ -252,7 +252,7 @@ pub async fn apply(
     api_client: &ApiClient,
 ) -> Result<(), CarbideCliError> {
     println!(
-        "Applying firmware ID '{}' ({}) to rack '{}'...",
+        "Scheduling firmware ID '{}' ({}) for rack '{}'...",
         opts.firmware_id, opts.firmware_type, opts.rack_id
     );
 
@@ -260,6 +260,7 @@ pub async fn apply(
         rack_id: Some(opts.rack_id),
         firmware_id: opts.firmware_id,
         firmware_type: opts.firmware_type,
+        max_unavailable: opts.max_unavailable,
     };
 
     let response = api_client
@@ -293,9 +294,9 @@ pub async fn apply(
 
         for device_result in &response.device_results {
             let status_text = if device_result.success {
-                "SUCCESS"
+                "SCHEDULED"
             } else {
-                "FAILED"
+                "SKIPPED"
             };
 
             table.add_row(Row::new(vec![
@@ -307,17 +308,18 @@ pub async fn apply(
         }
 
         println!("\n{}", "=".repeat(80));
-        println!("Firmware Update Summary");
+        println!("Firmware Upgrade Plan");
         println!("{}", "=".repeat(80));
         table.printstd();
-        println!("\nTotal updates: {}", response.total_updates);
-        println!("Successful: {}", response.successful_updates);
-        println!("Failed: {}", response.failed_updates);
+        println!("\nTotal devices: {}", response.total_updates);
+        println!("Scheduled: {}", response.successful_updates);
+        println!("Skipped: {}", response.failed_updates);
+        println!("\nThe progress of the upgrade is shown by `rack show`.");
     }
 
     if response.failed_updates > 0 {
         return Err(CarbideCliError::GenericError(format!(
-            "{} firmware updates failed",
+            "{} devices have no matching firmware",
             response.failed_updates
         )));
     }
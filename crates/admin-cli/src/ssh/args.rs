@@ -32,6 +32,10 @@ pub enum Cmd {
     CopyBfb(CopyBfbArgs),
     #[clap(about = "Show the DPU's BMC's OBMC log")]
     ShowObmcLog(SshArgs),
+    #[clap(about = "List sessions recorded by ssh-console")]
+    ListRecordings(ListRecordingsArgs),
+    #[clap(about = "Replay a session recorded by ssh-console")]
+    Replay(ReplayArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -57,3 +61,23 @@ pub struct CopyBfbArgs {
     #[clap(help = "BFB Path")]
     pub bfb_path: String,
 }
+
+#[derive(Parser, Debug, Clone)]
+pub struct ListRecordingsArgs {
+    #[clap(help = "Only list recordings for this machine ID or instance ID")]
+    pub machine: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ReplayArgs {
+    #[clap(help = "Machine ID or instance ID the session logged into")]
+    pub machine: String,
+    #[clap(help = "Recording name, as shown by list-recordings (defaults to the most recent)")]
+    pub recording: Option<String>,
+    #[clap(long, default_value_t = 1.0, help = "Playback speed multiplier")]
+    pub speed: f64,
+    #[clap(long, help = "Limit pauses between events to this many seconds")]
+    pub idle_time_limit: Option<f64>,
+    #[clap(long, help = "Also print the user's input (to stderr)")]
+    pub show_input: bool,
+}
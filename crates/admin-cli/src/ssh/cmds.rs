@@ -15,12 +15,20 @@
  * limitations under the License.
  */
 
-use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult};
+use std::io::Write;
+use std::time::Duration;
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::{GetConsoleRecordingRequest, ListConsoleRecordingsRequest};
+use chrono::{DateTime, Utc};
 use forge_ssh::ssh::{
     copy_bfb_to_bmc_rshim, disable_rshim, enable_rshim, is_rshim_enabled, read_obmc_console_log,
 };
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
 
-use super::args::{CopyBfbArgs, SshArgs};
+use super::args::{CopyBfbArgs, ListRecordingsArgs, ReplayArgs, SshArgs};
+use crate::rpc::ApiClient;
 
 pub async fn get_rshim_status(args: SshArgs) -> CarbideCliResult<()> {
     let is_rshim_enabled = is_rshim_enabled(
@@ -80,3 +88,172 @@ pub async fn show_obmc_log(args: SshArgs) -> CarbideCliResult<()> {
     println!("OBMC Console Log:\n{log}");
     Ok(())
 }
+
+/// A session recording, as listed by the `ListConsoleRecordings` RPC.
+#[derive(Debug, Serialize)]
+struct Recording {
+    machine: String,
+    name: String,
+    size_bytes: u64,
+    modified: String,
+}
+
+impl From<::rpc::forge::ConsoleRecordingInfo> for Recording {
+    fn from(recording: ::rpc::forge::ConsoleRecordingInfo) -> Self {
+        Self {
+            machine: recording.machine,
+            name: recording.name,
+            size_bytes: recording.size_bytes,
+            modified: recording
+                .modified
+                .and_then(|modified| DateTime::<Utc>::try_from(modified).ok())
+                .map(|modified| modified.to_rfc3339())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The asciicast v2 header line, plus the fields ssh-console adds to it.
+#[derive(Debug, Deserialize)]
+struct CastHeader {
+    width: u32,
+    height: u32,
+    timestamp: Option<i64>,
+    title: Option<String>,
+    user: Option<String>,
+    peer_addr: Option<String>,
+}
+
+async fn fetch_recordings(
+    api_client: &ApiClient,
+    machine: Option<&str>,
+) -> CarbideCliResult<Vec<Recording>> {
+    let recordings = api_client
+        .0
+        .list_console_recordings(ListConsoleRecordingsRequest {
+            machine: machine.map(str::to_string),
+        })
+        .await?;
+    Ok(recordings.recordings.into_iter().map(Into::into).collect())
+}
+
+async fn fetch_recording(
+    api_client: &ApiClient,
+    machine: &str,
+    name: &str,
+) -> CarbideCliResult<String> {
+    let mut stream = api_client
+        .0
+        .get_console_recording(GetConsoleRecordingRequest {
+            machine: machine.to_string(),
+            name: name.to_string(),
+        })
+        .await?;
+    let mut cast = Vec::new();
+    while let Some(chunk) = stream.message().await? {
+        cast.extend_from_slice(&chunk.data);
+    }
+    String::from_utf8(cast).map_err(|e| {
+        CarbideCliError::GenericError(format!(
+            "Recording {machine}/{name} is not valid UTF-8: {e}"
+        ))
+    })
+}
+
+pub async fn list_recordings(
+    args: ListRecordingsArgs,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let recordings = fetch_recordings(api_client, args.machine.as_deref()).await?;
+
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&recordings).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![
+        "Machine/Instance",
+        "Recording",
+        "Size (bytes)",
+        "Modified"
+    ]);
+    for recording in recordings {
+        table.add_row(row![
+            recording.machine,
+            recording.name,
+            recording.size_bytes,
+            recording.modified,
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+pub async fn replay(args: ReplayArgs, api_client: &ApiClient) -> CarbideCliResult<()> {
+    if args.speed <= 0.0 {
+        return Err(CarbideCliError::GenericError(
+            "--speed must be greater than zero".to_string(),
+        ));
+    }
+
+    let name = match args.recording {
+        Some(name) => name,
+        None => fetch_recordings(api_client, Some(&args.machine))
+            .await?
+            .pop()
+            .map(|recording| recording.name)
+            .ok_or_else(|| {
+                CarbideCliError::GenericError(format!("No recordings found for {}", args.machine))
+            })?,
+    };
+    let cast = fetch_recording(api_client, &args.machine, &name).await?;
+
+    let mut lines = cast.lines();
+    let header: CastHeader = serde_json::from_str(lines.next().unwrap_or_default())
+        .map_err(CarbideCliError::JsonError)?;
+    let started_at = header
+        .timestamp
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0))
+        .map(|ts| ts.to_rfc3339())
+        .unwrap_or_else(|| "<unknown>".to_string());
+    eprintln!(
+        "--- Replaying {name}: {} as {} from {}, started at {started_at} ({}x{}) ---\r",
+        header.title.as_deref().unwrap_or(&args.machine),
+        header.user.as_deref().unwrap_or("<unknown user>"),
+        header.peer_addr.as_deref().unwrap_or("<unknown address>"),
+        header.width,
+        header.height,
+    );
+
+    let mut stdout = std::io::stdout();
+    let mut last_time = 0.0;
+    for line in lines.filter(|line| !line.is_empty()) {
+        let (time, code, data): (f64, String, String) =
+            serde_json::from_str(line).map_err(CarbideCliError::JsonError)?;
+
+        let mut delay = (time - last_time).max(0.0);
+        if let Some(idle_time_limit) = args.idle_time_limit {
+            delay = delay.min(idle_time_limit);
+        }
+        last_time = time;
+        tokio::time::sleep(Duration::from_secs_f64(delay / args.speed)).await;
+
+        match code.as_str() {
+            "o" => {
+                stdout.write_all(data.as_bytes())?;
+                stdout.flush()?;
+            }
+            "i" if args.show_input => eprint!("{data}"),
+            "m" => eprintln!("\r\n--- {data} ---\r"),
+            _ => {}
+        }
+    }
+
+    eprintln!("\r\n--- End of recording ---\r");
+    Ok(())
+}
@@ -28,13 +28,17 @@ use crate::cfg::dispatch::Dispatch;
 use crate::cfg::runtime::RuntimeContext;
 
 impl Dispatch for Cmd {
-    async fn dispatch(self, _ctx: RuntimeContext) -> CarbideCliResult<()> {
+    async fn dispatch(self, ctx: RuntimeContext) -> CarbideCliResult<()> {
         match self {
             Cmd::GetRshimStatus(args) => cmds::get_rshim_status(args).await,
             Cmd::DisableRshim(args) => cmds::disable_rshim_cmd(args).await,
             Cmd::EnableRshim(args) => cmds::enable_rshim_cmd(args).await,
             Cmd::CopyBfb(args) => cmds::copy_bfb(args).await,
             Cmd::ShowObmcLog(args) => cmds::show_obmc_log(args).await,
+            Cmd::ListRecordings(args) => {
+                cmds::list_recordings(args, ctx.config.format, &ctx.api_client).await
+            }
+            Cmd::Replay(args) => cmds::replay(args, &ctx.api_client).await,
         }
     }
 }
@@ -143,3 +143,80 @@ fn parse_missing_credentials_fails() {
     let result = Cmd::try_parse_from(["ssh", "get-rshim-status", "192.168.1.100:443"]);
     assert!(result.is_err(), "should fail without username and password");
 }
+
+// parse_list_recordings ensures list-recordings parses with
+// and without a machine filter.
+#[test]
+fn parse_list_recordings() {
+    let cmd =
+        Cmd::try_parse_from(["ssh", "list-recordings"]).expect("should parse list-recordings");
+
+    match cmd {
+        Cmd::ListRecordings(args) => {
+            assert!(args.machine.is_none());
+        }
+        _ => panic!("expected ListRecordings variant"),
+    }
+
+    let cmd = Cmd::try_parse_from([
+        "ssh",
+        "list-recordings",
+        "fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg",
+    ])
+    .expect("should parse list-recordings with machine");
+
+    match cmd {
+        Cmd::ListRecordings(args) => {
+            assert_eq!(
+                args.machine.as_deref(),
+                Some("fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg")
+            );
+        }
+        _ => panic!("expected ListRecordings variant"),
+    }
+}
+
+// parse_replay ensures replay parses with defaults and
+// playback options.
+#[test]
+fn parse_replay() {
+    let cmd = Cmd::try_parse_from(["ssh", "replay", "2020eb71-7674-4a15-a05b-c7d73da747b4"])
+        .expect("should parse replay");
+
+    match cmd {
+        Cmd::Replay(args) => {
+            assert_eq!(args.machine, "2020eb71-7674-4a15-a05b-c7d73da747b4");
+            assert!(args.recording.is_none());
+            assert_eq!(args.speed, 1.0);
+            assert!(args.idle_time_limit.is_none());
+            assert!(!args.show_input);
+        }
+        _ => panic!("expected Replay variant"),
+    }
+
+    let cmd = Cmd::try_parse_from([
+        "ssh",
+        "replay",
+        "--speed",
+        "2",
+        "--idle-time-limit",
+        "1.5",
+        "--show-input",
+        "2020eb71-7674-4a15-a05b-c7d73da747b4",
+        "20260101T000000Z_0a9c.cast",
+    ])
+    .expect("should parse replay with options");
+
+    match cmd {
+        Cmd::Replay(args) => {
+            assert_eq!(
+                args.recording.as_deref(),
+                Some("20260101T000000Z_0a9c.cast")
+            );
+            assert_eq!(args.speed, 2.0);
+            assert_eq!(args.idle_time_limit, Some(1.5));
+            assert!(args.show_input);
+        }
+        _ => panic!("expected Replay variant"),
+    }
+}
@@ -22,3 +22,5 @@ pub mod container;
 pub mod command;
 
 pub mod image;
+
+pub mod task;
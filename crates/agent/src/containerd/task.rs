@@ -0,0 +1,320 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Runs containers directly on containerd using the `ctr` CLI.
+//!
+//! Containers started here are not managed by the kubelet and live in their own
+//! containerd namespace, so they never show up in `crictl` output.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use eyre::WrapErr;
+
+use crate::pretty_cmd;
+
+/// containerd namespace which holds all containers started through this module
+pub const CONTAINERD_NAMESPACE: &str = "extservice";
+
+const CONTAINERD_SOCKET_PATH: &str = "/run/containerd/containerd.sock";
+
+/// Pulling an image can take a while for large images on slow links
+const PULL_TIMEOUT: Duration = Duration::from_secs(300);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Status of a containerd task as printed by `ctr tasks ls`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskStatus {
+    Created,
+    Running,
+    Paused,
+    Stopped,
+    Unknown,
+}
+
+impl TaskStatus {
+    fn from_ctr(status: &str) -> Self {
+        match status.to_uppercase().as_str() {
+            "CREATED" => TaskStatus::Created,
+            "RUNNING" => TaskStatus::Running,
+            "PAUSED" | "PAUSING" => TaskStatus::Paused,
+            "STOPPED" => TaskStatus::Stopped,
+            _ => TaskStatus::Unknown,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Created => "CREATED",
+            TaskStatus::Running => "RUNNING",
+            TaskStatus::Paused => "PAUSED",
+            TaskStatus::Stopped => "STOPPED",
+            TaskStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// A task, i.e. the running process of a container
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskSummary {
+    pub container_id: String,
+    pub pid: u32,
+    pub status: TaskStatus,
+}
+
+/// A bind mount from the host into the container
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindMount {
+    pub source: String,
+    pub destination: String,
+    pub read_only: bool,
+}
+
+/// Everything needed to create and start a container
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunSpec {
+    pub container_id: String,
+    pub image: String,
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    pub mounts: Vec<BindMount>,
+    pub host_network: bool,
+    pub privileged: bool,
+}
+
+/// Registry credentials and proxy used for pulling an image
+#[derive(Clone, Debug, Default)]
+pub struct PullOptions {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub proxy: Option<String>,
+}
+
+/// Run `ctr` in the extension service namespace and return its stdout.
+/// Fails if ctr exits with a non-zero status.
+async fn ctr(args: &[&str], envs: &[(&str, &str)], timeout: Duration) -> eyre::Result<String> {
+    let mut cmd = tokio::process::Command::new("ctr");
+    cmd.args([
+        "--address",
+        CONTAINERD_SOCKET_PATH,
+        "-n",
+        CONTAINERD_NAMESPACE,
+    ])
+    .args(args)
+    .envs(envs.iter().copied())
+    .kill_on_drop(true);
+
+    let cmd_str = pretty_cmd(cmd.as_std());
+
+    let output = tokio::time::timeout(timeout, cmd.output())
+        .await
+        .wrap_err_with(|| format!("Timeout while running command: {cmd_str}"))?
+        .wrap_err_with(|| format!("Failed to run command: {cmd_str}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre::eyre!("{cmd_str} failed: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Pull an image into the extension service namespace
+pub async fn pull_image(image: &str, options: &PullOptions) -> eyre::Result<()> {
+    let user = match (&options.username, &options.password) {
+        (Some(username), Some(password)) => Some(format!("{username}:{password}")),
+        _ => None,
+    };
+
+    let mut args = vec!["images", "pull"];
+    if let Some(user) = user.as_deref() {
+        args.extend(["--user", user]);
+    }
+    args.push(image);
+
+    // Images are resolved and fetched by ctr itself rather than by the containerd
+    // daemon, so the proxy needs to be set on the ctr process.
+    let mut envs = Vec::new();
+    if let Some(proxy) = options.proxy.as_deref() {
+        envs.extend([("HTTP_PROXY", proxy), ("HTTPS_PROXY", proxy)]);
+    }
+
+    ctr(&args, &envs, PULL_TIMEOUT)
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            // Don't leak the registry password into logs and status messages
+            match user.as_deref() {
+                Some(user) => eyre::eyre!("{}", e.to_string().replace(user, "<redacted>")),
+                None => e,
+            }
+        })
+}
+
+/// Create a container from an already pulled image and start its task in the background
+pub async fn run_container(spec: &RunSpec) -> eyre::Result<()> {
+    let env: Vec<String> = spec.env.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    let mounts: Vec<String> = spec
+        .mounts
+        .iter()
+        .map(|m| {
+            format!(
+                "type=bind,src={},dst={},options=rbind:{}",
+                m.source,
+                m.destination,
+                if m.read_only { "ro" } else { "rw" }
+            )
+        })
+        .collect();
+
+    let mut args = vec!["run", "-d"];
+    if spec.host_network {
+        args.push("--net-host");
+    }
+    if spec.privileged {
+        args.push("--privileged");
+    }
+    for e in &env {
+        args.extend(["--env", e.as_str()]);
+    }
+    for m in &mounts {
+        args.extend(["--mount", m.as_str()]);
+    }
+    args.push(&spec.image);
+    args.push(&spec.container_id);
+    args.extend(spec.args.iter().map(String::as_str));
+
+    ctr(&args, &[], COMMAND_TIMEOUT).await.map(|_| ())
+}
+
+/// Start the task of an existing container again, e.g. after it exited.
+/// The old task needs to be deleted first since only one task can exist per container.
+pub async fn restart_task(container_id: &str) -> eyre::Result<()> {
+    if let Err(e) = ctr(&["tasks", "delete", container_id], &[], COMMAND_TIMEOUT).await {
+        tracing::debug!("No task to delete for container {}: {}", container_id, e);
+    }
+
+    ctr(
+        &["tasks", "start", "-d", container_id],
+        &[],
+        COMMAND_TIMEOUT,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Kill the task of a container and remove the container along with its snapshot
+pub async fn remove_container(container_id: &str) -> eyre::Result<()> {
+    if let Err(e) = ctr(
+        &["tasks", "delete", "--force", container_id],
+        &[],
+        COMMAND_TIMEOUT,
+    )
+    .await
+    {
+        tracing::debug!("No task to delete for container {}: {}", container_id, e);
+    }
+
+    ctr(
+        &["containers", "delete", container_id],
+        &[],
+        COMMAND_TIMEOUT,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// List the IDs of all containers in the extension service namespace
+pub async fn list_containers() -> eyre::Result<Vec<String>> {
+    let output = ctr(&["containers", "list", "--quiet"], &[], COMMAND_TIMEOUT).await?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// List all tasks in the extension service namespace
+pub async fn list_tasks() -> eyre::Result<Vec<TaskSummary>> {
+    let output = ctr(&["tasks", "list"], &[], COMMAND_TIMEOUT).await?;
+    Ok(parse_tasks(&output))
+}
+
+/// Parse the table printed by `ctr tasks ls`:
+///
+/// ```text
+/// TASK       PID     STATUS
+/// my-task    1234    RUNNING
+/// ```
+fn parse_tasks(output: &str) -> Vec<TaskSummary> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let container_id = fields.next()?.to_string();
+            let pid = fields.next()?.parse().ok()?;
+            let status = TaskStatus::from_ctr(fields.next()?);
+            Some(TaskSummary {
+                container_id,
+                pid,
+                status,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tasks() {
+        let output = "TASK                                                   PID      STATUS    \n\
+            extservice_123e4567-e89b-12d3-a456-426614174000_1     41234    RUNNING    \n\
+            extservice_123e4567-e89b-12d3-a456-426614174000_2     0        STOPPED    \n\
+            extservice_00000000-0000-0000-0000-000000000000_3     41300    PAUSING    \n";
+
+        let tasks = parse_tasks(output);
+        assert_eq!(
+            tasks,
+            vec![
+                TaskSummary {
+                    container_id: "extservice_123e4567-e89b-12d3-a456-426614174000_1".to_string(),
+                    pid: 41234,
+                    status: TaskStatus::Running,
+                },
+                TaskSummary {
+                    container_id: "extservice_123e4567-e89b-12d3-a456-426614174000_2".to_string(),
+                    pid: 0,
+                    status: TaskStatus::Stopped,
+                },
+                TaskSummary {
+                    container_id: "extservice_00000000-0000-0000-0000-000000000000_3".to_string(),
+                    pid: 41300,
+                    status: TaskStatus::Paused,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tasks_empty() {
+        assert!(parse_tasks("TASK    PID    STATUS    \n").is_empty());
+        assert!(parse_tasks("").is_empty());
+    }
+}
@@ -2435,6 +2435,7 @@ mod tests {
             asn: 4259912557,
             datacenter_asn: 11414,
             site_global_vpc_vni,
+            instance_public_keys: vec![],
             anycast_site_prefixes: vec!["5.255.255.0/24".to_string()],
             tenant_host_asn: Some(65100),
             common_internal_route_target: Some(rpc_common::RouteTarget {
@@ -2929,6 +2930,7 @@ mod tests {
 
         let mut network_config = rpc::ManagedHostNetworkConfigResponse {
             site_global_vpc_vni: None,
+            instance_public_keys: vec![],
             asn: 4259912557,
             datacenter_asn: 11414,
             common_internal_route_target: Some(rpc_common::RouteTarget {
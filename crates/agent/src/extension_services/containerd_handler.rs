@@ -0,0 +1,576 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use ::rpc::forge as rpc;
+use async_trait::async_trait;
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+
+use super::service_handler::{CredentialType, ExtensionServiceHandler, ServiceConfig};
+use crate::containerd::task::{self, BindMount, PullOptions, RunSpec, TaskStatus};
+use crate::extension_services::dpu_extension_service_observability;
+
+// For identifying the container in ctr
+const CONTAINER_ID_PREFIX: &str = "extservice";
+
+// Images are pulled through the same SOCKS proxy which is configured for containerd@mgmt
+const IMAGE_PULL_PROXY: &str = "socks5://socks.forge:1888";
+
+/// The container spec of a CONTAINERD_CONTAINER extension service
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ContainerSpec {
+    pub image: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub mounts: Vec<ContainerMount>,
+    #[serde(default = "default_host_network")]
+    pub host_network: bool,
+    #[serde(default)]
+    pub privileged: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ContainerMount {
+    pub source: String,
+    pub destination: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+fn default_host_network() -> bool {
+    true
+}
+
+/// Handler for CONTAINERD_CONTAINER extension services
+#[derive(Default)]
+pub struct ContainerdServicesHandler {
+    /// Map of (service_id, version) to error message if deployment/teardown hit issues
+    pub service_errors: HashMap<(String, u64), String>,
+
+    /// Map of (service_id, version) to how often the container got restarted after its task exited
+    pub restart_counts: HashMap<(String, u64), u32>,
+}
+
+impl ContainerdServicesHandler {
+    /// Generate the container ID for a service
+    fn get_container_id(service_id: &str, service_version: u64) -> String {
+        format!("{CONTAINER_ID_PREFIX}_{service_id}_{service_version}")
+    }
+
+    /// Parse the service ID and version from the container ID
+    fn get_service_id_and_version_from_container_id(
+        container_id: &str,
+    ) -> Option<(uuid::Uuid, u64)> {
+        let rest = container_id.strip_prefix(&format!("{CONTAINER_ID_PREFIX}_"))?;
+        let (id_str, ver_str) = rest.split_once('_')?;
+        let version = ver_str.parse::<u64>().ok()?;
+        let service_id = uuid::Uuid::parse_str(id_str).ok()?;
+
+        Some((service_id, version))
+    }
+
+    /// Split an image reference into the image URL and its tag or digest,
+    /// e.g. "nvcr.io/nvidia/doca/doca_hbn:2.3.0" -> ("nvcr.io/nvidia/doca/doca_hbn", "2.3.0")
+    fn split_image_reference(image: &str) -> (String, String) {
+        if let Some((url, digest)) = image.split_once('@') {
+            return (url.to_string(), digest.to_string());
+        }
+
+        // A colon after the last slash separates the tag, any other colon belongs to the
+        // registry port
+        let name_start = image.rfind('/').map(|pos| pos + 1).unwrap_or(0);
+        match image[name_start..].rfind(':') {
+            Some(pos) => (
+                image[..name_start + pos].to_string(),
+                image[name_start + pos + 1..].to_string(),
+            ),
+            None => (image.to_string(), "latest".to_string()),
+        }
+    }
+
+    /// Compute the deployment status of a service from the state of its container.
+    ///
+    /// Rules:
+    /// - When expected to be deployed:
+    ///     - no container or no task yet -> PENDING
+    ///     - task RUNNING -> RUNNING
+    ///     - task CREATED or PAUSED -> PENDING
+    ///     - task STOPPED -> ERROR (the task gets restarted with the next update)
+    ///     - otherwise -> UNKNOWN
+    /// - When NOT expected to be deployed (we expect it to be gone):
+    ///     - no container -> TERMINATED
+    ///     - container still present -> TERMINATING
+    fn aggregate_status(
+        container_exists: bool,
+        task_status: Option<TaskStatus>,
+        expected_deploy: bool,
+    ) -> rpc::DpuExtensionServiceDeploymentStatus {
+        if !expected_deploy {
+            return if container_exists {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceTerminating
+            } else {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceTerminated
+            };
+        }
+
+        match (container_exists, task_status) {
+            (false, _) | (true, None) => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServicePending
+            }
+            (true, Some(TaskStatus::Running)) => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceRunning
+            }
+            (true, Some(TaskStatus::Created | TaskStatus::Paused)) => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServicePending
+            }
+            (true, Some(TaskStatus::Stopped)) => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError
+            }
+            (true, Some(TaskStatus::Unknown)) => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceUnknown
+            }
+        }
+    }
+
+    /// Build the options for pulling the image of a service, using the service credential
+    /// if its registry URL is a prefix of the image
+    fn pull_options(service: &ServiceConfig, image: &str) -> PullOptions {
+        let mut options = PullOptions {
+            proxy: Some(IMAGE_PULL_PROXY.to_string()),
+            ..Default::default()
+        };
+
+        if let Some(credential) = &service.credential {
+            let prefix = credential.registry_url.trim_end_matches('/');
+            let prefix = prefix
+                .strip_prefix("https://")
+                .or_else(|| prefix.strip_prefix("http://"))
+                .unwrap_or(prefix);
+            if image.starts_with(prefix) {
+                match &credential.credential_type {
+                    CredentialType::UsernamePassword(up) => {
+                        options.username = Some(up.username.clone());
+                        options.password = Some(up.password.clone());
+                    }
+                }
+            }
+        }
+
+        options
+    }
+
+    /// Pull the image of a service and start its container
+    async fn deploy_service(&self, service: &ServiceConfig) -> Result<()> {
+        let spec = serde_yaml::from_str::<ContainerSpec>(&service.data)
+            .wrap_err("Invalid container spec")?;
+
+        task::pull_image(&spec.image, &Self::pull_options(service, &spec.image))
+            .await
+            .wrap_err_with(|| format!("Failed to pull image {}", spec.image))?;
+
+        let run_spec = RunSpec {
+            container_id: Self::get_container_id(
+                &service.id.to_string(),
+                service.version.version_nr(),
+            ),
+            image: spec.image,
+            args: spec.args,
+            env: spec.env,
+            mounts: spec
+                .mounts
+                .into_iter()
+                .map(|m| BindMount {
+                    source: m.source,
+                    destination: m.destination,
+                    read_only: m.read_only,
+                })
+                .collect(),
+            host_network: spec.host_network,
+            privileged: spec.privileged,
+        };
+
+        task::run_container(&run_spec)
+            .await
+            .wrap_err("Failed to start container")?;
+
+        tracing::debug!(
+            "Container {} for service {} V{} started",
+            run_spec.container_id,
+            service.id,
+            service.version
+        );
+
+        Ok(())
+    }
+
+    /// Reconcile the containers in the extension service namespace with the active services.
+    ///
+    /// - Containers of new services are created and started.
+    /// - Containers whose task exited are started again and their restart count is increased.
+    /// - Containers of services which are no longer active are removed.
+    async fn reconcile_containers(&mut self, new_active: &[ServiceConfig]) -> Result<()> {
+        let mut current_active: HashSet<(uuid::Uuid, u64)> = HashSet::new();
+        for container_id in task::list_containers().await? {
+            if let Some(id) = Self::get_service_id_and_version_from_container_id(&container_id) {
+                current_active.insert(id);
+            }
+        }
+
+        let tasks: HashMap<String, TaskStatus> = task::list_tasks()
+            .await?
+            .into_iter()
+            .map(|t| (t.container_id, t.status))
+            .collect();
+
+        for service in new_active {
+            let key = (service.id.to_string(), service.version.version_nr());
+            let container_id = Self::get_container_id(&key.0, key.1);
+
+            if !current_active.contains(&(service.id, service.version.version_nr())) {
+                // Found new service, pull the image and start the container
+                if let Err(e) = self.deploy_service(service).await {
+                    self.service_errors.insert(key, format!("{e:#}"));
+                }
+                continue;
+            }
+
+            match tasks.get(&container_id) {
+                Some(TaskStatus::Stopped) | None => {
+                    tracing::info!(
+                        "Task of container {} is not running, restarting it",
+                        container_id
+                    );
+                    match task::restart_task(&container_id).await {
+                        Ok(()) => *self.restart_counts.entry(key).or_default() += 1,
+                        Err(e) => {
+                            self.service_errors.insert(key, format!("{e:#}"));
+                        }
+                    }
+                }
+                Some(_) => (),
+            }
+        }
+
+        for (service_id, version) in current_active {
+            if !new_active
+                .iter()
+                .any(|s| s.id == service_id && s.version.version_nr() == version)
+            {
+                // Service is not active, remove its container
+                let container_id = Self::get_container_id(&service_id.to_string(), version);
+                match task::remove_container(&container_id).await {
+                    Ok(()) => {
+                        self.restart_counts
+                            .remove(&(service_id.to_string(), version));
+                    }
+                    Err(e) => {
+                        self.service_errors
+                            .insert((service_id.to_string(), version), format!("{e:#}"));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the containers in the extension service namespace and the metrics collection config
+    async fn update_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        // Clear any previous errors since we are starting a new update
+        self.service_errors.clear();
+
+        let active_services: Vec<ServiceConfig> = services
+            .iter()
+            .filter(|s| s.removed.is_none())
+            .cloned()
+            .collect();
+
+        self.reconcile_containers(&active_services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile containers: {}", e))?;
+
+        // Reconcile metrics collection config
+        dpu_extension_service_observability::reconcile(services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile metrics collection: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Determine the status of the container of a service from ctr
+    async fn get_container_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        let expected_deploy = service.removed.is_none();
+        let key = (service.id.to_string(), service.version.version_nr());
+        let container_id = Self::get_container_id(&key.0, key.1);
+
+        let container_exists = task::list_containers().await?.contains(&container_id);
+        let task_status = task::list_tasks()
+            .await?
+            .into_iter()
+            .find(|t| t.container_id == container_id)
+            .map(|t| t.status);
+
+        let state_enum = Self::aggregate_status(container_exists, task_status, expected_deploy);
+
+        let mut components = Vec::new();
+        if container_exists && let Ok(spec) = serde_yaml::from_str::<ContainerSpec>(&service.data) {
+            let (url, version) = Self::split_image_reference(&spec.image);
+            components.push(rpc::DpuExtensionServiceComponent {
+                name: container_id.clone(),
+                version,
+                url,
+                status: task_status
+                    .map(|s| s.as_str())
+                    .unwrap_or("CREATED")
+                    .to_string(),
+                restart_count: Some(self.restart_counts.get(&key).copied().unwrap_or(0)),
+            });
+        }
+
+        let message = match self.service_errors.get(&key) {
+            Some(e) => e.to_string(),
+            None if !container_exists => "No container found".to_string(),
+            None => format!(
+                "task state: {}",
+                task_status.map(|s| s.as_str()).unwrap_or("NO_TASK")
+            ),
+        };
+
+        Ok(rpc::DpuExtensionServiceStatusObservation {
+            service_id: service.id.to_string(),
+            service_type: service.service_type as i32,
+            service_name: service.id.to_string(),
+            version: service.version.to_string(),
+            removed: service.removed.clone(),
+            state: state_enum as i32,
+            components,
+            message,
+        })
+    }
+}
+
+#[async_trait]
+impl ExtensionServiceHandler for ContainerdServicesHandler {
+    /// Reconciles the containers in the extension service containerd namespace with the desired
+    /// services
+    async fn update_active_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        if let Err(e) = self.update_services(services).await {
+            tracing::error!("Failed to update active services: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn get_service_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        let res = self.get_container_status(service).await;
+        match res {
+            Ok(status) => Ok(status),
+            Err(e) => Ok(rpc::DpuExtensionServiceStatusObservation {
+                service_id: service.id.to_string(),
+                service_type: service.service_type as i32,
+                service_name: service.id.to_string(),
+                version: service.version.to_string(),
+                removed: service.removed.clone(),
+                state: rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError as i32,
+                components: Vec::new(),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extension_services::service_handler::{ServiceCredential, UsernamePassword};
+
+    fn service_with_credential(registry_url: &str) -> ServiceConfig {
+        ServiceConfig {
+            id: uuid::Uuid::nil(),
+            name: "test".to_string(),
+            service_type: rpc::DpuExtensionServiceType::ContainerdContainer,
+            version: config_version::ConfigVersion::initial(),
+            removed: None,
+            data: "image: nvcr.io/nvidia/doca/doca_hbn:2.3.0".to_string(),
+            credential: Some(ServiceCredential {
+                registry_url: registry_url.to_string(),
+                credential_type: CredentialType::UsernamePassword(UsernamePassword {
+                    username: "user".to_string(),
+                    password: "pass".to_string(),
+                }),
+            }),
+            observability: None,
+        }
+    }
+
+    #[test]
+    fn test_containerd_handler_container_id() {
+        let service_id = "123e4567-e89b-12d3-a456-426614174000";
+        let container_id = ContainerdServicesHandler::get_container_id(service_id, 3);
+        assert_eq!(
+            container_id,
+            "extservice_123e4567-e89b-12d3-a456-426614174000_3"
+        );
+
+        assert_eq!(
+            ContainerdServicesHandler::get_service_id_and_version_from_container_id(&container_id),
+            Some((uuid::Uuid::parse_str(service_id).unwrap(), 3))
+        );
+
+        for invalid in [
+            "doca-hbn",
+            "extservice_not-a-uuid_1",
+            "extservice_123e4567-e89b-12d3-a456-426614174000",
+            "extservice_123e4567-e89b-12d3-a456-426614174000_x",
+        ] {
+            assert_eq!(
+                ContainerdServicesHandler::get_service_id_and_version_from_container_id(invalid),
+                None,
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_containerd_handler_parse_container_spec() {
+        let spec = serde_yaml::from_str::<ContainerSpec>(
+            "image: nvcr.io/nvidia/doca/doca_hbn:2.3.0\n\
+             args: [\"--verbose\"]\n\
+             env:\n  LOG_LEVEL: info\n\
+             mounts:\n  - source: /var/log\n    destination: /logs\n    readOnly: true\n",
+        )
+        .unwrap();
+        assert_eq!(spec.image, "nvcr.io/nvidia/doca/doca_hbn:2.3.0");
+        assert_eq!(spec.args, vec!["--verbose".to_string()]);
+        assert_eq!(spec.env.get("LOG_LEVEL").map(String::as_str), Some("info"));
+        assert!(spec.mounts[0].read_only);
+        assert!(spec.host_network);
+        assert!(!spec.privileged);
+
+        // Typos must not be silently ignored
+        assert!(serde_yaml::from_str::<ContainerSpec>("image: nginx\nprivilged: true").is_err());
+        assert!(serde_yaml::from_str::<ContainerSpec>("args: [a]").is_err());
+    }
+
+    #[test]
+    fn test_containerd_handler_split_image_reference() {
+        assert_eq!(
+            ContainerdServicesHandler::split_image_reference("nvcr.io/nvidia/doca/doca_hbn:2.3.0"),
+            (
+                "nvcr.io/nvidia/doca/doca_hbn".to_string(),
+                "2.3.0".to_string()
+            )
+        );
+        assert_eq!(
+            ContainerdServicesHandler::split_image_reference("registry:5000/app"),
+            ("registry:5000/app".to_string(), "latest".to_string())
+        );
+        assert_eq!(
+            ContainerdServicesHandler::split_image_reference("registry:5000/app:1.0"),
+            ("registry:5000/app".to_string(), "1.0".to_string())
+        );
+        assert_eq!(
+            ContainerdServicesHandler::split_image_reference("nginx@sha256:abcd"),
+            ("nginx".to_string(), "sha256:abcd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_containerd_handler_aggregate_status() {
+        use rpc::DpuExtensionServiceDeploymentStatus as Status;
+
+        let cases = [
+            (false, None, true, Status::DpuExtensionServicePending),
+            (true, None, true, Status::DpuExtensionServicePending),
+            (
+                true,
+                Some(TaskStatus::Running),
+                true,
+                Status::DpuExtensionServiceRunning,
+            ),
+            (
+                true,
+                Some(TaskStatus::Created),
+                true,
+                Status::DpuExtensionServicePending,
+            ),
+            (
+                true,
+                Some(TaskStatus::Stopped),
+                true,
+                Status::DpuExtensionServiceError,
+            ),
+            (
+                true,
+                Some(TaskStatus::Unknown),
+                true,
+                Status::DpuExtensionServiceUnknown,
+            ),
+            (
+                true,
+                Some(TaskStatus::Running),
+                false,
+                Status::DpuExtensionServiceTerminating,
+            ),
+            (false, None, false, Status::DpuExtensionServiceTerminated),
+        ];
+
+        for (container_exists, task_status, expected_deploy, expected) in cases {
+            assert_eq!(
+                ContainerdServicesHandler::aggregate_status(
+                    container_exists,
+                    task_status,
+                    expected_deploy
+                ),
+                expected,
+                "container_exists={container_exists} task_status={task_status:?} expected_deploy={expected_deploy}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_containerd_handler_pull_options() {
+        let image = "nvcr.io/nvidia/doca/doca_hbn:2.3.0";
+
+        let options = ContainerdServicesHandler::pull_options(
+            &service_with_credential("https://nvcr.io/nvidia/"),
+            image,
+        );
+        assert_eq!(options.username.as_deref(), Some("user"));
+        assert_eq!(options.password.as_deref(), Some("pass"));
+        assert_eq!(options.proxy.as_deref(), Some(IMAGE_PULL_PROXY));
+
+        // Credentials are only passed to the registry they were configured for
+        let options = ContainerdServicesHandler::pull_options(
+            &service_with_credential("registry.test.com"),
+            image,
+        );
+        assert_eq!(options.username, None);
+        assert_eq!(options.password, None);
+    }
+}
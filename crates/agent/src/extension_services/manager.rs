@@ -18,8 +18,10 @@ use std::collections::HashMap;
 
 use ::rpc::forge::{self as rpc, DpuExtensionServiceType};
 
+use super::containerd_handler::ContainerdServicesHandler;
 use super::k8s_pod_handler::KubernetesPodServicesHandler;
 use super::service_handler::{ExtensionServiceHandler, ServiceConfig};
+use super::systemd_unit_handler::SystemdUnitServicesHandler;
 
 /// Manager for all extension services on the DPU
 ///
@@ -35,6 +37,14 @@ impl Default for ExtensionServiceManager {
             DpuExtensionServiceType::KubernetesPod,
             Box::new(KubernetesPodServicesHandler::default()) as Box<dyn ExtensionServiceHandler>,
         );
+        service_handlers.insert(
+            DpuExtensionServiceType::ContainerdContainer,
+            Box::new(ContainerdServicesHandler::default()) as Box<dyn ExtensionServiceHandler>,
+        );
+        service_handlers.insert(
+            DpuExtensionServiceType::SystemdUnit,
+            Box::new(SystemdUnitServicesHandler::default()) as Box<dyn ExtensionServiceHandler>,
+        );
 
         Self { service_handlers }
     }
@@ -59,12 +69,21 @@ impl ExtensionServiceManager {
             HashMap::new();
 
         for config in configs {
-            let service = ServiceConfig::try_from(config).map_err(|e| {
-                eyre::eyre!(
-                    "Failed to convert ManagedHostDpuExtensionServiceConfig to ServiceConfig: {}",
-                    e
-                )
-            })?;
+            // A service which can't be converted, e.g. because of a service type which is
+            // unknown to this agent, must not prevent the other services from being deployed.
+            // Its error is reported by get_service_statuses.
+            let service_id = config.service_id.clone();
+            let service = match ServiceConfig::try_from(config) {
+                Ok(service) => service,
+                Err(e) => {
+                    tracing::error!(
+                        service_id = %service_id,
+                        error = %e,
+                        "Failed to convert ManagedHostDpuExtensionServiceConfig to ServiceConfig, skipping service"
+                    );
+                    continue;
+                }
+            };
             services_by_type
                 .entry(service.service_type)
                 .or_default()
@@ -85,18 +104,26 @@ impl ExtensionServiceManager {
         &mut self,
         configs: Vec<rpc::ManagedHostDpuExtensionServiceConfig>,
     ) -> eyre::Result<Vec<rpc::DpuExtensionServiceStatusObservation>> {
-        let service_configs: Vec<ServiceConfig> = configs
-            .into_iter()
-            .map(|c| {
-                ServiceConfig::try_from(c).map_err(|e| eyre::eyre!(
-                "Failed to convert ManagedHostDpuExtensionServiceConfig to ServiceConfig: {e}"
-            ))
-            })
-            .collect::<Result<_, _>>()?;
-
-        let mut service_statuses = Vec::with_capacity(service_configs.len());
-        for service in service_configs {
-            let handler = self.get_handler_mut(&service.service_type).unwrap();
+        let mut service_statuses = Vec::with_capacity(configs.len());
+        for config in configs {
+            let service = match ServiceConfig::try_from(config.clone()) {
+                Ok(service) => service,
+                Err(e) => {
+                    service_statuses.push(rpc::DpuExtensionServiceStatusObservation {
+                        service_id: config.service_id.clone(),
+                        service_type: config.service_type,
+                        service_name: config.service_id,
+                        version: config.version,
+                        removed: config.removed,
+                        state: rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError
+                            as i32,
+                        components: Vec::new(),
+                        message: format!("Unsupported extension service config: {e}"),
+                    });
+                    continue;
+                }
+            };
+            let handler = self.get_handler_mut(&service.service_type)?;
             let status = handler.get_service_status(&service).await?;
             service_statuses.push(status);
         }
@@ -14,9 +14,12 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+pub mod containerd_handler;
 pub mod dpu_extension_service_observability;
 pub mod k8s_pod_handler;
 pub mod manager;
 pub mod service_handler;
+pub mod systemd;
+pub mod systemd_unit_handler;
 
 pub use manager::ExtensionServiceManager;
@@ -47,7 +47,7 @@ pub struct ServiceConfig {
     pub service_type: rpc::DpuExtensionServiceType,
     pub version: ConfigVersion,
     pub removed: Option<String>,
-    pub data: String, // Service specification (e.g., pod spec YAML for pods)
+    pub data: String, // Service specification (e.g., pod spec YAML for pods, unit file for systemd units)
     pub credential: Option<ServiceCredential>,
     pub observability: Option<DpuExtensionServiceObservability>,
 }
@@ -72,10 +72,12 @@ impl TryFrom<rpc::ManagedHostDpuExtensionServiceConfig> for ServiceConfig {
         Ok(Self {
             id: uuid::Uuid::parse_str(&config.service_id).unwrap(),
             name: config.name,
-            service_type: config
-                .service_type
-                .try_into()
-                .unwrap_or(rpc::DpuExtensionServiceType::KubernetesPod),
+            service_type: config.service_type.try_into().map_err(|_| {
+                RpcDataConversionError::InvalidValue(
+                    "DpuExtensionServiceType".to_string(),
+                    config.service_type.to_string(),
+                )
+            })?,
             version: config.version.parse().map_err(|e| {
                 RpcDataConversionError::InvalidConfigVersion(format!(
                     "Failed to parse version as ConfigVersion: {}",
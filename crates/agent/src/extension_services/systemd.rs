@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Helpers for managing systemd units from the extension service handlers
+
+use eyre::{Result, WrapErr};
+use tokio::process::Command as TokioCommand;
+
+/// Run `systemctl` with the given arguments and return its stdout.
+/// Fails if systemctl exits with a non-zero status.
+pub async fn systemctl(args: &[&str]) -> Result<String> {
+    let output = TokioCommand::new("systemctl")
+        .args(args)
+        .output()
+        .await
+        .wrap_err_with(|| format!("Failed to run systemctl {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre::eyre!(
+            "systemctl {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reload the systemd manager configuration to pick up changed unit files.
+/// Failures are only logged, since the following systemctl commands will report
+/// any unit which could not be loaded.
+pub async fn daemon_reload() {
+    if let Err(e) = systemctl(&["daemon-reload"]).await {
+        tracing::warn!("systemctl daemon-reload failed: {}", e);
+    }
+}
+
+/// Restart a systemd service and apply changes to the service configuration.
+pub async fn restart(service: &str) -> Result<()> {
+    tracing::debug!(
+        "systemctl daemon-reload and restart {} to apply changes",
+        service
+    );
+
+    daemon_reload().await;
+
+    systemctl(&["restart", service])
+        .await
+        .wrap_err_with(|| format!("Failed to restart {}", service))?;
+
+    tracing::debug!("Successfully restarted {}", service);
+
+    Ok(())
+}
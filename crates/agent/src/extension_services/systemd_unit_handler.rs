@@ -0,0 +1,535 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use ::rpc::forge as rpc;
+use async_trait::async_trait;
+use eyre::{Result, WrapErr};
+
+use super::service_handler::{ExtensionServiceHandler, ServiceConfig};
+use crate::extension_services::{dpu_extension_service_observability, systemd};
+
+// For writing the unit files to the systemd system unit directory
+const SYSTEMD_UNIT_DIR: &str = "/etc/systemd/system";
+const SYSTEMD_UNIT_PREFIX: &str = "extservice";
+const SYSTEMD_UNIT_SUFFIX: &str = ".service";
+
+// Appended to unit files without an [Install] section, so that `systemctl enable` works and the
+// unit comes back after a DPU reboot
+const SYSTEMD_INSTALL_SECTION: &str = "\n[Install]\nWantedBy=multi-user.target\n";
+
+/// The state of a unit as reported by `systemctl show`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitState {
+    pub load_state: String,
+    pub active_state: String,
+    pub sub_state: String,
+    pub restarts: Option<u32>,
+}
+
+/// Handler for SYSTEMD_UNIT extension services
+#[derive(Default)]
+pub struct SystemdUnitServicesHandler {
+    /// Map of (service_id, version) to error message if deployment/teardown hit issues
+    pub service_errors: HashMap<(String, u64), String>,
+}
+
+impl SystemdUnitServicesHandler {
+    /// Generate the unit name for a service
+    fn get_unit_name(service_id: &str, service_version: u64) -> String {
+        format!("{SYSTEMD_UNIT_PREFIX}-{service_id}-{service_version}{SYSTEMD_UNIT_SUFFIX}")
+    }
+
+    /// Generate the unit file path for a service
+    fn get_unit_path(service_id: &str, service_version: u64) -> PathBuf {
+        PathBuf::from(SYSTEMD_UNIT_DIR).join(Self::get_unit_name(service_id, service_version))
+    }
+
+    /// Parse the service ID and version from the unit file name
+    fn get_service_id_and_version_from_filename(filename: &str) -> Option<(uuid::Uuid, u64)> {
+        let rest = filename
+            .strip_prefix(&format!("{SYSTEMD_UNIT_PREFIX}-"))?
+            .strip_suffix(SYSTEMD_UNIT_SUFFIX)?;
+
+        // The service ID is a UUID which contains dashes itself
+        let (id_str, ver_str) = rest.rsplit_once('-')?;
+        let version = ver_str.parse::<u64>().ok()?;
+        let service_id = uuid::Uuid::parse_str(id_str).ok()?;
+
+        Some((service_id, version))
+    }
+
+    /// Add an [Install] section to the unit file if the tenant did not provide one
+    fn with_install_section(data: &str) -> String {
+        let has_install_section = data.lines().any(|line| line.trim() == "[Install]");
+        if has_install_section {
+            return data.to_string();
+        }
+
+        let mut unit = data.trim_end().to_string();
+        unit.push('\n');
+        unit.push_str(SYSTEMD_INSTALL_SECTION);
+        unit
+    }
+
+    /// Parse the output of `systemctl show --property=LoadState,ActiveState,SubState,NRestarts`
+    fn parse_unit_state(output: &str) -> UnitState {
+        let mut state = UnitState::default();
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "LoadState" => state.load_state = value.to_string(),
+                "ActiveState" => state.active_state = value.to_string(),
+                "SubState" => state.sub_state = value.to_string(),
+                "NRestarts" => state.restarts = value.parse().ok(),
+                _ => (),
+            }
+        }
+        state
+    }
+
+    /// Compute the deployment status of a service from the state of its unit.
+    ///
+    /// Rules:
+    /// - When expected to be deployed:
+    ///     - unit not loaded yet -> PENDING
+    ///     - active -> RUNNING
+    ///     - activating or reloading -> PENDING
+    ///     - failed, inactive or deactivating -> ERROR
+    ///     - otherwise -> UNKNOWN
+    /// - When NOT expected to be deployed (we expect it to be gone):
+    ///     - unit not loaded, inactive or failed -> TERMINATED
+    ///     - otherwise -> TERMINATING
+    fn aggregate_status(
+        state: &UnitState,
+        expected_deploy: bool,
+    ) -> rpc::DpuExtensionServiceDeploymentStatus {
+        let loaded = state.load_state == "loaded";
+
+        if !expected_deploy {
+            return match (loaded, state.active_state.as_str()) {
+                (false, _) | (true, "inactive" | "failed") => {
+                    rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceTerminated
+                }
+                _ => rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceTerminating,
+            };
+        }
+
+        if !loaded {
+            return rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServicePending;
+        }
+
+        match state.active_state.as_str() {
+            "active" => rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceRunning,
+            "activating" | "reloading" => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServicePending
+            }
+            "failed" | "inactive" | "deactivating" => {
+                rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError
+            }
+            _ => rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceUnknown,
+        }
+    }
+
+    /// Atomic write the unit file to the systemd unit directory
+    async fn write_unit_file(&self, service: &ServiceConfig) -> Result<()> {
+        let unit_path = Self::get_unit_path(&service.id.to_string(), service.version.version_nr());
+        let tmp_path = unit_path.with_extension("tmp");
+
+        tokio::fs::write(&tmp_path, Self::with_install_section(&service.data))
+            .await
+            .wrap_err_with(|| format!("Failed to write {}", tmp_path.display()))?;
+
+        tokio::fs::rename(&tmp_path, &unit_path)
+            .await
+            .wrap_err_with(|| {
+                format!(
+                    "Failed to rename {} to {}",
+                    tmp_path.display(),
+                    unit_path.display()
+                )
+            })?;
+
+        tracing::debug!(
+            "Unit file for service {} V{} written successfully at {}",
+            service.id,
+            service.version,
+            unit_path.display()
+        );
+
+        Ok(())
+    }
+
+    /// Stop and disable the unit of a service, then remove its unit file
+    async fn remove_unit(&self, service_id: &str, service_version: u64) -> Result<()> {
+        let unit_name = Self::get_unit_name(service_id, service_version);
+        if let Err(e) = systemd::systemctl(&["disable", "--now", &unit_name]).await {
+            tracing::warn!("Failed to stop {}: {}", unit_name, e);
+        }
+
+        let unit_path = Self::get_unit_path(service_id, service_version);
+        match tokio::fs::remove_file(&unit_path).await {
+            Ok(()) => {
+                tracing::debug!(
+                    "Unit file for service {} V{} removed successfully at {}",
+                    service_id,
+                    service_version,
+                    unit_path.display()
+                );
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => (),
+            Err(e) => {
+                return Err(e).wrap_err_with(|| {
+                    format!("Failed to remove unit file {}", unit_path.display())
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List the services which currently have a unit file in the systemd unit directory
+    fn list_unit_files(&self) -> Result<HashSet<(uuid::Uuid, u64)>> {
+        let unit_dir = Path::new(SYSTEMD_UNIT_DIR);
+        let dir_iter = std::fs::read_dir(unit_dir)
+            .wrap_err_with(|| format!("Failed to read unit directory {}", unit_dir.display()))?;
+
+        let mut current_active = HashSet::new();
+        for entry in dir_iter {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to read unit directory entry");
+                    continue;
+                }
+            };
+
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(id) = Self::get_service_id_and_version_from_filename(&file_name) {
+                current_active.insert(id);
+            }
+        }
+
+        Ok(current_active)
+    }
+
+    /// Reconcile the unit files in the systemd unit directory with the active services.
+    ///
+    /// New units are written and started, units of services which are no longer active are
+    /// stopped and removed. Restarting units which exited is left to the Restart= setting of
+    /// the unit.
+    async fn reconcile_units(&mut self, new_active: &[ServiceConfig]) -> Result<()> {
+        let current_active = self.list_unit_files()?;
+
+        let new_services: Vec<&ServiceConfig> = new_active
+            .iter()
+            .filter(|s| !current_active.contains(&(s.id, s.version.version_nr())))
+            .collect();
+        let removed_services: Vec<(uuid::Uuid, u64)> = current_active
+            .into_iter()
+            .filter(|(service_id, version)| {
+                !new_active
+                    .iter()
+                    .any(|s| s.id == *service_id && s.version.version_nr() == *version)
+            })
+            .collect();
+
+        for (service_id, version) in &removed_services {
+            if let Err(e) = self.remove_unit(&service_id.to_string(), *version).await {
+                self.service_errors
+                    .insert((service_id.to_string(), *version), format!("{e:#}"));
+            }
+        }
+
+        let mut written = Vec::with_capacity(new_services.len());
+        for service in new_services {
+            match self.write_unit_file(service).await {
+                Ok(()) => written.push(service),
+                Err(e) => {
+                    self.service_errors.insert(
+                        (service.id.to_string(), service.version.version_nr()),
+                        format!("{e:#}"),
+                    );
+                }
+            }
+        }
+
+        if written.is_empty() && removed_services.is_empty() {
+            return Ok(());
+        }
+
+        systemd::daemon_reload().await;
+
+        for service in written {
+            let unit_name =
+                Self::get_unit_name(&service.id.to_string(), service.version.version_nr());
+            if let Err(e) = systemd::systemctl(&["enable", "--now", &unit_name]).await {
+                self.service_errors.insert(
+                    (service.id.to_string(), service.version.version_nr()),
+                    format!("{e:#}"),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the units in the systemd unit directory and the metrics collection config
+    async fn update_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        // Clear any previous errors since we are starting a new update
+        self.service_errors.clear();
+
+        let active_services: Vec<ServiceConfig> = services
+            .iter()
+            .filter(|s| s.removed.is_none())
+            .cloned()
+            .collect();
+
+        self.reconcile_units(&active_services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile units: {}", e))?;
+
+        // Reconcile metrics collection config
+        dpu_extension_service_observability::reconcile(services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile metrics collection: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Determine the status of the unit of a service from systemctl
+    async fn get_unit_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        let expected_deploy = service.removed.is_none();
+        let key = (service.id.to_string(), service.version.version_nr());
+        let unit_name = Self::get_unit_name(&key.0, key.1);
+
+        let output = systemd::systemctl(&[
+            "show",
+            &unit_name,
+            "--property=LoadState,ActiveState,SubState,NRestarts",
+        ])
+        .await?;
+        let unit_state = Self::parse_unit_state(&output);
+
+        let state_enum = Self::aggregate_status(&unit_state, expected_deploy);
+
+        let mut components = Vec::new();
+        if unit_state.load_state == "loaded" {
+            components.push(rpc::DpuExtensionServiceComponent {
+                name: unit_name,
+                version: service.version.to_string(),
+                url: String::new(),
+                status: unit_state.active_state.to_uppercase(),
+                restart_count: unit_state.restarts,
+            });
+        }
+
+        let message = match self.service_errors.get(&key) {
+            Some(e) => e.to_string(),
+            None => format!(
+                "unit state: {} ({})",
+                unit_state.active_state, unit_state.sub_state
+            ),
+        };
+
+        Ok(rpc::DpuExtensionServiceStatusObservation {
+            service_id: service.id.to_string(),
+            service_type: service.service_type as i32,
+            service_name: service.id.to_string(),
+            version: service.version.to_string(),
+            removed: service.removed.clone(),
+            state: state_enum as i32,
+            components,
+            message,
+        })
+    }
+}
+
+#[async_trait]
+impl ExtensionServiceHandler for SystemdUnitServicesHandler {
+    /// Reconciles the extension service units in /etc/systemd/system with the desired services
+    async fn update_active_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        if let Err(e) = self.update_services(services).await {
+            tracing::error!("Failed to update active services: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn get_service_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        let res = self.get_unit_status(service).await;
+        match res {
+            Ok(status) => Ok(status),
+            Err(e) => Ok(rpc::DpuExtensionServiceStatusObservation {
+                service_id: service.id.to_string(),
+                service_type: service.service_type as i32,
+                service_name: service.id.to_string(),
+                version: service.version.to_string(),
+                removed: service.removed.clone(),
+                state: rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError as i32,
+                components: Vec::new(),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_systemd_unit_handler_unit_name() {
+        let service_id = "123e4567-e89b-12d3-a456-426614174000";
+        let unit_name = SystemdUnitServicesHandler::get_unit_name(service_id, 12);
+        assert_eq!(
+            unit_name,
+            "extservice-123e4567-e89b-12d3-a456-426614174000-12.service"
+        );
+        assert_eq!(
+            SystemdUnitServicesHandler::get_unit_path(service_id, 12),
+            PathBuf::from(
+                "/etc/systemd/system/extservice-123e4567-e89b-12d3-a456-426614174000-12.service"
+            )
+        );
+
+        assert_eq!(
+            SystemdUnitServicesHandler::get_service_id_and_version_from_filename(&unit_name),
+            Some((uuid::Uuid::parse_str(service_id).unwrap(), 12))
+        );
+
+        for invalid in [
+            "sshd.service",
+            "extservice-123e4567-e89b-12d3-a456-426614174000-12.service.tmp",
+            "extservice-123e4567-e89b-12d3-a456-426614174000.service",
+            "extservice-not-a-uuid-1.service",
+        ] {
+            assert_eq!(
+                SystemdUnitServicesHandler::get_service_id_and_version_from_filename(invalid),
+                None,
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_systemd_unit_handler_with_install_section() {
+        let unit = "[Unit]\nDescription=Test\n\n[Service]\nExecStart=/usr/bin/sleep infinity\n";
+        assert_eq!(
+            SystemdUnitServicesHandler::with_install_section(unit),
+            "[Unit]\nDescription=Test\n\n[Service]\nExecStart=/usr/bin/sleep infinity\n\n[Install]\nWantedBy=multi-user.target\n"
+        );
+
+        let unit = "[Service]\nExecStart=/usr/bin/true\n\n[Install]\nWantedBy=default.target\n";
+        assert_eq!(SystemdUnitServicesHandler::with_install_section(unit), unit);
+    }
+
+    #[test]
+    fn test_systemd_unit_handler_parse_unit_state() {
+        let state = SystemdUnitServicesHandler::parse_unit_state(
+            "LoadState=loaded\nActiveState=active\nSubState=running\nNRestarts=3\n",
+        );
+        assert_eq!(
+            state,
+            UnitState {
+                load_state: "loaded".to_string(),
+                active_state: "active".to_string(),
+                sub_state: "running".to_string(),
+                restarts: Some(3),
+            }
+        );
+
+        // systemd < 235 does not know about NRestarts
+        let state = SystemdUnitServicesHandler::parse_unit_state(
+            "LoadState=not-found\nActiveState=inactive\nSubState=dead\n",
+        );
+        assert_eq!(state.load_state, "not-found");
+        assert_eq!(state.restarts, None);
+    }
+
+    #[test]
+    fn test_systemd_unit_handler_aggregate_status() {
+        use rpc::DpuExtensionServiceDeploymentStatus as Status;
+
+        let state = |load_state: &str, active_state: &str| UnitState {
+            load_state: load_state.to_string(),
+            active_state: active_state.to_string(),
+            ..Default::default()
+        };
+
+        let cases = [
+            (
+                state("not-found", "inactive"),
+                true,
+                Status::DpuExtensionServicePending,
+            ),
+            (
+                state("loaded", "active"),
+                true,
+                Status::DpuExtensionServiceRunning,
+            ),
+            (
+                state("loaded", "activating"),
+                true,
+                Status::DpuExtensionServicePending,
+            ),
+            (
+                state("loaded", "failed"),
+                true,
+                Status::DpuExtensionServiceError,
+            ),
+            (
+                state("loaded", "maintenance"),
+                true,
+                Status::DpuExtensionServiceUnknown,
+            ),
+            (
+                state("loaded", "active"),
+                false,
+                Status::DpuExtensionServiceTerminating,
+            ),
+            (
+                state("loaded", "inactive"),
+                false,
+                Status::DpuExtensionServiceTerminated,
+            ),
+            (
+                state("not-found", "inactive"),
+                false,
+                Status::DpuExtensionServiceTerminated,
+            ),
+        ];
+
+        for (unit_state, expected_deploy, expected) in cases {
+            assert_eq!(
+                SystemdUnitServicesHandler::aggregate_status(&unit_state, expected_deploy),
+                expected,
+                "{unit_state:?} expected_deploy={expected_deploy}"
+            );
+        }
+    }
+}
@@ -14,15 +14,20 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use ::rpc::forge_tls_client::ForgeClientConfig;
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
 use axum::Router;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
-use axum::routing::{get, post};
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use carbide_host_support::agent_config::SessionTokenMode;
 use carbide_uuid::machine::MachineId;
 use eyre::eyre;
 use forge_dpu_agent_utils::utils::create_forge_client;
@@ -33,7 +38,7 @@ use mockall::automock;
 use nonzero_ext::nonzero;
 use rpc::forge::ManagedHostNetworkConfigResponse;
 
-use crate::periodic_config_fetcher::InstanceMetadata;
+use crate::periodic_config_fetcher::{InstanceMetadata, NetworkInterfaceConfig};
 use crate::util::phone_home;
 
 const PUBLIC_IPV4_CATEGORY: &str = "public-ipv4";
@@ -51,6 +56,27 @@ const MACHINE_ID_CATEGORY: &str = "machine-id";
 const INSTANCE_ID_CATEGORY: &str = "instance-id";
 const PHONE_HOME_CATEGORY: &str = "phone_home";
 const ASN_CATEGORY: &str = "asn";
+const PUBLIC_KEYS_CATEGORY: &str = "public-keys";
+const NETWORK_CATEGORY: &str = "network";
+const TAGS_CATEGORY: &str = "tags";
+const OPENSSH_KEY: &str = "openssh-key";
+const DEVICE_NUMBER: &str = "device-number";
+const MAC: &str = "mac";
+const LOCAL_IPV4S: &str = "local-ipv4s";
+const SUBNET_IPV4_CIDR_BLOCKS: &str = "subnet-ipv4-cidr-blocks";
+const GATEWAYS: &str = "gateways";
+const NETWORK_SEGMENT_ID: &str = "network-segment-id";
+const VPC_VNI: &str = "vpc-vni";
+const VPC_IPV4_CIDR_BLOCKS: &str = "vpc-ipv4-cidr-blocks";
+
+const TOKEN_PATH: &str = "/api/token";
+const TOKEN_HEADER: &str = "x-aws-ec2-metadata-token";
+const TOKEN_TTL_HEADER: &str = "x-aws-ec2-metadata-token-ttl-seconds";
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+const MAX_TOKEN_TTL_SECS: u64 = 21600;
+/// Upper bound for the amount of unexpired tokens, so that a misbehaving
+/// client can not exhaust the memory of the DPU
+const MAX_SESSION_TOKENS: usize = 4096;
 
 #[automock]
 #[async_trait]
@@ -140,7 +166,56 @@ impl InstanceMetadataRouterStateImpl {
     }
 }
 
-pub fn get_fmds_router(metadata_router_state: Arc<dyn InstanceMetadataRouterState>) -> Router {
+/// Session tokens which were handed out by `PUT /api/token`
+///
+/// Fetching a token requires a `PUT` request with a custom header, which
+/// can't be issued through most SSRF vulnerabilities of tenant workloads.
+pub struct SessionTokens {
+    mode: SessionTokenMode,
+    /// Maps each token to the time it expires
+    tokens: Mutex<HashMap<String, Instant>>,
+}
+
+impl SessionTokens {
+    pub fn new(mode: SessionTokenMode) -> Self {
+        Self {
+            mode,
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a new token which is valid for `ttl`. Returns `None` if too
+    /// many tokens are in use.
+    fn issue(&self, ttl: Duration) -> Option<String> {
+        let now = Instant::now();
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|_, expires_at| *expires_at > now);
+        if tokens.len() >= MAX_SESSION_TOKENS {
+            return None;
+        }
+
+        let token = format!(
+            "{:032x}{:032x}",
+            rand::random::<u128>(),
+            rand::random::<u128>()
+        );
+        tokens.insert(token.clone(), now + ttl);
+        Some(token)
+    }
+
+    fn is_valid(&self, token: &str) -> bool {
+        self.tokens
+            .lock()
+            .unwrap()
+            .get(token)
+            .is_some_and(|expires_at| *expires_at > Instant::now())
+    }
+}
+
+pub fn get_fmds_router(
+    metadata_router_state: Arc<dyn InstanceMetadataRouterState>,
+    session_tokens: Arc<SessionTokens>,
+) -> Router {
     let user_data_router =
         Router::new().route(&format!("/{USER_DATA_CATEGORY}"), get(get_userdata));
 
@@ -162,8 +237,68 @@ pub fn get_fmds_router(metadata_router_state: Arc<dyn InstanceMetadataRouterStat
                 ),
         );
 
+    // Directories are served with and without trailing slash, since EC2
+    // clients differ in how they request them
+    let public_keys_router = Router::new()
+        .route(&format!("/{PUBLIC_KEYS_CATEGORY}"), get(get_public_keys))
+        .route(&format!("/{PUBLIC_KEYS_CATEGORY}/"), get(get_public_keys))
+        .route(
+            &format!("/{PUBLIC_KEYS_CATEGORY}/{{index}}"),
+            get(get_public_key_formats),
+        )
+        .route(
+            &format!("/{PUBLIC_KEYS_CATEGORY}/{{index}}/"),
+            get(get_public_key_formats),
+        )
+        .route(
+            &format!("/{PUBLIC_KEYS_CATEGORY}/{{index}}/{{format}}"),
+            get(get_public_key),
+        );
+
+    let network_router = Router::new()
+        .route(&format!("/{NETWORK_CATEGORY}"), get(get_network_listing))
+        .route(&format!("/{NETWORK_CATEGORY}/"), get(get_network_listing))
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces"),
+            get(get_network_interfaces_listing),
+        )
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces/"),
+            get(get_network_interfaces_listing),
+        )
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces/macs"),
+            get(get_network_interfaces),
+        )
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces/macs/"),
+            get(get_network_interfaces),
+        )
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces/macs/{{mac}}"),
+            get(get_network_interface_attributes),
+        )
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces/macs/{{mac}}/"),
+            get(get_network_interface_attributes),
+        )
+        .route(
+            &format!("/{NETWORK_CATEGORY}/interfaces/macs/{{mac}}/{{attribute}}"),
+            get(get_network_interface_attribute),
+        );
+
+    let tags_router = Router::new()
+        .route(&format!("/{TAGS_CATEGORY}"), get(get_tags_listing))
+        .route(&format!("/{TAGS_CATEGORY}/"), get(get_tags_listing))
+        .route(&format!("/{TAGS_CATEGORY}/instance"), get(get_tags))
+        .route(&format!("/{TAGS_CATEGORY}/instance/"), get(get_tags))
+        .route(&format!("/{TAGS_CATEGORY}/instance/{{key}}"), get(get_tag));
+
     let service_router = Router::new()
         .nest(&format!("/{INFINIBAND_CATEGORY}"), ib_router)
+        .merge(public_keys_router)
+        .merge(network_router)
+        .merge(tags_router)
         .route(&format!("/{PHONE_HOME_CATEGORY}"), post(post_phone_home))
         .route(&format!("/{INSTANCE_ID_CATEGORY}"), get(get_instance_id))
         .route(&format!("/{MACHINE_ID_CATEGORY}"), get(get_machine_id))
@@ -176,10 +311,88 @@ pub fn get_fmds_router(metadata_router_state: Arc<dyn InstanceMetadataRouterStat
         .route(&format!("/{META_DATA_CATEGORY}"), get(get_metadata_params))
         .nest(&format!("/{META_DATA_CATEGORY}"), service_router);
 
+    let token_router = Router::new()
+        .route(TOKEN_PATH, put(put_session_token))
+        .with_state(session_tokens.clone());
+
     Router::new()
         .merge(metadata_router)
         .merge(user_data_router)
+        .route_layer(middleware::from_fn_with_state(
+            session_tokens,
+            check_session_token,
+        ))
         .with_state(metadata_router_state)
+        .merge(token_router)
+}
+
+/// Hands out a new session token, following the IMDSv2 protocol
+async fn put_session_token(
+    State(session_tokens): State<Arc<SessionTokens>>,
+    headers: HeaderMap,
+) -> (StatusCode, String) {
+    // Requests which went through a proxy are likely not issued by the instance itself
+    if headers.contains_key(FORWARDED_FOR_HEADER) {
+        return (
+            StatusCode::FORBIDDEN,
+            "forwarded requests are not allowed".to_string(),
+        );
+    }
+
+    let ttl = match headers
+        .get(TOKEN_TTL_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+    {
+        Some(ttl) if (1..=MAX_TOKEN_TTL_SECS).contains(&ttl) => Duration::from_secs(ttl),
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("{TOKEN_TTL_HEADER} must be between 1 and {MAX_TOKEN_TTL_SECS}"),
+            );
+        }
+    };
+
+    match session_tokens.issue(ttl) {
+        Some(token) => (StatusCode::OK, token),
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "too many session tokens in use".to_string(),
+        ),
+    }
+}
+
+/// Rejects requests with an invalid session token, and requests without
+/// token if tokens are required
+async fn check_session_token(
+    State(session_tokens): State<Arc<SessionTokens>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match request.headers().get(TOKEN_HEADER) {
+        Some(token) => {
+            if !token
+                .to_str()
+                .is_ok_and(|token| session_tokens.is_valid(token))
+            {
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    "invalid or expired session token".to_string(),
+                )
+                    .into_response();
+            }
+        }
+        None if session_tokens.mode == SessionTokenMode::Required => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                "session token required".to_string(),
+            )
+                .into_response();
+        }
+        None => {}
+    }
+
+    next.run(request).await
 }
 
 async fn get_metadata_parameter(
@@ -281,11 +494,219 @@ async fn get_metadata_params(
             MACHINE_ID_CATEGORY,
             INSTANCE_ID_CATEGORY,
             ASN_CATEGORY,
+            "public-keys/",
+            "network/",
+            "tags/",
         ]
         .join("\n"),
     )
 }
 
+fn read_instance_metadata(
+    state: &Arc<dyn InstanceMetadataRouterState>,
+) -> Result<Arc<InstanceMetadata>, (StatusCode, String)> {
+    state.read().0.ok_or_else(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "metadata currently unavailable".to_string(),
+        )
+    })
+}
+
+async fn get_public_keys(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+
+    let response = metadata
+        .public_keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| match &key.comment {
+            Some(comment) => format!("{index}={comment}"),
+            None => format!("{index}=key-{index}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    (StatusCode::OK, response)
+}
+
+async fn get_public_key_formats(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+    Path(index): Path<usize>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+
+    if metadata.public_keys.len() <= index {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("no public key at index: {index}"),
+        );
+    }
+    (StatusCode::OK, OPENSSH_KEY.to_string())
+}
+
+async fn get_public_key(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+    Path((index, format)): Path<(usize, String)>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+
+    match (metadata.public_keys.get(index), format.as_str()) {
+        (Some(key), OPENSSH_KEY) => (StatusCode::OK, key.public_key.clone()),
+        (Some(_), _) => (StatusCode::NOT_FOUND, "no such key format".to_string()),
+        (None, _) => (
+            StatusCode::NOT_FOUND,
+            format!("no public key at index: {index}"),
+        ),
+    }
+}
+
+async fn get_network_listing() -> (StatusCode, String) {
+    (StatusCode::OK, "interfaces/".to_string())
+}
+
+async fn get_network_interfaces_listing() -> (StatusCode, String) {
+    (StatusCode::OK, "macs/".to_string())
+}
+
+async fn get_network_interfaces(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+
+    let response = metadata
+        .network_interfaces
+        .iter()
+        .map(|interface| format!("{}/", interface.mac_address))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (StatusCode::OK, response)
+}
+
+fn find_network_interface<'a>(
+    metadata: &'a InstanceMetadata,
+    mac: &str,
+) -> Option<(usize, &'a NetworkInterfaceConfig)> {
+    metadata
+        .network_interfaces
+        .iter()
+        .enumerate()
+        .find(|(_, interface)| interface.mac_address.eq_ignore_ascii_case(mac))
+}
+
+async fn get_network_interface_attributes(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+    Path(mac): Path<String>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+    let Some((_, interface)) = find_network_interface(&metadata, &mac) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("no interface with mac: {mac}"),
+        );
+    };
+
+    let mut attributes = vec![DEVICE_NUMBER, MAC, LOCAL_IPV4S, SUBNET_IPV4_CIDR_BLOCKS];
+    if !interface.gateways.is_empty() {
+        attributes.push(GATEWAYS);
+    }
+    if interface.network_segment_id.is_some() {
+        attributes.push(NETWORK_SEGMENT_ID);
+    }
+    if interface.vpc_vni.is_some() {
+        attributes.push(VPC_VNI);
+        attributes.push(VPC_IPV4_CIDR_BLOCKS);
+    }
+    (StatusCode::OK, attributes.join("\n"))
+}
+
+async fn get_network_interface_attribute(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+    Path((mac, attribute)): Path<(String, String)>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+    let Some((index, interface)) = find_network_interface(&metadata, &mac) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("no interface with mac: {mac}"),
+        );
+    };
+
+    let value = match attribute.as_str() {
+        DEVICE_NUMBER => Some(index.to_string()),
+        MAC => Some(interface.mac_address.clone()),
+        LOCAL_IPV4S => Some(interface.addresses.join("\n")),
+        SUBNET_IPV4_CIDR_BLOCKS => Some(interface.prefixes.join("\n")),
+        GATEWAYS if !interface.gateways.is_empty() => Some(interface.gateways.join("\n")),
+        NETWORK_SEGMENT_ID => interface.network_segment_id.clone(),
+        VPC_VNI => interface.vpc_vni.map(|vni| vni.to_string()),
+        VPC_IPV4_CIDR_BLOCKS if interface.vpc_vni.is_some() => {
+            Some(interface.vpc_prefixes.join("\n"))
+        }
+        _ => None,
+    };
+
+    match value {
+        Some(value) => (StatusCode::OK, value),
+        None => (StatusCode::NOT_FOUND, "no such attribute".to_string()),
+    }
+}
+
+async fn get_tags_listing() -> (StatusCode, String) {
+    (StatusCode::OK, "instance/".to_string())
+}
+
+async fn get_tags(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+
+    let response = metadata
+        .labels
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    (StatusCode::OK, response)
+}
+
+async fn get_tag(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+    Path(key): Path<String>,
+) -> (StatusCode, String) {
+    let metadata = match read_instance_metadata(&state) {
+        Ok(metadata) => metadata,
+        Err(err) => return err,
+    };
+
+    match metadata.labels.get(&key) {
+        Some(value) => (StatusCode::OK, value.clone()),
+        None => (StatusCode::NOT_FOUND, format!("tag not found: {key}")),
+    }
+}
+
 async fn get_devices(
     State(state): State<Arc<dyn InstanceMetadataRouterState>>,
 ) -> (StatusCode, String) {
@@ -471,7 +892,9 @@ mod tests {
     use uuid::uuid;
 
     use super::*;
-    use crate::periodic_config_fetcher::{IBDeviceConfig, IBInstanceConfig, InstanceMetadata};
+    use crate::periodic_config_fetcher::{
+        IBDeviceConfig, IBInstanceConfig, InstanceMetadata, PublicKeyConfig,
+    };
 
     async fn setup_server(
         metadata: Option<InstanceMetadata>,
@@ -487,8 +910,34 @@ mod tests {
 
         let arc_mock_router_state = Arc::new(mock_router_state);
 
-        let router = get_fmds_router(arc_mock_router_state);
+        let router = get_fmds_router(
+            arc_mock_router_state,
+            Arc::new(SessionTokens::new(SessionTokenMode::Optional)),
+        );
+
+        serve_router(router).await
+    }
 
+    /// Sets up a server which serves the given metadata for any amount of requests
+    async fn setup_server_with_session_tokens(
+        metadata: InstanceMetadata,
+        session_token_mode: SessionTokenMode,
+    ) -> (tokio::task::JoinHandle<()>, u16) {
+        let mut mock_router_state = MockInstanceMetadataRouterState::new();
+        mock_router_state.expect_read().return_const((
+            Some(Arc::new(metadata)),
+            Some(Arc::new(ManagedHostNetworkConfigResponse::default())),
+        ));
+
+        let router = get_fmds_router(
+            Arc::new(mock_router_state),
+            Arc::new(SessionTokens::new(session_token_mode)),
+        );
+
+        serve_router(router).await
+    }
+
+    async fn serve_router(router: Router) -> (tokio::task::JoinHandle<()>, u16) {
         let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 0));
         let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
         let server_port = listener.local_addr().unwrap().port();
@@ -529,6 +978,345 @@ mod tests {
         assert_eq!(body_str, expected_body);
     }
 
+    async fn send_request(
+        port: u16,
+        method: hyper::Method,
+        path: &str,
+        headers: &[(&str, &str)],
+    ) -> (http::StatusCode, String) {
+        let client = hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build_http();
+        let mut builder = hyper::Request::builder()
+            .method(method)
+            .uri(format!("http://127.0.0.1:{port}/{path}"));
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let request: hyper::Request<Full<Bytes>> = builder.body("".into()).unwrap();
+
+        let response = client.request(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn test_metadata() -> InstanceMetadata {
+        InstanceMetadata {
+            instance_id: Some(uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8").into()),
+            machine_id: None,
+            address: "127.0.0.1".to_string(),
+            hostname: "localhost".to_string(),
+            user_data: "\"userData\": {\"data\": 0}".to_string(),
+            ib_devices: None,
+            config_version: "V2-T1666644937962267".parse().unwrap(),
+            network_config_version: "V1-T1666644937952267".parse().unwrap(),
+            sitename: Some("testsite".to_string()),
+            extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![
+                PublicKeyConfig {
+                    public_key: "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIK0wmN/Cr3JXqmLW7u+g9pTh+wyqDHpSQEIQczXkVx9q alice@laptop".to_string(),
+                    comment: Some("alice".to_string()),
+                },
+                PublicKeyConfig {
+                    public_key: "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIOMqqnkVzrm0SdG6UOoqKLsabgH5C9okWi0dh2l9GKJl".to_string(),
+                    comment: None,
+                },
+            ],
+            network_interfaces: vec![
+                NetworkInterfaceConfig {
+                    mac_address: "a0:88:c2:08:80:95".to_string(),
+                    addresses: vec!["192.168.0.10".to_string()],
+                    prefixes: vec!["192.168.0.10/32".to_string()],
+                    gateways: vec!["192.168.0.1/32".to_string()],
+                    network_segment_id: Some("91609f10-c91d-470d-a260-6293ea0c1200".to_string()),
+                    vpc_vni: Some(4242),
+                    vpc_prefixes: vec!["192.168.0.0/24".to_string(), "10.10.0.0/16".to_string()],
+                },
+                NetworkInterfaceConfig {
+                    mac_address: "a0:88:c2:08:80:96".to_string(),
+                    addresses: vec!["192.168.1.10".to_string()],
+                    prefixes: vec!["192.168.1.10/32".to_string()],
+                    ..Default::default()
+                },
+            ],
+            labels: [
+                ("team".to_string(), "storage".to_string()),
+                ("env".to_string(), "prod".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_public_keys() {
+        let metadata = test_metadata();
+        let (server, port) =
+            setup_server_with_session_tokens(metadata.clone(), SessionTokenMode::Optional).await;
+
+        for path in ["meta-data/public-keys", "meta-data/public-keys/"] {
+            assert_eq!(
+                send_request(port, hyper::Method::GET, path, &[]).await,
+                (StatusCode::OK, "0=alice\n1=key-1".to_string())
+            );
+        }
+        assert_eq!(
+            send_request(port, hyper::Method::GET, "meta-data/public-keys/0/", &[]).await,
+            (StatusCode::OK, OPENSSH_KEY.to_string())
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/public-keys/1/openssh-key",
+                &[]
+            )
+            .await,
+            (StatusCode::OK, metadata.public_keys[1].public_key.clone())
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/public-keys/2/openssh-key",
+                &[]
+            )
+            .await
+            .0,
+            StatusCode::NOT_FOUND
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_network_interfaces() {
+        let (server, port) =
+            setup_server_with_session_tokens(test_metadata(), SessionTokenMode::Optional).await;
+
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/network/interfaces/macs/",
+                &[]
+            )
+            .await,
+            (
+                StatusCode::OK,
+                "a0:88:c2:08:80:95/\na0:88:c2:08:80:96/".to_string()
+            )
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/network/interfaces/macs/A0:88:C2:08:80:95/",
+                &[]
+            )
+            .await,
+            (
+                StatusCode::OK,
+                [
+                    DEVICE_NUMBER,
+                    MAC,
+                    LOCAL_IPV4S,
+                    SUBNET_IPV4_CIDR_BLOCKS,
+                    GATEWAYS,
+                    NETWORK_SEGMENT_ID,
+                    VPC_VNI,
+                    VPC_IPV4_CIDR_BLOCKS
+                ]
+                .join("\n")
+            )
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/network/interfaces/macs/a0:88:c2:08:80:95/vpc-ipv4-cidr-blocks",
+                &[]
+            )
+            .await,
+            (StatusCode::OK, "192.168.0.0/24\n10.10.0.0/16".to_string())
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/network/interfaces/macs/a0:88:c2:08:80:96/device-number",
+                &[]
+            )
+            .await,
+            (StatusCode::OK, "1".to_string())
+        );
+        // The VPC of the second interface is unknown
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/network/interfaces/macs/a0:88:c2:08:80:96/vpc-vni",
+                &[]
+            )
+            .await
+            .0,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/network/interfaces/macs/a0:88:c2:08:80:97/mac",
+                &[]
+            )
+            .await
+            .0,
+            StatusCode::NOT_FOUND
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_tags() {
+        let (server, port) =
+            setup_server_with_session_tokens(test_metadata(), SessionTokenMode::Optional).await;
+
+        assert_eq!(
+            send_request(port, hyper::Method::GET, "meta-data/tags/instance", &[]).await,
+            (StatusCode::OK, "env\nteam".to_string())
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/tags/instance/team",
+                &[]
+            )
+            .await,
+            (StatusCode::OK, "storage".to_string())
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/tags/instance/owner",
+                &[]
+            )
+            .await
+            .0,
+            StatusCode::NOT_FOUND
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_session_token_required() {
+        let (server, port) =
+            setup_server_with_session_tokens(test_metadata(), SessionTokenMode::Required).await;
+
+        assert_eq!(
+            send_request(port, hyper::Method::GET, "user-data", &[])
+                .await
+                .0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send_request(port, hyper::Method::PUT, "api/token", &[])
+                .await
+                .0,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::PUT,
+                "api/token",
+                &[(TOKEN_TTL_HEADER, "21601")]
+            )
+            .await
+            .0,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::PUT,
+                "api/token",
+                &[(TOKEN_TTL_HEADER, "60"), (FORWARDED_FOR_HEADER, "10.0.0.1")]
+            )
+            .await
+            .0,
+            StatusCode::FORBIDDEN
+        );
+
+        let (status, token) = send_request(
+            port,
+            hyper::Method::PUT,
+            "api/token",
+            &[(TOKEN_TTL_HEADER, "60")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/hostname",
+                &[(TOKEN_HEADER, token.as_str())]
+            )
+            .await,
+            (StatusCode::OK, "localhost".to_string())
+        );
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/hostname",
+                &[(TOKEN_HEADER, "invalid")]
+            )
+            .await
+            .0,
+            StatusCode::UNAUTHORIZED
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_session_token_optional() {
+        let (server, port) =
+            setup_server_with_session_tokens(test_metadata(), SessionTokenMode::Optional).await;
+
+        assert_eq!(
+            send_request(port, hyper::Method::GET, "meta-data/hostname", &[]).await,
+            (StatusCode::OK, "localhost".to_string())
+        );
+        // Invalid tokens are rejected even if tokens are optional
+        assert_eq!(
+            send_request(
+                port,
+                hyper::Method::GET,
+                "meta-data/hostname",
+                &[(TOKEN_HEADER, "invalid")]
+            )
+            .await
+            .0,
+            StatusCode::UNAUTHORIZED
+        );
+        server.abort();
+    }
+
+    #[test]
+    fn test_session_token_expiry() {
+        let session_tokens = SessionTokens::new(SessionTokenMode::Required);
+        let token = session_tokens.issue(Duration::ZERO).unwrap();
+        assert!(!session_tokens.is_valid(&token));
+
+        let token = session_tokens.issue(Duration::from_secs(60)).unwrap();
+        assert!(session_tokens.is_valid(&token));
+        // Expired tokens are evicted when a new one is issued
+        assert_eq!(session_tokens.tokens.lock().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_get_metadata_parameter_public_ipv4_category() {
         let metadata = InstanceMetadata {
@@ -546,6 +1334,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -580,6 +1371,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -614,6 +1408,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let expected_output = [
@@ -622,6 +1419,9 @@ mod tests {
             MACHINE_ID_CATEGORY,
             INSTANCE_ID_CATEGORY,
             ASN_CATEGORY,
+            "public-keys/",
+            "network/",
+            "tags/",
         ]
         .join("\n");
 
@@ -660,6 +1460,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -724,6 +1527,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -765,6 +1571,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -813,6 +1622,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -854,6 +1666,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -895,6 +1710,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -936,6 +1754,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -977,6 +1798,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -1018,6 +1842,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -1052,6 +1879,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -1086,6 +1916,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
@@ -1120,6 +1953,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let network_config = ManagedHostNetworkConfigResponse {
@@ -1150,6 +1986,9 @@ mod tests {
             network_config_version: "V1-T1666644937952267".parse().unwrap(),
             sitename: Some("testsite".to_string()),
             extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+            public_keys: vec![],
+            network_interfaces: vec![],
+            labels: Default::default(),
         };
 
         let (server, server_port) = setup_server(
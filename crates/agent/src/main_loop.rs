@@ -122,6 +122,7 @@ pub async fn setup_and_run(
     if options.enable_metadata_service {
         crate::metadata_service::spawn_metadata_service(
             agent_config.metadata_service.address.clone(),
+            agent_config.metadata_service.session_tokens,
             agent_config.telemetry.metrics_address.clone(),
             metrics.clone(),
             instance_metadata_state.clone(),
@@ -18,19 +18,25 @@
 use std::sync::Arc;
 
 use axum::Router;
+use carbide_host_support::agent_config::SessionTokenMode;
 
-use crate::instance_metadata_endpoint::{InstanceMetadataRouterStateImpl, get_fmds_router};
+use crate::instance_metadata_endpoint::{
+    InstanceMetadataRouterStateImpl, SessionTokens, get_fmds_router,
+};
 use crate::instrumentation::{
     AgentMetricsState, WithTracingLayer, get_metrics_router, get_prometheus_registry,
 };
 
 pub fn spawn_metadata_service(
     metadata_service_address: String,
+    session_token_mode: SessionTokenMode,
     metrics_address: String,
     metrics_state: Arc<AgentMetricsState>,
     state: Arc<InstanceMetadataRouterStateImpl>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let instance_metadata_state = state;
+    // Tokens are shared, so that a token fetched with one API version can be used with the other
+    let session_tokens = Arc::new(SessionTokens::new(session_token_mode));
 
     let prometheus_registry = get_prometheus_registry();
     // let meter = get_dpu_agent_meter();
@@ -41,12 +47,13 @@ pub fn spawn_metadata_service(
         Router::new()
             .nest(
                 "/latest",
-                get_fmds_router(instance_metadata_state.clone())
+                get_fmds_router(instance_metadata_state.clone(), session_tokens.clone())
                     .with_tracing_layer(metrics_state.clone()),
             )
             .nest(
                 "/2009-04-04",
-                get_fmds_router(instance_metadata_state).with_tracing_layer(metrics_state),
+                get_fmds_router(instance_metadata_state, session_tokens)
+                    .with_tracing_layer(metrics_state),
             ),
     )
     .expect("metadata server panicked");
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Duration;
@@ -63,6 +64,30 @@ pub struct InstanceMetadata {
     pub config_version: ConfigVersion,
     pub network_config_version: ConfigVersion,
     pub extension_service_version: ConfigVersion,
+    /// SSH public keys of the tenant keysets configured for the instance
+    pub public_keys: Vec<PublicKeyConfig>,
+    /// Network interfaces of the instance, in the order of the instance config
+    pub network_interfaces: Vec<NetworkInterfaceConfig>,
+    /// Labels from the instance metadata
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PublicKeyConfig {
+    pub public_key: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NetworkInterfaceConfig {
+    pub mac_address: String,
+    pub addresses: Vec<String>,
+    pub prefixes: Vec<String>,
+    pub gateways: Vec<String>,
+    pub network_segment_id: Option<String>,
+    /// VNI of the VPC. Only known for interfaces which are attached to this DPU.
+    pub vpc_vni: Option<u32>,
+    pub vpc_prefixes: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -204,7 +229,12 @@ async fn single_fetch(
         Ok(resp) => {
             state.netconf.store(Some(Arc::new(resp.clone())));
 
-            match instance_metadata_from_instance(resp.instance, state.sitename.clone()) {
+            match instance_metadata_from_instance(
+                resp.instance,
+                resp.instance_public_keys,
+                &resp.tenant_interfaces,
+                state.sitename.clone(),
+            ) {
                 Ok(Some(config)) => {
                     state.instmeta.store(Some(Arc::new(config)));
                 }
@@ -250,6 +280,8 @@ pub async fn fetch(
 
 pub fn instance_metadata_from_instance(
     instance: Option<Instance>,
+    public_keys: Vec<rpc::TenantPublicKey>,
+    tenant_interfaces: &[rpc::FlatInterfaceConfig],
     sitename: Option<String>,
 ) -> Result<Option<InstanceMetadata>, eyre::Error> {
     let instance = match instance {
@@ -294,6 +326,20 @@ pub fn instance_metadata_from_instance(
         }
     };
 
+    let labels = instance
+        .metadata
+        .as_ref()
+        .map(|metadata| {
+            metadata
+                .labels
+                .iter()
+                .map(|label| (label.key.clone(), label.value.clone().unwrap_or_default()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let network_interfaces = extract_instance_network_interfaces(&instance, tenant_interfaces);
+
     Ok(Some(InstanceMetadata {
         address: pf_address,
         hostname,
@@ -314,9 +360,70 @@ pub fn instance_metadata_from_instance(
             .dpu_extension_service_version
             .parse()
             .wrap_err("Failed to parse instance extension_service_version")?,
+        public_keys: public_keys
+            .into_iter()
+            .map(|key| PublicKeyConfig {
+                public_key: key.public_key,
+                comment: key.comment,
+            })
+            .collect(),
+        network_interfaces,
+        labels,
     }))
 }
 
+/// Combines the network interface status of the instance with the VPC details
+/// of the interfaces which are attached to this DPU.
+///
+/// On hosts with multiple DPUs, interfaces of different DPUs can share the
+/// same function, e.g. the physical function of each DPU. The status of an
+/// interface is therefore matched with the interface of this DPU which has
+/// the same function and whose address was reported for it.
+fn extract_instance_network_interfaces(
+    instance: &Instance,
+    tenant_interfaces: &[rpc::FlatInterfaceConfig],
+) -> Vec<NetworkInterfaceConfig> {
+    let interface_configs = instance
+        .config
+        .as_ref()
+        .and_then(|config| config.network.as_ref())
+        .map(|network| network.interfaces.as_slice())
+        .unwrap_or_default();
+    let interface_statuses = instance
+        .status
+        .as_ref()
+        .and_then(|status| status.network.as_ref())
+        .map(|network| network.interfaces.as_slice())
+        .unwrap_or_default();
+
+    interface_statuses
+        .iter()
+        .enumerate()
+        // Interfaces without MAC address have not been configured yet
+        .filter_map(|(index, status)| {
+            let mac_address = status.mac_address.clone()?;
+            let tenant_interface = tenant_interfaces.iter().find(|iface| {
+                iface.virtual_function_id == status.virtual_function_id
+                    && status.addresses.contains(&iface.ip)
+            });
+            Some(NetworkInterfaceConfig {
+                mac_address,
+                addresses: status.addresses.clone(),
+                prefixes: status.prefixes.clone(),
+                gateways: status.gateways.clone(),
+                network_segment_id: interface_configs
+                    .get(index)
+                    .and_then(|config| config.network_segment_id)
+                    .map(|id| id.to_string()),
+                vpc_vni: tenant_interface.map(|iface| iface.vpc_vni),
+                vpc_prefixes: tenant_interface
+                    .map(|iface| iface.vpc_prefixes.clone())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
 fn extract_instance_ib_config(instance: &Instance) -> Result<Vec<IBDeviceConfig>, eyre::Error> {
     let ib_config = instance
         .config
@@ -364,3 +471,69 @@ fn extract_instance_ib_config(instance: &Instance) -> Result<Vec<IBDeviceConfig>
 
     Ok(devices)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface_status(
+        virtual_function_id: Option<u32>,
+        mac_address: &str,
+        address: &str,
+    ) -> rpc::InstanceInterfaceStatus {
+        rpc::InstanceInterfaceStatus {
+            virtual_function_id,
+            mac_address: Some(mac_address.to_string()),
+            addresses: vec![address.to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn tenant_interface(
+        virtual_function_id: Option<u32>,
+        ip: &str,
+        vpc_vni: u32,
+    ) -> rpc::FlatInterfaceConfig {
+        rpc::FlatInterfaceConfig {
+            virtual_function_id,
+            ip: ip.to_string(),
+            vpc_vni,
+            vpc_prefixes: vec![format!("{ip}/32")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_network_interfaces_of_multi_dpu_host() {
+        // Both DPUs of the host expose their physical function to the instance
+        let instance = Instance {
+            status: Some(rpc::InstanceStatus {
+                network: Some(rpc::InstanceNetworkStatus {
+                    interfaces: vec![
+                        interface_status(None, "a0:88:c2:00:00:01", "10.0.1.2"),
+                        interface_status(None, "a0:88:c2:00:00:02", "10.0.2.2"),
+                        interface_status(Some(0), "a0:88:c2:00:00:03", "10.0.2.3"),
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        // This DPU only carries the interfaces of the second VPC
+        let tenant_interfaces = [
+            tenant_interface(None, "10.0.2.2", 2002),
+            tenant_interface(Some(0), "10.0.2.3", 2002),
+        ];
+
+        let interfaces = extract_instance_network_interfaces(&instance, &tenant_interfaces);
+
+        assert_eq!(interfaces.len(), 3);
+        assert_eq!(interfaces[0].vpc_vni, None);
+        assert!(interfaces[0].vpc_prefixes.is_empty());
+        assert_eq!(interfaces[1].vpc_vni, Some(2002));
+        assert_eq!(interfaces[1].vpc_prefixes, vec!["10.0.2.2/32".to_string()]);
+        assert_eq!(interfaces[2].vpc_vni, Some(2002));
+        assert_eq!(interfaces[2].vpc_prefixes, vec!["10.0.2.3/32".to_string()]);
+    }
+}
@@ -834,6 +834,7 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
             }),
             configs_synced: rpc::SyncState::Synced.into(),
             update: None,
+            attestation: None,
         }),
         network_config_version: "V1-T1748645613333257".to_string(),
         ib_config_version: "V1-T1748645613333260".to_string(),
@@ -845,6 +846,7 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
 
     let netconf = rpc::forge::ManagedHostNetworkConfigResponse {
         site_global_vpc_vni: None,
+        instance_public_keys: vec![],
         asn: 65535,
         datacenter_asn: 11414,
         common_internal_route_target: Some(rpc_common::RouteTarget {
@@ -0,0 +1,27 @@
+-- Tenant facing lifecycle events of instances, consumed by StreamInstanceEvents.
+-- The table intentionally has no foreign key to instances, since events
+-- (e.g. the final transition to TERMINATED) need to outlive the instance.
+CREATE TABLE instance_events (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    instance_id uuid NOT NULL,
+    tenant_organization_id VARCHAR NOT NULL,
+    event jsonb NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_instance_events_instance_id ON instance_events (instance_id, id);
+CREATE INDEX idx_instance_events_tenant_organization_id ON instance_events (tenant_organization_id, id);
+
+CREATE OR REPLACE FUNCTION instance_events_keep_limit()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    DELETE FROM instance_events WHERE instance_id=NEW.instance_id AND id NOT IN (SELECT id from instance_events where instance_id=NEW.instance_id ORDER BY id DESC LIMIT 250);
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_instance_events_keep_limit
+  AFTER INSERT ON instance_events
+  FOR EACH ROW EXECUTE PROCEDURE instance_events_keep_limit();
@@ -0,0 +1,18 @@
+-- Durable record of mutating admin and tenant RPCs, queried via FindAuditLogEntries.
+-- Successful calls are recorded in the same transaction as the change they perform.
+CREATE TYPE audit_log_outcome AS ENUM ('success', 'failure');
+
+CREATE TABLE audit_log (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    principal VARCHAR NOT NULL,
+    rpc_name VARCHAR NOT NULL,
+    object_ids VARCHAR[] NOT NULL DEFAULT '{}',
+    request TEXT NOT NULL,
+    outcome audit_log_outcome NOT NULL,
+    error TEXT
+);
+
+CREATE INDEX idx_audit_log_timestamp ON audit_log (timestamp);
+CREATE INDEX idx_audit_log_principal ON audit_log (principal, id);
+CREATE INDEX idx_audit_log_object_ids ON audit_log USING GIN (object_ids);
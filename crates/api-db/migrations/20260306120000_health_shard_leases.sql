@@ -0,0 +1,17 @@
+-- Membership leases of carbide-hw-health replicas. BMC endpoints are assigned to
+-- all replicas with an unexpired lease via rendezvous hashing.
+CREATE TABLE health_shard_leases (
+    member_id VARCHAR PRIMARY KEY,
+    renewed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+-- State of health collectors, e.g. log cursors, which is handed over to another
+-- replica when a BMC endpoint moves between replicas.
+CREATE TABLE health_collector_state (
+    endpoint_key VARCHAR NOT NULL,
+    collector VARCHAR NOT NULL,
+    state JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (endpoint_key, collector)
+);
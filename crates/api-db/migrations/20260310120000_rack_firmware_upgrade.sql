@@ -0,0 +1,2 @@
+-- Progress of the rack firmware upgrade which is driven by the rack state controller
+ALTER TABLE racks ADD COLUMN firmware_upgrade jsonb;
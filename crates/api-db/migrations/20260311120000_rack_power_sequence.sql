@@ -0,0 +1,2 @@
+-- Progress of the rack power sequence which is driven by the rack state controller
+ALTER TABLE racks ADD COLUMN power_sequence jsonb;
@@ -0,0 +1,12 @@
+-- Result of expanding the object references (VPCs, VPC prefixes and network
+-- segments) in the rules of a network security group into prefixes.
+-- The status is only valid for the NSG version it was computed for.
+CREATE TABLE network_security_group_expansion_status (
+    network_security_group_id   character varying(64) NOT NULL,
+    version                     character varying(64) NOT NULL,
+    expanded_rule_count         bigint NOT NULL,
+    error                       text,
+    checked_at                  timestamp with time zone DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+ALTER TABLE ONLY network_security_group_expansion_status ADD CONSTRAINT network_security_group_expansion_status_pkey PRIMARY KEY (network_security_group_id);
+ALTER TABLE ONLY network_security_group_expansion_status ADD CONSTRAINT network_security_group_expansion_status_nsg_id_fkey FOREIGN KEY (network_security_group_id) REFERENCES network_security_groups(id) ON DELETE CASCADE;
@@ -0,0 +1,7 @@
+-- OS images are downloaded, verified and inspected before they can be used.
+-- Errors found while doing so are reported in the status message.
+ALTER TABLE os_images ALTER COLUMN status_message TYPE TEXT;
+
+-- When the ingestion of an image was started, to retry images whose
+-- ingestion got interrupted
+ALTER TABLE os_images ADD COLUMN ingestion_started_at TIMESTAMPTZ;
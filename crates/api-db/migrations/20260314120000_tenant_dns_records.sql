@@ -0,0 +1,22 @@
+-- DNS records that tenants manage inside the domains of their network segments.
+-- Records derived from interface addresses stay in the dns_records views; these
+-- hold everything a tenant adds on top (CNAME, TXT, SRV and extra A/AAAA).
+CREATE TABLE tenant_dns_records (
+    id uuid DEFAULT gen_random_uuid() NOT NULL,
+    domain_id uuid NOT NULL REFERENCES domains(id),
+    tenant_organization_id VARCHAR(64) NOT NULL,
+    -- Fully qualified name including the trailing dot, matching dns_records.q_name
+    q_name VARCHAR(255) NOT NULL,
+    q_type VARCHAR(10) NOT NULL,
+    content TEXT NOT NULL,
+    ttl INTEGER,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY(id),
+    CONSTRAINT tenant_dns_records_q_type_check CHECK (q_type IN ('A', 'AAAA', 'CNAME', 'TXT', 'SRV'))
+);
+
+CREATE INDEX tenant_dns_records_q_name_idx ON tenant_dns_records (q_name);
+CREATE INDEX tenant_dns_records_tenant_idx ON tenant_dns_records (tenant_organization_id, domain_id);
+CREATE UNIQUE INDEX tenant_dns_records_unique_content ON tenant_dns_records (q_name, q_type, content);
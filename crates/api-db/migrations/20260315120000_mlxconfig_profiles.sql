@@ -0,0 +1,34 @@
+-- mlxconfig profiles managed through the API rather than the static
+-- runtime config. The profile column holds a libmlx SerializableProfile.
+CREATE TABLE mlxconfig_profiles (
+    name VARCHAR(256) PRIMARY KEY NOT NULL,
+    version VARCHAR(64) NOT NULL,
+    description VARCHAR,
+    profile JSONB NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A profile is bound either to a SKU or to an instance type. A SKU binding
+-- takes precedence when a machine matches both.
+CREATE TABLE mlxconfig_profile_bindings (
+    profile_name VARCHAR(256) NOT NULL REFERENCES mlxconfig_profiles(name),
+    sku_id VARCHAR(256) REFERENCES machine_skus(id) ON DELETE CASCADE,
+    instance_type_id VARCHAR(64) REFERENCES instance_types(id),
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT mlxconfig_profile_bindings_target_check CHECK (num_nonnulls(sku_id, instance_type_id) = 1)
+);
+
+CREATE UNIQUE INDEX mlxconfig_profile_bindings_sku_idx ON mlxconfig_profile_bindings (sku_id) WHERE sku_id IS NOT NULL;
+CREATE UNIQUE INDEX mlxconfig_profile_bindings_instance_type_idx ON mlxconfig_profile_bindings (instance_type_id) WHERE instance_type_id IS NOT NULL;
+
+-- The outcome of the last profile sync scout reported for a host.
+CREATE TABLE machine_mlxconfig_status (
+    machine_id VARCHAR(64) PRIMARY KEY NOT NULL REFERENCES machines(id) ON DELETE CASCADE,
+    profile_name VARCHAR(256) NOT NULL,
+    profile_version VARCHAR(64) NOT NULL,
+    synced BOOLEAN NOT NULL,
+    variables_changed INTEGER NOT NULL DEFAULT 0,
+    observed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,16 @@
+-- Raw TCG event logs captured alongside measurement reports, so a report
+-- can be replayed against its quoted PCR values and compared event by event
+-- against the bundle it was (or should have been) matched with.
+CREATE TABLE measurement_reports_event_logs (
+    report_id uuid PRIMARY KEY REFERENCES measurement_reports ON DELETE CASCADE,
+    event_log bytea NOT NULL,
+    ts TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+-- Bundles created from a report keep a copy of the report's event log,
+-- which serves as the golden baseline when diffing later reports.
+CREATE TABLE measurement_bundles_event_logs (
+    bundle_id uuid PRIMARY KEY REFERENCES measurement_bundles ON DELETE CASCADE,
+    event_log bytea NOT NULL,
+    ts TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
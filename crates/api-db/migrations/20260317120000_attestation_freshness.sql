@@ -0,0 +1,15 @@
+-- When a machine last completed SPDM attestation, and whether it passed.
+-- The attestation policy re-attests machines whose last attestation is older
+-- than the configured interval, and both columns are exposed to tenants as
+-- the attestation freshness of their host.
+ALTER TABLE spdm_machine_attestation
+    ADD COLUMN last_attested_at TIMESTAMPTZ,
+    ADD COLUMN last_attestation_passed BOOLEAN;
+
+-- Requests for a machine to submit new measured boot measurements, e.g.
+-- after its firmware was updated. A request is served once the machine
+-- submits a measurement report newer than the request.
+CREATE TABLE measured_boot_reattestation_requests (
+    machine_id VARCHAR PRIMARY KEY REFERENCES machines(id) ON DELETE CASCADE,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,10 @@
+-- Instance events are streamed in the order of the transactions which recorded them,
+-- and only once no older transaction can commit further events. Streaming them by
+-- their identity column alone skips events of transactions which commit after events
+-- with a higher ID had already been streamed.
+ALTER TABLE instance_events ADD COLUMN xid xid8 NOT NULL DEFAULT pg_current_xact_id();
+
+DROP INDEX idx_instance_events_instance_id;
+DROP INDEX idx_instance_events_tenant_organization_id;
+CREATE INDEX idx_instance_events_instance_id ON instance_events (instance_id, xid, id);
+CREATE INDEX idx_instance_events_tenant_organization_id ON instance_events (tenant_organization_id, xid, id);
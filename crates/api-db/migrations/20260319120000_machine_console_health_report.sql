@@ -0,0 +1,3 @@
+-- Add a field to store the health report sent by ssh-console's console monitor, so that it
+-- doesn't overwrite the report of the log parser
+ALTER TABLE machines ADD COLUMN IF NOT EXISTS console_health_report jsonb;
@@ -0,0 +1,3 @@
+-- Audit log entries are recorded as pending before the call is handled, and
+-- updated with the outcome of the call
+ALTER TYPE audit_log_outcome ADD VALUE IF NOT EXISTS 'pending';
@@ -0,0 +1,12 @@
+-- The outcome of replaying the event log a machine submitted with a
+-- report against the PCR values it quoted. Event logs stored before
+-- replays were recorded have no outcome.
+CREATE TYPE measurement_event_log_replay AS enum (
+    'matched',
+    'mismatched',
+    'failed'
+);
+
+ALTER TABLE measurement_reports_event_logs
+    ADD COLUMN replay_status measurement_event_log_replay,
+    ADD COLUMN replay_message text;
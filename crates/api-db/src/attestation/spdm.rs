@@ -16,6 +16,7 @@
  */
 
 use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
 use config_version::ConfigVersion;
 use itertools::Itertools;
 use libredfish::model::component_integrity::{CaCertificate, Evidence};
@@ -219,17 +220,76 @@ pub async fn update_attestation_status(
     Ok(())
 }
 
+/// Records the outcome of a completed attestation, which is how fresh the attestation of the
+/// machine is.
+pub async fn update_attestation_result(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    passed: bool,
+) -> DatabaseResult<()> {
+    let current_time = chrono::Utc::now();
+    let query = r#"UPDATE spdm_machine_attestation
+        SET last_attested_at = $2, last_attestation_passed = $3
+        WHERE machine_id = $1
+        RETURNING *"#;
+    let _res: SpdmMachineAttestation = sqlx::query_as(query)
+        .bind(machine_id)
+        .bind(current_time)
+        .bind(passed)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// Requests a new attestation of a machine whose last attestation is completed, the same way
+/// `TriggerMachineAttestation` does.
+/// Returns false, and requests nothing, for machines for which attestation was never requested,
+/// is not supported or is still running.
+pub async fn request_reattestation(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> DatabaseResult<bool> {
+    let query = "SELECT * FROM spdm_machine_attestation WHERE machine_id = $1";
+    let machine: Option<SpdmMachineAttestation> = sqlx::query_as(query)
+        .bind(machine_id)
+        .fetch_optional(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let Some(machine) = machine else {
+        return Ok(false);
+    };
+    if machine.attestation_status != SpdmAttestationStatus::Completed {
+        return Ok(false);
+    }
+
+    let attestation_request = SpdmMachineAttestation {
+        requested_at: chrono::Utc::now(),
+        state: AttestationState::CheckIfAttestationSupported,
+        state_version: machine.state_version.increment(),
+        ..machine
+    };
+    insert_or_update_machine_attestation_request(txn, &attestation_request).await?;
+
+    Ok(true)
+}
+
 // This function has to find two sets of ids,
 // 1. The machine_ids for which the attestation has to be started. Here device_ids will be None.
 //      a. machine_ids where status is `not-started`.
 //      b. machine_ids where cancellation is not triggered.
 //      c. machine_ids where device/component fetching is pending.
+//      d. machine_ids where attestation is completed but older than `reattest_before`. The
+//         state handler requests re-attestation for them.
 // 2. The (machine_ids, device_ids) pair for which:
 //      a. machine_ids not in above group (to leave devices for which re-attestation is triggered)
 //      b. Attestation is not yet completed. (machine.status != completed)
 //      c. Cancellation request is not received.
 pub async fn find_machine_ids_for_attestation(
     txn: &mut PgConnection,
+    reattest_before: Option<DateTime<Utc>>,
 ) -> Result<Vec<SpdmObjectId>, DatabaseError> {
     let state = AttestationState::FetchAttestationTargetsAndUpdateDb;
     let query = r#"
@@ -243,6 +303,14 @@ pub async fn find_machine_ids_for_attestation(
                 m.attestation_status = 'not_started'
                 OR
                 m.state = $1
+                OR
+                (
+                    $2::timestamptz IS NOT NULL
+                    AND
+                    m.attestation_status = 'completed'
+                    AND
+                    (m.last_attested_at IS NULL OR m.last_attested_at < $2)
+                )
             ) 
             AND 
             (   
@@ -255,6 +323,7 @@ pub async fn find_machine_ids_for_attestation(
     // ids for which attestation has to be (re)started.
     let res: Vec<MachineId> = sqlx::query_as(query)
         .bind(sqlx::types::Json(state))
+        .bind(reattest_before)
         .fetch_all(&mut *txn)
         .await
         .map_err(|e| DatabaseError::query(query, e))?;
@@ -0,0 +1,86 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use model::audit_log::{AuditLogEntry, AuditLogFilter, AuditLogOutcome, NewAuditLogEntry};
+use sqlx::PgConnection;
+
+use crate::DatabaseError;
+
+/// Records a new entry in the audit log
+pub async fn persist(
+    txn: &mut PgConnection,
+    entry: &NewAuditLogEntry,
+) -> Result<AuditLogEntry, DatabaseError> {
+    let query = "INSERT INTO audit_log (principal, rpc_name, object_ids, request, outcome, error)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, timestamp, principal, rpc_name, object_ids, request, outcome, error";
+    sqlx::query_as::<_, AuditLogEntry>(query)
+        .bind(&entry.principal)
+        .bind(&entry.rpc_name)
+        .bind(&entry.object_ids)
+        .bind(&entry.request)
+        .bind(entry.outcome)
+        .bind(&entry.error)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records the outcome of the call of a pending entry
+pub async fn update_outcome(
+    txn: &mut PgConnection,
+    id: i64,
+    outcome: AuditLogOutcome,
+    error: Option<&str>,
+) -> Result<(), DatabaseError> {
+    let query = "UPDATE audit_log SET outcome = $2, error = $3 WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .bind(outcome)
+        .bind(error)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Retrieves up to `limit` entries which match the filter, starting with the
+/// most recent entry
+pub async fn find(
+    txn: &mut PgConnection,
+    filter: &AuditLogFilter,
+    limit: i64,
+) -> Result<Vec<AuditLogEntry>, DatabaseError> {
+    let query = "SELECT id, timestamp, principal, rpc_name, object_ids, request, outcome, error
+        FROM audit_log
+        WHERE ($1::varchar IS NULL OR principal = $1)
+          AND ($2::varchar IS NULL OR $2 = ANY(object_ids))
+          AND ($3::varchar IS NULL OR rpc_name = $3)
+          AND ($4::timestamptz IS NULL OR timestamp >= $4)
+          AND ($5::timestamptz IS NULL OR timestamp <= $5)
+        ORDER BY id DESC
+        LIMIT $6";
+    sqlx::query_as::<_, AuditLogEntry>(query)
+        .bind(&filter.principal)
+        .bind(&filter.object_id)
+        .bind(&filter.rpc_name)
+        .bind(filter.start_time)
+        .bind(filter.end_time)
+        .bind(limit)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
@@ -21,6 +21,7 @@ use carbide_uuid::domain::DomainId;
 use chrono::{DateTime, Utc};
 use hickory_proto::rr::Name;
 use model::dns::{Domain, NewDomain, SoaSnapshot};
+use model::tenant::TenantOrganizationId;
 use sqlx::{FromRow, PgConnection};
 
 use super::super::{ColumnInfo, FilterableQueryBuilder, ObjectColumnFilter};
@@ -191,6 +192,36 @@ pub async fn find_by_uuid(
         .map(|f| f.first().cloned())
 }
 
+/// Returns whether the domain belongs to the tenant.
+///
+/// Tenants don't own domains directly. A domain is considered to be owned by a
+/// tenant if it is the subdomain of a network segment in one of the tenant's VPCs,
+/// and of no other segment. Domains shared with other tenants or with segments
+/// outside of a tenant VPC (e.g. the admin and underlay segments) are owned by nobody.
+pub async fn is_owned_by_tenant(
+    txn: impl DbReader<'_>,
+    domain_id: DomainId,
+    tenant_organization_id: &TenantOrganizationId,
+) -> Result<bool, DatabaseError> {
+    let query = "SELECT EXISTS (
+            SELECT 1 FROM network_segments ns
+            JOIN vpcs v ON v.id = ns.vpc_id
+            WHERE ns.subdomain_id = $1 AND v.organization_id = $2
+                AND ns.deleted IS NULL AND v.deleted IS NULL
+        ) AND NOT EXISTS (
+            SELECT 1 FROM network_segments ns
+            LEFT JOIN vpcs v ON v.id = ns.vpc_id AND v.deleted IS NULL
+            WHERE ns.subdomain_id = $1 AND ns.deleted IS NULL
+                AND (v.organization_id IS NULL OR v.organization_id <> $2)
+        )";
+    sqlx::query_scalar(query)
+        .bind(domain_id)
+        .bind(tenant_organization_id.as_str())
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 pub async fn delete(value: Domain, txn: &mut PgConnection) -> Result<Domain, DatabaseError> {
     let query = "UPDATE domains SET updated=NOW(), deleted=NOW() WHERE id=$1 RETURNING *";
     sqlx::query_as::<_, DbDomain>(query)
@@ -18,6 +18,7 @@ use std::net::IpAddr;
 
 use carbide_uuid::domain::DomainId;
 use dns_record::SoaRecord;
+use model::dns::tenant_record::DEFAULT_TTL;
 use sqlx::postgres::PgRow;
 use sqlx::{Error, FromRow, Row};
 
@@ -111,6 +112,100 @@ pub async fn find_record(
 
     Ok(result)
 }
+/// Suffix of IPv4 reverse zones
+const IPV4_REVERSE_SUFFIX: &str = ".in-addr.arpa";
+
+/// Suffix of IPv6 reverse zones
+const IPV6_REVERSE_SUFFIX: &str = ".ip6.arpa";
+
+/// Parses the name of a PTR query (e.g. `4.3.2.10.in-addr.arpa.`) into the
+/// address it refers to. Returns `None` for names which aren't a complete
+/// reverse name for a single address.
+pub fn parse_reverse_name(query_name: &str) -> Option<IpAddr> {
+    let name = crate::dns::normalize_domain(query_name);
+
+    if let Some(labels) = name.strip_suffix(IPV4_REVERSE_SUFFIX) {
+        let mut octets = labels
+            .split('.')
+            .map(|label| label.parse::<u8>().ok())
+            .collect::<Option<Vec<u8>>>()?;
+        octets.reverse();
+        let octets: [u8; 4] = octets.try_into().ok()?;
+        return Some(IpAddr::from(octets));
+    }
+
+    if let Some(labels) = name.strip_suffix(IPV6_REVERSE_SUFFIX) {
+        let nibbles = labels
+            .split('.')
+            .map(|label| match label.len() {
+                1 => u8::from_str_radix(label, 16).ok(),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()?;
+        if nibbles.len() != 32 {
+            return None;
+        }
+        let mut octets = [0u8; 16];
+        for (i, pair) in nibbles.rchunks(2).enumerate() {
+            octets[i] = (pair[1] << 4) | pair[0];
+        }
+        return Some(IpAddr::from(octets));
+    }
+
+    None
+}
+
+/// Generates the PTR records for a reverse name.
+///
+/// Every address that is allocated to an instance or a BMC resolves to the forward
+/// names of the address. Records are only returned if a (non-deleted) domain for a
+/// reverse zone containing the name exists, since we are not authoritative otherwise.
+pub async fn find_ptr_records(
+    txn: impl DbReader<'_>,
+    query_name: &str,
+) -> Result<Vec<DbResourceRecord>, DatabaseError> {
+    let Some(address) = parse_reverse_name(query_name) else {
+        return Ok(Vec::new());
+    };
+    let zone_name = crate::dns::normalize_domain(query_name);
+
+    let query = r#"
+        WITH zone AS (
+            SELECT id FROM domains
+            WHERE deleted IS NULL AND ($1 = name OR $1 LIKE '%.' || name)
+            ORDER BY length(name) DESC
+            LIMIT 1
+        )
+        SELECT DISTINCT r.q_name, zone.id AS domain_id
+        FROM zone, (
+            SELECT q_name, resource_record FROM dns_records_instance
+            UNION ALL
+            SELECT q_name, resource_record FROM dns_records_bmc_host_id
+            UNION ALL
+            SELECT q_name, resource_record FROM dns_records_bmc_dpu_id
+        ) r
+        WHERE host(r.resource_record)::inet = $2::inet
+        ORDER BY r.q_name"#;
+
+    let targets: Vec<(String, DomainId)> = sqlx::query_as(query)
+        .bind(&zone_name)
+        .bind(address)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(targets
+        .into_iter()
+        .map(|(target, domain_id)| DbResourceRecord {
+            q_type: dns_record::constants::DNS_TYPE_PTR.to_string(),
+            ttl: DEFAULT_TTL as i32,
+            q_name: format!("{zone_name}."),
+            record: target,
+            domain_id,
+        })
+        .collect())
+}
+
 pub async fn get_all_records(
     txn: impl DbReader<'_>,
     query_name: &str,
@@ -131,3 +226,35 @@ pub async fn get_all_records(
         .await
         .map_err(|e| DatabaseError::query(query, e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reverse_name() {
+        assert_eq!(
+            parse_reverse_name("4.3.2.10.in-addr.arpa."),
+            Some("10.2.3.4".parse().unwrap())
+        );
+        assert_eq!(
+            parse_reverse_name(
+                "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.d.f.IP6.ARPA"
+            ),
+            Some("fd00::1".parse().unwrap())
+        );
+        assert_eq!(
+            parse_reverse_name(
+                "b.a.9.8.7.6.5.0.4.0.0.0.3.0.0.0.2.0.0.0.1.0.0.0.0.0.0.0.1.2.3.4.ip6.arpa"
+            ),
+            Some("4321:0:1:2:3:4:567:89ab".parse().unwrap())
+        );
+
+        // Zones and partial names don't refer to a single address
+        assert_eq!(parse_reverse_name("2.10.in-addr.arpa."), None);
+        assert_eq!(parse_reverse_name("in-addr.arpa."), None);
+        assert_eq!(parse_reverse_name("d.f.ip6.arpa."), None);
+        assert_eq!(parse_reverse_name("256.3.2.10.in-addr.arpa."), None);
+        assert_eq!(parse_reverse_name("www.example.com."), None);
+    }
+}
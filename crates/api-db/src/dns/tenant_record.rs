@@ -0,0 +1,488 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use carbide_uuid::domain::{DomainId, TenantDnsRecordId};
+use dns_record::DnsResourceRecordType;
+use hickory_proto::rr::Name;
+use model::dns::tenant_record::TENANT_RECORD_TYPES;
+use model::dns::{NewTenantDnsRecord, TenantDnsRecord};
+use model::tenant::TenantOrganizationId;
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// Longest TTL a tenant may configure (one week)
+const MAX_TTL: u32 = 604800;
+
+/// Longest TXT string which fits into a single character-string
+const MAX_TXT_LENGTH: usize = 255;
+
+fn invalid(message: String) -> DatabaseError {
+    DatabaseError::InvalidArgument(message)
+}
+
+/// Parses a DNS name and returns it lowercased and fully qualified
+fn normalize_name(name: &str) -> Result<String, DatabaseError> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err(invalid("DNS name must not be empty".to_string()));
+    }
+    let parsed = Name::from_str(&name).map_err(|_| invalid(format!("invalid DNS name: {name}")))?;
+    let mut name = parsed.to_ascii();
+    if !name.ends_with('.') {
+        name.push('.');
+    }
+    Ok(name)
+}
+
+/// Validates the name of a record and returns it in the form stored in the database
+fn validate_name(
+    domain_name: &str,
+    name: &str,
+    q_type: DnsResourceRecordType,
+) -> Result<String, DatabaseError> {
+    let q_name = normalize_name(name)?;
+    let zone = format!("{}.", crate::dns::normalize_domain(domain_name));
+
+    if q_name != zone && !q_name.ends_with(&format!(".{zone}")) {
+        return Err(invalid(format!("{q_name} is not part of domain {zone}")));
+    }
+    if q_type == DnsResourceRecordType::CNAME && q_name == zone {
+        return Err(invalid(format!(
+            "CNAME records are not allowed at the domain apex {zone}"
+        )));
+    }
+    if q_type == DnsResourceRecordType::SRV {
+        // RFC 2782: _service._proto.name
+        let mut labels = q_name.split('.');
+        let service = labels.next().unwrap_or_default();
+        let proto = labels.next().unwrap_or_default();
+        if !service.starts_with('_') || !proto.starts_with('_') {
+            return Err(invalid(format!(
+                "SRV record name {q_name} must have the form _service._proto.name"
+            )));
+        }
+    }
+
+    Ok(q_name)
+}
+
+/// Validates the data of a record and returns it in the form stored in the database
+fn validate_content(q_type: DnsResourceRecordType, content: &str) -> Result<String, DatabaseError> {
+    match q_type {
+        DnsResourceRecordType::A => content
+            .trim()
+            .parse::<Ipv4Addr>()
+            .map(|ip| ip.to_string())
+            .map_err(|_| invalid(format!("invalid IPv4 address: {content}"))),
+        DnsResourceRecordType::AAAA => content
+            .trim()
+            .parse::<Ipv6Addr>()
+            .map(|ip| ip.to_string())
+            .map_err(|_| invalid(format!("invalid IPv6 address: {content}"))),
+        DnsResourceRecordType::CNAME => normalize_name(content),
+        DnsResourceRecordType::TXT => {
+            if content.is_empty() || content.len() > MAX_TXT_LENGTH {
+                return Err(invalid(format!(
+                    "TXT data must be between 1 and {MAX_TXT_LENGTH} bytes long"
+                )));
+            }
+            if content.chars().any(char::is_control) {
+                return Err(invalid(
+                    "TXT data must not contain control characters".to_string(),
+                ));
+            }
+            Ok(content.to_string())
+        }
+        DnsResourceRecordType::SRV => {
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [priority, weight, port, target] = fields[..] else {
+                return Err(invalid(format!(
+                    "SRV data must have the form \"<priority> <weight> <port> <target>\": {content}"
+                )));
+            };
+            for (field, value) in [("priority", priority), ("weight", weight), ("port", port)] {
+                value
+                    .parse::<u16>()
+                    .map_err(|_| invalid(format!("invalid SRV {field}: {value}")))?;
+            }
+            Ok(format!(
+                "{priority} {weight} {port} {}",
+                normalize_name(target)?
+            ))
+        }
+        _ => Err(invalid(format!(
+            "record type {q_type} can not be managed by tenants"
+        ))),
+    }
+}
+
+fn validate_ttl(ttl: Option<u32>) -> Result<(), DatabaseError> {
+    match ttl {
+        Some(ttl) if ttl == 0 || ttl > MAX_TTL => Err(invalid(format!(
+            "TTL must be between 1 and {MAX_TTL} seconds"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Validates a new record against the domain it is added to and normalizes
+/// its name and content
+pub fn validate(
+    domain_name: &str,
+    mut value: NewTenantDnsRecord,
+) -> Result<NewTenantDnsRecord, DatabaseError> {
+    if !TENANT_RECORD_TYPES.contains(&value.q_type) {
+        return Err(invalid(format!(
+            "record type {} can not be managed by tenants",
+            value.q_type
+        )));
+    }
+    value.q_name = validate_name(domain_name, &value.q_name, value.q_type)?;
+    value.content = validate_content(value.q_type, &value.content)?;
+    validate_ttl(value.ttl)?;
+    Ok(value)
+}
+
+/// Rejects records whose name is already served by anyone other than the
+/// tenant: site-managed names from `dns_records`, records of other tenants and
+/// names inside a more specific domain. Within the tenant's own records, a name
+/// holding a CNAME can't hold anything else, and a CNAME can't be added to a
+/// name which already holds other records.
+async fn check_name_conflict(
+    txn: &mut PgConnection,
+    value: &NewTenantDnsRecord,
+) -> DatabaseResult<()> {
+    let query = "SELECT q_type, tenant_organization_id = $2 AS owned
+            FROM tenant_dns_records WHERE q_name = $1
+        UNION ALL
+        SELECT q_type::text, false FROM dns_records WHERE lower(q_name) = $1";
+    let existing: Vec<(String, bool)> = sqlx::query_as(query)
+        .bind(&value.q_name)
+        .bind(value.tenant_organization_id.as_str())
+        .fetch_all(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let is_cname = value.q_type == DnsResourceRecordType::CNAME;
+    if existing
+        .iter()
+        .any(|(t, owned)| !owned || is_cname || t == dns_record::constants::DNS_TYPE_CNAME)
+    {
+        return Err(DatabaseError::AlreadyFoundError {
+            kind: "dns_record",
+            id: value.q_name.clone(),
+        });
+    }
+
+    // The name is already known to be inside the record's own domain, so any
+    // longer domain which is a suffix of it is a more specific (child) domain
+    let query = "SELECT name FROM domains
+        WHERE id <> $2 AND deleted IS NULL
+            AND length(name) > (SELECT length(name) FROM domains WHERE id = $2)
+            AND ($1 = lower(name) || '.' OR right($1, length(name) + 2) = '.' || lower(name) || '.')
+        LIMIT 1";
+    let child_domain: Option<String> = sqlx::query_scalar(query)
+        .bind(&value.q_name)
+        .bind(value.domain_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    if let Some(child_domain) = child_domain {
+        return Err(invalid(format!(
+            "{} is part of domain {child_domain}",
+            value.q_name
+        )));
+    }
+    Ok(())
+}
+
+/// Stores a new tenant record after validating it against its domain
+pub async fn persist(
+    txn: &mut PgConnection,
+    domain_name: &str,
+    value: NewTenantDnsRecord,
+) -> DatabaseResult<TenantDnsRecord> {
+    let value = validate(domain_name, value)?;
+    check_name_conflict(txn, &value).await?;
+
+    let query = "INSERT INTO tenant_dns_records
+            (domain_id, tenant_organization_id, q_name, q_type, content, ttl)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *";
+    match sqlx::query_as::<_, TenantDnsRecord>(query)
+        .bind(value.domain_id)
+        .bind(value.tenant_organization_id.as_str())
+        .bind(&value.q_name)
+        .bind(value.q_type.to_string())
+        .bind(&value.content)
+        .bind(value.ttl.map(|ttl| ttl as i32))
+        .fetch_one(txn)
+        .await
+    {
+        Ok(record) => Ok(record),
+        Err(sqlx::Error::Database(db_err))
+            if db_err.is_unique_violation()
+                && db_err.constraint() == Some("tenant_dns_records_unique_content") =>
+        {
+            Err(DatabaseError::AlreadyFoundError {
+                kind: "dns_record",
+                id: format!("{} {} {}", value.q_name, value.q_type, value.content),
+            })
+        }
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+/// Updates the content and TTL of an existing record
+pub async fn update(
+    txn: &mut PgConnection,
+    id: TenantDnsRecordId,
+    q_type: DnsResourceRecordType,
+    content: &str,
+    ttl: Option<u32>,
+) -> DatabaseResult<TenantDnsRecord> {
+    let content = validate_content(q_type, content)?;
+    validate_ttl(ttl)?;
+
+    let query = "UPDATE tenant_dns_records SET content = $1, ttl = $2, updated = NOW()
+            WHERE id = $3 RETURNING *";
+    sqlx::query_as::<_, TenantDnsRecord>(query)
+        .bind(&content)
+        .bind(ttl.map(|ttl| ttl as i32))
+        .bind(id)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err)
+                if db_err.is_unique_violation()
+                    && db_err.constraint() == Some("tenant_dns_records_unique_content") =>
+            {
+                DatabaseError::AlreadyFoundError {
+                    kind: "dns_record",
+                    id: format!("{q_type} {content}"),
+                }
+            }
+            e => DatabaseError::query(query, e),
+        })
+}
+
+pub async fn delete(txn: &mut PgConnection, id: TenantDnsRecordId) -> DatabaseResult<()> {
+    let query = "DELETE FROM tenant_dns_records WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+pub async fn find_by_id(
+    txn: impl DbReader<'_>,
+    id: TenantDnsRecordId,
+) -> DatabaseResult<Option<TenantDnsRecord>> {
+    let query = "SELECT * FROM tenant_dns_records WHERE id = $1";
+    sqlx::query_as::<_, TenantDnsRecord>(query)
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Finds all records of a tenant, optionally limited to a single domain
+pub async fn find_by_tenant(
+    txn: impl DbReader<'_>,
+    tenant_organization_id: &TenantOrganizationId,
+    domain_id: Option<DomainId>,
+) -> DatabaseResult<Vec<TenantDnsRecord>> {
+    let query = "SELECT * FROM tenant_dns_records
+            WHERE tenant_organization_id = $1 AND ($2::uuid IS NULL OR domain_id = $2)
+            ORDER BY q_name, q_type, content";
+    sqlx::query_as::<_, TenantDnsRecord>(query)
+        .bind(tenant_organization_id.as_str())
+        .bind(domain_id)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Finds the records for a fully qualified name in domains which weren't deleted
+pub async fn find_by_q_name(
+    txn: impl DbReader<'_>,
+    q_name: &str,
+) -> DatabaseResult<Vec<TenantDnsRecord>> {
+    let query = "SELECT r.* FROM tenant_dns_records r
+            JOIN domains d ON d.id = r.domain_id
+            WHERE r.q_name = $1 AND d.deleted IS NULL";
+    sqlx::query_as::<_, TenantDnsRecord>(query)
+        .bind(q_name)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Finds all records of a domain which wasn't deleted
+pub async fn find_by_domain_name(
+    txn: impl DbReader<'_>,
+    domain_name: &str,
+) -> DatabaseResult<Vec<TenantDnsRecord>> {
+    let domain_name = crate::dns::normalize_domain(domain_name);
+    let query = "SELECT r.* FROM tenant_dns_records r
+            JOIN domains d ON d.id = r.domain_id
+            WHERE d.name = $1 AND d.deleted IS NULL";
+    sqlx::query_as::<_, TenantDnsRecord>(query)
+        .bind(domain_name)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_record(name: &str, q_type: DnsResourceRecordType, content: &str) -> NewTenantDnsRecord {
+        NewTenantDnsRecord {
+            domain_id: DomainId::new(),
+            tenant_organization_id: "tenant-a".parse().unwrap(),
+            q_name: name.to_string(),
+            q_type,
+            content: content.to_string(),
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_normalizes_records() {
+        let record = validate(
+            "tenant.example.com",
+            new_record(
+                "WWW.Tenant.example.com",
+                DnsResourceRecordType::CNAME,
+                "Web.tenant.example.com",
+            ),
+        )
+        .unwrap();
+        assert_eq!(record.q_name, "www.tenant.example.com.");
+        assert_eq!(record.content, "web.tenant.example.com.");
+
+        let record = validate(
+            "tenant.example.com",
+            new_record(
+                "_http._tcp.tenant.example.com.",
+                DnsResourceRecordType::SRV,
+                "10 5  8080 web.tenant.example.com",
+            ),
+        )
+        .unwrap();
+        assert_eq!(record.content, "10 5 8080 web.tenant.example.com.");
+
+        let record = validate(
+            "tenant.example.com",
+            new_record(
+                "tenant.example.com",
+                DnsResourceRecordType::AAAA,
+                "fd00:0::1",
+            ),
+        )
+        .unwrap();
+        assert_eq!(record.q_name, "tenant.example.com.");
+        assert_eq!(record.content, "fd00::1");
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_records() {
+        let invalid_records = [
+            // Outside of the domain
+            new_record("www.other.com", DnsResourceRecordType::A, "10.0.0.1"),
+            new_record(
+                "eviltenant.example.com",
+                DnsResourceRecordType::A,
+                "10.0.0.1",
+            ),
+            // CNAME at the apex
+            new_record(
+                "tenant.example.com",
+                DnsResourceRecordType::CNAME,
+                "www.other.com",
+            ),
+            // Record types managed by the site
+            new_record(
+                "tenant.example.com",
+                DnsResourceRecordType::PTR,
+                "www.other.com",
+            ),
+            new_record(
+                "tenant.example.com",
+                DnsResourceRecordType::NS,
+                "ns.other.com",
+            ),
+            // Bad content
+            new_record(
+                "www.tenant.example.com",
+                DnsResourceRecordType::A,
+                "fd00::1",
+            ),
+            new_record(
+                "www.tenant.example.com",
+                DnsResourceRecordType::AAAA,
+                "10.0.0.1",
+            ),
+            new_record("www.tenant.example.com", DnsResourceRecordType::TXT, ""),
+            new_record(
+                "www.tenant.example.com",
+                DnsResourceRecordType::TXT,
+                "line\nbreak",
+            ),
+            new_record(
+                "_http._tcp.tenant.example.com",
+                DnsResourceRecordType::SRV,
+                "10 5 web",
+            ),
+            new_record(
+                "_http._tcp.tenant.example.com",
+                DnsResourceRecordType::SRV,
+                "10 5 70000 web",
+            ),
+            // SRV without service and protocol labels
+            new_record(
+                "web.tenant.example.com",
+                DnsResourceRecordType::SRV,
+                "10 5 80 web",
+            ),
+        ];
+        for record in invalid_records {
+            let description = format!("{record:?}");
+            assert!(
+                validate("tenant.example.com", record).is_err(),
+                "{description} should be rejected"
+            );
+        }
+
+        let mut record = new_record(
+            "www.tenant.example.com",
+            DnsResourceRecordType::A,
+            "10.0.0.1",
+        );
+        record.ttl = Some(0);
+        assert!(validate("tenant.example.com", record).is_err());
+    }
+}
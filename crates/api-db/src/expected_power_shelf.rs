@@ -25,6 +25,7 @@ use model::expected_power_shelf::{ExpectedPowerShelf, LinkedExpectedPowerShelf};
 use model::metadata::Metadata;
 use sqlx::PgConnection;
 
+use crate::db_read::DbReader;
 use crate::{DatabaseError, DatabaseResult};
 
 const SQL_VIOLATION_DUPLICATE_MAC: &str = "expected_power_shelves_bmc_mac_address_key";
@@ -41,6 +42,18 @@ pub async fn find_by_bmc_mac_address(
         .map_err(|err| DatabaseError::query(sql, err))
 }
 
+pub async fn find_by_serial_number(
+    txn: impl DbReader<'_>,
+    serial_number: &str,
+) -> DatabaseResult<Option<ExpectedPowerShelf>> {
+    let sql = "SELECT * FROM expected_power_shelves WHERE serial_number=$1";
+    sqlx::query_as(sql)
+        .bind(serial_number)
+        .fetch_optional(txn)
+        .await
+        .map_err(|err| DatabaseError::query(sql, err))
+}
+
 pub async fn find_many_by_bmc_mac_address(
     txn: &mut PgConnection,
     bmc_mac_addresses: &[MacAddress],
@@ -24,6 +24,7 @@ use model::expected_switch::{ExpectedSwitch, LinkedExpectedSwitch};
 use model::metadata::Metadata;
 use sqlx::PgConnection;
 
+use crate::db_read::DbReader;
 use crate::{DatabaseError, DatabaseResult};
 
 const SQL_VIOLATION_DUPLICATE_MAC: &str = "expected_switches_bmc_mac_address_key";
@@ -39,6 +40,18 @@ pub async fn find_by_bmc_mac_address(
         .map_err(|err| DatabaseError::query(sql, err))
 }
 
+pub async fn find_by_serial_number(
+    txn: impl DbReader<'_>,
+    serial_number: &str,
+) -> Result<Option<ExpectedSwitch>, DatabaseError> {
+    let sql = "SELECT * FROM expected_switches WHERE serial_number=$1";
+    sqlx::query_as(sql)
+        .bind(serial_number)
+        .fetch_optional(txn)
+        .await
+        .map_err(|err| DatabaseError::query(sql, err))
+}
+
 pub async fn find_by_rack_id(
     txn: &mut PgConnection,
     rack_id: String,
@@ -51,6 +64,18 @@ pub async fn find_by_rack_id(
         .map_err(|err| DatabaseError::query(sql, err))
 }
 
+pub async fn find_all_by_rack_id(
+    txn: &mut PgConnection,
+    rack_id: String,
+) -> Result<Vec<ExpectedSwitch>, DatabaseError> {
+    let sql = "SELECT * FROM expected_switches WHERE rack_id=$1 ORDER BY bmc_mac_address";
+    sqlx::query_as(sql)
+        .bind(rack_id)
+        .fetch_all(txn)
+        .await
+        .map_err(|err| DatabaseError::query(sql, err))
+}
+
 pub async fn find_many_by_bmc_mac_address(
     txn: &mut PgConnection,
     bmc_mac_addresses: &[MacAddress],
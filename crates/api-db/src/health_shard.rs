@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use model::health_shard::{HealthCollectorState, HealthShardLease};
+use sqlx::PgConnection;
+
+use crate::DatabaseError;
+
+/// Creates or extends the membership lease of a carbide-hw-health replica.
+/// Expiry times are based on the clock of the database, so that the clocks of
+/// the replicas don't need to be in sync.
+pub async fn renew_lease(
+    txn: &mut PgConnection,
+    member_id: &str,
+    lease_duration: std::time::Duration,
+) -> Result<HealthShardLease, DatabaseError> {
+    let query = "INSERT INTO health_shard_leases (member_id, renewed_at, expires_at)
+        VALUES ($1, NOW(), NOW() + make_interval(secs => $2))
+        ON CONFLICT (member_id) DO UPDATE
+        SET renewed_at = EXCLUDED.renewed_at, expires_at = EXCLUDED.expires_at
+        RETURNING member_id, expires_at";
+    sqlx::query_as::<_, HealthShardLease>(query)
+        .bind(member_id)
+        .bind(lease_duration.as_secs_f64())
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Removes the lease of a replica
+pub async fn release_lease(txn: &mut PgConnection, member_id: &str) -> Result<(), DatabaseError> {
+    let query = "DELETE FROM health_shard_leases WHERE member_id = $1";
+    sqlx::query(query)
+        .bind(member_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Removes expired leases and returns all remaining ones, ordered by member ID
+pub async fn find_active_leases(
+    txn: &mut PgConnection,
+) -> Result<Vec<HealthShardLease>, DatabaseError> {
+    let query = "DELETE FROM health_shard_leases WHERE expires_at <= NOW()";
+    sqlx::query(query)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let query = "SELECT member_id, expires_at FROM health_shard_leases ORDER BY member_id";
+    sqlx::query_as::<_, HealthShardLease>(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Stores the state of a collector for an endpoint, replacing any earlier state
+pub async fn save_collector_state(
+    txn: &mut PgConnection,
+    endpoint_key: &str,
+    collector: &str,
+    state: &serde_json::Value,
+) -> Result<(), DatabaseError> {
+    let query = "INSERT INTO health_collector_state (endpoint_key, collector, state, updated_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (endpoint_key, collector) DO UPDATE
+        SET state = EXCLUDED.state, updated_at = EXCLUDED.updated_at";
+    sqlx::query(query)
+        .bind(endpoint_key)
+        .bind(collector)
+        .bind(sqlx::types::Json(state))
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+pub async fn find_collector_state(
+    txn: &mut PgConnection,
+    endpoint_key: &str,
+    collector: &str,
+) -> Result<Option<HealthCollectorState>, DatabaseError> {
+    let query = "SELECT endpoint_key, collector, state, updated_at
+        FROM health_collector_state
+        WHERE endpoint_key = $1 AND collector = $2";
+    sqlx::query_as::<_, HealthCollectorState>(query)
+        .bind(endpoint_key)
+        .bind(collector)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
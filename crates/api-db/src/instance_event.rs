@@ -0,0 +1,134 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::instance::InstanceId;
+use model::instance::event::{
+    InstanceEvent, InstanceEventPayload, InstanceEventResumeToken, InstanceEventScope,
+};
+use model::tenant::TenantOrganizationId;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::DatabaseError;
+
+/// A row of the `instance_events` table
+struct DbInstanceEvent(InstanceEvent);
+
+impl<'r> FromRow<'r, PgRow> for DbInstanceEvent {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let tenant_organization_id: String = row.try_get("tenant_organization_id")?;
+        Ok(DbInstanceEvent(InstanceEvent {
+            id: row.try_get("id")?,
+            xid: row.try_get("xid")?,
+            instance_id: row.try_get("instance_id")?,
+            tenant_organization_id: TenantOrganizationId::try_from(tenant_organization_id)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            timestamp: row.try_get("timestamp")?,
+            payload: row
+                .try_get::<sqlx::types::Json<InstanceEventPayload>, _>("event")?
+                .0,
+        }))
+    }
+}
+
+/// Records a new event for an instance
+pub async fn persist(
+    txn: &mut PgConnection,
+    instance_id: InstanceId,
+    tenant_organization_id: &TenantOrganizationId,
+    payload: &InstanceEventPayload,
+) -> Result<InstanceEvent, DatabaseError> {
+    let query = "INSERT INTO instance_events (instance_id, tenant_organization_id, event)
+        VALUES ($1, $2, $3)
+        RETURNING id, xid::text::bigint AS xid, instance_id, tenant_organization_id, event, timestamp";
+    sqlx::query_as::<_, DbInstanceEvent>(query)
+        .bind(instance_id)
+        .bind(tenant_organization_id.as_str())
+        .bind(sqlx::types::Json(payload))
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+        .map(|event| event.0)
+}
+
+/// Retrieves up to `limit` events in the given scope which had been recorded
+/// after the event identified by `after`, starting with the oldest event.
+///
+/// Only events of transactions which are older than all transactions that are
+/// still in progress are returned. Transactions which are still in progress
+/// could otherwise commit events that are positioned before the returned events.
+pub async fn find_after(
+    txn: &mut PgConnection,
+    scope: &InstanceEventScope,
+    after: InstanceEventResumeToken,
+    limit: i64,
+) -> Result<Vec<InstanceEvent>, DatabaseError> {
+    let result = match scope {
+        InstanceEventScope::Instance(instance_id) => {
+            let query = "SELECT id, xid::text::bigint AS xid, instance_id, tenant_organization_id, event, timestamp
+                FROM instance_events
+                WHERE instance_id=$1
+                    AND (xid, id) > ($2::text::xid8, $3)
+                    AND xid < pg_snapshot_xmin(pg_current_snapshot())
+                ORDER BY xid ASC, id ASC
+                LIMIT $4";
+            sqlx::query_as::<_, DbInstanceEvent>(query)
+                .bind(instance_id)
+                .bind(after.xid)
+                .bind(after.id)
+                .bind(limit)
+                .fetch_all(txn)
+                .await
+                .map_err(|e| DatabaseError::query(query, e))
+        }
+        InstanceEventScope::Tenant(tenant_organization_id) => {
+            let query = "SELECT id, xid::text::bigint AS xid, instance_id, tenant_organization_id, event, timestamp
+                FROM instance_events
+                WHERE tenant_organization_id=$1
+                    AND (xid, id) > ($2::text::xid8, $3)
+                    AND xid < pg_snapshot_xmin(pg_current_snapshot())
+                ORDER BY xid ASC, id ASC
+                LIMIT $4";
+            sqlx::query_as::<_, DbInstanceEvent>(query)
+                .bind(tenant_organization_id.as_str())
+                .bind(after.xid)
+                .bind(after.id)
+                .bind(limit)
+                .fetch_all(txn)
+                .await
+                .map_err(|e| DatabaseError::query(query, e))
+        }
+    }?;
+
+    Ok(result.into_iter().map(|event| event.0).collect())
+}
+
+/// Returns the resume token of the current position of the event stream.
+/// Streaming events after this token yields all events which are recorded in
+/// the future. Recently recorded events of transactions which completed after
+/// an older transaction that is still in progress are yielded as well.
+pub async fn latest_resume_token(
+    txn: &mut PgConnection,
+) -> Result<InstanceEventResumeToken, DatabaseError> {
+    // Events of transactions which are still in progress are positioned at or
+    // after the oldest of them
+    let query = "SELECT pg_snapshot_xmin(pg_current_snapshot())::text::bigint";
+    let (xid,): (i64,) = sqlx::query_as(query)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(InstanceEventResumeToken { xid, id: 0 })
+}
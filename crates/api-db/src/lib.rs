@@ -20,6 +20,7 @@
 #![allow(unknown_lints)]
 
 pub mod attestation;
+pub mod audit_log;
 pub mod bmc_metadata;
 pub mod carbide_version;
 pub mod db_read;
@@ -38,10 +39,12 @@ pub mod expected_switch;
 pub mod explored_endpoints;
 pub mod explored_managed_host;
 pub mod extension_service;
+pub mod health_shard;
 pub mod host_machine_update;
 pub mod ib_partition;
 pub mod instance;
 pub mod instance_address;
+pub mod instance_event;
 pub mod instance_network_config;
 pub mod instance_type;
 pub mod ip_allocator;
@@ -59,6 +62,7 @@ pub mod machine_validation_suites;
 pub mod managed_host;
 pub mod measured_boot;
 pub mod migrations;
+pub mod mlxconfig_profile;
 pub mod network_devices;
 pub mod network_prefix;
 pub mod network_security_group;
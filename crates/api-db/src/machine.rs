@@ -937,6 +937,25 @@ pub async fn update_log_parser_health_report(
     Ok(())
 }
 
+pub async fn update_console_health_report(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    health_report: &HealthReport,
+) -> Result<(), DatabaseError> {
+    let query = String::from(
+        "UPDATE machines SET console_health_report = $1::json WHERE id = $2
+            RETURNING id",
+    );
+    let _id: (MachineId,) = sqlx::query_as(&query)
+        .bind(sqlx::types::Json(&health_report))
+        .bind(machine_id)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::new("update health report", e))?;
+
+    Ok(())
+}
+
 pub async fn update_machine_validation_health_report(
     txn: &mut PgConnection,
     machine_id: &MachineId,
@@ -243,6 +243,24 @@ pub async fn find_by_machine_ids(
     )
 }
 
+pub async fn find_by_switch_id(
+    txn: impl DbReader<'_>,
+    switch_id: &SwitchId,
+) -> Result<Vec<MachineInterfaceSnapshot>, DatabaseError> {
+    find_by(txn, ObjectColumnFilter::One(SwitchIdColumn, switch_id)).await
+}
+
+pub async fn find_by_power_shelf_id(
+    txn: impl DbReader<'_>,
+    power_shelf_id: &PowerShelfId,
+) -> Result<Vec<MachineInterfaceSnapshot>, DatabaseError> {
+    find_by(
+        txn,
+        ObjectColumnFilter::One(PowerShelfIdColumn, power_shelf_id),
+    )
+    .await
+}
+
 pub async fn count_by_segment_id(
     txn: &mut PgConnection,
     segment_id: &NetworkSegmentId,
@@ -655,7 +673,7 @@ fn address_to_hostname(address: &IpAddr) -> DatabaseResult<String> {
 }
 
 async fn find_by<'a, C: ColumnInfo<'a, TableType = MachineInterfaceSnapshot>>(
-    txn: &mut PgConnection,
+    txn: impl DbReader<'_>,
     filter: ObjectColumnFilter<'a, C>,
 ) -> Result<Vec<MachineInterfaceSnapshot>, DatabaseError> {
     let mut query = FilterableQueryBuilder::new(MACHINE_INTERFACE_SNAPSHOT_QUERY)
@@ -36,7 +36,8 @@ use sqlx::{PgConnection, PgTransaction};
 use crate::db_read::DbReader;
 use crate::measured_boot::interface::bundle::{
     delete_bundle_for_id, delete_bundle_values_for_id, get_machines_for_bundle_id,
-    get_measurement_bundle_by_id, get_measurement_bundle_for_name, get_measurement_bundle_records,
+    get_measurement_bundle_by_id, get_measurement_bundle_event_log,
+    get_measurement_bundle_for_name, get_measurement_bundle_records,
     get_measurement_bundle_records_for_profile_id, get_measurement_bundle_values_for_bundle_id,
     insert_measurement_bundle_record, insert_measurement_bundle_value_records,
     rename_bundle_for_bundle_id, rename_bundle_for_bundle_name, update_state_for_bundle_id,
@@ -154,6 +155,16 @@ pub async fn from_name(
     }
 }
 
+/// get_event_log returns the raw TCG event log stored for
+/// `bundle_id`, if the bundle was created from a report that
+/// had one.
+pub async fn get_event_log(
+    txn: &mut PgConnection,
+    bundle_id: MeasurementBundleId,
+) -> DatabaseResult<Option<Vec<u8>>> {
+    get_measurement_bundle_event_log(txn, bundle_id).await
+}
+
 /// set_state_for_id sets the bundle state for
 /// the given bundle ID.
 pub async fn set_state_for_id(
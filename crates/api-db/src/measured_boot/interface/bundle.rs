@@ -387,3 +387,36 @@ pub async fn import_measurement_bundles_value(
         .await
         .map_err(|e| DatabaseError::new("import_measurement_bundles_value", e))
 }
+
+/// insert_measurement_bundle_event_log stores the raw TCG event log
+/// the bundle was created from, to be used as the baseline when diffing
+/// reports against this bundle.
+pub async fn insert_measurement_bundle_event_log(
+    txn: &mut PgConnection,
+    bundle_id: MeasurementBundleId,
+    event_log: &[u8],
+) -> Result<(), DatabaseError> {
+    let query = "insert into measurement_bundles_event_logs(bundle_id, event_log) values($1, $2)
+        on conflict (bundle_id) do update set event_log = excluded.event_log, ts = clock_timestamp()";
+    sqlx::query(query)
+        .bind(bundle_id)
+        .bind(event_log)
+        .execute(txn)
+        .await
+        .map(|_| ())
+        .map_err(|e| DatabaseError::new("insert_measurement_bundle_event_log", e))
+}
+
+/// get_measurement_bundle_event_log returns the raw TCG event log
+/// stored for the given `bundle_id`, if the bundle has one.
+pub async fn get_measurement_bundle_event_log(
+    txn: impl DbReader<'_>,
+    bundle_id: MeasurementBundleId,
+) -> Result<Option<Vec<u8>>, DatabaseError> {
+    let query = "select event_log from measurement_bundles_event_logs where bundle_id = $1";
+    sqlx::query_scalar(query)
+        .bind(bundle_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_measurement_bundle_event_log", e))
+}
@@ -20,8 +20,12 @@
  *  tables in the database, leveraging the report-specific record types.
 */
 
+use std::collections::HashMap;
+
 use carbide_uuid::machine::MachineId;
 use carbide_uuid::measured_boot::MeasurementReportId;
+use chrono::{DateTime, Utc};
+use measured_boot::eventlog::{EventLogReplay, EventLogReplayStatus};
 use measured_boot::pcr::PcrRegisterValue;
 use measured_boot::records::{MeasurementReportRecord, MeasurementReportValueRecord};
 use sqlx::{PgConnection, Postgres, QueryBuilder};
@@ -255,3 +259,99 @@ pub async fn get_all_measurement_report_value_records(
         .await
         .map_err(|e| e.with_op_name("get_all_measurement_report_value_records"))
 }
+
+/// insert_measurement_report_event_log stores the raw TCG event log
+/// that was submitted with a report, along with the outcome of
+/// replaying it, replacing any previously stored log if the report
+/// was re-used for identical values.
+pub async fn insert_measurement_report_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+    event_log: &[u8],
+    replay: &EventLogReplay,
+) -> Result<(), DatabaseError> {
+    let query = "insert into measurement_reports_event_logs(report_id, event_log, replay_status, replay_message)
+        values($1, $2, $3, $4)
+        on conflict (report_id) do update set event_log = excluded.event_log,
+            replay_status = excluded.replay_status, replay_message = excluded.replay_message,
+            ts = clock_timestamp()";
+    sqlx::query(query)
+        .bind(report_id)
+        .bind(event_log)
+        .bind(replay.status)
+        .bind(&replay.message)
+        .execute(txn)
+        .await
+        .map(|_| ())
+        .map_err(|e| DatabaseError::new("insert_measurement_report_event_log", e))
+}
+
+/// get_measurement_report_event_log_replay returns the outcome of
+/// replaying the event log stored for the given `report_id`, if one
+/// was submitted and replayed.
+pub async fn get_measurement_report_event_log_replay(
+    txn: impl DbReader<'_>,
+    report_id: MeasurementReportId,
+) -> Result<Option<EventLogReplay>, DatabaseError> {
+    let query = "select replay_status, replay_message from measurement_reports_event_logs
+        where report_id = $1 and replay_status is not null";
+    sqlx::query_as(query)
+        .bind(report_id)
+        .fetch_optional(txn)
+        .await
+        .map(|row| row.map(|(status, message)| EventLogReplay { status, message }))
+        .map_err(|e| DatabaseError::new("get_measurement_report_event_log_replay", e))
+}
+
+/// get_all_measurement_report_event_log_replays returns the outcome
+/// of replaying every stored event log, keyed by report.
+pub async fn get_all_measurement_report_event_log_replays(
+    txn: impl DbReader<'_>,
+) -> Result<HashMap<MeasurementReportId, EventLogReplay>, DatabaseError> {
+    let query =
+        "select report_id, replay_status, replay_message from measurement_reports_event_logs
+        where replay_status is not null";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map(
+            |rows: Vec<(MeasurementReportId, EventLogReplayStatus, Option<String>)>| {
+                rows.into_iter()
+                    .map(|(report_id, status, message)| {
+                        (report_id, EventLogReplay { status, message })
+                    })
+                    .collect()
+            },
+        )
+        .map_err(|e| DatabaseError::new("get_all_measurement_report_event_log_replays", e))
+}
+
+/// get_measurement_report_event_log returns the raw TCG event log
+/// stored for the given `report_id`, if one was submitted.
+pub async fn get_measurement_report_event_log(
+    txn: impl DbReader<'_>,
+    report_id: MeasurementReportId,
+) -> Result<Option<Vec<u8>>, DatabaseError> {
+    let query = "select event_log from measurement_reports_event_logs where report_id = $1";
+    sqlx::query_scalar(query)
+        .bind(report_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_measurement_report_event_log", e))
+}
+
+/// get_latest_measurement_report_tstamp returns when `machine_id` last
+/// submitted measurements, i.e. the timestamp of its most recent report.
+/// Resubmitting the same measurements refreshes the timestamp of the
+/// existing report, so this is also when the machine was last measured.
+pub async fn get_latest_measurement_report_tstamp(
+    txn: impl DbReader<'_>,
+    machine_id: MachineId,
+) -> Result<Option<DateTime<Utc>>, DatabaseError> {
+    let query = "select max(ts) from measurement_reports where machine_id = $1";
+    sqlx::query_scalar(query)
+        .bind(machine_id)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_latest_measurement_report_tstamp", e))
+}
@@ -24,7 +24,7 @@ use std::collections::HashMap;
 
 use carbide_uuid::DbTable;
 use carbide_uuid::machine::{MachineId, MachineType};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use measured_boot::journal::MeasurementJournal;
 use measured_boot::machine::CandidateMachine;
 use measured_boot::records::{MeasurementBundleState, MeasurementMachineState};
@@ -211,6 +211,36 @@ where
     Ok(res)
 }
 
+/// request_reattestation asks `machine_id` to submit new measurements,
+/// even if its latest measurement report is still considered fresh.
+pub async fn request_reattestation(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> DatabaseResult<()> {
+    let query = "insert into measured_boot_reattestation_requests (machine_id, requested_at) values ($1, now()) on conflict (machine_id) do update set requested_at = now()";
+    sqlx::query(query)
+        .bind(machine_id)
+        .execute(txn)
+        .await
+        .map(|_| ())
+        .map_err(|e| DatabaseError::new("request_reattestation", e))
+}
+
+/// get_reattestation_requested_at returns when new measurements were
+/// last requested from `machine_id` through request_reattestation.
+pub async fn get_reattestation_requested_at(
+    txn: impl DbReader<'_>,
+    machine_id: &MachineId,
+) -> DatabaseResult<Option<DateTime<Utc>>> {
+    let query =
+        "select requested_at from measured_boot_reattestation_requests where machine_id = $1";
+    sqlx::query_scalar(query)
+        .bind(machine_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_reattestation_requested_at", e))
+}
+
 /// machine_state_from_journal returns the computed machine
 /// state for a given journal record.
 pub fn machine_state_from_journal(journal: &Option<MeasurementJournal>) -> MeasurementMachineState {
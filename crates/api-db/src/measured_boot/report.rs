@@ -27,6 +27,7 @@ use carbide_uuid::measured_boot::{
     MeasurementBundleId, MeasurementReportId, MeasurementSystemProfileId, TrustedMachineId,
 };
 use measured_boot::bundle::MeasurementBundle;
+use measured_boot::eventlog::EventLogReplay;
 use measured_boot::journal::MeasurementJournal;
 use measured_boot::pcr::{PcrRegisterValue, PcrSet, parse_pcr_index_input};
 use measured_boot::records::{
@@ -40,8 +41,11 @@ use crate::db_read::DbReader;
 use crate::measured_boot::interface::common;
 use crate::measured_boot::interface::common::pcr_register_values_to_map;
 use crate::measured_boot::interface::report::{
-    delete_report_for_id, delete_report_values_for_id, get_measurement_report_record_by_id,
-    get_measurement_report_values_for_report_id, insert_measurement_report_record,
+    delete_report_for_id, delete_report_values_for_id,
+    get_all_measurement_report_event_log_replays, get_latest_measurement_report_tstamp,
+    get_measurement_report_event_log, get_measurement_report_event_log_replay,
+    get_measurement_report_record_by_id, get_measurement_report_values_for_report_id,
+    insert_measurement_report_event_log, insert_measurement_report_record,
     insert_measurement_report_value_records, update_report_tstamp, update_report_values_tstamp,
 };
 use crate::measured_boot::interface::site::{
@@ -56,7 +60,19 @@ pub async fn new(
     machine_id: MachineId,
     values: &[PcrRegisterValue],
 ) -> DatabaseResult<MeasurementReport> {
-    create_measurement_report(txn, machine_id, values).await
+    create_measurement_report(txn, machine_id, values, None).await
+}
+
+/// new_with_event_log creates a new measurement report, also storing
+/// the raw TCG event log the machine submitted alongside its quote,
+/// and the outcome of replaying it against the quoted values.
+pub async fn new_with_event_log(
+    txn: &mut PgTransaction<'_>,
+    machine_id: MachineId,
+    values: &[PcrRegisterValue],
+    event_log: Option<(&[u8], EventLogReplay)>,
+) -> DatabaseResult<MeasurementReport> {
+    create_measurement_report(txn, machine_id, values, event_log).await
 }
 
 pub async fn from_id(
@@ -66,6 +82,15 @@ pub async fn from_id(
     get_measurement_report_by_id(txn, report_id).await
 }
 
+/// get_event_log returns the raw TCG event log stored
+/// for `report_id`, if one was submitted with the report.
+pub async fn get_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+) -> DatabaseResult<Option<Vec<u8>>> {
+    get_measurement_report_event_log(txn, report_id).await
+}
+
 /// delete_for_id deletes a MeasurementReport and associated
 /// MeasurementReportValues, returning a fully populated instance of
 /// MeasurementReport of the data that was deleted for `report_id`.
@@ -73,6 +98,7 @@ pub async fn delete_for_id(
     txn: &mut PgConnection,
     report_id: MeasurementReportId,
 ) -> DatabaseResult<MeasurementReport> {
+    let event_log_replay = get_measurement_report_event_log_replay(&mut *txn, report_id).await?;
     let values = delete_report_values_for_id(txn, report_id).await?;
     match delete_report_for_id(txn, report_id).await? {
         Some(info) => Ok(MeasurementReport {
@@ -80,6 +106,7 @@ pub async fn delete_for_id(
             machine_id: info.machine_id,
             ts: info.ts,
             values,
+            event_log_replay,
         }),
         None => Err(DatabaseError::NotFoundError {
             kind: "MeasurementReport",
@@ -95,6 +122,15 @@ pub async fn get_all_for_machine_id(
     get_measurement_reports_for_machine_id(txn, machine_id).await
 }
 
+/// get_latest_tstamp_for_machine_id returns when `machine_id`
+/// last submitted a measurement report, if it ever did.
+pub async fn get_latest_tstamp_for_machine_id(
+    txn: impl DbReader<'_>,
+    machine_id: MachineId,
+) -> DatabaseResult<Option<chrono::DateTime<chrono::Utc>>> {
+    get_latest_measurement_report_tstamp(txn, machine_id).await
+}
+
 pub async fn get_all<DB>(txn: &mut DB) -> DatabaseResult<Vec<MeasurementReport>>
 where
     for<'db> &'db mut DB: DbReader<'db>,
@@ -119,11 +155,13 @@ pub async fn create_revoked_bundle(
 }
 
 /// create_measurement_report handles the work of creating a new
-/// measurement report as well as all associated value records.
+/// measurement report as well as all associated value records, and
+/// the raw event log and its replay, if one was provided.
 pub async fn create_measurement_report(
     txn: &mut PgTransaction<'_>,
     machine_id: MachineId,
     values: &[PcrRegisterValue],
+    event_log: Option<(&[u8], EventLogReplay)>,
 ) -> DatabaseResult<MeasurementReport> {
     // we check if a previous measurement report has the same values,
     // if true, we'll just update the timestamp on the previous report
@@ -146,11 +184,24 @@ pub async fn create_measurement_report(
             }
         };
 
+    // Store the event log before any auto-approval happens, so that
+    // a bundle created from this report can inherit it. A re-used
+    // report keeps the replay of the log it was last stored with.
+    let event_log_replay = match event_log {
+        Some((event_log, replay)) => {
+            insert_measurement_report_event_log(txn, info.report_id, event_log, &replay).await?;
+            Some(replay)
+        }
+        None if new_report_created => None,
+        None => get_measurement_report_event_log_replay(txn.as_mut(), info.report_id).await?,
+    };
+
     let report = MeasurementReport {
         report_id: info.report_id,
         machine_id: info.machine_id,
         ts: info.ts,
         values,
+        event_log_replay,
     };
 
     let journal_data =
@@ -209,7 +260,9 @@ where
     for<'db> &'db mut DB: DbReader<'db>,
 {
     let report_records: Vec<MeasurementReportRecord> = common::get_all_objects(&mut *txn).await?;
-    let mut report_values: Vec<MeasurementReportValueRecord> = common::get_all_objects(txn).await?;
+    let mut report_values: Vec<MeasurementReportValueRecord> =
+        common::get_all_objects(&mut *txn).await?;
+    let mut replays_by_report_id = get_all_measurement_report_event_log_replays(txn).await?;
 
     let mut values_by_report_id: HashMap<MeasurementReportId, Vec<MeasurementReportValueRecord>> =
         HashMap::new();
@@ -231,6 +284,7 @@ where
             machine_id: report_record.machine_id,
             ts: report_record.ts,
             values: values.to_vec(),
+            event_log_replay: replays_by_report_id.remove(&report_record.report_id),
         });
     }
     Ok(res)
@@ -245,11 +299,14 @@ pub async fn get_measurement_report_by_id(
     match get_measurement_report_record_by_id(txn, report_id).await? {
         Some(info) => {
             let values = get_measurement_report_values_for_report_id(txn, info.report_id).await?;
+            let event_log_replay =
+                get_measurement_report_event_log_replay(&mut *txn, info.report_id).await?;
             Ok(MeasurementReport {
                 report_id: info.report_id,
                 machine_id: info.machine_id,
                 ts: info.ts,
                 values,
+                event_log_replay,
             })
         }
         None => Err(DatabaseError::NotFoundError {
@@ -272,11 +329,14 @@ pub async fn get_measurement_reports_for_machine_id(
     for report_record in report_records.iter() {
         let values =
             get_measurement_report_values_for_report_id(txn, report_record.report_id).await?;
+        let event_log_replay =
+            get_measurement_report_event_log_replay(&mut *txn, report_record.report_id).await?;
         res.push(MeasurementReport {
             report_id: report_record.report_id,
             machine_id: report_record.machine_id,
             ts: report_record.ts,
             values,
+            event_log_replay,
         });
     }
     Ok(res)
@@ -456,7 +516,22 @@ pub async fn create_bundle_with_state(
         None => report.pcr_values(),
     };
 
-    crate::measured_boot::bundle::new(txn, profile.profile_id, None, &values, Some(state)).await
+    let bundle =
+        crate::measured_boot::bundle::new(txn, profile.profile_id, None, &values, Some(state))
+            .await?;
+
+    // Carry the report's event log over to the bundle, so later
+    // reports can be diffed against it event by event.
+    if let Some(event_log) = get_event_log(txn, report.report_id).await? {
+        crate::measured_boot::interface::bundle::insert_measurement_bundle_event_log(
+            txn,
+            bundle.bundle_id,
+            &event_log,
+        )
+        .await?;
+    }
+
+    Ok(bundle)
 }
 
 enum SameOrNot {
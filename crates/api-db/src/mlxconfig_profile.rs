@@ -0,0 +1,261 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineId;
+use config_version::ConfigVersion;
+use libmlx::profile::serialization::SerializableProfile;
+use model::mlxconfig_profile::{
+    MachineMlxConfigStatus, MlxConfigProfileBinding, MlxConfigProfileTarget, StoredMlxConfigProfile,
+};
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// Stores a new profile at the initial version
+pub async fn create(
+    txn: &mut PgConnection,
+    profile: &SerializableProfile,
+) -> DatabaseResult<StoredMlxConfigProfile> {
+    let query = "INSERT INTO mlxconfig_profiles (name, version, description, profile)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *";
+    match sqlx::query_as::<_, StoredMlxConfigProfile>(query)
+        .bind(&profile.name)
+        .bind(ConfigVersion::initial())
+        .bind(&profile.description)
+        .bind(sqlx::types::Json(profile))
+        .fetch_one(txn)
+        .await
+    {
+        Ok(stored) => Ok(stored),
+        Err(sqlx::Error::Database(db_err))
+            if db_err.is_unique_violation()
+                && db_err.constraint() == Some("mlxconfig_profiles_pkey") =>
+        {
+            Err(DatabaseError::AlreadyFoundError {
+                kind: "MlxConfigProfile",
+                id: profile.name.clone(),
+            })
+        }
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+/// Replaces the content of a stored profile and increments its version.
+/// The update only happens if the profile is still at `expected_version`.
+pub async fn update(
+    txn: &mut PgConnection,
+    profile: &SerializableProfile,
+    expected_version: ConfigVersion,
+) -> DatabaseResult<StoredMlxConfigProfile> {
+    let query = "UPDATE mlxconfig_profiles
+            SET version=$1, description=$2, profile=$3, updated=NOW()
+            WHERE name=$4 AND version=$5
+            RETURNING *";
+    match sqlx::query_as::<_, StoredMlxConfigProfile>(query)
+        .bind(expected_version.increment())
+        .bind(&profile.description)
+        .bind(sqlx::types::Json(profile))
+        .bind(&profile.name)
+        .bind(expected_version)
+        .fetch_one(txn)
+        .await
+    {
+        Ok(stored) => Ok(stored),
+        Err(sqlx::Error::RowNotFound) => Err(DatabaseError::ConcurrentModificationError(
+            "MlxConfigProfile",
+            expected_version.to_string(),
+        )),
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+/// Deletes a stored profile. Profiles which are still bound can not be deleted.
+pub async fn delete(txn: &mut PgConnection, name: &str) -> DatabaseResult<()> {
+    let query = "DELETE FROM mlxconfig_profiles WHERE name=$1 RETURNING name";
+    match sqlx::query_scalar::<_, String>(query)
+        .bind(name)
+        .fetch_one(txn)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::RowNotFound) => Err(DatabaseError::NotFoundError {
+            kind: "MlxConfigProfile",
+            id: name.to_string(),
+        }),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_foreign_key_violation() => {
+            Err(DatabaseError::FailedPrecondition(format!(
+                "mlxconfig profile {name} is still bound and can not be deleted"
+            )))
+        }
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+pub async fn find_by_name(
+    txn: impl DbReader<'_>,
+    name: &str,
+) -> DatabaseResult<Option<StoredMlxConfigProfile>> {
+    let query = "SELECT * FROM mlxconfig_profiles WHERE name=$1";
+    sqlx::query_as(query)
+        .bind(name)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_all(txn: impl DbReader<'_>) -> DatabaseResult<Vec<StoredMlxConfigProfile>> {
+    let query = "SELECT * FROM mlxconfig_profiles ORDER BY name";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Binds a profile to a target, replacing the profile previously bound to it
+pub async fn bind(
+    txn: &mut PgConnection,
+    binding: &MlxConfigProfileBinding,
+) -> DatabaseResult<MlxConfigProfileBinding> {
+    let (sku_id, instance_type_id) = target_columns(&binding.target);
+    let conflict_target = match binding.target {
+        MlxConfigProfileTarget::Sku(_) => "(sku_id) WHERE sku_id IS NOT NULL",
+        MlxConfigProfileTarget::InstanceType(_) => {
+            "(instance_type_id) WHERE instance_type_id IS NOT NULL"
+        }
+    };
+    let query = format!(
+        "INSERT INTO mlxconfig_profile_bindings (profile_name, sku_id, instance_type_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT {conflict_target}
+            DO UPDATE SET profile_name=EXCLUDED.profile_name, created=NOW()
+            RETURNING *"
+    );
+    match sqlx::query_as::<_, MlxConfigProfileBinding>(&query)
+        .bind(&binding.profile_name)
+        .bind(sku_id)
+        .bind(instance_type_id)
+        .fetch_one(txn)
+        .await
+    {
+        Ok(binding) => Ok(binding),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_foreign_key_violation() => {
+            Err(DatabaseError::FailedPrecondition(format!(
+                "mlxconfig profile {} or {} does not exist",
+                binding.profile_name, binding.target
+            )))
+        }
+        Err(e) => Err(DatabaseError::query(&query, e)),
+    }
+}
+
+/// Removes the binding of a target. Returns whether a binding existed.
+pub async fn unbind(
+    txn: &mut PgConnection,
+    target: &MlxConfigProfileTarget,
+) -> DatabaseResult<bool> {
+    let (sku_id, instance_type_id) = target_columns(target);
+    let query = "DELETE FROM mlxconfig_profile_bindings
+            WHERE sku_id IS NOT DISTINCT FROM $1 AND instance_type_id IS NOT DISTINCT FROM $2";
+    sqlx::query(query)
+        .bind(sku_id)
+        .bind(instance_type_id)
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_bindings(txn: impl DbReader<'_>) -> DatabaseResult<Vec<MlxConfigProfileBinding>> {
+    let query =
+        "SELECT * FROM mlxconfig_profile_bindings ORDER BY profile_name, sku_id, instance_type_id";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the profile bound to a machine with the given SKU and instance type.
+/// A SKU binding takes precedence over an instance type binding.
+pub async fn find_bound_profile(
+    txn: impl DbReader<'_>,
+    hw_sku: Option<&str>,
+    instance_type_id: Option<&InstanceTypeId>,
+) -> DatabaseResult<Option<StoredMlxConfigProfile>> {
+    if hw_sku.is_none() && instance_type_id.is_none() {
+        return Ok(None);
+    }
+
+    let query = "SELECT p.* FROM mlxconfig_profiles p
+            INNER JOIN mlxconfig_profile_bindings b ON b.profile_name = p.name
+            WHERE b.sku_id = $1 OR b.instance_type_id = $2
+            ORDER BY b.sku_id IS NULL
+            LIMIT 1";
+    sqlx::query_as(query)
+        .bind(hw_sku)
+        .bind(instance_type_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records the outcome of a profile sync reported for a host
+pub async fn update_machine_status(
+    txn: &mut PgConnection,
+    status: &MachineMlxConfigStatus,
+) -> DatabaseResult<()> {
+    let query = "INSERT INTO machine_mlxconfig_status
+            (machine_id, profile_name, profile_version, synced, variables_changed, observed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (machine_id) DO UPDATE SET
+                profile_name=EXCLUDED.profile_name,
+                profile_version=EXCLUDED.profile_version,
+                synced=EXCLUDED.synced,
+                variables_changed=EXCLUDED.variables_changed,
+                observed_at=EXCLUDED.observed_at";
+    sqlx::query(query)
+        .bind(status.machine_id)
+        .bind(&status.profile_name)
+        .bind(status.profile_version)
+        .bind(status.synced)
+        .bind(status.variables_changed)
+        .bind(status.observed_at)
+        .execute(txn)
+        .await
+        .map(|_| ())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_machine_status(
+    txn: impl DbReader<'_>,
+    machine_id: &MachineId,
+) -> DatabaseResult<Option<MachineMlxConfigStatus>> {
+    let query = "SELECT * FROM machine_mlxconfig_status WHERE machine_id=$1";
+    sqlx::query_as(query)
+        .bind(machine_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+fn target_columns(target: &MlxConfigProfileTarget) -> (Option<&str>, Option<&InstanceTypeId>) {
+    match target {
+        MlxConfigProfileTarget::Sku(sku_id) => (Some(sku_id.as_str()), None),
+        MlxConfigProfileTarget::InstanceType(id) => (None, Some(id)),
+    }
+}
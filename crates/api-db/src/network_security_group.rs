@@ -252,10 +252,13 @@ pub async fn get_propagation_status(
         sum(interfaces_expected)::INT4 as interfaces_expected,
         sum(interfaces_applied)::INT4 as interfaces_applied,
         COALESCE(json_agg(distinct instance_id) FILTER (WHERE interfaces_expected != interfaces_applied), '[]') as unpropagated_instance_ids,
-        COALESCE(json_agg(distinct instance_id) FILTER (WHERE instance_id IS NOT  NULL), '[]') as related_instance_ids
+        COALESCE(json_agg(distinct instance_id) FILTER (WHERE instance_id IS NOT  NULL), '[]') as related_instance_ids,
+        max(expansion_error) as expansion_error
         FROM (
             SELECT
                 v.id as vpc_id, i.id as instance_id,
+                /* Set if the object references of the NSG expand beyond the limits. */
+                nsges.error as expansion_error,
                 /*
                 * Get the number of interfaces associated with the instance
                 * that do not have NSGs on the interface.
@@ -282,6 +285,7 @@ pub async fn get_propagation_status(
             JOIN network_segments ns on ns.id=(ifc->>'network_segment_id')::uuid
             JOIN vpcs v on v.id=ns.vpc_id
             JOIN network_security_groups nsg on nsg.id=v.network_security_group_id
+            LEFT OUTER JOIN network_security_group_expansion_status nsges on nsges.network_security_group_id=nsg.id AND nsges.version=nsg.version
             WHERE i.network_security_group_id IS NULL
             AND i.deleted IS NULL"
     );
@@ -303,13 +307,14 @@ pub async fn get_propagation_status(
         vpc_query_builder.push_bind(tenant_organization_id.map(|t| t.to_string()));
     }
 
-    vpc_query_builder.push(" GROUP BY v.id, i.id) as prop_stats GROUP BY vpc_id");
+    vpc_query_builder.push(" GROUP BY v.id, i.id, nsges.error) as prop_stats GROUP BY vpc_id");
 
     let mut instance_query_builder = sqlx::QueryBuilder::new("
         SELECT
         instance_id::text as id,
         interfaces_expected,
         interfaces_applied,
+        expansion_error,
 
         /* Provide a list of instances related to the object that don't have the correct NSG details. */    
         COALESCE(json_agg(distinct instance_id) FILTER (WHERE interfaces_expected != interfaces_applied), '[]') as unpropagated_instance_ids,
@@ -319,6 +324,8 @@ pub async fn get_propagation_status(
         FROM (
             SELECT
                 i.id as instance_id,
+                /* Set if the object references of the NSG expand beyond the limits. */
+                nsges.error as expansion_error,
                 /*
                 * Get the number of interfaces associated with the instance
                 * that do not have NSGs on the interface.
@@ -346,6 +353,7 @@ pub async fn get_propagation_status(
             /* network_status_observation is stored in dpu now. */
             LEFT OUTER JOIN jsonb_array_elements(dpu.network_status_observation #>'{instance_network_observation,interfaces}') ifco on ifco->>'internal_uuid' = ifc->>'internal_uuid'
             JOIN network_security_groups nsg on nsg.id=i.network_security_group_id
+            LEFT OUTER JOIN network_security_group_expansion_status nsges on nsges.network_security_group_id=nsg.id AND nsges.version=nsg.version
             WHERE i.deleted IS NULL");
 
     if network_security_group_ids.is_some() {
@@ -366,7 +374,7 @@ pub async fn get_propagation_status(
     }
 
     instance_query_builder.push(
-        " GROUP BY i.id) as prop_stats GROUP BY instance_id,interfaces_expected,interfaces_applied",
+        " GROUP BY i.id, nsges.error) as prop_stats GROUP BY instance_id,interfaces_expected,interfaces_applied,expansion_error",
     );
 
     let vpcs = vpc_query_builder
@@ -384,6 +392,66 @@ pub async fn get_propagation_status(
     Ok((vpcs, instances))
 }
 
+/// Returns all non-deleted NetworkSecurityGroup records with at least one
+/// rule that references an object (VPC, VPC prefix, network segment)
+/// instead of a literal prefix.
+///
+/// * `txn` - A reference to an active DB transaction
+///
+pub async fn find_with_object_references(
+    txn: &mut PgConnection,
+) -> Result<Vec<NetworkSecurityGroup>, DatabaseError> {
+    let query = "SELECT * FROM network_security_groups nsg
+            WHERE nsg.deleted IS NULL
+            AND EXISTS (
+                SELECT 1 FROM jsonb_array_elements(nsg.rules) r
+                WHERE NOT (r->'src_net' ? 'Prefix') OR NOT (r->'dst_net' ? 'Prefix')
+            )";
+
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records the result of expanding the object references of a
+/// NetworkSecurityGroup into prefixes.
+///
+/// * `txn`                 - A reference to an active DB transaction
+/// * `id`                  - A reference to the NetworkSecurityGroupId the result is for
+/// * `version`             - The version of the NetworkSecurityGroup the result was computed for.
+///   Results for other versions are ignored when reporting propagation status.
+/// * `expanded_rule_count` - The number of rules after expansion
+/// * `error`               - Optional description of why the expanded rules can't be applied
+///
+pub async fn set_expansion_status(
+    txn: &mut PgConnection,
+    id: &NetworkSecurityGroupId,
+    version: &ConfigVersion,
+    expanded_rule_count: u64,
+    error: Option<&str>,
+) -> Result<(), DatabaseError> {
+    let query = "INSERT INTO network_security_group_expansion_status
+                (network_security_group_id, version, expanded_rule_count, error, checked_at)
+            VALUES ($1::varchar, $2::varchar, $3, $4, NOW())
+            ON CONFLICT (network_security_group_id) DO UPDATE SET
+                version=EXCLUDED.version,
+                expanded_rule_count=EXCLUDED.expanded_rule_count,
+                error=EXCLUDED.error,
+                checked_at=EXCLUDED.checked_at";
+
+    sqlx::query(query)
+        .bind(id)
+        .bind(version)
+        .bind(i64::try_from(expanded_rule_count).unwrap_or(i64::MAX))
+        .bind(error)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
 /// Updates a NetworkSecurityGroup records in the DB.
 ///
 /// * `txn`                    - A reference to an active DB transaction
@@ -61,11 +61,12 @@ pub async fn get(txn: &mut PgConnection, os_image_id: Uuid) -> Result<OsImage, D
 pub async fn create(
     txn: &mut PgConnection,
     attrs: &OsImageAttributes,
+    status: OsImageStatus,
 ) -> Result<OsImage, DatabaseError> {
     let timestamp: DateTime<Utc> = Utc::now();
     let os_image = OsImage {
         attributes: attrs.clone(),
-        status: OsImageStatus::Ready,
+        status,
         status_message: None,
         created_at: Some(timestamp.to_string()),
         modified_at: None,
@@ -74,6 +75,76 @@ pub async fn create(
     persist(os_image, txn, false).await
 }
 
+/// Returns the IDs of all OS images
+pub async fn list_ids(txn: &mut PgConnection) -> Result<Vec<Uuid>, DatabaseError> {
+    let query = "SELECT id FROM os_images";
+    sqlx::query_scalar(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Moves the oldest OS image which waits for ingestion to `InProgress` and
+/// returns it. Images whose ingestion was started more than `timeout` ago
+/// are claimed again, since their ingestion got interrupted.
+pub async fn claim_for_ingestion(
+    txn: &mut PgConnection,
+    timeout: std::time::Duration,
+) -> Result<Option<OsImage>, DatabaseError> {
+    let query = "UPDATE os_images SET status = 'inprogress', status_message = NULL, ingestion_started_at = NOW()
+        WHERE id = (
+            SELECT id FROM os_images
+            WHERE status = 'uninitialized'
+                OR (status = 'inprogress' AND (ingestion_started_at IS NULL OR ingestion_started_at < NOW() - make_interval(secs => $1)))
+            ORDER BY created_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *";
+    sqlx::query_as(query)
+        .bind(timeout.as_secs_f64())
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records the result of the ingestion of an OS image. Returns `None` if the
+/// image is no longer in progress, e.g. because it got deleted.
+pub async fn finish_ingestion(
+    txn: &mut PgConnection,
+    os_image_id: Uuid,
+    status: OsImageStatus,
+    status_message: Option<String>,
+) -> Result<Option<OsImage>, DatabaseError> {
+    let timestamp: DateTime<Utc> = Utc::now();
+    let query = "UPDATE os_images SET status = $1, status_message = $2, modified_at = $3
+        WHERE id = $4 AND status = 'inprogress'
+        RETURNING *";
+    sqlx::query_as(query)
+        .bind(status)
+        .bind(status_message)
+        .bind(timestamp.to_string())
+        .bind(os_image_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Moves an OS image back to `Uninitialized`, so that it gets ingested again
+pub async fn restart_ingestion(
+    txn: &mut PgConnection,
+    os_image_id: Uuid,
+) -> Result<OsImage, DatabaseError> {
+    let query = "UPDATE os_images SET status = 'uninitialized', status_message = NULL, ingestion_started_at = NULL
+        WHERE id = $1
+        RETURNING *";
+    sqlx::query_as(query)
+        .bind(os_image_id)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 pub async fn delete(value: &OsImage, txn: &mut PgConnection) -> Result<(), DatabaseError> {
     let query = "DELETE FROM os_images WHERE id = $1";
     sqlx::query(query)
@@ -106,7 +177,8 @@ async fn persist(
     update: bool,
 ) -> Result<OsImage, DatabaseError> {
     let os_image = if update {
-        let query = "UPDATE os_images SET name = $1, description = $2, auth_type = $3, auth_token = $4, rootfs_id = $5, rootfs_label = $6, boot_disk = $7, bootfs_id = $8, efifs_id = $9, modified_at = $10, status = $11, status_message = $12 WHERE id = $13 RETURNING *";
+        // The status is owned by the ingestion and not changed by updates
+        let query = "UPDATE os_images SET name = $1, description = $2, auth_type = $3, auth_token = $4, rootfs_id = $5, rootfs_label = $6, boot_disk = $7, bootfs_id = $8, efifs_id = $9, modified_at = $10 WHERE id = $11 RETURNING *";
         sqlx::query_as(query)
             .bind(&value.attributes.name)
             .bind(&value.attributes.description)
@@ -118,8 +190,6 @@ async fn persist(
             .bind(&value.attributes.bootfs_id)
             .bind(&value.attributes.efifs_id)
             .bind(&value.modified_at)
-            .bind(value.status.clone())
-            .bind(&value.status_message)
             .bind(value.attributes.id)
             .fetch_one(txn)
             .await
@@ -19,7 +19,7 @@ use carbide_uuid::rack::RackId;
 use config_version::ConfigVersion;
 use mac_address::MacAddress;
 use model::controller_outcome::PersistentStateHandlerOutcome;
-use model::rack::{Rack, RackConfig, RackState};
+use model::rack::{Rack, RackConfig, RackFirmwareUpgrade, RackPowerSequence, RackState};
 use sqlx::PgConnection;
 
 use crate::db_read::DbReader;
@@ -120,6 +120,40 @@ pub async fn update(
     Ok(rack)
 }
 
+/// Stores the firmware upgrade of the rack. This is kept apart from the rack
+/// config, since the config is also modified by discovery.
+pub async fn update_firmware_upgrade(
+    txn: &mut PgConnection,
+    rack_id: RackId,
+    firmware_upgrade: &RackFirmwareUpgrade,
+) -> DatabaseResult<()> {
+    let query = "UPDATE racks SET firmware_upgrade = $1, updated=NOW() WHERE id = $2";
+    sqlx::query(query)
+        .bind(sqlx::types::Json(firmware_upgrade))
+        .bind(rack_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+pub async fn update_power_sequence(
+    txn: &mut PgConnection,
+    rack_id: RackId,
+    power_sequence: &RackPowerSequence,
+) -> DatabaseResult<()> {
+    let query = "UPDATE racks SET power_sequence = $1, updated=NOW() WHERE id = $2";
+    sqlx::query(query)
+        .bind(sqlx::types::Json(power_sequence))
+        .bind(rack_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
 pub async fn try_update_controller_state(
     txn: &mut PgConnection,
     rack_id: RackId,
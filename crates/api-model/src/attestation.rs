@@ -54,7 +54,10 @@ pub mod spdm {
     use config_version::ConfigVersion;
     use itertools::Itertools;
     use libredfish::model::component_integrity::{CaCertificate, ComponentIntegrity, Evidence};
-    use nras::{NrasError, NrasVerifierClient, ProcessedAttestationOutcome, RawAttestationOutcome};
+    use nras::{
+        LocalVerifierClient, NrasError, NrasVerifierClient, ProcessedAttestationOutcome,
+        RawAttestationOutcome,
+    };
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use sqlx::postgres::PgRow;
@@ -125,6 +128,11 @@ pub mod spdm {
         pub state_outcome: Option<PersistentStateHandlerOutcome>,
         // If attestation is started, completed or not supported
         pub attestation_status: SpdmAttestationStatus,
+        // When the last attestation completed. Used by the attestation policy to decide when a
+        // machine has to be re-attested.
+        pub last_attested_at: Option<DateTime<Utc>>,
+        // If all devices passed the last completed attestation.
+        pub last_attestation_passed: Option<bool>,
     }
 
     /// Data model to store progress of attestation related to a device/component of a machine BMC (e.g.
@@ -370,6 +378,8 @@ pub mod spdm {
                 state_version: row.try_get("state_version")?,
                 state_outcome: controller_state_outcome.map(|x| x.0),
                 attestation_status: row.try_get("attestation_status")?,
+                last_attested_at: row.try_get("last_attested_at")?,
+                last_attestation_passed: row.try_get("last_attestation_passed")?,
             })
         }
     }
@@ -445,6 +455,8 @@ pub mod spdm {
                 state_outcome: value.machine.state_outcome.map(|x| x.to_string()),
                 status: format!("{:?}", value.machine.attestation_status),
                 device_data: value.devices.iter().map(|x| x.clone().into()).collect_vec(),
+                last_attested_at: value.machine.last_attested_at.map(|x| x.into()),
+                last_attestation_passed: value.machine.last_attestation_passed,
             }
         }
     }
@@ -527,17 +539,26 @@ pub mod spdm {
     #[async_trait::async_trait]
     impl Verifier for VerifierImpl {
         fn client(&self, nras_config: nras::Config) -> Box<dyn nras::VerifierClient> {
-            Box::new(NrasVerifierClient::new_with_config(&nras_config))
+            if nras_config.local_verifier.is_some() {
+                Box::new(LocalVerifierClient::new_with_config(&nras_config))
+            } else {
+                Box::new(NrasVerifierClient::new_with_config(&nras_config))
+            }
         }
         async fn parse_attestation_outcome(
             &self,
             nras_config: &nras::Config,
             state: &RawAttestationOutcome,
         ) -> Result<ProcessedAttestationOutcome, NrasError> {
-            // now create a KeyStore to validate those tokens
-            let nras_keystore = nras::NrasKeyStore::new_with_config(nras_config).await?;
             let parser = nras::Parser::new_with_config(nras_config);
-            parser.parse_attestation_outcome(state, &nras_keystore)
+            // now create a KeyStore to validate those tokens
+            if nras_config.local_verifier.is_some() {
+                let local_keystore = nras::LocalKeyStore::new_with_config(nras_config)?;
+                parser.parse_attestation_outcome(state, &local_keystore)
+            } else {
+                let nras_keystore = nras::NrasKeyStore::new_with_config(nras_config).await?;
+                parser.parse_attestation_outcome(state, &nras_keystore)
+            }
         }
     }
 }
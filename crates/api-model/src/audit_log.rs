@@ -0,0 +1,153 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Audit log of mutating admin and tenant RPCs, as returned by `FindAuditLogEntries`
+
+use ::rpc::errors::RpcDataConversionError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// The outcome of an audited RPC call
+#[derive(Copy, Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "audit_log_outcome")]
+#[sqlx(rename_all = "snake_case")]
+pub enum AuditLogOutcome {
+    Success,
+    Failure,
+    /// The call was started, but its outcome was not recorded yet
+    Pending,
+}
+
+/// An audit log entry which is about to be recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewAuditLogEntry {
+    /// The authenticated principal which issued the call,
+    /// e.g. `spiffe-service-id/carbide-dhcp` or `external-user/admins/jdoe`
+    pub principal: String,
+    /// Name of the RPC, e.g. `SetMaintenance`
+    pub rpc_name: String,
+    /// IDs of the objects the call targeted
+    pub object_ids: Vec<String>,
+    /// The request, with all secrets removed
+    pub request: String,
+    pub outcome: AuditLogOutcome,
+    /// The error which was returned to the caller, if the call failed
+    pub error: Option<String>,
+}
+
+/// A row of the `audit_log` table
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    /// The time when the entry was recorded
+    pub timestamp: DateTime<Utc>,
+    pub principal: String,
+    pub rpc_name: String,
+    pub object_ids: Vec<String>,
+    pub request: String,
+    pub outcome: AuditLogOutcome,
+    pub error: Option<String>,
+}
+
+/// Selects the audit log entries which are returned by `FindAuditLogEntries`.
+/// All criteria that are set need to match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLogFilter {
+    pub principal: Option<String>,
+    pub object_id: Option<String>,
+    pub rpc_name: Option<String>,
+    /// Only entries recorded at or after this time
+    pub start_time: Option<DateTime<Utc>>,
+    /// Only entries recorded at or before this time
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<&rpc::forge::FindAuditLogEntriesRequest> for AuditLogFilter {
+    type Error = RpcDataConversionError;
+
+    fn try_from(request: &rpc::forge::FindAuditLogEntriesRequest) -> Result<Self, Self::Error> {
+        let convert_time = |time: Option<&rpc::Timestamp>| {
+            time.map(|time| {
+                DateTime::<Utc>::try_from(time.clone())
+                    .map_err(|_| RpcDataConversionError::InvalidTimestamp(time.to_string()))
+            })
+            .transpose()
+        };
+
+        Ok(Self {
+            principal: request.principal.clone(),
+            object_id: request.object_id.clone(),
+            rpc_name: request.rpc_name.clone(),
+            start_time: convert_time(request.start_time.as_ref())?,
+            end_time: convert_time(request.end_time.as_ref())?,
+        })
+    }
+}
+
+impl From<AuditLogOutcome> for rpc::forge::AuditLogOutcome {
+    fn from(outcome: AuditLogOutcome) -> Self {
+        match outcome {
+            AuditLogOutcome::Success => rpc::forge::AuditLogOutcome::Success,
+            AuditLogOutcome::Failure => rpc::forge::AuditLogOutcome::Failure,
+            AuditLogOutcome::Pending => rpc::forge::AuditLogOutcome::Pending,
+        }
+    }
+}
+
+impl From<AuditLogEntry> for rpc::forge::AuditLogEntry {
+    fn from(entry: AuditLogEntry) -> Self {
+        Self {
+            id: entry.id,
+            timestamp: Some(entry.timestamp.into()),
+            principal: entry.principal,
+            rpc_name: entry.rpc_name,
+            object_ids: entry.object_ids,
+            request: entry.request,
+            outcome: rpc::forge::AuditLogOutcome::from(entry.outcome) as i32,
+            error: entry.error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_from_rpc_request() {
+        let start_time = "2026-03-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let request = rpc::forge::FindAuditLogEntriesRequest {
+            principal: Some("external-user/admins/jdoe".to_string()),
+            object_id: None,
+            rpc_name: Some("SetMaintenance".to_string()),
+            start_time: Some(start_time.into()),
+            end_time: None,
+            limit: None,
+        };
+        assert_eq!(
+            AuditLogFilter::try_from(&request).unwrap(),
+            AuditLogFilter {
+                principal: Some("external-user/admins/jdoe".to_string()),
+                object_id: None,
+                rpc_name: Some("SetMaintenance".to_string()),
+                start_time: Some(start_time),
+                end_time: None,
+            }
+        );
+    }
+}
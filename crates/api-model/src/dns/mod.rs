@@ -24,11 +24,13 @@ pub mod domain_info;
 pub mod metadata;
 pub mod resource_record;
 pub mod snapshot;
+pub mod tenant_record;
 
 pub use domain_info::DomainInfo;
 pub use metadata::DomainMetadata;
 pub use resource_record::ResourceRecord;
 pub use snapshot::SoaSnapshot;
+pub use tenant_record::{NewTenantDnsRecord, TenantDnsRecord};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Domain {
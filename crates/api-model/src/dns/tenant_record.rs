@@ -0,0 +1,156 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! DNS records that tenants manage inside their own domains.
+
+use carbide_uuid::domain::{DomainId, TenantDnsRecordId};
+use chrono::{DateTime, Utc};
+use dns_record::DnsResourceRecordType;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+use super::ResourceRecord;
+use crate::tenant::TenantOrganizationId;
+
+/// TTL used for records which don't specify one
+pub const DEFAULT_TTL: u32 = 300;
+
+/// Record types a tenant is allowed to manage.
+/// Everything else (SOA, NS, PTR) is owned by the site.
+pub const TENANT_RECORD_TYPES: &[DnsResourceRecordType] = &[
+    DnsResourceRecordType::A,
+    DnsResourceRecordType::AAAA,
+    DnsResourceRecordType::CNAME,
+    DnsResourceRecordType::TXT,
+    DnsResourceRecordType::SRV,
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantDnsRecord {
+    pub id: TenantDnsRecordId,
+    pub domain_id: DomainId,
+    pub tenant_organization_id: TenantOrganizationId,
+    /// Fully qualified name of the record, including the trailing dot
+    pub q_name: String,
+    pub q_type: DnsResourceRecordType,
+    /// Record data as supplied by the tenant. TXT data is stored unquoted.
+    pub content: String,
+    pub ttl: Option<u32>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl TenantDnsRecord {
+    /// Returns the record data in the presentation format PowerDNS expects
+    pub fn rdata(&self) -> String {
+        match self.q_type {
+            DnsResourceRecordType::TXT => format!(
+                "\"{}\"",
+                self.content.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+            _ => self.content.clone(),
+        }
+    }
+}
+
+/// A DNS record that a tenant is about to add to one of its domains
+#[derive(Clone, Debug)]
+pub struct NewTenantDnsRecord {
+    pub domain_id: DomainId,
+    pub tenant_organization_id: TenantOrganizationId,
+    pub q_name: String,
+    pub q_type: DnsResourceRecordType,
+    pub content: String,
+    pub ttl: Option<u32>,
+}
+
+impl<'r> FromRow<'r, PgRow> for TenantDnsRecord {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let tenant_organization_id: String = row.try_get("tenant_organization_id")?;
+        let q_type: String = row.try_get("q_type")?;
+        let ttl: Option<i32> = row.try_get("ttl")?;
+
+        Ok(TenantDnsRecord {
+            id: row.try_get("id")?,
+            domain_id: row.try_get("domain_id")?,
+            tenant_organization_id: tenant_organization_id
+                .parse::<TenantOrganizationId>()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            q_name: row.try_get("q_name")?,
+            q_type: DnsResourceRecordType::try_from(q_type)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            content: row.try_get("content")?,
+            ttl: ttl.map(|ttl| ttl as u32),
+            created: row.try_get("created")?,
+            updated: row.try_get("updated")?,
+        })
+    }
+}
+
+impl From<TenantDnsRecord> for ResourceRecord {
+    fn from(record: TenantDnsRecord) -> Self {
+        Self {
+            q_type: record.q_type.to_string(),
+            content: record.rdata(),
+            q_name: record.q_name,
+            ttl: record.ttl.unwrap_or(DEFAULT_TTL),
+            domain_id: Some(record.domain_id.to_string()),
+        }
+    }
+}
+
+impl From<TenantDnsRecord> for rpc::protos::dns::TenantDnsRecord {
+    fn from(record: TenantDnsRecord) -> Self {
+        Self {
+            id: record.id.to_string(),
+            domain_id: Some(record.domain_id),
+            tenant_organization_id: record.tenant_organization_id.to_string(),
+            name: record.q_name,
+            record_type: record.q_type.to_string(),
+            content: record.content,
+            ttl: record.ttl,
+            created: Some(record.created.into()),
+            updated: Some(record.updated.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_txt_rdata_is_quoted() {
+        let record = TenantDnsRecord {
+            id: TenantDnsRecordId::new(),
+            domain_id: DomainId::new(),
+            tenant_organization_id: "tenant-a".parse().unwrap(),
+            q_name: "_acme.tenant.example.com.".to_string(),
+            q_type: DnsResourceRecordType::TXT,
+            content: r#"v=spf1 "quoted" \ end"#.to_string(),
+            ttl: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+        };
+        assert_eq!(record.rdata(), r#""v=spf1 \"quoted\" \\ end""#);
+
+        let rr = ResourceRecord::from(record);
+        assert_eq!(rr.q_type, "TXT");
+        assert_eq!(rr.ttl, DEFAULT_TTL);
+    }
+}
@@ -37,12 +37,16 @@ static LOG_PATH_BAD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z0-9\-\_\
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ExtensionServiceType {
     KubernetesPod,
+    ContainerdContainer,
+    SystemdUnit,
 }
 
 impl std::fmt::Display for ExtensionServiceType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ExtensionServiceType::KubernetesPod => write!(f, "kubernetes_pod"),
+            ExtensionServiceType::ContainerdContainer => write!(f, "containerd_container"),
+            ExtensionServiceType::SystemdUnit => write!(f, "systemd_unit"),
         }
     }
 }
@@ -57,6 +61,8 @@ impl std::str::FromStr for ExtensionServiceType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "kubernetes_pod" => Ok(ExtensionServiceType::KubernetesPod),
+            "containerd_container" => Ok(ExtensionServiceType::ContainerdContainer),
+            "systemd_unit" => Ok(ExtensionServiceType::SystemdUnit),
             _ => Err(InvalidExtensionServiceTypeError(s.to_string())),
         }
     }
@@ -66,6 +72,10 @@ impl From<ExtensionServiceType> for rpc::DpuExtensionServiceType {
     fn from(service_type: ExtensionServiceType) -> Self {
         match service_type {
             ExtensionServiceType::KubernetesPod => rpc::DpuExtensionServiceType::KubernetesPod,
+            ExtensionServiceType::ContainerdContainer => {
+                rpc::DpuExtensionServiceType::ContainerdContainer
+            }
+            ExtensionServiceType::SystemdUnit => rpc::DpuExtensionServiceType::SystemdUnit,
         }
     }
 }
@@ -74,6 +84,10 @@ impl From<rpc::DpuExtensionServiceType> for ExtensionServiceType {
     fn from(service_type: rpc::DpuExtensionServiceType) -> Self {
         match service_type {
             rpc::DpuExtensionServiceType::KubernetesPod => ExtensionServiceType::KubernetesPod,
+            rpc::DpuExtensionServiceType::ContainerdContainer => {
+                ExtensionServiceType::ContainerdContainer
+            }
+            rpc::DpuExtensionServiceType::SystemdUnit => ExtensionServiceType::SystemdUnit,
         }
     }
 }
@@ -0,0 +1,57 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Membership leases and handed over collector state of carbide-hw-health replicas
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A row of the `health_shard_leases` table
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct HealthShardLease {
+    pub member_id: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<HealthShardLease> for rpc::forge::HealthShardMember {
+    fn from(lease: HealthShardLease) -> Self {
+        Self {
+            member_id: lease.member_id,
+            expires_at: Some(lease.expires_at.into()),
+        }
+    }
+}
+
+/// A row of the `health_collector_state` table
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct HealthCollectorState {
+    pub endpoint_key: String,
+    pub collector: String,
+    pub state: sqlx::types::Json<serde_json::Value>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<HealthCollectorState> for rpc::forge::HealthCollectorState {
+    fn from(state: HealthCollectorState) -> Self {
+        Self {
+            endpoint_key: state.endpoint_key,
+            collector: state.collector,
+            state: state.state.0.to_string(),
+            updated_at: Some(state.updated_at.into()),
+        }
+    }
+}
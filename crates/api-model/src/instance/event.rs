@@ -0,0 +1,302 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tenant facing lifecycle events of instances, as streamed by `StreamInstanceEvents`
+
+use std::str::FromStr;
+
+use ::rpc::errors::RpcDataConversionError;
+use carbide_uuid::instance::InstanceId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::instance::status::tenant::TenantState;
+use crate::tenant::TenantOrganizationId;
+
+/// An event in the lifecycle of an instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceEvent {
+    /// Monotonically increasing ID of the event
+    pub id: i64,
+    /// ID of the database transaction which recorded the event
+    pub xid: i64,
+    pub instance_id: InstanceId,
+    pub tenant_organization_id: TenantOrganizationId,
+    /// The time when the event was recorded
+    pub timestamp: DateTime<Utc>,
+    pub payload: InstanceEventPayload,
+}
+
+impl InstanceEvent {
+    /// The position of the event in the event stream
+    pub fn resume_token(&self) -> InstanceEventResumeToken {
+        InstanceEventResumeToken {
+            xid: self.xid,
+            id: self.id,
+        }
+    }
+}
+
+/// The type specific data of an [`InstanceEvent`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InstanceEventPayload {
+    /// The host of the instance transitioned into a new state
+    StateTransition {
+        tenant_state: TenantState,
+        /// The internal state of the host the instance is running on
+        machine_state: String,
+    },
+    /// The network config with the given version has been applied to all DPUs
+    NetworkConfigApplied { network_config_version: String },
+    /// The instance called phone-home
+    PhoneHome,
+    /// A power operation had been requested via `InvokeInstancePower`
+    PowerAction {
+        /// Name of the `InstancePowerRequest.Operation`
+        operation: String,
+        boot_with_custom_ipxe: bool,
+        apply_updates_on_reboot: bool,
+    },
+    /// Release of the instance had been requested
+    Release { issue: Option<InstanceReleaseIssue> },
+}
+
+/// The issue that a tenant reported while releasing an instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceReleaseIssue {
+    /// Name of the `IssueCategory`
+    pub category: String,
+    pub summary: String,
+    pub details: String,
+}
+
+impl From<&rpc::forge::Issue> for InstanceReleaseIssue {
+    fn from(issue: &rpc::forge::Issue) -> Self {
+        Self {
+            category: rpc::forge::IssueCategory::try_from(issue.category)
+                .unwrap_or(rpc::forge::IssueCategory::Unspecified)
+                .as_str_name()
+                .to_string(),
+            summary: issue.summary.clone(),
+            details: issue.details.clone(),
+        }
+    }
+}
+
+impl From<InstanceReleaseIssue> for rpc::forge::Issue {
+    fn from(issue: InstanceReleaseIssue) -> Self {
+        Self {
+            category: rpc::forge::IssueCategory::from_str_name(&issue.category)
+                .unwrap_or(rpc::forge::IssueCategory::Unspecified) as i32,
+            summary: issue.summary,
+            details: issue.details,
+        }
+    }
+}
+
+impl From<&rpc::forge::InstancePowerRequest> for InstanceEventPayload {
+    fn from(request: &rpc::forge::InstancePowerRequest) -> Self {
+        Self::PowerAction {
+            operation: request.operation().as_str_name().to_string(),
+            boot_with_custom_ipxe: request.boot_with_custom_ipxe,
+            apply_updates_on_reboot: request.apply_updates_on_reboot,
+        }
+    }
+}
+
+/// Selects the instances whose events are streamed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstanceEventScope {
+    /// Events of a single instance
+    Instance(InstanceId),
+    /// Events of all instances of a tenant
+    Tenant(TenantOrganizationId),
+}
+
+impl TryFrom<rpc::forge::instance_event_stream_request::Scope> for InstanceEventScope {
+    type Error = RpcDataConversionError;
+
+    fn try_from(
+        scope: rpc::forge::instance_event_stream_request::Scope,
+    ) -> Result<Self, Self::Error> {
+        use rpc::forge::instance_event_stream_request::Scope;
+
+        Ok(match scope {
+            Scope::InstanceId(instance_id) => Self::Instance(instance_id),
+            Scope::TenantOrganizationId(tenant_organization_id) => Self::Tenant(
+                TenantOrganizationId::try_from(tenant_organization_id.clone()).map_err(|_| {
+                    RpcDataConversionError::InvalidValue(
+                        "tenant_organization_id".to_string(),
+                        tenant_organization_id,
+                    )
+                })?,
+            ),
+        })
+    }
+}
+
+/// The position of an event in the event stream, handed out to clients as
+/// opaque resume token.
+///
+/// Events are streamed ordered by the ID of the transaction which recorded
+/// them, and by event ID within a transaction. Ordering by event ID alone would
+/// skip events of transactions which commit after events with a higher ID had
+/// already been streamed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InstanceEventResumeToken {
+    pub xid: i64,
+    pub id: i64,
+}
+
+impl InstanceEventResumeToken {
+    /// The position before the first event
+    pub const START: Self = Self { xid: 0, id: 0 };
+}
+
+impl std::fmt::Display for InstanceEventResumeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.xid, self.id)
+    }
+}
+
+impl FromStr for InstanceEventResumeToken {
+    type Err = RpcDataConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = || {
+            let (xid, id) = s.split_once('.')?;
+            let xid = xid.parse::<i64>().ok().filter(|xid| *xid >= 0)?;
+            let id = id.parse::<i64>().ok().filter(|id| *id >= 0)?;
+            Some(Self { xid, id })
+        };
+        parse().ok_or_else(|| {
+            RpcDataConversionError::InvalidValue("resume_token".to_string(), s.to_string())
+        })
+    }
+}
+
+impl TryFrom<InstanceEvent> for rpc::forge::InstanceEvent {
+    type Error = RpcDataConversionError;
+
+    fn try_from(event: InstanceEvent) -> Result<Self, Self::Error> {
+        use rpc::forge::instance_event::Event;
+
+        let event_data = match event.payload {
+            InstanceEventPayload::StateTransition {
+                tenant_state,
+                machine_state,
+            } => Event::StateTransition(rpc::forge::InstanceStateTransitionEvent {
+                tenant_state: rpc::forge::TenantState::try_from(tenant_state)? as i32,
+                machine_state,
+            }),
+            InstanceEventPayload::NetworkConfigApplied {
+                network_config_version,
+            } => Event::NetworkConfigApplied(rpc::forge::InstanceNetworkConfigAppliedEvent {
+                network_config_version,
+            }),
+            InstanceEventPayload::PhoneHome => {
+                Event::PhoneHome(rpc::forge::InstancePhoneHomeEvent {})
+            }
+            InstanceEventPayload::PowerAction {
+                operation,
+                boot_with_custom_ipxe,
+                apply_updates_on_reboot,
+            } => Event::PowerAction(rpc::forge::InstancePowerActionEvent {
+                operation: rpc::forge::instance_power_request::Operation::from_str_name(&operation)
+                    .ok_or_else(|| {
+                        RpcDataConversionError::InvalidValue("operation".to_string(), operation)
+                    })? as i32,
+                boot_with_custom_ipxe,
+                apply_updates_on_reboot,
+            }),
+            InstanceEventPayload::Release { issue } => {
+                Event::Release(rpc::forge::InstanceReleaseEvent {
+                    issue: issue.map(Into::into),
+                })
+            }
+        };
+
+        Ok(rpc::forge::InstanceEvent {
+            resume_token: event.resume_token().to_string(),
+            instance_id: Some(event.instance_id),
+            tenant_organization_id: event.tenant_organization_id.to_string(),
+            timestamp: Some(event.timestamp.into()),
+            event: Some(event_data),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_payload() {
+        let payload = InstanceEventPayload::StateTransition {
+            tenant_state: TenantState::Ready,
+            machine_state: "Assigned/Ready".to_string(),
+        };
+        let serialized = serde_json::to_string(&payload).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"state_transition","tenant_state":"ready","machine_state":"Assigned/Ready"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<InstanceEventPayload>(&serialized).unwrap(),
+            payload
+        );
+
+        let payload = InstanceEventPayload::PhoneHome;
+        let serialized = serde_json::to_string(&payload).unwrap();
+        assert_eq!(serialized, r#"{"type":"phone_home"}"#);
+    }
+
+    #[test]
+    fn convert_release_issue() {
+        let issue = rpc::forge::Issue {
+            category: rpc::forge::IssueCategory::Hardware as i32,
+            summary: "GPU fell off the bus".to_string(),
+            details: "Xid 79".to_string(),
+        };
+        let model_issue = InstanceReleaseIssue::from(&issue);
+        assert_eq!(model_issue.category, "HARDWARE");
+        assert_eq!(rpc::forge::Issue::from(model_issue), issue);
+    }
+
+    #[test]
+    fn parse_resume_token() {
+        assert_eq!(
+            "1234.42".parse::<InstanceEventResumeToken>().unwrap(),
+            InstanceEventResumeToken { xid: 1234, id: 42 }
+        );
+        assert_eq!(
+            InstanceEventResumeToken { xid: 1234, id: 42 }
+                .to_string()
+                .parse::<InstanceEventResumeToken>()
+                .unwrap(),
+            InstanceEventResumeToken { xid: 1234, id: 42 }
+        );
+        assert_eq!(
+            InstanceEventResumeToken::START.to_string(),
+            "0.0".to_string()
+        );
+        assert!("42".parse::<InstanceEventResumeToken>().is_err());
+        assert!("1234.-1".parse::<InstanceEventResumeToken>().is_err());
+        assert!("abc".parse::<InstanceEventResumeToken>().is_err());
+    }
+}
@@ -24,6 +24,7 @@ use crate::instance::config::InstanceConfig;
 use crate::metadata::Metadata;
 
 pub mod config;
+pub mod event;
 pub mod snapshot;
 pub mod status;
 
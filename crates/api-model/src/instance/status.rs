@@ -82,6 +82,8 @@ impl TryFrom<InstanceStatus> for rpc::InstanceStatus {
             nvlink: Some(status.nvlink.try_into()?),
             configs_synced: rpc::SyncState::try_from(status.configs_synced)? as i32,
             update: status.reprovision_request.map(|request| request.into()),
+            // Filled in by the API from the attestation records of the host
+            attestation: None,
         })
     }
 }
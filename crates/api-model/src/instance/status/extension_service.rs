@@ -348,6 +348,8 @@ pub struct ExtensionServiceComponent {
     pub version: String, // This is the version of the component, not the version of the extension service
     pub url: String,
     pub status: String,
+    #[serde(default)]
+    pub restart_count: Option<u32>,
 }
 
 impl TryFrom<rpc::DpuExtensionServiceComponent> for ExtensionServiceComponent {
@@ -359,6 +361,7 @@ impl TryFrom<rpc::DpuExtensionServiceComponent> for ExtensionServiceComponent {
             version: component.version,
             url: component.url,
             status: component.status,
+            restart_count: component.restart_count,
         })
     }
 }
@@ -370,6 +373,7 @@ impl From<ExtensionServiceComponent> for rpc::DpuExtensionServiceComponent {
             version: component.version,
             url: component.url,
             status: component.status,
+            restart_count: component.restart_count,
         }
     }
 }
@@ -496,12 +500,7 @@ impl From<ExtensionServiceStatusObservation> for rpc::DpuExtensionServiceStatusO
             components: observation
                 .components
                 .into_iter()
-                .map(|c| rpc::DpuExtensionServiceComponent {
-                    name: c.name,
-                    version: c.version,
-                    url: c.url,
-                    status: c.status,
-                })
+                .map(rpc::DpuExtensionServiceComponent::from)
                 .collect(),
             message: observation.message,
         }
@@ -35,6 +35,7 @@ use serde::{Deserialize, Serialize};
 
 pub mod address_selection_strategy;
 pub mod attestation;
+pub mod audit_log;
 pub mod bmc_info;
 pub mod controller_outcome;
 pub mod dhcp_entry;
@@ -50,6 +51,7 @@ pub mod expected_switch;
 pub mod extension_service;
 pub mod firmware;
 pub mod hardware_info;
+pub mod health_shard;
 pub mod host_machine_update;
 pub mod ib;
 pub mod ib_partition;
@@ -62,6 +64,7 @@ pub mod machine_interface_address;
 pub mod machine_update_module;
 pub mod machine_validation;
 pub mod metadata;
+pub mod mlxconfig_profile;
 pub mod network_devices;
 pub mod network_prefix;
 pub mod network_security_group;
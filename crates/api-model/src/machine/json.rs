@@ -89,6 +89,7 @@ pub struct MachineSnapshotPgJson {
     pub interfaces: Vec<MachineInterfaceSnapshot>,
     pub topology: Vec<MachineTopology>,
     pub log_parser_health_report: Option<HealthReport>,
+    pub console_health_report: Option<HealthReport>,
     pub labels: HashMap<String, String>,
     pub name: String,
     pub description: String,
@@ -204,6 +205,7 @@ impl TryFrom<MachineSnapshotPgJson> for Machine {
             metadata,
             instance_type_id: value.instance_type_id,
             log_parser_health_report: value.log_parser_health_report,
+            console_health_report: value.console_health_report,
             version,
             // Columns for these exist, but are unused in rust code
             // deployed: value.deployed,
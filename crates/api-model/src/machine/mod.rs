@@ -60,6 +60,7 @@ use crate::firmware::FirmwareComponentType;
 use crate::hardware_info::{HardwareInfo, MachineNvLinkInfo};
 use crate::instance::config::network::DeviceLocator;
 use crate::instance::snapshot::InstanceSnapshotPgJson;
+use crate::instance::status::InstanceStatus;
 use crate::machine::capabilities::MachineCapabilitiesSet;
 use crate::machine::health_override::HealthReportOverrides;
 use crate::machine_interface_address::InterfaceAssociationType;
@@ -263,10 +264,13 @@ impl ManagedHostStateSnapshot {
             output.merge(sku_validation_health_report);
         }
 
-        // log parser reports are only merged if available, heartbeat timeout is not applicable
+        // log parser and console reports are only merged if available, heartbeat timeout is not applicable
         if let Some(input) = &self.host_snapshot.log_parser_health_report {
             output.merge(input);
         }
+        if let Some(input) = &self.host_snapshot.console_health_report {
+            output.merge(input);
+        }
 
         if let Some(report) = self.host_snapshot.site_explorer_health_report.as_ref() {
             output.merge(report);
@@ -380,6 +384,41 @@ impl ManagedHostStateSnapshot {
         }
     }
 
+    /// Derives the status of the instance which is running on the host.
+    /// Returns `None` if no instance is assigned to the host.
+    pub fn derive_instance_status(&self) -> Result<Option<InstanceStatus>, RpcDataConversionError> {
+        let Some(instance) = self.instance.as_ref() else {
+            return Ok(None);
+        };
+
+        // TODO: If multiple DPUs have reprovisioning requested, we might not get
+        // the expected response
+        let mut reprovision_request = self.host_snapshot.reprovision_requested.clone();
+        for dpu in &self.dpu_snapshots {
+            if let Some(reprovision_requested) = dpu.reprovision_requested.as_ref() {
+                reprovision_request = Some(reprovision_requested.clone());
+            }
+        }
+        let (_, dpu_id_to_device_map) = self
+            .host_snapshot
+            .get_dpu_device_and_id_mappings()
+            .map_err(|e| {
+                RpcDataConversionError::InvalidValue(
+                    "dpu_id_to_device_map".to_string(),
+                    e.to_string(),
+                )
+            })?;
+        instance
+            .derive_status(
+                dpu_id_to_device_map,
+                self.managed_state.clone(),
+                reprovision_request,
+                self.host_snapshot.infiniband_status_observation.as_ref(),
+                self.host_snapshot.nvlink_status_observation.as_ref(),
+            )
+            .map(Some)
+    }
+
     /// Returns true if the desired managedhost networking configuration had been synced
     /// to **all** DPUs.
     pub fn managed_host_network_config_version_synced(&self) -> bool {
@@ -484,38 +523,13 @@ impl TryFrom<ManagedHostStateSnapshot> for Option<rpc::Instance> {
     type Error = RpcDataConversionError;
 
     fn try_from(mut snapshot: ManagedHostStateSnapshot) -> Result<Self, Self::Error> {
+        let Some(status) = snapshot.derive_instance_status()? else {
+            return Ok(None);
+        };
         let Some(instance) = snapshot.instance.take() else {
             return Ok(None);
         };
 
-        // TODO: If multiple DPUs have reprovisioning requested, we might not get
-        // the expected response
-        let mut reprovision_request = snapshot.host_snapshot.reprovision_requested.clone();
-        for dpu in &snapshot.dpu_snapshots {
-            if let Some(reprovision_requested) = dpu.reprovision_requested.as_ref() {
-                reprovision_request = Some(reprovision_requested.clone());
-            }
-        }
-        let (_, dpu_id_to_device_map) = snapshot
-            .host_snapshot
-            .get_dpu_device_and_id_mappings()
-            .map_err(|e| {
-                RpcDataConversionError::InvalidValue(
-                    "dpu_id_to_device_map".to_string(),
-                    e.to_string(),
-                )
-            })?;
-        let status = instance.derive_status(
-            dpu_id_to_device_map,
-            snapshot.managed_state.clone(),
-            reprovision_request,
-            snapshot
-                .host_snapshot
-                .infiniband_status_observation
-                .as_ref(),
-            snapshot.host_snapshot.nvlink_status_observation.as_ref(),
-        )?;
-
         Ok(Some(rpc::Instance {
             id: Some(instance.id),
             machine_id: Some(instance.machine_id),
@@ -657,6 +671,9 @@ pub struct Machine {
     /// Latest log parser health report received from the log parser
     pub log_parser_health_report: Option<HealthReport>,
 
+    /// Latest health report received from ssh-console's console monitor
+    pub console_health_report: Option<HealthReport>,
+
     /// Latest health report generated by validation tests
     pub machine_validation_health_report: HealthReport,
 
@@ -1697,6 +1714,9 @@ pub enum MachineState {
     WaitingForLockdown {
         lockdown_info: LockdownInfo,
     },
+    /// Syncing the mlxconfig profile bound to the host's SKU or instance type
+    /// before the host becomes Ready.
+    MlxConfigSync,
     // MachineValidating has been moved to ValidationState
 }
 
@@ -2725,6 +2745,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_deserialize_mlxconfig_sync_state() {
+        let serialized = r#"{"state":"hostinit","machine_state":{"state":"mlxconfigsync"}}"#;
+        let deserialized: ManagedHostState = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(
+            deserialized,
+            ManagedHostState::HostInit {
+                machine_state: MachineState::MlxConfigSync,
+            }
+        );
+    }
+
     #[test]
     fn test_json_deserialize_lockdown_states() {
         // Test Lockdown state
@@ -0,0 +1,264 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::protos::mlx_device as rpc;
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use libmlx::profile::serialization::SerializableProfile;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+/// An mlxconfig profile stored in the database
+#[derive(Clone, Debug)]
+pub struct StoredMlxConfigProfile {
+    pub name: String,
+    pub version: ConfigVersion,
+    pub description: Option<String>,
+    pub profile: SerializableProfile,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, PgRow> for StoredMlxConfigProfile {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let profile: sqlx::types::Json<SerializableProfile> = row.try_get("profile")?;
+        Ok(StoredMlxConfigProfile {
+            name: row.try_get("name")?,
+            version: row.try_get("version")?,
+            description: row.try_get("description")?,
+            profile: profile.0,
+            created: row.try_get("created")?,
+            updated: row.try_get("updated")?,
+        })
+    }
+}
+
+impl From<&StoredMlxConfigProfile> for rpc::ProfileSummary {
+    fn from(value: &StoredMlxConfigProfile) -> Self {
+        rpc::ProfileSummary {
+            name: value.name.clone(),
+            description: value.description.clone(),
+            registry_name: value.profile.registry_name.clone(),
+            variable_count: value.profile.config.len() as u32,
+            version: Some(value.version.to_string()),
+        }
+    }
+}
+
+/// What a stored mlxconfig profile is bound to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MlxConfigProfileTarget {
+    Sku(String),
+    InstanceType(InstanceTypeId),
+}
+
+impl std::fmt::Display for MlxConfigProfileTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MlxConfigProfileTarget::Sku(sku_id) => write!(f, "SKU {sku_id}"),
+            MlxConfigProfileTarget::InstanceType(id) => write!(f, "InstanceType {id}"),
+        }
+    }
+}
+
+impl TryFrom<rpc::MlxConfigProfileTarget> for MlxConfigProfileTarget {
+    type Error = RpcDataConversionError;
+
+    fn try_from(value: rpc::MlxConfigProfileTarget) -> Result<Self, Self::Error> {
+        match value.target {
+            Some(rpc::mlx_config_profile_target::Target::SkuId(sku_id)) => {
+                if sku_id.is_empty() {
+                    return Err(RpcDataConversionError::InvalidValue(
+                        "sku_id".to_string(),
+                        sku_id,
+                    ));
+                }
+                Ok(MlxConfigProfileTarget::Sku(sku_id))
+            }
+            Some(rpc::mlx_config_profile_target::Target::InstanceTypeId(id)) => id
+                .parse::<InstanceTypeId>()
+                .map(MlxConfigProfileTarget::InstanceType)
+                .map_err(|e| RpcDataConversionError::InvalidInstanceTypeId(e.value())),
+            None => Err(RpcDataConversionError::MissingArgument("target")),
+        }
+    }
+}
+
+impl From<MlxConfigProfileTarget> for rpc::MlxConfigProfileTarget {
+    fn from(value: MlxConfigProfileTarget) -> Self {
+        let target = match value {
+            MlxConfigProfileTarget::Sku(sku_id) => {
+                rpc::mlx_config_profile_target::Target::SkuId(sku_id)
+            }
+            MlxConfigProfileTarget::InstanceType(id) => {
+                rpc::mlx_config_profile_target::Target::InstanceTypeId(id.to_string())
+            }
+        };
+        rpc::MlxConfigProfileTarget {
+            target: Some(target),
+        }
+    }
+}
+
+/// Binds a stored mlxconfig profile to a SKU or an instance type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MlxConfigProfileBinding {
+    pub profile_name: String,
+    pub target: MlxConfigProfileTarget,
+}
+
+impl<'r> FromRow<'r, PgRow> for MlxConfigProfileBinding {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let sku_id: Option<String> = row.try_get("sku_id")?;
+        let instance_type_id: Option<InstanceTypeId> = row.try_get("instance_type_id")?;
+        let target = match (sku_id, instance_type_id) {
+            (Some(sku_id), None) => MlxConfigProfileTarget::Sku(sku_id),
+            (None, Some(id)) => MlxConfigProfileTarget::InstanceType(id),
+            _ => {
+                return Err(sqlx::Error::ColumnDecode {
+                    index: "sku_id".to_string(),
+                    source: "exactly one of sku_id and instance_type_id must be set".into(),
+                });
+            }
+        };
+        Ok(MlxConfigProfileBinding {
+            profile_name: row.try_get("profile_name")?,
+            target,
+        })
+    }
+}
+
+impl TryFrom<rpc::MlxConfigProfileBinding> for MlxConfigProfileBinding {
+    type Error = RpcDataConversionError;
+
+    fn try_from(value: rpc::MlxConfigProfileBinding) -> Result<Self, Self::Error> {
+        if value.profile_name.is_empty() {
+            return Err(RpcDataConversionError::MissingArgument("profile_name"));
+        }
+        let target = value
+            .target
+            .ok_or(RpcDataConversionError::MissingArgument("target"))?
+            .try_into()?;
+        Ok(MlxConfigProfileBinding {
+            profile_name: value.profile_name,
+            target,
+        })
+    }
+}
+
+impl From<MlxConfigProfileBinding> for rpc::MlxConfigProfileBinding {
+    fn from(value: MlxConfigProfileBinding) -> Self {
+        rpc::MlxConfigProfileBinding {
+            profile_name: value.profile_name,
+            target: Some(value.target.into()),
+        }
+    }
+}
+
+/// The outcome of the last profile sync scout reported for a host
+#[derive(Clone, Debug, FromRow)]
+pub struct MachineMlxConfigStatus {
+    pub machine_id: MachineId,
+    pub profile_name: String,
+    pub profile_version: ConfigVersion,
+    /// Whether every device matching the profile was synced successfully
+    pub synced: bool,
+    /// The number of variables changed across all devices. These changes
+    /// only take effect after a reboot.
+    pub variables_changed: i32,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl MachineMlxConfigStatus {
+    /// Returns whether the status was reported for the current version of `profile`
+    pub fn is_for(&self, profile: &StoredMlxConfigProfile) -> bool {
+        self.profile_name == profile.name && self.profile_version == profile.version
+    }
+
+    /// Returns whether the host is known to run the current version of `profile`,
+    /// with no pending changes that still require a reboot
+    pub fn is_in_sync_with(&self, profile: &StoredMlxConfigProfile) -> bool {
+        self.is_for(profile) && self.synced && self.variables_changed == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn stored_profile(version: ConfigVersion) -> StoredMlxConfigProfile {
+        StoredMlxConfigProfile {
+            name: "cx7-eth".to_string(),
+            version,
+            description: None,
+            profile: SerializableProfile::new("cx7-eth", "mlx_generic")
+                .with_config("LINK_TYPE_P1", "ETH"),
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_status_in_sync_with_profile() {
+        let profile = stored_profile(ConfigVersion::initial());
+        let mut status = MachineMlxConfigStatus {
+            machine_id: MachineId::from_str(
+                "fm100htes3rn1npvbtm5qd57dkilaag7ljugl1llmm7rfuq1ov50i0rpl30",
+            )
+            .unwrap(),
+            profile_name: profile.name.clone(),
+            profile_version: profile.version,
+            synced: true,
+            variables_changed: 0,
+            observed_at: Utc::now(),
+        };
+        assert!(status.is_in_sync_with(&profile));
+
+        // Changes applied by the sync still need a reboot
+        status.variables_changed = 2;
+        assert!(status.is_for(&profile));
+        assert!(!status.is_in_sync_with(&profile));
+
+        // An updated profile invalidates the previous sync
+        status.variables_changed = 0;
+        let updated = stored_profile(profile.version.increment());
+        assert!(!status.is_for(&updated));
+        assert!(!status.is_in_sync_with(&updated));
+    }
+
+    #[test]
+    fn test_binding_target_from_rpc() {
+        let target: MlxConfigProfileTarget = rpc::MlxConfigProfileTarget {
+            target: Some(rpc::mlx_config_profile_target::Target::SkuId(
+                "PowerEdge R760".to_string(),
+            )),
+        }
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            target,
+            MlxConfigProfileTarget::Sku("PowerEdge R760".to_string())
+        );
+
+        let err = MlxConfigProfileTarget::try_from(rpc::MlxConfigProfileTarget { target: None });
+        assert!(err.is_err());
+    }
+}
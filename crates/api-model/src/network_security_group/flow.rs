@@ -0,0 +1,361 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluation of synthetic flows against network security group rules.
+//!
+//! The evaluation follows the ACLs the DPU agent renders for NVUE:
+//! rules of a direction and IP version are ordered by priority, with ties
+//! kept in the order of the rule set, and the first matching rule decides.
+
+use std::net::IpAddr;
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+
+use super::{
+    NetworkSecurityGroupRule, NetworkSecurityGroupRuleDirection, NetworkSecurityGroupRuleProtocol,
+};
+
+/// NetworkSecurityGroupFlow describes a single new connection
+/// of an instance interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSecurityGroupFlow {
+    pub direction: NetworkSecurityGroupRuleDirection,
+    pub protocol: NetworkSecurityGroupRuleProtocol,
+    pub src_ip: IpAddr,
+    pub dst_ip: IpAddr,
+    pub src_port: Option<u32>,
+    pub dst_port: Option<u32>,
+}
+
+impl NetworkSecurityGroupFlow {
+    pub fn is_ipv6(&self) -> bool {
+        self.src_ip.is_ipv6()
+    }
+}
+
+impl TryFrom<&rpc::ExplainNetworkSecurityGroupFlowRequest> for NetworkSecurityGroupFlow {
+    type Error = RpcDataConversionError;
+
+    fn try_from(req: &rpc::ExplainNetworkSecurityGroupFlowRequest) -> Result<Self, Self::Error> {
+        let protocol = NetworkSecurityGroupRuleProtocol::try_from(req.protocol())?;
+
+        match protocol {
+            NetworkSecurityGroupRuleProtocol::Any => {
+                return Err(RpcDataConversionError::InvalidValue(
+                    "protocol".to_string(),
+                    "a flow must have a specific protocol".to_string(),
+                ));
+            }
+            NetworkSecurityGroupRuleProtocol::Tcp | NetworkSecurityGroupRuleProtocol::Udp => {
+                if req.src_port.is_none() || req.dst_port.is_none() {
+                    return Err(RpcDataConversionError::MissingArgument(
+                        "src_port and dst_port are required for TCP and UDP flows",
+                    ));
+                }
+            }
+            NetworkSecurityGroupRuleProtocol::Icmp | NetworkSecurityGroupRuleProtocol::Icmp6 => {
+                if req.src_port.is_some() || req.dst_port.is_some() {
+                    return Err(RpcDataConversionError::InvalidValue(
+                        "protocol".to_string(),
+                        format!("ports cannot be specified for `{protocol}` flows"),
+                    ));
+                }
+            }
+        }
+
+        if let Some(port) = [req.src_port, req.dst_port]
+            .into_iter()
+            .flatten()
+            .find(|p| *p > u16::MAX as u32)
+        {
+            return Err(RpcDataConversionError::InvalidValue(
+                "port".to_string(),
+                format!("{port} is not a valid port"),
+            ));
+        }
+
+        let src_ip: IpAddr = req
+            .src_ip
+            .parse()
+            .map_err(|_| RpcDataConversionError::InvalidIpAddress(req.src_ip.clone()))?;
+        let dst_ip: IpAddr = req
+            .dst_ip
+            .parse()
+            .map_err(|_| RpcDataConversionError::InvalidIpAddress(req.dst_ip.clone()))?;
+
+        if src_ip.is_ipv6() != dst_ip.is_ipv6() {
+            return Err(RpcDataConversionError::InvalidValue(
+                "dst_ip".to_string(),
+                "IP version of dst_ip does not match IP version of src_ip".to_string(),
+            ));
+        }
+
+        match (&protocol, src_ip.is_ipv6()) {
+            (NetworkSecurityGroupRuleProtocol::Icmp, true) => {
+                return Err(RpcDataConversionError::InvalidValue(
+                    "protocol".to_string(),
+                    "ICMP cannot be used with ipv6 flows".to_string(),
+                ));
+            }
+            (NetworkSecurityGroupRuleProtocol::Icmp6, false) => {
+                return Err(RpcDataConversionError::InvalidValue(
+                    "protocol".to_string(),
+                    "ICMP6 cannot be used with ipv4 flows".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(NetworkSecurityGroupFlow {
+            direction: req.direction().try_into()?,
+            protocol,
+            src_ip,
+            dst_ip,
+            src_port: req.src_port,
+            dst_port: req.dst_port,
+        })
+    }
+}
+
+/// Returns whether a port of a flow falls into the port range of a rule.
+/// A rule without a range matches any port.
+fn port_matches(start: Option<u32>, end: Option<u32>, port: Option<u32>) -> bool {
+    match (start, end) {
+        (Some(start), Some(end)) => port.is_some_and(|p| (start..=end).contains(&p)),
+        _ => true,
+    }
+}
+
+impl NetworkSecurityGroupRule {
+    /// Returns whether the rule matches a flow.
+    ///
+    /// The source and destination of the rule are passed as the prefixes
+    /// they expand to, since object references can only be expanded with
+    /// the current state of the referenced objects.
+    pub fn matches_flow(
+        &self,
+        flow: &NetworkSecurityGroupFlow,
+        src_prefixes: &[ipnetwork::IpNetwork],
+        dst_prefixes: &[ipnetwork::IpNetwork],
+    ) -> bool {
+        self.direction == flow.direction
+            && self.ipv6 == flow.is_ipv6()
+            && (self.protocol == NetworkSecurityGroupRuleProtocol::Any
+                || self.protocol == flow.protocol)
+            && port_matches(self.src_port_start, self.src_port_end, flow.src_port)
+            && port_matches(self.dst_port_start, self.dst_port_end, flow.dst_port)
+            && src_prefixes.iter().any(|p| p.contains(flow.src_ip))
+            && dst_prefixes.iter().any(|p| p.contains(flow.dst_ip))
+    }
+}
+
+/// Returns the index of the rule which decides a flow, or `None` if no rule
+/// matches and the default action applies.
+///
+/// * `rules` - The rules and the prefixes their source and destination expand to,
+///   in the order of the rule set.
+/// * `flow`  - The flow to evaluate
+pub fn find_matching_rule<'a, I>(rules: I, flow: &NetworkSecurityGroupFlow) -> Option<usize>
+where
+    I: IntoIterator<
+        Item = (
+            &'a NetworkSecurityGroupRule,
+            &'a [ipnetwork::IpNetwork],
+            &'a [ipnetwork::IpNetwork],
+        ),
+    >,
+{
+    let mut rules: Vec<_> = rules.into_iter().enumerate().collect();
+
+    // Same (stable) ordering as the DPU agent uses when rendering the ACLs
+    rules.sort_by_key(|(_, (rule, _, _))| rule.priority);
+
+    rules
+        .into_iter()
+        .find(|(_, (rule, src_prefixes, dst_prefixes))| {
+            rule.matches_flow(flow, src_prefixes, dst_prefixes)
+        })
+        .map(|(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{NetworkSecurityGroupRuleAction, NetworkSecurityGroupRuleNet};
+    use super::*;
+
+    fn rule(
+        id: &str,
+        priority: u32,
+        protocol: NetworkSecurityGroupRuleProtocol,
+        dst_ports: Option<(u32, u32)>,
+        action: NetworkSecurityGroupRuleAction,
+    ) -> NetworkSecurityGroupRule {
+        NetworkSecurityGroupRule {
+            id: Some(id.to_string()),
+            src_net: NetworkSecurityGroupRuleNet::Prefix("0.0.0.0/0".parse().unwrap()),
+            dst_net: NetworkSecurityGroupRuleNet::Prefix("0.0.0.0/0".parse().unwrap()),
+            direction: NetworkSecurityGroupRuleDirection::Ingress,
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: dst_ports.map(|p| p.0),
+            dst_port_end: dst_ports.map(|p| p.1),
+            protocol,
+            action,
+            priority,
+        }
+    }
+
+    fn tcp_flow(dst_port: u32) -> NetworkSecurityGroupFlow {
+        NetworkSecurityGroupFlow {
+            direction: NetworkSecurityGroupRuleDirection::Ingress,
+            protocol: NetworkSecurityGroupRuleProtocol::Tcp,
+            src_ip: "192.0.2.10".parse().unwrap(),
+            dst_ip: "198.51.100.5".parse().unwrap(),
+            src_port: Some(40000),
+            dst_port: Some(dst_port),
+        }
+    }
+
+    #[test]
+    fn test_find_matching_rule() {
+        let any: Vec<ipnetwork::IpNetwork> = vec!["0.0.0.0/0".parse().unwrap()];
+        let other: Vec<ipnetwork::IpNetwork> = vec!["203.0.113.0/24".parse().unwrap()];
+
+        let rules = [
+            rule(
+                "deny_all",
+                200,
+                NetworkSecurityGroupRuleProtocol::Any,
+                None,
+                NetworkSecurityGroupRuleAction::Deny,
+            ),
+            rule(
+                "permit_ssh",
+                100,
+                NetworkSecurityGroupRuleProtocol::Tcp,
+                Some((22, 22)),
+                NetworkSecurityGroupRuleAction::Permit,
+            ),
+            rule(
+                "permit_web_from_other",
+                50,
+                NetworkSecurityGroupRuleProtocol::Tcp,
+                Some((80, 443)),
+                NetworkSecurityGroupRuleAction::Permit,
+            ),
+        ];
+        let prefixes = [(&any, &any), (&any, &any), (&other, &any)];
+        let expanded = || {
+            rules
+                .iter()
+                .zip(prefixes.iter())
+                .map(|(r, (s, d))| (r, s.as_slice(), d.as_slice()))
+        };
+
+        // Lower priority values are evaluated first
+        assert_eq!(find_matching_rule(expanded(), &tcp_flow(22)), Some(1));
+        // The source of the flow isn't part of the expanded source prefixes
+        assert_eq!(find_matching_rule(expanded(), &tcp_flow(443)), Some(0));
+
+        // Egress flows don't match ingress rules
+        let mut egress = tcp_flow(22);
+        egress.direction = NetworkSecurityGroupRuleDirection::Egress;
+        assert_eq!(find_matching_rule(expanded(), &egress), None);
+
+        // IPv6 flows don't match IPv4 rules
+        let mut ipv6 = tcp_flow(22);
+        ipv6.src_ip = "2001:db8::1".parse().unwrap();
+        ipv6.dst_ip = "2001:db8::2".parse().unwrap();
+        assert_eq!(find_matching_rule(expanded(), &ipv6), None);
+
+        // Rules of the same priority keep the order of the rule set
+        let tied = [
+            rule(
+                "first",
+                10,
+                NetworkSecurityGroupRuleProtocol::Any,
+                None,
+                NetworkSecurityGroupRuleAction::Deny,
+            ),
+            rule(
+                "second",
+                10,
+                NetworkSecurityGroupRuleProtocol::Any,
+                None,
+                NetworkSecurityGroupRuleAction::Permit,
+            ),
+        ];
+        assert_eq!(
+            find_matching_rule(
+                tied.iter().map(|r| (r, any.as_slice(), any.as_slice())),
+                &tcp_flow(22)
+            ),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_flow_try_from_rpc() {
+        let req = rpc::ExplainNetworkSecurityGroupFlowRequest {
+            instance_id: None,
+            virtual_function_id: None,
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionEgress.into(),
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoUdp.into(),
+            src_ip: "192.0.2.10".to_string(),
+            dst_ip: "198.51.100.5".to_string(),
+            src_port: Some(5353),
+            dst_port: Some(53),
+        };
+
+        let flow = NetworkSecurityGroupFlow::try_from(&req).unwrap();
+        assert_eq!(flow.direction, NetworkSecurityGroupRuleDirection::Egress);
+        assert_eq!(flow.protocol, NetworkSecurityGroupRuleProtocol::Udp);
+        assert_eq!(flow.dst_port, Some(53));
+
+        // Invalid flows
+        for req in [
+            rpc::ExplainNetworkSecurityGroupFlowRequest {
+                protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+                ..req.clone()
+            },
+            rpc::ExplainNetworkSecurityGroupFlowRequest {
+                dst_port: None,
+                ..req.clone()
+            },
+            rpc::ExplainNetworkSecurityGroupFlowRequest {
+                protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp.into(),
+                ..req.clone()
+            },
+            rpc::ExplainNetworkSecurityGroupFlowRequest {
+                dst_port: Some(70000),
+                ..req.clone()
+            },
+            rpc::ExplainNetworkSecurityGroupFlowRequest {
+                dst_ip: "2001:db8::2".to_string(),
+                ..req.clone()
+            },
+            rpc::ExplainNetworkSecurityGroupFlowRequest {
+                src_ip: "not-an-ip".to_string(),
+                ..req.clone()
+            },
+        ] {
+            assert!(NetworkSecurityGroupFlow::try_from(&req).is_err());
+        }
+    }
+}
@@ -20,8 +20,9 @@ use std::fmt;
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
 use carbide_uuid::instance::InstanceId;
+use carbide_uuid::network::NetworkSegmentId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
-use carbide_uuid::vpc::VpcId;
+use carbide_uuid::vpc::{VpcId, VpcPrefixId};
 use chrono::prelude::*;
 use config_version::ConfigVersion;
 use ipnetwork;
@@ -33,6 +34,8 @@ use uuid::Uuid;
 use super::tenant::TenantOrganizationId;
 use crate::metadata::Metadata;
 
+pub mod flow;
+
 /// The maximum priority value allowed for security group rule.
 /// We could expose this in config and validate it in the API
 /// handlers, but it's based on the hard limit of the field in
@@ -371,29 +374,86 @@ impl TryFrom<rpc::NetworkSecurityGroupRuleAction> for NetworkSecurityGroupRuleAc
 /// network traffic. It can be either an explicit prefix
 /// or defined by an object ID.
 ///
+/// Object references are expanded into the prefixes of the
+/// object when the rules are sent to the DPU. Since the expanded
+/// rule set can grow when prefixes are added to the referenced
+/// object without the NSG ever being touched, the expansion is
+/// re-checked against the NSG size limit whenever VPC prefixes
+/// change, and a tenant can only reference VPCs they own or
+/// have peered with one of their VPCs.
+///
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum NetworkSecurityGroupRuleNet {
     Prefix(ipnetwork::IpNetwork),
-    // Not yet supported.  We hide this in the proto spec.
-    // Implementation wouldn't be hard, but it would be hard
-    // to manage operationally because this would allow users
-    // to create a rule that does not exceed any limits but
-    // later silently exceeds limits by adding more prefixes
-    // to a VPC without ever touching the NSG.  It would be
-    // dangerous/irresponsible to simply allow the overflow
-    // (the behavior on the DPU could be undefined), or trim
-    // out anything beyond our limits.
-    // We either need some job checking the state of things
-    // and alerting, or the DPU needs to alert when limits
-    // are exceeded, but the damage is already done by the
-    // time something is alerting.
-    // This also perhaps opens a security issue unless we
-    // restrict the prefixes that a tenant is allowed to add
-    // to a VPC.  Otherwise, If I allow (VPC ID 123), then
-    // it means I allow whatever random prefixes the owner
-    // of that VPC attaches.  It implicitly hands off part of
-    // "my" ACL control over to the other side.
-    // VpcId(VpcId),
+    /// All VPC prefixes and network segment prefixes of a VPC
+    VpcId(VpcId),
+    /// A single VPC prefix
+    VpcPrefixId(VpcPrefixId),
+    /// All prefixes of a network segment
+    NetworkSegmentId(NetworkSegmentId),
+}
+
+impl NetworkSecurityGroupRuleNet {
+    /// Returns the literal prefix of the net, if it isn't an object reference
+    pub fn prefix(&self) -> Option<&ipnetwork::IpNetwork> {
+        match self {
+            NetworkSecurityGroupRuleNet::Prefix(p) => Some(p),
+            NetworkSecurityGroupRuleNet::VpcId(_)
+            | NetworkSecurityGroupRuleNet::VpcPrefixId(_)
+            | NetworkSecurityGroupRuleNet::NetworkSegmentId(_) => None,
+        }
+    }
+
+    /// Whether the net references an object which needs to be expanded
+    /// into prefixes
+    pub fn is_object_reference(&self) -> bool {
+        self.prefix().is_none()
+    }
+}
+
+impl fmt::Display for NetworkSecurityGroupRuleNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkSecurityGroupRuleNet::Prefix(p) => write!(f, "{p}"),
+            NetworkSecurityGroupRuleNet::VpcId(id) => write!(f, "vpc:{id}"),
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => write!(f, "vpc_prefix:{id}"),
+            NetworkSecurityGroupRuleNet::NetworkSegmentId(id) => {
+                write!(f, "network_segment:{id}")
+            }
+        }
+    }
+}
+
+fn parse_net_prefix(p: &str) -> Result<NetworkSecurityGroupRuleNet, RpcDataConversionError> {
+    Ok(NetworkSecurityGroupRuleNet::Prefix(
+        p.parse::<ipnetwork::IpNetwork>()
+            .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
+    ))
+}
+
+fn parse_net_vpc_id(id: &str) -> Result<NetworkSecurityGroupRuleNet, RpcDataConversionError> {
+    Ok(NetworkSecurityGroupRuleNet::VpcId(
+        id.parse::<VpcId>()
+            .map_err(|_| RpcDataConversionError::InvalidVpcId(id.to_string()))?,
+    ))
+}
+
+fn parse_net_vpc_prefix_id(
+    id: &str,
+) -> Result<NetworkSecurityGroupRuleNet, RpcDataConversionError> {
+    Ok(NetworkSecurityGroupRuleNet::VpcPrefixId(
+        id.parse::<VpcPrefixId>()
+            .map_err(|_| RpcDataConversionError::InvalidUuid("VpcPrefixId", id.to_string()))?,
+    ))
+}
+
+fn parse_net_network_segment_id(
+    id: &str,
+) -> Result<NetworkSecurityGroupRuleNet, RpcDataConversionError> {
+    Ok(NetworkSecurityGroupRuleNet::NetworkSegmentId(
+        id.parse::<NetworkSegmentId>()
+            .map_err(|_| RpcDataConversionError::InvalidNetworkSegmentId(id.to_string()))?,
+    ))
 }
 
 impl TryFrom<rpc::network_security_group_rule_attributes::SourceNet>
@@ -404,13 +464,12 @@ impl TryFrom<rpc::network_security_group_rule_attributes::SourceNet>
     fn try_from(
         net: rpc::network_security_group_rule_attributes::SourceNet,
     ) -> Result<Self, Self::Error> {
+        use rpc::network_security_group_rule_attributes::SourceNet;
         match net {
-            rpc::network_security_group_rule_attributes::SourceNet::SrcPrefix(p) => {
-                Ok(NetworkSecurityGroupRuleNet::Prefix(
-                    p.parse::<ipnetwork::IpNetwork>()
-                        .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
-                ))
-            }
+            SourceNet::SrcPrefix(p) => parse_net_prefix(&p),
+            SourceNet::SrcVpcId(id) => parse_net_vpc_id(&id),
+            SourceNet::SrcVpcPrefixId(id) => parse_net_vpc_prefix_id(&id),
+            SourceNet::SrcNetworkSegmentId(id) => parse_net_network_segment_id(&id),
         }
     }
 }
@@ -423,13 +482,12 @@ impl TryFrom<rpc::network_security_group_rule_attributes::DestinationNet>
     fn try_from(
         net: rpc::network_security_group_rule_attributes::DestinationNet,
     ) -> Result<Self, Self::Error> {
+        use rpc::network_security_group_rule_attributes::DestinationNet;
         match net {
-            rpc::network_security_group_rule_attributes::DestinationNet::DstPrefix(p) => {
-                Ok(NetworkSecurityGroupRuleNet::Prefix(
-                    p.parse::<ipnetwork::IpNetwork>()
-                        .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
-                ))
-            }
+            DestinationNet::DstPrefix(p) => parse_net_prefix(&p),
+            DestinationNet::DstVpcId(id) => parse_net_vpc_id(&id),
+            DestinationNet::DstVpcPrefixId(id) => parse_net_vpc_prefix_id(&id),
+            DestinationNet::DstNetworkSegmentId(id) => parse_net_network_segment_id(&id),
         }
     }
 }
@@ -440,11 +498,17 @@ impl TryFrom<NetworkSecurityGroupRuleNet>
     type Error = RpcDataConversionError;
 
     fn try_from(net: NetworkSecurityGroupRuleNet) -> Result<Self, Self::Error> {
-        match net {
-            NetworkSecurityGroupRuleNet::Prefix(p) => Ok(
-                rpc::network_security_group_rule_attributes::SourceNet::SrcPrefix(p.to_string()),
-            ),
-        }
+        use rpc::network_security_group_rule_attributes::SourceNet;
+        Ok(match net {
+            NetworkSecurityGroupRuleNet::Prefix(p) => SourceNet::SrcPrefix(p.to_string()),
+            NetworkSecurityGroupRuleNet::VpcId(id) => SourceNet::SrcVpcId(id.to_string()),
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => {
+                SourceNet::SrcVpcPrefixId(id.to_string())
+            }
+            NetworkSecurityGroupRuleNet::NetworkSegmentId(id) => {
+                SourceNet::SrcNetworkSegmentId(id.to_string())
+            }
+        })
     }
 }
 
@@ -454,15 +518,20 @@ impl TryFrom<NetworkSecurityGroupRuleNet>
     type Error = RpcDataConversionError;
 
     fn try_from(net: NetworkSecurityGroupRuleNet) -> Result<Self, Self::Error> {
-        match net {
-            NetworkSecurityGroupRuleNet::Prefix(p) => Ok(
-                rpc::network_security_group_rule_attributes::DestinationNet::DstPrefix(
-                    p.to_string(),
-                ),
-            ),
-        }
+        use rpc::network_security_group_rule_attributes::DestinationNet;
+        Ok(match net {
+            NetworkSecurityGroupRuleNet::Prefix(p) => DestinationNet::DstPrefix(p.to_string()),
+            NetworkSecurityGroupRuleNet::VpcId(id) => DestinationNet::DstVpcId(id.to_string()),
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => {
+                DestinationNet::DstVpcPrefixId(id.to_string())
+            }
+            NetworkSecurityGroupRuleNet::NetworkSegmentId(id) => {
+                DestinationNet::DstNetworkSegmentId(id.to_string())
+            }
+        })
     }
 }
+
 /* ********************************** */
 /*       NetworkSecurityGroupRule     */
 /* ********************************** */
@@ -596,23 +665,25 @@ impl TryFrom<rpc::NetworkSecurityGroupRuleAttributes> for NetworkSecurityGroupRu
 
         // If prefix is used for src or dst, IP version must match rule ipv6 value.
         // This also implicitly ensures that src and dst are the same IP version.
-        match (&converted_rule.src_net, &converted_rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(s), NetworkSecurityGroupRuleNet::Prefix(d)) => {
-                if s.is_ipv6() != converted_rule.ipv6 {
-                    return Err(RpcDataConversionError::InvalidValue(
-                        "src_prefix".to_string(),
-                        "IP version of prefix does not match IP version of rule".to_string(),
-                    ));
-                }
+        // Object references are expanded into the prefixes of the IP version
+        // of the rule, so there is nothing to check for them here.
+        if let Some(s) = converted_rule.src_net.prefix()
+            && s.is_ipv6() != converted_rule.ipv6
+        {
+            return Err(RpcDataConversionError::InvalidValue(
+                "src_prefix".to_string(),
+                "IP version of prefix does not match IP version of rule".to_string(),
+            ));
+        }
 
-                if d.is_ipv6() != converted_rule.ipv6 {
-                    return Err(RpcDataConversionError::InvalidValue(
-                        "dst_prefix".to_string(),
-                        "IP version of prefix does not match IP version of rule".to_string(),
-                    ));
-                }
-            }
-        };
+        if let Some(d) = converted_rule.dst_net.prefix()
+            && d.is_ipv6() != converted_rule.ipv6
+        {
+            return Err(RpcDataConversionError::InvalidValue(
+                "dst_prefix".to_string(),
+                "IP version of prefix does not match IP version of rule".to_string(),
+            ));
+        }
 
         Ok(converted_rule)
     }
@@ -757,6 +828,10 @@ pub struct NetworkSecurityGroupPropagationObjectStatus {
     pub interfaces_applied: u32,
     pub related_instance_ids: Vec<InstanceId>,
     pub unpropagated_instance_ids: Vec<InstanceId>,
+    /// Set if the object references in the rules of the NSG
+    /// expand to more rules than allowed. The DPUs keep their
+    /// previous rules until this is resolved.
+    pub expansion_error: Option<String>,
 }
 
 impl From<NetworkSecurityGroupPropagationObjectStatus>
@@ -764,7 +839,12 @@ impl From<NetworkSecurityGroupPropagationObjectStatus>
 {
     fn from(status: NetworkSecurityGroupPropagationObjectStatus) -> Self {
         let (status_type, details) = {
-            if status.interfaces_applied == status.interfaces_expected {
+            if let Some(error) = status.expansion_error {
+                (
+                    rpc::NetworkSecurityGroupPropagationStatus::NsgPropStatusError,
+                    Some(error),
+                )
+            } else if status.interfaces_applied == status.interfaces_expected {
                 (
                     rpc::NetworkSecurityGroupPropagationStatus::NsgPropStatusFull,
                     None,
@@ -871,6 +951,7 @@ impl<'r> sqlx::FromRow<'r, PgRow> for NetworkSecurityGroupPropagationObjectStatu
                 .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
             related_instance_ids: related_instance_ids.0,
             unpropagated_instance_ids: unpropagated_instance_ids.0,
+            expansion_error: row.try_get("expansion_error")?,
         })
     }
 }
@@ -935,6 +1016,7 @@ mod tests {
             interfaces_applied: 0,
             unpropagated_instance_ids: vec![],
             related_instance_ids: vec![],
+            expansion_error: None,
         };
 
         assert_eq!(
@@ -962,6 +1044,7 @@ mod tests {
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
             unpropagated_instance_ids: vec![],
+            expansion_error: None,
         };
 
         assert_eq!(
@@ -992,6 +1075,7 @@ mod tests {
             unpropagated_instance_ids: vec![
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
+            expansion_error: None,
         };
 
         assert_eq!(
@@ -1020,6 +1104,7 @@ mod tests {
                 "200f1043-1653-426d-bd0e-97f5b06bdb3f".parse().unwrap(),
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
+            expansion_error: None,
         };
 
         assert_eq!(
@@ -1048,6 +1133,30 @@ mod tests {
                 "200f1043-1653-426d-bd0e-97f5b06bdb3f".parse().unwrap(),
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
+            expansion_error: None,
+        };
+
+        assert_eq!(
+            req_type,
+            rpc::NetworkSecurityGroupPropagationObjectStatus::from(status)
+        );
+
+        // Error, which takes precedence over interface counts
+        let req_type = rpc::NetworkSecurityGroupPropagationObjectStatus {
+            id: "any_id".to_string(),
+            status: rpc::NetworkSecurityGroupPropagationStatus::NsgPropStatusError.into(),
+            details: Some("expanded rule count 300 exceeds limit 200".to_string()),
+            related_instance_ids: vec!["200f1043-1653-426d-bd0e-97f5b06bdb3f".to_string()],
+            unpropagated_instance_ids: vec![],
+        };
+
+        let status = NetworkSecurityGroupPropagationObjectStatus {
+            id: "any_id".to_string(),
+            interfaces_expected: 1,
+            interfaces_applied: 1,
+            related_instance_ids: vec!["200f1043-1653-426d-bd0e-97f5b06bdb3f".parse().unwrap()],
+            unpropagated_instance_ids: vec![],
+            expansion_error: Some("expanded rule count 300 exceeds limit 200".to_string()),
         };
 
         assert_eq!(
@@ -1313,6 +1422,106 @@ mod tests {
             ),
         };
         NetworkSecurityGroupRule::try_from(req).unwrap_err();
+
+        // Invalid object references should fail
+        let req = rpc::NetworkSecurityGroupRuleAttributes {
+            id: Some("anything".to_string()),
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: None,
+            dst_port_end: None,
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+            action: rpc::NetworkSecurityGroupRuleAction::NsgRuleActionDeny.into(),
+            priority: 9001,
+            source_net: Some(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcVpcId(
+                    "not-a-vpc".to_string(),
+                ),
+            ),
+            destination_net: Some(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                    "0.0.0.0/0".to_string(),
+                ),
+            ),
+        };
+        NetworkSecurityGroupRule::try_from(req).unwrap_err();
+
+        // Prefix sides of rules with object references are still checked
+        let req = rpc::NetworkSecurityGroupRuleAttributes {
+            id: Some("anything".to_string()),
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+            ipv6: true,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: None,
+            dst_port_end: None,
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+            action: rpc::NetworkSecurityGroupRuleAction::NsgRuleActionDeny.into(),
+            priority: 9001,
+            source_net: Some(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcNetworkSegmentId(
+                    "91609f10-c91d-470d-a260-6293ea0c1200".to_string(),
+                ),
+            ),
+            destination_net: Some(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                    "0.0.0.0/0".to_string(),
+                ),
+            ),
+        };
+        NetworkSecurityGroupRule::try_from(req).unwrap_err();
+    }
+
+    #[test]
+    fn test_rpc_rule_with_object_references_round_trip() {
+        let req = rpc::NetworkSecurityGroupRuleAttributes {
+            id: Some("anything".to_string()),
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionEgress.into(),
+            ipv6: true,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: Some(443),
+            dst_port_end: Some(443),
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            action: rpc::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+            priority: 9001,
+            source_net: Some(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcVpcPrefixId(
+                    "60d92a18-e56b-11ef-8ecd-ef90f290abf4".to_string(),
+                ),
+            ),
+            destination_net: Some(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstVpcId(
+                    "200f1043-1653-426d-bd0e-97f5b06bdb3f".to_string(),
+                ),
+            ),
+        };
+
+        let rule = NetworkSecurityGroupRule::try_from(req.clone()).unwrap();
+        assert_eq!(
+            rule.src_net,
+            NetworkSecurityGroupRuleNet::VpcPrefixId(
+                "60d92a18-e56b-11ef-8ecd-ef90f290abf4".parse().unwrap()
+            )
+        );
+        assert_eq!(
+            rule.dst_net,
+            NetworkSecurityGroupRuleNet::VpcId(
+                "200f1043-1653-426d-bd0e-97f5b06bdb3f".parse().unwrap()
+            )
+        );
+        assert!(rule.src_net.is_object_reference());
+
+        // Rules are persisted as JSON
+        let json = serde_json::to_string(&rule).unwrap();
+        let rule: NetworkSecurityGroupRule = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            req,
+            rpc::NetworkSecurityGroupRuleAttributes::try_from(rule).unwrap()
+        );
     }
 
     #[test]
@@ -15,19 +15,24 @@
  * limitations under the License.
  */
 
+use std::collections::BTreeMap;
+
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
 use carbide_uuid::power_shelf::PowerShelfId;
 use chrono::prelude::*;
 use config_version::{ConfigVersion, Versioned};
+use health_report::HealthReport;
 use serde::{Deserialize, Serialize};
 use sqlx::postgres::PgRow;
 use sqlx::{FromRow, Row};
 
 use crate::StateSla;
 use crate::controller_outcome::PersistentStateHandlerOutcome;
+use crate::power_shelf::power_supply::{PowerRedundancyStatus, PowerSupplyStatus};
 
 pub mod power_shelf_id;
+pub mod power_supply;
 pub mod slas;
 
 #[derive(Debug, Clone)]
@@ -65,11 +70,35 @@ pub struct PowerShelfConfig {
     pub location: Option<String>, // Physical location
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PowerShelfStatus {
     pub shelf_name: String,
     pub power_state: String,   // "on", "off", "standby"
     pub health_status: String, // "ok", "warning", "critical"
+    /// Chassis serial number reported by the power shelf BMC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial_number: Option<String>,
+    /// Firmware inventory reported by the power shelf BMC, keyed by inventory ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub firmware_versions: BTreeMap<String, String>,
+    /// Power supply units of the shelf
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub power_supplies: Vec<PowerSupplyStatus>,
+    /// Power supply redundancy as reported by the shelf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redundancy: Option<PowerRedundancyStatus>,
+    /// Alerts raised by the last health check of the state controller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_report: Option<HealthReport>,
+    /// The last time the state controller queried the power shelf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_health_check: Option<DateTime<Utc>>,
+}
+
+impl PowerShelfStatus {
+    pub const HEALTH_OK: &str = "ok";
+    pub const HEALTH_WARNING: &str = "warning";
+    pub const HEALTH_CRITICAL: &str = "critical";
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,285 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Power supply inventory of a power shelf, as reported by the Redfish `Power`
+//! resource of the shelf chassis.
+
+use serde::{Deserialize, Serialize};
+
+/// Redfish `Status.Health` value of a healthy resource
+const REDFISH_HEALTH_OK: &str = "OK";
+
+/// Redfish `Status.Health` value of a resource which requires immediate attention
+const REDFISH_HEALTH_CRITICAL: &str = "Critical";
+
+/// Redfish `Status.State` values of resources which are not able to deliver power
+const REDFISH_INACTIVE_STATES: &[&str] = &["Absent", "Disabled", "UnavailableOffline"];
+
+/// A single power supply unit (PSU) of a power shelf
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PowerSupplyStatus {
+    /// The Redfish `MemberId` of the PSU
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware_version: Option<String>,
+    /// Redfish `Status.State`, e.g. `Enabled` or `Absent`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Redfish `Status.Health`: `OK`, `Warning` or `Critical`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_output_watts: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_capacity_watts: Option<f64>,
+}
+
+impl PowerSupplyStatus {
+    /// Returns whether the PSU is present and reports healthy
+    pub fn is_healthy(&self) -> bool {
+        let active = self
+            .state
+            .as_deref()
+            .is_none_or(|state| !REDFISH_INACTIVE_STATES.contains(&state));
+        let healthy = self
+            .health
+            .as_deref()
+            .is_some_and(|health| health.eq_ignore_ascii_case(REDFISH_HEALTH_OK));
+        active && healthy
+    }
+}
+
+/// The redundancy group of the power supplies in a power shelf
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerRedundancyStatus {
+    /// Redfish redundancy mode, e.g. `N+m`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// The minimum number of PSUs that are needed to carry the load
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_num_needed: Option<u32>,
+    /// Redfish `Status.Health` of the redundancy group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
+}
+
+impl PowerRedundancyStatus {
+    /// Returns whether the shelf reports that redundancy is intact
+    pub fn is_healthy(&self) -> bool {
+        self.health
+            .as_deref()
+            .is_none_or(|health| health.eq_ignore_ascii_case(REDFISH_HEALTH_OK))
+    }
+
+    /// Returns whether the shelf reports that the remaining PSUs can no longer
+    /// carry the load with redundancy. A degraded (`Warning`) redundancy group
+    /// is not considered lost.
+    pub fn is_lost(&self) -> bool {
+        self.health
+            .as_deref()
+            .is_some_and(|health| health.eq_ignore_ascii_case(REDFISH_HEALTH_CRITICAL))
+    }
+}
+
+/// Power supplies and redundancy parsed from a Redfish `Power` resource
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PowerSupplyInventory {
+    pub power_supplies: Vec<PowerSupplyStatus>,
+    pub redundancy: Option<PowerRedundancyStatus>,
+}
+
+impl PowerSupplyInventory {
+    /// Parses the JSON representation of a Redfish `Power` resource.
+    ///
+    /// Only `PowerSupplies` and `Redundancy` are taken into account. Fields
+    /// which are missing or `null` are left empty.
+    pub fn from_redfish_power(power: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        let power = RedfishPower::deserialize(power)?;
+
+        let power_supplies = power
+            .power_supplies
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(index, psu)| {
+                let status = psu.status.unwrap_or_default();
+                PowerSupplyStatus {
+                    id: psu.member_id.unwrap_or_else(|| index.to_string()),
+                    name: psu.name,
+                    serial_number: psu.serial_number.map(|serial| serial.trim().to_string()),
+                    firmware_version: psu.firmware_version,
+                    state: status.state,
+                    health: status.health,
+                    power_output_watts: psu.last_power_output_watts,
+                    power_capacity_watts: psu.power_capacity_watts,
+                }
+            })
+            .collect();
+
+        let redundancy = power
+            .redundancy
+            .and_then(|redundancy| redundancy.into_iter().next())
+            .map(|redundancy| PowerRedundancyStatus {
+                mode: redundancy.mode,
+                min_num_needed: redundancy.min_num_needed,
+                health: redundancy.status.unwrap_or_default().health,
+            });
+
+        Ok(Self {
+            power_supplies,
+            redundancy,
+        })
+    }
+
+    /// The number of PSUs which are present and healthy
+    pub fn healthy_count(&self) -> usize {
+        self.power_supplies
+            .iter()
+            .filter(|psu| psu.is_healthy())
+            .count()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RedfishPower {
+    #[serde(default)]
+    power_supplies: Option<Vec<RedfishPowerSupply>>,
+    #[serde(default)]
+    redundancy: Option<Vec<RedfishRedundancy>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RedfishPowerSupply {
+    #[serde(default)]
+    member_id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    serial_number: Option<String>,
+    #[serde(default)]
+    firmware_version: Option<String>,
+    #[serde(default)]
+    last_power_output_watts: Option<f64>,
+    #[serde(default)]
+    power_capacity_watts: Option<f64>,
+    #[serde(default)]
+    status: Option<RedfishStatus>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RedfishRedundancy {
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    min_num_needed: Option<u32>,
+    #[serde(default)]
+    status: Option<RedfishStatus>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RedfishStatus {
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    health: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_redfish_power() {
+        let power = json!({
+            "@odata.id": "/redfish/v1/Chassis/powershelf/Power",
+            "Id": "Power",
+            "PowerSupplies": [
+                {
+                    "MemberId": "0",
+                    "Name": "PSU0",
+                    "SerialNumber": " PSU-SN-0 ",
+                    "FirmwareVersion": "02.01.05",
+                    "LastPowerOutputWatts": 1200.0,
+                    "PowerCapacityWatts": 5500.0,
+                    "Status": { "State": "Enabled", "Health": "OK" }
+                },
+                {
+                    "MemberId": "1",
+                    "Name": "PSU1",
+                    "LastPowerOutputWatts": null,
+                    "Status": { "State": "Enabled", "Health": "Critical" }
+                },
+                {
+                    "MemberId": "2",
+                    "Status": { "State": "Absent", "Health": "OK" }
+                }
+            ],
+            "Redundancy": [
+                {
+                    "MemberId": "0",
+                    "Mode": "N+m",
+                    "MinNumNeeded": 2,
+                    "Status": { "State": "Enabled", "Health": "Warning" }
+                }
+            ]
+        });
+
+        let inventory = PowerSupplyInventory::from_redfish_power(&power).unwrap();
+        assert_eq!(inventory.power_supplies.len(), 3);
+        assert_eq!(
+            inventory.power_supplies[0],
+            PowerSupplyStatus {
+                id: "0".to_string(),
+                name: Some("PSU0".to_string()),
+                serial_number: Some("PSU-SN-0".to_string()),
+                firmware_version: Some("02.01.05".to_string()),
+                state: Some("Enabled".to_string()),
+                health: Some("OK".to_string()),
+                power_output_watts: Some(1200.0),
+                power_capacity_watts: Some(5500.0),
+            }
+        );
+        assert!(inventory.power_supplies[0].is_healthy());
+        assert!(!inventory.power_supplies[1].is_healthy());
+        assert!(!inventory.power_supplies[2].is_healthy());
+        assert_eq!(inventory.healthy_count(), 1);
+
+        let redundancy = inventory.redundancy.unwrap();
+        assert_eq!(redundancy.min_num_needed, Some(2));
+        assert!(!redundancy.is_healthy());
+        assert!(!redundancy.is_lost());
+    }
+
+    #[test]
+    fn parse_redfish_power_without_power_supplies() {
+        let inventory = PowerSupplyInventory::from_redfish_power(&json!({
+            "PowerSupplies": null,
+        }))
+        .unwrap();
+        assert_eq!(inventory, PowerSupplyInventory::default());
+    }
+}
@@ -39,6 +39,8 @@ pub struct Rack {
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
     pub deleted: Option<DateTime<Utc>>,
+    pub firmware_upgrade: Option<RackFirmwareUpgrade>,
+    pub power_sequence: Option<RackPowerSequence>,
 }
 
 impl From<Rack> for rpc::forge::Rack {
@@ -64,6 +66,8 @@ impl From<Rack> for rpc::forge::Rack {
             created: Some(Timestamp::from(value.created)),
             updated: Some(Timestamp::from(value.updated)),
             deleted: value.deleted.map(Timestamp::from),
+            firmware_upgrade: value.firmware_upgrade.map(Into::into),
+            power_sequence: value.power_sequence.map(Into::into),
         }
     }
 }
@@ -74,6 +78,10 @@ impl<'r> FromRow<'r, PgRow> for Rack {
         let controller_state: sqlx::types::Json<RackState> = row.try_get("controller_state")?;
         let controller_state_outcome: Option<sqlx::types::Json<PersistentStateHandlerOutcome>> =
             row.try_get("controller_state_outcome").ok();
+        let firmware_upgrade: Option<sqlx::types::Json<RackFirmwareUpgrade>> =
+            row.try_get("firmware_upgrade")?;
+        let power_sequence: Option<sqlx::types::Json<RackPowerSequence>> =
+            row.try_get("power_sequence")?;
         Ok(Rack {
             id: row.try_get("id")?,
             config: config.0,
@@ -85,6 +93,8 @@ impl<'r> FromRow<'r, PgRow> for Rack {
             created: row.try_get("created")?,
             updated: row.try_get("updated")?,
             deleted: row.try_get("deleted")?,
+            firmware_upgrade: firmware_upgrade.map(|u| u.0),
+            power_sequence: power_sequence.map(|p| p.0),
         })
     }
 }
@@ -224,6 +234,323 @@ pub struct RackConfig {
     pub expected_power_shelves: Vec<MacAddress>,
 }
 
+/// Firmware upgrade of a rack which was requested via `ApplyRackFirmware`.
+/// The rack state controller applies it in the `FirmwareUpgrade` maintenance
+/// states and records the progress of every device here.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RackFirmwareUpgrade {
+    pub firmware_id: String,
+    // "dev" or "prod"
+    pub firmware_type: String,
+    /// Maximum number of compute trays which are reprovisioned at the same time
+    pub max_unavailable: usize,
+    pub requested_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub devices: Vec<RackDeviceFirmwareUpgrade>,
+}
+
+impl RackFirmwareUpgrade {
+    pub fn devices_of_type(
+        &self,
+        device_type: RackDeviceType,
+    ) -> impl Iterator<Item = &RackDeviceFirmwareUpgrade> {
+        self.devices
+            .iter()
+            .filter(move |d| d.device_type == device_type)
+    }
+
+    /// Describes all devices whose upgrade failed, used as cause of the rack
+    /// error state
+    pub fn failure_report(&self) -> Option<String> {
+        let failures: Vec<String> = self
+            .devices
+            .iter()
+            .filter(|d| d.status == RackDeviceFirmwareUpgradeStatus::Failed)
+            .map(|d| {
+                format!(
+                    "{} {}: {}",
+                    d.device_type,
+                    d.device_id,
+                    d.message.as_deref().unwrap_or("unknown error")
+                )
+            })
+            .collect();
+        if failures.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Firmware upgrade {} failed for {} device(s): {}",
+            self.firmware_id,
+            failures.len(),
+            failures.join("; ")
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RackDeviceFirmwareUpgrade {
+    pub device_id: String,
+    pub device_type: RackDeviceType,
+    /// Firmware components of the firmware set which apply to this device
+    pub components: Vec<RackFirmwareComponent>,
+    pub status: RackDeviceFirmwareUpgradeStatus,
+    pub message: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl RackDeviceFirmwareUpgrade {
+    pub fn start(&mut self) {
+        self.status = RackDeviceFirmwareUpgradeStatus::InProgress;
+        self.started_at = Some(Utc::now());
+    }
+
+    pub fn complete(&mut self) {
+        self.status = RackDeviceFirmwareUpgradeStatus::Completed;
+        self.finished_at = Some(Utc::now());
+    }
+
+    pub fn fail(&mut self, message: String) {
+        self.status = RackDeviceFirmwareUpgradeStatus::Failed;
+        self.message = Some(message);
+        self.finished_at = Some(Utc::now());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RackFirmwareComponent {
+    pub name: String,
+    pub filename: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RackDeviceType {
+    ComputeTray,
+    Switch,
+    PowerShelf,
+}
+
+impl Display for RackDeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RackDeviceFirmwareUpgradeStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Failed,
+}
+
+impl Display for RackDeviceFirmwareUpgradeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl From<RackFirmwareUpgrade> for rpc::forge::RackFirmwareUpgradeStatus {
+    fn from(value: RackFirmwareUpgrade) -> Self {
+        rpc::forge::RackFirmwareUpgradeStatus {
+            firmware_id: value.firmware_id,
+            firmware_type: value.firmware_type,
+            max_unavailable: value.max_unavailable as u32,
+            requested_at: Some(Timestamp::from(value.requested_at)),
+            started_at: value.started_at.map(Timestamp::from),
+            completed_at: value.completed_at.map(Timestamp::from),
+            devices: value
+                .devices
+                .into_iter()
+                .map(|d| rpc::forge::RackDeviceFirmwareUpgradeStatus {
+                    device_id: d.device_id,
+                    device_type: d.device_type.to_string(),
+                    status: d.status.to_string(),
+                    message: d.message,
+                    started_at: d.started_at.map(Timestamp::from),
+                    finished_at: d.finished_at.map(Timestamp::from),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Power action which is sequenced across all devices of a rack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RackPowerAction {
+    On,
+    Off,
+    Reset,
+}
+
+impl RackPowerAction {
+    /// The maintenance state in which the rack state controller runs the action
+    pub fn power_state(self) -> RackPowerState {
+        match self {
+            RackPowerAction::On => RackPowerState::PoweringOn,
+            RackPowerAction::Off => RackPowerState::PoweringOff,
+            RackPowerAction::Reset => RackPowerState::PowerReset,
+        }
+    }
+}
+
+impl Display for RackPowerAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Power state a step of a rack power sequence drives its devices to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RackPowerTarget {
+    On,
+    Off,
+}
+
+impl Display for RackPowerTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Devices which are switched together. The next step only starts once all
+/// devices of a step reached the target power state.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RackPowerStep {
+    pub target: RackPowerTarget,
+    pub device_type: RackDeviceType,
+    pub bmc_mac_addresses: Vec<MacAddress>,
+}
+
+/// Rack wide power operation which was requested via `SetRackPowerState`.
+/// The rack state controller runs the steps in order in the `PowerSequence`
+/// maintenance states.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RackPowerSequence {
+    pub action: RackPowerAction,
+    pub requested_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub steps: Vec<RackPowerStep>,
+    pub current_step: usize,
+    /// Time at which the power commands of the current step were issued
+    pub step_started_at: Option<DateTime<Utc>>,
+    /// Time at which all devices of the current step reached the target state
+    pub step_verified_at: Option<DateTime<Utc>>,
+    /// Why the sequence was aborted
+    pub error: Option<String>,
+}
+
+impl RackPowerSequence {
+    /// Orders the devices of a rack for a power action. Power shelves are
+    /// powered on first, followed by NVLink switches and compute trays in
+    /// waves of at most `compute_wave_size` trays. Powering off happens in
+    /// the reverse order, and a reset powers the rack off and on again.
+    pub fn plan(
+        action: RackPowerAction,
+        power_shelves: &[MacAddress],
+        nvlink_switches: &[MacAddress],
+        compute_trays: &[MacAddress],
+        compute_wave_size: usize,
+    ) -> Self {
+        let mut power_on_steps = Vec::new();
+        let mut add_steps = |device_type: RackDeviceType, devices: &[MacAddress], size: usize| {
+            for wave in devices.chunks(size.max(1)) {
+                power_on_steps.push(RackPowerStep {
+                    target: RackPowerTarget::On,
+                    device_type,
+                    bmc_mac_addresses: wave.to_vec(),
+                });
+            }
+        };
+        add_steps(
+            RackDeviceType::PowerShelf,
+            power_shelves,
+            power_shelves.len(),
+        );
+        add_steps(
+            RackDeviceType::Switch,
+            nvlink_switches,
+            nvlink_switches.len(),
+        );
+        add_steps(
+            RackDeviceType::ComputeTray,
+            compute_trays,
+            compute_wave_size,
+        );
+
+        let mut power_off_steps: Vec<RackPowerStep> = power_on_steps
+            .iter()
+            .rev()
+            .map(|step| RackPowerStep {
+                target: RackPowerTarget::Off,
+                ..step.clone()
+            })
+            .collect();
+        let steps = match action {
+            RackPowerAction::On => power_on_steps,
+            RackPowerAction::Off => power_off_steps,
+            RackPowerAction::Reset => {
+                power_off_steps.extend(power_on_steps);
+                power_off_steps
+            }
+        };
+
+        Self {
+            action,
+            requested_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            steps,
+            current_step: 0,
+            step_started_at: None,
+            step_verified_at: None,
+            error: None,
+        }
+    }
+
+    /// Returns whether the sequence still needs to be run by the rack state
+    /// controller
+    pub fn is_active(&self) -> bool {
+        self.completed_at.is_none() && self.error.is_none()
+    }
+
+    /// Returns whether the sequence determines the power state of the rack's
+    /// devices, so that their power policies must not be applied: while the
+    /// sequence is running, and after the rack was powered off by it.
+    pub fn overrides_power_policy(&self) -> bool {
+        self.is_active() || (self.action == RackPowerAction::Off && self.completed_at.is_some())
+    }
+}
+
+impl From<RackPowerSequence> for rpc::forge::RackPowerSequenceStatus {
+    fn from(value: RackPowerSequence) -> Self {
+        rpc::forge::RackPowerSequenceStatus {
+            action: value.action.to_string(),
+            requested_at: Some(Timestamp::from(value.requested_at)),
+            started_at: value.started_at.map(Timestamp::from),
+            completed_at: value.completed_at.map(Timestamp::from),
+            current_step: value.current_step as u32,
+            steps: value
+                .steps
+                .into_iter()
+                .map(|step| rpc::forge::RackPowerStepStatus {
+                    target: step.target.to_string(),
+                    device_type: step.device_type.to_string(),
+                    bmc_mac_addresses: step
+                        .bmc_mac_addresses
+                        .iter()
+                        .map(|mac| mac.to_string())
+                        .collect(),
+                })
+                .collect(),
+            error: value.error,
+        }
+    }
+}
+
 pub fn state_sla(state: &RackState, state_version: &ConfigVersion) -> StateSla {
     let _time_in_state = chrono::Utc::now()
         .signed_duration_since(state_version.timestamp())
@@ -250,3 +577,69 @@ impl From<RackStateHistory> for rpc::forge::RackStateHistoryRecord {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn macs(prefix: u8, count: u8) -> Vec<MacAddress> {
+        (0..count)
+            .map(|i| MacAddress::new([prefix, 0, 0, 0, 0, i]))
+            .collect()
+    }
+
+    #[test]
+    fn test_power_on_sequence_order() {
+        let sequence = RackPowerSequence::plan(
+            RackPowerAction::On,
+            &macs(1, 2),
+            &macs(2, 3),
+            &macs(3, 5),
+            2,
+        );
+        let steps: Vec<(RackDeviceType, usize)> = sequence
+            .steps
+            .iter()
+            .map(|step| (step.device_type, step.bmc_mac_addresses.len()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (RackDeviceType::PowerShelf, 2),
+                (RackDeviceType::Switch, 3),
+                (RackDeviceType::ComputeTray, 2),
+                (RackDeviceType::ComputeTray, 2),
+                (RackDeviceType::ComputeTray, 1),
+            ]
+        );
+        assert!(
+            sequence
+                .steps
+                .iter()
+                .all(|step| step.target == RackPowerTarget::On)
+        );
+    }
+
+    #[test]
+    fn test_power_off_and_reset_sequence_order() {
+        let on = RackPowerSequence::plan(RackPowerAction::On, &macs(1, 1), &[], &macs(3, 3), 2);
+        let off = RackPowerSequence::plan(RackPowerAction::Off, &macs(1, 1), &[], &macs(3, 3), 2);
+        assert_eq!(off.steps.len(), 3);
+        assert_eq!(
+            off.steps[0].bmc_mac_addresses,
+            on.steps[2].bmc_mac_addresses
+        );
+        assert_eq!(off.steps[2].device_type, RackDeviceType::PowerShelf);
+        assert!(
+            off.steps
+                .iter()
+                .all(|step| step.target == RackPowerTarget::Off)
+        );
+
+        let reset =
+            RackPowerSequence::plan(RackPowerAction::Reset, &macs(1, 1), &[], &macs(3, 3), 2);
+        assert_eq!(reset.steps.len(), 6);
+        assert_eq!(reset.steps[..3], off.steps[..]);
+        assert_eq!(reset.steps[3..], on.steps[..]);
+    }
+}
@@ -49,12 +49,12 @@ pub struct OsImageAttributes {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
 #[sqlx(rename_all = "lowercase")]
 #[sqlx(type_name = "os_image_status")]
-/// Note: "Ready" is the only actually-used variant as of today. Other statuses are meant for when
-/// carbide manages storage volumes, which is not the case today.
+/// New OS images are downloaded, verified and inspected by the OS image ingestion before they
+/// become ready. If the ingestion is disabled, images are ready as soon as they are created.
 pub enum OsImageStatus {
-    Uninitialized = 0, // initial state when db entry created
-    InProgress,        // golden volume creation in progress if applicable
-    Failed,            // golden volume creation error
+    Uninitialized = 0, // initial state when db entry created, waiting for ingestion
+    InProgress,        // image is being downloaded and inspected
+    Failed,            // ingestion error, see the status message
     Ready,             // ready for use during allocate instance calls
     Disabled,          // disabled or deprecated, no new instance allocations can use it
 }
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
@@ -85,11 +85,29 @@ pub struct FabricManagerConfig {
     pub config_map: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SwitchStatus {
     pub switch_name: String,
     pub power_state: String,   // "on", "off", "standby"
     pub health_status: String, // "ok", "warning", "critical"
+    /// Chassis serial numbers reported by the switch BMC
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub serial_numbers: Vec<String>,
+    /// Firmware inventory reported by the switch BMC, keyed by inventory ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub firmware_versions: BTreeMap<String, String>,
+    /// NVLink switch health as reported by NMX-M, if NMX-C is enabled for the switch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nmxm_health: Option<String>,
+    /// The last time the state controller successfully queried the switch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_health_check: Option<DateTime<Utc>>,
+}
+
+impl SwitchStatus {
+    pub const HEALTH_OK: &str = "ok";
+    pub const HEALTH_WARNING: &str = "warning";
+    pub const HEALTH_CRITICAL: &str = "critical";
 }
 
 #[derive(Debug, Clone)]
@@ -25,10 +25,12 @@ use std::sync::Arc;
 pub use ::rpc::forge as rpc;
 use ::rpc::forge::{RemoveSkuRequest, SkuIdList};
 use ::rpc::protos::dns::{
-    CreateDomainRequest, DnsResourceRecordLookupRequest, DnsResourceRecordLookupResponse, Domain,
-    DomainDeletionRequest, DomainDeletionResult, DomainList, DomainMetadataRequest,
-    DomainMetadataResponse, DomainSearchQuery, GetAllDomainsRequest, GetAllDomainsResponse,
-    UpdateDomainRequest,
+    CreateDomainRequest, CreateTenantDnsRecordRequest, DeleteTenantDnsRecordRequest,
+    DeleteTenantDnsRecordResponse, DnsResourceRecordLookupRequest, DnsResourceRecordLookupResponse,
+    Domain, DomainDeletionRequest, DomainDeletionResult, DomainList, DomainMetadataRequest,
+    DomainMetadataResponse, DomainSearchQuery, FindTenantDnsRecordsRequest, GetAllDomainsRequest,
+    GetAllDomainsResponse, TenantDnsRecord, TenantDnsRecordList, UpdateDomainRequest,
+    UpdateTenantDnsRecordRequest,
 };
 use ::rpc::protos::{measured_boot as measured_boot_pb, mlx_device as mlx_device_pb};
 use carbide_dpf::KubeImpl;
@@ -52,6 +54,7 @@ use crate::cfg::file::CarbideConfig;
 use crate::dynamic_settings::DynamicSettings;
 use crate::ethernet_virtualization::EthVirtData;
 use crate::ib::IBFabricManager;
+use crate::instance_events::notifier::InstanceEventNotifier;
 use crate::logging::log_limiter::LogLimiter;
 use crate::nvlink::NmxmClientPool;
 use crate::redfish::RedfishClientPool;
@@ -81,14 +84,27 @@ pub struct Api {
     pub(crate) kube_client_provider: Arc<dyn KubeImpl>,
     pub(crate) machine_state_handler_enqueuer: Enqueuer<MachineStateControllerIO>,
     pub(crate) metric_emitter: ApiMetricsEmitter,
+    pub(crate) instance_event_notifier: InstanceEventNotifier,
 }
 
 pub(crate) type ScoutStreamType =
     Pin<Box<dyn Stream<Item = Result<rpc::ScoutStreamScoutBoundMessage, Status>> + Send>>;
 
+pub(crate) type InstanceEventStreamType =
+    Pin<Box<dyn Stream<Item = Result<rpc::InstanceEvent, Status>> + Send>>;
+
+pub(crate) type ConsoleRecordingStreamType =
+    Pin<Box<dyn Stream<Item = Result<rpc::ConsoleRecordingChunk, Status>> + Send>>;
+
+pub(crate) type OsImageContentStreamType =
+    Pin<Box<dyn Stream<Item = Result<rpc::OsImageContentChunk, Status>> + Send>>;
+
 #[tonic::async_trait]
 impl Forge for Api {
     type ScoutStreamStream = ScoutStreamType;
+    type StreamInstanceEventsStream = InstanceEventStreamType;
+    type GetConsoleRecordingStream = ConsoleRecordingStreamType;
+    type GetOsImageContentStream = OsImageContentStreamType;
 
     async fn version(
         &self,
@@ -125,6 +141,43 @@ impl Forge for Api {
         crate::handlers::domain::find(self, request).await
     }
 
+    async fn create_tenant_dns_record(
+        &self,
+        request: Request<CreateTenantDnsRecordRequest>,
+    ) -> Result<Response<TenantDnsRecord>, Status> {
+        crate::audit_log::audited(self, "CreateTenantDnsRecord", request, |request| {
+            crate::handlers::tenant_dns_record::create(self, request)
+        })
+        .await
+    }
+
+    async fn update_tenant_dns_record(
+        &self,
+        request: Request<UpdateTenantDnsRecordRequest>,
+    ) -> Result<Response<TenantDnsRecord>, Status> {
+        crate::audit_log::audited(self, "UpdateTenantDnsRecord", request, |request| {
+            crate::handlers::tenant_dns_record::update(self, request)
+        })
+        .await
+    }
+
+    async fn delete_tenant_dns_record(
+        &self,
+        request: Request<DeleteTenantDnsRecordRequest>,
+    ) -> Result<Response<DeleteTenantDnsRecordResponse>, Status> {
+        crate::audit_log::audited(self, "DeleteTenantDnsRecord", request, |request| {
+            crate::handlers::tenant_dns_record::delete(self, request)
+        })
+        .await
+    }
+
+    async fn find_tenant_dns_records(
+        &self,
+        request: Request<FindTenantDnsRecordsRequest>,
+    ) -> Result<Response<TenantDnsRecordList>, Status> {
+        crate::handlers::tenant_dns_record::find(self, request).await
+    }
+
     // Legacy domain methods for backward compatibility
     // TODO: Remove this after clients have migrated
     async fn create_domain_legacy(
@@ -159,28 +212,40 @@ impl Forge for Api {
         &self,
         request: Request<rpc::VpcCreationRequest>,
     ) -> Result<Response<rpc::Vpc>, Status> {
-        crate::handlers::vpc::create(self, request).await
+        crate::audit_log::audited(self, "CreateVpc", request, |request| {
+            crate::handlers::vpc::create(self, request)
+        })
+        .await
     }
 
     async fn update_vpc(
         &self,
         request: Request<rpc::VpcUpdateRequest>,
     ) -> Result<Response<rpc::VpcUpdateResult>, Status> {
-        crate::handlers::vpc::update(self, request).await
+        crate::audit_log::audited(self, "UpdateVpc", request, |request| {
+            crate::handlers::vpc::update(self, request)
+        })
+        .await
     }
 
     async fn update_vpc_virtualization(
         &self,
         request: Request<rpc::VpcUpdateVirtualizationRequest>,
     ) -> Result<Response<rpc::VpcUpdateVirtualizationResult>, Status> {
-        crate::handlers::vpc::update_virtualization(self, request).await
+        crate::audit_log::audited(self, "UpdateVpcVirtualization", request, |request| {
+            crate::handlers::vpc::update_virtualization(self, request)
+        })
+        .await
     }
 
     async fn delete_vpc(
         &self,
         request: Request<rpc::VpcDeletionRequest>,
     ) -> Result<Response<rpc::VpcDeletionResult>, Status> {
-        crate::handlers::vpc::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteVpc", request, |request| {
+            crate::handlers::vpc::delete(self, request)
+        })
+        .await
     }
 
     async fn find_vpc_ids(
@@ -201,7 +266,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::VpcPrefixCreationRequest>,
     ) -> Result<Response<rpc::VpcPrefix>, Status> {
-        crate::handlers::vpc_prefix::create(self, request).await
+        crate::audit_log::audited(self, "CreateVpcPrefix", request, |request| {
+            crate::handlers::vpc_prefix::create(self, request)
+        })
+        .await
     }
 
     async fn search_vpc_prefixes(
@@ -222,20 +290,29 @@ impl Forge for Api {
         &self,
         request: Request<rpc::VpcPrefixUpdateRequest>,
     ) -> Result<Response<rpc::VpcPrefix>, Status> {
-        crate::handlers::vpc_prefix::update(self, request).await
+        crate::audit_log::audited(self, "UpdateVpcPrefix", request, |request| {
+            crate::handlers::vpc_prefix::update(self, request)
+        })
+        .await
     }
     async fn delete_vpc_prefix(
         &self,
         request: Request<rpc::VpcPrefixDeletionRequest>,
     ) -> Result<Response<rpc::VpcPrefixDeletionResult>, Status> {
-        crate::handlers::vpc_prefix::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteVpcPrefix", request, |request| {
+            crate::handlers::vpc_prefix::delete(self, request)
+        })
+        .await
     }
 
     async fn create_vpc_peering(
         &self,
         request: Request<rpc::VpcPeeringCreationRequest>,
     ) -> Result<Response<rpc::VpcPeering>, Status> {
-        crate::handlers::vpc_peering::create(self, request).await
+        crate::audit_log::audited(self, "CreateVpcPeering", request, |request| {
+            crate::handlers::vpc_peering::create(self, request)
+        })
+        .await
     }
 
     async fn find_vpc_peering_ids(
@@ -256,7 +333,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::VpcPeeringDeletionRequest>,
     ) -> Result<Response<rpc::VpcPeeringDeletionResult>, Status> {
-        crate::handlers::vpc_peering::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteVpcPeering", request, |request| {
+            crate::handlers::vpc_peering::delete(self, request)
+        })
+        .await
     }
 
     async fn find_ib_partition_ids(
@@ -277,14 +357,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::IbPartitionCreationRequest>,
     ) -> Result<Response<rpc::IbPartition>, Status> {
-        crate::handlers::ib_partition::create(self, request).await
+        crate::audit_log::audited(self, "CreateIBPartition", request, |request| {
+            crate::handlers::ib_partition::create(self, request)
+        })
+        .await
     }
 
     async fn delete_ib_partition(
         &self,
         request: Request<rpc::IbPartitionDeletionRequest>,
     ) -> Result<Response<rpc::IbPartitionDeletionResult>, Status> {
-        crate::handlers::ib_partition::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteIBPartition", request, |request| {
+            crate::handlers::ib_partition::delete(self, request)
+        })
+        .await
     }
 
     async fn ib_partitions_for_tenant(
@@ -305,7 +391,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::PowerShelfDeletionRequest>,
     ) -> Result<Response<rpc::PowerShelfDeletionResult>, Status> {
-        crate::handlers::power_shelf::delete_power_shelf(self, request).await
+        crate::audit_log::audited(self, "DeletePowerShelf", request, |request| {
+            crate::handlers::power_shelf::delete_power_shelf(self, request)
+        })
+        .await
     }
 
     async fn find_switches(
@@ -319,7 +408,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::SwitchDeletionRequest>,
     ) -> Result<Response<rpc::SwitchDeletionResult>, Status> {
-        crate::handlers::switch::delete_switch(self, request).await
+        crate::audit_log::audited(self, "DeleteSwitch", request, |request| {
+            crate::handlers::switch::delete_switch(self, request)
+        })
+        .await
     }
 
     async fn find_ib_fabric_ids(
@@ -347,14 +439,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::NetworkSegmentCreationRequest>,
     ) -> Result<Response<rpc::NetworkSegment>, Status> {
-        crate::handlers::network_segment::create(self, request).await
+        crate::audit_log::audited(self, "CreateNetworkSegment", request, |request| {
+            crate::handlers::network_segment::create(self, request)
+        })
+        .await
     }
 
     async fn delete_network_segment(
         &self,
         request: Request<rpc::NetworkSegmentDeletionRequest>,
     ) -> Result<Response<rpc::NetworkSegmentDeletionResult>, Status> {
-        crate::handlers::network_segment::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteNetworkSegment", request, |request| {
+            crate::handlers::network_segment::delete(self, request)
+        })
+        .await
     }
 
     async fn network_segments_for_vpc(
@@ -368,14 +466,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InstanceAllocationRequest>,
     ) -> Result<Response<rpc::Instance>, Status> {
-        crate::handlers::instance::allocate(self, request).await
+        crate::audit_log::audited(self, "AllocateInstance", request, |request| {
+            crate::handlers::instance::allocate(self, request)
+        })
+        .await
     }
 
     async fn allocate_instances(
         &self,
         request: Request<rpc::BatchInstanceAllocationRequest>,
     ) -> Result<Response<rpc::BatchInstanceAllocationResponse>, Status> {
-        crate::handlers::instance::batch_allocate(self, request).await
+        crate::audit_log::audited(self, "AllocateInstances", request, |request| {
+            crate::handlers::instance::batch_allocate(self, request)
+        })
+        .await
     }
 
     async fn find_instance_ids(
@@ -403,7 +507,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InstanceReleaseRequest>,
     ) -> Result<Response<rpc::InstanceReleaseResult>, Status> {
-        crate::handlers::instance::release(self, request).await
+        crate::audit_log::audited(self, "ReleaseInstance", request, |request| {
+            crate::handlers::instance::release(self, request)
+        })
+        .await
     }
 
     async fn update_instance_phone_home_last_contact(
@@ -417,14 +524,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InstanceOperatingSystemUpdateRequest>,
     ) -> Result<Response<rpc::Instance>, Status> {
-        crate::handlers::instance::update_operating_system(self, request).await
+        crate::audit_log::audited(self, "UpdateInstanceOperatingSystem", request, |request| {
+            crate::handlers::instance::update_operating_system(self, request)
+        })
+        .await
     }
 
     async fn update_instance_config(
         &self,
         request: Request<rpc::InstanceConfigUpdateRequest>,
     ) -> Result<Response<rpc::Instance>, Status> {
-        crate::handlers::instance::update_instance_config(self, request).await
+        crate::audit_log::audited(self, "UpdateInstanceConfig", request, |request| {
+            crate::handlers::instance::update_instance_config(self, request)
+        })
+        .await
     }
 
     async fn get_managed_host_network_config(
@@ -469,6 +582,41 @@ impl Forge for Api {
         crate::handlers::health::record_log_parser_health_report(self, request).await
     }
 
+    async fn record_console_health_report(
+        &self,
+        request: Request<rpc::HardwareHealthReport>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::health::record_console_health_report(self, request).await
+    }
+
+    async fn renew_health_shard_lease(
+        &self,
+        request: Request<rpc::HealthShardLeaseRequest>,
+    ) -> Result<Response<rpc::HealthShardMembers>, Status> {
+        crate::handlers::health_shard::renew_lease(self, request).await
+    }
+
+    async fn release_health_shard_lease(
+        &self,
+        request: Request<rpc::HealthShardLeaseRelease>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::health_shard::release_lease(self, request).await
+    }
+
+    async fn save_health_collector_state(
+        &self,
+        request: Request<rpc::HealthCollectorState>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::health_shard::save_collector_state(self, request).await
+    }
+
+    async fn get_health_collector_state(
+        &self,
+        request: Request<rpc::HealthCollectorStateRequest>,
+    ) -> Result<Response<rpc::OptionalHealthCollectorState>, Status> {
+        crate::handlers::health_shard::get_collector_state(self, request).await
+    }
+
     async fn list_health_report_overrides(
         &self,
         request: Request<MachineId>,
@@ -480,14 +628,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InsertHealthReportOverrideRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::health::insert_health_report_override(self, request).await
+        crate::audit_log::audited(self, "InsertHealthReportOverride", request, |request| {
+            crate::handlers::health::insert_health_report_override(self, request)
+        })
+        .await
     }
 
     async fn remove_health_report_override(
         &self,
         request: Request<rpc::RemoveHealthReportOverrideRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::health::remove_health_report_override(self, request).await
+        crate::audit_log::audited(self, "RemoveHealthReportOverride", request, |request| {
+            crate::handlers::health::remove_health_report_override(self, request)
+        })
+        .await
     }
 
     async fn list_rack_health_report_overrides(
@@ -501,14 +655,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InsertRackHealthReportOverrideRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::rack::insert_rack_health_report_override(self, request).await
+        crate::audit_log::audited(self, "InsertRackHealthReportOverride", request, |request| {
+            crate::handlers::rack::insert_rack_health_report_override(self, request)
+        })
+        .await
     }
 
     async fn remove_rack_health_report_override(
         &self,
         request: Request<rpc::RemoveRackHealthReportOverrideRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::rack::remove_rack_health_report_override(self, request).await
+        crate::audit_log::audited(self, "RemoveRackHealthReportOverride", request, |request| {
+            crate::handlers::rack::remove_rack_health_report_override(self, request)
+        })
+        .await
     }
 
     async fn get_all_domain_metadata(
@@ -544,7 +704,17 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InstancePowerRequest>,
     ) -> Result<Response<rpc::InstancePowerResult>, Status> {
-        crate::handlers::instance::invoke_power(self, request).await
+        crate::audit_log::audited(self, "InvokeInstancePower", request, |request| {
+            crate::handlers::instance::invoke_power(self, request)
+        })
+        .await
+    }
+
+    async fn stream_instance_events(
+        &self,
+        request: Request<rpc::InstanceEventStreamRequest>,
+    ) -> Result<Response<Self::StreamInstanceEventsStream>, Status> {
+        crate::handlers::instance_event::stream_events(self, request).await
     }
 
     async fn echo(
@@ -558,7 +728,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::CreateTenantRequest>,
     ) -> Result<Response<rpc::CreateTenantResponse>, Status> {
-        crate::handlers::tenant::create(self, request).await
+        crate::audit_log::audited(self, "CreateTenant", request, |request| {
+            crate::handlers::tenant::create(self, request)
+        })
+        .await
     }
 
     async fn find_tenant(
@@ -572,7 +745,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::UpdateTenantRequest>,
     ) -> Result<Response<rpc::UpdateTenantResponse>, Status> {
-        crate::handlers::tenant::update(self, request).await
+        crate::audit_log::audited(self, "UpdateTenant", request, |request| {
+            crate::handlers::tenant::update(self, request)
+        })
+        .await
     }
 
     async fn find_tenants_by_organization_ids(
@@ -593,7 +769,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::CreateTenantKeysetRequest>,
     ) -> Result<Response<rpc::CreateTenantKeysetResponse>, Status> {
-        crate::handlers::tenant_keyset::create(self, request).await
+        crate::audit_log::audited(self, "CreateTenantKeyset", request, |request| {
+            crate::handlers::tenant_keyset::create(self, request)
+        })
+        .await
     }
 
     async fn find_tenant_keyset_ids(
@@ -614,14 +793,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::UpdateTenantKeysetRequest>,
     ) -> Result<Response<rpc::UpdateTenantKeysetResponse>, Status> {
-        crate::handlers::tenant_keyset::update(self, request).await
+        crate::audit_log::audited(self, "UpdateTenantKeyset", request, |request| {
+            crate::handlers::tenant_keyset::update(self, request)
+        })
+        .await
     }
 
     async fn delete_tenant_keyset(
         &self,
         request: Request<rpc::DeleteTenantKeysetRequest>,
     ) -> Result<Response<rpc::DeleteTenantKeysetResponse>, Status> {
-        crate::handlers::tenant_keyset::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteTenantKeyset", request, |request| {
+            crate::handlers::tenant_keyset::delete(self, request)
+        })
+        .await
     }
 
     async fn validate_tenant_public_key(
@@ -631,6 +816,45 @@ impl Forge for Api {
         crate::handlers::tenant_keyset::validate_public_key(self, request).await
     }
 
+    async fn create_console_access_token(
+        &self,
+        request: Request<rpc::CreateConsoleAccessTokenRequest>,
+    ) -> Result<Response<rpc::ConsoleAccessToken>, Status> {
+        crate::audit_log::audited(self, "CreateConsoleAccessToken", request, |request| {
+            crate::handlers::console_access::create_console_access_token(self, request)
+        })
+        .await
+    }
+
+    async fn validate_console_access_token(
+        &self,
+        request: Request<rpc::ValidateConsoleAccessTokenRequest>,
+    ) -> Result<Response<rpc::ValidateConsoleAccessTokenResponse>, Status> {
+        crate::handlers::console_access::validate_console_access_token(self, request).await
+    }
+
+    async fn list_console_recordings(
+        &self,
+        request: Request<rpc::ListConsoleRecordingsRequest>,
+    ) -> Result<Response<rpc::ConsoleRecordingList>, Status> {
+        crate::handlers::console_access::list_console_recordings(self, request).await
+    }
+
+    async fn get_console_recording(
+        &self,
+        request: Request<rpc::GetConsoleRecordingRequest>,
+    ) -> Result<Response<Self::GetConsoleRecordingStream>, Status> {
+        crate::handlers::console_access::get_console_recording(self, request).await
+    }
+
+    async fn validate_console_recording_access_token(
+        &self,
+        request: Request<rpc::ValidateConsoleRecordingAccessTokenRequest>,
+    ) -> Result<Response<rpc::ValidateConsoleRecordingAccessTokenResponse>, Status> {
+        crate::handlers::console_access::validate_console_recording_access_token(self, request)
+            .await
+    }
+
     async fn renew_machine_certificate(
         &self,
         request: Request<rpc::MachineCertificateRenewRequest>,
@@ -814,7 +1038,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::InterfaceDeleteQuery>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::machine_interface::delete_interface(self, request).await
+        crate::audit_log::audited(self, "DeleteInterface", request, |request| {
+            crate::handlers::machine_interface::delete_interface(self, request)
+        })
+        .await
     }
 
     // Fetch the DPU admin SSH password from Vault.
@@ -857,7 +1084,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::MachineCredentialsUpdateRequest>,
     ) -> Result<Response<rpc::MachineCredentialsUpdateResponse>, Status> {
-        crate::handlers::credential::update_machine_credentials(self, request).await
+        crate::audit_log::audited(self, "UpdateMachineCredentials", request, |request| {
+            crate::handlers::credential::update_machine_credentials(self, request)
+        })
+        .await
     }
 
     // The carbide pxe server makes this RPC call
@@ -875,11 +1105,21 @@ impl Forge for Api {
         crate::handlers::pxe::get_cloud_init_instructions(self, request).await
     }
 
+    async fn get_os_image_content(
+        &self,
+        request: Request<rpc::GetOsImageContentRequest>,
+    ) -> Result<Response<Self::GetOsImageContentStream>, Status> {
+        crate::handlers::pxe::get_os_image_content(self, request).await
+    }
+
     async fn clear_site_exploration_error(
         &self,
         request: Request<rpc::ClearSiteExplorationErrorRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::site_explorer::clear_site_exploration_error(self, request).await
+        crate::audit_log::audited(self, "ClearSiteExplorationError", request, |request| {
+            crate::handlers::site_explorer::clear_site_exploration_error(self, request)
+        })
+        .await
     }
 
     async fn is_bmc_in_managed_host(
@@ -900,21 +1140,35 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ReExploreEndpointRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::site_explorer::re_explore_endpoint(self, request).await
+        crate::audit_log::audited(self, "ReExploreEndpoint", request, |request| {
+            crate::handlers::site_explorer::re_explore_endpoint(self, request)
+        })
+        .await
     }
 
     async fn delete_explored_endpoint(
         &self,
         request: Request<rpc::DeleteExploredEndpointRequest>,
     ) -> Result<Response<rpc::DeleteExploredEndpointResponse>, Status> {
-        crate::handlers::site_explorer::delete_explored_endpoint(self, request).await
+        crate::audit_log::audited(self, "DeleteExploredEndpoint", request, |request| {
+            crate::handlers::site_explorer::delete_explored_endpoint(self, request)
+        })
+        .await
     }
 
     async fn pause_explored_endpoint_remediation(
         &self,
         request: Request<rpc::PauseExploredEndpointRemediationRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::site_explorer::pause_explored_endpoint_remediation(self, request).await
+        crate::audit_log::audited(
+            self,
+            "PauseExploredEndpointRemediation",
+            request,
+            |request| {
+                crate::handlers::site_explorer::pause_explored_endpoint_remediation(self, request)
+            },
+        )
+        .await
     }
 
     // DEPRECATED: use find_explored_endpoint_ids, find_explored_endpoints_by_ids and find_explored_managed_host_ids, find_explored_managed_hosts_by_ids instead
@@ -957,8 +1211,12 @@ impl Forge for Api {
         &self,
         request: Request<::rpc::forge::UpdateMachineHardwareInfoRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::machine_hardware_info::handle_machine_hardware_info_update(self, request)
-            .await
+        crate::audit_log::audited(self, "UpdateMachineHardwareInfo", request, |request| {
+            crate::handlers::machine_hardware_info::handle_machine_hardware_info_update(
+                self, request,
+            )
+        })
+        .await
     }
 
     // Ad-hoc BMC exploration
@@ -982,7 +1240,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::AdminForceDeleteMachineRequest>,
     ) -> Result<Response<rpc::AdminForceDeleteMachineResponse>, Status> {
-        crate::handlers::machine::admin_force_delete_machine(self, request).await
+        crate::audit_log::audited(self, "AdminForceDeleteMachine", request, |request| {
+            crate::handlers::machine::admin_force_delete_machine(self, request)
+        })
+        .await
     }
 
     /// Example TOML data in request.text:
@@ -1001,7 +1262,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::GrowResourcePoolRequest>,
     ) -> Result<Response<rpc::GrowResourcePoolResponse>, Status> {
-        crate::handlers::resource_pool::grow(self, request).await
+        crate::audit_log::audited(self, "AdminGrowResourcePool", request, |request| {
+            crate::handlers::resource_pool::grow(self, request)
+        })
+        .await
     }
 
     async fn admin_list_resource_pools(
@@ -1015,21 +1279,30 @@ impl Forge for Api {
         &self,
         request: Request<rpc::MachineMetadataUpdateRequest>,
     ) -> std::result::Result<Response<()>, Status> {
-        crate::handlers::machine::update_machine_metadata(self, request).await
+        crate::audit_log::audited(self, "UpdateMachineMetadata", request, |request| {
+            crate::handlers::machine::update_machine_metadata(self, request)
+        })
+        .await
     }
 
     async fn update_machine_nv_link_info(
         &self,
         request: Request<rpc::UpdateMachineNvLinkInfoRequest>,
     ) -> std::result::Result<Response<()>, Status> {
-        crate::handlers::machine::update_machine_nv_link_info(self, request).await
+        crate::audit_log::audited(self, "UpdateMachineNvLinkInfo", request, |request| {
+            crate::handlers::machine::update_machine_nv_link_info(self, request)
+        })
+        .await
     }
 
     async fn set_maintenance(
         &self,
         request: Request<rpc::MaintenanceRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::managed_host::set_maintenance(self, request).await
+        crate::audit_log::audited(self, "SetMaintenance", request, |request| {
+            crate::handlers::managed_host::set_maintenance(self, request)
+        })
+        .await
     }
 
     async fn find_ip_address(
@@ -1071,7 +1344,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::PowerOptionUpdateRequest>,
     ) -> Result<Response<rpc::PowerOptionResponse>, Status> {
-        crate::handlers::power_options::update_power_option(self, request).await
+        crate::audit_log::audited(self, "UpdatePowerOption", request, |request| {
+            crate::handlers::power_options::update_power_option(self, request)
+        })
+        .await
     }
 
     async fn get_rack(
@@ -1085,7 +1361,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::DeleteRackRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::rack::delete_rack(self, request).await
+        crate::audit_log::audited(self, "DeleteRack", request, |request| {
+            crate::handlers::rack::delete_rack(self, request)
+        })
+        .await
+    }
+
+    async fn set_rack_power_state(
+        &self,
+        request: Request<rpc::SetRackPowerStateRequest>,
+    ) -> Result<Response<()>, Status> {
+        crate::audit_log::audited(self, "SetRackPowerState", request, |request| {
+            crate::handlers::rack::set_rack_power_state(self, request)
+        })
+        .await
     }
 
     /// Trigger DPU reprovisioning
@@ -1093,7 +1382,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::DpuReprovisioningRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::dpu::trigger_dpu_reprovisioning(self, request).await
+        crate::audit_log::audited(self, "TriggerDpuReprovisioning", request, |request| {
+            crate::handlers::dpu::trigger_dpu_reprovisioning(self, request)
+        })
+        .await
     }
 
     async fn list_dpu_waiting_for_reprovisioning(
@@ -1107,15 +1399,27 @@ impl Forge for Api {
         &self,
         request: Request<rpc::HostReprovisioningRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::host_reprovisioning::trigger_host_reprovisioning(self, request).await
+        crate::audit_log::audited(self, "TriggerHostReprovisioning", request, |request| {
+            crate::handlers::host_reprovisioning::trigger_host_reprovisioning(self, request)
+        })
+        .await
     }
 
     async fn mark_manual_firmware_upgrade_complete(
         &self,
         request: Request<MachineId>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::host_reprovisioning::mark_manual_firmware_upgrade_complete(self, request)
-            .await
+        crate::audit_log::audited(
+            self,
+            "MarkManualFirmwareUpgradeComplete",
+            request,
+            |request| {
+                crate::handlers::host_reprovisioning::mark_manual_firmware_upgrade_complete(
+                    self, request,
+                )
+            },
+        )
+        .await
     }
 
     async fn list_hosts_waiting_for_reprovisioning(
@@ -1145,14 +1449,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::MachineBootOverride>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::boot_override::set(self, request).await
+        crate::audit_log::audited(self, "SetMachineBootOverride", request, |request| {
+            crate::handlers::boot_override::set(self, request)
+        })
+        .await
     }
 
     async fn clear_machine_boot_override(
         &self,
         request: Request<MachineInterfaceId>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::boot_override::clear(self, request).await
+        crate::audit_log::audited(self, "ClearMachineBootOverride", request, |request| {
+            crate::handlers::boot_override::clear(self, request)
+        })
+        .await
     }
 
     async fn get_network_topology(
@@ -1166,21 +1476,30 @@ impl Forge for Api {
         &self,
         request: Request<rpc::AdminBmcResetRequest>,
     ) -> Result<Response<rpc::AdminBmcResetResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::admin_bmc_reset(self, request).await
+        crate::audit_log::audited(self, "AdminBmcReset", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::admin_bmc_reset(self, request)
+        })
+        .await
     }
 
     async fn disable_secure_boot(
         &self,
         request: Request<rpc::BmcEndpointRequest>,
     ) -> Result<Response<::rpc::forge::DisableSecureBootResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::disable_secure_boot(self, request).await
+        crate::audit_log::audited(self, "DisableSecureBoot", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::disable_secure_boot(self, request)
+        })
+        .await
     }
 
     async fn lockdown(
         &self,
         request: Request<rpc::LockdownRequest>,
     ) -> Result<Response<::rpc::forge::LockdownResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::lockdown(self, request).await
+        crate::audit_log::audited(self, "Lockdown", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::lockdown(self, request)
+        })
+        .await
     }
 
     async fn lockdown_status(
@@ -1194,7 +1513,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::EnableInfiniteBootRequest>,
     ) -> Result<Response<::rpc::forge::EnableInfiniteBootResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::enable_infinite_boot(self, request).await
+        crate::audit_log::audited(self, "EnableInfiniteBoot", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::enable_infinite_boot(self, request)
+        })
+        .await
     }
 
     async fn is_infinite_boot_enabled(
@@ -1208,14 +1530,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::MachineSetupRequest>,
     ) -> Result<Response<::rpc::forge::MachineSetupResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::machine_setup(self, request).await
+        crate::audit_log::audited(self, "MachineSetup", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::machine_setup(self, request)
+        })
+        .await
     }
 
     async fn set_dpu_first_boot_order(
         &self,
         request: Request<rpc::SetDpuFirstBootOrderRequest>,
     ) -> Result<Response<::rpc::forge::SetDpuFirstBootOrderResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::set_dpu_first_boot_order(self, request).await
+        crate::audit_log::audited(self, "SetDpuFirstBootOrder", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::set_dpu_first_boot_order(self, request)
+        })
+        .await
     }
 
     /// Should this DPU upgrade it's forge-dpu-agent?
@@ -1233,21 +1561,30 @@ impl Forge for Api {
         &self,
         request: Request<rpc::DpuAgentUpgradePolicyRequest>,
     ) -> Result<Response<rpc::DpuAgentUpgradePolicyResponse>, Status> {
-        crate::handlers::dpu::dpu_agent_upgrade_policy_action(self, request).await
+        crate::audit_log::audited(self, "DpuAgentUpgradePolicyAction", request, |request| {
+            crate::handlers::dpu::dpu_agent_upgrade_policy_action(self, request)
+        })
+        .await
     }
 
     async fn create_credential(
         &self,
         request: Request<rpc::CredentialCreationRequest>,
     ) -> Result<Response<rpc::CredentialCreationResult>, Status> {
-        crate::handlers::credential::create_credential(self, request).await
+        crate::audit_log::audited(self, "CreateCredential", request, |request| {
+            crate::handlers::credential::create_credential(self, request)
+        })
+        .await
     }
 
     async fn delete_credential(
         &self,
         request: Request<rpc::CredentialDeletionRequest>,
     ) -> Result<Response<rpc::CredentialDeletionResult>, Status> {
-        crate::handlers::credential::delete_credential(self, request).await
+        crate::audit_log::audited(self, "DeleteCredential", request, |request| {
+            crate::handlers::credential::delete_credential(self, request)
+        })
+        .await
     }
 
     /// get_route_servers returns a list of all configured route server
@@ -1267,7 +1604,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::RouteServers>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::route_server::add(self, request).await
+        crate::audit_log::audited(self, "AddRouteServers", request, |request| {
+            crate::handlers::route_server::add(self, request)
+        })
+        .await
     }
 
     /// remove_route_servers removes route server entries for the
@@ -1278,7 +1618,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::RouteServers>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::route_server::remove(self, request).await
+        crate::audit_log::audited(self, "RemoveRouteServers", request, |request| {
+            crate::handlers::route_server::remove(self, request)
+        })
+        .await
     }
 
     /// replace_route_servers replaces all route server entries
@@ -1296,21 +1639,37 @@ impl Forge for Api {
         &self,
         request: Request<rpc::SetDynamicConfigRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::api::set_dynamic_config(self, request)
+        crate::audit_log::audited(self, "SetDynamicConfig", request, |request| async move {
+            crate::handlers::api::set_dynamic_config(self, request)
+        })
+        .await
+    }
+
+    async fn find_audit_log_entries(
+        &self,
+        request: Request<rpc::FindAuditLogEntriesRequest>,
+    ) -> Result<Response<rpc::AuditLogEntries>, Status> {
+        crate::handlers::audit_log::find_entries(self, request).await
     }
 
     async fn clear_host_uefi_password(
         &self,
         request: Request<rpc::ClearHostUefiPasswordRequest>,
     ) -> Result<Response<rpc::ClearHostUefiPasswordResponse>, Status> {
-        crate::handlers::uefi::clear_host_uefi_password(self, request).await
+        crate::audit_log::audited(self, "ClearHostUefiPassword", request, |request| {
+            crate::handlers::uefi::clear_host_uefi_password(self, request)
+        })
+        .await
     }
 
     async fn set_host_uefi_password(
         &self,
         request: Request<rpc::SetHostUefiPasswordRequest>,
     ) -> Result<Response<rpc::SetHostUefiPasswordResponse>, Status> {
-        crate::handlers::uefi::set_host_uefi_password(self, request).await
+        crate::audit_log::audited(self, "SetHostUefiPassword", request, |request| {
+            crate::handlers::uefi::set_host_uefi_password(self, request)
+        })
+        .await
     }
 
     async fn get_expected_machine(
@@ -1324,28 +1683,40 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ExpectedMachine>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_machine::add(self, request).await
+        crate::audit_log::audited(self, "AddExpectedMachine", request, |request| {
+            crate::handlers::expected_machine::add(self, request)
+        })
+        .await
     }
 
     async fn delete_expected_machine(
         &self,
         request: Request<rpc::ExpectedMachineRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_machine::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteExpectedMachine", request, |request| {
+            crate::handlers::expected_machine::delete(self, request)
+        })
+        .await
     }
 
     async fn update_expected_machine(
         &self,
         request: Request<rpc::ExpectedMachine>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_machine::update(self, request).await
+        crate::audit_log::audited(self, "UpdateExpectedMachine", request, |request| {
+            crate::handlers::expected_machine::update(self, request)
+        })
+        .await
     }
 
     async fn replace_all_expected_machines(
         &self,
         request: Request<rpc::ExpectedMachineList>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_machine::replace_all(self, request).await
+        crate::audit_log::audited(self, "ReplaceAllExpectedMachines", request, |request| {
+            crate::handlers::expected_machine::replace_all(self, request)
+        })
+        .await
     }
 
     async fn get_all_expected_machines(
@@ -1366,28 +1737,40 @@ impl Forge for Api {
         &self,
         request: Request<()>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_machine::delete_all(self, request).await
+        crate::audit_log::audited(self, "DeleteAllExpectedMachines", request, |request| {
+            crate::handlers::expected_machine::delete_all(self, request)
+        })
+        .await
     }
 
     async fn create_expected_machines(
         &self,
         request: Request<rpc::BatchExpectedMachineOperationRequest>,
     ) -> Result<Response<rpc::BatchExpectedMachineOperationResponse>, Status> {
-        crate::handlers::expected_machine::create_expected_machines(self, request).await
+        crate::audit_log::audited(self, "CreateExpectedMachines", request, |request| {
+            crate::handlers::expected_machine::create_expected_machines(self, request)
+        })
+        .await
     }
 
     async fn update_expected_machines(
         &self,
         request: Request<rpc::BatchExpectedMachineOperationRequest>,
     ) -> Result<Response<rpc::BatchExpectedMachineOperationResponse>, Status> {
-        crate::handlers::expected_machine::update_expected_machines(self, request).await
+        crate::audit_log::audited(self, "UpdateExpectedMachines", request, |request| {
+            crate::handlers::expected_machine::update_expected_machines(self, request)
+        })
+        .await
     }
 
     async fn create_rack_firmware(
         &self,
         request: tonic::Request<rpc::RackFirmwareCreateRequest>,
     ) -> Result<Response<rpc::RackFirmware>, tonic::Status> {
-        crate::handlers::rack_firmware::create(self, request).await
+        crate::audit_log::audited(self, "CreateRackFirmware", request, |request| {
+            crate::handlers::rack_firmware::create(self, request)
+        })
+        .await
     }
 
     async fn get_rack_firmware(
@@ -1408,14 +1791,20 @@ impl Forge for Api {
         &self,
         request: tonic::Request<rpc::RackFirmwareDeleteRequest>,
     ) -> Result<Response<()>, tonic::Status> {
-        crate::handlers::rack_firmware::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteRackFirmware", request, |request| {
+            crate::handlers::rack_firmware::delete(self, request)
+        })
+        .await
     }
 
     async fn apply_rack_firmware(
         &self,
         request: tonic::Request<rpc::RackFirmwareApplyRequest>,
     ) -> Result<Response<rpc::RackFirmwareApplyResponse>, tonic::Status> {
-        crate::handlers::rack_firmware::apply(self, request).await
+        crate::audit_log::audited(self, "ApplyRackFirmware", request, |request| {
+            crate::handlers::rack_firmware::apply(self, request)
+        })
+        .await
     }
 
     async fn get_expected_power_shelf(
@@ -1429,29 +1818,40 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ExpectedPowerShelf>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_power_shelf::add_expected_power_shelf(self, request).await
+        crate::audit_log::audited(self, "AddExpectedPowerShelf", request, |request| {
+            crate::handlers::expected_power_shelf::add_expected_power_shelf(self, request)
+        })
+        .await
     }
 
     async fn delete_expected_power_shelf(
         &self,
         request: Request<rpc::ExpectedPowerShelfRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_power_shelf::delete_expected_power_shelf(self, request).await
+        crate::audit_log::audited(self, "DeleteExpectedPowerShelf", request, |request| {
+            crate::handlers::expected_power_shelf::delete_expected_power_shelf(self, request)
+        })
+        .await
     }
 
     async fn update_expected_power_shelf(
         &self,
         request: Request<rpc::ExpectedPowerShelf>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_power_shelf::update_expected_power_shelf(self, request).await
+        crate::audit_log::audited(self, "UpdateExpectedPowerShelf", request, |request| {
+            crate::handlers::expected_power_shelf::update_expected_power_shelf(self, request)
+        })
+        .await
     }
 
     async fn replace_all_expected_power_shelves(
         &self,
         request: Request<rpc::ExpectedPowerShelfList>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_power_shelf::replace_all_expected_power_shelves(self, request)
-            .await
+        crate::audit_log::audited(self, "ReplaceAllExpectedPowerShelves", request, |request| {
+            crate::handlers::expected_power_shelf::replace_all_expected_power_shelves(self, request)
+        })
+        .await
     }
 
     async fn get_all_expected_power_shelves(
@@ -1473,8 +1873,10 @@ impl Forge for Api {
         &self,
         request: Request<()>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_power_shelf::delete_all_expected_power_shelves(self, request)
-            .await
+        crate::audit_log::audited(self, "DeleteAllExpectedPowerShelves", request, |request| {
+            crate::handlers::expected_power_shelf::delete_all_expected_power_shelves(self, request)
+        })
+        .await
     }
 
     async fn get_expected_switch(
@@ -1488,28 +1890,40 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ExpectedSwitch>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_switch::add_expected_switch(self, request).await
+        crate::audit_log::audited(self, "AddExpectedSwitch", request, |request| {
+            crate::handlers::expected_switch::add_expected_switch(self, request)
+        })
+        .await
     }
 
     async fn delete_expected_switch(
         &self,
         request: Request<rpc::ExpectedSwitchRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_switch::delete_expected_switch(self, request).await
+        crate::audit_log::audited(self, "DeleteExpectedSwitch", request, |request| {
+            crate::handlers::expected_switch::delete_expected_switch(self, request)
+        })
+        .await
     }
 
     async fn update_expected_switch(
         &self,
         request: Request<rpc::ExpectedSwitch>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_switch::update_expected_switch(self, request).await
+        crate::audit_log::audited(self, "UpdateExpectedSwitch", request, |request| {
+            crate::handlers::expected_switch::update_expected_switch(self, request)
+        })
+        .await
     }
 
     async fn replace_all_expected_switches(
         &self,
         request: Request<rpc::ExpectedSwitchList>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_switch::replace_all_expected_switches(self, request).await
+        crate::audit_log::audited(self, "ReplaceAllExpectedSwitches", request, |request| {
+            crate::handlers::expected_switch::replace_all_expected_switches(self, request)
+        })
+        .await
     }
 
     async fn get_all_expected_switches(
@@ -1530,7 +1944,10 @@ impl Forge for Api {
         &self,
         request: Request<()>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::expected_switch::delete_all_expected_switches(self, request).await
+        crate::audit_log::audited(self, "DeleteAllExpectedSwitches", request, |request| {
+            crate::handlers::expected_switch::delete_all_expected_switches(self, request)
+        })
+        .await
     }
 
     async fn find_connected_devices_by_dpu_machine_ids(
@@ -1573,21 +1990,30 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::CreateMeasurementSystemProfileRequest>,
     ) -> Result<Response<measured_boot_pb::CreateMeasurementSystemProfileResponse>, Status> {
-        crate::handlers::measured_boot::create_system_profile(self, request).await
+        crate::audit_log::audited(self, "CreateMeasurementSystemProfile", request, |request| {
+            crate::handlers::measured_boot::create_system_profile(self, request)
+        })
+        .await
     }
 
     async fn delete_measurement_system_profile(
         &self,
         request: Request<measured_boot_pb::DeleteMeasurementSystemProfileRequest>,
     ) -> Result<Response<measured_boot_pb::DeleteMeasurementSystemProfileResponse>, Status> {
-        crate::handlers::measured_boot::delete_system_profile(self, request).await
+        crate::audit_log::audited(self, "DeleteMeasurementSystemProfile", request, |request| {
+            crate::handlers::measured_boot::delete_system_profile(self, request)
+        })
+        .await
     }
 
     async fn rename_measurement_system_profile(
         &self,
         request: Request<measured_boot_pb::RenameMeasurementSystemProfileRequest>,
     ) -> Result<Response<measured_boot_pb::RenameMeasurementSystemProfileResponse>, Status> {
-        crate::handlers::measured_boot::rename_system_profile(self, request).await
+        crate::audit_log::audited(self, "RenameMeasurementSystemProfile", request, |request| {
+            crate::handlers::measured_boot::rename_system_profile(self, request)
+        })
+        .await
     }
 
     async fn show_measurement_system_profile(
@@ -1631,28 +2057,40 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::CreateMeasurementReportRequest>,
     ) -> Result<Response<measured_boot_pb::CreateMeasurementReportResponse>, Status> {
-        crate::handlers::measured_boot::create_report(self, request).await
+        crate::audit_log::audited(self, "CreateMeasurementReport", request, |request| {
+            crate::handlers::measured_boot::create_report(self, request)
+        })
+        .await
     }
 
     async fn delete_measurement_report(
         &self,
         request: Request<measured_boot_pb::DeleteMeasurementReportRequest>,
     ) -> Result<Response<measured_boot_pb::DeleteMeasurementReportResponse>, Status> {
-        crate::handlers::measured_boot::delete_report(self, request).await
+        crate::audit_log::audited(self, "DeleteMeasurementReport", request, |request| {
+            crate::handlers::measured_boot::delete_report(self, request)
+        })
+        .await
     }
 
     async fn promote_measurement_report(
         &self,
         request: Request<measured_boot_pb::PromoteMeasurementReportRequest>,
     ) -> Result<Response<measured_boot_pb::PromoteMeasurementReportResponse>, Status> {
-        crate::handlers::measured_boot::promote_report(self, request).await
+        crate::audit_log::audited(self, "PromoteMeasurementReport", request, |request| {
+            crate::handlers::measured_boot::promote_report(self, request)
+        })
+        .await
     }
 
     async fn revoke_measurement_report(
         &self,
         request: Request<measured_boot_pb::RevokeMeasurementReportRequest>,
     ) -> Result<Response<measured_boot_pb::RevokeMeasurementReportResponse>, Status> {
-        crate::handlers::measured_boot::revoke_report(self, request).await
+        crate::audit_log::audited(self, "RevokeMeasurementReport", request, |request| {
+            crate::handlers::measured_boot::revoke_report(self, request)
+        })
+        .await
     }
 
     async fn show_measurement_report_for_id(
@@ -1690,32 +2128,51 @@ impl Forge for Api {
         crate::handlers::measured_boot::match_report(self, request).await
     }
 
+    async fn diff_measurement_report(
+        &self,
+        request: Request<measured_boot_pb::DiffMeasurementReportRequest>,
+    ) -> Result<Response<measured_boot_pb::DiffMeasurementReportResponse>, Status> {
+        crate::handlers::measured_boot::diff_report(self, request).await
+    }
+
     async fn create_measurement_bundle(
         &self,
         request: Request<measured_boot_pb::CreateMeasurementBundleRequest>,
     ) -> Result<Response<measured_boot_pb::CreateMeasurementBundleResponse>, Status> {
-        crate::handlers::measured_boot::create_bundle(self, request).await
+        crate::audit_log::audited(self, "CreateMeasurementBundle", request, |request| {
+            crate::handlers::measured_boot::create_bundle(self, request)
+        })
+        .await
     }
 
     async fn delete_measurement_bundle(
         &self,
         request: Request<measured_boot_pb::DeleteMeasurementBundleRequest>,
     ) -> Result<Response<measured_boot_pb::DeleteMeasurementBundleResponse>, Status> {
-        crate::handlers::measured_boot::delete_bundle(self, request).await
+        crate::audit_log::audited(self, "DeleteMeasurementBundle", request, |request| {
+            crate::handlers::measured_boot::delete_bundle(self, request)
+        })
+        .await
     }
 
     async fn rename_measurement_bundle(
         &self,
         request: Request<measured_boot_pb::RenameMeasurementBundleRequest>,
     ) -> Result<Response<measured_boot_pb::RenameMeasurementBundleResponse>, Status> {
-        crate::handlers::measured_boot::rename_bundle(self, request).await
+        crate::audit_log::audited(self, "RenameMeasurementBundle", request, |request| {
+            crate::handlers::measured_boot::rename_bundle(self, request)
+        })
+        .await
     }
 
     async fn update_measurement_bundle(
         &self,
         request: Request<measured_boot_pb::UpdateMeasurementBundleRequest>,
     ) -> Result<Response<measured_boot_pb::UpdateMeasurementBundleResponse>, Status> {
-        crate::handlers::measured_boot::update_bundle(self, request).await
+        crate::audit_log::audited(self, "UpdateMeasurementBundle", request, |request| {
+            crate::handlers::measured_boot::update_bundle(self, request)
+        })
+        .await
     }
 
     async fn show_measurement_bundle(
@@ -1757,7 +2214,10 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::DeleteMeasurementJournalRequest>,
     ) -> Result<Response<measured_boot_pb::DeleteMeasurementJournalResponse>, Status> {
-        crate::handlers::measured_boot::delete_journal(self, request).await
+        crate::audit_log::audited(self, "DeleteMeasurementJournal", request, |request| {
+            crate::handlers::measured_boot::delete_journal(self, request)
+        })
+        .await
     }
 
     async fn show_measurement_journal(
@@ -1785,7 +2245,10 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::AttestCandidateMachineRequest>,
     ) -> Result<Response<measured_boot_pb::AttestCandidateMachineResponse>, Status> {
-        crate::handlers::measured_boot::attest_candidate_machine(self, request).await
+        crate::audit_log::audited(self, "AttestCandidateMachine", request, |request| {
+            crate::handlers::measured_boot::attest_candidate_machine(self, request)
+        })
+        .await
     }
 
     async fn show_candidate_machine(
@@ -1813,7 +2276,10 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::ImportSiteMeasurementsRequest>,
     ) -> Result<Response<measured_boot_pb::ImportSiteMeasurementsResponse>, Status> {
-        crate::handlers::measured_boot::import_site_measurements(self, request).await
+        crate::audit_log::audited(self, "ImportSiteMeasurements", request, |request| {
+            crate::handlers::measured_boot::import_site_measurements(self, request)
+        })
+        .await
     }
 
     async fn export_site_measurements(
@@ -1827,14 +2293,23 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::AddMeasurementTrustedMachineRequest>,
     ) -> Result<Response<measured_boot_pb::AddMeasurementTrustedMachineResponse>, Status> {
-        crate::handlers::measured_boot::add_trusted_machine(self, request).await
+        crate::audit_log::audited(self, "AddMeasurementTrustedMachine", request, |request| {
+            crate::handlers::measured_boot::add_trusted_machine(self, request)
+        })
+        .await
     }
 
     async fn remove_measurement_trusted_machine(
         &self,
         request: Request<measured_boot_pb::RemoveMeasurementTrustedMachineRequest>,
     ) -> Result<Response<measured_boot_pb::RemoveMeasurementTrustedMachineResponse>, Status> {
-        crate::handlers::measured_boot::remove_trusted_machine(self, request).await
+        crate::audit_log::audited(
+            self,
+            "RemoveMeasurementTrustedMachine",
+            request,
+            |request| crate::handlers::measured_boot::remove_trusted_machine(self, request),
+        )
+        .await
     }
 
     async fn list_measurement_trusted_machines(
@@ -1848,14 +2323,23 @@ impl Forge for Api {
         &self,
         request: Request<measured_boot_pb::AddMeasurementTrustedProfileRequest>,
     ) -> Result<Response<measured_boot_pb::AddMeasurementTrustedProfileResponse>, Status> {
-        crate::handlers::measured_boot::add_trusted_profile(self, request).await
+        crate::audit_log::audited(self, "AddMeasurementTrustedProfile", request, |request| {
+            crate::handlers::measured_boot::add_trusted_profile(self, request)
+        })
+        .await
     }
 
     async fn remove_measurement_trusted_profile(
         &self,
         request: Request<measured_boot_pb::RemoveMeasurementTrustedProfileRequest>,
     ) -> Result<Response<measured_boot_pb::RemoveMeasurementTrustedProfileResponse>, Status> {
-        crate::handlers::measured_boot::remove_trusted_profile(self, request).await
+        crate::audit_log::audited(
+            self,
+            "RemoveMeasurementTrustedProfile",
+            request,
+            |request| crate::handlers::measured_boot::remove_trusted_profile(self, request),
+        )
+        .await
     }
 
     async fn list_measurement_trusted_profiles(
@@ -1906,7 +2390,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::MachineSetAutoUpdateRequest>,
     ) -> Result<Response<rpc::MachineSetAutoUpdateResponse>, Status> {
-        crate::handlers::machine::machine_set_auto_update(self, request).await
+        crate::audit_log::audited(self, "MachineSetAutoUpdate", request, |request| {
+            crate::handlers::machine::machine_set_auto_update(self, request)
+        })
+        .await
     }
 
     async fn get_machine_validation_external_config(
@@ -1929,8 +2416,15 @@ impl Forge for Api {
         &self,
         request: Request<rpc::AddUpdateMachineValidationExternalConfigRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::machine_validation::add_update_machine_validation_external_config(
-            self, request,
+        crate::audit_log::audited(
+            self,
+            "AddUpdateMachineValidationExternalConfig",
+            request,
+            |request| {
+                crate::handlers::machine_validation::add_update_machine_validation_external_config(
+                    self, request,
+                )
+            },
         )
         .await
     }
@@ -1939,7 +2433,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::OsImageAttributes>,
     ) -> Result<Response<rpc::OsImage>, Status> {
-        crate::storage::create_os_image(self, request).await
+        crate::audit_log::audited(self, "CreateOsImage", request, |request| {
+            crate::storage::create_os_image(self, request)
+        })
+        .await
     }
 
     async fn list_os_image(
@@ -1960,14 +2457,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::DeleteOsImageRequest>,
     ) -> Result<Response<rpc::DeleteOsImageResponse>, Status> {
-        crate::storage::delete_os_image(self, request).await
+        crate::audit_log::audited(self, "DeleteOsImage", request, |request| {
+            crate::storage::delete_os_image(self, request)
+        })
+        .await
     }
 
     async fn update_os_image(
         &self,
         request: Request<rpc::OsImageAttributes>,
     ) -> Result<Response<rpc::OsImage>, Status> {
-        crate::storage::update_os_image(self, request).await
+        crate::audit_log::audited(self, "UpdateOsImage", request, |request| {
+            crate::storage::update_os_image(self, request)
+        })
+        .await
     }
     async fn get_machine_validation_runs(
         &self,
@@ -1980,21 +2483,30 @@ impl Forge for Api {
         &self,
         request: Request<rpc::AdminPowerControlRequest>,
     ) -> Result<Response<rpc::AdminPowerControlResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::admin_power_control(self, request).await
+        crate::audit_log::audited(self, "AdminPowerControl", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::admin_power_control(self, request)
+        })
+        .await
     }
 
     async fn on_demand_machine_validation(
         &self,
         request: Request<rpc::MachineValidationOnDemandRequest>,
     ) -> Result<Response<rpc::MachineValidationOnDemandResponse>, Status> {
-        crate::handlers::machine_validation::on_demand_machine_validation(self, request).await
+        crate::audit_log::audited(self, "OnDemandMachineValidation", request, |request| {
+            crate::handlers::machine_validation::on_demand_machine_validation(self, request)
+        })
+        .await
     }
 
     async fn tpm_add_ca_cert(
         &self,
         request: Request<rpc::TpmCaCert>,
     ) -> Result<Response<rpc::TpmCaAddedCaStatus>, Status> {
-        crate::handlers::tpm_ca::tpm_add_ca_cert(self, request).await
+        crate::audit_log::audited(self, "TpmAddCaCert", request, |request| {
+            crate::handlers::tpm_ca::tpm_add_ca_cert(self, request)
+        })
+        .await
     }
 
     async fn tpm_show_ca_certs(
@@ -2015,15 +2527,25 @@ impl Forge for Api {
         &self,
         request: Request<rpc::TpmCaCertId>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::tpm_ca::tpm_delete_ca_cert(self, request).await
+        crate::audit_log::audited(self, "TpmDeleteCaCert", request, |request| {
+            crate::handlers::tpm_ca::tpm_delete_ca_cert(self, request)
+        })
+        .await
     }
 
     async fn remove_machine_validation_external_config(
         &self,
         request: Request<rpc::RemoveMachineValidationExternalConfigRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::machine_validation::remove_machine_validation_external_config(
-            self, request,
+        crate::audit_log::audited(
+            self,
+            "RemoveMachineValidationExternalConfig",
+            request,
+            |request| {
+                crate::handlers::machine_validation::remove_machine_validation_external_config(
+                    self, request,
+                )
+            },
         )
         .await
     }
@@ -2039,36 +2561,61 @@ impl Forge for Api {
         &self,
         request: Request<rpc::MachineValidationTestUpdateRequest>,
     ) -> Result<Response<rpc::MachineValidationTestAddUpdateResponse>, Status> {
-        crate::handlers::machine_validation::update_machine_validation_test(self, request).await
+        crate::audit_log::audited(self, "UpdateMachineValidationTest", request, |request| {
+            crate::handlers::machine_validation::update_machine_validation_test(self, request)
+        })
+        .await
     }
     async fn add_machine_validation_test(
         &self,
         request: Request<rpc::MachineValidationTestAddRequest>,
     ) -> Result<Response<rpc::MachineValidationTestAddUpdateResponse>, Status> {
-        crate::handlers::machine_validation::add_machine_validation_test(self, request).await
+        crate::audit_log::audited(self, "AddMachineValidationTest", request, |request| {
+            crate::handlers::machine_validation::add_machine_validation_test(self, request)
+        })
+        .await
     }
 
     async fn machine_validation_test_verfied(
         &self,
         request: Request<rpc::MachineValidationTestVerfiedRequest>,
     ) -> Result<Response<rpc::MachineValidationTestVerfiedResponse>, Status> {
-        crate::handlers::machine_validation::machine_validation_test_verfied(self, request).await
+        crate::audit_log::audited(self, "MachineValidationTestVerfied", request, |request| {
+            crate::handlers::machine_validation::machine_validation_test_verfied(self, request)
+        })
+        .await
     }
 
     async fn machine_validation_test_next_version(
         &self,
         request: Request<rpc::MachineValidationTestNextVersionRequest>,
     ) -> Result<Response<rpc::MachineValidationTestNextVersionResponse>, Status> {
-        crate::handlers::machine_validation::machine_validation_test_next_version(self, request)
-            .await
+        crate::audit_log::audited(
+            self,
+            "MachineValidationTestNextVersion",
+            request,
+            |request| {
+                crate::handlers::machine_validation::machine_validation_test_next_version(
+                    self, request,
+                )
+            },
+        )
+        .await
     }
 
     async fn machine_validation_test_enable_disable_test(
         &self,
         request: Request<rpc::MachineValidationTestEnableDisableTestRequest>,
     ) -> Result<Response<rpc::MachineValidationTestEnableDisableTestResponse>, Status> {
-        crate::handlers::machine_validation::machine_validation_test_enable_disable_test(
-            self, request,
+        crate::audit_log::audited(
+            self,
+            "MachineValidationTestEnableDisableTest",
+            request,
+            |request| {
+                crate::handlers::machine_validation::machine_validation_test_enable_disable_test(
+                    self, request,
+                )
+            },
         )
         .await
     }
@@ -2083,7 +2630,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::CreateInstanceTypeRequest>,
     ) -> Result<Response<rpc::CreateInstanceTypeResponse>, Status> {
-        crate::handlers::instance_type::create(self, request).await
+        crate::audit_log::audited(self, "CreateInstanceType", request, |request| {
+            crate::handlers::instance_type::create(self, request)
+        })
+        .await
     }
 
     async fn find_instance_type_ids(
@@ -2104,28 +2654,46 @@ impl Forge for Api {
         &self,
         request: Request<rpc::DeleteInstanceTypeRequest>,
     ) -> Result<Response<rpc::DeleteInstanceTypeResponse>, Status> {
-        crate::handlers::instance_type::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteInstanceType", request, |request| {
+            crate::handlers::instance_type::delete(self, request)
+        })
+        .await
     }
 
     async fn update_instance_type(
         &self,
         request: Request<rpc::UpdateInstanceTypeRequest>,
     ) -> Result<Response<rpc::UpdateInstanceTypeResponse>, Status> {
-        crate::handlers::instance_type::update(self, request).await
+        crate::audit_log::audited(self, "UpdateInstanceType", request, |request| {
+            crate::handlers::instance_type::update(self, request)
+        })
+        .await
     }
 
     async fn associate_machines_with_instance_type(
         &self,
         request: Request<rpc::AssociateMachinesWithInstanceTypeRequest>,
     ) -> Result<Response<rpc::AssociateMachinesWithInstanceTypeResponse>, Status> {
-        crate::handlers::instance_type::associate_machines(self, request).await
+        crate::audit_log::audited(
+            self,
+            "AssociateMachinesWithInstanceType",
+            request,
+            |request| crate::handlers::instance_type::associate_machines(self, request),
+        )
+        .await
     }
 
     async fn remove_machine_instance_type_association(
         &self,
         request: Request<rpc::RemoveMachineInstanceTypeAssociationRequest>,
     ) -> Result<Response<rpc::RemoveMachineInstanceTypeAssociationResponse>, Status> {
-        crate::handlers::instance_type::remove_machine_association(self, request).await
+        crate::audit_log::audited(
+            self,
+            "RemoveMachineInstanceTypeAssociation",
+            request,
+            |request| crate::handlers::instance_type::remove_machine_association(self, request),
+        )
+        .await
     }
 
     async fn redfish_browse(
@@ -2146,27 +2714,39 @@ impl Forge for Api {
         &self,
         request: Request<rpc::RedfishCreateActionRequest>,
     ) -> Result<Response<rpc::RedfishCreateActionResponse>, Status> {
-        crate::handlers::redfish::redfish_create_action(self, request).await
+        crate::audit_log::audited(self, "RedfishCreateAction", request, |request| {
+            crate::handlers::redfish::redfish_create_action(self, request)
+        })
+        .await
     }
 
     async fn redfish_approve_action(
         &self,
         request: Request<rpc::RedfishActionId>,
     ) -> Result<Response<rpc::RedfishApproveActionResponse>, Status> {
-        crate::handlers::redfish::redfish_approve_action(self, request).await
+        crate::audit_log::audited(self, "RedfishApproveAction", request, |request| {
+            crate::handlers::redfish::redfish_approve_action(self, request)
+        })
+        .await
     }
     async fn redfish_apply_action(
         &self,
         request: Request<rpc::RedfishActionId>,
     ) -> Result<Response<rpc::RedfishApplyActionResponse>, Status> {
-        crate::handlers::redfish::redfish_apply_action(self, request).await
+        crate::audit_log::audited(self, "RedfishApplyAction", request, |request| {
+            crate::handlers::redfish::redfish_apply_action(self, request)
+        })
+        .await
     }
 
     async fn redfish_cancel_action(
         &self,
         request: Request<rpc::RedfishActionId>,
     ) -> Result<Response<rpc::RedfishCancelActionResponse>, Status> {
-        crate::handlers::redfish::redfish_cancel_action(self, request).await
+        crate::audit_log::audited(self, "RedfishCancelAction", request, |request| {
+            crate::handlers::redfish::redfish_cancel_action(self, request)
+        })
+        .await
     }
 
     async fn ufm_browse(
@@ -2180,7 +2760,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::CreateNetworkSecurityGroupRequest>,
     ) -> Result<Response<rpc::CreateNetworkSecurityGroupResponse>, Status> {
-        crate::handlers::network_security_group::create(self, request).await
+        crate::audit_log::audited(self, "CreateNetworkSecurityGroup", request, |request| {
+            crate::handlers::network_security_group::create(self, request)
+        })
+        .await
     }
 
     async fn find_network_security_group_ids(
@@ -2201,14 +2784,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::DeleteNetworkSecurityGroupRequest>,
     ) -> Result<Response<rpc::DeleteNetworkSecurityGroupResponse>, Status> {
-        crate::handlers::network_security_group::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteNetworkSecurityGroup", request, |request| {
+            crate::handlers::network_security_group::delete(self, request)
+        })
+        .await
     }
 
     async fn update_network_security_group(
         &self,
         request: Request<rpc::UpdateNetworkSecurityGroupRequest>,
     ) -> Result<Response<rpc::UpdateNetworkSecurityGroupResponse>, Status> {
-        crate::handlers::network_security_group::update(self, request).await
+        crate::audit_log::audited(self, "UpdateNetworkSecurityGroup", request, |request| {
+            crate::handlers::network_security_group::update(self, request)
+        })
+        .await
     }
 
     async fn get_network_security_group_propagation_status(
@@ -2225,6 +2814,13 @@ impl Forge for Api {
         crate::handlers::network_security_group::get_attachments(self, request).await
     }
 
+    async fn explain_network_security_group_flow(
+        &self,
+        request: Request<rpc::ExplainNetworkSecurityGroupFlowRequest>,
+    ) -> Result<Response<rpc::ExplainNetworkSecurityGroupFlowResponse>, Status> {
+        crate::handlers::network_security_group::explain_flow(self, request).await
+    }
+
     async fn get_desired_firmware_versions(
         &self,
         request: Request<rpc::GetDesiredFirmwareVersionsRequest>,
@@ -2236,18 +2832,27 @@ impl Forge for Api {
         &self,
         request: Request<rpc::SkuList>,
     ) -> Result<Response<rpc::SkuIdList>, Status> {
-        crate::handlers::sku::create(self, request).await
+        crate::audit_log::audited(self, "CreateSku", request, |request| {
+            crate::handlers::sku::create(self, request)
+        })
+        .await
     }
 
     async fn delete_sku(&self, request: Request<SkuIdList>) -> Result<Response<()>, Status> {
-        crate::handlers::sku::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteSku", request, |request| {
+            crate::handlers::sku::delete(self, request)
+        })
+        .await
     }
 
     async fn generate_sku_from_machine(
         &self,
         request: Request<MachineId>,
     ) -> Result<Response<rpc::Sku>, Status> {
-        crate::handlers::sku::generate_from_machine(self, request).await
+        crate::audit_log::audited(self, "GenerateSkuFromMachine", request, |request| {
+            crate::handlers::sku::generate_from_machine(self, request)
+        })
+        .await
     }
 
     async fn verify_sku_for_machine(
@@ -2261,14 +2866,20 @@ impl Forge for Api {
         &self,
         request: Request<::rpc::forge::SkuMachinePair>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::sku::assign_to_machine(self, request).await
+        crate::audit_log::audited(self, "AssignSkuToMachine", request, |request| {
+            crate::handlers::sku::assign_to_machine(self, request)
+        })
+        .await
     }
 
     async fn remove_sku_association(
         &self,
         request: Request<RemoveSkuRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::sku::remove_sku_association(self, request).await
+        crate::audit_log::audited(self, "RemoveSkuAssociation", request, |request| {
+            crate::handlers::sku::remove_sku_association(self, request)
+        })
+        .await
     }
 
     async fn get_all_sku_ids(
@@ -2289,18 +2900,27 @@ impl Forge for Api {
         &self,
         request: Request<rpc::SkuUpdateMetadataRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::sku::update_sku_metadata(self, request).await
+        crate::audit_log::audited(self, "UpdateSkuMetadata", request, |request| {
+            crate::handlers::sku::update_sku_metadata(self, request)
+        })
+        .await
     }
 
     async fn replace_sku(&self, request: Request<rpc::Sku>) -> Result<Response<rpc::Sku>, Status> {
-        crate::handlers::sku::replace_sku(self, request).await
+        crate::audit_log::audited(self, "ReplaceSku", request, |request| {
+            crate::handlers::sku::replace_sku(self, request)
+        })
+        .await
     }
 
     async fn set_managed_host_quarantine_state(
         &self,
         request: Request<rpc::SetManagedHostQuarantineStateRequest>,
     ) -> Result<Response<rpc::SetManagedHostQuarantineStateResponse>, Status> {
-        crate::handlers::machine_quarantine::set_managed_host_quarantine_state(self, request).await
+        crate::audit_log::audited(self, "SetManagedHostQuarantineState", request, |request| {
+            crate::handlers::machine_quarantine::set_managed_host_quarantine_state(self, request)
+        })
+        .await
     }
 
     async fn get_managed_host_quarantine_state(
@@ -2314,22 +2934,37 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ClearManagedHostQuarantineStateRequest>,
     ) -> Result<Response<rpc::ClearManagedHostQuarantineStateResponse>, Status> {
-        crate::handlers::machine_quarantine::clear_managed_host_quarantine_state(self, request)
-            .await
+        crate::audit_log::audited(
+            self,
+            "ClearManagedHostQuarantineState",
+            request,
+            |request| {
+                crate::handlers::machine_quarantine::clear_managed_host_quarantine_state(
+                    self, request,
+                )
+            },
+        )
+        .await
     }
 
     async fn reset_host_reprovisioning(
         &self,
         request: Request<MachineId>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::host_reprovisioning::reset_host_reprovisioning(self, request).await
+        crate::audit_log::audited(self, "ResetHostReprovisioning", request, |request| {
+            crate::handlers::host_reprovisioning::reset_host_reprovisioning(self, request)
+        })
+        .await
     }
 
     async fn copy_bfb_to_dpu_rshim(
         &self,
         request: Request<rpc::CopyBfbToDpuRshimRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::bmc_endpoint_explorer::copy_bfb_to_dpu_rshim(self, request).await
+        crate::audit_log::audited(self, "CopyBfbToDpuRshim", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::copy_bfb_to_dpu_rshim(self, request)
+        })
+        .await
     }
 
     async fn find_nv_link_partition_ids(
@@ -2371,14 +3006,20 @@ impl Forge for Api {
         &self,
         request: Request<rpc::NvLinkLogicalPartitionCreationRequest>,
     ) -> Result<Response<rpc::NvLinkLogicalPartition>, Status> {
-        crate::handlers::logical_partition::create(self, request).await
+        crate::audit_log::audited(self, "CreateNVLinkLogicalPartition", request, |request| {
+            crate::handlers::logical_partition::create(self, request)
+        })
+        .await
     }
 
     async fn delete_nv_link_logical_partition(
         &self,
         request: Request<rpc::NvLinkLogicalPartitionDeletionRequest>,
     ) -> Result<Response<rpc::NvLinkLogicalPartitionDeletionResult>, Status> {
-        crate::handlers::logical_partition::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteNVLinkLogicalPartition", request, |request| {
+            crate::handlers::logical_partition::delete(self, request)
+        })
+        .await
     }
 
     async fn nv_link_logical_partitions_for_tenant(
@@ -2392,7 +3033,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::NvLinkLogicalPartitionUpdateRequest>,
     ) -> Result<Response<rpc::NvLinkLogicalPartitionUpdateResult>, Status> {
-        crate::handlers::logical_partition::update(self, request).await
+        crate::audit_log::audited(self, "UpdateNVLinkLogicalPartition", request, |request| {
+            crate::handlers::logical_partition::update(self, request)
+        })
+        .await
     }
 
     async fn nmxm_browse(
@@ -2458,21 +3102,30 @@ impl Forge for Api {
         &self,
         request: Request<rpc::CreateBmcUserRequest>,
     ) -> Result<Response<rpc::CreateBmcUserResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::create_bmc_user(self, request).await
+        crate::audit_log::audited(self, "CreateBmcUser", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::create_bmc_user(self, request)
+        })
+        .await
     }
 
     async fn delete_bmc_user(
         &self,
         request: Request<rpc::DeleteBmcUserRequest>,
     ) -> Result<Response<rpc::DeleteBmcUserResponse>, Status> {
-        crate::handlers::bmc_endpoint_explorer::delete_bmc_user(self, request).await
+        crate::audit_log::audited(self, "DeleteBmcUser", request, |request| {
+            crate::handlers::bmc_endpoint_explorer::delete_bmc_user(self, request)
+        })
+        .await
     }
 
     async fn set_firmware_update_time_window(
         &self,
         request: Request<rpc::SetFirmwareUpdateTimeWindowRequest>,
     ) -> Result<Response<rpc::SetFirmwareUpdateTimeWindowResponse>, Status> {
-        crate::handlers::firmware::set_firmware_update_time_window(self, request).await
+        crate::audit_log::audited(self, "SetFirmwareUpdateTimeWindow", request, |request| {
+            crate::handlers::firmware::set_firmware_update_time_window(self, request)
+        })
+        .await
     }
 
     async fn list_host_firmware(
@@ -2503,42 +3156,60 @@ impl Forge for Api {
         &self,
         request: Request<rpc::TrimTableRequest>,
     ) -> Result<Response<rpc::TrimTableResponse>, Status> {
-        crate::handlers::db::trim_table(self, request).await
+        crate::audit_log::audited(self, "TrimTable", request, |request| {
+            crate::handlers::db::trim_table(self, request)
+        })
+        .await
     }
 
     async fn create_remediation(
         &self,
         request: Request<rpc::CreateRemediationRequest>,
     ) -> Result<Response<rpc::CreateRemediationResponse>, Status> {
-        crate::handlers::dpu_remediation::create(self, request).await
+        crate::audit_log::audited(self, "CreateRemediation", request, |request| {
+            crate::handlers::dpu_remediation::create(self, request)
+        })
+        .await
     }
 
     async fn approve_remediation(
         &self,
         request: Request<rpc::ApproveRemediationRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::dpu_remediation::approve(self, request).await
+        crate::audit_log::audited(self, "ApproveRemediation", request, |request| {
+            crate::handlers::dpu_remediation::approve(self, request)
+        })
+        .await
     }
 
     async fn revoke_remediation(
         &self,
         request: Request<rpc::RevokeRemediationRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::dpu_remediation::revoke(self, request).await
+        crate::audit_log::audited(self, "RevokeRemediation", request, |request| {
+            crate::handlers::dpu_remediation::revoke(self, request)
+        })
+        .await
     }
 
     async fn enable_remediation(
         &self,
         request: Request<rpc::EnableRemediationRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::dpu_remediation::enable(self, request).await
+        crate::audit_log::audited(self, "EnableRemediation", request, |request| {
+            crate::handlers::dpu_remediation::enable(self, request)
+        })
+        .await
     }
 
     async fn disable_remediation(
         &self,
         request: Request<rpc::DisableRemediationRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::dpu_remediation::disable(self, request).await
+        crate::audit_log::audited(self, "DisableRemediation", request, |request| {
+            crate::handlers::dpu_remediation::disable(self, request)
+        })
+        .await
     }
 
     async fn find_remediation_ids(
@@ -2587,28 +3258,40 @@ impl Forge for Api {
         &self,
         request: Request<rpc::SetPrimaryDpuRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::managed_host::set_primary_dpu(self, request).await
+        crate::audit_log::audited(self, "SetPrimaryDpu", request, |request| {
+            crate::handlers::managed_host::set_primary_dpu(self, request)
+        })
+        .await
     }
 
     async fn create_dpu_extension_service(
         &self,
         request: Request<rpc::CreateDpuExtensionServiceRequest>,
     ) -> Result<Response<rpc::DpuExtensionService>, Status> {
-        crate::handlers::extension_service::create(self, request).await
+        crate::audit_log::audited(self, "CreateDpuExtensionService", request, |request| {
+            crate::handlers::extension_service::create(self, request)
+        })
+        .await
     }
 
     async fn update_dpu_extension_service(
         &self,
         request: Request<rpc::UpdateDpuExtensionServiceRequest>,
     ) -> Result<Response<rpc::DpuExtensionService>, Status> {
-        crate::handlers::extension_service::update(self, request).await
+        crate::audit_log::audited(self, "UpdateDpuExtensionService", request, |request| {
+            crate::handlers::extension_service::update(self, request)
+        })
+        .await
     }
 
     async fn delete_dpu_extension_service(
         &self,
         request: Request<rpc::DeleteDpuExtensionServiceRequest>,
     ) -> Result<Response<rpc::DeleteDpuExtensionServiceResponse>, Status> {
-        crate::handlers::extension_service::delete(self, request).await
+        crate::audit_log::audited(self, "DeleteDpuExtensionService", request, |request| {
+            crate::handlers::extension_service::delete(self, request)
+        })
+        .await
     }
 
     async fn find_dpu_extension_service_ids(
@@ -2643,14 +3326,20 @@ impl Forge for Api {
         &self,
         request: tonic::Request<rpc::AttestationData>,
     ) -> Result<tonic::Response<()>, Status> {
-        crate::handlers::attestation::trigger_machine_attestation(self, request).await
+        crate::audit_log::audited(self, "TriggerMachineAttestation", request, |request| {
+            crate::handlers::attestation::trigger_machine_attestation(self, request)
+        })
+        .await
     }
 
     async fn cancel_machine_attestation(
         &self,
         request: tonic::Request<rpc::AttestationData>,
     ) -> Result<tonic::Response<()>, Status> {
-        crate::handlers::attestation::cancel_machine_attestation(self, request).await
+        crate::audit_log::audited(self, "CancelMachineAttestation", request, |request| {
+            crate::handlers::attestation::cancel_machine_attestation(self, request)
+        })
+        .await
     }
 
     async fn find_machines_under_attestation(
@@ -2682,7 +3371,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ModifyDpfStateRequest>,
     ) -> Result<Response<()>, Status> {
-        crate::handlers::dpf::modify_dpf_state(self, request).await
+        crate::audit_log::audited(self, "ModifyDPFState", request, |request| {
+            crate::handlers::dpf::modify_dpf_state(self, request)
+        })
+        .await
     }
 
     async fn get_dpf_state(
@@ -2720,7 +3412,10 @@ impl Forge for Api {
         &self,
         request: Request<rpc::ScoutStreamDisconnectRequest>,
     ) -> Result<Response<rpc::ScoutStreamDisconnectResponse>, Status> {
-        crate::handlers::scout_stream::disconnect(self, request).await
+        crate::audit_log::audited(self, "ScoutStreamDisconnect", request, |request| {
+            crate::handlers::scout_stream::disconnect(self, request)
+        })
+        .await
     }
 
     // scout_stream_ping is used to ping the
@@ -2736,14 +3431,17 @@ impl Forge for Api {
         &self,
         request: Request<mlx_device_pb::MlxAdminProfileSyncRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminProfileSyncResponse>, Status> {
-        crate::handlers::mlx_admin::profile_sync(self, request).await
+        crate::audit_log::audited(self, "MlxAdminProfileSync", request, |request| {
+            crate::handlers::mlx_admin::profile_sync(self, request)
+        })
+        .await
     }
 
     async fn mlx_admin_profile_show(
         &self,
         request: Request<mlx_device_pb::MlxAdminProfileShowRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminProfileShowResponse>, Status> {
-        crate::handlers::mlx_admin::profile_show(self, request)
+        crate::handlers::mlx_admin::profile_show(self, request).await
     }
 
     async fn mlx_admin_profile_compare(
@@ -2757,21 +3455,84 @@ impl Forge for Api {
         &self,
         request: Request<mlx_device_pb::MlxAdminProfileListRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminProfileListResponse>, Status> {
-        crate::handlers::mlx_admin::profile_list(self, request)
+        crate::handlers::mlx_admin::profile_list(self, request).await
+    }
+
+    async fn mlx_admin_profile_create(
+        &self,
+        request: Request<mlx_device_pb::MlxAdminProfileCreateRequest>,
+    ) -> Result<Response<mlx_device_pb::MlxAdminProfileCreateResponse>, Status> {
+        crate::audit_log::audited(self, "MlxAdminProfileCreate", request, |request| {
+            crate::handlers::mlx_admin::profile_create(self, request)
+        })
+        .await
+    }
+
+    async fn mlx_admin_profile_update(
+        &self,
+        request: Request<mlx_device_pb::MlxAdminProfileUpdateRequest>,
+    ) -> Result<Response<mlx_device_pb::MlxAdminProfileUpdateResponse>, Status> {
+        crate::audit_log::audited(self, "MlxAdminProfileUpdate", request, |request| {
+            crate::handlers::mlx_admin::profile_update(self, request)
+        })
+        .await
+    }
+
+    async fn mlx_admin_profile_delete(
+        &self,
+        request: Request<mlx_device_pb::MlxAdminProfileDeleteRequest>,
+    ) -> Result<Response<mlx_device_pb::MlxAdminProfileDeleteResponse>, Status> {
+        crate::audit_log::audited(self, "MlxAdminProfileDelete", request, |request| {
+            crate::handlers::mlx_admin::profile_delete(self, request)
+        })
+        .await
+    }
+
+    async fn mlx_admin_profile_bind(
+        &self,
+        request: Request<mlx_device_pb::MlxAdminProfileBindRequest>,
+    ) -> Result<Response<mlx_device_pb::MlxAdminProfileBindResponse>, Status> {
+        crate::audit_log::audited(self, "MlxAdminProfileBind", request, |request| {
+            crate::handlers::mlx_admin::profile_bind(self, request)
+        })
+        .await
+    }
+
+    async fn mlx_admin_profile_unbind(
+        &self,
+        request: Request<mlx_device_pb::MlxAdminProfileUnbindRequest>,
+    ) -> Result<Response<mlx_device_pb::MlxAdminProfileUnbindResponse>, Status> {
+        crate::audit_log::audited(self, "MlxAdminProfileUnbind", request, |request| {
+            crate::handlers::mlx_admin::profile_unbind(self, request)
+        })
+        .await
+    }
+
+    async fn mlx_admin_profile_binding_list(
+        &self,
+        request: Request<mlx_device_pb::MlxAdminProfileBindingListRequest>,
+    ) -> Result<Response<mlx_device_pb::MlxAdminProfileBindingListResponse>, Status> {
+        crate::handlers::mlx_admin::profile_binding_list(self, request).await
     }
 
     async fn mlx_admin_lockdown_lock(
         &self,
         request: Request<mlx_device_pb::MlxAdminLockdownLockRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminLockdownLockResponse>, Status> {
-        crate::handlers::mlx_admin::lockdown_lock(self, request).await
+        crate::audit_log::audited(self, "MlxAdminLockdownLock", request, |request| {
+            crate::handlers::mlx_admin::lockdown_lock(self, request)
+        })
+        .await
     }
 
     async fn mlx_admin_lockdown_unlock(
         &self,
         request: Request<mlx_device_pb::MlxAdminLockdownUnlockRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminLockdownUnlockResponse>, Status> {
-        crate::handlers::mlx_admin::lockdown_unlock(self, request).await
+        crate::audit_log::audited(self, "MlxAdminLockdownUnlock", request, |request| {
+            crate::handlers::mlx_admin::lockdown_unlock(self, request)
+        })
+        .await
     }
 
     async fn mlx_admin_lockdown_status(
@@ -2820,14 +3581,20 @@ impl Forge for Api {
         &self,
         request: Request<mlx_device_pb::MlxAdminConfigSetRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminConfigSetResponse>, Status> {
-        crate::handlers::mlx_admin::config_set(self, request).await
+        crate::audit_log::audited(self, "MlxAdminConfigSet", request, |request| {
+            crate::handlers::mlx_admin::config_set(self, request)
+        })
+        .await
     }
 
     async fn mlx_admin_config_sync(
         &self,
         request: Request<mlx_device_pb::MlxAdminConfigSyncRequest>,
     ) -> Result<Response<mlx_device_pb::MlxAdminConfigSyncResponse>, Status> {
-        crate::handlers::mlx_admin::config_sync(self, request).await
+        crate::audit_log::audited(self, "MlxAdminConfigSync", request, |request| {
+            crate::handlers::mlx_admin::config_sync(self, request)
+        })
+        .await
     }
 
     async fn mlx_admin_config_compare(
@@ -2861,10 +3628,21 @@ impl Forge for Api {
         &self,
         request: tonic::Request<::rpc::forge::BmcEndpointRequest>,
     ) -> Result<tonic::Response<()>, Status> {
-        crate::api::log_request_data(&request);
-
-        crate::handlers::power_options::allow_ingestion_and_power_on(self, &request.into_inner())
-            .await
+        crate::audit_log::audited(
+            self,
+            "AllowIngestionAndPowerOn",
+            request,
+            |request| async move {
+                crate::api::log_request_data(&request);
+
+                crate::handlers::power_options::allow_ingestion_and_power_on(
+                    self,
+                    &request.into_inner(),
+                )
+                .await
+            },
+        )
+        .await
     }
 }
 
@@ -2887,7 +3665,7 @@ pub(crate) fn log_tenant_organization_id(organization_id: &str) {
     tracing::Span::current().record("tenant.organization_id", organization_id);
 }
 
-fn truncate(mut s: String, len: usize) -> String {
+pub(crate) fn truncate(mut s: String, len: usize) -> String {
     if s.len() < len || len < 3 {
         return s;
     }
@@ -20,6 +20,7 @@ use std::fs::File;
 use std::io::Write;
 use std::process::Command;
 
+use ::measured_boot::eventlog::EventLog;
 use byteorder::{BigEndian, ByteOrder};
 use carbide_uuid::machine::MachineId;
 use carbide_uuid::measured_boot::MeasurementReportId;
@@ -273,14 +274,19 @@ fn extract_cred_secret(creds: &[u8]) -> CarbideResult<(Vec<u8>, Vec<u8>)> {
 /// comes to us via the proto as an Option<Vec<u8>) into a String,
 /// for passing to tracing/logging.
 ///
+/// A binary TCG event log is rendered one event per line. Older
+/// scouts send the text output of tpm2_eventlog instead, which is
+/// passed through as-is.
+///
 /// since the event log is currently "best effort", we'll log a
 /// little "error" in <>'s if we notice there's no event log.
 pub fn event_log_to_string(event_log: &Option<Vec<u8>>) -> String {
     event_log
         .as_ref()
-        .map(|log_utf8| {
-            String::from_utf8(log_utf8.to_vec())
-                .unwrap_or(String::from("<event log failed utf8 conversion>"))
+        .map(|log_bytes| match EventLog::parse(log_bytes) {
+            Ok(parsed) => parsed.to_string(),
+            Err(_) => String::from_utf8(log_bytes.to_vec())
+                .unwrap_or(String::from("<event log failed utf8 conversion>")),
         })
         .unwrap_or(String::from("<event log empty>"))
 }
@@ -17,6 +17,8 @@
 
 pub mod measured_boot;
 
+pub mod policy;
+
 pub mod tpm_ca_cert;
 use carbide_uuid::machine::MachineId;
 use db::{ObjectFilter, Transaction};
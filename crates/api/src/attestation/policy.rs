@@ -0,0 +1,488 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Enforcement of the attestation policy (`attestation_policy` in the site config).
+//!
+//! SPDM and measured boot attestation normally run at specific points of the
+//! lifecycle of a host. The policy re-attests hosts periodically, after their
+//! host firmware was updated, and after an instance was allocated on them
+//! before the instance is provisioned.
+//!
+//! A host which fails attestation raises an `AttestationFailure` health alert,
+//! which prevents allocations, and is also quarantined if the policy says so for
+//! its SKU. Both are lifted once the host passes the same kind of attestation again.
+
+use std::collections::HashMap;
+
+use ::rpc::forge as rpc;
+use ::rpc::forge_agent_control_response::Action;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use db::DatabaseError;
+use health_report::{HealthReport, OverrideMode};
+use itertools::Itertools;
+use model::attestation::spdm::{AttestationDeviceState, AttestationStatus, SpdmAttestationStatus};
+use model::machine::ManagedHostState;
+use model::machine::machine_search_config::MachineSearchConfig;
+use model::machine::network::{ManagedHostQuarantineMode, ManagedHostQuarantineState};
+use sqlx::PgConnection;
+
+use crate::CarbideResult;
+use crate::cfg::file::{AttestationFailureAction, AttestationPolicyConfig};
+
+/// The ways a host gets attested
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AttestationKind {
+    Spdm,
+    MeasuredBoot,
+}
+
+impl AttestationKind {
+    fn health_report_source(self) -> &'static str {
+        match self {
+            AttestationKind::Spdm => HealthReport::SPDM_ATTESTATION_SOURCE,
+            AttestationKind::MeasuredBoot => HealthReport::MEASURED_BOOT_ATTESTATION_SOURCE,
+        }
+    }
+
+    /// The prefix of the reason of quarantines placed by the policy. Only
+    /// quarantines with this prefix are lifted once the host passes again.
+    fn quarantine_reason_prefix(self) -> &'static str {
+        match self {
+            AttestationKind::Spdm => "SPDM attestation failed",
+            AttestationKind::MeasuredBoot => "Measured boot attestation failed",
+        }
+    }
+}
+
+/// Returns whether a host which was last attested at `last_attested_at` has to
+/// be re-attested. `allocated_at` is when the instance on the host was allocated,
+/// if there is one.
+pub(crate) fn reattestation_due(
+    policy: &AttestationPolicyConfig,
+    last_attested_at: Option<DateTime<Utc>>,
+    allocated_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    if policy.reattest_before_allocation
+        && let Some(allocated_at) = allocated_at
+        && last_attested_at.is_none_or(|attested_at| attested_at < allocated_at)
+    {
+        return true;
+    }
+
+    policy.periodic_reattestation_enabled()
+        && last_attested_at
+            .is_none_or(|attested_at| attested_at + policy.reattestation_interval < now)
+}
+
+/// Returns a `(device, message)` pair for every device which failed the SPDM
+/// attestation of a machine
+pub(crate) fn spdm_failures(
+    devices_state: &HashMap<String, AttestationDeviceState>,
+) -> Vec<(String, String)> {
+    devices_state
+        .iter()
+        .filter_map(|(device_id, state)| match state {
+            AttestationDeviceState::AttestationCompleted {
+                status: AttestationStatus::Failure { cause },
+            } => Some((device_id.clone(), cause.to_string())),
+            _ => None,
+        })
+        .sorted()
+        .collect()
+}
+
+/// Requests SPDM re-attestation of a host if it is due for it.
+/// Returns whether the host is being, or will be, re-attested.
+pub(crate) async fn request_spdm_reattestation_if_due(
+    txn: &mut PgConnection,
+    policy: &AttestationPolicyConfig,
+    machine_id: &MachineId,
+    allocated_at: Option<DateTime<Utc>>,
+) -> Result<bool, DatabaseError> {
+    let details = db::attestation::spdm::load_details_for_machine_ids(txn, &[*machine_id]).await?;
+    let Some(attestation) = details.into_iter().next().map(|details| details.machine) else {
+        // SPDM attestation was never requested for the host
+        return Ok(false);
+    };
+
+    match attestation.attestation_status {
+        SpdmAttestationStatus::NotSupported | SpdmAttestationStatus::DeviceListMismatch => {
+            Ok(false)
+        }
+        SpdmAttestationStatus::NotStarted | SpdmAttestationStatus::Started => Ok(true),
+        SpdmAttestationStatus::Completed => {
+            if attestation
+                .started_at
+                .is_none_or(|started_at| attestation.requested_at > started_at)
+            {
+                // Re-attestation was requested already, and not picked up yet
+                return Ok(true);
+            }
+            if !reattestation_due(
+                policy,
+                attestation.last_attested_at,
+                allocated_at,
+                Utc::now(),
+            ) {
+                return Ok(false);
+            }
+            tracing::info!(%machine_id, "Requesting SPDM re-attestation");
+            db::attestation::spdm::request_reattestation(txn, machine_id).await
+        }
+    }
+}
+
+/// Requests SPDM and measured boot re-attestation of a host whose host firmware
+/// was updated
+pub(crate) async fn request_reattestation_after_firmware_change(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> Result<(), DatabaseError> {
+    tracing::info!(%machine_id, "Requesting re-attestation after host firmware change");
+    db::attestation::spdm::request_reattestation(txn, machine_id).await?;
+    db::measured_boot::machine::request_reattestation(txn, machine_id).await
+}
+
+/// Decides whether scout should send new measurements for a `Ready` host,
+/// which it does once the host is due for measured boot re-attestation
+pub(crate) async fn measured_boot_scout_action(
+    txn: &mut PgConnection,
+    policy: &AttestationPolicyConfig,
+    machine_id: &MachineId,
+) -> CarbideResult<Action> {
+    let last_measured_at =
+        db::measured_boot::report::get_latest_tstamp_for_machine_id(&mut *txn, *machine_id).await?;
+    let requested_at =
+        db::measured_boot::machine::get_reattestation_requested_at(&mut *txn, machine_id).await?;
+    let allocated_at = db::instance::find_by_machine_id(txn, machine_id)
+        .await?
+        .map(|instance| instance.config_version.timestamp());
+
+    let requested = requested_at.is_some_and(|requested_at| {
+        last_measured_at.is_none_or(|measured_at| measured_at < requested_at)
+    });
+    if requested || reattestation_due(policy, last_measured_at, allocated_at, Utc::now()) {
+        tracing::info!(%machine_id, ?last_measured_at, "Requesting measured boot re-attestation");
+        return Ok(Action::Measure);
+    }
+    Ok(Action::Noop)
+}
+
+/// Applies the policy to the outcome of an attestation of a host.
+/// `failures` holds a `(target, message)` pair for every component which failed
+/// attestation, and is empty if the host passed.
+pub(crate) async fn apply_attestation_result(
+    txn: &mut PgConnection,
+    policy: &AttestationPolicyConfig,
+    machine_id: &MachineId,
+    kind: AttestationKind,
+    failures: Vec<(String, String)>,
+) -> Result<(), DatabaseError> {
+    if failures.is_empty() {
+        db::machine::remove_health_report_override(
+            txn,
+            machine_id,
+            OverrideMode::Merge,
+            kind.health_report_source(),
+        )
+        .await?;
+
+        if db::machine::get_quarantine_state(txn, machine_id)
+            .await?
+            .is_some_and(|state| {
+                state
+                    .reason_str()
+                    .starts_with(kind.quarantine_reason_prefix())
+            })
+        {
+            tracing::info!(%machine_id, ?kind, "Lifting attestation failure quarantine");
+            db::machine::clear_quarantine_state(txn, machine_id).await?;
+        }
+        return Ok(());
+    }
+
+    let machine = db::machine::find_one(txn, machine_id, MachineSearchConfig::default()).await?;
+    let action = policy.failure_action_for_sku(machine.as_ref().and_then(|m| m.hw_sku.as_deref()));
+    tracing::warn!(%machine_id, ?kind, ?action, ?failures, "Host failed attestation");
+
+    let reason = format!(
+        "{}: {}",
+        kind.quarantine_reason_prefix(),
+        failures
+            .iter()
+            .map(|(target, message)| format!("{target}: {message}"))
+            .join("; ")
+    );
+    db::machine::insert_health_report_override(
+        txn,
+        machine_id,
+        OverrideMode::Merge,
+        &HealthReport::attestation_failure(kind.health_report_source(), failures),
+        false,
+    )
+    .await?;
+
+    if action == AttestationFailureAction::Quarantine {
+        // Don't replace a quarantine which was placed for another reason
+        let quarantined_otherwise = db::machine::get_quarantine_state(txn, machine_id)
+            .await?
+            .is_some_and(|state| {
+                !state
+                    .reason_str()
+                    .starts_with(kind.quarantine_reason_prefix())
+            });
+        if !quarantined_otherwise {
+            db::machine::set_quarantine_state(
+                txn,
+                machine_id,
+                ManagedHostQuarantineState {
+                    reason: Some(reason),
+                    mode: ManagedHostQuarantineMode::BlockAllTraffic,
+                },
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the policy to the outcome of a measured boot attestation.
+/// The outcome of the first measurements of a host is left to the `Measuring`
+/// state, so the policy only applies to hosts which passed it before.
+pub(crate) async fn apply_measured_boot_result(
+    txn: &mut PgConnection,
+    policy: &AttestationPolicyConfig,
+    machine_id: &MachineId,
+    report_id: &str,
+    passed: bool,
+) -> Result<(), DatabaseError> {
+    let Some(machine) =
+        db::machine::find_one(txn, machine_id, MachineSearchConfig::default()).await?
+    else {
+        return Ok(());
+    };
+    if !matches!(
+        machine.state.value,
+        ManagedHostState::Ready | ManagedHostState::Assigned { .. }
+    ) {
+        return Ok(());
+    }
+
+    let failures = if passed {
+        vec![]
+    } else {
+        vec![(
+            "TPM".to_string(),
+            format!("Measurement report {report_id} does not match an approved bundle"),
+        )]
+    };
+    apply_attestation_result(
+        txn,
+        policy,
+        machine_id,
+        AttestationKind::MeasuredBoot,
+        failures,
+    )
+    .await
+}
+
+/// Returns how recently the host `machine_id` was attested
+pub(crate) async fn instance_attestation_status(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> Result<rpc::InstanceAttestationStatus, DatabaseError> {
+    let spdm = db::attestation::spdm::load_details_for_machine_ids(&mut *txn, &[*machine_id])
+        .await?
+        .into_iter()
+        .next()
+        .map(|details| details.machine);
+    let measured_boot_attested_at =
+        db::measured_boot::report::get_latest_tstamp_for_machine_id(&mut *txn, *machine_id).await?;
+
+    Ok(rpc::InstanceAttestationStatus {
+        spdm_attested_at: spdm
+            .as_ref()
+            .and_then(|spdm| spdm.last_attested_at)
+            .map(|x| x.into()),
+        spdm_attestation_passed: spdm.and_then(|spdm| spdm.last_attestation_passed),
+        measured_boot_attested_at: measured_boot_attested_at.map(|x| x.into()),
+    })
+}
+
+/// Fills in the attestation status of `instances`
+pub(crate) async fn add_instance_attestation_status(
+    txn: &mut PgConnection,
+    instances: &mut [rpc::Instance],
+) -> Result<(), DatabaseError> {
+    for instance in instances {
+        let (Some(machine_id), Some(status)) = (instance.machine_id, instance.status.as_mut())
+        else {
+            continue;
+        };
+        status.attestation = Some(instance_attestation_status(txn, &machine_id).await?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use model::attestation::spdm::SpdmHandlerError;
+
+    use super::*;
+
+    fn policy(
+        reattestation_interval: Duration,
+        reattest_before_allocation: bool,
+    ) -> AttestationPolicyConfig {
+        AttestationPolicyConfig {
+            reattestation_interval,
+            reattest_before_allocation,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_periodic_reattestation_due() {
+        let now = Utc::now();
+        let policy = policy(Duration::hours(24), false);
+
+        assert!(!reattestation_due(
+            &policy,
+            Some(now - Duration::hours(1)),
+            None,
+            now
+        ));
+        assert!(reattestation_due(
+            &policy,
+            Some(now - Duration::hours(25)),
+            None,
+            now
+        ));
+        assert!(reattestation_due(&policy, None, None, now));
+
+        // An allocation alone doesn't trigger re-attestation
+        assert!(!reattestation_due(
+            &policy,
+            Some(now - Duration::hours(1)),
+            Some(now),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_reattestation_disabled() {
+        let now = Utc::now();
+        let policy = policy(Duration::zero(), false);
+
+        assert!(!reattestation_due(&policy, None, None, now));
+        assert!(!reattestation_due(
+            &policy,
+            Some(now - Duration::days(365)),
+            Some(now),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_reattestation_due_before_allocation() {
+        let now = Utc::now();
+        let allocated_at = now - Duration::minutes(5);
+        let policy = policy(Duration::zero(), true);
+
+        assert!(reattestation_due(
+            &policy,
+            Some(now - Duration::hours(1)),
+            Some(allocated_at),
+            now
+        ));
+        assert!(reattestation_due(&policy, None, Some(allocated_at), now));
+        assert!(!reattestation_due(
+            &policy,
+            Some(now - Duration::minutes(1)),
+            Some(allocated_at),
+            now
+        ));
+        assert!(!reattestation_due(
+            &policy,
+            Some(now - Duration::hours(1)),
+            None,
+            now
+        ));
+    }
+
+    #[test]
+    fn test_failure_action_for_sku() {
+        let policy = AttestationPolicyConfig {
+            sku_failure_actions: HashMap::from([(
+                "gb200".to_string(),
+                AttestationFailureAction::Quarantine,
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            policy.failure_action_for_sku(Some("gb200")),
+            AttestationFailureAction::Quarantine
+        );
+        assert_eq!(
+            policy.failure_action_for_sku(Some("h100")),
+            AttestationFailureAction::HealthAlert
+        );
+        assert_eq!(
+            policy.failure_action_for_sku(None),
+            AttestationFailureAction::HealthAlert
+        );
+    }
+
+    #[test]
+    fn test_spdm_failures() {
+        let devices_state = HashMap::from([
+            (
+                "HGX_IRoT_GPU_1".to_string(),
+                AttestationDeviceState::AttestationCompleted {
+                    status: AttestationStatus::Failure {
+                        cause: SpdmHandlerError::VerificationFailed("bad measurement".to_string()),
+                    },
+                },
+            ),
+            (
+                "HGX_IRoT_GPU_0".to_string(),
+                AttestationDeviceState::AttestationCompleted {
+                    status: AttestationStatus::Success,
+                },
+            ),
+            (
+                "ERoT_BMC_0".to_string(),
+                AttestationDeviceState::AttestationCompleted {
+                    status: AttestationStatus::NotSupported,
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            spdm_failures(&devices_state),
+            vec![(
+                "HGX_IRoT_GPU_1".to_string(),
+                "Verification Failed: bad measurement".to_string()
+            )]
+        );
+    }
+}
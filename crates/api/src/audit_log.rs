@@ -0,0 +1,415 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Durable audit log of mutating admin and tenant RPCs.
+//!
+//! Audited RPCs are wrapped with [`audited`], which records a pending entry
+//! before the handler runs. If that fails, the call is rejected, so that no
+//! change can be made without an entry in the audit log. The handler gets an
+//! [`AuditLogRecorder`] attached to the request. Handlers which persist their
+//! changes in a single transaction mark the entry as successful in that
+//! transaction via [`AuditLogRecorder::record_success`], so that the outcome
+//! gets committed atomically with the change. For all other calls [`audited`]
+//! records the outcome once the handler returned. Should that fail, the entry
+//! stays pending.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use db::DatabaseError;
+use model::audit_log::{AuditLogOutcome, NewAuditLogEntry};
+use sqlx::PgConnection;
+use tonic::{Request, Response, Status};
+
+use crate::api::{Api, truncate};
+use crate::auth::{AuthContext, Principal};
+
+mod requests;
+
+/// The maximum length of the request that is stored with an entry
+const MAX_REQUEST_LEN: usize = 4096;
+
+/// Replaces secrets in recorded requests
+const REDACTED: &str = "REDACTED";
+
+/// A request of an RPC which is recorded in the audit log
+pub(crate) trait AuditedRequest: std::fmt::Debug + Clone {
+    /// Returns the IDs of the objects which are targeted by the request.
+    ///
+    /// Requests which don't target specific objects - e.g. requests which
+    /// change site wide settings - don't return any IDs.
+    fn object_ids(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Removes all secrets from the request before it gets recorded
+    fn redact(&mut self) {}
+}
+
+/// Records the audit log entry of a single call of an audited RPC
+///
+/// Recorders of requests which are not audited - e.g. because a handler
+/// is invoked internally by another handler - are disabled and don't record
+/// anything.
+#[derive(Clone, Default)]
+pub(crate) struct AuditLogRecorder {
+    inner: Option<Arc<RecorderInner>>,
+}
+
+struct RecorderInner {
+    /// ID of the pending entry
+    id: i64,
+    recorded: AtomicBool,
+}
+
+impl AuditLogRecorder {
+    fn new_entry<T: AuditedRequest>(rpc_name: &str, request: &Request<T>) -> NewAuditLogEntry {
+        let principal = request
+            .extensions()
+            .get::<AuthContext>()
+            .map(AuthContext::audit_principal)
+            .unwrap_or_else(|| Principal::Anonymous.as_identifier());
+
+        let mut redacted_request = request.get_ref().clone();
+        redacted_request.redact();
+
+        NewAuditLogEntry {
+            principal,
+            rpc_name: rpc_name.to_string(),
+            object_ids: request.get_ref().object_ids(),
+            request: truncate(format!("{redacted_request:?}"), MAX_REQUEST_LEN),
+            outcome: AuditLogOutcome::Pending,
+            error: None,
+        }
+    }
+
+    /// Returns the recorder which [`audited`] attached to the request
+    pub fn from_request<T>(request: &Request<T>) -> Self {
+        request
+            .extensions()
+            .get::<Self>()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Attaches the recorder to a request which is passed to another handler,
+    /// so that this handler records the call in its transaction
+    pub fn attach_to<T>(&self, request: &mut Request<T>) {
+        request.extensions_mut().insert(self.clone());
+    }
+
+    /// Records the successful outcome of the call.
+    ///
+    /// This needs to be called in the transaction which commits the last
+    /// change of the handler, right before committing it.
+    pub async fn record_success(&self, txn: &mut PgConnection) -> Result<(), DatabaseError> {
+        let Some(inner) = &self.inner else {
+            return Ok(());
+        };
+        db::audit_log::update_outcome(txn, inner.id, AuditLogOutcome::Success, None).await?;
+        inner.recorded.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Invokes the handler of an audited RPC and makes sure its outcome is recorded
+pub(crate) async fn audited<T, R, F, Fut>(
+    api: &Api,
+    rpc_name: &'static str,
+    mut request: Request<T>,
+    handler: F,
+) -> Result<Response<R>, Status>
+where
+    T: AuditedRequest,
+    F: FnOnce(Request<T>) -> Fut,
+    Fut: Future<Output = Result<Response<R>, Status>>,
+{
+    let entry = AuditLogRecorder::new_entry(rpc_name, &request);
+    let id = persist_pending(api, &entry).await.map_err(|e| {
+        tracing::error!(rpc_name, error = %e, "Failed to record audit log entry");
+        Status::unavailable(format!("Failed to record audit log entry: {e}"))
+    })?;
+    let inner = Arc::new(RecorderInner {
+        id,
+        recorded: AtomicBool::new(false),
+    });
+    AuditLogRecorder {
+        inner: Some(inner.clone()),
+    }
+    .attach_to(&mut request);
+
+    let result = handler(request).await;
+
+    let (outcome, error) = match &result {
+        Ok(_) if inner.recorded.load(Ordering::SeqCst) => return result,
+        Ok(_) => (AuditLogOutcome::Success, None),
+        Err(status) => (AuditLogOutcome::Failure, Some(status.message())),
+    };
+    if let Err(e) = update_outcome(api, id, outcome, error).await {
+        tracing::error!(rpc_name, id, error = %e, "Failed to record outcome of audit log entry");
+    }
+
+    result
+}
+
+async fn persist_pending(api: &Api, entry: &NewAuditLogEntry) -> Result<i64, DatabaseError> {
+    let mut txn = api.txn_begin().await?;
+    let entry = db::audit_log::persist(&mut txn, entry).await?;
+    txn.commit().await?;
+    Ok(entry.id)
+}
+
+async fn update_outcome(
+    api: &Api,
+    id: i64,
+    outcome: AuditLogOutcome,
+    error: Option<&str>,
+) -> Result<(), DatabaseError> {
+    let mut txn = api.txn_begin().await?;
+    db::audit_log::update_outcome(&mut txn, id, outcome, error).await?;
+    txn.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use ::rpc::forge as rpc;
+
+    use super::*;
+
+    /// RPCs whose names start with these prefixes don't change any state
+    const READ_ONLY_PREFIXES: &[&str] = &[
+        "Diff", "Echo", "Explain", "Export", "Find", "Get", "Identify", "Is", "List", "Lookup",
+        "Resolve", "Search", "Show", "Stream", "Validate", "Verify", "Version",
+    ];
+
+    /// RPCs which don't change any state, but don't follow the naming scheme
+    const READ_ONLY_RPCS: &[&str] = &[
+        "AdminListResourcePools",
+        "BmcCredentialStatus",
+        "DetermineMachineIngestionState",
+        "DpuAgentUpgradeCheck",
+        "Explore",
+        "IBPartitionsForTenant",
+        "LockdownStatus",
+        "MatchMeasurementReport",
+        "MlxAdminConfigCompare",
+        "MlxAdminConfigQuery",
+        "MlxAdminLockdownStatus",
+        "MlxAdminProfileBindingList",
+        "MlxAdminProfileCompare",
+        "MlxAdminProfileList",
+        "MlxAdminProfileShow",
+        "MlxAdminRegistryList",
+        "MlxAdminRegistryShow",
+        "MlxAdminShowDevice",
+        "MlxAdminShowMachine",
+        "NVLinkLogicalPartitionsForTenant",
+        "NVLinkPartitionsForTenant",
+        "NetworkSegmentsForVpc",
+        "NmxmBrowse",
+        "RedfishBrowse",
+        "RedfishListActions",
+        "ScoutStreamPing",
+        "ScoutStreamShowConnections",
+        "TpmShowCaCerts",
+        "TpmShowUnmatchedEkCerts",
+        "UfmBrowse",
+    ];
+
+    /// Mutating RPCs which are not audited, because they are exclusively
+    /// invoked by the agents and services running on the managed machines
+    /// and within the site - e.g. to report discovery results or health.
+    /// RPCs which can be invoked by operators or tenants must be audited.
+    const UNAUDITED_RPCS: &[&str] = &[
+        "AttestQuote",
+        "CleanupMachineCompleted",
+        "CreateDomain",
+        "CreateDomainLegacy",
+        "CreateDpaInterface",
+        "DeleteDomain",
+        "DeleteDomainLegacy",
+        "DeleteDpaInterface",
+        "DiscoverDhcp",
+        "DiscoverMachine",
+        "DiscoveryCompleted",
+        "EnsureDpaInterface",
+        "ForgeAgentControl",
+        "MachineValidationCompleted",
+        "PersistValidationResult",
+        "PublishMlxDeviceReport",
+        "PublishMlxObservationReport",
+        "RebootCompleted",
+        "RecordConsoleHealthReport",
+        "RecordDpuNetworkStatus",
+        "RecordHardwareHealthReport",
+        "RecordLogParserHealthReport",
+        "ReleaseHealthShardLease",
+        "RemediationApplied",
+        "RenewHealthShardLease",
+        "RenewMachineCertificate",
+        "ReplaceRouteServers",
+        "ReportForgeScoutError",
+        "SaveHealthCollectorState",
+        "ScoutStream",
+        "SetDpaNetworkObservationStatus",
+        "SignMachineIdentity",
+        "UpdateAgentReportedInventory",
+        "UpdateDomain",
+        "UpdateDomainLegacy",
+        "UpdateInstancePhoneHomeLastContact",
+        "UpdateMachineValidationRun",
+    ];
+
+    #[test]
+    fn test_credentials_are_redacted() {
+        let request = Request::new(rpc::CredentialCreationRequest {
+            credential_type: rpc::CredentialType::HostBmc as i32,
+            username: None,
+            password: "hunter2".to_string(),
+            vendor: None,
+            mac_address: Some("00:11:22:33:44:55".to_string()),
+        });
+        let recorder = AuditLogRecorder::new("CreateCredential", &request);
+        let entry = &recorder.inner.as_ref().unwrap().entry;
+        assert!(!entry.request.contains("hunter2"));
+        assert!(entry.request.contains(REDACTED));
+        assert_eq!(entry.object_ids, vec!["00:11:22:33:44:55".to_string()]);
+        assert_eq!(entry.principal, "anonymous");
+
+        let request = rpc::MachineCredentialsUpdateRequest {
+            machine_id: None,
+            credentials: vec![rpc::machine_credentials_update_request::Credentials {
+                user: "root".to_string(),
+                password: "hunter2".to_string(),
+                credential_purpose: 0,
+            }],
+            mac_address: None,
+        };
+        let mut redacted = request.clone();
+        redacted.redact();
+        assert!(!format!("{redacted:?}").contains("hunter2"));
+        assert!(format!("{redacted:?}").contains("root"));
+    }
+
+    #[test]
+    fn test_principal_of_external_user() {
+        let mut request = Request::new(rpc::SetDynamicConfigRequest {
+            setting: rpc::ConfigSetting::LogFilter as i32,
+            value: "debug".to_string(),
+            expiry: None,
+        });
+        request.extensions_mut().insert(AuthContext {
+            principals: vec![
+                Principal::TrustedCertificate,
+                Principal::from_web_cookie("jdoe".to_string(), "admins".to_string()),
+            ],
+            authorization: None,
+        });
+        let recorder = AuditLogRecorder::new("SetDynamicConfig", &request);
+        let entry = &recorder.inner.as_ref().unwrap().entry;
+        assert_eq!(entry.principal, "external-user/admins/jdoe");
+        assert_eq!(entry.object_ids, vec!["LOG_FILTER".to_string()]);
+    }
+
+    #[test]
+    fn test_instance_user_data_is_redacted() {
+        let config = rpc::InstanceConfig {
+            os: Some(rpc::OperatingSystem {
+                user_data: Some("password: hunter2".to_string()),
+                variant: Some(rpc::operating_system::Variant::Ipxe(rpc::InlineIpxe {
+                    ipxe_script: "chain http://boot.example.com".to_string(),
+                    user_data: Some("password: hunter3".to_string()),
+                })),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let request = Request::new(rpc::InstanceAllocationRequest {
+            config: Some(config.clone()),
+            ..Default::default()
+        });
+        let recorder = AuditLogRecorder::new("AllocateInstance", &request);
+        let entry = &recorder.inner.as_ref().unwrap().entry;
+        assert!(!entry.request.contains("hunter2"));
+        assert!(!entry.request.contains("hunter3"));
+        assert!(entry.request.contains("boot.example.com"));
+
+        let request = Request::new(rpc::InstanceConfigUpdateRequest {
+            config: Some(config),
+            ..Default::default()
+        });
+        let recorder = AuditLogRecorder::new("UpdateInstanceConfig", &request);
+        let entry = &recorder.inner.as_ref().unwrap().entry;
+        assert!(!entry.request.contains("hunter2"));
+        assert!(!entry.request.contains("hunter3"));
+        assert!(entry.request.contains(REDACTED));
+    }
+
+    #[test]
+    fn all_mutating_rpcs_audited() -> Result<(), eyre::Report> {
+        let proto = std::fs::read_to_string("../rpc/proto/forge.proto")?;
+        let rpcs: Vec<&str> = proto
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("rpc "))
+            .filter_map(|line| line.split('(').next())
+            .map(str::trim)
+            .collect();
+        if rpcs.is_empty() {
+            panic!("Parsing failed, no RPCs found")
+        }
+
+        // rustfmt might wrap the arguments of `audited`
+        let api: String = std::fs::read_to_string("src/api.rs")?
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let audited: HashSet<&str> = rpcs
+            .iter()
+            .copied()
+            .filter(|rpc| api.contains(&format!("audit_log::audited(self,\"{rpc}\",")))
+            .collect();
+
+        let mut missing = vec![];
+        for rpc in rpcs.iter().copied() {
+            let read_only = READ_ONLY_PREFIXES
+                .iter()
+                .any(|prefix| rpc.starts_with(prefix))
+                || READ_ONLY_RPCS.contains(&rpc);
+            if !read_only && !audited.contains(rpc) && !UNAUDITED_RPCS.contains(&rpc) {
+                missing.push(rpc);
+            }
+        }
+        if !missing.is_empty() {
+            panic!("Mutating RPCs which are not audited: {missing:?}");
+        }
+
+        let stale: Vec<&str> = READ_ONLY_RPCS
+            .iter()
+            .chain(UNAUDITED_RPCS.iter())
+            .copied()
+            .filter(|rpc| !rpcs.contains(rpc) || audited.contains(rpc))
+            .collect();
+        if !stale.is_empty() {
+            panic!("Stale entries in the lists of RPCs which are not audited: {stale:?}");
+        }
+        Ok(())
+    }
+}
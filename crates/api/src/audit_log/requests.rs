@@ -0,0 +1,1222 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! [`AuditedRequest`] implementations of the requests of all audited RPCs
+
+use ::rpc::forge as rpc;
+use ::rpc::protos::{dns, measured_boot, mlx_device};
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+
+use super::{AuditedRequest, REDACTED};
+
+impl AuditedRequest for rpc::MaintenanceRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.host_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::AdminForceDeleteMachineRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.host_query.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::AdminPowerControlRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::SetDynamicConfigRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.setting().as_str_name().to_string()]
+    }
+}
+
+impl AuditedRequest for rpc::InsertHealthReportOverrideRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.machine_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::RemoveHealthReportOverrideRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.machine_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::CredentialCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.mac_address
+            .iter()
+            .chain(self.username.iter())
+            .cloned()
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        self.password = REDACTED.to_string();
+    }
+}
+
+impl AuditedRequest for rpc::CredentialDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.mac_address
+            .iter()
+            .chain(self.username.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::MachineCredentialsUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.machine_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.mac_address.iter().cloned())
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        for credentials in self.credentials.iter_mut() {
+            credentials.password = REDACTED.to_string();
+        }
+    }
+}
+
+impl AuditedRequest for rpc::InstanceAllocationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.instance_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.machine_id.iter().map(ToString::to_string))
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        redact_instance_config(self.config.as_mut());
+    }
+}
+
+impl AuditedRequest for rpc::BatchInstanceAllocationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.instance_requests
+            .iter()
+            .flat_map(AuditedRequest::object_ids)
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        for request in self.instance_requests.iter_mut() {
+            request.redact();
+        }
+    }
+}
+
+impl AuditedRequest for rpc::InstanceReleaseRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::InstancePowerRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.instance_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.machine_id.iter().map(ToString::to_string))
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::InstanceConfigUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.instance_id.iter().map(ToString::to_string).collect()
+    }
+
+    fn redact(&mut self) {
+        redact_instance_config(self.config.as_mut());
+    }
+}
+
+impl AuditedRequest for rpc::InstanceOperatingSystemUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.instance_id.iter().map(ToString::to_string).collect()
+    }
+
+    fn redact(&mut self) {
+        if let Some(os) = self.os.as_mut() {
+            redact_operating_system(os);
+        }
+    }
+}
+
+/// Removes the tenant provided user data - which commonly contains
+/// credentials - from an instance configuration
+fn redact_instance_config(config: Option<&mut rpc::InstanceConfig>) {
+    if let Some(os) = config.and_then(|config| config.os.as_mut()) {
+        redact_operating_system(os);
+    }
+}
+
+fn redact_operating_system(os: &mut rpc::OperatingSystem) {
+    redact_optional(&mut os.user_data);
+    if let Some(rpc::operating_system::Variant::Ipxe(ipxe)) = os.variant.as_mut() {
+        redact_optional(&mut ipxe.user_data);
+    }
+}
+
+/// Redacts an optional secret, but keeps track of whether it was passed
+fn redact_optional(secret: &mut Option<String>) {
+    if secret.is_some() {
+        *secret = Some(REDACTED.to_string());
+    }
+}
+
+impl AuditedRequest for rpc::CreateConsoleAccessTokenRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.instance_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::SetRackPowerStateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.rack_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for dns::CreateTenantDnsRecordRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.domain_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(std::iter::once(self.name.clone()))
+            .collect()
+    }
+}
+
+impl AuditedRequest for dns::UpdateTenantDnsRecordRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for dns::DeleteTenantDnsRecordRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for mlx_device::MlxAdminProfileCreateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.serializable_profile
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+}
+
+impl AuditedRequest for mlx_device::MlxAdminProfileUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.serializable_profile
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+}
+
+impl AuditedRequest for mlx_device::MlxAdminProfileDeleteRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.profile_name.clone()]
+    }
+}
+
+impl AuditedRequest for mlx_device::MlxAdminProfileBindRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.binding
+            .iter()
+            .flat_map(|binding| {
+                std::iter::once(binding.profile_name.clone())
+                    .chain(profile_target_id(binding.target.as_ref()))
+            })
+            .collect()
+    }
+}
+
+impl AuditedRequest for mlx_device::MlxAdminProfileUnbindRequest {
+    fn object_ids(&self) -> Vec<String> {
+        profile_target_id(self.target.as_ref())
+            .into_iter()
+            .collect()
+    }
+}
+
+fn profile_target_id(target: Option<&mlx_device::MlxConfigProfileTarget>) -> Option<String> {
+    use mlx_device::mlx_config_profile_target::Target;
+
+    match target?.target.as_ref()? {
+        Target::SkuId(id) | Target::InstanceTypeId(id) => Some(id.clone()),
+    }
+}
+
+/// Implements [`AuditedRequest`] for requests of the mlx admin RPCs which
+/// target a single device of a machine
+macro_rules! audited_device_request {
+    ($($request:ty),+ $(,)?) => {
+        $(
+            impl AuditedRequest for $request {
+                fn object_ids(&self) -> Vec<String> {
+                    self.machine_id
+                        .iter()
+                        .map(ToString::to_string)
+                        .chain(std::iter::once(self.device_id.clone()))
+                        .collect()
+                }
+            }
+        )+
+    };
+}
+
+audited_device_request!(
+    mlx_device::MlxAdminProfileSyncRequest,
+    mlx_device::MlxAdminLockdownLockRequest,
+    mlx_device::MlxAdminLockdownUnlockRequest,
+    mlx_device::MlxAdminConfigSetRequest,
+    mlx_device::MlxAdminConfigSyncRequest,
+);
+
+/// Returns the IDs of the targets of requests which either address a
+/// machine or the BMC endpoint of a machine
+fn bmc_target_ids(
+    machine_id: Option<&String>,
+    bmc_endpoint: Option<&rpc::BmcEndpointRequest>,
+) -> Vec<String> {
+    machine_id
+        .cloned()
+        .into_iter()
+        .chain(bmc_endpoint.map(|endpoint| endpoint.ip_address.clone()))
+        .collect()
+}
+
+impl AuditedRequest for rpc::BmcEndpointRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.ip_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::AdminBmcResetRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::CreateBmcUserRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+
+    fn redact(&mut self) {
+        self.create_password = REDACTED.to_string();
+    }
+}
+
+impl AuditedRequest for rpc::DeleteBmcUserRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::EnableInfiniteBootRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::MachineSetupRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::SetDpuFirstBootOrderRequest {
+    fn object_ids(&self) -> Vec<String> {
+        bmc_target_ids(self.machine_id.as_ref(), self.bmc_endpoint_request.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::LockdownRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.machine_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(
+                self.bmc_endpoint_request
+                    .iter()
+                    .map(|endpoint| endpoint.ip_address.clone()),
+            )
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::CopyBfbToDpuRshimRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.ssh_request
+            .iter()
+            .flat_map(|ssh_request| ssh_request.endpoint_request.iter())
+            .map(|endpoint| endpoint.ip_address.clone())
+            .collect()
+    }
+}
+
+impl AuditedRequest for MachineId {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.to_string()]
+    }
+}
+
+impl AuditedRequest for MachineInterfaceId {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.to_string()]
+    }
+}
+
+/// Implements [`AuditedRequest`] for requests which target a single machine
+/// via their `machine_id` field
+macro_rules! audited_machine_request {
+    ($($request:ty),+ $(,)?) => {
+        $(
+            impl AuditedRequest for $request {
+                fn object_ids(&self) -> Vec<String> {
+                    self.machine_id.iter().map(ToString::to_string).collect()
+                }
+            }
+        )+
+    };
+}
+
+audited_machine_request!(
+    rpc::AttestationData,
+    rpc::ClearManagedHostQuarantineStateRequest,
+    rpc::HostReprovisioningRequest,
+    rpc::MachineMetadataUpdateRequest,
+    rpc::MachineSetAutoUpdateRequest,
+    rpc::MachineValidationOnDemandRequest,
+    rpc::ModifyDpfStateRequest,
+    rpc::PowerOptionUpdateRequest,
+    rpc::RemoveSkuRequest,
+    rpc::ScoutStreamDisconnectRequest,
+    rpc::SetManagedHostQuarantineStateRequest,
+    rpc::UpdateMachineHardwareInfoRequest,
+    rpc::UpdateMachineNvLinkInfoRequest,
+);
+
+impl AuditedRequest for rpc::DpuReprovisioningRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.dpu_id
+            .iter()
+            .chain(self.machine_id.iter())
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::SetPrimaryDpuRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.host_machine_id
+            .iter()
+            .chain(self.dpu_machine_id.iter())
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::SetFirmwareUpdateTimeWindowRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.machine_ids.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::SetHostUefiPasswordRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.host_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.machine_query.iter().cloned())
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::ClearHostUefiPasswordRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.host_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.machine_query.iter().cloned())
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::MachineBootOverride {
+    fn object_ids(&self) -> Vec<String> {
+        self.machine_interface_id
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        redact_optional(&mut self.custom_user_data);
+    }
+}
+
+impl AuditedRequest for rpc::InterfaceDeleteQuery {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::AddUpdateMachineValidationExternalConfigRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+
+    fn redact(&mut self) {
+        // External configs commonly contain credentials of the services
+        // which are used by the validation tests
+        self.config = REDACTED.as_bytes().to_vec();
+    }
+}
+
+impl AuditedRequest for rpc::RemoveMachineValidationExternalConfigRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::MachineValidationTestAddRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::MachineValidationTestUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.test_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::MachineValidationTestVerfiedRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.test_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::MachineValidationTestNextVersionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.test_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::MachineValidationTestEnableDisableTestRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.test_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::ClearSiteExplorationErrorRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.ip_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::ReExploreEndpointRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.ip_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::DeleteExploredEndpointRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.ip_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::PauseExploredEndpointRemediationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.ip_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedMachine {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.bmc_mac_address.clone()]
+    }
+
+    fn redact(&mut self) {
+        self.bmc_password = REDACTED.to_string();
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedMachineList {
+    fn object_ids(&self) -> Vec<String> {
+        self.expected_machines
+            .iter()
+            .flat_map(AuditedRequest::object_ids)
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        for expected_machine in self.expected_machines.iter_mut() {
+            expected_machine.redact();
+        }
+    }
+}
+
+impl AuditedRequest for rpc::BatchExpectedMachineOperationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.expected_machines
+            .iter()
+            .flat_map(AuditedRequest::object_ids)
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        if let Some(expected_machines) = self.expected_machines.as_mut() {
+            expected_machines.redact();
+        }
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedMachineRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.bmc_mac_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedPowerShelf {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.bmc_mac_address.clone()]
+    }
+
+    fn redact(&mut self) {
+        self.bmc_password = REDACTED.to_string();
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedPowerShelfList {
+    fn object_ids(&self) -> Vec<String> {
+        self.expected_power_shelves
+            .iter()
+            .flat_map(AuditedRequest::object_ids)
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        for expected_power_shelf in self.expected_power_shelves.iter_mut() {
+            expected_power_shelf.redact();
+        }
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedPowerShelfRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.bmc_mac_address.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedSwitch {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.bmc_mac_address.clone()]
+    }
+
+    fn redact(&mut self) {
+        self.bmc_password = REDACTED.to_string();
+        redact_optional(&mut self.nvos_password);
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedSwitchList {
+    fn object_ids(&self) -> Vec<String> {
+        self.expected_switches
+            .iter()
+            .flat_map(AuditedRequest::object_ids)
+            .collect()
+    }
+
+    fn redact(&mut self) {
+        for expected_switch in self.expected_switches.iter_mut() {
+            expected_switch.redact();
+        }
+    }
+}
+
+impl AuditedRequest for rpc::ExpectedSwitchRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.bmc_mac_address.clone()]
+    }
+}
+
+/// Requests which delete all expected machines, power shelves or switches
+impl AuditedRequest for () {}
+
+impl AuditedRequest for rpc::PowerShelfDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::SwitchDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::DeleteRackRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::InsertRackHealthReportOverrideRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.rack_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::RemoveRackHealthReportOverrideRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.rack_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::RackFirmwareCreateRequest {
+    fn redact(&mut self) {
+        self.artifactory_token = REDACTED.to_string();
+    }
+}
+
+impl AuditedRequest for rpc::RackFirmwareDeleteRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::RackFirmwareApplyRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.rack_id
+            .iter()
+            .map(ToString::to_string)
+            .chain(std::iter::once(self.firmware_id.clone()))
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::GrowResourcePoolRequest {}
+
+impl AuditedRequest for rpc::RouteServers {
+    fn object_ids(&self) -> Vec<String> {
+        self.route_servers.clone()
+    }
+}
+
+impl AuditedRequest for rpc::TrimTableRequest {}
+
+impl AuditedRequest for rpc::DpuAgentUpgradePolicyRequest {}
+
+impl AuditedRequest for rpc::TpmCaCert {}
+
+impl AuditedRequest for rpc::TpmCaCertId {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.ca_cert_id.to_string()]
+    }
+}
+
+impl AuditedRequest for rpc::RedfishCreateActionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.ips.clone()
+    }
+}
+
+impl AuditedRequest for rpc::RedfishActionId {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.request_id.to_string()]
+    }
+}
+
+impl AuditedRequest for rpc::CreateRemediationRequest {}
+
+impl AuditedRequest for rpc::ApproveRemediationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.remediation_id
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::RevokeRemediationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.remediation_id
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::EnableRemediationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.remediation_id
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::DisableRemediationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.remediation_id
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::Sku {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::SkuList {
+    fn object_ids(&self) -> Vec<String> {
+        self.skus.iter().map(|sku| sku.id.clone()).collect()
+    }
+}
+
+impl AuditedRequest for rpc::SkuIdList {
+    fn object_ids(&self) -> Vec<String> {
+        self.ids.clone()
+    }
+}
+
+impl AuditedRequest for rpc::SkuMachinePair {
+    fn object_ids(&self) -> Vec<String> {
+        std::iter::once(self.sku_id.clone())
+            .chain(self.machine_id.iter().map(ToString::to_string))
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::SkuUpdateMetadataRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.sku_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::CreateTenantRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.organization_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::UpdateTenantRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.organization_id.clone()]
+    }
+}
+
+fn keyset_ids(keyset_identifier: Option<&rpc::TenantKeysetIdentifier>) -> Vec<String> {
+    keyset_identifier
+        .map(|identifier| identifier.keyset_id.clone())
+        .into_iter()
+        .collect()
+}
+
+impl AuditedRequest for rpc::CreateTenantKeysetRequest {
+    fn object_ids(&self) -> Vec<String> {
+        keyset_ids(self.keyset_identifier.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::UpdateTenantKeysetRequest {
+    fn object_ids(&self) -> Vec<String> {
+        keyset_ids(self.keyset_identifier.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::DeleteTenantKeysetRequest {
+    fn object_ids(&self) -> Vec<String> {
+        keyset_ids(self.keyset_identifier.as_ref())
+    }
+}
+
+impl AuditedRequest for rpc::VpcCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcUpdateVirtualizationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcPrefixCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.vpc_id.iter().map(ToString::to_string))
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcPrefixUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcPrefixDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcPeeringCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.vpc_id
+            .iter()
+            .chain(self.peer_vpc_id.iter())
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::VpcPeeringDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::NetworkSegmentCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.vpc_id.iter().map(ToString::to_string))
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::NetworkSegmentDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::IbPartitionCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::IbPartitionDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::NvLinkLogicalPartitionCreationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::NvLinkLogicalPartitionUpdateRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::NvLinkLogicalPartitionDeletionRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for rpc::CreateNetworkSecurityGroupRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().cloned().collect()
+    }
+}
+
+impl AuditedRequest for rpc::UpdateNetworkSecurityGroupRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::DeleteNetworkSecurityGroupRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::CreateInstanceTypeRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().cloned().collect()
+    }
+}
+
+impl AuditedRequest for rpc::UpdateInstanceTypeRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::DeleteInstanceTypeRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::AssociateMachinesWithInstanceTypeRequest {
+    fn object_ids(&self) -> Vec<String> {
+        std::iter::once(self.instance_type_id.clone())
+            .chain(self.machine_ids.iter().cloned())
+            .collect()
+    }
+}
+
+impl AuditedRequest for rpc::RemoveMachineInstanceTypeAssociationRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.machine_id.clone()]
+    }
+}
+
+impl AuditedRequest for rpc::OsImageAttributes {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+
+    fn redact(&mut self) {
+        redact_optional(&mut self.auth_token);
+    }
+}
+
+impl AuditedRequest for rpc::DeleteOsImageRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.id.iter().map(ToString::to_string).collect()
+    }
+}
+
+fn redact_extension_service_credential(
+    credential: Option<&mut rpc::DpuExtensionServiceCredential>,
+) {
+    if let Some(rpc::dpu_extension_service_credential::Type::UsernamePassword(credentials)) =
+        credential.and_then(|credential| credential.r#type.as_mut())
+    {
+        credentials.password = REDACTED.to_string();
+    }
+}
+
+impl AuditedRequest for rpc::CreateDpuExtensionServiceRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.service_id.iter().cloned().collect()
+    }
+
+    fn redact(&mut self) {
+        redact_extension_service_credential(self.credential.as_mut());
+    }
+}
+
+impl AuditedRequest for rpc::UpdateDpuExtensionServiceRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.service_id.clone()]
+    }
+
+    fn redact(&mut self) {
+        redact_extension_service_credential(self.credential.as_mut());
+    }
+}
+
+impl AuditedRequest for rpc::DeleteDpuExtensionServiceRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.service_id.clone()]
+    }
+}
+
+impl AuditedRequest for measured_boot::CreateMeasurementBundleRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.profile_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::DeleteMeasurementBundleRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::delete_measurement_bundle_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::BundleId(id)) => vec![id.to_string()],
+            Some(Selector::BundleName(name)) => vec![name.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl AuditedRequest for measured_boot::RenameMeasurementBundleRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::rename_measurement_bundle_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::BundleId(id)) => vec![id.to_string()],
+            Some(Selector::BundleName(name)) => vec![name.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl AuditedRequest for measured_boot::UpdateMeasurementBundleRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::update_measurement_bundle_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::BundleId(id)) => vec![id.to_string()],
+            Some(Selector::BundleName(name)) => vec![name.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl AuditedRequest for measured_boot::CreateMeasurementSystemProfileRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.name.iter().cloned().collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::DeleteMeasurementSystemProfileRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::delete_measurement_system_profile_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::ProfileId(id)) => vec![id.to_string()],
+            Some(Selector::ProfileName(name)) => vec![name.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl AuditedRequest for measured_boot::RenameMeasurementSystemProfileRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::rename_measurement_system_profile_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::ProfileId(id)) => vec![id.to_string()],
+            Some(Selector::ProfileName(name)) => vec![name.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl AuditedRequest for measured_boot::CreateMeasurementReportRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.machine_id.clone()]
+    }
+}
+
+impl AuditedRequest for measured_boot::DeleteMeasurementReportRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.report_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::PromoteMeasurementReportRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.report_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::RevokeMeasurementReportRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.report_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::DeleteMeasurementJournalRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.journal_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::AttestCandidateMachineRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.machine_id.clone()]
+    }
+}
+
+impl AuditedRequest for measured_boot::ImportSiteMeasurementsRequest {}
+
+impl AuditedRequest for measured_boot::AddMeasurementTrustedMachineRequest {
+    fn object_ids(&self) -> Vec<String> {
+        vec![self.machine_id.clone()]
+    }
+}
+
+impl AuditedRequest for measured_boot::RemoveMeasurementTrustedMachineRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::remove_measurement_trusted_machine_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::ApprovalId(id)) => vec![id.to_string()],
+            Some(Selector::MachineId(machine_id)) => vec![machine_id.clone()],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl AuditedRequest for measured_boot::AddMeasurementTrustedProfileRequest {
+    fn object_ids(&self) -> Vec<String> {
+        self.profile_id.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl AuditedRequest for measured_boot::RemoveMeasurementTrustedProfileRequest {
+    fn object_ids(&self) -> Vec<String> {
+        use measured_boot::remove_measurement_trusted_profile_request::Selector;
+
+        match self.selector.as_ref() {
+            Some(Selector::ApprovalId(id)) => vec![id.to_string()],
+            Some(Selector::ProfileId(id)) => vec![id.to_string()],
+            None => Vec::new(),
+        }
+    }
+}
@@ -363,6 +363,37 @@ impl AuthContext {
         })
     }
 
+    /// Returns the identity of the caller as it is recorded in the audit log.
+    /// Unlike [`Principal::as_identifier`], this includes the machine ID of
+    /// machines and the name of external users.
+    pub fn audit_principal(&self) -> String {
+        let principal = self
+            .principals
+            .iter()
+            .find(|p| {
+                matches!(
+                    p,
+                    Principal::SpiffeServiceIdentifier(_)
+                        | Principal::SpiffeMachineIdentifier(_)
+                        | Principal::ExternalUser(_)
+                )
+            })
+            .or_else(|| self.principals.first());
+
+        match principal {
+            Some(Principal::SpiffeMachineIdentifier(machine_id)) => {
+                format!("spiffe-machine-id/{machine_id}")
+            }
+            Some(Principal::ExternalUser(ExternalUserInfo {
+                group,
+                user: Some(user),
+                ..
+            })) if !user.is_empty() => format!("external-user/{group}/{user}"),
+            Some(principal) => principal.as_identifier(),
+            None => Principal::Anonymous.as_identifier(),
+        }
+    }
+
     pub fn get_external_user_name(&self) -> Option<&str> {
         self.principals.iter().find_map(|p| match p {
             Principal::ExternalUser(external_user_info) => external_user_info
@@ -67,6 +67,10 @@ impl InternalRBACRules {
         x.perm("UpdateDomain", vec![]);
         x.perm("DeleteDomain", vec![]);
         x.perm("FindDomain", vec![ForgeAdminCLI]);
+        x.perm("CreateTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("UpdateTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("DeleteTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindTenantDnsRecords", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("CreateVpc", vec![SiteAgent, Machineatron]);
         x.perm("UpdateVpc", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("UpdateVpcVirtualization", vec![ForgeAdminCLI, SiteAgent]);
@@ -129,6 +133,11 @@ impl InternalRBACRules {
         x.perm("RecordDpuNetworkStatus", vec![Agent, Machineatron]);
         x.perm("RecordHardwareHealthReport", vec![Health]);
         x.perm("RecordLogParserHealthReport", vec![Health, Ssh, SshRs]);
+        x.perm("RecordConsoleHealthReport", vec![SshRs]);
+        x.perm("RenewHealthShardLease", vec![Health]);
+        x.perm("ReleaseHealthShardLease", vec![Health]);
+        x.perm("SaveHealthCollectorState", vec![Health]);
+        x.perm("GetHealthCollectorState", vec![Health]);
         x.perm("GetHardwareHealthReport", vec![]);
         x.perm("ListHealthReportOverrides", vec![ForgeAdminCLI]);
         x.perm("InsertHealthReportOverride", vec![ForgeAdminCLI]);
@@ -152,6 +161,7 @@ impl InternalRBACRules {
         x.perm("GetAllDomainMetadata", vec![Dns]);
         x.perm("GetAllDomains", vec![Dns]);
         x.perm("InvokeInstancePower", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("StreamInstanceEvents", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ForgeAgentControl", vec![Machineatron, Scout]);
         x.perm("DiscoverMachine", vec![Anonymous]);
         x.perm("RenewMachineCertificate", vec![Agent]);
@@ -198,6 +208,7 @@ impl InternalRBACRules {
         x.perm("UpdateMachineCredentials", vec![]);
         x.perm("GetPxeInstructions", vec![Pxe, Machineatron]);
         x.perm("GetCloudInitInstructions", vec![Pxe]);
+        x.perm("GetOsImageContent", vec![Pxe]);
         x.perm("Echo", vec![Dhcp]);
         x.perm("CreateTenant", vec![SiteAgent]);
         x.perm("FindTenant", vec![SiteAgent, ForgeAdminCLI]);
@@ -208,6 +219,11 @@ impl InternalRBACRules {
         x.perm("UpdateTenantKeyset", vec![SiteAgent]);
         x.perm("DeleteTenantKeyset", vec![SiteAgent]);
         x.perm("ValidateTenantPublicKey", vec![SiteAgent, Ssh, SshRs]);
+        x.perm("CreateConsoleAccessToken", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("ValidateConsoleAccessToken", vec![Ssh, SshRs]);
+        x.perm("ListConsoleRecordings", vec![ForgeAdminCLI]);
+        x.perm("GetConsoleRecording", vec![ForgeAdminCLI]);
+        x.perm("ValidateConsoleRecordingAccessToken", vec![Ssh, SshRs]);
         x.perm("GetDpuSSHCredential", vec![ForgeAdminCLI]);
         x.perm("GetAllManagedHostNetworkStatus", vec![ForgeAdminCLI]);
         x.perm(
@@ -229,6 +245,7 @@ impl InternalRBACRules {
         x.perm("AdminGrowResourcePool", vec![ForgeAdminCLI]);
         x.perm("SetMaintenance", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("SetDynamicConfig", vec![ForgeAdminCLI, Machineatron]);
+        x.perm("FindAuditLogEntries", vec![ForgeAdminCLI]);
         x.perm("TriggerDpuReprovisioning", vec![ForgeAdminCLI]);
         x.perm("TriggerHostReprovisioning", vec![ForgeAdminCLI, Rla]);
         x.perm("ListDpuWaitingForReprovisioning", vec![ForgeAdminCLI]);
@@ -261,7 +278,7 @@ impl InternalRBACRules {
         x.perm("GetExpectedMachine", vec![ForgeAdminCLI, Rla]);
         x.perm(
             "GetAllExpectedMachines",
-            vec![ForgeAdminCLI, SiteAgent, Rla],
+            vec![ForgeAdminCLI, SiteAgent, Rla, Health],
         );
         x.perm("ReplaceAllExpectedMachines", vec![ForgeAdminCLI]);
         x.perm("DeleteAllExpectedMachines", vec![ForgeAdminCLI]);
@@ -314,6 +331,7 @@ impl InternalRBACRules {
         x.perm("ShowMeasurementReports", vec![ForgeAdminCLI]);
         x.perm("ListMeasurementReport", vec![ForgeAdminCLI]);
         x.perm("MatchMeasurementReport", vec![ForgeAdminCLI]);
+        x.perm("DiffMeasurementReport", vec![ForgeAdminCLI]);
         x.perm("ImportSiteMeasurements", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ExportSiteMeasurements", vec![ForgeAdminCLI, SiteAgent]);
         x.perm(
@@ -469,6 +487,10 @@ impl InternalRBACRules {
             "GetNetworkSecurityGroupAttachments",
             vec![ForgeAdminCLI, SiteAgent],
         );
+        x.perm(
+            "ExplainNetworkSecurityGroupFlow",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
         x.perm(
             "GetDesiredFirmwareVersions",
             vec![ForgeAdminCLI, Machineatron],
@@ -609,7 +631,7 @@ impl InternalRBACRules {
         x.perm("GetExpectedSwitch", vec![ForgeAdminCLI, Machineatron, Rla]);
         x.perm(
             "GetAllExpectedSwitches",
-            vec![ForgeAdminCLI, Machineatron, Rla],
+            vec![ForgeAdminCLI, Machineatron, Rla, Health],
         );
         x.perm(
             "ReplaceAllExpectedSwitches",
@@ -629,6 +651,7 @@ impl InternalRBACRules {
         );
         x.perm("GetRack", vec![ForgeAdminCLI, Rla]);
         x.perm("DeleteRack", vec![ForgeAdminCLI, Rla]);
+        x.perm("SetRackPowerState", vec![ForgeAdminCLI]);
         x.perm("RackManagerCall", vec![ForgeAdminCLI]);
         x.perm("ScoutStream", vec![Scout]);
         x.perm("ScoutStreamShowConnections", vec![ForgeAdminCLI]);
@@ -638,6 +661,12 @@ impl InternalRBACRules {
         x.perm("MlxAdminProfileShow", vec![ForgeAdminCLI]);
         x.perm("MlxAdminProfileCompare", vec![ForgeAdminCLI]);
         x.perm("MlxAdminProfileList", vec![ForgeAdminCLI]);
+        x.perm("MlxAdminProfileCreate", vec![ForgeAdminCLI]);
+        x.perm("MlxAdminProfileUpdate", vec![ForgeAdminCLI]);
+        x.perm("MlxAdminProfileDelete", vec![ForgeAdminCLI]);
+        x.perm("MlxAdminProfileBind", vec![ForgeAdminCLI]);
+        x.perm("MlxAdminProfileUnbind", vec![ForgeAdminCLI]);
+        x.perm("MlxAdminProfileBindingList", vec![ForgeAdminCLI]);
         x.perm("MlxAdminLockdownLock", vec![ForgeAdminCLI]);
         x.perm("MlxAdminLockdownUnlock", vec![ForgeAdminCLI]);
         x.perm("MlxAdminLockdownStatus", vec![ForgeAdminCLI]);
@@ -874,6 +903,42 @@ mod rbac_rule_tests {
                 None
             ))]
         ));
+        assert!(InternalRBACRules::allowed_from_static(
+            "GetOsImageContent",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-pxe".to_string()
+            )]
+        ));
+        assert!(!InternalRBACRules::allowed_from_static(
+            "GetOsImageContent",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-dhcp".to_string()
+            )]
+        ));
+        assert!(InternalRBACRules::allowed_from_static(
+            "ValidateConsoleAccessToken",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-ssh-console-rs".to_string()
+            )]
+        ));
+        assert!(!InternalRBACRules::allowed_from_static(
+            "CreateConsoleAccessToken",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-ssh-console-rs".to_string()
+            )]
+        ));
+        assert!(!InternalRBACRules::allowed_from_static(
+            "GetConsoleRecording",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-ssh-console-rs".to_string()
+            )]
+        ));
+        assert!(InternalRBACRules::allowed_from_static(
+            "ValidateConsoleRecordingAccessToken",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-ssh-console-rs".to_string()
+            )]
+        ));
         assert!(InternalRBACRules::allowed_from_static(
             "CreateVpc",
             &[Principal::SpiffeServiceIdentifier(
@@ -887,12 +952,42 @@ mod rbac_rule_tests {
             )]
         ));
 
+        assert!(InternalRBACRules::allowed_from_static(
+            "CreateTenantDnsRecord",
+            &[Principal::SpiffeServiceIdentifier(
+                "elektra-site-agent".to_string()
+            )]
+        ));
+        assert!(!InternalRBACRules::allowed_from_static(
+            "CreateTenantDnsRecord",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-dns".to_string()
+            )]
+        ));
+        assert!(!InternalRBACRules::allowed_from_static(
+            "MlxAdminProfileBind",
+            &[Principal::SpiffeServiceIdentifier(
+                "elektra-site-agent".to_string()
+            )]
+        ));
         assert!(InternalRBACRules::allowed_from_static(
             "CreateTenantKeyset",
             &[Principal::SpiffeServiceIdentifier(
                 "elektra-site-agent".to_string()
             )]
         ));
+        assert!(InternalRBACRules::allowed_from_static(
+            "ExplainNetworkSecurityGroupFlow",
+            &[Principal::SpiffeServiceIdentifier(
+                "elektra-site-agent".to_string()
+            )]
+        ));
+        assert!(!InternalRBACRules::allowed_from_static(
+            "ExplainNetworkSecurityGroupFlow",
+            &[Principal::SpiffeServiceIdentifier(
+                "carbide-dhcp".to_string()
+            )]
+        ));
         assert!(InternalRBACRules::allowed_from_static(
             "FindNetworkSegmentsByIds",
             &[
@@ -0,0 +1,228 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Short-lived tokens for attaching to an instance's serial console.
+//!
+//! ssh-console's WebSocket frontend can't authenticate users with SSH keys
+//! or certificates, so the API mints a token naming the instance, whether
+//! the holder may type into the console, and an expiry time. ssh-console
+//! passes the token back to the API to find out which console to attach to.
+//!
+//! Session recordings are only served to the API, which proxies them to
+//! authorized users. The API authenticates to ssh-console with a
+//! [`RecordingsAccessToken`], signed with a separate key so that a console
+//! token can't be used to download recordings.
+
+use std::str::FromStr;
+
+use carbide_uuid::instance::InstanceId;
+use chrono::{DateTime, Utc};
+
+use crate::signed_token::{SigningKey, TokenError};
+
+// Versioned so that the token format can be changed without
+// accepting tokens signed for a different format.
+pub const KDF_INFO: &str = "console-access:v1";
+pub const RECORDINGS_KDF_INFO: &str = "console-recordings:v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl ConsoleAccess {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ConsoleAccess::ReadOnly => "ro",
+            ConsoleAccess::ReadWrite => "rw",
+        }
+    }
+}
+
+impl FromStr for ConsoleAccess {
+    type Err = TokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ro" => Ok(ConsoleAccess::ReadOnly),
+            "rw" => Ok(ConsoleAccess::ReadWrite),
+            _ => Err(TokenError::Malformed),
+        }
+    }
+}
+
+/// Grants access to a single instance's console until `expires_at`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleAccessToken {
+    pub instance_id: InstanceId,
+    pub access: ConsoleAccess,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ConsoleAccessToken {
+    /// Encodes the token as `{instance_id}.{ro|rw}.{expiry}.{signature}`,
+    /// which can be passed as a URL query parameter without escaping.
+    pub fn sign(&self, key: &SigningKey) -> String {
+        key.sign(&format!(
+            "{}.{}.{}",
+            self.instance_id,
+            self.access.as_str(),
+            self.expires_at.timestamp()
+        ))
+    }
+
+    /// Decodes a token produced by [`Self::sign`], checking its signature and
+    /// that it has not expired at `now`.
+    pub fn verify(token: &str, key: &SigningKey, now: DateTime<Utc>) -> Result<Self, TokenError> {
+        let payload = key.verify(token)?;
+
+        let mut fields = payload.split('.');
+        let (Some(instance_id), Some(access), Some(expires_at), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(TokenError::Malformed);
+        };
+        let token = Self {
+            instance_id: InstanceId::from_str(instance_id).map_err(|_| TokenError::Malformed)?,
+            access: access.parse()?,
+            expires_at: expires_at
+                .parse()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .ok_or(TokenError::Malformed)?,
+        };
+
+        if token.expires_at <= now {
+            return Err(TokenError::Expired);
+        }
+        Ok(token)
+    }
+}
+
+/// Grants the holder access to all session recordings until `expires_at`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingsAccessToken {
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RecordingsAccessToken {
+    /// Encodes the token as `recordings.{expiry}.{signature}`
+    pub fn sign(&self, key: &SigningKey) -> String {
+        key.sign(&format!("recordings.{}", self.expires_at.timestamp()))
+    }
+
+    /// Decodes a token produced by [`Self::sign`], checking its signature and
+    /// that it has not expired at `now`.
+    pub fn verify(token: &str, key: &SigningKey, now: DateTime<Utc>) -> Result<Self, TokenError> {
+        let payload = key.verify(token)?;
+
+        let token = Self {
+            expires_at: payload
+                .strip_prefix("recordings.")
+                .and_then(|secs| secs.parse().ok())
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .ok_or(TokenError::Malformed)?,
+        };
+
+        if token.expires_at <= now {
+            return Err(TokenError::Expired);
+        }
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn token(now: DateTime<Utc>, access: ConsoleAccess) -> ConsoleAccessToken {
+        ConsoleAccessToken {
+            instance_id: InstanceId::new(),
+            access,
+            expires_at: DateTime::from_timestamp(now.timestamp(), 0).unwrap()
+                + TimeDelta::minutes(5),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let now = Utc::now();
+        for access in [ConsoleAccess::ReadOnly, ConsoleAccess::ReadWrite] {
+            let token = token(now, access);
+            let signed = token.sign(&key);
+            assert_eq!(ConsoleAccessToken::verify(&signed, &key, now), Ok(token));
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_token() {
+        let key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let now = Utc::now();
+        let token = token(now, ConsoleAccess::ReadOnly);
+
+        assert_eq!(
+            ConsoleAccessToken::verify(&token.sign(&key), &key, token.expires_at),
+            Err(TokenError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_upgraded_access() {
+        let key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let now = Utc::now();
+        let signed = token(now, ConsoleAccess::ReadOnly).sign(&key);
+
+        // A viewer can't turn their token into a read-write one
+        let tampered = signed.replacen(".ro.", ".rw.", 1);
+        assert_eq!(
+            ConsoleAccessToken::verify(&tampered, &key, now),
+            Err(TokenError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_recordings_token() {
+        let key = SigningKey::derive(b"site-root", RECORDINGS_KDF_INFO).unwrap();
+        let now = Utc::now();
+        let recordings_token = RecordingsAccessToken {
+            expires_at: DateTime::from_timestamp(now.timestamp(), 0).unwrap()
+                + TimeDelta::minutes(1),
+        };
+
+        let signed = recordings_token.sign(&key);
+        assert_eq!(
+            RecordingsAccessToken::verify(&signed, &key, now),
+            Ok(recordings_token.clone())
+        );
+        assert_eq!(
+            RecordingsAccessToken::verify(&signed, &key, recordings_token.expires_at),
+            Err(TokenError::Expired)
+        );
+
+        // Console access tokens are signed with a different key
+        let console_key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let console_token = token(now, ConsoleAccess::ReadWrite).sign(&console_key);
+        assert_eq!(
+            RecordingsAccessToken::verify(&console_token, &key, now),
+            Err(TokenError::InvalidSignature)
+        );
+    }
+}
@@ -23,9 +23,7 @@ use db::{self, ObjectColumnFilter, network_security_group};
 use forge_network::virtualization::{VpcVirtualizationType, get_svi_ip};
 use ipnetwork::{IpNetwork, Ipv4Network};
 use model::instance::config::network::{InstanceInterfaceConfig, InterfaceFunctionId};
-use model::network_security_group::{
-    NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet,
-};
+use model::network_security_group::NetworkSecurityGroup;
 use model::network_segment::NetworkSegment;
 use model::resource_pool::common::CommonPools;
 use sqlx::PgConnection;
@@ -249,6 +247,7 @@ pub async fn tenant_network(
     network_virtualization_type: VpcVirtualizationType,
     suppress_tenant_security_groups: bool,
     network_security_group_details: Option<(i32, NetworkSecurityGroup)>,
+    max_network_security_group_size: usize,
     segment: &NetworkSegment,
     vpc_peering_policy_on_existing: Option<VpcPeeringPolicy>,
     booturl: &Option<String>,
@@ -439,6 +438,23 @@ pub async fn tenant_network(
         _ => None,
     };
 
+    let network_security_group = match network_security_group_details {
+        None => None,
+        Some((source, nsg)) => Some(rpc::FlatInterfaceNetworkSecurityGroupConfig {
+            id: nsg.id.to_string(),
+            version: nsg.version.to_string(),
+            source,
+            stateful_egress: nsg.stateful_egress,
+            rules: resolve_security_group_rules(txn, &nsg, max_network_security_group_size)
+                .await
+                .map_err(|e| {
+                    Status::internal(format!(
+                        "failed to configure FlatInterfaceConfig.network_security_group: {e}"
+                    ))
+                })?,
+        }),
+    };
+
     Ok(rpc::FlatInterfaceConfig {
         function_type: rpc_ft.into(),
         virtual_function_id: match iface.function_id {
@@ -462,55 +478,51 @@ pub async fn tenant_network(
         is_l2_segment,
         vpc_peer_prefixes,
         vpc_peer_vnis,
-        network_security_group: network_security_group_details
-            .map(|(source, nsg)| {
-                Ok(
-                        rpc::FlatInterfaceNetworkSecurityGroupConfig {
-                            id: nsg.id.to_string(),
-                            version: nsg.version.to_string(),
-                            source,
-                            stateful_egress: nsg.stateful_egress,
-                            rules:
-                                nsg.rules
-                                    .into_iter()
-                                    .map(resolve_security_group_rule)
-                                    .collect::<Result<
-                                        Vec<rpc::ResolvedNetworkSecurityGroupRule>,
-                                        CarbideError,
-                                    >>()?,
-                        },
-                    )
-            })
-            .transpose()
-            .map_err(|e: CarbideError| {
-                Status::internal(format!(
-                    "failed to configure FlatInterfaceConfig.network_security_group: {e}"
-                ))
-            })?,
+        network_security_group,
         internal_uuid: Some(iface.internal_uuid.into()),
         mtu: u32::try_from(segment.mtu).ok(),
     })
 }
 
-pub fn resolve_security_group_rule(
-    rule: NetworkSecurityGroupRule,
-) -> Result<rpc::ResolvedNetworkSecurityGroupRule, CarbideError> {
-    Ok(rpc::ResolvedNetworkSecurityGroupRule {
-        // When we decide to allow object references,
-        // they would be resolved to their actual prefix
-        // lists and stored here.
-        src_prefixes: match rule.src_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
-        dst_prefixes: match rule.dst_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
-        rule: Some(rule.try_into()?),
-    })
+/// Expands the rules of a network security group into the form sent to the DPU.
+///
+/// If the object references in the rules expand beyond the limit, only the
+/// rules which fit are sent, so that the overflow of one NSG doesn't fail the
+/// network config of the host. The problem is recorded in the expansion status
+/// of the NSG, which is reported by `GetNetworkSecurityGroupPropagationStatus`.
+async fn resolve_security_group_rules(
+    txn: &mut PgConnection,
+    nsg: &NetworkSecurityGroup,
+    max_network_security_group_size: usize,
+) -> Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError> {
+    let (rules, error) = crate::network_security_group::expand_network_security_group(
+        txn,
+        nsg,
+        max_network_security_group_size,
+    )
+    .await?;
+
+    if let Some(error) = error {
+        tracing::warn!(
+            network_security_group_id = %nsg.id,
+            tenant_organization_id = %nsg.tenant_organization_id,
+            %error,
+            "Expanded rules of network security group exceed the limit, sending the rules which fit"
+        );
+        crate::network_security_group::check_network_security_group(
+            txn,
+            nsg,
+            max_network_security_group_size,
+        )
+        .await?;
+    }
+
+    rules
+        .into_iter()
+        .map(|rule| {
+            rpc::ResolvedNetworkSecurityGroupRule::try_from(rule).map_err(CarbideError::from)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -54,6 +54,8 @@ pub(crate) async fn trigger_machine_attestation(
         state_version,
         state_outcome: None,
         attestation_status: model::attestation::spdm::SpdmAttestationStatus::NotStarted,
+        last_attested_at: None,
+        last_attestation_passed: None,
     };
     insert_or_update_machine_attestation_request(&mut txn, &attestation_request).await?;
     txn.commit().await?;
@@ -175,26 +177,66 @@ pub(crate) async fn attest_quote(
         .collect::<Vec<String>>()
         .into();
 
-    // In this case, we're not doing anything with
-    // the resulting report (at least not yet), so just
-    // throw it away.
-    let report =
-        db::measured_boot::report::new(&mut txn, machine_id, pcr_values.into_inner().as_slice())
-            .await
-            .map_err(|e| {
-                Status::internal(format!(
-                    "Failed storing measurement report: (machine_id: {}, err: {})",
-                    &machine_id, e
-                ))
-            })?;
+    let pcr_values = pcr_values.into_inner();
+
+    // Only keep the event log if it's a binary TCG event log we can
+    // parse, since it's what reports get replayed and diffed against.
+    // Older scouts send the text output of tpm2_eventlog instead.
+    //
+    // Replaying the event log should land on the quoted PCR values. If
+    // it doesn't, the log can't be trusted to explain the measurements,
+    // which gets recorded with the report, but bundle matching still
+    // only goes by the quoted values.
+    let event_log = request.event_log.as_deref().and_then(|data| {
+        ::measured_boot::eventlog::EventLog::parse(data)
+            .inspect_err(|e| {
+                tracing::warn!(%machine_id, "Not storing unparseable event log: {e}");
+            })
+            .ok()
+            .map(|parsed| (data, parsed.replay_against(&pcr_values)))
+    });
+
+    let report = db::measured_boot::report::new_with_event_log(
+        &mut txn,
+        machine_id,
+        pcr_values.as_slice(),
+        event_log,
+    )
+    .await
+    .map_err(|e| {
+        Status::internal(format!(
+            "Failed storing measurement report: (machine_id: {}, err: {})",
+            &machine_id, e
+        ))
+    })?;
+
+    if let Some(replay) = &report.event_log_replay
+        && !replay.matches()
+    {
+        tracing::warn!(
+            %machine_id,
+            report_id = %report.report_id,
+            "Event log replay does not match quote: {replay}",
+        );
+    }
 
     // if the attestation was successful and enabled, we can now vend the certs
     // - get attestation result
     // - if enabled and not successful, send response without certs
     // - else send response with certs
     let attestation_failed = if api.runtime_config.attestation_enabled {
-        !crate::attestation::has_passed_attestation(&mut txn, &machine_id, &report.report_id)
-            .await?
+        let attestation_failed =
+            !crate::attestation::has_passed_attestation(&mut txn, &machine_id, &report.report_id)
+                .await?;
+        crate::attestation::policy::apply_measured_boot_result(
+            &mut txn,
+            &api.runtime_config.attestation_policy,
+            &machine_id,
+            &report.report_id.to_string(),
+            !attestation_failed,
+        )
+        .await?;
+        attestation_failed
     } else {
         false
     };
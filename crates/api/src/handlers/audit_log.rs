@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as rpc;
+use model::audit_log::AuditLogFilter;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+/// The amount of entries which are returned if the request doesn't specify a limit
+const DEFAULT_LIMIT: u32 = 100;
+
+/// The maximum amount of entries which are returned by a single request
+const MAX_LIMIT: u32 = 1000;
+
+/// Returns the audit log entries which match the filter of the request,
+/// starting with the most recent entry
+pub(crate) async fn find_entries(
+    api: &Api,
+    request: Request<rpc::FindAuditLogEntriesRequest>,
+) -> Result<Response<rpc::AuditLogEntries>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let filter = AuditLogFilter::try_from(&request).map_err(CarbideError::from)?;
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT);
+    if limit == 0 || limit > MAX_LIMIT {
+        return Err(CarbideError::InvalidArgument(format!(
+            "limit must be between 1 and {MAX_LIMIT}"
+        ))
+        .into());
+    }
+
+    let mut txn = api.txn_begin().await?;
+    let entries = db::audit_log::find(&mut txn, &filter, limit.into()).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::AuditLogEntries {
+        entries: entries.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -0,0 +1,258 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as rpc;
+use chrono::{DateTime, SubsecRound, TimeDelta, Utc};
+use db;
+use futures_util::StreamExt;
+use reqwest::StatusCode;
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use tonic::{Request, Response, Status};
+use url::Url;
+
+use crate::CarbideError;
+use crate::api::{Api, ConsoleRecordingStreamType, log_machine_id, log_request_data};
+use crate::console_access::{
+    ConsoleAccess, ConsoleAccessToken, KDF_INFO, RECORDINGS_KDF_INFO, RecordingsAccessToken,
+};
+use crate::signed_token::SigningKey;
+
+/// How long the token which the API passes to ssh-console to fetch a recording
+/// is valid for. Downloads which started before it expires are not interrupted.
+const RECORDINGS_TOKEN_LIFETIME: TimeDelta = TimeDelta::seconds(60);
+
+// Called by the web UI and admin-cli to attach to an instance's console
+// through ssh-console's WebSocket frontend
+pub(crate) async fn create_console_access_token(
+    api: &Api,
+    request: Request<rpc::CreateConsoleAccessTokenRequest>,
+) -> Result<Response<rpc::ConsoleAccessToken>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let instance_id = request
+        .instance_id
+        .ok_or(CarbideError::MissingArgument("instance_id"))?;
+
+    let mut txn = api.txn_begin().await?;
+    let instance = db::instance::find_by_id(&mut txn, instance_id)
+        .await?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "instance",
+            id: instance_id.to_string(),
+        })?;
+    txn.commit().await?;
+
+    log_machine_id(&instance.machine_id);
+    if instance.deleted.is_some() {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "Instance {instance_id} is being deleted"
+        ))
+        .into());
+    }
+
+    let lifetime = TimeDelta::from_std(api.runtime_config.console_access.token_lifetime)
+        .map_err(|e| Status::internal(format!("Invalid console token lifetime: {e}")))?;
+    let token = ConsoleAccessToken {
+        instance_id,
+        access: if request.read_only {
+            ConsoleAccess::ReadOnly
+        } else {
+            ConsoleAccess::ReadWrite
+        },
+        // The token only encodes whole seconds
+        expires_at: Utc::now().trunc_subsecs(0) + lifetime,
+    };
+    let key = SigningKey::fetch(api.credential_provider.as_ref(), KDF_INFO)
+        .await
+        .map_err(CarbideError::from)?;
+
+    Ok(Response::new(rpc::ConsoleAccessToken {
+        token: token.sign(&key),
+        expires_at: Some(token.expires_at.into()),
+        websocket_url: api.runtime_config.console_access.websocket_url.clone(),
+    }))
+}
+
+// ssh-console makes this RPC call when a client connects to its WebSocket frontend
+pub(crate) async fn validate_console_access_token(
+    api: &Api,
+    request: Request<rpc::ValidateConsoleAccessTokenRequest>,
+) -> Result<Response<rpc::ValidateConsoleAccessTokenResponse>, Status> {
+    // The request isn't logged, since the token grants access to the console
+    let request = request.into_inner();
+
+    let key = SigningKey::fetch(api.credential_provider.as_ref(), KDF_INFO)
+        .await
+        .map_err(CarbideError::from)?;
+    let token = ConsoleAccessToken::verify(&request.token, &key, Utc::now())
+        .map_err(|e| Status::permission_denied(format!("Invalid console access token: {e}")))?;
+
+    let mut txn = api.txn_begin().await?;
+    let instance = db::instance::find_by_id(&mut txn, token.instance_id).await?;
+    txn.commit().await?;
+
+    // Tokens outlive neither the instance nor its machine assignment
+    let Some(instance) = instance.filter(|instance| instance.deleted.is_none()) else {
+        return Err(Status::permission_denied(format!(
+            "Instance {} no longer exists",
+            token.instance_id
+        )));
+    };
+    log_machine_id(&instance.machine_id);
+
+    Ok(Response::new(rpc::ValidateConsoleAccessTokenResponse {
+        instance_id: Some(token.instance_id),
+        machine_id: Some(instance.machine_id),
+        read_only: token.access == ConsoleAccess::ReadOnly,
+    }))
+}
+
+// Recordings as listed by ssh-console's `/recordings` endpoints
+#[derive(Deserialize)]
+struct RecordingInfo {
+    machine: String,
+    name: String,
+    size_bytes: u64,
+    modified: DateTime<Utc>,
+}
+
+impl From<RecordingInfo> for rpc::ConsoleRecordingInfo {
+    fn from(recording: RecordingInfo) -> Self {
+        Self {
+            machine: recording.machine,
+            name: recording.name,
+            size_bytes: recording.size_bytes,
+            modified: Some(recording.modified.into()),
+        }
+    }
+}
+
+pub(crate) async fn list_console_recordings(
+    api: &Api,
+    request: Request<rpc::ListConsoleRecordingsRequest>,
+) -> Result<Response<rpc::ConsoleRecordingList>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let segments: Vec<&str> = request.machine.as_deref().into_iter().collect();
+    let response = fetch_from_ssh_console(api, &segments).await?;
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| Status::unavailable(format!("Failed to list recordings: {e}")))?;
+    let recordings: Vec<RecordingInfo> = serde_json::from_slice(&body)
+        .map_err(|e| Status::internal(format!("Invalid recording list from ssh-console: {e}")))?;
+
+    Ok(Response::new(rpc::ConsoleRecordingList {
+        recordings: recordings.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// Recordings contain everything typed into the console, so they are only
+// served through this RPC and never directly by ssh-console
+pub(crate) async fn get_console_recording(
+    api: &Api,
+    request: Request<rpc::GetConsoleRecordingRequest>,
+) -> Result<Response<ConsoleRecordingStreamType>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    if request.machine.is_empty() {
+        return Err(CarbideError::MissingArgument("machine").into());
+    }
+    if request.name.is_empty() {
+        return Err(CarbideError::MissingArgument("name").into());
+    }
+
+    let response = fetch_from_ssh_console(api, &[&request.machine, &request.name]).await?;
+    let stream = response.bytes_stream().map(|chunk| {
+        chunk
+            .map(|data| rpc::ConsoleRecordingChunk {
+                data: data.to_vec(),
+            })
+            .map_err(|e| Status::unavailable(format!("Failed to download recording: {e}")))
+    });
+
+    Ok(Response::new(Box::pin(stream)))
+}
+
+// Requests `{recordings_url}/{segments...}` from ssh-console, authenticating
+// with a freshly minted recordings access token
+async fn fetch_from_ssh_console(api: &Api, segments: &[&str]) -> Result<reqwest::Response, Status> {
+    let Some(recordings_url) = &api.runtime_config.console_access.recordings_url else {
+        return Err(CarbideError::FailedPrecondition(
+            "console_access.recordings_url is not configured".to_string(),
+        )
+        .into());
+    };
+    let mut url = Url::parse(recordings_url)
+        .map_err(|e| Status::internal(format!("Invalid console_access.recordings_url: {e}")))?;
+    url.path_segments_mut()
+        .map_err(|_| Status::internal("Invalid console_access.recordings_url"))?
+        .pop_if_empty()
+        .extend(segments);
+
+    let token = RecordingsAccessToken {
+        expires_at: Utc::now().trunc_subsecs(0) + RECORDINGS_TOKEN_LIFETIME,
+    };
+    let key = SigningKey::fetch(api.credential_provider.as_ref(), RECORDINGS_KDF_INFO)
+        .await
+        .map_err(CarbideError::from)?;
+
+    let response = reqwest::Client::new()
+        .get(url)
+        .header(AUTHORIZATION, format!("Bearer {}", token.sign(&key)))
+        .send()
+        .await
+        .map_err(|e| Status::unavailable(format!("Failed to reach ssh-console: {e}")))?;
+
+    match response.status() {
+        status if status.is_success() => Ok(response),
+        StatusCode::NOT_FOUND => Err(Status::not_found(format!(
+            "Recording not found: {}",
+            segments.join("/")
+        ))),
+        StatusCode::BAD_REQUEST => Err(Status::invalid_argument(format!(
+            "Invalid recording name: {}",
+            segments.join("/")
+        ))),
+        status => Err(Status::unavailable(format!(
+            "ssh-console returned {status} for recordings request"
+        ))),
+    }
+}
+
+// ssh-console makes this RPC call when carbide-api requests a session recording
+pub(crate) async fn validate_console_recording_access_token(
+    api: &Api,
+    request: Request<rpc::ValidateConsoleRecordingAccessTokenRequest>,
+) -> Result<Response<rpc::ValidateConsoleRecordingAccessTokenResponse>, Status> {
+    // The request isn't logged, since the token grants access to the recordings
+    let request = request.into_inner();
+
+    let key = SigningKey::fetch(api.credential_provider.as_ref(), RECORDINGS_KDF_INFO)
+        .await
+        .map_err(CarbideError::from)?;
+    RecordingsAccessToken::verify(&request.token, &key, Utc::now())
+        .map_err(|e| Status::permission_denied(format!("Invalid recordings access token: {e}")))?;
+
+    Ok(Response::new(
+        rpc::ValidateConsoleRecordingAccessTokenResponse {},
+    ))
+}
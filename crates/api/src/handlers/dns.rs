@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 use ::rpc::protos;
-use db::dns::resource_record;
+use db::dns::{resource_record, tenant_record};
 use dns_record::constants::*;
 use dns_record::{DnsResourceRecordReply, DnsResourceRecordType};
 use tonic::{Request, Response, Status};
@@ -60,10 +60,16 @@ async fn lookup_soa_record(
 }
 
 /// Returns ALL record types (A, AAAA, CNAME, etc.) - PowerDNS filters to requested type
-async fn lookup_records_by_qname(
-    txn: impl DbReader<'_>,
+///
+/// This includes the records derived from interface addresses, the records managed by
+/// tenants and the PTR records generated for reverse zones.
+async fn lookup_records_by_qname<DB>(
+    txn: &mut DB,
     query_name: &str,
-) -> Result<Vec<DnsResourceRecordReply>, tonic::Status> {
+) -> Result<Vec<DnsResourceRecordReply>, tonic::Status>
+where
+    for<'db> &'db mut DB: DbReader<'db>,
+{
     tracing::debug!("Looking up records for {}", query_name);
 
     // dns_records view expects trailing dots (FQDN format)
@@ -73,17 +79,26 @@ async fn lookup_records_by_qname(
         query_name.to_string()
     };
 
-    let result = resource_record::find_record(txn, &qname_with_dot)
+    let mut records: Vec<model::dns::ResourceRecord> =
+        resource_record::find_record(&mut *txn, &qname_with_dot)
+            .await
+            .map_err(CarbideError::from)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+    // Names of tenant records are stored lowercase
+    let tenant_records = tenant_record::find_by_q_name(&mut *txn, &qname_with_dot.to_lowercase())
         .await
-        .map_err(CarbideError::from)?
-        .into_iter()
-        .map(|db_record| {
-            let model_record: model::dns::ResourceRecord = db_record.into();
-            model_record.into()
-        })
-        .collect::<Vec<_>>();
+        .map_err(CarbideError::from)?;
+    records.extend(tenant_records.into_iter().map(Into::into));
+
+    let ptr_records = resource_record::find_ptr_records(txn, &qname_with_dot)
+        .await
+        .map_err(CarbideError::from)?;
+    records.extend(ptr_records.into_iter().map(Into::into));
 
-    Ok(result)
+    Ok(records.into_iter().map(Into::into).collect())
 }
 
 /// Handles ANY DNS record lookups for queries from PowerDNS
@@ -110,7 +125,8 @@ async fn lookup_records_by_qname(
 /// # Implementation
 ///
 /// This function:
-/// 1. Queries the dns_records view for all matching records (A, AAAA, CNAME, etc.)
+/// 1. Queries the dns_records view, tenant records and generated PTR records for all
+///    matching records (A, AAAA, CNAME, PTR, etc.)
 /// 2. If the qname matches a domain we're authoritative for, includes the SOA record
 /// 3. Returns everything - PowerDNS decides what to include in the final packet
 ///
@@ -295,7 +311,7 @@ pub async fn lookup_record(
         }
         _ => {
             // For all other types (A, AAAA, MX, CNAME, etc.):
-            lookup_records_by_qname(&api.database_connection, &qname).await?
+            lookup_records_by_qname(&mut api.db_reader(), &qname).await?
         }
     };
 
@@ -706,6 +706,22 @@ pub(crate) async fn publish_mlx_observation_report(
 ) -> Result<Response<mlx_device_pb::PublishMlxObservationReportResponse>, Status> {
     log_request_data(&request);
 
+    // Results of syncing the mlxconfig profile bound to the host are evaluated
+    // independently of DPA provisioning, and are not DPA card state updates.
+    let mut request = request;
+    if let Some(report) = request.get_mut().report.as_mut() {
+        crate::mlxconfig_policy::process_observation_report(api, report).await?;
+        let observation_count = report.observations.len();
+        report
+            .observations
+            .retain(|obs| obs.profile_version.is_none());
+        if observation_count > 0 && report.observations.is_empty() {
+            return Ok(Response::new(
+                mlx_device_pb::PublishMlxObservationReportResponse {},
+            ));
+        }
+    }
+
     if !api.runtime_config.is_dpa_enabled() {
         return Ok(Response::new(
             mlx_device_pb::PublishMlxObservationReportResponse {},
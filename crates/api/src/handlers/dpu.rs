@@ -23,8 +23,8 @@ use ::rpc::errors::RpcDataConversionError;
 use ::rpc::{common as rpc_common, forge as rpc};
 use carbide_uuid::machine::MachineId;
 use db::{
-    DatabaseError, ObjectColumnFilter, dpu_agent_upgrade_policy, network_security_group,
-    network_segment,
+    DatabaseError, ObjectColumnFilter, ObjectFilter, dpu_agent_upgrade_policy,
+    network_security_group, network_segment,
 };
 use forge_network::virtualization::VpcVirtualizationType;
 use futures_util::future::join_all;
@@ -441,6 +441,7 @@ pub(crate) async fn get_managed_host_network_config_inner(
                         network_virtualization_type,
                         suppress_tenant_security_groups,
                         network_security_group_details.clone(),
+                        api.runtime_config.network_security_group.max_network_security_group_size as usize,
                         segment,
                         match api.runtime_config.vpc_peering_policy_on_existing {
                             None => api.runtime_config.vpc_peering_policy,
@@ -564,6 +565,35 @@ pub(crate) async fn get_managed_host_network_config_inner(
         Vec::new()
     };
 
+    // The SSH keys of the instance are served to the tenant by the metadata service on the DPU
+    let instance_public_keys = match snapshot.instance.as_ref() {
+        Some(instance) if !instance.config.tenant.tenant_keyset_ids.is_empty() => {
+            let keysets = db::tenant_keyset::find(
+                Some(instance.config.tenant.tenant_organization_id.to_string()),
+                ObjectFilter::List(&instance.config.tenant.tenant_keyset_ids),
+                true,
+                &mut txn,
+            )
+            .await?;
+            keysets
+                .into_iter()
+                .flat_map(|keyset| keyset.keyset_content.public_keys)
+                .map(rpc::TenantPublicKey::from)
+                .unique_by(|key| key.public_key.clone())
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    // Policy overrides are defined by the site operator, so object references
+    // in them are not restricted to the VPCs of any tenant.
+    let network_security_policy_overrides = crate::network_security_group::resolve_rules(
+        &mut txn,
+        None,
+        &api.runtime_config.network_security_group.policy_overrides,
+    )
+    .await?;
+
     // Next, get credentials for each extension service from vault. This should be done after the
     // transaction is committed.
     txn.commit().await?;
@@ -629,18 +659,13 @@ pub(crate) async fn get_managed_host_network_config_inner(
             HBN_SINGLE_VLAN_DEVICE.to_string()
         },
         site_global_vpc_vni: api.runtime_config.site_global_vpc_vni,
+        instance_public_keys,
         managed_host_config: Some(network_config),
         managed_host_config_version: dpu_snapshot.network_config.version.version_string(),
         use_admin_network,
         admin_interface: Some(admin_interface_rpc),
         tenant_interfaces,
-        network_security_policy_overrides: api
-            .runtime_config
-            .network_security_group
-            .policy_overrides
-            .iter()
-            .map(|r| ethernet_virtualization::resolve_security_group_rule(r.clone()))
-            .collect::<Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError>>()?,
+        network_security_policy_overrides,
         stateful_acls_enabled: api
             .runtime_config
             .network_security_group
@@ -871,6 +871,108 @@ fn validate_pod_spec_file(data: &str) -> Result<(), CarbideError> {
     Ok(())
 }
 
+/// Validates the container spec file format for ContainerdContainer service.
+/// The container spec file must be a valid YAML/JSON object that must contain the following fields:
+/// - image
+///
+/// The optional fields `args` (array of strings) and `env` (mapping of strings) are checked
+/// for their type.
+fn validate_container_spec_file(data: &str) -> Result<(), CarbideError> {
+    if data.is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "Invalid empty data for ContainerdContainer service, need a valid container spec"
+                .to_string(),
+        ));
+    }
+
+    let root = serde_yaml::from_str::<serde_yaml::Value>(data).map_err(|e| {
+        CarbideError::InvalidArgument(format!(
+            "Invalid container spec file for ContainerdContainer service: {}",
+            e
+        ))
+    })?;
+
+    let serde_yaml::Value::Mapping(mapping) = root else {
+        return Err(CarbideError::InvalidArgument(
+            "Container spec must be a valid mapping object that contains image".to_string(),
+        ));
+    };
+
+    match mapping.get(serde_yaml::Value::String("image".to_string())) {
+        Some(serde_yaml::Value::String(image)) if !image.trim().is_empty() => {}
+        _ => {
+            return Err(CarbideError::InvalidArgument(
+                "Container spec missing required field: image".to_string(),
+            ));
+        }
+    }
+
+    if let Some(args) = mapping.get(serde_yaml::Value::String("args".to_string())) {
+        let valid = args
+            .as_sequence()
+            .is_some_and(|args| args.iter().all(|arg| arg.is_string()));
+        if !valid {
+            return Err(CarbideError::InvalidArgument(
+                "Container spec field args must be an array of strings".to_string(),
+            ));
+        }
+    }
+
+    if let Some(env) = mapping.get(serde_yaml::Value::String("env".to_string())) {
+        let valid = env.as_mapping().is_some_and(|env| {
+            env.iter()
+                .all(|(name, value)| name.is_string() && value.is_string())
+        });
+        if !valid {
+            return Err(CarbideError::InvalidArgument(
+                "Container spec field env must be a mapping of strings".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the unit file format for SystemdUnit service.
+/// The unit file must contain a [Service] section with an ExecStart= entry.
+fn validate_systemd_unit_file(data: &str) -> Result<(), CarbideError> {
+    if data.trim().is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "Invalid empty data for SystemdUnit service, need a valid unit file".to_string(),
+        ));
+    }
+
+    let mut section = "";
+    let mut has_service_section = false;
+    let mut has_exec_start = false;
+    for line in data.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line;
+            has_service_section |= section == "[Service]";
+            continue;
+        }
+        if section == "[Service]" && line.starts_with("ExecStart=") {
+            has_exec_start = true;
+        }
+    }
+
+    if !has_service_section {
+        return Err(CarbideError::InvalidArgument(
+            "Unit file missing required section: [Service]".to_string(),
+        ));
+    }
+    if !has_exec_start {
+        return Err(CarbideError::InvalidArgument(
+            "Unit file missing required field: Service.ExecStart".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Validates extension service data fields based on service type
 fn validate_extension_service_data(
     service_type: &ExtensionServiceType,
@@ -887,6 +989,16 @@ fn validate_extension_service_data(
         ExtensionServiceType::KubernetesPod => {
             validate_pod_spec_file(data)?;
 
+            Ok(())
+        }
+        ExtensionServiceType::ContainerdContainer => {
+            validate_container_spec_file(data)?;
+
+            Ok(())
+        }
+        ExtensionServiceType::SystemdUnit => {
+            validate_systemd_unit_file(data)?;
+
             Ok(())
         }
     }
@@ -929,6 +1041,20 @@ fn validate_extension_service_credential(
                 ));
             }
         }
+        ExtensionServiceType::ContainerdContainer => {
+            // The registry URL is used as host of the registry which the credential
+            // is passed to when containerd pulls the image.
+            if credential.registry_url.is_empty() || credential.registry_url.len() > 255 {
+                return Err(CarbideError::InvalidArgument(
+                    "Invalid credential registry URL".to_string(),
+                ));
+            }
+        }
+        ExtensionServiceType::SystemdUnit => {
+            return Err(CarbideError::InvalidArgument(
+                "Credentials are not supported for SystemdUnit services".to_string(),
+            ));
+        }
     }
 
     Ok(())
@@ -960,6 +1086,24 @@ fn detect_extension_service_spec_change(
                 })?;
             old_data_yaml != new_data_yaml
         }
+        ExtensionServiceType::ContainerdContainer => {
+            let old_data_yaml =
+                serde_yaml::from_str::<serde_yaml::Value>(old_data).map_err(|e| {
+                    CarbideError::internal(format!(
+                        "Found corrupted data for ContainerdContainer service: {}",
+                        e
+                    ))
+                })?;
+            let new_data_yaml =
+                serde_yaml::from_str::<serde_yaml::Value>(new_data).map_err(|e| {
+                    CarbideError::InvalidArgument(format!(
+                        "Invalid container spec file for ContainerdContainer service: {}",
+                        e
+                    ))
+                })?;
+            old_data_yaml != new_data_yaml
+        }
+        ExtensionServiceType::SystemdUnit => old_data.trim() != new_data.trim(),
     };
 
     let cred_changed = match (old_cred.as_ref(), new_cred.as_ref()) {
@@ -990,12 +1134,12 @@ async fn create_extension_service_credential(
     credential: &rpc::DpuExtensionServiceCredential,
 ) -> Result<(), CarbideError> {
     match service_type {
-        ExtensionServiceType::KubernetesPod => {
+        ExtensionServiceType::KubernetesPod | ExtensionServiceType::ContainerdContainer => {
             use ::rpc::forge::dpu_extension_service_credential::Type as CredType;
 
             match credential.r#type.as_ref() {
                 Some(CredType::UsernamePassword(up)) => {
-                    // The username format is "url: {registry_url}, username: {username}" for KubernetesPod service credentials
+                    // The username format is "url: {registry_url}, username: {username}" for registry credentials
                     // Because we don't have a separate field for registry_url in the credential struct in vault
                     let cred_username = format!(
                         "url: {}, username: {}",
@@ -1021,6 +1165,9 @@ async fn create_extension_service_credential(
                 )),
             }
         }
+        ExtensionServiceType::SystemdUnit => Err(CarbideError::InvalidArgument(
+            "Credentials are not supported for SystemdUnit services".to_string(),
+        )),
     }
 }
 
@@ -24,6 +24,7 @@ use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::Api;
+use crate::audit_log::AuditLogRecorder;
 use crate::handlers::utils::convert_and_log_machine_id;
 
 pub async fn record_hardware_health_report(
@@ -82,6 +83,9 @@ pub async fn get_hardware_health_report(
         if let Some(log_parser_health_report) = host_machine.log_parser_health_report.as_ref() {
             hardware_health_report.merge(log_parser_health_report);
         }
+        if let Some(console_health_report) = host_machine.console_health_report.as_ref() {
+            hardware_health_report.merge(console_health_report);
+        }
         Some(hardware_health_report)
     } else {
         None
@@ -142,6 +146,27 @@ pub async fn record_log_parser_health_report(
     Ok(Response::new(()))
 }
 
+pub async fn record_console_health_report(
+    api: &Api,
+    request: Request<rpc::HardwareHealthReport>,
+) -> Result<Response<()>, Status> {
+    let mut txn = api.txn_begin().await?;
+
+    let rpc::HardwareHealthReport { machine_id, report } = request.into_inner();
+    let machine_id = convert_and_log_machine_id(machine_id.as_ref())?;
+    let Some(report) = report else {
+        return Err(CarbideError::MissingArgument("report").into());
+    };
+
+    let report = health_report::HealthReport::try_from(report)
+        .map_err(|e| CarbideError::internal(e.to_string()))?;
+    db::machine::update_console_health_report(&mut txn, &machine_id, &report).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
+
 async fn remove_by_source(
     txn: &mut PgConnection,
     machine_id: MachineId,
@@ -194,6 +219,7 @@ pub async fn insert_health_report_override(
     api: &Api,
     request: Request<rpc::InsertHealthReportOverrideRequest>,
 ) -> Result<Response<()>, Status> {
+    let audit_log = AuditLogRecorder::from_request(&request);
     let rpc::InsertHealthReportOverrideRequest {
         machine_id,
         r#override: Some(rpc::HealthReportOverride { report, mode }),
@@ -233,6 +259,7 @@ pub async fn insert_health_report_override(
 
     db::machine::insert_health_report_override(&mut txn, &machine_id, mode, &report, false).await?;
 
+    audit_log.record_success(&mut txn).await?;
     txn.commit().await?;
 
     Ok(Response::new(()))
@@ -242,12 +269,14 @@ pub async fn remove_health_report_override(
     api: &Api,
     request: Request<rpc::RemoveHealthReportOverrideRequest>,
 ) -> Result<Response<()>, Status> {
+    let audit_log = AuditLogRecorder::from_request(&request);
     let mut txn = api.txn_begin().await?;
 
     let rpc::RemoveHealthReportOverrideRequest { machine_id, source } = request.into_inner();
     let machine_id = convert_and_log_machine_id(machine_id.as_ref())?;
 
     remove_by_source(&mut txn, machine_id, source).await?;
+    audit_log.record_success(&mut txn).await?;
     txn.commit().await?;
 
     Ok(Response::new(()))
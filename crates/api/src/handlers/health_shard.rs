@@ -0,0 +1,129 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use ::rpc::forge as rpc;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+/// Bounds of the lease duration which replicas can request
+const MIN_LEASE_DURATION: Duration = Duration::from_secs(5);
+const MAX_LEASE_DURATION: Duration = Duration::from_secs(600);
+
+fn validate_not_empty(name: &'static str, value: &str) -> Result<(), CarbideError> {
+    if value.trim().is_empty() {
+        return Err(CarbideError::InvalidArgument(format!(
+            "{name} must not be empty"
+        )));
+    }
+    Ok(())
+}
+
+/// Renews the lease of the calling replica and returns all replicas with an
+/// unexpired lease
+pub(crate) async fn renew_lease(
+    api: &Api,
+    request: Request<rpc::HealthShardLeaseRequest>,
+) -> Result<Response<rpc::HealthShardMembers>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    validate_not_empty("member_id", &request.member_id)?;
+    let lease_duration = request
+        .lease_duration
+        .ok_or(CarbideError::MissingArgument("lease_duration"))?;
+    let lease_duration = Duration::try_from(lease_duration)
+        .map_err(|e| CarbideError::InvalidArgument(format!("Invalid lease_duration: {e}")))?;
+    if !(MIN_LEASE_DURATION..=MAX_LEASE_DURATION).contains(&lease_duration) {
+        return Err(CarbideError::InvalidArgument(format!(
+            "lease_duration must be between {}s and {}s",
+            MIN_LEASE_DURATION.as_secs(),
+            MAX_LEASE_DURATION.as_secs()
+        ))
+        .into());
+    }
+
+    let mut txn = api.txn_begin().await?;
+    db::health_shard::renew_lease(&mut txn, &request.member_id, lease_duration).await?;
+    let leases = db::health_shard::find_active_leases(&mut txn).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::HealthShardMembers {
+        members: leases.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub(crate) async fn release_lease(
+    api: &Api,
+    request: Request<rpc::HealthShardLeaseRelease>,
+) -> Result<Response<()>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    validate_not_empty("member_id", &request.member_id)?;
+
+    let mut txn = api.txn_begin().await?;
+    db::health_shard::release_lease(&mut txn, &request.member_id).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
+
+pub(crate) async fn save_collector_state(
+    api: &Api,
+    request: Request<rpc::HealthCollectorState>,
+) -> Result<Response<()>, Status> {
+    let request = request.into_inner();
+    validate_not_empty("endpoint_key", &request.endpoint_key)?;
+    validate_not_empty("collector", &request.collector)?;
+    let state: serde_json::Value = serde_json::from_str(&request.state)
+        .map_err(|e| CarbideError::InvalidArgument(format!("state is not valid JSON: {e}")))?;
+
+    let mut txn = api.txn_begin().await?;
+    db::health_shard::save_collector_state(
+        &mut txn,
+        &request.endpoint_key,
+        &request.collector,
+        &state,
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
+
+pub(crate) async fn get_collector_state(
+    api: &Api,
+    request: Request<rpc::HealthCollectorStateRequest>,
+) -> Result<Response<rpc::OptionalHealthCollectorState>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    validate_not_empty("endpoint_key", &request.endpoint_key)?;
+    validate_not_empty("collector", &request.collector)?;
+
+    let mut txn = api.txn_begin().await?;
+    let state =
+        db::health_shard::find_collector_state(&mut txn, &request.endpoint_key, &request.collector)
+            .await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::OptionalHealthCollectorState {
+        state: state.map(Into::into),
+    }))
+}
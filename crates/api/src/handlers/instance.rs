@@ -36,7 +36,9 @@ use model::instance::config::infiniband::InstanceInfinibandConfig;
 use model::instance::config::network::{InstanceNetworkConfig, NetworkDetails};
 use model::instance::config::nvlink::InstanceNvLinkConfig;
 use model::instance::config::tenant_config::TenantConfig;
+use model::instance::event::InstanceEventPayload;
 use model::instance::snapshot::InstanceSnapshot;
+use model::instance::status::tenant::TenantState;
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{
     InstanceState, LoadSnapshotOptions, ManagedHostState, ManagedHostStateSnapshot,
@@ -47,6 +49,7 @@ use serde_json::json;
 use tonic::{Request, Response, Status};
 
 use crate::api::{Api, log_machine_id, log_request_data, log_tenant_organization_id};
+use crate::audit_log::AuditLogRecorder;
 use crate::handlers::utils::convert_and_log_machine_id;
 use crate::instance::{
     InstanceAllocationRequest, allocate_ib_port_guid, allocate_instance, allocate_network,
@@ -222,6 +225,7 @@ pub(crate) async fn find_by_ids(
     for snapshot in snapshots.into_iter() {
         instances.push(snapshot_to_instance(snapshot)?);
     }
+    crate::attestation::policy::add_instance_attestation_status(&mut txn, &mut instances).await?;
     let _ = txn.rollback().await;
 
     Ok(Response::new(rpc::InstanceList { instances }))
@@ -252,11 +256,12 @@ pub(crate) async fn find_by_machine_id(
     let maybe_instance =
         Option::<rpc::Instance>::try_from(mh_snapshot).map_err(CarbideError::from)?;
 
-    let instances = if let Some(instance) = maybe_instance {
+    let mut instances = if let Some(instance) = maybe_instance {
         vec![instance]
     } else {
         vec![]
     };
+    crate::attestation::policy::add_instance_attestation_status(&mut txn, &mut instances).await?;
 
     let response = Response::new(rpc::InstanceList { instances });
 
@@ -622,6 +627,7 @@ pub(crate) async fn release(
     request: Request<rpc::InstanceReleaseRequest>,
 ) -> Result<Response<rpc::InstanceReleaseResult>, Status> {
     log_request_data(&request);
+    let audit_log = AuditLogRecorder::from_request(&request);
     let delete_instance = DeleteInstance::try_from(request.into_inner())?;
 
     let mut txn = api.txn_begin().await?;
@@ -696,7 +702,19 @@ pub(crate) async fn release(
     // we convert this case of the DatabaseError into NotFound too.
     db::instance::mark_as_deleted(delete_instance.instance_id, &mut txn).await?;
 
+    db::instance_event::persist(
+        &mut txn,
+        instance.id,
+        &instance.config.tenant.tenant_organization_id,
+        &InstanceEventPayload::Release {
+            issue: delete_instance.issue.as_ref().map(Into::into),
+        },
+    )
+    .await?;
+
+    audit_log.record_success(&mut txn).await?;
     txn.commit().await?;
+    api.instance_event_notifier.notify();
 
     Ok(Response::new(rpc::InstanceReleaseResult {}))
 }
@@ -725,7 +743,16 @@ pub(crate) async fn update_phone_home_last_contact(
 
     let res = db::instance::update_phone_home_last_contact(&mut txn, instance.id).await?;
 
+    db::instance_event::persist(
+        &mut txn,
+        instance.id,
+        &instance.config.tenant.tenant_organization_id,
+        &InstanceEventPayload::PhoneHome,
+    )
+    .await?;
+
     txn.commit().await?;
+    api.instance_event_notifier.notify();
 
     Ok(Response::new(rpc::InstancePhoneHomeLastContactResponse {
         timestamp: Some(res.into()),
@@ -795,6 +822,14 @@ pub(crate) async fn invoke_power(
     // Log tenant organization ID
     if let Some(ref instance) = snapshot.instance {
         log_tenant_organization_id(instance.config.tenant.tenant_organization_id.as_str());
+
+        db::instance_event::persist(
+            &mut txn,
+            instance.id,
+            &instance.config.tenant.tenant_organization_id,
+            &InstanceEventPayload::from(&request),
+        )
+        .await?;
     }
 
     let bmc_ip =
@@ -904,6 +939,7 @@ pub(crate) async fn invoke_power(
     }
 
     txn.commit().await?;
+    api.instance_event_notifier.notify();
 
     if reprovision_handled {
         // Host will reboot once DPU reprovisioning is successfully finished.
@@ -1040,6 +1076,7 @@ pub(crate) async fn update_instance_config(
     request: tonic::Request<rpc::InstanceConfigUpdateRequest>,
 ) -> Result<tonic::Response<rpc::Instance>, Status> {
     log_request_data(&request);
+    let audit_log = AuditLogRecorder::from_request(&request);
 
     let request = request.into_inner();
 
@@ -1253,6 +1290,7 @@ pub(crate) async fn update_instance_config(
     })?;
     let instance = snapshot_to_instance(mh_snapshot)?;
 
+    audit_log.record_success(&mut txn).await?;
     txn.commit().await?;
 
     Ok(Response::new(instance))
@@ -1552,7 +1590,19 @@ pub async fn force_delete_instance(
     .await
     .map_err(|e| CarbideError::internal(e.to_string()))?;
 
+    db::instance_event::persist(
+        &mut txn,
+        instance_id,
+        &instance.config.tenant.tenant_organization_id,
+        &InstanceEventPayload::StateTransition {
+            tenant_state: TenantState::Terminated,
+            machine_state: snapshot.managed_state.to_string(),
+        },
+    )
+    .await?;
+
     txn.commit().await?;
+    api.instance_event_notifier.notify();
 
     Ok(())
 }
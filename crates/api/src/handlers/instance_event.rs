@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use ::rpc::forge as rpc;
+use model::instance::event::{InstanceEvent, InstanceEventResumeToken, InstanceEventScope};
+use sqlx::PgPool;
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{
+    Api, InstanceEventStreamType, TransactionVending, log_request_data, log_tenant_organization_id,
+};
+
+/// The maximum amount of events which are loaded from the database at once
+const EVENT_BATCH_SIZE: usize = 100;
+
+/// Interval in which open streams check for new events even without being
+/// notified. This picks up events which had been recorded by other
+/// carbide-api instances.
+const EVENT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Streams the lifecycle events of a single instance or of all instances of a tenant.
+///
+/// The stream first replays all retained events after the provided resume token,
+/// and afterwards forwards new events as they get recorded. The stream ends
+/// once the client disconnects.
+pub(crate) async fn stream_events(
+    api: &Api,
+    request: Request<rpc::InstanceEventStreamRequest>,
+) -> Result<Response<InstanceEventStreamType>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let scope = InstanceEventScope::try_from(
+        request
+            .scope
+            .ok_or(CarbideError::MissingArgument("scope"))?,
+    )
+    .map_err(CarbideError::from)?;
+    if let InstanceEventScope::Tenant(tenant_organization_id) = &scope {
+        log_tenant_organization_id(tenant_organization_id.as_str());
+    }
+
+    // Subscribe before determining the starting point, so that no
+    // notification for events after that point can get lost.
+    let notifications = api.instance_event_notifier.subscribe();
+
+    let cursor = match request.resume_token {
+        Some(token) => token
+            .parse::<InstanceEventResumeToken>()
+            .map_err(CarbideError::from)?,
+        None => {
+            let mut txn = api.txn_begin().await?;
+            let token = db::instance_event::latest_resume_token(&mut txn).await?;
+            txn.commit().await?;
+            token
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel(EVENT_BATCH_SIZE);
+    tokio::spawn(forward_events(
+        api.database_connection.clone(),
+        scope,
+        cursor,
+        notifications,
+        sender,
+    ));
+
+    Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+}
+
+/// Forwards all events after `cursor` to the client until it disconnects
+async fn forward_events(
+    db_pool: PgPool,
+    scope: InstanceEventScope,
+    mut cursor: InstanceEventResumeToken,
+    mut notifications: watch::Receiver<u64>,
+    sender: mpsc::Sender<Result<rpc::InstanceEvent, Status>>,
+) {
+    loop {
+        notifications.borrow_and_update();
+
+        let events = match load_events(&db_pool, &scope, cursor).await {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load instance events");
+                let _ = sender.send(Err(e.into())).await;
+                return;
+            }
+        };
+
+        let more_events_pending = events.len() == EVENT_BATCH_SIZE;
+        for event in events {
+            cursor = event.resume_token();
+            let message = rpc::InstanceEvent::try_from(event)
+                .map_err(|e| Status::from(CarbideError::from(e)));
+            if sender.send(message).await.is_err() {
+                // The client disconnected
+                return;
+            }
+        }
+        if more_events_pending {
+            continue;
+        }
+
+        tokio::select! {
+            _ = sender.closed() => return,
+            Ok(()) = notifications.changed() => {}
+            _ = tokio::time::sleep(EVENT_POLL_INTERVAL) => {}
+        }
+    }
+}
+
+async fn load_events(
+    db_pool: &PgPool,
+    scope: &InstanceEventScope,
+    cursor: InstanceEventResumeToken,
+) -> Result<Vec<InstanceEvent>, CarbideError> {
+    let mut txn = db_pool.txn_begin().await?;
+    let events =
+        db::instance_event::find_after(&mut txn, scope, cursor, EVENT_BATCH_SIZE as i64).await?;
+    txn.commit().await?;
+    Ok(events)
+}
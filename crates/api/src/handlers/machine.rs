@@ -33,6 +33,7 @@ use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_machine_id, log_request_data};
+use crate::audit_log::AuditLogRecorder;
 use crate::handlers::utils::convert_and_log_machine_id;
 use crate::redfish::RedfishAuth;
 
@@ -271,6 +272,7 @@ pub(crate) async fn admin_force_delete_machine(
     request: Request<rpc::AdminForceDeleteMachineRequest>,
 ) -> Result<Response<rpc::AdminForceDeleteMachineResponse>, Status> {
     log_request_data(&request);
+    let audit_log = AuditLogRecorder::from_request(&request);
 
     let request = request.into_inner();
     let query = request.host_query;
@@ -626,6 +628,9 @@ pub(crate) async fn admin_force_delete_machine(
         }
     }
 
+    // If clearing the BMC credentials fails afterwards, the failure is
+    // recorded as an additional entry
+    audit_log.record_success(&mut txn).await?;
     txn.commit().await?;
 
     // Do BMC operations outside a transaction to avoid long-running transactions
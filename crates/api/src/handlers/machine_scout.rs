@@ -241,6 +241,17 @@ pub(crate) async fn forge_agent_control(
             ManagedHostState::Measuring {
                 measuring_state: MeasuringState::WaitingForMeasurements,
             } => (Action::Measure, None, Some(txn)),
+            // Hosts which are due for measured boot re-attestation are asked
+            // for fresh measurements while they are Ready.
+            ManagedHostState::Ready if api.runtime_config.attestation_enabled => {
+                let action = crate::attestation::policy::measured_boot_scout_action(
+                    &mut txn,
+                    &api.runtime_config.attestation_policy,
+                    &machine_id,
+                )
+                .await?;
+                (action, None, Some(txn))
+            }
             ManagedHostState::WaitingForCleanup {
                 cleanup_state: CleanupState::HostCleanup { .. },
             }
@@ -267,6 +278,13 @@ pub(crate) async fn forge_agent_control(
                     (Action::Reset, None, Some(txn))
                 }
             }
+            ManagedHostState::HostInit {
+                machine_state: MachineState::MlxConfigSync,
+            } => {
+                let (action, action_data) =
+                    crate::mlxconfig_policy::process_scout_req(&mut txn, &host_machine).await?;
+                (action, action_data, Some(txn))
+            }
             ManagedHostState::BomValidating {
                 bom_validating_state: BomValidating::UpdatingInventory(_),
             } => {
@@ -24,6 +24,7 @@ use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_machine_id, log_request_data};
+use crate::audit_log::AuditLogRecorder;
 use crate::handlers::utils::convert_and_log_machine_id;
 
 // This is a work-around for FORGE-7085.  Due to an issue with interface reporting in the host BMC
@@ -214,6 +215,7 @@ pub(crate) async fn set_maintenance(
     request: Request<rpc::MaintenanceRequest>,
 ) -> Result<Response<()>, Status> {
     log_request_data(&request);
+    let audit_log = AuditLogRecorder::from_request(&request);
     let req = request.into_inner();
     let machine_id = convert_and_log_machine_id(req.host_id.as_ref())?;
 
@@ -245,9 +247,7 @@ pub(crate) async fn set_maintenance(
             }
 
             // Maintenance mode is implemented as a host health override
-            crate::handlers::health::insert_health_report_override(
-                api,
-                Request::new(rpc::InsertHealthReportOverrideRequest {
+            let mut override_request = Request::new(rpc::InsertHealthReportOverrideRequest {
                     machine_id: req.host_id,
                     r#override: Some(::rpc::forge::HealthReportOverride {
                         report: Some(health_report::HealthReport {
@@ -269,9 +269,10 @@ pub(crate) async fn set_maintenance(
                                      .into()),
                         mode: ::rpc::forge::OverrideMode::Merge.into(),
                     }),
-                }),
-            )
-                .await?;
+                });
+            // Record the call in the transaction which inserts the override
+            audit_log.attach_to(&mut override_request);
+            crate::handlers::health::insert_health_report_override(api, override_request).await?;
         }
         rpc::MaintenanceOperation::Disable => {
             for dpu_machine in dpu_machines.iter() {
@@ -283,14 +284,13 @@ pub(crate) async fn set_maintenance(
                 }
             }
 
-            match crate::handlers::health::remove_health_report_override(
-                api,
-                Request::new(rpc::RemoveHealthReportOverrideRequest {
-                    machine_id: req.host_id,
-                    source: "maintenance".to_string(),
-                }),
-            )
-            .await
+            let mut override_request = Request::new(rpc::RemoveHealthReportOverrideRequest {
+                machine_id: req.host_id,
+                source: "maintenance".to_string(),
+            });
+            audit_log.attach_to(&mut override_request);
+            match crate::handlers::health::remove_health_report_override(api, override_request)
+                .await
             {
                 Ok(_) => (),
                 Err(status) if status.code() == tonic::Code::NotFound => (),
@@ -210,6 +210,15 @@ pub async fn match_report(
         .map(Response::new)
 }
 
+pub async fn diff_report(
+    api: &Api,
+    request: Request<pb::DiffMeasurementReportRequest>,
+) -> Result<Response<pb::DiffMeasurementReportResponse>, Status> {
+    report::handle_diff_measurement_report(api, request.into_inner())
+        .await
+        .map(Response::new)
+}
+
 pub async fn create_bundle(
     api: &Api,
     request: Request<pb::CreateMeasurementBundleRequest>,
@@ -15,13 +15,18 @@
  * limitations under the License.
  */
 
+use std::collections::HashSet;
+
 use ::rpc::forge::{scout_stream_api_bound_message, scout_stream_scout_bound_message};
 use ::rpc::protos::forge::ScoutStreamScoutBoundMessage;
 use ::rpc::protos::mlx_device;
 use carbide_uuid::machine::MachineId;
+use config_version::ConfigVersion;
 use libmlx::profile::serialization::SerializableProfile;
+use model::mlxconfig_profile::{MlxConfigProfileBinding, MlxConfigProfileTarget};
 use tonic::{Request, Response, Status};
 
+use crate::CarbideError;
 use crate::api::{Api, log_request_data};
 use crate::handlers::utils::convert_and_log_machine_id;
 
@@ -38,13 +43,13 @@ pub async fn profile_sync(
     Ok(Response::new(response))
 }
 
-pub fn profile_show(
+pub async fn profile_show(
     api: &Api,
     request: Request<mlx_device::MlxAdminProfileShowRequest>,
 ) -> Result<Response<mlx_device::MlxAdminProfileShowResponse>, Status> {
     log_request_data(&request);
     let request = request.into_inner();
-    let response = handle_profile_show(api, request.profile_name)?;
+    let response = handle_profile_show(api, request.profile_name).await?;
     Ok(Response::new(response))
 }
 
@@ -60,15 +65,143 @@ pub async fn profile_compare(
     Ok(Response::new(response))
 }
 
-pub fn profile_list(
+pub async fn profile_list(
     api: &Api,
     request: Request<mlx_device::MlxAdminProfileListRequest>,
 ) -> Result<Response<mlx_device::MlxAdminProfileListResponse>, Status> {
     log_request_data(&request);
-    let response = handle_profile_list(api)?;
+    let response = handle_profile_list(api).await?;
     Ok(Response::new(response))
 }
 
+pub async fn profile_create(
+    api: &Api,
+    request: Request<mlx_device::MlxAdminProfileCreateRequest>,
+) -> Result<Response<mlx_device::MlxAdminProfileCreateResponse>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    let profile = validated_profile(request.serializable_profile)?;
+
+    let mut txn = api.txn_begin().await?;
+    let stored = db::mlxconfig_profile::create(&mut txn, &profile).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(mlx_device::MlxAdminProfileCreateResponse {
+        profile: Some((&stored).into()),
+    }))
+}
+
+pub async fn profile_update(
+    api: &Api,
+    request: Request<mlx_device::MlxAdminProfileUpdateRequest>,
+) -> Result<Response<mlx_device::MlxAdminProfileUpdateResponse>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+    let profile = validated_profile(request.serializable_profile)?;
+
+    let mut txn = api.txn_begin().await?;
+    let current = db::mlxconfig_profile::find_by_name(txn.as_mut(), &profile.name)
+        .await?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "MlxConfigProfile",
+            id: profile.name.clone(),
+        })?;
+
+    let expected_version = match request.if_version_match {
+        Some(version) => version
+            .parse::<ConfigVersion>()
+            .map_err(CarbideError::from)?,
+        None => current.version,
+    };
+    if expected_version != current.version {
+        return Err(CarbideError::ConcurrentModificationError(
+            "MlxConfigProfile",
+            expected_version.to_string(),
+        )
+        .into());
+    }
+
+    let stored = db::mlxconfig_profile::update(&mut txn, &profile, expected_version).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(mlx_device::MlxAdminProfileUpdateResponse {
+        profile: Some((&stored).into()),
+    }))
+}
+
+pub async fn profile_delete(
+    api: &Api,
+    request: Request<mlx_device::MlxAdminProfileDeleteRequest>,
+) -> Result<Response<mlx_device::MlxAdminProfileDeleteResponse>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+    db::mlxconfig_profile::delete(&mut txn, &request.profile_name).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(mlx_device::MlxAdminProfileDeleteResponse {}))
+}
+
+pub async fn profile_bind(
+    api: &Api,
+    request: Request<mlx_device::MlxAdminProfileBindRequest>,
+) -> Result<Response<mlx_device::MlxAdminProfileBindResponse>, Status> {
+    log_request_data(&request);
+    let binding: MlxConfigProfileBinding = request
+        .into_inner()
+        .binding
+        .ok_or(CarbideError::MissingArgument("binding"))?
+        .try_into()
+        .map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    let binding = db::mlxconfig_profile::bind(&mut txn, &binding).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(mlx_device::MlxAdminProfileBindResponse {
+        binding: Some(binding.into()),
+    }))
+}
+
+pub async fn profile_unbind(
+    api: &Api,
+    request: Request<mlx_device::MlxAdminProfileUnbindRequest>,
+) -> Result<Response<mlx_device::MlxAdminProfileUnbindResponse>, Status> {
+    log_request_data(&request);
+    let target: MlxConfigProfileTarget = request
+        .into_inner()
+        .target
+        .ok_or(CarbideError::MissingArgument("target"))?
+        .try_into()
+        .map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    if !db::mlxconfig_profile::unbind(&mut txn, &target).await? {
+        return Err(CarbideError::NotFoundError {
+            kind: "MlxConfigProfileBinding",
+            id: target.to_string(),
+        }
+        .into());
+    }
+    txn.commit().await?;
+
+    Ok(Response::new(mlx_device::MlxAdminProfileUnbindResponse {}))
+}
+
+pub async fn profile_binding_list(
+    api: &Api,
+    request: Request<mlx_device::MlxAdminProfileBindingListRequest>,
+) -> Result<Response<mlx_device::MlxAdminProfileBindingListResponse>, Status> {
+    log_request_data(&request);
+    let bindings = db::mlxconfig_profile::find_bindings(&api.database_connection).await?;
+    Ok(Response::new(
+        mlx_device::MlxAdminProfileBindingListResponse {
+            bindings: bindings.into_iter().map(Into::into).collect(),
+        },
+    ))
+}
+
 pub async fn lockdown_lock(
     api: &Api,
     request: Request<mlx_device::MlxAdminLockdownLockRequest>,
@@ -218,18 +351,36 @@ pub async fn config_compare(
     Ok(Response::new(response))
 }
 
-// handle_profile_sync is an internal helper method for handling a profile sync call.
-async fn handle_profile_sync(
-    api: &Api,
-    machine_id: MachineId,
-    device_id: String,
-    profile_name: String,
-) -> Result<mlx_device::MlxAdminProfileSyncResponse, Status> {
-    // Check if the machine is connected.
-    if !api.scout_stream_registry.is_connected(machine_id).await {
-        return Err(Status::not_found(format!(
-            "scout agent on machine is not connected: {machine_id}"
-        )));
+// validated_profile converts a profile from an admin request into a SerializableProfile,
+// making sure it resolves against a known registry and passes validation before it
+// gets stored.
+fn validated_profile(
+    profile_pb: Option<mlx_device::SerializableMlxConfigProfile>,
+) -> Result<SerializableProfile, CarbideError> {
+    let profile_pb = profile_pb.ok_or(CarbideError::MissingArgument("serializable_profile"))?;
+    let profile: SerializableProfile = profile_pb.try_into().map_err(|e| {
+        CarbideError::InvalidArgument(format!("failed to parse mlxconfig profile: {e}"))
+    })?;
+    if profile.name.is_empty() {
+        return Err(CarbideError::MissingArgument("serializable_profile.name"));
+    }
+
+    profile
+        .clone()
+        .into_profile()
+        .and_then(|p| p.validate())
+        .map_err(|e| CarbideError::InvalidArgument(format!("invalid mlxconfig profile: {e}")))?;
+
+    Ok(profile)
+}
+
+// find_profile looks up a profile by name. Profiles stored in the database take
+// precedence over profiles loaded from the static runtime config.
+async fn find_profile(api: &Api, profile_name: &str) -> Result<SerializableProfile, Status> {
+    if let Some(stored) =
+        db::mlxconfig_profile::find_by_name(&api.database_connection, profile_name).await?
+    {
+        return Ok(stored.profile);
     }
 
     // Check if mlxconfig profiles are configured.
@@ -237,19 +388,36 @@ async fn handle_profile_sync(
         .runtime_config
         .mlxconfig_profiles
         .as_ref()
-        .ok_or_else(|| Status::not_found("no mlxconfig profiles are configured"))?;
+        .ok_or_else(|| Status::not_found(format!("mlxconfig profile not found: {profile_name}")))?;
 
     // Get the profile from the loaded profiles.
     let profile = profiles
-        .get(&profile_name)
+        .get(profile_name)
         .ok_or_else(|| Status::not_found(format!("mlxconfig profile not found: {profile_name}")))?;
 
-    // Convert MlxConfigProfile to SerializableProfile, then to JSON.
-    let serializable_profile = SerializableProfile::from_profile(profile).map_err(|e| {
+    // Convert MlxConfigProfile to SerializableProfile.
+    SerializableProfile::from_profile(profile).map_err(|e| {
         Status::internal(format!(
             "failed to convert mlxconfig profile to serializable profile: {e}"
         ))
-    })?;
+    })
+}
+
+// handle_profile_sync is an internal helper method for handling a profile sync call.
+async fn handle_profile_sync(
+    api: &Api,
+    machine_id: MachineId,
+    device_id: String,
+    profile_name: String,
+) -> Result<mlx_device::MlxAdminProfileSyncResponse, Status> {
+    // Check if the machine is connected.
+    if !api.scout_stream_registry.is_connected(machine_id).await {
+        return Err(Status::not_found(format!(
+            "scout agent on machine is not connected: {machine_id}"
+        )));
+    }
+
+    let serializable_profile = find_profile(api, &profile_name).await?;
 
     let serializable_profile_pb: mlx_device::SerializableMlxConfigProfile =
         serializable_profile.try_into().map_err(|e| {
@@ -316,28 +484,11 @@ async fn handle_profile_sync(
 }
 
 // handle_profile_show is a helper method for returning an MlxConfigProfile.
-fn handle_profile_show(
+async fn handle_profile_show(
     api: &Api,
     profile_name: String,
 ) -> Result<mlx_device::MlxAdminProfileShowResponse, Status> {
-    // Check if mlxconfig profiles are configured.
-    let profiles = api
-        .runtime_config
-        .mlxconfig_profiles
-        .as_ref()
-        .ok_or_else(|| Status::not_found("no mlxconfig profiles are configured"))?;
-
-    // Get the profile from the loaded profiles.
-    let profile = profiles
-        .get(&profile_name)
-        .ok_or_else(|| Status::not_found(format!("mlxconfig profile not found: {profile_name}")))?;
-
-    // Convert MlxConfigProfile to SerializableProfile, then to JSON.
-    let serializable = SerializableProfile::from_profile(profile).map_err(|e| {
-        Status::internal(format!(
-            "failed to convert mlxconfig profile to serializable profile: {e}"
-        ))
-    })?;
+    let serializable = find_profile(api, &profile_name).await?;
 
     let serializable_profile_pb = serializable.try_into().map_err(|e| {
         Status::internal(format!("failed to serialize serializable profile pb: {e}"))
@@ -362,24 +513,7 @@ async fn handle_profile_compare(
         )));
     }
 
-    // Check if mlxconfig profiles are configured.
-    let profiles = api
-        .runtime_config
-        .mlxconfig_profiles
-        .as_ref()
-        .ok_or_else(|| Status::not_found("no mlxconfig profiles are configured"))?;
-
-    // Get the profile from the loaded profiles.
-    let profile = profiles
-        .get(&profile_name)
-        .ok_or_else(|| Status::not_found(format!("mlxconfig profile not found: {profile_name}")))?;
-
-    // Convert MlxConfigProfile to SerializableProfile, then to protobuf.
-    let serializable = SerializableProfile::from_profile(profile).map_err(|e| {
-        Status::internal(format!(
-            "failed to convert mlxconfig profile to serializable profile: {e}"
-        ))
-    })?;
+    let serializable = find_profile(api, &profile_name).await?;
 
     let serializable_profile_pb = serializable.try_into().map_err(|e| {
         Status::internal(format!("failed to serialize serializable profile pb: {e}"))
@@ -443,23 +577,28 @@ async fn handle_profile_compare(
 }
 
 // handle_profile_list is a helper method for listing profiles.
-fn handle_profile_list(api: &Api) -> Result<mlx_device::MlxAdminProfileListResponse, Status> {
-    // Check if mlxconfig profiles are configured.
-    let profiles = api
-        .runtime_config
-        .mlxconfig_profiles
-        .as_ref()
-        .ok_or_else(|| Status::not_found("no mlxconfig profiles are configured"))?;
-
-    let profile_list: Vec<mlx_device::ProfileSummary> = profiles
-        .iter()
-        .map(|(name, profile)| mlx_device::ProfileSummary {
-            name: name.clone(),
-            description: profile.description.clone(),
-            registry_name: profile.registry.name.clone(),
-            variable_count: profile.config_values.len() as u32,
-        })
-        .collect();
+// Profiles stored in the database shadow static profiles with the same name.
+async fn handle_profile_list(api: &Api) -> Result<mlx_device::MlxAdminProfileListResponse, Status> {
+    let stored = db::mlxconfig_profile::find_all(&api.database_connection).await?;
+    let stored_names: HashSet<&str> = stored.iter().map(|p| p.name.as_str()).collect();
+
+    let static_profiles = api.runtime_config.mlxconfig_profiles.iter().flatten();
+    if stored.is_empty() && api.runtime_config.mlxconfig_profiles.is_none() {
+        return Err(Status::not_found("no mlxconfig profiles are configured"));
+    }
+
+    let mut profile_list: Vec<mlx_device::ProfileSummary> = stored.iter().map(Into::into).collect();
+    profile_list.extend(
+        static_profiles
+            .filter(|(name, _)| !stored_names.contains(name.as_str()))
+            .map(|(name, profile)| mlx_device::ProfileSummary {
+                name: name.clone(),
+                description: profile.description.clone(),
+                registry_name: profile.registry.name.clone(),
+                variable_count: profile.config_values.len() as u32,
+                version: None,
+            }),
+    );
 
     Ok(mlx_device::MlxAdminProfileListResponse {
         profiles: profile_list,
@@ -17,9 +17,11 @@
 
 pub mod api;
 pub mod attestation;
+pub mod audit_log;
 pub mod bmc_endpoint_explorer;
 pub mod bmc_metadata;
 pub mod boot_override;
+pub mod console_access;
 pub mod credential;
 pub mod db;
 pub mod dns;
@@ -35,10 +37,12 @@ pub mod extension_service;
 pub mod finder;
 pub mod firmware;
 pub mod health;
+pub mod health_shard;
 pub mod host_reprovisioning;
 pub mod ib_fabric;
 pub mod ib_partition;
 pub mod instance;
+pub mod instance_event;
 pub mod instance_type;
 pub mod logical_partition;
 pub mod machine;
@@ -69,6 +73,7 @@ pub mod site_explorer;
 pub mod sku;
 pub mod switch;
 pub mod tenant;
+pub mod tenant_dns_record;
 pub mod tenant_keyset;
 pub mod tpm_ca;
 pub mod uefi;
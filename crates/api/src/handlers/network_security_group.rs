@@ -24,9 +24,12 @@ use carbide_uuid::network_security_group::NetworkSecurityGroupId;
 use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
 use db::network_security_group;
+use model::instance::config::network::InterfaceFunctionId;
 use model::metadata::Metadata;
-use model::network_security_group::{NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet};
+use model::network_security_group::NetworkSecurityGroupRule;
+use model::network_security_group::flow::NetworkSecurityGroupFlow;
 use model::tenant::{InvalidTenantOrg, TenantOrganizationId};
+use sqlx::PgConnection;
 use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
@@ -100,6 +103,11 @@ pub(crate) async fn create(
     // Start a new transaction for a db write.
     let mut txn = api.txn_begin().await?;
 
+    // Object references can only be validated and expanded with
+    // the current state of the referenced objects.
+    let expanded_rule_count =
+        validate_object_references(&mut txn, &tenant_organization_id, &rules, max_nsg_size).await?;
+
     // Write a new NetworkSecurityGroup to the DB and get back
     // our new NetworkSecurityGroup.
     let network_security_group = network_security_group::create(
@@ -113,6 +121,17 @@ pub(crate) async fn create(
     )
     .await?;
 
+    if let Some(expanded_rule_count) = expanded_rule_count {
+        network_security_group::set_expansion_status(
+            &mut txn,
+            &network_security_group.id,
+            &network_security_group.version,
+            expanded_rule_count,
+            None,
+        )
+        .await?;
+    }
+
     // Prepare the response to send back
     let rpc_out = rpc::CreateNetworkSecurityGroupResponse {
         network_security_group: Some(network_security_group.try_into()?),
@@ -375,6 +394,9 @@ pub(crate) async fn update(
         }
     };
 
+    let expanded_rule_count =
+        validate_object_references(&mut txn, &tenant_organization_id, &rules, max_nsg_size).await?;
+
     // Update record in the DB and get back
     // our new NetworkSecurityGroup state.
     let network_security_group = network_security_group::update(
@@ -389,6 +411,17 @@ pub(crate) async fn update(
     )
     .await?;
 
+    if let Some(expanded_rule_count) = expanded_rule_count {
+        network_security_group::set_expansion_status(
+            &mut txn,
+            &network_security_group.id,
+            &network_security_group.version,
+            expanded_rule_count,
+            None,
+        )
+        .await?;
+    }
+
     // Prepare the response to send back
     let rpc_out = rpc::UpdateNetworkSecurityGroupResponse {
         network_security_group: Some(network_security_group.try_into()?),
@@ -629,6 +662,98 @@ pub(crate) async fn get_attachments(
     Ok(Response::new(rpc_out))
 }
 
+pub(crate) async fn explain_flow(
+    api: &Api,
+    request: Request<rpc::ExplainNetworkSecurityGroupFlowRequest>,
+) -> Result<Response<rpc::ExplainNetworkSecurityGroupFlowResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    let instance_id =
+        req.instance_id
+            .ok_or(CarbideError::from(RpcDataConversionError::MissingArgument(
+                "instance_id",
+            )))?;
+
+    let flow = NetworkSecurityGroupFlow::try_from(&req).map_err(CarbideError::from)?;
+
+    let function_id = match req.virtual_function_id {
+        None => InterfaceFunctionId::Physical {},
+        Some(id) => u8::try_from(id)
+            .ok()
+            .and_then(|id| InterfaceFunctionId::try_virtual_from(id).ok())
+            .ok_or(CarbideError::from(
+                RpcDataConversionError::InvalidVirtualFunctionId(id as usize),
+            ))?,
+    };
+
+    let mut txn = api.txn_begin().await?;
+
+    let instance = db::instance::find_by_id(&mut txn, instance_id)
+        .await?
+        .ok_or(CarbideError::NotFoundError {
+            kind: "instance",
+            id: instance_id.to_string(),
+        })?;
+
+    let interface = instance
+        .config
+        .network
+        .interfaces
+        .iter()
+        .find(|i| i.function_id == function_id)
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "instance interface",
+            id: format!("{instance_id} {function_id:?}"),
+        })?;
+
+    // An NSG on the instance overrides the NSG of the VPC
+    // for all interfaces, just like when the DPU config is built.
+    let nsg_details = match instance.config.network_security_group_id.as_ref() {
+        Some(nsg_id) => Some((
+            rpc::NetworkSecurityGroupSource::NsgSourceInstance,
+            nsg_id.to_owned(),
+        )),
+        None => match interface.network_segment_id {
+            None => None,
+            Some(segment_id) => db::vpc::find_by_segment(&mut txn, segment_id)
+                .await?
+                .network_security_group_id
+                .map(|nsg_id| (rpc::NetworkSecurityGroupSource::NsgSourceVpc, nsg_id)),
+        },
+    };
+
+    let nsg = match nsg_details {
+        None => None,
+        Some((source, nsg_id)) => Some((
+            source,
+            network_security_group::find_by_ids(&mut txn, &[nsg_id.clone()], None, false)
+                .await?
+                .pop()
+                .ok_or(CarbideError::NotFoundError {
+                    kind: "NetworkSecurityGroup",
+                    id: nsg_id.to_string(),
+                })?,
+        )),
+    };
+
+    let rpc_out = crate::network_security_group::explain_flow(
+        &mut txn,
+        &api.runtime_config.network_security_group.policy_overrides,
+        nsg.as_ref().map(|(source, nsg)| (*source, nsg)),
+        &flow,
+        api.runtime_config
+            .network_security_group
+            .max_network_security_group_size as usize,
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc_out))
+}
+
 fn validate_expanded_rule_set(
     rules: &[NetworkSecurityGroupRule],
     limit: usize,
@@ -651,35 +776,61 @@ fn validate_expanded_rule_set(
             )));
         }
 
-        match (&rule.src_net, &rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(_), NetworkSecurityGroupRuleNet::Prefix(_)) => {
-                // Negative ranges are caught when we convert from rpc to internal struct.
-                // so we can keep this simple.
-                let rule_count = (rule.src_port_end.unwrap_or_default()
-                    - rule.src_port_start.unwrap_or_default()
-                    + 1)
-                .saturating_mul(
-                    rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default()
-                        + 1,
-                );
-
-                total_rules = match total_rules.overflowing_add(rule_count) {
-                    (_, true) => {
-                        return Err(CarbideError::InvalidArgument(format!(
-                            "expanded rule set contains more than {limit} maximum number of rules"
-                        )));
-                    }
-                    (v, false) => v,
-                };
-
-                if total_rules as usize > limit {
-                    return Err(CarbideError::InvalidArgument(format!(
-                        "expanded rule set contains more than {limit} maximum number of rules"
-                    )));
-                }
+        // Rules with object references are counted once they
+        // are expanded, see validate_object_references.
+        if rule.src_net.is_object_reference() || rule.dst_net.is_object_reference() {
+            continue;
+        }
+
+        // Negative ranges are caught when we convert from rpc to internal struct.
+        // so we can keep this simple.
+        let rule_count = (rule.src_port_end.unwrap_or_default()
+            - rule.src_port_start.unwrap_or_default()
+            + 1)
+        .saturating_mul(
+            rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default() + 1,
+        );
+
+        total_rules = match total_rules.overflowing_add(rule_count) {
+            (_, true) => {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "expanded rule set contains more than {limit} maximum number of rules"
+                )));
             }
+            (v, false) => v,
+        };
+
+        if total_rules as usize > limit {
+            return Err(CarbideError::InvalidArgument(format!(
+                "expanded rule set contains more than {limit} maximum number of rules"
+            )));
         }
     }
 
     Ok(())
 }
+
+/// Validates the object references of a rule set against the DB and returns
+/// the expanded rule count, or `None` if the rules don't reference objects.
+async fn validate_object_references(
+    txn: &mut PgConnection,
+    tenant_organization_id: &TenantOrganizationId,
+    rules: &[NetworkSecurityGroupRule],
+    limit: usize,
+) -> Result<Option<u64>, CarbideError> {
+    if !rules
+        .iter()
+        .any(|rule| rule.src_net.is_object_reference() || rule.dst_net.is_object_reference())
+    {
+        return Ok(None);
+    }
+
+    crate::network_security_group::validate_object_references(
+        txn,
+        tenant_organization_id,
+        rules,
+        limit,
+    )
+    .await
+    .map(Some)
+}
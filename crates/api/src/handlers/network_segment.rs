@@ -149,6 +149,19 @@ pub(crate) async fn create(
 
     let network_segment = save(api, &mut txn, new_network_segment, false, allocate_svi_ip).await?;
 
+    // Segment prefixes outside of VPC prefixes are part of the expanded
+    // rule set of network security groups that reference the VPC.
+    if let Some(vpc_id) = network_segment.vpc_id {
+        crate::network_security_group::recheck_vpc_references(
+            &mut txn,
+            vpc_id,
+            api.runtime_config
+                .network_security_group
+                .max_network_security_group_size as usize,
+        )
+        .await?;
+    }
+
     let response = Ok(Response::new(network_segment.try_into()?));
     txn.commit().await?;
     response
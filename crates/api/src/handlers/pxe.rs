@@ -15,15 +15,24 @@
  * limitations under the License.
  */
 
+use std::io::SeekFrom;
 use std::net::IpAddr;
 
 use ::rpc::forge as rpc;
 use db;
+use futures_util::{Stream, StreamExt};
+use model::storage::OsImageAttributes;
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, CONTENT_RANGE, RANGE};
+use tokio::io::AsyncSeekExt;
+use tokio_util::io::ReaderStream;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
-use crate::api::{Api, log_request_data};
+use crate::api::{Api, OsImageContentStreamType, log_machine_id, log_request_data};
 use crate::ipxe::PxeInstructions;
+use crate::os_image_download::{DownloadTokenSigner, OsImageDownloadToken};
+use crate::signed_token::SigningKey;
 
 // The carbide pxe server makes this RPC call
 pub(crate) async fn get_pxe_instructions(
@@ -36,7 +45,12 @@ pub(crate) async fn get_pxe_instructions(
 
     let request = request.into_inner().try_into()?;
 
-    let pxe_script = PxeInstructions::get_pxe_instructions(&mut txn, request).await?;
+    let download_token_signer = DownloadTokenSigner::new(
+        api.credential_provider.as_ref(),
+        api.runtime_config.os_image_download.url_lifetime,
+    );
+    let pxe_script =
+        PxeInstructions::get_pxe_instructions(&mut txn, request, &download_token_signer).await?;
 
     txn.commit().await?;
 
@@ -195,3 +209,183 @@ pub(crate) async fn get_cloud_init_instructions(
 
     Ok(Response::new(instructions))
 }
+
+// The carbide pxe server makes this RPC call when a host downloads its OS image.
+// Ingested images are read from the ingestion cache, and all others are streamed
+// from their source.
+pub(crate) async fn get_os_image_content(
+    api: &Api,
+    request: Request<rpc::GetOsImageContentRequest>,
+) -> Result<Response<OsImageContentStreamType>, Status> {
+    // The request isn't logged, since the token grants access to the image
+    let request = request.into_inner();
+
+    let key = SigningKey::fetch(
+        api.credential_provider.as_ref(),
+        crate::os_image_download::KDF_INFO,
+    )
+    .await
+    .map_err(CarbideError::from)?;
+    let token = OsImageDownloadToken::verify(&request.token, &key, chrono::Utc::now())
+        .map_err(|e| Status::permission_denied(format!("Invalid OS image download URL: {e}")))?;
+    log_machine_id(&token.machine_id);
+
+    let client_ip: IpAddr = request.client_ip.parse().map_err(|e| {
+        Status::invalid_argument(format!("Failed parsing IP '{}': {e}", request.client_ip))
+    })?;
+
+    let mut txn = api.txn_begin().await?;
+
+    // The URL is only usable by the host it was handed out to, through the
+    // interface it PXE booted from. The host either uses the address of that
+    // interface, or an address of its instance.
+    let interface = db::machine_interface::find_one(&mut txn, token.interface_id).await?;
+    let is_issued_to_client = interface.machine_id == Some(token.machine_id)
+        && (interface.addresses.contains(&client_ip)
+            || match db::instance_address::find_by_address(&mut txn, client_ip).await? {
+                Some(instance_address) => {
+                    db::instance::find_by_id(&mut txn, instance_address.instance_id)
+                        .await?
+                        .is_some_and(|instance| instance.machine_id == token.machine_id)
+                }
+                None => false,
+            });
+    if !is_issued_to_client {
+        return Err(Status::permission_denied(format!(
+            "OS image download URL was not issued to {client_ip}"
+        )));
+    }
+
+    // And only as long as the instance on the host uses the OS image
+    let uses_os_image = db::instance::find_by_machine_id(&mut txn, &token.machine_id)
+        .await?
+        .is_some_and(|instance| {
+            instance.config.os.variant
+                == model::os::OperatingSystemVariant::OsImage(token.os_image_id)
+        });
+    if !uses_os_image {
+        return Err(Status::permission_denied(format!(
+            "OS image {} is not used by machine {}",
+            token.os_image_id, token.machine_id
+        )));
+    }
+
+    let os_image = db::os_image::get(&mut txn, token.os_image_id).await?;
+
+    txn.commit().await?;
+
+    let path = crate::os_image_ingestion::ingested_image_path(
+        &api.runtime_config.os_image_ingestion.cache_dir,
+        os_image.attributes.id,
+    );
+    let stream = match tokio::fs::File::open(&path).await {
+        Ok(file) => stream_ingested_image(file, request.offset).await?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            stream_image_source(&os_image.attributes, request.offset).await?
+        }
+        Err(e) => {
+            return Err(Status::internal(format!(
+                "Failed to open ingested OS image {}: {e}",
+                os_image.attributes.id
+            )));
+        }
+    };
+
+    Ok(Response::new(stream))
+}
+
+/// Size of the chunks ingested OS images are streamed in
+const OS_IMAGE_CHUNK_SIZE: usize = 256 * 1024;
+
+// The first chunk only carries the size of the image
+fn os_image_content_stream(
+    total_size: Option<u64>,
+    chunks: impl Stream<Item = Result<rpc::OsImageContentChunk, Status>> + Send + 'static,
+) -> OsImageContentStreamType {
+    let header = rpc::OsImageContentChunk {
+        total_size,
+        data: Vec::new(),
+    };
+    Box::pin(futures_util::stream::once(async move { Ok(header) }).chain(chunks))
+}
+
+async fn stream_ingested_image(
+    mut file: tokio::fs::File,
+    offset: u64,
+) -> Result<OsImageContentStreamType, Status> {
+    let read_error = |e: std::io::Error| Status::internal(format!("Failed to read OS image: {e}"));
+
+    let total_size = file.metadata().await.map_err(read_error)?.len();
+    if offset > 0 && offset >= total_size {
+        return Err(Status::out_of_range(format!(
+            "Offset {offset} is beyond the end of the OS image"
+        )));
+    }
+    file.seek(SeekFrom::Start(offset))
+        .await
+        .map_err(read_error)?;
+
+    let chunks = ReaderStream::with_capacity(file, OS_IMAGE_CHUNK_SIZE).map(move |chunk| {
+        chunk
+            .map(|data| rpc::OsImageContentChunk {
+                total_size: None,
+                data: data.to_vec(),
+            })
+            .map_err(read_error)
+    });
+    Ok(os_image_content_stream(Some(total_size), chunks))
+}
+
+async fn stream_image_source(
+    attributes: &OsImageAttributes,
+    offset: u64,
+) -> Result<OsImageContentStreamType, Status> {
+    let mut request = reqwest::Client::new().get(&attributes.source_url);
+    if let Some(auth_token) = &attributes.auth_token {
+        // Same default as the imaging script
+        let auth_type = attributes.auth_type.as_deref().unwrap_or("Bearer");
+        request = request.header(AUTHORIZATION, format!("{auth_type} {auth_token}"));
+    }
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| Status::unavailable(format!("Failed to download OS image: {e}")))?;
+    let total_size = match response.status() {
+        StatusCode::OK if offset == 0 => response.content_length(),
+        StatusCode::PARTIAL_CONTENT => response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|range| range.to_str().ok())
+            .and_then(|range| range.rsplit_once('/'))
+            .and_then(|(_, total_size)| total_size.parse().ok()),
+        StatusCode::OK => {
+            return Err(Status::failed_precondition(
+                "The source of the OS image does not support resuming downloads",
+            ));
+        }
+        StatusCode::RANGE_NOT_SATISFIABLE => {
+            return Err(Status::out_of_range(format!(
+                "Offset {offset} is beyond the end of the OS image"
+            )));
+        }
+        status => {
+            return Err(Status::unavailable(format!(
+                "Download of OS image failed with status {status}"
+            )));
+        }
+    };
+
+    let chunks = response.bytes_stream().map(|chunk| {
+        chunk
+            .map(|data| rpc::OsImageContentChunk {
+                total_size: None,
+                data: data.to_vec(),
+            })
+            .map_err(|e| Status::unavailable(format!("Failed to download OS image: {e}")))
+    });
+    Ok(os_image_content_stream(total_size, chunks))
+}
@@ -20,6 +20,7 @@ use ::rpc::forge as rpc;
 use carbide_uuid::rack::RackId;
 use db::{WithTransaction, rack as db_rack};
 use futures_util::FutureExt;
+use model::rack::{RackMaintenanceState, RackPowerAction, RackPowerSequence, RackState};
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -111,6 +112,95 @@ pub async fn delete_rack(
     Ok(Response::new(()))
 }
 
+/// Requests a power sequence for all devices of a rack. The sequence is run
+/// by the rack state controller.
+pub async fn set_rack_power_state(
+    api: &Api,
+    request: Request<rpc::SetRackPowerStateRequest>,
+) -> Result<Response<()>, Status> {
+    let req = request.into_inner();
+    let rack_id = req
+        .rack_id
+        .ok_or(CarbideError::MissingArgument("rack_id"))?;
+    let action = match req.action() {
+        rpc::RackPowerAction::On => RackPowerAction::On,
+        rpc::RackPowerAction::Off => RackPowerAction::Off,
+        rpc::RackPowerAction::Reset => RackPowerAction::Reset,
+    };
+    let compute_wave_size = match req.compute_wave_size {
+        Some(0) => {
+            return Err(CarbideError::InvalidArgument(
+                "compute_wave_size must be greater than 0".to_string(),
+            )
+            .into());
+        }
+        Some(compute_wave_size) => compute_wave_size as usize,
+        None => {
+            api.runtime_config
+                .rack_state_controller
+                .power_sequence_compute_wave_size
+        }
+    };
+
+    let mut txn = api.txn_begin().await?;
+    let rack = db_rack::get(txn.as_mut(), rack_id)
+        .await
+        .map_err(|e| Status::internal(format!("Getting rack {}", e)))?;
+
+    if let RackState::Maintenance {
+        rack_maintenance:
+            RackMaintenanceState::FirmwareUpgrade { .. } | RackMaintenanceState::PowerSequence { .. },
+    } = rack.controller_state.value
+    {
+        return Err(Status::failed_precondition(format!(
+            "Rack '{}' is in state {} and can not be power sequenced",
+            rack_id, rack.controller_state.value
+        )));
+    }
+    if rack
+        .power_sequence
+        .as_ref()
+        .is_some_and(|sequence| sequence.is_active())
+    {
+        return Err(Status::failed_precondition(format!(
+            "Rack '{}' already has a pending power sequence",
+            rack_id
+        )));
+    }
+
+    let nvlink_switches: Vec<_> =
+        db::expected_switch::find_all_by_rack_id(&mut txn, rack_id.to_string())
+            .await?
+            .into_iter()
+            .map(|switch| switch.bmc_mac_address)
+            .collect();
+    let sequence = RackPowerSequence::plan(
+        action,
+        &rack.config.expected_power_shelves,
+        &nvlink_switches,
+        &rack.config.expected_compute_trays,
+        compute_wave_size,
+    );
+    if sequence.steps.is_empty() {
+        return Err(Status::failed_precondition(format!(
+            "Rack '{}' contains no devices",
+            rack_id
+        )));
+    }
+
+    db_rack::update_power_sequence(&mut txn, rack_id, &sequence).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        rack_id = %rack_id,
+        %action,
+        steps = sequence.steps.len(),
+        "Scheduled rack power sequence"
+    );
+
+    Ok(Response::new(()))
+}
+
 /// List health report overrides for a rack.
 ///
 /// This is a stub - actual implementation TBD.
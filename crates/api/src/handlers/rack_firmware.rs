@@ -21,6 +21,10 @@ use std::sync::Arc;
 use db::DatabaseError;
 use db::rack_firmware::RackFirmware as DbRackFirmware;
 use forge_secrets::credentials::{CredentialKey, CredentialProvider, Credentials};
+use model::rack::{
+    RackDeviceFirmwareUpgrade, RackDeviceFirmwareUpgradeStatus, RackDeviceType,
+    RackFirmwareComponent, RackFirmwareUpgrade, RackMaintenanceState, RackState,
+};
 use rpc::forge::{
     DeviceUpdateResult, RackFirmware, RackFirmwareApplyRequest, RackFirmwareApplyResponse,
     RackFirmwareCreateRequest, RackFirmwareDeleteRequest, RackFirmwareGetRequest, RackFirmwareList,
@@ -903,7 +907,8 @@ async fn download_single_file(
     Ok(())
 }
 
-/// Apply firmware to all devices in a rack
+/// Schedule a firmware upgrade of all devices in a rack. The upgrade is applied
+/// by the rack state controller.
 pub async fn apply(
     api: &Api,
     request: Request<RackFirmwareApplyRequest>,
@@ -912,12 +917,26 @@ pub async fn apply(
     let rack_id = req
         .rack_id
         .ok_or_else(|| Status::invalid_argument("rack_id is required"))?;
+    let max_unavailable = match req.max_unavailable {
+        Some(0) => {
+            return Err(Status::invalid_argument(
+                "max_unavailable must be greater than 0",
+            ));
+        }
+        Some(max_unavailable) => max_unavailable as usize,
+        None => {
+            api.runtime_config
+                .rack_state_controller
+                .firmware_upgrade_max_unavailable
+        }
+    };
 
     tracing::info!(
         rack_id = %rack_id,
         firmware_id = %req.firmware_id,
         firmware_type = %req.firmware_type,
-        "Starting firmware apply operation"
+        max_unavailable,
+        "Scheduling rack firmware upgrade"
     );
 
     // 1. Get the RackFirmware configuration from the database
@@ -941,27 +960,41 @@ pub async fn apply(
             serde_json::json!({})
         });
 
-    let rack = db::rack::get(&api.database_connection, rack_id)
+    let mut txn = api.txn_begin().await?;
+    let rack = db::rack::get(txn.as_mut(), rack_id)
         .await
         .map_err(|e| Status::internal(format!("Failed to get rack: {}", e)))?;
 
-    // Convert rack to proto to get device IDs
-    let rack_proto: rpc::forge::Rack = rack.into();
+    if let RackState::Maintenance {
+        rack_maintenance: RackMaintenanceState::FirmwareUpgrade { .. },
+    } = rack.controller_state.value
+    {
+        return Err(Status::failed_precondition(format!(
+            "Rack '{}' is already upgrading firmware",
+            rack_id
+        )));
+    }
+    if let RackState::Maintenance {
+        rack_maintenance: RackMaintenanceState::PowerSequence { .. },
+    } = rack.controller_state.value
+    {
+        return Err(Status::failed_precondition(format!(
+            "Rack '{}' is running a power sequence",
+            rack_id
+        )));
+    }
 
-    // Collect all devices from the rack
+    // 2. Collect all devices from the rack
     let mut all_devices = Vec::new();
-    for machine_id in rack_proto.compute_trays {
-        all_devices.push((machine_id.to_string(), "Compute Node".to_string()));
+    for machine_id in &rack.config.compute_trays {
+        all_devices.push((machine_id.to_string(), RackDeviceType::ComputeTray));
     }
-    for power_shelf_id in rack_proto.power_shelves {
-        all_devices.push((power_shelf_id.to_string(), "Power Shelf".to_string()));
+    for power_shelf_id in &rack.config.power_shelves {
+        all_devices.push((power_shelf_id.to_string(), RackDeviceType::PowerShelf));
     }
     // TODO: Add switches once nvlink_switches is implemented in RackConfig
     // Currently both nvlink_switches and expected_nvlink_switches are commented out
-    // in the RackConfig struct (api/src/model/rack/mod.rs), so this will always be empty
-    // for switch_id in &rack_proto.expected_nvlink_switches {
-    //     all_devices.push((switch_id.clone(), "Switch Tray".to_string()));
-    // }
+    // in the RackConfig struct (api-model/src/rack.rs), so this will always be empty
 
     if all_devices.is_empty() {
         return Err(Status::failed_precondition(format!(
@@ -976,130 +1009,89 @@ pub async fn apply(
         "Found devices in rack"
     );
 
-    // 3. Apply firmware to each device
+    // 3. Look up the firmware components of each device
     let mut device_results = Vec::new();
-    let mut successful_updates = 0;
-    let mut failed_updates = 0;
-
-    for (device_id, hardware_type) in all_devices {
-        // Find all firmware components for this device type
-        let firmware_components = find_firmware_components_for_device(
+    let mut devices = Vec::new();
+    for (device_id, device_type) in all_devices {
+        let hardware_type = match device_type {
+            RackDeviceType::ComputeTray => "Compute Node",
+            RackDeviceType::Switch => "Switch Tray",
+            RackDeviceType::PowerShelf => "Power Shelf",
+        };
+        let components: Vec<RackFirmwareComponent> = find_firmware_components_for_device(
             &parsed_components,
-            &hardware_type,
+            hardware_type,
             &req.firmware_type,
-        );
+        )
+        .into_iter()
+        .map(|(name, filename, target)| RackFirmwareComponent {
+            name,
+            filename,
+            target,
+        })
+        .collect();
 
-        if firmware_components.is_empty() {
+        if components.is_empty() {
             device_results.push(DeviceUpdateResult {
-                device_id: device_id.clone(),
-                device_type: hardware_type,
+                device_id,
+                device_type: hardware_type.to_string(),
                 success: false,
                 message: "No matching firmware found in config".to_string(),
             });
-            failed_updates += 1;
             continue;
         }
 
-        tracing::info!(
-            device_id = %device_id,
-            hardware_type = %hardware_type,
-            component_count = firmware_components.len(),
-            "Updating device with multiple firmware components"
-        );
-
-        // Apply each firmware component to the device
-        for (component_name, filename, target) in firmware_components {
-            let full_firmware_path = format!(
-                "/forge-boot-artifacts/blobs/internal/fw/rack_firmware/{}/{}",
-                req.firmware_id, filename
-            );
-
-            tracing::debug!(
-                device_id = %device_id,
-                component = %component_name,
-                firmware_path = %full_firmware_path,
-                target = %target,
-                "Updating device firmware component"
-            );
-
-            let Some(rms_client) = &api.rms_client else {
-                tracing::warn!(
-                    device_id = %device_id,
-                    component = %component_name,
-                    "RMS client not configured, cannot update firmware"
-                );
-
-                device_results.push(DeviceUpdateResult {
-                    device_id: device_id.clone(),
-                    device_type: format!("{} ({})", hardware_type, component_name),
-                    success: false,
-                    message: "RMS client not configured".to_string(),
-                });
-                failed_updates += 1;
-                continue;
-            };
-
-            let request = librms::protos::rack_manager::UpdateNodeFirmwareRequest {
-                metadata: None,
-                node_id: device_id.clone(),
-                filename: full_firmware_path.clone(),
-                target: target.clone(),
-                activate: false,
-                rack_id: rack_id.to_string(),
-                ..Default::default()
-            };
-            match rms_client.update_node_firmware(request).await {
-                Ok(response) => {
-                    let status_msg = response.message;
-                    let success =
-                        response.status == librms::protos::rack_manager::ReturnCode::Success as i32;
-
-                    if success {
-                        successful_updates += 1;
-                    } else {
-                        failed_updates += 1;
-                    }
-
-                    device_results.push(DeviceUpdateResult {
-                        device_id: device_id.clone(),
-                        device_type: format!("{} ({})", hardware_type, component_name),
-                        success,
-                        message: status_msg,
-                    });
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        device_id = %device_id,
-                        component = %component_name,
-                        error = %e,
-                        "Failed to update device firmware component"
-                    );
+        device_results.push(DeviceUpdateResult {
+            device_id: device_id.clone(),
+            device_type: hardware_type.to_string(),
+            success: true,
+            message: format!("Scheduled {} firmware components", components.len()),
+        });
+        devices.push(RackDeviceFirmwareUpgrade {
+            device_id,
+            device_type,
+            components,
+            status: RackDeviceFirmwareUpgradeStatus::Pending,
+            message: None,
+            started_at: None,
+            finished_at: None,
+        });
+    }
 
-                    device_results.push(DeviceUpdateResult {
-                        device_id: device_id.clone(),
-                        device_type: format!("{} ({})", hardware_type, component_name),
-                        success: false,
-                        message: format!("RMS API Error: {}", e),
-                    });
-                    failed_updates += 1;
-                }
-            }
-        }
+    let scheduled_updates = devices.len() as i32;
+    let skipped_updates = device_results.len() as i32 - scheduled_updates;
+    if devices.is_empty() {
+        return Err(Status::failed_precondition(format!(
+            "Firmware configuration '{}' contains no firmware for the devices of rack '{}'",
+            req.firmware_id, rack_id
+        )));
     }
 
+    // 4. Hand the upgrade over to the rack state controller
+    let firmware_upgrade = RackFirmwareUpgrade {
+        firmware_id: req.firmware_id.clone(),
+        firmware_type: req.firmware_type.clone(),
+        max_unavailable,
+        requested_at: chrono::Utc::now(),
+        started_at: None,
+        completed_at: None,
+        devices,
+    };
+    db::rack::update_firmware_upgrade(&mut txn, rack_id, &firmware_upgrade).await?;
+    txn.commit().await?;
+
     tracing::info!(
         rack_id = %rack_id,
         firmware_id = %req.firmware_id,
-        successful = successful_updates,
-        failed = failed_updates,
-        total = device_results.len(),
-        "Firmware apply operation completed"
+        scheduled = scheduled_updates,
+        skipped = skipped_updates,
+        "Rack firmware upgrade scheduled"
     );
 
     Ok(Response::new(RackFirmwareApplyResponse {
         total_updates: device_results.len() as i32,
-        successful_updates,
-        failed_updates,
+        successful_updates: scheduled_updates,
+        failed_updates: skipped_updates,
         device_results,
     }))
 }
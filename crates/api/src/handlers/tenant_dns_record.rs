@@ -0,0 +1,215 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::protos::dns::{
+    CreateTenantDnsRecordRequest, DeleteTenantDnsRecordRequest, DeleteTenantDnsRecordResponse,
+    FindTenantDnsRecordsRequest, TenantDnsRecord, TenantDnsRecordList,
+    UpdateTenantDnsRecordRequest,
+};
+use carbide_uuid::domain::{DomainId, TenantDnsRecordId};
+use db::dns::{domain, tenant_record};
+use dns_record::DnsResourceRecordType;
+use model::dns::{Domain, NewTenantDnsRecord};
+use model::tenant::{InvalidTenantOrg, TenantOrganizationId};
+use sqlx::PgConnection;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data, log_tenant_organization_id};
+
+fn parse_tenant_organization_id(id: &str) -> Result<TenantOrganizationId, CarbideError> {
+    log_tenant_organization_id(id);
+    id.parse().map_err(|e: InvalidTenantOrg| {
+        CarbideError::from(RpcDataConversionError::InvalidTenantOrg(e.to_string()))
+    })
+}
+
+fn parse_record_id(id: &str) -> Result<TenantDnsRecordId, CarbideError> {
+    if id.is_empty() {
+        return Err(CarbideError::MissingArgument("id"));
+    }
+    id.parse::<TenantDnsRecordId>().map_err(|e| {
+        CarbideError::from(RpcDataConversionError::InvalidUuid(
+            "TenantDnsRecordId",
+            e.to_string(),
+        ))
+    })
+}
+
+/// Loads a domain the tenant is allowed to manage records in.
+///
+/// See [`domain::is_owned_by_tenant`] for when a domain belongs to a tenant.
+/// Domains of other tenants are reported as not found, same as records.
+async fn find_tenant_domain(
+    txn: &mut PgConnection,
+    domain_id: DomainId,
+    tenant_organization_id: &TenantOrganizationId,
+) -> Result<Domain, CarbideError> {
+    if let Some(domain) = domain::find_by_uuid(txn, domain_id).await?
+        && domain.deleted.is_none()
+        && domain::is_owned_by_tenant(&mut *txn, domain_id, tenant_organization_id).await?
+    {
+        return Ok(domain);
+    }
+
+    Err(CarbideError::NotFoundError {
+        kind: "domain",
+        id: domain_id.to_string(),
+    })
+}
+
+/// Loads a record and makes sure it belongs to the tenant.
+///
+/// Records of other tenants are reported as not found, so that their existence isn't leaked.
+async fn find_tenant_record(
+    txn: &mut PgConnection,
+    id: TenantDnsRecordId,
+    tenant_organization_id: &TenantOrganizationId,
+) -> Result<model::dns::TenantDnsRecord, CarbideError> {
+    tenant_record::find_by_id(&mut *txn, id)
+        .await?
+        .filter(|record| &record.tenant_organization_id == tenant_organization_id)
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "dns_record",
+            id: id.to_string(),
+        })
+}
+
+/// Bumps the SOA serial of a domain so secondaries pick up the record change
+async fn increment_serial(txn: &mut PgConnection, mut domain: Domain) -> Result<(), CarbideError> {
+    domain.ensure_soa_and_increment();
+    domain::update(&mut domain, txn).await?;
+    Ok(())
+}
+
+pub(crate) async fn create(
+    api: &Api,
+    request: Request<CreateTenantDnsRecordRequest>,
+) -> Result<Response<TenantDnsRecord>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let tenant_organization_id = parse_tenant_organization_id(&req.tenant_organization_id)?;
+    let domain_id = req
+        .domain_id
+        .ok_or(CarbideError::MissingArgument("domain_id"))?;
+    if req.name.is_empty() {
+        return Err(CarbideError::MissingArgument("name").into());
+    }
+    let q_type = DnsResourceRecordType::try_from(req.record_type.to_uppercase())
+        .map_err(CarbideError::InvalidArgument)?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let domain = find_tenant_domain(&mut txn, domain_id, &tenant_organization_id).await?;
+
+    let record = tenant_record::persist(
+        &mut txn,
+        &domain.name,
+        NewTenantDnsRecord {
+            domain_id,
+            tenant_organization_id,
+            q_name: req.name,
+            q_type,
+            content: req.content,
+            ttl: req.ttl,
+        },
+    )
+    .await?;
+
+    increment_serial(&mut txn, domain).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+pub(crate) async fn update(
+    api: &Api,
+    request: Request<UpdateTenantDnsRecordRequest>,
+) -> Result<Response<TenantDnsRecord>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let tenant_organization_id = parse_tenant_organization_id(&req.tenant_organization_id)?;
+    let id = parse_record_id(&req.id)?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let record = find_tenant_record(&mut txn, id, &tenant_organization_id).await?;
+    let domain = find_tenant_domain(&mut txn, record.domain_id, &tenant_organization_id).await?;
+
+    let record =
+        tenant_record::update(&mut txn, record.id, record.q_type, &req.content, req.ttl).await?;
+
+    increment_serial(&mut txn, domain).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+pub(crate) async fn delete(
+    api: &Api,
+    request: Request<DeleteTenantDnsRecordRequest>,
+) -> Result<Response<DeleteTenantDnsRecordResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let tenant_organization_id = parse_tenant_organization_id(&req.tenant_organization_id)?;
+    let id = parse_record_id(&req.id)?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let record = find_tenant_record(&mut txn, id, &tenant_organization_id).await?;
+
+    tenant_record::delete(&mut txn, record.id).await?;
+
+    // The domain might no longer belong to the tenant, e.g. because the segment was
+    // deleted. Removing the record is still fine, but the serial only needs to change
+    // if the domain is still served.
+    if let Some(domain) = domain::find_by_uuid(&mut txn, record.domain_id).await?
+        && domain.deleted.is_none()
+    {
+        increment_serial(&mut txn, domain).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(Response::new(DeleteTenantDnsRecordResponse {}))
+}
+
+pub(crate) async fn find(
+    api: &Api,
+    request: Request<FindTenantDnsRecordsRequest>,
+) -> Result<Response<TenantDnsRecordList>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let tenant_organization_id = parse_tenant_organization_id(&req.tenant_organization_id)?;
+
+    let records = tenant_record::find_by_tenant(
+        &api.database_connection,
+        &tenant_organization_id,
+        req.domain_id,
+    )
+    .await?;
+
+    Ok(Response::new(TenantDnsRecordList {
+        records: records.into_iter().map(TenantDnsRecord::from).collect(),
+    }))
+}
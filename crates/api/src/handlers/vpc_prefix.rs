@@ -158,6 +158,18 @@ pub async fn create(
         .await?;
     }
 
+    // The new prefix grows the expanded rule set of every network security
+    // group that references the VPC. Refuse it if any of them would no
+    // longer fit on the DPUs.
+    crate::network_security_group::recheck_vpc_references(
+        &mut txn,
+        vpc_prefix.vpc_id,
+        api.runtime_config
+            .network_security_group
+            .max_network_security_group_size as usize,
+    )
+    .await?;
+
     txn.commit().await?;
 
     Ok(tonic::Response::new(vpc_prefix.into()))
@@ -42,6 +42,7 @@ use model::machine::{
 };
 use model::metadata::Metadata;
 use model::os::OperatingSystemVariant;
+use model::storage::OsImageStatus;
 use model::tenant::TenantOrganizationId;
 use model::vpc_prefix::VpcPrefix;
 use sqlx::PgConnection;
@@ -562,17 +563,26 @@ pub async fn batch_allocate_instances(
                 "Image ID is required for image based storage".to_string(),
             ));
         }
-        if let Err(e) = db::os_image::get(&mut txn, *os_image_id).await {
-            return if e.is_not_found() {
-                Err(CarbideError::FailedPrecondition(format!(
-                    "Image OS `{}` does not exist",
-                    os_image_id
-                )))
-            } else {
-                Err(CarbideError::internal(format!(
-                    "Failed to get OS image error: {e}"
-                )))
-            };
+        let os_image = match db::os_image::get(&mut txn, *os_image_id).await {
+            Ok(os_image) => os_image,
+            Err(e) => {
+                return if e.is_not_found() {
+                    Err(CarbideError::FailedPrecondition(format!(
+                        "Image OS `{}` does not exist",
+                        os_image_id
+                    )))
+                } else {
+                    Err(CarbideError::internal(format!(
+                        "Failed to get OS image error: {e}"
+                    )))
+                };
+            }
+        };
+        if os_image.status != OsImageStatus::Ready {
+            return Err(CarbideError::FailedPrecondition(format!(
+                "Image OS `{}` is not ready, status: {}",
+                os_image_id, os_image.status
+            )));
         }
     }
 
@@ -0,0 +1,50 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State change hook which notifies instance event streams about `ManagedHostState` transitions.
+
+use carbide_uuid::machine::MachineId;
+use model::machine::ManagedHostState;
+
+use crate::instance_events::notifier::InstanceEventNotifier;
+use crate::instance_events::recorder::is_instance_state;
+use crate::state_controller::state_change_emitter::{StateChangeEvent, StateChangeHook};
+
+/// Notifies open instance event streams after a host which carries an
+/// instance transitioned.
+///
+/// The events for the transition are recorded by [`super::recorder`] within the
+/// transaction of the transition. Hooks are only called once that transaction
+/// had been committed, which makes the events visible to the streams.
+pub struct InstanceEventStateChangeHook {
+    notifier: InstanceEventNotifier,
+}
+
+impl InstanceEventStateChangeHook {
+    pub fn new(notifier: InstanceEventNotifier) -> Self {
+        Self { notifier }
+    }
+}
+
+impl StateChangeHook<MachineId, ManagedHostState> for InstanceEventStateChangeHook {
+    fn on_state_changed(&self, event: &StateChangeEvent<'_, MachineId, ManagedHostState>) {
+        if is_instance_state(event.new_state) || event.previous_state.is_some_and(is_instance_state)
+        {
+            self.notifier.notify();
+        }
+    }
+}
@@ -0,0 +1,35 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tenant facing instance lifecycle events.
+//!
+//! Events are recorded in the `instance_events` table, which acts as the
+//! durable log behind the `StreamInstanceEvents` RPC:
+//! - State transitions and applied network configs are derived from the
+//!   `ManagedHostState` changes of the machine state controller by the
+//!   [`recorder`], within the transaction which persists the change.
+//!   The [`hook::InstanceEventStateChangeHook`] notifies streams afterwards.
+//! - Phone-home, power and release requests are recorded by the respective
+//!   API handlers.
+//!
+//! After recording events, producers signal the [`notifier::InstanceEventNotifier`]
+//! so that open streams on the same carbide-api instance can deliver the events
+//! without waiting for their next poll.
+
+pub mod hook;
+pub mod notifier;
+pub mod recorder;
@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Wakes up open instance event streams once new events have been committed.
+///
+/// The notifier only carries the information that *something* changed.
+/// Streams always read the events themselves from the database, which makes
+/// it safe to coalesce notifications.
+#[derive(Clone)]
+pub struct InstanceEventNotifier {
+    sender: Arc<watch::Sender<u64>>,
+}
+
+impl Default for InstanceEventNotifier {
+    fn default() -> Self {
+        let (sender, _) = watch::channel(0);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+}
+
+impl InstanceEventNotifier {
+    /// Signals all subscribers that new events have been recorded.
+    /// Must only be called after the transaction which recorded the events
+    /// had been committed.
+    pub fn notify(&self) {
+        self.sender.send_modify(|generation| {
+            *generation = generation.wrapping_add(1);
+        });
+    }
+
+    /// Returns a receiver which observes all notifications that are sent
+    /// after this call.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.sender.subscribe()
+    }
+}
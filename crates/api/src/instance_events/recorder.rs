@@ -0,0 +1,186 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records instance events for `ManagedHostState` transitions.
+
+use carbide_uuid::machine::MachineId;
+use db::DatabaseError;
+use model::instance::event::InstanceEventPayload;
+use model::machine::{
+    InstanceState, LoadSnapshotOptions, ManagedHostState, NetworkConfigUpdateState,
+};
+use sqlx::PgConnection;
+
+/// Returns whether a host in `state` can carry an instance whose tenant
+/// observes the state.
+///
+/// Hosts only carry instances while they are in `Assigned` state - or in
+/// `Ready` for the short time between instance creation and the state machine
+/// picking up the instance.
+pub fn is_instance_state(state: &ManagedHostState) -> bool {
+    matches!(
+        state,
+        ManagedHostState::Assigned { .. } | ManagedHostState::Ready
+    )
+}
+
+/// Records the `StateTransition` and `NetworkConfigApplied` instance events for
+/// the transition of a host into `new_state`.
+///
+/// This needs to be called within the transaction which persists the transition,
+/// before the new state is written, so that the events get committed together
+/// with the new state.
+/// Returns whether any event had been recorded.
+pub async fn record_state_change(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    new_state: &ManagedHostState,
+) -> Result<bool, DatabaseError> {
+    // Most transitions happen on hosts without instance. Skip loading the
+    // full snapshot for them.
+    if db::instance::find_id_by_machine_id(txn, machine_id)
+        .await?
+        .is_none()
+    {
+        // The final transition to `Terminated` is recorded by the state
+        // handler which deletes the instance.
+        return Ok(false);
+    }
+
+    let Some(mut snapshot) =
+        db::managed_host::load_snapshot(txn, machine_id, LoadSnapshotOptions::default()).await?
+    else {
+        return Ok(false);
+    };
+    let previous_state = snapshot.managed_state.clone();
+    if !is_instance_state(&previous_state) && !is_instance_state(new_state) {
+        return Ok(false);
+    }
+    let Some(instance) = snapshot.instance.as_ref() else {
+        return Ok(false);
+    };
+    let instance_id = instance.id;
+    let tenant_organization_id = instance.config.tenant.tenant_organization_id.clone();
+    let network_config_version = instance.network_config_version;
+
+    // Derive the tenant state for the state which is entered with this transition
+    snapshot.managed_state = new_state.clone();
+    let Some(tenant_status) = snapshot
+        .derive_instance_status()
+        .map_err(|e| {
+            DatabaseError::internal(format!(
+                "Failed to derive instance status of machine {machine_id}: {e}"
+            ))
+        })?
+        .and_then(|status| status.tenant)
+    else {
+        return Ok(false);
+    };
+
+    if network_config_applied(&previous_state, new_state) {
+        db::instance_event::persist(
+            txn,
+            instance_id,
+            &tenant_organization_id,
+            &InstanceEventPayload::NetworkConfigApplied {
+                network_config_version: network_config_version.version_string(),
+            },
+        )
+        .await?;
+    }
+
+    db::instance_event::persist(
+        txn,
+        instance_id,
+        &tenant_organization_id,
+        &InstanceEventPayload::StateTransition {
+            tenant_state: tenant_status.state,
+            machine_state: new_state.to_string(),
+        },
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// Returns whether the transition from `previous_state` to `new_state`
+/// indicates that the DPUs applied the desired instance network config
+fn network_config_applied(previous_state: &ManagedHostState, new_state: &ManagedHostState) -> bool {
+    match (previous_state, new_state) {
+        (
+            ManagedHostState::Assigned {
+                instance_state: InstanceState::WaitingForNetworkConfig,
+            },
+            ManagedHostState::Assigned { instance_state },
+        ) => !matches!(
+            instance_state,
+            InstanceState::WaitingForNetworkConfig | InstanceState::Failed { .. }
+        ),
+        (
+            ManagedHostState::Assigned {
+                instance_state:
+                    InstanceState::NetworkConfigUpdate {
+                        network_config_update_state:
+                            NetworkConfigUpdateState::WaitingForConfigSynced,
+                    },
+            },
+            ManagedHostState::Assigned {
+                instance_state:
+                    InstanceState::NetworkConfigUpdate {
+                        network_config_update_state: NetworkConfigUpdateState::ReleaseOldResources,
+                    },
+            },
+        ) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assigned(instance_state: InstanceState) -> ManagedHostState {
+        ManagedHostState::Assigned { instance_state }
+    }
+
+    #[test]
+    fn test_network_config_applied() {
+        assert!(network_config_applied(
+            &assigned(InstanceState::WaitingForNetworkConfig),
+            &assigned(InstanceState::WaitingForRebootToReady),
+        ));
+        assert!(network_config_applied(
+            &assigned(InstanceState::NetworkConfigUpdate {
+                network_config_update_state: NetworkConfigUpdateState::WaitingForConfigSynced,
+            }),
+            &assigned(InstanceState::NetworkConfigUpdate {
+                network_config_update_state: NetworkConfigUpdateState::ReleaseOldResources,
+            }),
+        ));
+        assert!(!network_config_applied(
+            &assigned(InstanceState::WaitingForNetworkSegmentToBeReady),
+            &assigned(InstanceState::WaitingForNetworkConfig),
+        ));
+        assert!(!network_config_applied(
+            &assigned(InstanceState::Ready),
+            &assigned(InstanceState::NetworkConfigUpdate {
+                network_config_update_state:
+                    NetworkConfigUpdateState::WaitingForNetworkSegmentToBeReady,
+            }),
+        ));
+    }
+}
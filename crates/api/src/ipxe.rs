@@ -27,6 +27,7 @@ use model::pxe::PxeInstructionRequest;
 use sqlx::PgConnection;
 
 use crate::CarbideError;
+use crate::os_image_download::DownloadTokenSigner;
 
 const QCOW_IMAGER_IPXE: &str =
     "chain ${base-url}/internal/x86_64/qcow-imager.efi loglevel=7 console=tty0 pci=realloc=off ";
@@ -109,6 +110,7 @@ impl PxeInstructions {
     pub async fn get_pxe_instructions(
         txn: &mut PgConnection,
         target: PxeInstructionRequest,
+        download_token_signer: &DownloadTokenSigner<'_>,
     ) -> Result<String, CarbideError> {
         let error_instructions = |machine_id: MachineId,
                                   interface_id: MachineInterfaceId,
@@ -390,21 +392,24 @@ exit ||
                                         machine.current_state(),
                                     )
                                 } else {
+                                    // The image is downloaded through carbide-pxe, and the registry
+                                    // credentials never leave carbide-api. The host only gets a
+                                    // short-lived token, since the command line ends up in console logs.
+                                    let token = download_token_signer
+                                        .sign(
+                                            machine_id,
+                                            target.interface_id,
+                                            os_image.attributes.id,
+                                        )
+                                        .await?;
                                     let mut qcow_imaging_ipxe = format!(
-                                        "{} console={},115200 image_url={} image_sha={}",
+                                        "{} console={},115200 image_url=${{image-url}}{}/{}.qcow2 image_sha={}",
                                         QCOW_IMAGER_IPXE,
                                         console,
-                                        os_image.attributes.source_url,
+                                        token,
+                                        os_image.attributes.id,
                                         os_image.attributes.digest
                                     );
-                                    if let Some(x) = os_image.attributes.auth_token {
-                                        qcow_imaging_ipxe +=
-                                            format!(" image_auth_token={x}").as_str();
-                                    }
-                                    if let Some(x) = os_image.attributes.auth_type {
-                                        qcow_imaging_ipxe +=
-                                            format!(" image_auth_type={x}").as_str();
-                                    }
                                     if let Some(x) = os_image.attributes.rootfs_id {
                                         qcow_imaging_ipxe += format!(" rootfs_uuid={x}").as_str();
                                     }
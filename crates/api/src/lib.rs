@@ -35,8 +35,10 @@
 
 mod api;
 mod attestation;
+mod audit_log;
 mod auth;
 mod cfg;
+mod console_access;
 mod credentials;
 mod db_init;
 mod dhcp;
@@ -49,6 +51,7 @@ mod handlers;
 mod ib;
 mod ib_fabric_monitor;
 mod instance;
+mod instance_events;
 mod ipmitool;
 mod ipxe;
 mod listener;
@@ -57,22 +60,28 @@ mod machine_identity;
 mod machine_update_manager;
 mod machine_validation;
 mod measured_boot;
+mod mlxconfig_policy;
 mod mqtt_state_change_hook;
+mod network_security_group;
 mod network_segment;
 mod nvl_partition_monitor;
 mod nvlink;
+mod os_image_download;
+mod os_image_ingestion;
 mod preingestion_manager;
 mod rack;
 mod redfish;
 mod run;
 mod scout_stream;
 mod setup;
+mod signed_token;
 mod site_explorer;
 mod state_controller;
 mod storage;
 #[cfg(test)]
 mod tests;
 mod web;
+mod webhook_state_change_hook;
 
 // Allow carbide_macros::sqlx_test to be referred as #[crate::sqlx_test]
 #[cfg(test)]
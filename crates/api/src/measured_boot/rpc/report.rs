@@ -27,12 +27,14 @@ use db::measured_boot::interface::report::{
     get_all_measurement_report_records, get_measurement_report_records_for_machine_id,
     match_latest_reports,
 };
+use measured_boot::eventlog::{EventLog, MeasurementReportDiff};
 use measured_boot::pcr::{PcrRegisterValue, PcrSet, parse_pcr_index_input};
 use rpc::protos::measured_boot::{
     CreateMeasurementReportRequest, CreateMeasurementReportResponse,
-    DeleteMeasurementReportRequest, DeleteMeasurementReportResponse, ListMeasurementReportRequest,
-    ListMeasurementReportResponse, MatchMeasurementReportRequest, MatchMeasurementReportResponse,
-    MeasurementReportRecordPb, PromoteMeasurementReportRequest, PromoteMeasurementReportResponse,
+    DeleteMeasurementReportRequest, DeleteMeasurementReportResponse, DiffMeasurementReportRequest,
+    DiffMeasurementReportResponse, ListMeasurementReportRequest, ListMeasurementReportResponse,
+    MatchMeasurementReportRequest, MatchMeasurementReportResponse, MeasurementReportRecordPb,
+    PromoteMeasurementReportRequest, PromoteMeasurementReportResponse,
     RevokeMeasurementReportRequest, RevokeMeasurementReportResponse,
     ShowMeasurementReportForIdRequest, ShowMeasurementReportForIdResponse,
     ShowMeasurementReportsForMachineRequest, ShowMeasurementReportsForMachineResponse,
@@ -276,3 +278,102 @@ pub async fn handle_match_measurement_report(
         reports: report_pbs,
     })
 }
+
+/// handle_diff_measurement_report handles the DiffMeasurementReport
+/// API endpoint. The report's event log is replayed against the PCR
+/// values it quoted, and then diffed against the event log of either
+/// the requested bundle, the bundle the report was matched with, or
+/// the closest matching bundle for the machine's profile.
+pub async fn handle_diff_measurement_report(
+    api: &Api,
+    req: DiffMeasurementReportRequest,
+) -> Result<DiffMeasurementReportResponse, Status> {
+    let mut txn = api.txn_begin().await?;
+    let report_id = req
+        .report_id
+        .ok_or(CarbideError::MissingArgument("report_id"))?;
+
+    let report = db::measured_boot::report::from_id(&mut txn, report_id)
+        .await
+        .map_err(|e| Status::internal(format!("diff failed fetching report: {e}")))?;
+    let report_log = load_event_log(
+        db::measured_boot::report::get_event_log(&mut txn, report_id).await,
+        "report",
+        &report_id.to_string(),
+    )?;
+
+    let bundle_id = match req.bundle_id {
+        Some(bundle_id) => Some(bundle_id),
+        None => {
+            let journal =
+                db::measured_boot::journal::get_journal_for_report_id(&mut txn, report_id)
+                    .await
+                    .map_err(|e| Status::internal(format!("diff failed fetching journal: {e}")))?;
+            match (journal.bundle_id, journal.profile_id) {
+                (Some(bundle_id), _) => Some(bundle_id),
+                (None, Some(profile_id)) => db::measured_boot::bundle::find_closest_match(
+                    &mut txn,
+                    profile_id,
+                    &report.pcr_values(),
+                )
+                .await
+                .map_err(|e| Status::internal(format!("diff failed matching bundle: {e}")))?
+                .map(|bundle| bundle.bundle_id),
+                (None, None) => None,
+            }
+        }
+    };
+    let bundle_id = bundle_id.ok_or_else(|| {
+        Status::not_found(format!(
+            "no bundle found to diff report {report_id} against"
+        ))
+    })?;
+
+    let bundle = db::measured_boot::bundle::from_id(&mut txn, bundle_id)
+        .await
+        .map_err(|e| Status::internal(format!("diff failed fetching bundle: {e}")))?;
+    let bundle_log = load_event_log(
+        db::measured_boot::bundle::get_event_log(&mut txn, bundle_id).await,
+        "bundle",
+        &bundle_id.to_string(),
+    )?;
+    txn.commit().await?;
+
+    let pcr_set = PcrSet(
+        bundle
+            .pcr_values()
+            .iter()
+            .map(|value| value.pcr_register)
+            .collect(),
+    );
+
+    let pcr_replays = report_log
+        .verify(&report.pcr_values())
+        .map_err(|e| Status::internal(format!("diff failed replaying event log: {e}")))?;
+    let differences = report_log
+        .diff(&bundle_log, Some(&pcr_set))
+        .map_err(|e| Status::internal(format!("diff failed comparing event logs: {e}")))?;
+
+    Ok(MeasurementReportDiff {
+        bundle_id: Some(bundle_id),
+        pcr_replays,
+        differences,
+        event_log_replay: report.event_log_replay,
+    }
+    .into())
+}
+
+/// load_event_log parses an event log loaded from the database,
+/// turning a missing or unparseable log into a failed precondition.
+fn load_event_log(
+    result: Result<Option<Vec<u8>>, db::DatabaseError>,
+    kind: &str,
+    id: &str,
+) -> Result<EventLog, Status> {
+    let data = result
+        .map_err(|e| Status::internal(format!("failed fetching {kind} event log: {e}")))?
+        .ok_or_else(|| Status::failed_precondition(format!("{kind} {id} has no event log")))?;
+    EventLog::parse(&data).map_err(|e| {
+        Status::failed_precondition(format!("{kind} {id} event log failed to parse: {e}"))
+    })
+}
@@ -0,0 +1,436 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! tests/eventlog.rs
+//!
+//! Event logs:
+//! [x] test_event_log_parse_and_verify: Make sure a log parses and replays to the quoted PCRs.
+//! [x] test_event_log_diff: Make sure changed events are called out by name.
+//! [x] test_diff_measurement_report: Make sure reports are diffed against the closest bundle.
+//! [x] test_event_log_replay_recorded: Make sure the replay outcome is stored with reports.
+
+#[cfg(test)]
+mod tests {
+    use measured_boot::eventlog::{
+        EventDifferenceKind, EventLog, EventLogReplay, EventLogReplayStatus, EventType,
+        HashAlgorithm,
+    };
+    use measured_boot::pcr::PcrRegisterValue;
+    use rpc::protos::measured_boot as mbrpc;
+
+    use crate::measured_boot::rpc::report;
+    use crate::measured_boot::tests::common::{create_test_machine, load_topology_json};
+    use crate::tests::common::api_fixtures::create_test_env;
+
+    const SHA256_ALG_ID: u16 = 0x000b;
+
+    // spec_id_event builds the legacy-format Spec ID event that
+    // starts a crypto-agile log with SHA256 digests.
+    fn spec_id_event() -> Vec<u8> {
+        let mut data = b"Spec ID Event03\0".to_vec();
+        data.extend(0u32.to_le_bytes()); // platformClass
+        data.extend([0, 2, 0, 2]); // spec version + uintnSize
+        data.extend(1u32.to_le_bytes());
+        data.extend(SHA256_ALG_ID.to_le_bytes());
+        data.extend(32u16.to_le_bytes());
+        data.push(0); // vendorInfoSize
+
+        let mut event = Vec::new();
+        event.extend(0u32.to_le_bytes());
+        event.extend(EventType::NO_ACTION.0.to_le_bytes());
+        event.extend([0u8; 20]);
+        event.extend((data.len() as u32).to_le_bytes());
+        event.extend(data);
+        event
+    }
+
+    // event2 builds a crypto-agile event with a single SHA256 digest.
+    fn event2(pcr_index: u32, event_type: EventType, digest: [u8; 32], data: &[u8]) -> Vec<u8> {
+        let mut event = Vec::new();
+        event.extend(pcr_index.to_le_bytes());
+        event.extend(event_type.0.to_le_bytes());
+        event.extend(1u32.to_le_bytes());
+        event.extend(SHA256_ALG_ID.to_le_bytes());
+        event.extend(digest);
+        event.extend((data.len() as u32).to_le_bytes());
+        event.extend(data);
+        event
+    }
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    // efi_variable_data builds a UEFI_VARIABLE_DATA structure.
+    fn efi_variable_data(name: &str, value: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; 16];
+        data.extend((name.encode_utf16().count() as u64).to_le_bytes());
+        data.extend((value.len() as u64).to_le_bytes());
+        data.extend(utf16(name));
+        data.extend(value);
+        data
+    }
+
+    // image_load_data builds a UEFI_IMAGE_LOAD_EVENT structure whose
+    // device path is a single file path node.
+    fn image_load_data(file_path: &str) -> Vec<u8> {
+        let mut file_node = utf16(file_path);
+        file_node.extend([0, 0]);
+
+        let mut device_path = vec![0x04, 0x04];
+        device_path.extend(((file_node.len() + 4) as u16).to_le_bytes());
+        device_path.extend(file_node);
+        device_path.extend([0x7f, 0xff, 0x04, 0x00]);
+
+        let mut data = vec![0u8; 24];
+        data.extend((device_path.len() as u64).to_le_bytes());
+        data.extend(device_path);
+        data
+    }
+
+    // build_event_log builds a log measuring the CRTM version into PCR0,
+    // shim into PCR4 and the Secure Boot db into PCR7.
+    fn build_event_log(shim_digest: u8, db_digest: u8) -> Vec<u8> {
+        let mut log = spec_id_event();
+        log.extend(event2(
+            0,
+            EventType::S_CRTM_VERSION,
+            [0x01; 32],
+            &utf16("1.0.0"),
+        ));
+        log.extend(event2(
+            7,
+            EventType::EFI_VARIABLE_DRIVER_CONFIG,
+            [db_digest; 32],
+            &efi_variable_data("db", b"certs"),
+        ));
+        log.extend(event2(
+            4,
+            EventType::EFI_BOOT_SERVICES_APPLICATION,
+            [shim_digest; 32],
+            &image_load_data("\\EFI\\BOOT\\shimx64.efi"),
+        ));
+        for pcr_index in [0, 4, 7] {
+            log.extend(event2(pcr_index, EventType::SEPARATOR, [0x02; 32], &[0; 4]));
+        }
+        // Firmware padding after the last event.
+        log.extend([0xff; 16]);
+        log
+    }
+
+    // expected_pcr_values replays the log by hand, returning
+    // the values a TPM would quote for PCRs 0, 4 and 7.
+    fn expected_pcr_values(shim_digest: u8, db_digest: u8) -> Vec<PcrRegisterValue> {
+        let extend = |digests: &[[u8; 32]]| {
+            digests.iter().fold(vec![0u8; 32], |pcr, digest| {
+                HashAlgorithm::Sha256.extend(&pcr, digest)
+            })
+        };
+        [
+            (0, extend(&[[0x01; 32], [0x02; 32]])),
+            (4, extend(&[[shim_digest; 32], [0x02; 32]])),
+            (7, extend(&[[db_digest; 32], [0x02; 32]])),
+        ]
+        .into_iter()
+        .map(|(pcr_register, value)| PcrRegisterValue {
+            pcr_register,
+            sha_any: hex::encode(value),
+        })
+        .collect()
+    }
+
+    // with_replay pairs an event log with the outcome of
+    // replaying it against `quoted`, as attestation stores it.
+    fn with_replay<'a>(
+        event_log: &'a [u8],
+        quoted: &[PcrRegisterValue],
+    ) -> (&'a [u8], EventLogReplay) {
+        let replay = EventLog::parse(event_log).unwrap().replay_against(quoted);
+        (event_log, replay)
+    }
+
+    #[test]
+    fn test_event_log_parse_and_verify() -> Result<(), Box<dyn std::error::Error>> {
+        let event_log = EventLog::parse(&build_event_log(0xaa, 0xbb))?;
+        assert_eq!(event_log.events.len(), 7);
+        assert_eq!(event_log.algorithms(), vec![HashAlgorithm::Sha256]);
+        assert_eq!(event_log.events[2].description(), "Secure Boot db");
+        assert_eq!(event_log.events[3].description(), "shim");
+
+        // Replaying the log lands on the quoted values.
+        let replays = event_log.verify(&expected_pcr_values(0xaa, 0xbb))?;
+        assert_eq!(replays.len(), 3);
+        assert!(replays.iter().all(|replay| replay.matches()));
+
+        // And a quote the log doesn't explain gets caught.
+        let replays = event_log.verify(&expected_pcr_values(0xcc, 0xbb))?;
+        let mismatched: Vec<i16> = replays
+            .iter()
+            .filter(|replay| !replay.matches())
+            .map(|replay| replay.pcr_register)
+            .collect();
+        assert_eq!(mismatched, vec![4]);
+
+        // Which is what gets stored with the report.
+        let replay = event_log.replay_against(&expected_pcr_values(0xaa, 0xbb));
+        assert_eq!(replay.status, EventLogReplayStatus::Matched);
+        let replay = event_log.replay_against(&expected_pcr_values(0xcc, 0xbb));
+        assert_eq!(replay.status, EventLogReplayStatus::Mismatched);
+        assert_eq!(
+            replay.message.as_deref(),
+            Some("replay does not match the quote for PCR4")
+        );
+        let sha1_quote = vec![PcrRegisterValue {
+            pcr_register: 0,
+            sha_any: "00".repeat(20),
+        }];
+        let replay = event_log.replay_against(&sha1_quote);
+        assert_eq!(replay.status, EventLogReplayStatus::Failed);
+
+        // A truncated log fails to parse, rather than panicking.
+        let truncated = build_event_log(0xaa, 0xbb);
+        assert!(EventLog::parse(&truncated[..truncated.len() - 40]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_log_diff() -> Result<(), Box<dyn std::error::Error>> {
+        let baseline = EventLog::parse(&build_event_log(0xaa, 0xbb))?;
+
+        // Identical logs have no differences.
+        assert!(baseline.diff(&baseline, None)?.is_empty());
+
+        // A new shim and db show up as such.
+        let updated = EventLog::parse(&build_event_log(0xcc, 0xdd))?;
+        let differences = updated.diff(&baseline, None)?;
+        let described: Vec<String> = differences.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            described,
+            vec!["PCR4: shim changed", "PCR7: Secure Boot db changed"]
+        );
+        assert!(
+            differences
+                .iter()
+                .all(|d| d.kind == EventDifferenceKind::Changed)
+        );
+        assert_eq!(
+            differences[0].expected_digest.as_deref(),
+            Some(hex::encode([0xaa; 32]).as_str())
+        );
+        assert_eq!(
+            differences[0].actual_digest.as_deref(),
+            Some(hex::encode([0xcc; 32]).as_str())
+        );
+
+        // Limiting the diff to a set of PCRs leaves out the rest.
+        let differences = updated.diff(&baseline, Some(&measured_boot::pcr::PcrSet(vec![7])))?;
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].pcr_register, 7);
+        Ok(())
+    }
+
+    // test_diff_measurement_report stores a report with an event log,
+    // promotes it into a bundle, and then makes sure a later report
+    // with a new shim gets diffed against that bundle.
+    #[crate::sqlx_test]
+    pub async fn test_diff_measurement_report(
+        db_conn: sqlx::PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let env = create_test_env(db_conn).await;
+        let api = &env.api;
+
+        let mut txn = api.txn_begin().await?;
+        let machine = create_test_machine(
+            &mut txn,
+            "fm100hseddco33hvlofuqvg543p6p9aj60g76q5cq491g9m9tgtf2dk0530",
+            &load_topology_json("dell_r750.json"),
+        )
+        .await?;
+
+        let golden_log = build_event_log(0xaa, 0xbb);
+        let golden_report = db::measured_boot::report::new_with_event_log(
+            &mut txn,
+            machine.machine_id,
+            &expected_pcr_values(0xaa, 0xbb),
+            Some(with_replay(&golden_log, &expected_pcr_values(0xaa, 0xbb))),
+        )
+        .await?;
+        assert_eq!(
+            db::measured_boot::report::get_event_log(&mut txn, golden_report.report_id).await?,
+            Some(golden_log.clone())
+        );
+
+        // The bundle inherits the event log of the report.
+        let bundle =
+            db::measured_boot::report::create_active_bundle(&mut txn, &golden_report, &None)
+                .await?;
+        assert_eq!(
+            db::measured_boot::bundle::get_event_log(&mut txn, bundle.bundle_id).await?,
+            Some(golden_log)
+        );
+
+        // A report without an event log has nothing to diff.
+        let no_log_report = db::measured_boot::report::new(
+            &mut txn,
+            machine.machine_id,
+            &expected_pcr_values(0xee, 0xbb),
+        )
+        .await?;
+
+        let new_shim_report = db::measured_boot::report::new_with_event_log(
+            &mut txn,
+            machine.machine_id,
+            &expected_pcr_values(0xcc, 0xbb),
+            Some(with_replay(
+                &build_event_log(0xcc, 0xbb),
+                &expected_pcr_values(0xcc, 0xbb),
+            )),
+        )
+        .await?;
+        txn.commit().await?;
+
+        // No bundle given, so the closest bundle gets picked.
+        let resp = report::handle_diff_measurement_report(
+            api,
+            mbrpc::DiffMeasurementReportRequest {
+                report_id: Some(new_shim_report.report_id),
+                bundle_id: None,
+            },
+        )
+        .await?;
+        assert_eq!(resp.bundle_id, Some(bundle.bundle_id));
+        assert_eq!(resp.pcr_replays.len(), 3);
+        assert!(
+            resp.pcr_replays
+                .iter()
+                .all(|replay| replay.quoted == replay.replayed)
+        );
+        assert_eq!(resp.differences.len(), 1);
+        assert_eq!(resp.differences[0].pcr_register, 4);
+        assert_eq!(resp.differences[0].description, "shim");
+        assert_eq!(
+            resp.differences[0].kind,
+            mbrpc::EventLogDifferenceKindPb::Changed as i32
+        );
+
+        // Diffing the golden report against its own bundle is clean.
+        let resp = report::handle_diff_measurement_report(
+            api,
+            mbrpc::DiffMeasurementReportRequest {
+                report_id: Some(golden_report.report_id),
+                bundle_id: Some(bundle.bundle_id),
+            },
+        )
+        .await?;
+        assert!(resp.differences.is_empty());
+
+        let err = report::handle_diff_measurement_report(
+            api,
+            mbrpc::DiffMeasurementReportRequest {
+                report_id: Some(no_log_report.report_id),
+                bundle_id: Some(bundle.bundle_id),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        Ok(())
+    }
+
+    // test_event_log_replay_recorded makes sure a report whose event
+    // log doesn't replay to the quoted values carries that outcome
+    // through the report and diff APIs.
+    #[crate::sqlx_test]
+    pub async fn test_event_log_replay_recorded(
+        db_conn: sqlx::PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let env = create_test_env(db_conn).await;
+        let api = &env.api;
+
+        let mut txn = api.txn_begin().await?;
+        let machine = create_test_machine(
+            &mut txn,
+            "fm100hseddco33hvlofuqvg543p6p9aj60g76q5cq491g9m9tgtf2dk0530",
+            &load_topology_json("dell_r750.json"),
+        )
+        .await?;
+
+        let golden_report = db::measured_boot::report::new_with_event_log(
+            &mut txn,
+            machine.machine_id,
+            &expected_pcr_values(0xaa, 0xbb),
+            Some(with_replay(
+                &build_event_log(0xaa, 0xbb),
+                &expected_pcr_values(0xaa, 0xbb),
+            )),
+        )
+        .await?;
+        assert_eq!(
+            golden_report.event_log_replay.map(|replay| replay.status),
+            Some(EventLogReplayStatus::Matched)
+        );
+        let bundle =
+            db::measured_boot::report::create_active_bundle(&mut txn, &golden_report, &None)
+                .await?;
+
+        // The quote claims the golden shim, but the log has a new one.
+        let spoofed_report = db::measured_boot::report::new_with_event_log(
+            &mut txn,
+            machine.machine_id,
+            &expected_pcr_values(0xaa, 0xdd),
+            Some(with_replay(
+                &build_event_log(0xcc, 0xdd),
+                &expected_pcr_values(0xaa, 0xdd),
+            )),
+        )
+        .await?;
+        txn.commit().await?;
+
+        let resp = report::handle_show_measurement_report_for_id(
+            api,
+            mbrpc::ShowMeasurementReportForIdRequest {
+                report_id: Some(spoofed_report.report_id),
+            },
+        )
+        .await?;
+        let replay = resp.report.unwrap().event_log_replay.unwrap();
+        assert_eq!(
+            replay.status,
+            mbrpc::EventLogReplayStatusPb::Mismatched as i32
+        );
+        assert_eq!(
+            replay.message.as_deref(),
+            Some("replay does not match the quote for PCR4")
+        );
+
+        let resp = report::handle_diff_measurement_report(
+            api,
+            mbrpc::DiffMeasurementReportRequest {
+                report_id: Some(spoofed_report.report_id),
+                bundle_id: Some(bundle.bundle_id),
+            },
+        )
+        .await?;
+        assert_eq!(
+            resp.event_log_replay.map(|replay| replay.status),
+            Some(mbrpc::EventLogReplayStatusPb::Mismatched as i32)
+        );
+
+        Ok(())
+    }
+}
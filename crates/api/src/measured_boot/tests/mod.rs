@@ -20,6 +20,7 @@
 //!
 
 pub mod common;
+mod eventlog;
 mod integration;
 mod journal;
 mod metrics;
@@ -0,0 +1,517 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Enforcement of the mlxconfig profiles which are bound to SKUs and instance types.
+//!
+//! Hosts with a bound profile are synced to it in the `MlxConfigSync` host state
+//! before they become Ready. Scout reports the outcome of every sync through
+//! `PublishMlxObservationReport`. The outcome is recorded per host, and devices
+//! which could not be synced to the current version of the bound profile raise
+//! an `MlxConfigDrift` health alert until a later sync succeeds.
+//!
+//! Scout also reports the current and pending variable values of the devices
+//! with its other observations. Values of a `Ready` host which differ from the
+//! bound profile raise the same alert, and mark the host as out of sync, which
+//! makes the state handler sync the profile again.
+
+use std::collections::BTreeSet;
+
+use ::rpc::protos::mlx_device::{MlxObservation, MlxObservationReport};
+use carbide_host_support::dpa_cmds::{DpaCommand, OpCode};
+use chrono::Utc;
+use health_report::{HealthReport, OverrideMode};
+use libmlx::runner::result_types::QueriedVariable;
+use model::machine::machine_search_config::MachineSearchConfig;
+use model::machine::{Machine, ManagedHostState};
+use model::mlxconfig_profile::{MachineMlxConfigStatus, StoredMlxConfigProfile};
+use rpc::forge_agent_control_response::forge_agent_control_extra_info::KeyValuePair;
+use rpc::forge_agent_control_response::{Action, ForgeAgentControlExtraInfo};
+use sqlx::PgConnection;
+
+use crate::api::Api;
+use crate::{CarbideError, CarbideResult};
+
+/// PCI vendor name reported for Mellanox devices in the hardware inventory
+const MELLANOX_VENDOR: &str = "Mellanox Technologies";
+
+/// Returns the PCI addresses of the Mellanox devices in the hardware inventory
+/// of a machine. mlxconfig settings are per device, so every device is only
+/// returned once, addressed through its first function.
+pub(crate) fn mellanox_devices(machine: &Machine) -> BTreeSet<String> {
+    let Some(hardware_info) = machine.hardware_info.as_ref() else {
+        return BTreeSet::new();
+    };
+
+    hardware_info
+        .network_interfaces
+        .iter()
+        .filter_map(|iface| iface.pci_properties.as_ref())
+        .chain(
+            hardware_info
+                .infiniband_interfaces
+                .iter()
+                .filter_map(|iface| iface.pci_properties.as_ref()),
+        )
+        .filter(|pci| pci.vendor.eq_ignore_ascii_case(MELLANOX_VENDOR))
+        .filter_map(|pci| pci.slot.as_deref())
+        .filter(|slot| !slot.is_empty())
+        .map(|slot| match slot.rsplit_once('.') {
+            Some((device, _function)) => format!("{device}.0"),
+            None => slot.to_string(),
+        })
+        .collect()
+}
+
+/// Builds the MlxAction commands which make scout sync `profile` to every
+/// Mellanox device of the machine
+pub(crate) fn sync_profile_commands(
+    machine: &Machine,
+    profile: &StoredMlxConfigProfile,
+) -> CarbideResult<Vec<KeyValuePair>> {
+    mellanox_devices(machine)
+        .into_iter()
+        .map(|pci_name| {
+            let command = DpaCommand {
+                op: OpCode::SyncProfile {
+                    profile: Box::new(profile.profile.clone()),
+                    version: profile.version.to_string(),
+                },
+            };
+            Ok(KeyValuePair {
+                key: pci_name,
+                value: serde_json::to_string(&command).map_err(CarbideError::from)?,
+            })
+        })
+        .collect()
+}
+
+/// Decides what scout should do for a host in `MlxConfigSync`: sync the bound
+/// profile, unless scout already reported a sync since the host entered the state
+pub(crate) async fn process_scout_req(
+    txn: &mut PgConnection,
+    machine: &Machine,
+) -> CarbideResult<(Action, Option<ForgeAgentControlExtraInfo>)> {
+    let Some(profile) = db::mlxconfig_profile::find_bound_profile(
+        &mut *txn,
+        machine.hw_sku.as_deref(),
+        machine.instance_type_id.as_ref(),
+    )
+    .await?
+    else {
+        return Ok((Action::Noop, None));
+    };
+
+    let status = db::mlxconfig_profile::find_machine_status(&mut *txn, &machine.id).await?;
+    if status.is_some_and(|status| {
+        status.is_for(&profile) && status.observed_at > machine.state.version.timestamp()
+    }) {
+        return Ok((Action::Noop, None));
+    }
+
+    let pair = sync_profile_commands(machine, &profile)?;
+    if pair.is_empty() {
+        // A host without Mellanox devices has nothing to sync
+        let status = MachineMlxConfigStatus {
+            machine_id: machine.id,
+            profile_name: profile.name,
+            profile_version: profile.version,
+            synced: true,
+            variables_changed: 0,
+            observed_at: Utc::now(),
+        };
+        db::mlxconfig_profile::update_machine_status(txn, &status).await?;
+        return Ok((Action::Noop, None));
+    }
+
+    Ok((Action::MlxAction, Some(ForgeAgentControlExtraInfo { pair })))
+}
+
+/// The outcome of a profile sync, evaluated against the profile bound to the host
+#[derive(Debug, PartialEq)]
+struct SyncEvaluation {
+    variables_changed: u32,
+    /// `(device, message)` for every device which is not synced to the bound profile
+    drifts: Vec<(String, String)>,
+}
+
+fn evaluate_observations(
+    profile: &StoredMlxConfigProfile,
+    observations: &[&MlxObservation],
+) -> SyncEvaluation {
+    let expected_version = profile.version.to_string();
+    let mut evaluation = SyncEvaluation {
+        variables_changed: 0,
+        drifts: Vec::new(),
+    };
+
+    for obs in observations {
+        let device = obs
+            .device_info
+            .as_ref()
+            .map(|info| info.pci_name.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        let profile_name = obs.profile_name.as_deref().unwrap_or_default();
+        let profile_version = obs.profile_version.as_deref().unwrap_or_default();
+
+        let drift = if profile_name != profile.name {
+            Some(format!(
+                "Device reports mlxconfig profile {profile_name}, expected {}",
+                profile.name
+            ))
+        } else if profile_version != expected_version {
+            Some(format!(
+                "Device was synced to version {profile_version} of mlxconfig profile {}, expected {expected_version}",
+                profile.name
+            ))
+        } else if obs.profile_synced != Some(true) {
+            Some(format!(
+                "Failed to sync mlxconfig profile {} version {expected_version}",
+                profile.name
+            ))
+        } else {
+            None
+        };
+
+        match drift {
+            Some(message) => evaluation.drifts.push((device, message)),
+            None => evaluation.variables_changed += obs.profile_variables_changed.unwrap_or(0),
+        }
+    }
+
+    evaluation
+}
+
+/// Compares the variable values scout observed on the devices against `profile`,
+/// and returns `(device, message)` for every device with variables whose current
+/// or pending value differs from the profile
+fn evaluate_observed_variables(
+    profile: &StoredMlxConfigProfile,
+    observations: &[&MlxObservation],
+) -> Vec<(String, String)> {
+    let expected = match profile.profile.clone().into_profile() {
+        Ok(expected) => expected,
+        Err(err) => {
+            tracing::warn!(
+                %err,
+                profile_name = %profile.name,
+                "Failed to load mlxconfig profile, skipping drift detection"
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut drifts = Vec::new();
+    for obs in observations {
+        let device = obs
+            .device_info
+            .as_ref()
+            .map(|info| info.pci_name.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut mismatches = Vec::new();
+        for variable in &obs.observed_variables {
+            let observed = match QueriedVariable::try_from(variable.clone()) {
+                Ok(observed) => observed,
+                Err(err) => {
+                    tracing::warn!(%err, %device, "Ignoring invalid observed mlxconfig variable");
+                    continue;
+                }
+            };
+            let Some(expected_value) = expected.get_variable(observed.name()) else {
+                continue;
+            };
+
+            let mismatch = if observed.next_value.value != expected_value.value {
+                Some(format!(
+                    "{} is set to {}, expected {expected_value}",
+                    observed.name(),
+                    observed.next_value
+                ))
+            } else if observed.current_value.value != expected_value.value {
+                Some(format!(
+                    "{} is {} until the next reboot, expected {expected_value}",
+                    observed.name(),
+                    observed.current_value
+                ))
+            } else {
+                None
+            };
+            mismatches.extend(mismatch);
+        }
+
+        if !mismatches.is_empty() {
+            drifts.push((
+                device,
+                format!(
+                    "Device drifted from mlxconfig profile {}: {}",
+                    profile.name,
+                    mismatches.join(", ")
+                ),
+            ));
+        }
+    }
+
+    drifts
+}
+
+/// Evaluates an observation report against the profile bound to the host.
+/// Observations with a profile version carry the outcome of a stored profile
+/// sync. The variable values reported with any other observation are compared
+/// against the profile while the host is `Ready`, since the host is expected to
+/// differ from it while it is still being synced.
+pub(crate) async fn process_observation_report(
+    api: &Api,
+    report: &MlxObservationReport,
+) -> CarbideResult<()> {
+    let (sync_observations, observed_observations): (Vec<&MlxObservation>, Vec<&MlxObservation>) =
+        report
+            .observations
+            .iter()
+            .filter(|obs| obs.profile_version.is_some() || !obs.observed_variables.is_empty())
+            .partition(|obs| obs.profile_version.is_some());
+    if sync_observations.is_empty() && observed_observations.is_empty() {
+        return Ok(());
+    }
+
+    let machine_id = report
+        .machine_id
+        .ok_or(CarbideError::MissingArgument("machine_id"))?;
+    let (machine, mut txn) = api
+        .load_machine(&machine_id, MachineSearchConfig::default())
+        .await?;
+
+    let profile = db::mlxconfig_profile::find_bound_profile(
+        txn.as_mut(),
+        machine.hw_sku.as_deref(),
+        machine.instance_type_id.as_ref(),
+    )
+    .await?;
+
+    match profile {
+        Some(profile) => {
+            let mut evaluation = evaluate_observations(&profile, &sync_observations);
+            if matches!(machine.current_state(), ManagedHostState::Ready) {
+                evaluation.drifts.extend(evaluate_observed_variables(
+                    &profile,
+                    &observed_observations,
+                ));
+            }
+            if sync_observations.is_empty() && evaluation.drifts.is_empty() {
+                // Matching values don't tell anything about the outcome of the
+                // last sync, which is what the recorded status is about
+                return Ok(());
+            }
+
+            let status = MachineMlxConfigStatus {
+                machine_id,
+                profile_name: profile.name.clone(),
+                profile_version: profile.version,
+                synced: evaluation.drifts.is_empty(),
+                variables_changed: evaluation.variables_changed as i32,
+                observed_at: Utc::now(),
+            };
+            db::mlxconfig_profile::update_machine_status(&mut txn, &status).await?;
+
+            if evaluation.drifts.is_empty() {
+                db::machine::remove_health_report_override(
+                    &mut txn,
+                    &machine_id,
+                    OverrideMode::Merge,
+                    HealthReport::MLXCONFIG_PROFILE_SOURCE,
+                )
+                .await?;
+            } else {
+                tracing::warn!(
+                    %machine_id,
+                    profile_name = %profile.name,
+                    drifts = ?evaluation.drifts,
+                    "mlxconfig profile drift detected"
+                );
+                db::machine::insert_health_report_override(
+                    &mut txn,
+                    &machine_id,
+                    OverrideMode::Merge,
+                    &HealthReport::mlxconfig_drift(evaluation.drifts),
+                    false,
+                )
+                .await?;
+            }
+        }
+        None => {
+            // The profile was unbound since the sync was requested.
+            // There is nothing the host could drift from anymore.
+            db::machine::remove_health_report_override(
+                &mut txn,
+                &machine_id,
+                OverrideMode::Merge,
+                HealthReport::MLXCONFIG_PROFILE_SOURCE,
+            )
+            .await?;
+        }
+    }
+
+    txn.commit().await?;
+
+    // Wake up the state handler, which might be waiting for the sync
+    if let Err(err) = api
+        .machine_state_handler_enqueuer
+        .enqueue_object(&machine_id)
+        .await
+    {
+        tracing::warn!(%err, %machine_id, "Failed to wake up state handler for machine");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rpc::protos::mlx_device::{MlxDeviceInfo, QueriedVariable as QueriedVariablePb};
+    use config_version::ConfigVersion;
+    use libmlx::profile::serialization::SerializableProfile;
+    use libmlx::variables::value::{MlxConfigValue, MlxValueType};
+
+    use super::*;
+
+    fn profile() -> StoredMlxConfigProfile {
+        StoredMlxConfigProfile {
+            name: "cx7-eth".to_string(),
+            version: ConfigVersion::initial(),
+            description: None,
+            profile: SerializableProfile::new("cx7-eth", "mlx_generic")
+                .with_config("SRIOV_EN", true),
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    fn observation(
+        pci_name: &str,
+        profile_name: &str,
+        version: ConfigVersion,
+        synced: bool,
+        changed: u32,
+    ) -> MlxObservation {
+        MlxObservation {
+            device_info: Some(MlxDeviceInfo {
+                pci_name: pci_name.to_string(),
+                ..Default::default()
+            }),
+            lock_status: None,
+            profile_name: Some(profile_name.to_string()),
+            profile_synced: Some(synced),
+            firmware_report: None,
+            profile_version: Some(version.to_string()),
+            profile_variables_changed: synced.then_some(changed),
+            observed_variables: vec![],
+        }
+    }
+
+    fn observed_sriov_en(current: bool, next: bool) -> QueriedVariablePb {
+        let variable = libmlx::registry::registries::get("mlx_generic")
+            .and_then(|registry| registry.get_variable("SRIOV_EN"))
+            .unwrap()
+            .clone();
+        let value = |enabled| {
+            MlxConfigValue::new(variable.clone(), MlxValueType::Boolean(enabled)).unwrap()
+        };
+        QueriedVariable::new(
+            variable.clone(),
+            value(current),
+            value(false),
+            value(next),
+            current != next,
+            false,
+        )
+        .try_into()
+        .unwrap()
+    }
+
+    fn observed(pci_name: &str, variables: Vec<QueriedVariablePb>) -> MlxObservation {
+        MlxObservation {
+            device_info: Some(MlxDeviceInfo {
+                pci_name: pci_name.to_string(),
+                ..Default::default()
+            }),
+            observed_variables: variables,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_evaluate_synced_observations() {
+        let profile = profile();
+        let first = observation("0000:3b:00.0", "cx7-eth", profile.version, true, 2);
+        let second = observation("0000:5e:00.0", "cx7-eth", profile.version, true, 0);
+
+        let evaluation = evaluate_observations(&profile, &[&first, &second]);
+        assert_eq!(
+            evaluation,
+            SyncEvaluation {
+                variables_changed: 2,
+                drifts: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_evaluate_drifted_observations() {
+        let profile = profile();
+        let failed = observation("0000:3b:00.0", "cx7-eth", profile.version, false, 0);
+        let stale = observation(
+            "0000:5e:00.0",
+            "cx7-eth",
+            profile.version.increment(),
+            true,
+            0,
+        );
+        let other = observation("0000:86:00.0", "cx7-ib", profile.version, true, 1);
+
+        let evaluation = evaluate_observations(&profile, &[&failed, &stale, &other]);
+        assert_eq!(evaluation.variables_changed, 0);
+        let devices: Vec<&str> = evaluation
+            .drifts
+            .iter()
+            .map(|(device, _)| device.as_str())
+            .collect();
+        assert_eq!(devices, ["0000:3b:00.0", "0000:5e:00.0", "0000:86:00.0"]);
+        assert!(evaluation.drifts[2].1.contains("cx7-ib"));
+    }
+
+    #[test]
+    fn test_evaluate_observed_variables_in_sync() {
+        let profile = profile();
+        let in_sync = observed("0000:3b:00.0", vec![observed_sriov_en(true, true)]);
+        let not_observed = observed("0000:5e:00.0", vec![]);
+
+        let drifts = evaluate_observed_variables(&profile, &[&in_sync, &not_observed]);
+        assert!(drifts.is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_drifted_observed_variables() {
+        let profile = profile();
+        let changed = observed("0000:3b:00.0", vec![observed_sriov_en(true, false)]);
+        let pending_reboot = observed("0000:5e:00.0", vec![observed_sriov_en(false, true)]);
+
+        let drifts = evaluate_observed_variables(&profile, &[&changed, &pending_reboot]);
+        let devices: Vec<&str> = drifts.iter().map(|(device, _)| device.as_str()).collect();
+        assert_eq!(devices, ["0000:3b:00.0", "0000:5e:00.0"]);
+        assert!(drifts[0].1.contains("SRIOV_EN is set to false"));
+        assert!(drifts[1].1.contains("until the next reboot"));
+    }
+}
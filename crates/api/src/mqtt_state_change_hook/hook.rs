@@ -17,23 +17,24 @@
 
 //! MQTT hook implementation for publishing state changes.
 
+use std::fmt::Display;
 use std::sync::Arc;
 use std::time::Duration;
 
-use carbide_uuid::machine::MachineId;
-use model::machine::ManagedHostState;
 use mqttea::{MqtteaClient, MqtteaClientError};
 use opentelemetry::metrics::Meter;
 use tokio::sync::mpsc;
 use tokio::time::error::Elapsed;
 use tokio::time::{Instant, timeout_at};
 
-use crate::mqtt_state_change_hook::message::ManagedHostStateChangeMessage;
+use crate::mqtt_state_change_hook::message::StateChangeMessage;
 use crate::mqtt_state_change_hook::metrics::MqttHookMetrics;
-use crate::state_controller::state_change_emitter::{StateChangeEvent, StateChangeHook};
+use crate::state_controller::state_change_emitter::{
+    PublishableState, StateChangeEvent, StateChangeHook,
+};
 
 /// Topic prefix for state change messages.
-const TOPIC_PREFIX: &str = "carbide/v1/machine";
+const TOPIC_PREFIX: &str = "carbide/v1";
 
 /// Internal queue item containing pre-serialized MQTT message with deadline.
 struct QueuedMessage {
@@ -64,13 +65,19 @@ impl<T: MqttPublisher> MqttPublisher for Arc<T> {
     }
 }
 
-/// MQTT hook that publishes `ManagedHostState` changes to the MQTT broker.
+/// MQTT hook that publishes state changes to the MQTT broker.
 ///
-/// Implements the AsyncAPI specification in `carbide.yaml`, publishing to
-/// `carbide/v1/machine/{machineId}/state`.
+/// Publishes to `carbide/v1/{objectType}/{objectId}/state` - e.g.
+/// `carbide/v1/machine/{machineId}/state` for `ManagedHostState` changes.
+/// The topics of all state controllers are listed in the [module documentation](super).
 ///
 /// This hook maintains an internal queue and processes events in a background task.
 /// If the queue is full, events are dropped and a warning is logged.
+///
+/// The hook can be registered for any [`PublishableState`]. Clones of the hook
+/// share the same queue, which allows registering a single hook with the
+/// emitters of all state controllers.
+#[derive(Clone)]
 pub struct MqttStateChangeHook {
     sender: mpsc::Sender<QueuedMessage>,
     publish_timeout: Duration,
@@ -100,17 +107,20 @@ impl MqttStateChangeHook {
         }
     }
 
-    fn build_topic(machine_id: &MachineId) -> String {
-        format!("{}/{}/state", TOPIC_PREFIX, machine_id)
+    fn build_topic(object_type: &str, object_id: &impl Display) -> String {
+        format!("{TOPIC_PREFIX}/{object_type}/{object_id}/state")
     }
 }
 
-impl StateChangeHook<MachineId, ManagedHostState> for MqttStateChangeHook {
-    fn on_state_changed(&self, event: &StateChangeEvent<'_, MachineId, ManagedHostState>) {
+impl<Id, S> StateChangeHook<Id, S> for MqttStateChangeHook
+where
+    Id: Display + Clone + Send + Sync + 'static,
+    S: PublishableState,
+{
+    fn on_state_changed(&self, event: &StateChangeEvent<'_, Id, S>) {
         // Serialize immediately to avoid cloning state
-        let message =
-            ManagedHostStateChangeMessage::new(event.object_id, event.new_state, event.timestamp);
-        let topic = Self::build_topic(event.object_id);
+        let message = StateChangeMessage::new(event.object_id, event.new_state, event.timestamp);
+        let topic = Self::build_topic(S::OBJECT_TYPE, event.object_id);
 
         match message.to_json_bytes() {
             Ok(payload) => {
@@ -127,7 +137,8 @@ impl StateChangeHook<MachineId, ManagedHostState> for MqttStateChangeHook {
             }
             Err(e) => {
                 tracing::error!(
-                    machine_id = %event.object_id,
+                    object_type = S::OBJECT_TYPE,
+                    object_id = %event.object_id,
                     error = %e,
                     "Failed to serialize state change message"
                 );
@@ -173,6 +184,8 @@ async fn process_events<P: MqttPublisher>(
 mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
 
+    use carbide_uuid::machine::MachineId;
+    use model::machine::ManagedHostState;
     use opentelemetry::global;
     use tokio::sync::Barrier;
 
@@ -246,6 +259,36 @@ mod tests {
         assert_eq!(state_obj.get("state").unwrap(), "ready");
     }
 
+    #[tokio::test]
+    async fn test_events_of_other_objects_are_published() {
+        use carbide_uuid::switch::{SwitchId, SwitchIdSource, SwitchType};
+        use model::switch::SwitchControllerState;
+
+        let (publisher, mut receiver) = SignalingPublisher::new();
+        let hook = MqttStateChangeHook::new(publisher, Duration::from_secs(1), 16, &test_meter());
+
+        let id = SwitchId::new(
+            SwitchIdSource::ProductBoardChassisSerial,
+            [0; 32],
+            SwitchType::NvLink,
+        );
+        let state = SwitchControllerState::Ready;
+        hook.on_state_changed(&StateChangeEvent {
+            object_id: &id,
+            previous_state: None,
+            new_state: &state,
+            timestamp: chrono::Utc::now(),
+        });
+
+        let (topic, payload) = receiver.recv().await.expect("should receive message");
+        assert_eq!(topic, format!("carbide/v1/switch/{id}/state"));
+
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed.get("switch_id").unwrap(), &id.to_string());
+        let state_obj = parsed.get("switch_state").unwrap();
+        assert_eq!(state_obj.get("state").unwrap(), "ready");
+    }
+
     #[tokio::test]
     async fn test_publish_timeout() {
         let started = Arc::new(Barrier::new(2));
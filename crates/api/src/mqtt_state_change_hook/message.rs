@@ -17,36 +17,43 @@
 
 //! Message types for the MQTT state change hook.
 
+use std::fmt::Display;
+
 use carbide_uuid::machine::MachineId;
 use chrono::{DateTime, Utc};
 use model::machine::ManagedHostState;
 use serde::Serialize;
+use serde::ser::SerializeMap;
 
-/// MQTT message for managed host state changes.
+use crate::state_controller::state_change_emitter::PublishableState;
+
+/// MQTT message for state changes of any [`PublishableState`].
 ///
-/// Serializes to JSON with the state flattened directly into the message,
-/// using `ManagedHostState`'s native serde serialization (lowercase state names).
-#[derive(Debug, Clone, Serialize)]
-pub struct ManagedHostStateChangeMessage<'a> {
-    /// Unique identifier for the managed host machine.
-    pub machine_id: &'a MachineId,
+/// Serializes to a JSON object with the fields `{object_type}_id`, `timestamp`
+/// and the field named by [`PublishableState::STATE_FIELD`]. The state uses
+/// its native serde serialization (lowercase state names).
+#[derive(Debug, Clone)]
+pub struct StateChangeMessage<'a, Id, S> {
+    /// Unique identifier of the object whose state changed.
+    pub object_id: &'a Id,
     /// ISO 8601 timestamp of the state change.
     pub timestamp: DateTime<Utc>,
-    /// The managed host state.
-    pub managed_host_state: &'a ManagedHostState,
+    /// The new state of the object.
+    pub state: &'a S,
 }
 
-impl<'a> ManagedHostStateChangeMessage<'a> {
+/// MQTT message for managed host state changes.
+///
+/// Serializes as `{"machine_id": ..., "timestamp": ..., "managed_host_state": ...}`.
+pub type ManagedHostStateChangeMessage<'a> = StateChangeMessage<'a, MachineId, ManagedHostState>;
+
+impl<'a, Id: Display, S: PublishableState> StateChangeMessage<'a, Id, S> {
     /// Create a new message from the given state change data.
-    pub fn new(
-        machine_id: &'a MachineId,
-        state: &'a ManagedHostState,
-        timestamp: DateTime<Utc>,
-    ) -> Self {
+    pub fn new(object_id: &'a Id, state: &'a S, timestamp: DateTime<Utc>) -> Self {
         Self {
-            machine_id,
+            object_id,
             timestamp,
-            managed_host_state: state,
+            state,
         }
     }
 
@@ -56,6 +63,19 @@ impl<'a> ManagedHostStateChangeMessage<'a> {
     }
 }
 
+impl<Id: Display, S: PublishableState> Serialize for StateChangeMessage<'_, Id, S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry(
+            &format!("{}_id", S::OBJECT_TYPE),
+            &self.object_id.to_string(),
+        )?;
+        map.serialize_entry("timestamp", &self.timestamp)?;
+        map.serialize_entry(S::STATE_FIELD, self.state)?;
+        map.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use model::machine::InstanceState;
@@ -100,6 +120,30 @@ mod tests {
         assert!(state_obj.get("instance_state").is_some());
     }
 
+    #[test]
+    fn test_switch_state_serialization() {
+        use carbide_uuid::switch::{SwitchId, SwitchIdSource, SwitchType};
+        use model::switch::SwitchControllerState;
+
+        let switch_id = SwitchId::new(
+            SwitchIdSource::ProductBoardChassisSerial,
+            [0; 32],
+            SwitchType::NvLink,
+        );
+        let state = SwitchControllerState::Ready;
+
+        let message = StateChangeMessage::new(&switch_id, &state, Utc::now());
+        let json = message.to_json_bytes().unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(
+            parsed.get("switch_id").unwrap().as_str().unwrap(),
+            switch_id.to_string()
+        );
+        let state_obj = parsed.get("switch_state").unwrap();
+        assert_eq!(state_obj.get("state").unwrap(), "ready");
+    }
+
     #[test]
     fn test_timestamp_is_rfc3339() {
         let machine_id = test_machine_id();
@@ -15,10 +15,26 @@
  * limitations under the License.
  */
 
-//! MQTT state change hook for publishing ManagedHostState transitions.
+//! MQTT state change hook for publishing state controller transitions.
 //!
-//! This module implements the AsyncAPI specification defined in `carbide.yaml`,
-//! publishing state changes to `carbide/v1/machine/{machineId}/state` over MQTT 3.1.1.
+//! State changes are published over MQTT 3.1.1 to `carbide/v1/{objectType}/{objectId}/state`.
+//! Every message is a JSON object which carries the ID of the object in the field
+//! `{objectType}_id`, the `timestamp` of the state change, and the new state in
+//! a state controller specific field:
+//!
+//! | State controller | Topic                                                      | State field             |
+//! |------------------|------------------------------------------------------------|-------------------------|
+//! | Machine          | `carbide/v1/machine/{machineId}/state`                     | `managed_host_state`    |
+//! | Switch           | `carbide/v1/switch/{switchId}/state`                       | `switch_state`          |
+//! | Power shelf      | `carbide/v1/power_shelf/{powerShelfId}/state`              | `power_shelf_state`     |
+//! | Rack             | `carbide/v1/rack/{rackId}/state`                           | `rack_state`            |
+//! | Network segment  | `carbide/v1/network_segment/{segmentId}/state`             | `network_segment_state` |
+//! | IB partition     | `carbide/v1/ib_partition/{partitionId}/state`              | `ib_partition_state`    |
+//! | DPA interface    | `carbide/v1/dpa_interface/{interfaceId}/state`             | `dpa_interface_state`   |
+//! | SPDM attestation | `carbide/v1/spdm_attestation/{machineId},{deviceId}/state` | `attestation_state`     |
+//!
+//! States use their native serde serialization: The `state` field carries the
+//! lowercase name of the state, and further fields carry state specific details.
 
 pub mod hook;
 pub mod message;
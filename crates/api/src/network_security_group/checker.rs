@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use tokio::sync::oneshot;
+
+use crate::CarbideResult;
+use crate::cfg::file::NetworkSecurityGroupConfig;
+
+/// `NetworkSecurityGroupExpansionChecker` periodically expands the object
+/// references in the rules of all network security groups and records
+/// whether the expanded rule sets still fit within the configured limit.
+///
+/// Changes to VPC prefixes are checked synchronously by the API handlers.
+/// The checker catches everything else that can change the prefixes of a
+/// referenced object, and keeps the status reported by
+/// `GetNetworkSecurityGroupPropagationStatus` up to date.
+pub struct NetworkSecurityGroupExpansionChecker {
+    database_connection: sqlx::PgPool,
+    config: NetworkSecurityGroupConfig,
+}
+
+impl NetworkSecurityGroupExpansionChecker {
+    /// Create a NetworkSecurityGroupExpansionChecker
+    pub fn new(database_connection: sqlx::PgPool, config: NetworkSecurityGroupConfig) -> Self {
+        NetworkSecurityGroupExpansionChecker {
+            database_connection,
+            config,
+        }
+    }
+
+    /// Start the NetworkSecurityGroupExpansionChecker and return a [sending channel](tokio::sync::oneshot::Sender)
+    /// that will stop the NetworkSecurityGroupExpansionChecker when dropped.
+    pub fn start(self) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+
+        if self.config.expansion_checker.enabled {
+            tokio::task::Builder::new()
+                .name("nsg_expansion_checker")
+                .spawn(async move { self.run(stop_receiver).await })?;
+        }
+
+        Ok(stop_sender)
+    }
+
+    async fn run(&self, mut stop_receiver: oneshot::Receiver<i32>) {
+        loop {
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("NetworkSecurityGroupExpansionChecker error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.expansion_checker.run_interval) => {},
+                _ = &mut stop_receiver => {
+                    tracing::info!("NetworkSecurityGroupExpansionChecker stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    pub async fn run_single_iteration(&self) -> CarbideResult<()> {
+        let limit = self.config.max_network_security_group_size as usize;
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+
+        let nsgs = db::network_security_group::find_with_object_references(&mut txn).await?;
+        let mut num_overflows = 0;
+
+        for nsg in nsgs.iter() {
+            if let Some(error) = super::check_network_security_group(&mut txn, nsg, limit).await? {
+                num_overflows += 1;
+                tracing::warn!(
+                    network_security_group_id = %nsg.id,
+                    tenant_organization_id = %nsg.tenant_organization_id,
+                    %error,
+                    "Expanded rules of network security group exceed the limit"
+                );
+            }
+        }
+
+        tracing::debug!(
+            num_checked = nsgs.len(),
+            num_overflows,
+            "Checked expansion of network security groups"
+        );
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
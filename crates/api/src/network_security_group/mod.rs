@@ -0,0 +1,696 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Expansion of object references in the rules of network security groups.
+//!
+//! Rules can reference VPCs, VPC prefixes and network segments instead of
+//! literal prefixes. The references are expanded into the prefixes of the
+//! referenced objects before the rules are sent to DPUs. Since the prefixes
+//! of an object can change without the NSG ever being touched, the expanded
+//! rule count is re-checked whenever prefixes are added to a VPC, and
+//! periodically by the [`checker::NetworkSecurityGroupExpansionChecker`].
+//! A rule set which still ends up beyond the limit is degraded to the rules
+//! which fit, see [`fit_expanded_rules`], instead of failing the DPU config.
+//!
+//! The expanded rules are also used to explain which rule the DPU applies to
+//! a given flow, see [`explain_flow`].
+
+use std::collections::HashMap;
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use carbide_uuid::vpc::VpcId;
+use db::ObjectColumnFilter;
+use ipnetwork::IpNetwork;
+use itertools::Itertools;
+use model::network_security_group::flow::{NetworkSecurityGroupFlow, find_matching_rule};
+use model::network_security_group::{
+    NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleAction,
+    NetworkSecurityGroupRuleNet,
+};
+use model::network_segment::NetworkSegmentSearchConfig;
+use model::tenant::TenantOrganizationId;
+use sqlx::PgConnection;
+
+use crate::CarbideError;
+
+pub mod checker;
+
+/// A rule with its source and destination expanded into prefixes
+#[derive(Clone, Debug)]
+pub struct ExpandedRule {
+    pub rule: NetworkSecurityGroupRule,
+    pub src_prefixes: Vec<IpNetwork>,
+    pub dst_prefixes: Vec<IpNetwork>,
+}
+
+impl ExpandedRule {
+    /// The number of rules the DPU has to install for this rule
+    /// (src port range * dst port range * src prefix list * dst prefix list)
+    pub fn rule_count(&self) -> u64 {
+        // Negative ranges are caught when we convert from rpc to internal struct.
+        let port_range = |start: Option<u32>, end: Option<u32>| {
+            u64::from(
+                end.unwrap_or_default()
+                    .saturating_sub(start.unwrap_or_default()),
+            ) + 1
+        };
+
+        port_range(self.rule.src_port_start, self.rule.src_port_end)
+            .saturating_mul(port_range(self.rule.dst_port_start, self.rule.dst_port_end))
+            .saturating_mul(self.src_prefixes.len() as u64)
+            .saturating_mul(self.dst_prefixes.len() as u64)
+    }
+}
+
+impl TryFrom<ExpandedRule> for rpc::ResolvedNetworkSecurityGroupRule {
+    type Error = RpcDataConversionError;
+
+    fn try_from(expanded: ExpandedRule) -> Result<Self, Self::Error> {
+        Ok(rpc::ResolvedNetworkSecurityGroupRule {
+            src_prefixes: expanded
+                .src_prefixes
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            dst_prefixes: expanded
+                .dst_prefixes
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            rule: Some(expanded.rule.try_into()?),
+        })
+    }
+}
+
+/// Returns the total number of rules the DPU has to install for a rule set
+pub fn expanded_rule_count(rules: &[ExpandedRule]) -> u64 {
+    rules
+        .iter()
+        .fold(0u64, |total, rule| total.saturating_add(rule.rule_count()))
+}
+
+/// Returns an error if an expanded rule count exceeds the limit
+pub fn check_expanded_rule_count(count: u64, limit: usize) -> Result<(), CarbideError> {
+    if count > limit as u64 {
+        return Err(CarbideError::InvalidArgument(format!(
+            "expanded rule set contains {count} rules, which is more than the {limit} maximum number of rules"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Reduces an expanded rule set which exceeds `limit` to the rules which fit.
+///
+/// Rules are considered in the order the DPU evaluates them, and rules which
+/// would exceed the limit are dropped. Once a deny rule got dropped, so are all
+/// permit rules with a lower priority, since they could permit flows the deny
+/// rule was meant to block. The degraded rule set never permits a flow which
+/// the full rule set denies.
+///
+/// Returns the rules which are kept and the rules which are dropped, each in
+/// their original order.
+pub fn fit_expanded_rules(
+    rules: Vec<ExpandedRule>,
+    limit: usize,
+) -> (Vec<ExpandedRule>, Vec<ExpandedRule>) {
+    let mut order: Vec<usize> = (0..rules.len()).collect();
+    order.sort_by_key(|&idx| rules[idx].rule.priority);
+
+    let mut keep = vec![false; rules.len()];
+    let mut total = 0u64;
+    let mut deny_dropped = false;
+    for idx in order {
+        let rule = &rules[idx];
+        let permit = rule.rule.action == NetworkSecurityGroupRuleAction::Permit;
+        if deny_dropped && permit {
+            continue;
+        }
+
+        let count = rule.rule_count();
+        if total.saturating_add(count) > limit as u64 {
+            deny_dropped |= !permit;
+            continue;
+        }
+
+        total += count;
+        keep[idx] = true;
+    }
+
+    rules
+        .into_iter()
+        .zip(keep)
+        .partition_map(|(rule, keep)| match keep {
+            true => itertools::Either::Left(rule),
+            false => itertools::Either::Right(rule),
+        })
+}
+
+/// Expands the rules of a network security group into the rules the DPU
+/// applies. If they exceed the limit, the rules which fit are returned along
+/// with a description of the problem.
+pub async fn expand_network_security_group(
+    txn: &mut PgConnection,
+    nsg: &NetworkSecurityGroup,
+    limit: usize,
+) -> Result<(Vec<ExpandedRule>, Option<String>), CarbideError> {
+    let rules = expand_rules(txn, Some(&nsg.tenant_organization_id), &nsg.rules).await?;
+
+    let Err(e) = check_expanded_rule_count(expanded_rule_count(&rules), limit) else {
+        return Ok((rules, None));
+    };
+
+    let (rules, dropped) = fit_expanded_rules(rules, limit);
+    Ok((rules, Some(describe_overflow(e, &dropped))))
+}
+
+fn describe_overflow(e: CarbideError, dropped: &[ExpandedRule]) -> String {
+    format!(
+        "{e}; rules which don't fit: {}",
+        dropped
+            .iter()
+            .map(|rule| rule.rule.id.as_deref().unwrap_or("<unnamed>"))
+            .join(", ")
+    )
+}
+
+/// Returns the prefixes the source or destination of a rule matches.
+///
+/// Object references only expand into the prefixes of the IP version of
+/// the rule. Objects which no longer exist expand into no prefixes.
+pub async fn resolve_rule_net(
+    txn: &mut PgConnection,
+    net: &NetworkSecurityGroupRuleNet,
+    ipv6: bool,
+) -> Result<Vec<IpNetwork>, CarbideError> {
+    let prefixes: Vec<IpNetwork> = match net {
+        NetworkSecurityGroupRuleNet::Prefix(p) => return Ok(vec![*p]),
+        NetworkSecurityGroupRuleNet::VpcId(vpc_id) => {
+            let vpc_ids = vec![*vpc_id];
+            let vpc_prefixes = db::vpc_prefix::find_by_vpcs(txn, &vpc_ids).await?;
+            // Only returns the segment prefixes which aren't already
+            // covered by one of the VPC prefixes.
+            let segment_prefixes = db::network_prefix::find_by_vpc(txn, *vpc_id).await?;
+
+            vpc_prefixes
+                .into_iter()
+                .map(|p| p.config.prefix)
+                .chain(segment_prefixes.into_iter().map(|p| p.prefix))
+                .collect()
+        }
+        NetworkSecurityGroupRuleNet::VpcPrefixId(vpc_prefix_id) => db::vpc_prefix::get_by_id(
+            txn,
+            ObjectColumnFilter::One(db::vpc_prefix::IdColumn, vpc_prefix_id),
+        )
+        .await?
+        .into_iter()
+        .map(|p| p.config.prefix)
+        .collect(),
+        NetworkSecurityGroupRuleNet::NetworkSegmentId(segment_id) => db::network_prefix::find_by(
+            txn,
+            ObjectColumnFilter::One(db::network_prefix::SegmentIdColumn, segment_id),
+        )
+        .await?
+        .into_iter()
+        .map(|p| p.prefix)
+        .collect(),
+    };
+
+    Ok(prefixes
+        .into_iter()
+        .filter(|p| p.is_ipv6() == ipv6)
+        .unique()
+        .collect())
+}
+
+/// Returns the VPC a referenced object belongs to, or `None` if the net is
+/// a literal prefix or the object doesn't exist (anymore).
+pub async fn referenced_vpc_id(
+    txn: &mut PgConnection,
+    net: &NetworkSecurityGroupRuleNet,
+) -> Result<Option<VpcId>, CarbideError> {
+    Ok(match net {
+        NetworkSecurityGroupRuleNet::Prefix(_) => None,
+        NetworkSecurityGroupRuleNet::VpcId(vpc_id) => db::vpc::find_by(
+            &mut *txn,
+            ObjectColumnFilter::One(db::vpc::IdColumn, vpc_id),
+        )
+        .await?
+        .first()
+        .map(|vpc| vpc.id),
+        NetworkSecurityGroupRuleNet::VpcPrefixId(vpc_prefix_id) => db::vpc_prefix::get_by_id(
+            txn,
+            ObjectColumnFilter::One(db::vpc_prefix::IdColumn, vpc_prefix_id),
+        )
+        .await?
+        .first()
+        .map(|p| p.vpc_id),
+        NetworkSecurityGroupRuleNet::NetworkSegmentId(segment_id) => db::network_segment::find_by(
+            txn,
+            ObjectColumnFilter::One(db::network_segment::IdColumn, segment_id),
+            NetworkSegmentSearchConfig::default(),
+        )
+        .await?
+        .first()
+        .and_then(|segment| segment.vpc_id),
+    })
+}
+
+/// Returns whether a tenant is allowed to reference a VPC in its rules.
+/// This is the case for VPCs owned by the tenant and VPCs which are peered
+/// with at least one of the VPCs of the tenant.
+async fn is_vpc_accessible(
+    txn: &mut PgConnection,
+    tenant_organization_id: &TenantOrganizationId,
+    vpc_id: VpcId,
+) -> Result<bool, CarbideError> {
+    let tenant_organization_id = tenant_organization_id.to_string();
+
+    let vpcs = db::vpc::find_by(
+        &mut *txn,
+        ObjectColumnFilter::One(db::vpc::IdColumn, &vpc_id),
+    )
+    .await?;
+    if vpcs
+        .iter()
+        .any(|vpc| vpc.tenant_organization_id == tenant_organization_id)
+    {
+        return Ok(true);
+    }
+
+    let peer_ids = db::vpc_peering::get_vpc_peer_ids(txn, vpc_id).await?;
+    if peer_ids.is_empty() {
+        return Ok(false);
+    }
+
+    let peers = db::vpc::find_by(
+        &mut *txn,
+        ObjectColumnFilter::List(db::vpc::IdColumn, &peer_ids),
+    )
+    .await?;
+
+    Ok(peers
+        .iter()
+        .any(|vpc| vpc.tenant_organization_id == tenant_organization_id))
+}
+
+/// Expands the object references in a set of rules into prefixes.
+///
+/// If a tenant is given, references to objects in VPCs the tenant is no
+/// longer allowed to access (e.g. because a peering got removed) expand
+/// into no prefixes. Rules defined by the site operator are not restricted.
+pub async fn expand_rules(
+    txn: &mut PgConnection,
+    tenant_organization_id: Option<&TenantOrganizationId>,
+    rules: &[NetworkSecurityGroupRule],
+) -> Result<Vec<ExpandedRule>, CarbideError> {
+    let mut accessible_vpcs = HashMap::<VpcId, bool>::new();
+    let mut expanded = Vec::with_capacity(rules.len());
+
+    for rule in rules {
+        let mut prefixes = Vec::with_capacity(2);
+
+        for net in [&rule.src_net, &rule.dst_net] {
+            if let Some(tenant_organization_id) = tenant_organization_id
+                && net.is_object_reference()
+            {
+                let accessible = match referenced_vpc_id(txn, net).await? {
+                    None => false,
+                    Some(vpc_id) => match accessible_vpcs.get(&vpc_id) {
+                        Some(accessible) => *accessible,
+                        None => {
+                            let accessible =
+                                is_vpc_accessible(txn, tenant_organization_id, vpc_id).await?;
+                            accessible_vpcs.insert(vpc_id, accessible);
+                            accessible
+                        }
+                    },
+                };
+
+                if !accessible {
+                    tracing::warn!(
+                        rule_id = rule.id.as_deref().unwrap_or_default(),
+                        %net,
+                        %tenant_organization_id,
+                        "Network security group rule references an object which is not accessible by the tenant"
+                    );
+                    prefixes.push(Vec::new());
+                    continue;
+                }
+            }
+
+            prefixes.push(resolve_rule_net(txn, net, rule.ipv6).await?);
+        }
+
+        let dst_prefixes = prefixes.pop().unwrap_or_default();
+        let src_prefixes = prefixes.pop().unwrap_or_default();
+        expanded.push(ExpandedRule {
+            rule: rule.clone(),
+            src_prefixes,
+            dst_prefixes,
+        });
+    }
+
+    Ok(expanded)
+}
+
+/// Expands a set of rules into the form which is sent to DPUs
+pub async fn resolve_rules(
+    txn: &mut PgConnection,
+    tenant_organization_id: Option<&TenantOrganizationId>,
+    rules: &[NetworkSecurityGroupRule],
+) -> Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError> {
+    expand_rules(txn, tenant_organization_id, rules)
+        .await?
+        .into_iter()
+        .map(|rule| {
+            rpc::ResolvedNetworkSecurityGroupRule::try_from(rule).map_err(CarbideError::from)
+        })
+        .collect()
+}
+
+/// Validates the object references in the rules of a network security group
+/// which is about to be created or updated.
+///
+/// All referenced objects must exist and belong to a VPC which the tenant
+/// owns or is peered with, and the rule set must not exceed the limit once
+/// expanded. Returns the expanded rule count.
+pub async fn validate_object_references(
+    txn: &mut PgConnection,
+    tenant_organization_id: &TenantOrganizationId,
+    rules: &[NetworkSecurityGroupRule],
+    limit: usize,
+) -> Result<u64, CarbideError> {
+    let mut accessible_vpcs = HashMap::<VpcId, bool>::new();
+
+    for net in rules
+        .iter()
+        .flat_map(|rule| [&rule.src_net, &rule.dst_net])
+        .filter(|net| net.is_object_reference())
+    {
+        let vpc_id = referenced_vpc_id(txn, net).await?.ok_or_else(|| {
+            CarbideError::InvalidArgument(format!(
+                "referenced object `{net}` does not exist or does not belong to a VPC"
+            ))
+        })?;
+
+        let accessible = match accessible_vpcs.get(&vpc_id) {
+            Some(accessible) => *accessible,
+            None => {
+                let accessible = is_vpc_accessible(txn, tenant_organization_id, vpc_id).await?;
+                accessible_vpcs.insert(vpc_id, accessible);
+                accessible
+            }
+        };
+
+        if !accessible {
+            return Err(CarbideError::InvalidArgument(format!(
+                "referenced object `{net}` belongs to VPC {vpc_id}, which is neither owned by \
+                tenant {tenant_organization_id} nor peered with one of its VPCs"
+            )));
+        }
+    }
+
+    let count = expanded_rule_count(&expand_rules(txn, Some(tenant_organization_id), rules).await?);
+    check_expanded_rule_count(count, limit)?;
+
+    Ok(count)
+}
+
+/// Expands the rules of a network security group, records the result and
+/// returns a description of the problem if the expanded rule set exceeds
+/// the limit.
+pub async fn check_network_security_group(
+    txn: &mut PgConnection,
+    nsg: &NetworkSecurityGroup,
+    limit: usize,
+) -> Result<Option<String>, CarbideError> {
+    let rules = expand_rules(txn, Some(&nsg.tenant_organization_id), &nsg.rules).await?;
+    let count = expanded_rule_count(&rules);
+
+    let error = check_expanded_rule_count(count, limit)
+        .err()
+        .map(|e| describe_overflow(e, &fit_expanded_rules(rules, limit).1));
+
+    db::network_security_group::set_expansion_status(
+        txn,
+        &nsg.id,
+        &nsg.version,
+        count,
+        error.as_deref(),
+    )
+    .await?;
+
+    Ok(error)
+}
+
+/// Re-checks all network security groups which reference a VPC, or any of
+/// its prefixes or segments, after prefixes got added to the VPC.
+///
+/// Returns an error if the rules of any of those network security groups
+/// would expand beyond the limit, so that the change can be refused.
+pub async fn recheck_vpc_references(
+    txn: &mut PgConnection,
+    vpc_id: VpcId,
+    limit: usize,
+) -> Result<(), CarbideError> {
+    for nsg in db::network_security_group::find_with_object_references(txn).await? {
+        let mut references_vpc = false;
+        for net in nsg
+            .rules
+            .iter()
+            .flat_map(|rule| [&rule.src_net, &rule.dst_net])
+            .filter(|net| net.is_object_reference())
+        {
+            if referenced_vpc_id(txn, net).await? == Some(vpc_id) {
+                references_vpc = true;
+                break;
+            }
+        }
+
+        if !references_vpc {
+            continue;
+        }
+
+        if let Some(error) = check_network_security_group(txn, &nsg, limit).await? {
+            return Err(CarbideError::InvalidArgument(format!(
+                "the change would exceed the limits of network security group {}, which references VPC {vpc_id}: {error}",
+                nsg.id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluates a flow against the rules that apply to an instance interface,
+/// in the same order the DPU evaluates its ACLs: site-wide policy overrides
+/// first, then the rules of the network security group of the interface.
+///
+/// If a network security group applies and none of its rules match, the flow
+/// is denied. Without a network security group, flows which don't match a
+/// policy override are permitted.
+///
+/// * `policy_overrides`       - The site-wide policy override rules
+/// * `network_security_group` - The network security group of the interface
+///   and whether it is attached to the instance or inherited from the VPC
+/// * `flow`                   - The flow to evaluate
+/// * `limit`                  - The maximum size of an expanded rule set, beyond
+///   which the DPU only applies the rules which fit
+pub async fn explain_flow(
+    txn: &mut PgConnection,
+    policy_overrides: &[NetworkSecurityGroupRule],
+    network_security_group: Option<(rpc::NetworkSecurityGroupSource, &NetworkSecurityGroup)>,
+    flow: &NetworkSecurityGroupFlow,
+    limit: usize,
+) -> Result<rpc::ExplainNetworkSecurityGroupFlowResponse, CarbideError> {
+    let mut response = rpc::ExplainNetworkSecurityGroupFlowResponse {
+        action: rpc::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+        rule_source: rpc::NetworkSecurityGroupFlowRuleSource::NsgFlowRuleSourceDefault.into(),
+        network_security_group_source: rpc::NetworkSecurityGroupSource::NsgSourceNone.into(),
+        network_security_group_id: None,
+        network_security_group_version: None,
+        matched_rule: None,
+        stateful_egress: false,
+    };
+
+    if let Some((source, nsg)) = network_security_group {
+        response.network_security_group_source = source.into();
+        response.network_security_group_id = Some(nsg.id.to_string());
+        response.network_security_group_version = Some(nsg.version.to_string());
+        response.stateful_egress = nsg.stateful_egress;
+    }
+
+    let policy_overrides = expand_rules(txn, None, policy_overrides).await?;
+    let mut decision = find_matching_expanded_rule(&policy_overrides, flow).map(|rule| {
+        (
+            rpc::NetworkSecurityGroupFlowRuleSource::NsgFlowRuleSourcePolicyOverride,
+            rule,
+        )
+    });
+
+    if decision.is_none()
+        && let Some((_, nsg)) = network_security_group
+    {
+        let (rules, _) = expand_network_security_group(txn, nsg, limit).await?;
+
+        match find_matching_expanded_rule(&rules, flow) {
+            Some(rule) => {
+                decision = Some((
+                    rpc::NetworkSecurityGroupFlowRuleSource::NsgFlowRuleSourceNsg,
+                    rule,
+                ))
+            }
+            // Every NSG ACL ends with a rule that denies everything else
+            None => response.action = rpc::NetworkSecurityGroupRuleAction::NsgRuleActionDeny.into(),
+        }
+    }
+
+    if let Some((rule_source, rule)) = decision {
+        response.rule_source = rule_source.into();
+        response.action = rpc::NetworkSecurityGroupRuleAction::from(rule.action.clone()).into();
+        response.matched_rule = Some(rule.clone().try_into()?);
+    }
+
+    Ok(response)
+}
+
+fn find_matching_expanded_rule<'a>(
+    rules: &'a [ExpandedRule],
+    flow: &NetworkSecurityGroupFlow,
+) -> Option<&'a NetworkSecurityGroupRule> {
+    find_matching_rule(
+        rules.iter().map(|r| {
+            (
+                &r.rule,
+                r.src_prefixes.as_slice(),
+                r.dst_prefixes.as_slice(),
+            )
+        }),
+        flow,
+    )
+    .map(|idx| &rules[idx].rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use model::network_security_group::{
+        NetworkSecurityGroupRuleAction, NetworkSecurityGroupRuleDirection,
+        NetworkSecurityGroupRuleProtocol,
+    };
+
+    use super::*;
+
+    fn rule(
+        src_ports: Option<(u32, u32)>,
+        dst_ports: Option<(u32, u32)>,
+    ) -> NetworkSecurityGroupRule {
+        NetworkSecurityGroupRule {
+            id: Some("rule".to_string()),
+            src_net: NetworkSecurityGroupRuleNet::VpcId(
+                "60d92a18-e56b-11ef-8ecd-ef90f290abf4".parse().unwrap(),
+            ),
+            dst_net: NetworkSecurityGroupRuleNet::Prefix("0.0.0.0/0".parse().unwrap()),
+            direction: NetworkSecurityGroupRuleDirection::Ingress,
+            ipv6: false,
+            src_port_start: src_ports.map(|(start, _)| start),
+            src_port_end: src_ports.map(|(_, end)| end),
+            dst_port_start: dst_ports.map(|(start, _)| start),
+            dst_port_end: dst_ports.map(|(_, end)| end),
+            protocol: NetworkSecurityGroupRuleProtocol::Tcp,
+            action: NetworkSecurityGroupRuleAction::Permit,
+            priority: 1,
+        }
+    }
+
+    #[test]
+    fn test_expanded_rule_count() {
+        let expanded = vec![
+            ExpandedRule {
+                rule: rule(Some((80, 81)), Some((443, 445))),
+                src_prefixes: vec![
+                    "192.0.2.0/25".parse().unwrap(),
+                    "192.0.2.128/25".parse().unwrap(),
+                ],
+                dst_prefixes: vec!["0.0.0.0/0".parse().unwrap()],
+            },
+            ExpandedRule {
+                rule: rule(None, None),
+                src_prefixes: vec!["198.51.100.0/24".parse().unwrap()],
+                dst_prefixes: vec!["0.0.0.0/0".parse().unwrap()],
+            },
+            // References which don't resolve to any prefix don't add rules.
+            ExpandedRule {
+                rule: rule(None, None),
+                src_prefixes: vec![],
+                dst_prefixes: vec!["0.0.0.0/0".parse().unwrap()],
+            },
+        ];
+
+        assert_eq!(expanded[0].rule_count(), 12);
+        assert_eq!(expanded_rule_count(&expanded), 13);
+
+        check_expanded_rule_count(13, 13).unwrap();
+        check_expanded_rule_count(14, 13).unwrap_err();
+    }
+
+    #[test]
+    fn test_fit_expanded_rules() {
+        let expanded = |id: &str, action, priority, prefixes: usize| ExpandedRule {
+            rule: NetworkSecurityGroupRule {
+                id: Some(id.to_string()),
+                action,
+                priority,
+                ..rule(None, None)
+            },
+            src_prefixes: vec!["192.0.2.0/24".parse().unwrap(); prefixes],
+            dst_prefixes: vec!["0.0.0.0/0".parse().unwrap()],
+        };
+        let ids = |rules: &[ExpandedRule]| {
+            rules
+                .iter()
+                .map(|rule| rule.rule.id.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Permit rules which don't fit are dropped on their own.
+        let (kept, dropped) = fit_expanded_rules(
+            vec![
+                expanded("big", NetworkSecurityGroupRuleAction::Permit, 2, 10),
+                expanded("first", NetworkSecurityGroupRuleAction::Permit, 1, 2),
+                expanded("last", NetworkSecurityGroupRuleAction::Permit, 3, 1),
+            ],
+            5,
+        );
+        assert_eq!(ids(&kept), ["first", "last"]);
+        assert_eq!(ids(&dropped), ["big"]);
+
+        // A dropped deny rule takes all lower priority permit rules with it.
+        let (kept, dropped) = fit_expanded_rules(
+            vec![
+                expanded("first", NetworkSecurityGroupRuleAction::Permit, 1, 2),
+                expanded("big", NetworkSecurityGroupRuleAction::Deny, 2, 10),
+                expanded("permit", NetworkSecurityGroupRuleAction::Permit, 3, 1),
+                expanded("deny", NetworkSecurityGroupRuleAction::Deny, 4, 1),
+            ],
+            5,
+        );
+        assert_eq!(ids(&kept), ["first", "deny"]);
+        assert_eq!(ids(&dropped), ["big", "permit"]);
+    }
+}
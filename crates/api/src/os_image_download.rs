@@ -0,0 +1,213 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Short-lived download tokens for OS images served by carbide-pxe.
+//!
+//! Instead of passing the tenant's registry credentials on the kernel
+//! command line, the qcow imager is pointed at carbide-pxe with a token
+//! that names the machine, the interface it PXE booted from, the OS image
+//! and an expiry time, signed with a key derived from the site-wide root
+//! secret. carbide-pxe passes the token back to the API, which verifies it
+//! and streams the image.
+
+use std::str::FromStr;
+
+use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+use chrono::{DateTime, TimeDelta, Utc};
+use forge_secrets::credentials::CredentialProvider;
+use uuid::Uuid;
+
+use crate::signed_token::{SigningKey, TokenError};
+
+// Versioned so that the token format can be changed without
+// accepting tokens signed for a different format.
+pub const KDF_INFO: &str = "os-image-download:v2";
+
+/// Signs download tokens for the OS images of machines being provisioned
+pub struct DownloadTokenSigner<'a> {
+    credential_provider: &'a dyn CredentialProvider,
+    lifetime: std::time::Duration,
+}
+
+impl<'a> DownloadTokenSigner<'a> {
+    pub fn new(
+        credential_provider: &'a dyn CredentialProvider,
+        lifetime: std::time::Duration,
+    ) -> Self {
+        Self {
+            credential_provider,
+            lifetime,
+        }
+    }
+
+    /// Returns a signed token that lets `machine_id` download `os_image_id`
+    /// through `interface_id` until the configured lifetime has passed.
+    pub async fn sign(
+        &self,
+        machine_id: MachineId,
+        interface_id: MachineInterfaceId,
+        os_image_id: Uuid,
+    ) -> Result<String, eyre::Report> {
+        let key = SigningKey::fetch(self.credential_provider, KDF_INFO).await?;
+        let token = OsImageDownloadToken {
+            machine_id,
+            interface_id,
+            os_image_id,
+            expires_at: Utc::now() + TimeDelta::from_std(self.lifetime)?,
+        };
+        Ok(token.sign(&key))
+    }
+}
+
+/// Grants a single machine access to a single OS image until `expires_at`.
+/// The image is downloaded through the interface the machine PXE booted from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsImageDownloadToken {
+    pub machine_id: MachineId,
+    pub interface_id: MachineInterfaceId,
+    pub os_image_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl OsImageDownloadToken {
+    /// Encodes the token as `{machine_id}.{interface_id}.{os_image_id}.{expiry}.{signature}`.
+    /// None of the fields contain dots or characters which need escaping in
+    /// URLs or on the kernel command line.
+    pub fn sign(&self, key: &SigningKey) -> String {
+        key.sign(&self.payload())
+    }
+
+    /// Decodes a token produced by [`Self::sign`], checking its signature and
+    /// that it has not expired at `now`.
+    pub fn verify(token: &str, key: &SigningKey, now: DateTime<Utc>) -> Result<Self, TokenError> {
+        let payload = key.verify(token)?;
+
+        let mut fields = payload.split('.');
+        let (Some(machine_id), Some(interface_id), Some(os_image_id), Some(expires_at), None) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(TokenError::Malformed);
+        };
+        let token = Self {
+            machine_id: MachineId::from_str(machine_id).map_err(|_| TokenError::Malformed)?,
+            interface_id: MachineInterfaceId::from_str(interface_id)
+                .map_err(|_| TokenError::Malformed)?,
+            os_image_id: Uuid::from_str(os_image_id).map_err(|_| TokenError::Malformed)?,
+            expires_at: expires_at
+                .parse()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .ok_or(TokenError::Malformed)?,
+        };
+
+        if token.expires_at <= now {
+            return Err(TokenError::Expired);
+        }
+        Ok(token)
+    }
+
+    fn payload(&self) -> String {
+        format!(
+            "{}.{}.{}.{}",
+            self.machine_id,
+            self.interface_id,
+            self.os_image_id,
+            self.expires_at.timestamp()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(now: DateTime<Utc>) -> OsImageDownloadToken {
+        OsImageDownloadToken {
+            machine_id: MachineId::from_str(
+                "fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg",
+            )
+            .unwrap(),
+            interface_id: MachineInterfaceId::from(Uuid::new_v4()),
+            os_image_id: Uuid::new_v4(),
+            expires_at: DateTime::from_timestamp(now.timestamp(), 0).unwrap() + TimeDelta::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let now = Utc::now();
+        let token = token(now);
+
+        let signed = token.sign(&key);
+        assert_eq!(OsImageDownloadToken::verify(&signed, &key, now), Ok(token));
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_token() {
+        let key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let now = Utc::now();
+        let token = token(now);
+
+        let signed = token.sign(&key);
+        assert_eq!(
+            OsImageDownloadToken::verify(&signed, &key, token.expires_at),
+            Err(TokenError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_other_key() {
+        let now = Utc::now();
+        let signed = token(now).sign(&SigningKey::derive(b"site-root", KDF_INFO).unwrap());
+        let other_key = SigningKey::derive(b"other-site-root", KDF_INFO).unwrap();
+
+        assert_eq!(
+            OsImageDownloadToken::verify(&signed, &other_key, now),
+            Err(TokenError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_token() {
+        let key = SigningKey::derive(b"site-root", KDF_INFO).unwrap();
+        let now = Utc::now();
+        let token = token(now);
+        let signed = token.sign(&key);
+
+        // Extend the expiry while keeping the signature
+        let (payload, signature) = signed.rsplit_once('.').unwrap();
+        let (prefix, _) = payload.rsplit_once('.').unwrap();
+        let tampered = format!(
+            "{prefix}.{}.{signature}",
+            (token.expires_at + TimeDelta::days(1)).timestamp()
+        );
+        assert_eq!(
+            OsImageDownloadToken::verify(&tampered, &key, now),
+            Err(TokenError::InvalidSignature)
+        );
+
+        assert_eq!(
+            OsImageDownloadToken::verify("not-a-token", &key, now),
+            Err(TokenError::Malformed)
+        );
+    }
+}
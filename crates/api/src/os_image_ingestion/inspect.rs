@@ -0,0 +1,580 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Discovery of the partitions and filesystems of a disk image, and checks
+//! of the filesystems declared in the attributes of an OS image.
+//!
+//! UUIDs and labels are reported the way `blkid` reports them, since this is
+//! what the imaging script uses to look up the declared filesystems.
+
+use eyre::{WrapErr, bail};
+use model::storage::OsImageAttributes;
+use uuid::Uuid;
+
+const SECTOR_SIZE: u64 = 512;
+/// Enough to contain the superblocks of all supported filesystems
+const PROBE_SIZE: u64 = 0x11000;
+const MAX_GPT_ENTRIES: u32 = 1024;
+
+/// Random access to the content of a disk
+pub trait ReadAt {
+    /// The size of the disk in bytes
+    fn size(&self) -> u64;
+    /// Fills `buf` with the content of the disk starting at `offset`
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> eyre::Result<()>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilesystemKind {
+    Ext,
+    Xfs,
+    Btrfs,
+    Vfat,
+}
+
+/// A filesystem found on a disk image
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filesystem {
+    /// The number of the partition, or `None` for a filesystem which spans
+    /// the whole disk
+    pub partition: Option<u32>,
+    pub kind: FilesystemKind,
+    pub uuid: Option<String>,
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DiskLayout {
+    pub filesystems: Vec<Filesystem>,
+    /// Whether the disk contains LVM physical volumes. Filesystems on
+    /// logical volumes can't be discovered.
+    pub has_lvm: bool,
+}
+
+impl DiskLayout {
+    fn find_by_uuid(&self, uuid: &str) -> Option<&Filesystem> {
+        self.filesystems.iter().find(|fs| {
+            fs.uuid
+                .as_deref()
+                .is_some_and(|fs_uuid| fs_uuid.eq_ignore_ascii_case(uuid))
+        })
+    }
+
+    fn find_by_label(&self, label: &str) -> Option<&Filesystem> {
+        self.filesystems
+            .iter()
+            .find(|fs| fs.label.as_deref() == Some(label))
+    }
+}
+
+/// Returns the filesystems on the partitions of the disk, or on the disk
+/// itself if it has no partition table
+pub fn inspect(disk: &mut impl ReadAt) -> eyre::Result<DiskLayout> {
+    let partitions = match read_gpt(disk).wrap_err("failed to read GPT")? {
+        Some(partitions) => partitions,
+        None => read_mbr(disk)?,
+    };
+
+    let mut layout = DiskLayout::default();
+    if partitions.is_empty() {
+        probe(disk, None, 0, disk.size(), &mut layout)?;
+    }
+    for partition in partitions {
+        if partition.start.saturating_add(partition.size) > disk.size() {
+            bail!("partition {} exceeds the disk", partition.number);
+        }
+        probe(
+            disk,
+            Some(partition.number),
+            partition.start,
+            partition.size,
+            &mut layout,
+        )
+        .wrap_err_with(|| format!("failed to inspect partition {}", partition.number))?;
+    }
+    Ok(layout)
+}
+
+/// Checks that the filesystems and the boot disk which are declared in the
+/// attributes of an OS image can be used by the imaging script
+pub fn verify(layout: &DiskLayout, attributes: &OsImageAttributes) -> eyre::Result<()> {
+    let filesystem_ids = [
+        ("rootfs_id", &attributes.rootfs_id),
+        ("bootfs_id", &attributes.bootfs_id),
+        ("efifs_id", &attributes.efifs_id),
+    ];
+    for (name, id) in filesystem_ids {
+        if let Some(id) = id
+            && layout.find_by_uuid(id).is_none()
+        {
+            if name == "rootfs_id" && layout.has_lvm {
+                tracing::info!(
+                    rootfs_id = %id,
+                    "Root filesystem not found, but it may be on an LVM logical volume"
+                );
+                continue;
+            }
+            bail!("image does not contain a filesystem with the {name} {id}");
+        }
+    }
+
+    if let Some(label) = &attributes.rootfs_label
+        && layout.find_by_label(label).is_none()
+    {
+        if layout.has_lvm {
+            tracing::info!(
+                rootfs_label = %label,
+                "Root filesystem not found, but it may be on an LVM logical volume"
+            );
+        } else {
+            bail!("image does not contain a filesystem with the rootfs_label {label}");
+        }
+    }
+
+    // The boot disk is the device of the host the image gets written to.
+    // It can't be found in the image, but the imaging script can only use
+    // device paths.
+    if let Some(boot_disk) = &attributes.boot_disk
+        && (!boot_disk.starts_with("/dev/")
+            || boot_disk.len() == "/dev/".len()
+            || boot_disk.contains(char::is_whitespace))
+    {
+        bail!("boot_disk {boot_disk} is not a device path");
+    }
+
+    Ok(())
+}
+
+struct Partition {
+    number: u32,
+    start: u64,
+    size: u64,
+}
+
+fn read_gpt(disk: &mut impl ReadAt) -> eyre::Result<Option<Vec<Partition>>> {
+    if disk.size() < 2 * SECTOR_SIZE {
+        return Ok(None);
+    }
+    let mut header = [0u8; SECTOR_SIZE as usize];
+    disk.read_at(SECTOR_SIZE, &mut header)?;
+    if &header[0..8] != b"EFI PART" {
+        return Ok(None);
+    }
+
+    let entries_lba = le_u64(&header, 72);
+    let num_entries = le_u32(&header, 80);
+    let entry_size = le_u32(&header, 84);
+    if num_entries > MAX_GPT_ENTRIES || !(128..=4096).contains(&entry_size) {
+        bail!("invalid partition entry array");
+    }
+
+    let mut entries = vec![0u8; (num_entries * entry_size) as usize];
+    disk.read_at(entries_lba.saturating_mul(SECTOR_SIZE), &mut entries)?;
+
+    let mut partitions = Vec::new();
+    for (index, entry) in entries.chunks_exact(entry_size as usize).enumerate() {
+        if entry[0..16].iter().all(|byte| *byte == 0) {
+            continue;
+        }
+        let first_lba = le_u64(entry, 32);
+        let last_lba = le_u64(entry, 40);
+        if last_lba < first_lba {
+            bail!("invalid bounds of partition {}", index + 1);
+        }
+        partitions.push(Partition {
+            number: index as u32 + 1,
+            start: first_lba.saturating_mul(SECTOR_SIZE),
+            size: (last_lba - first_lba)
+                .saturating_add(1)
+                .saturating_mul(SECTOR_SIZE),
+        });
+    }
+    Ok(Some(partitions))
+}
+
+fn read_mbr(disk: &mut impl ReadAt) -> eyre::Result<Vec<Partition>> {
+    if disk.size() < SECTOR_SIZE {
+        return Ok(Vec::new());
+    }
+    let mut mbr = [0u8; SECTOR_SIZE as usize];
+    disk.read_at(0, &mut mbr)?;
+    if mbr[510..512] != [0x55, 0xaa] {
+        return Ok(Vec::new());
+    }
+
+    let entries = (0..4).map(|index| &mbr[446 + index * 16..446 + (index + 1) * 16]);
+    // Status bytes other than 0x00 and 0x80 mean that this is the boot
+    // sector of a filesystem rather than a partition table
+    if entries.clone().any(|entry| entry[0] & 0x7f != 0) {
+        return Ok(Vec::new());
+    }
+
+    Ok(entries
+        .enumerate()
+        // Skips empty and extended partitions
+        .filter(|(_, entry)| !matches!(entry[4], 0x00 | 0x05 | 0x0f | 0x85))
+        .map(|(index, entry)| Partition {
+            number: index as u32 + 1,
+            start: le_u32(entry, 8) as u64 * SECTOR_SIZE,
+            size: le_u32(entry, 12) as u64 * SECTOR_SIZE,
+        })
+        .collect())
+}
+
+fn probe(
+    disk: &mut impl ReadAt,
+    partition: Option<u32>,
+    start: u64,
+    size: u64,
+    layout: &mut DiskLayout,
+) -> eyre::Result<()> {
+    let mut buf = vec![0u8; size.min(PROBE_SIZE) as usize];
+    disk.read_at(start, &mut buf)?;
+
+    if is_lvm_physical_volume(&buf) {
+        layout.has_lvm = true;
+        return Ok(());
+    }
+    if let Some((kind, uuid, label)) = probe_ext(&buf)
+        .or_else(|| probe_xfs(&buf))
+        .or_else(|| probe_btrfs(&buf))
+        .or_else(|| probe_vfat(&buf))
+    {
+        layout.filesystems.push(Filesystem {
+            partition,
+            kind,
+            uuid,
+            label,
+        });
+    }
+    Ok(())
+}
+
+type ProbeResult = Option<(FilesystemKind, Option<String>, Option<String>)>;
+
+fn is_lvm_physical_volume(buf: &[u8]) -> bool {
+    buf.len() >= 544 && &buf[512..520] == b"LABELONE" && &buf[536..544] == b"LVM2 001"
+}
+
+fn probe_ext(buf: &[u8]) -> ProbeResult {
+    let superblock = buf.get(1024..1160)?;
+    if superblock[56..58] != [0x53, 0xef] {
+        return None;
+    }
+    Some((
+        FilesystemKind::Ext,
+        format_uuid(&superblock[104..120]),
+        format_label(&superblock[120..136]),
+    ))
+}
+
+fn probe_xfs(buf: &[u8]) -> ProbeResult {
+    let superblock = buf.get(0..120)?;
+    if &superblock[0..4] != b"XFSB" {
+        return None;
+    }
+    Some((
+        FilesystemKind::Xfs,
+        format_uuid(&superblock[32..48]),
+        format_label(&superblock[108..120]),
+    ))
+}
+
+fn probe_btrfs(buf: &[u8]) -> ProbeResult {
+    let superblock = buf.get(0x10000..0x1022b)?;
+    if &superblock[0x40..0x48] != b"_BHRfS_M" {
+        return None;
+    }
+    Some((
+        FilesystemKind::Btrfs,
+        format_uuid(&superblock[0x20..0x30]),
+        format_label(&superblock[0x12b..0x22b]),
+    ))
+}
+
+fn probe_vfat(buf: &[u8]) -> ProbeResult {
+    let boot_sector = buf.get(0..512)?;
+    if boot_sector[510..512] != [0x55, 0xaa] {
+        return None;
+    }
+    let (serial, label) = if &boot_sector[82..87] == b"FAT32" {
+        (&boot_sector[67..71], &boot_sector[71..82])
+    } else if &boot_sector[54..57] == b"FAT" {
+        (&boot_sector[39..43], &boot_sector[43..54])
+    } else {
+        return None;
+    };
+
+    let serial = le_u32(serial, 0);
+    let label = String::from_utf8_lossy(label).trim_end().to_string();
+    Some((
+        FilesystemKind::Vfat,
+        Some(format!("{:04X}-{:04X}", serial >> 16, serial & 0xffff)),
+        (!label.is_empty() && label != "NO NAME").then_some(label),
+    ))
+}
+
+fn format_uuid(bytes: &[u8]) -> Option<String> {
+    let uuid = Uuid::from_slice(bytes).ok()?;
+    (!uuid.is_nil()).then(|| uuid.to_string())
+}
+
+fn format_label(bytes: &[u8]) -> Option<String> {
+    let end = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    let label = String::from_utf8_lossy(&bytes[..end]).to_string();
+    (!label.is_empty()).then_some(label)
+}
+
+fn le_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn le_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+/// Builds raw disks with a GPT and a set of filesystem superblocks
+#[cfg(test)]
+pub(crate) mod test_disk {
+    use super::{FilesystemKind, SECTOR_SIZE};
+
+    pub const PARTITION_SIZE: usize = 128 * 1024;
+
+    pub struct TestFilesystem<'a> {
+        pub kind: FilesystemKind,
+        pub uuid: [u8; 16],
+        pub label: &'a str,
+    }
+
+    /// Creates a disk with one partition per filesystem. The GPT occupies
+    /// the first 34 sectors, partitions start at 64 KiB.
+    pub fn build(filesystems: &[TestFilesystem]) -> Vec<u8> {
+        let first_partition = 64 * 1024;
+        let mut disk = vec![0u8; first_partition + filesystems.len() * PARTITION_SIZE];
+
+        let header = &mut disk[512..1024];
+        header[0..8].copy_from_slice(b"EFI PART");
+        header[72..80].copy_from_slice(&2u64.to_le_bytes());
+        header[80..84].copy_from_slice(&128u32.to_le_bytes());
+        header[84..88].copy_from_slice(&128u32.to_le_bytes());
+
+        for (index, fs) in filesystems.iter().enumerate() {
+            let start = first_partition + index * PARTITION_SIZE;
+            let first_lba = start as u64 / SECTOR_SIZE;
+            let last_lba = (start + PARTITION_SIZE) as u64 / SECTOR_SIZE - 1;
+            let entry = &mut disk[1024 + index * 128..1024 + (index + 1) * 128];
+            entry[0..16].copy_from_slice(&[0xaf; 16]);
+            entry[32..40].copy_from_slice(&first_lba.to_le_bytes());
+            entry[40..48].copy_from_slice(&last_lba.to_le_bytes());
+
+            let partition = &mut disk[start..start + PARTITION_SIZE];
+            write_superblock(partition, fs);
+        }
+        disk
+    }
+
+    fn write_superblock(partition: &mut [u8], fs: &TestFilesystem) {
+        let label = fs.label.as_bytes();
+        match fs.kind {
+            FilesystemKind::Ext => {
+                partition[1080..1082].copy_from_slice(&[0x53, 0xef]);
+                partition[1128..1144].copy_from_slice(&fs.uuid);
+                partition[1144..1144 + label.len()].copy_from_slice(label);
+            }
+            FilesystemKind::Xfs => {
+                partition[0..4].copy_from_slice(b"XFSB");
+                partition[32..48].copy_from_slice(&fs.uuid);
+                partition[108..108 + label.len()].copy_from_slice(label);
+            }
+            FilesystemKind::Btrfs => {
+                partition[0x10040..0x10048].copy_from_slice(b"_BHRfS_M");
+                partition[0x10020..0x10030].copy_from_slice(&fs.uuid);
+                partition[0x1012b..0x1012b + label.len()].copy_from_slice(label);
+            }
+            FilesystemKind::Vfat => {
+                partition[82..90].copy_from_slice(b"FAT32   ");
+                partition[67..71].copy_from_slice(&fs.uuid[0..4]);
+                partition[71..82].copy_from_slice(format!("{:<11}", fs.label).as_bytes());
+                partition[510..512].copy_from_slice(&[0x55, 0xaa]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use model::tenant::TenantOrganizationId;
+
+    use super::test_disk::{TestFilesystem, build};
+    use super::*;
+
+    struct RawDisk(Vec<u8>);
+
+    impl ReadAt for RawDisk {
+        fn size(&self) -> u64 {
+            self.0.len() as u64
+        }
+
+        fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> eyre::Result<()> {
+            let offset = offset as usize;
+            buf.copy_from_slice(&self.0[offset..offset + buf.len()]);
+            Ok(())
+        }
+    }
+
+    const ROOT_UUID: [u8; 16] = [
+        0x8d, 0x6b, 0x3f, 0x2a, 0x41, 0x7c, 0x4e, 0x1b, 0x9a, 0x52, 0x0c, 0x6e, 0x73, 0x1d, 0x88,
+        0x0f,
+    ];
+
+    fn test_layout() -> DiskLayout {
+        let mut disk = RawDisk(build(&[
+            TestFilesystem {
+                kind: FilesystemKind::Vfat,
+                uuid: [0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                label: "UEFI",
+            },
+            TestFilesystem {
+                kind: FilesystemKind::Xfs,
+                uuid: [0x11; 16],
+                label: "boot",
+            },
+            TestFilesystem {
+                kind: FilesystemKind::Ext,
+                uuid: ROOT_UUID,
+                label: "cloudimg-rootfs",
+            },
+            TestFilesystem {
+                kind: FilesystemKind::Btrfs,
+                uuid: [0x22; 16],
+                label: "data",
+            },
+        ]));
+        inspect(&mut disk).unwrap()
+    }
+
+    fn attributes() -> OsImageAttributes {
+        OsImageAttributes {
+            id: Uuid::new_v4(),
+            source_url: "https://example.com/image.qcow2".to_string(),
+            digest: "".to_string(),
+            tenant_organization_id: TenantOrganizationId::try_from("test-org".to_string()).unwrap(),
+            create_volume: false,
+            name: None,
+            description: None,
+            auth_type: None,
+            auth_token: None,
+            rootfs_id: None,
+            rootfs_label: None,
+            boot_disk: None,
+            capacity: None,
+            bootfs_id: None,
+            efifs_id: None,
+        }
+    }
+
+    #[test]
+    fn test_inspect_gpt() {
+        let layout = test_layout();
+        assert!(!layout.has_lvm);
+        assert_eq!(
+            layout.filesystems,
+            vec![
+                Filesystem {
+                    partition: Some(1),
+                    kind: FilesystemKind::Vfat,
+                    uuid: Some("1234-5678".to_string()),
+                    label: Some("UEFI".to_string()),
+                },
+                Filesystem {
+                    partition: Some(2),
+                    kind: FilesystemKind::Xfs,
+                    uuid: Some("11111111-1111-1111-1111-111111111111".to_string()),
+                    label: Some("boot".to_string()),
+                },
+                Filesystem {
+                    partition: Some(3),
+                    kind: FilesystemKind::Ext,
+                    uuid: Some("8d6b3f2a-417c-4e1b-9a52-0c6e731d880f".to_string()),
+                    label: Some("cloudimg-rootfs".to_string()),
+                },
+                Filesystem {
+                    partition: Some(4),
+                    kind: FilesystemKind::Btrfs,
+                    uuid: Some("22222222-2222-2222-2222-222222222222".to_string()),
+                    label: Some("data".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspect_unpartitioned_disk() {
+        let mut partition = vec![0u8; test_disk::PARTITION_SIZE];
+        partition[1080..1082].copy_from_slice(&[0x53, 0xef]);
+        partition[1128..1144].copy_from_slice(&ROOT_UUID);
+        let layout = inspect(&mut RawDisk(partition)).unwrap();
+        assert_eq!(layout.filesystems.len(), 1);
+        assert_eq!(layout.filesystems[0].partition, None);
+        assert_eq!(layout.filesystems[0].label, None);
+    }
+
+    #[test]
+    fn test_verify() {
+        let layout = test_layout();
+
+        let mut attrs = attributes();
+        attrs.rootfs_id = Some("8D6B3F2A-417C-4E1B-9A52-0C6E731D880F".to_string());
+        attrs.rootfs_label = Some("cloudimg-rootfs".to_string());
+        attrs.bootfs_id = Some("11111111-1111-1111-1111-111111111111".to_string());
+        attrs.efifs_id = Some("1234-5678".to_string());
+        attrs.boot_disk = Some("/dev/nvme0n1".to_string());
+        verify(&layout, &attrs).unwrap();
+
+        let mut attrs = attributes();
+        attrs.rootfs_id = Some("8d6b3f2a-417c-4e1b-9a52-0c6e731d8810".to_string());
+        let err = verify(&layout, &attrs).unwrap_err();
+        assert!(err.to_string().contains("rootfs_id"), "{err}");
+
+        let mut attrs = attributes();
+        attrs.rootfs_label = Some("rootfs".to_string());
+        let err = verify(&layout, &attrs).unwrap_err();
+        assert!(err.to_string().contains("rootfs_label"), "{err}");
+
+        let mut attrs = attributes();
+        attrs.boot_disk = Some("nvme0n1".to_string());
+        let err = verify(&layout, &attrs).unwrap_err();
+        assert!(err.to_string().contains("boot_disk"), "{err}");
+    }
+
+    #[test]
+    fn test_verify_root_filesystem_on_lvm() {
+        let layout = DiskLayout {
+            filesystems: vec![],
+            has_lvm: true,
+        };
+        let mut attrs = attributes();
+        attrs.rootfs_label = Some("rootfs".to_string());
+        verify(&layout, &attrs).unwrap();
+
+        attrs.efifs_id = Some("1234-5678".to_string());
+        assert!(verify(&layout, &attrs).is_err());
+    }
+}
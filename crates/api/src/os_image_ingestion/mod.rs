@@ -0,0 +1,465 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Ingestion of OS images.
+//!
+//! OS images are created `Uninitialized`. The [`OsImageIngestion`] claims
+//! them one at a time by moving them to `InProgress`, downloads the
+//! `source_url` into a site-local cache, verifies the `digest` and inspects
+//! the qcow2 image for the declared filesystems. Afterwards the image is
+//! either `Ready`, or `Failed` with the reason in its status message.
+//! Instances can only be allocated with `Ready` images, which hosts then
+//! download from the cache through carbide-pxe.
+
+use std::collections::HashSet;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use eyre::{WrapErr, bail, eyre};
+use futures_util::StreamExt;
+use model::storage::{OsImage, OsImageStatus};
+use reqwest::Client;
+use sha2::digest::DynDigest;
+use sha2::{Sha256, Sha384, Sha512};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use crate::cfg::file::OsImageIngestionConfig;
+use crate::{CarbideError, CarbideResult};
+
+mod inspect;
+mod qcow2;
+
+#[cfg(test)]
+pub(crate) use inspect::FilesystemKind;
+#[cfg(test)]
+pub(crate) use inspect::test_disk;
+#[cfg(test)]
+pub(crate) use qcow2::test_image;
+
+/// `OsImageIngestion` periodically looks for new OS images, and makes them
+/// ready once they are downloaded and verified.
+pub struct OsImageIngestion {
+    database_connection: sqlx::PgPool,
+    config: OsImageIngestionConfig,
+    http_client: Client,
+}
+
+impl OsImageIngestion {
+    /// Create an OsImageIngestion
+    pub fn new(database_connection: sqlx::PgPool, config: OsImageIngestionConfig) -> Self {
+        OsImageIngestion {
+            database_connection,
+            config,
+            http_client: Client::new(),
+        }
+    }
+
+    /// Start the OsImageIngestion and return a [sending channel](tokio::sync::oneshot::Sender)
+    /// that will stop the OsImageIngestion when dropped.
+    pub fn start(self) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+
+        if self.config.enabled {
+            tokio::task::Builder::new()
+                .name("os_image_ingestion")
+                .spawn(async move { self.run(stop_receiver).await })?;
+        }
+
+        Ok(stop_sender)
+    }
+
+    async fn run(&self, mut stop_receiver: oneshot::Receiver<i32>) {
+        loop {
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("OsImageIngestion error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.run_interval) => {},
+                _ = &mut stop_receiver => {
+                    tracing::info!("OsImageIngestion stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Ingests all OS images which are waiting for ingestion, and removes
+    /// cached images of deleted OS images
+    pub async fn run_single_iteration(&self) -> CarbideResult<()> {
+        self.prune_cache().await?;
+
+        loop {
+            let mut txn = db::Transaction::begin(&self.database_connection).await?;
+            let os_image =
+                db::os_image::claim_for_ingestion(&mut txn, self.config.ingestion_timeout).await?;
+            txn.commit().await?;
+            let Some(os_image) = os_image else {
+                return Ok(());
+            };
+
+            let (status, status_message) = match self.ingest(&os_image).await {
+                Ok(()) => {
+                    tracing::info!(os_image_id = %os_image.attributes.id, "Ingested OS image");
+                    (OsImageStatus::Ready, None)
+                }
+                Err(error) => {
+                    let error = format!("{error:#}");
+                    tracing::warn!(
+                        os_image_id = %os_image.attributes.id,
+                        %error,
+                        "Failed to ingest OS image"
+                    );
+                    (OsImageStatus::Failed, Some(error))
+                }
+            };
+
+            let mut txn = db::Transaction::begin(&self.database_connection).await?;
+            db::os_image::finish_ingestion(
+                &mut txn,
+                os_image.attributes.id,
+                status,
+                status_message,
+            )
+            .await?;
+            txn.commit().await?;
+        }
+    }
+
+    /// Downloads, verifies and inspects a single OS image
+    async fn ingest(&self, os_image: &OsImage) -> eyre::Result<()> {
+        let digest = ImageDigest::parse(&os_image.attributes.digest)?;
+
+        let ingestion = async {
+            let path = self.fetch(os_image, &digest).await?;
+            let attributes = os_image.attributes.clone();
+            tokio::task::spawn_blocking(move || {
+                let file = std::fs::File::open(&path).wrap_err("failed to open cached image")?;
+                let mut image = qcow2::Qcow2Image::open(BufReader::new(file))?;
+                let layout = inspect::inspect(&mut image)?;
+                inspect::verify(&layout, &attributes)
+            })
+            .await?
+        };
+
+        tokio::time::timeout(self.config.ingestion_timeout, ingestion)
+            .await
+            .map_err(|_| {
+                eyre!(
+                    "ingestion did not finish within {:?}",
+                    self.config.ingestion_timeout
+                )
+            })?
+    }
+
+    /// Returns the path of the OS image in the cache, and downloads it if it
+    /// isn't cached yet
+    async fn fetch(&self, os_image: &OsImage, digest: &ImageDigest) -> eyre::Result<PathBuf> {
+        let path = self.cache_path(os_image.attributes.id);
+        if tokio::fs::try_exists(&path).await? {
+            if hash_file(&path, digest.algorithm).await? == digest.value {
+                return Ok(path);
+            }
+            tracing::warn!(
+                os_image_id = %os_image.attributes.id,
+                "Cached OS image does not match its digest, downloading it again"
+            );
+            tokio::fs::remove_file(&path).await?;
+        }
+
+        tokio::fs::create_dir_all(&self.config.cache_dir)
+            .await
+            .wrap_err("failed to create image cache directory")?;
+        let download_path = path.with_extension("qcow2.download");
+        let actual = match self.download(os_image, digest, &download_path).await {
+            Ok(actual) => actual,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&download_path).await;
+                return Err(e);
+            }
+        };
+        if actual != digest.value {
+            let _ = tokio::fs::remove_file(&download_path).await;
+            bail!(
+                "digest mismatch: expected {}, but the downloaded image has {}",
+                digest.value,
+                actual
+            );
+        }
+
+        tokio::fs::rename(&download_path, &path).await?;
+        Ok(path)
+    }
+
+    /// Downloads the OS image to `path` and returns its digest
+    async fn download(
+        &self,
+        os_image: &OsImage,
+        digest: &ImageDigest,
+        path: &Path,
+    ) -> eyre::Result<String> {
+        let attributes = &os_image.attributes;
+        let mut request = self.http_client.get(&attributes.source_url);
+        if let Some(auth_token) = &attributes.auth_token {
+            // Same default as the imaging script
+            let auth_type = attributes.auth_type.as_deref().unwrap_or("Bearer");
+            request = request.header(
+                reqwest::header::AUTHORIZATION,
+                format!("{auth_type} {auth_token}"),
+            );
+        }
+
+        let response = request.send().await.wrap_err("failed to download image")?;
+        if !response.status().is_success() {
+            bail!("download of image failed with status {}", response.status());
+        }
+
+        let max_image_size = self.config.max_image_size;
+        if let Some(size) = response.content_length()
+            && size > max_image_size
+        {
+            bail!("image has {size} bytes, which exceeds the limit of {max_image_size} bytes");
+        }
+
+        let mut file = tokio::fs::File::create(path)
+            .await
+            .wrap_err("failed to create cached image")?;
+        let mut hasher = digest.algorithm.hasher();
+        let mut body = response.bytes_stream();
+        let mut size = 0u64;
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.wrap_err("failed to download image")?;
+            // The source can send more than it announced, or not announce
+            // the size at all
+            size += chunk.len() as u64;
+            if size > max_image_size {
+                bail!("image exceeds the limit of {max_image_size} bytes");
+            }
+            hasher.update(&chunk);
+            file.write_all(&chunk)
+                .await
+                .wrap_err("failed to write cached image")?;
+        }
+        file.sync_all()
+            .await
+            .wrap_err("failed to write cached image")?;
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Removes cached OS images whose OS image got deleted
+    async fn prune_cache(&self) -> CarbideResult<()> {
+        let mut entries = match tokio::fs::read_dir(&self.config.cache_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(CarbideError::internal(format!(
+                    "Failed to read OS image cache: {e}"
+                )));
+            }
+        };
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let os_image_ids: HashSet<Uuid> = db::os_image::list_ids(&mut txn)
+            .await?
+            .into_iter()
+            .collect();
+        txn.commit().await?;
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| CarbideError::internal(format!("Failed to read OS image cache: {e}")))?
+        {
+            let file_name = entry.file_name();
+            let Some(os_image_id) = file_name
+                .to_str()
+                .and_then(|name| name.split_once('.'))
+                .and_then(|(id, _)| Uuid::parse_str(id).ok())
+            else {
+                continue;
+            };
+            if os_image_ids.contains(&os_image_id) {
+                continue;
+            }
+            // Images which are created while pruning may already be
+            // downloaded by another server which shares the cache
+            let is_recent = entry
+                .metadata()
+                .await
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| {
+                    modified.elapsed().unwrap_or_default() < self.config.ingestion_timeout
+                });
+            if is_recent {
+                continue;
+            }
+
+            match tokio::fs::remove_file(entry.path()).await {
+                Ok(()) => tracing::info!(%os_image_id, "Removed cached image of deleted OS image"),
+                Err(error) => tracing::warn!(
+                    %os_image_id,
+                    %error,
+                    "Failed to remove cached image of deleted OS image"
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cache_path(&self, os_image_id: Uuid) -> PathBuf {
+        ingested_image_path(&self.config.cache_dir, os_image_id)
+    }
+}
+
+/// Returns the path of an ingested OS image in the cache. The file only exists
+/// once the image got verified.
+pub(crate) fn ingested_image_path(cache_dir: &Path, os_image_id: Uuid) -> PathBuf {
+    cache_dir.join(format!("{os_image_id}.qcow2"))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    fn name(self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha384 => "sha384",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn hasher(self) -> Box<dyn DynDigest + Send> {
+        match self {
+            DigestAlgorithm::Sha256 => Box::new(Sha256::default()),
+            DigestAlgorithm::Sha384 => Box::new(Sha384::default()),
+            DigestAlgorithm::Sha512 => Box::new(Sha512::default()),
+        }
+    }
+}
+
+/// The digest of an OS image. Like the imaging script, the algorithm is
+/// derived from the length of the hex encoded digest. It can optionally be
+/// prefixed with the name of the algorithm, e.g. `sha256:`.
+#[derive(Debug, PartialEq, Eq)]
+struct ImageDigest {
+    algorithm: DigestAlgorithm,
+    value: String,
+}
+
+impl ImageDigest {
+    fn parse(digest: &str) -> eyre::Result<Self> {
+        let (prefix, value) = match digest.split_once(':') {
+            Some((prefix, value)) => (Some(prefix), value),
+            None => (None, digest),
+        };
+        if value.is_empty() || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("digest {digest} is not hex encoded");
+        }
+
+        let algorithm = match value.len() {
+            64 => DigestAlgorithm::Sha256,
+            96 => DigestAlgorithm::Sha384,
+            128 => DigestAlgorithm::Sha512,
+            40 => bail!("SHA-1 digests are not supported, use SHA-256 or stronger"),
+            len => bail!("digest with {len} hex digits does not match a supported algorithm"),
+        };
+        if let Some(prefix) = prefix
+            && !prefix.eq_ignore_ascii_case(algorithm.name())
+        {
+            bail!(
+                "digest is prefixed with {prefix}, but its length matches {}",
+                algorithm.name()
+            );
+        }
+
+        Ok(Self {
+            algorithm,
+            value: value.to_ascii_lowercase(),
+        })
+    }
+}
+
+async fn hash_file(path: &Path, algorithm: DigestAlgorithm) -> eyre::Result<String> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .wrap_err("failed to open cached image")?;
+    let mut hasher = algorithm.hasher();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let len = file
+            .read(&mut buf)
+            .await
+            .wrap_err("failed to read cached image")?;
+        if len == 0 {
+            break;
+        }
+        hasher.update(&buf[..len]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_digest() {
+        let sha256 = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        assert_eq!(
+            ImageDigest::parse(sha256).unwrap(),
+            ImageDigest {
+                algorithm: DigestAlgorithm::Sha256,
+                value: sha256.to_ascii_lowercase(),
+            }
+        );
+        assert_eq!(
+            ImageDigest::parse(&format!("sha256:{sha256}"))
+                .unwrap()
+                .algorithm,
+            DigestAlgorithm::Sha256
+        );
+        assert_eq!(
+            ImageDigest::parse(&"a".repeat(96)).unwrap().algorithm,
+            DigestAlgorithm::Sha384
+        );
+        assert_eq!(
+            ImageDigest::parse(&format!("SHA512:{}", "0".repeat(128)))
+                .unwrap()
+                .algorithm,
+            DigestAlgorithm::Sha512
+        );
+
+        for invalid in [
+            "",
+            "sha256:1234567890",
+            "sha512:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            "a94a8fe5ccb19ba61c4c0873d391e987982fbbd3",
+            "zz86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+        ] {
+            assert!(ImageDigest::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,402 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Read-only access to the guest disk stored in a qcow2 image.
+//!
+//! Only the subset of the format which is needed to look at partition tables
+//! and filesystem superblocks is supported: standard and zlib compressed
+//! clusters, without backing files, encryption or external data files.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use eyre::{WrapErr, bail, eyre};
+use flate2::read::DeflateDecoder;
+
+use super::inspect::ReadAt;
+
+const QCOW2_MAGIC: u32 = 0x5146_49fb;
+
+/// Offsets within the L1 and L2 table entries
+const L1_OFFSET_MASK: u64 = 0x00ff_ffff_ffff_fe00;
+const L2_OFFSET_MASK: u64 = 0x00ff_ffff_ffff_fe00;
+const L2_COMPRESSED: u64 = 1 << 62;
+const L2_ZERO: u64 = 1;
+
+/// Incompatible feature bits of qcow2 v3 headers
+const INCOMPATIBLE_CORRUPT: u64 = 1 << 1;
+const INCOMPATIBLE_EXTERNAL_DATA_FILE: u64 = 1 << 2;
+const INCOMPATIBLE_COMPRESSION_TYPE: u64 = 1 << 3;
+const INCOMPATIBLE_EXTENDED_L2: u64 = 1 << 4;
+/// The bits above, and the `dirty` bit which doesn't affect reading
+const INCOMPATIBLE_KNOWN: u64 = 0x1f;
+
+#[derive(Debug)]
+struct Header {
+    cluster_bits: u32,
+    size: u64,
+    l1_size: u32,
+    l1_table_offset: u64,
+}
+
+/// A qcow2 image whose guest disk can be read through [`ReadAt`]
+pub struct Qcow2Image<R> {
+    reader: R,
+    file_size: u64,
+    header: Header,
+    l1_table: Vec<u64>,
+    /// The most recently used L2 table and its offset
+    l2_cache: Option<(u64, Vec<u64>)>,
+    /// The most recently decompressed cluster and its L2 entry
+    cluster_cache: Option<(u64, Vec<u8>)>,
+}
+
+impl<R: Read + Seek> Qcow2Image<R> {
+    /// Parses the header and L1 table of a qcow2 image
+    pub fn open(mut reader: R) -> eyre::Result<Self> {
+        let file_size = reader.seek(SeekFrom::End(0))?;
+        let mut buf = [0u8; 104];
+        read_exact_at(&mut reader, 0, &mut buf[..72]).wrap_err("image is too small")?;
+
+        if be_u32(&buf, 0) != QCOW2_MAGIC {
+            bail!("image is not in qcow2 format");
+        }
+        let version = be_u32(&buf, 4);
+        if version != 2 && version != 3 {
+            bail!("unsupported qcow2 version {version}");
+        }
+        if be_u64(&buf, 8) != 0 {
+            bail!("qcow2 images with backing files are not supported");
+        }
+        let cluster_bits = be_u32(&buf, 20);
+        if !(9..=21).contains(&cluster_bits) {
+            bail!("invalid qcow2 cluster size 2^{cluster_bits}");
+        }
+        if be_u32(&buf, 32) != 0 {
+            bail!("encrypted qcow2 images are not supported");
+        }
+
+        if version == 3 {
+            read_exact_at(&mut reader, 0, &mut buf).wrap_err("qcow2 v3 header is truncated")?;
+            let incompatible = be_u64(&buf, 72);
+            if incompatible & !INCOMPATIBLE_KNOWN != 0 {
+                bail!("qcow2 image uses unknown incompatible features {incompatible:#x}");
+            }
+            if incompatible & INCOMPATIBLE_CORRUPT != 0 {
+                bail!("qcow2 image is marked as corrupt");
+            }
+            if incompatible & INCOMPATIBLE_EXTERNAL_DATA_FILE != 0 {
+                bail!("qcow2 images with external data files are not supported");
+            }
+            if incompatible & INCOMPATIBLE_COMPRESSION_TYPE != 0 {
+                bail!("qcow2 images which are not zlib compressed are not supported");
+            }
+            if incompatible & INCOMPATIBLE_EXTENDED_L2 != 0 {
+                bail!("qcow2 images with extended L2 entries are not supported");
+            }
+        }
+
+        let header = Header {
+            cluster_bits,
+            size: be_u64(&buf, 24),
+            l1_size: be_u32(&buf, 36),
+            l1_table_offset: be_u64(&buf, 40),
+        };
+
+        // Every L1 entry maps cluster_size / 8 clusters
+        let bytes_per_l1_entry = 1u64 << (2 * cluster_bits - 3);
+        if (header.l1_size as u64) < header.size.div_ceil(bytes_per_l1_entry) {
+            bail!("qcow2 L1 table is too small for the virtual disk size");
+        }
+        if header
+            .l1_table_offset
+            .saturating_add(header.l1_size as u64 * 8)
+            > file_size
+        {
+            bail!("qcow2 L1 table exceeds the image file");
+        }
+
+        let l1_table = read_table(&mut reader, header.l1_table_offset, header.l1_size as usize)
+            .wrap_err("failed to read qcow2 L1 table")?;
+
+        Ok(Self {
+            reader,
+            file_size,
+            header,
+            l1_table,
+            l2_cache: None,
+            cluster_cache: None,
+        })
+    }
+
+    fn cluster_size(&self) -> u64 {
+        1 << self.header.cluster_bits
+    }
+
+    /// Reads the part of a guest cluster starting at `offset` into `buf`,
+    /// which must not cross the end of the cluster
+    fn read_cluster_part(&mut self, offset: u64, buf: &mut [u8]) -> eyre::Result<()> {
+        let cluster_bits = self.header.cluster_bits;
+        let l2_entries = 1u64 << (cluster_bits - 3);
+        let cluster_index = offset >> cluster_bits;
+        let offset_in_cluster = offset & (self.cluster_size() - 1);
+
+        let l1_entry = self.l1_table[(cluster_index / l2_entries) as usize];
+        let l2_offset = l1_entry & L1_OFFSET_MASK;
+        if l2_offset == 0 {
+            buf.fill(0);
+            return Ok(());
+        }
+
+        let l2_entry = self.l2_entry(l2_offset, (cluster_index % l2_entries) as usize)?;
+        if l2_entry & L2_COMPRESSED != 0 {
+            let cluster = self.decompress_cluster(l2_entry)?;
+            let start = offset_in_cluster as usize;
+            buf.copy_from_slice(&cluster[start..start + buf.len()]);
+            return Ok(());
+        }
+
+        let host_offset = l2_entry & L2_OFFSET_MASK;
+        if host_offset == 0 || l2_entry & L2_ZERO != 0 {
+            buf.fill(0);
+            return Ok(());
+        }
+        read_exact_at(&mut self.reader, host_offset + offset_in_cluster, buf)
+            .wrap_err_with(|| format!("failed to read qcow2 cluster at {host_offset:#x}"))
+    }
+
+    fn l2_entry(&mut self, l2_offset: u64, index: usize) -> eyre::Result<u64> {
+        if !matches!(&self.l2_cache, Some((cached, _)) if *cached == l2_offset) {
+            let entries = (self.cluster_size() / 8) as usize;
+            if l2_offset + self.cluster_size() > self.file_size {
+                bail!("qcow2 L2 table at {l2_offset:#x} exceeds the image file");
+            }
+            let table = read_table(&mut self.reader, l2_offset, entries)
+                .wrap_err_with(|| format!("failed to read qcow2 L2 table at {l2_offset:#x}"))?;
+            self.l2_cache = Some((l2_offset, table));
+        }
+        Ok(self
+            .l2_cache
+            .as_ref()
+            .map(|(_, table)| table[index])
+            .unwrap())
+    }
+
+    fn decompress_cluster(&mut self, l2_entry: u64) -> eyre::Result<&[u8]> {
+        if !matches!(&self.cluster_cache, Some((cached, _)) if *cached == l2_entry) {
+            let cluster_bits = self.header.cluster_bits;
+            let offset_bits = 62 - (cluster_bits - 8);
+            let host_offset = l2_entry & ((1u64 << offset_bits) - 1);
+            let additional_sectors = (l2_entry >> offset_bits) & ((1u64 << (cluster_bits - 8)) - 1);
+            let compressed_size = ((additional_sectors + 1) * 512 - (host_offset & 511))
+                .min(self.file_size.saturating_sub(host_offset));
+
+            let mut compressed = vec![0u8; compressed_size as usize];
+            read_exact_at(&mut self.reader, host_offset, &mut compressed).wrap_err_with(|| {
+                format!("failed to read compressed qcow2 cluster at {host_offset:#x}")
+            })?;
+
+            let mut cluster = vec![0u8; self.cluster_size() as usize];
+            DeflateDecoder::new(compressed.as_slice())
+                .read_exact(&mut cluster)
+                .wrap_err_with(|| {
+                    format!("failed to decompress qcow2 cluster at {host_offset:#x}")
+                })?;
+            self.cluster_cache = Some((l2_entry, cluster));
+        }
+        Ok(self
+            .cluster_cache
+            .as_ref()
+            .map(|(_, cluster)| cluster.as_slice())
+            .unwrap())
+    }
+}
+
+impl<R: Read + Seek> ReadAt for Qcow2Image<R> {
+    fn size(&self) -> u64 {
+        self.header.size
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> eyre::Result<()> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .filter(|end| *end <= self.header.size)
+            .ok_or_else(|| eyre!("read at {offset:#x} exceeds the virtual disk size"))?;
+
+        let mut position = offset;
+        while position < end {
+            let cluster_end = (position | (self.cluster_size() - 1)) + 1;
+            let chunk_end = cluster_end.min(end);
+            let chunk = &mut buf[(position - offset) as usize..(chunk_end - offset) as usize];
+            self.read_cluster_part(position, chunk)?;
+            position = chunk_end;
+        }
+        Ok(())
+    }
+}
+
+fn read_exact_at<R: Read + Seek>(reader: &mut R, offset: u64, buf: &mut [u8]) -> eyre::Result<()> {
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(buf)?;
+    Ok(())
+}
+
+fn read_table<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    entries: usize,
+) -> eyre::Result<Vec<u64>> {
+    let mut raw = vec![0u8; entries * 8];
+    read_exact_at(reader, offset, &mut raw)?;
+    Ok(raw
+        .chunks_exact(8)
+        .map(|entry| u64::from_be_bytes(entry.try_into().unwrap()))
+        .collect())
+}
+
+fn be_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn be_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_be_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+/// Builds qcow2 v3 images with 64 KiB clusters and a single L2 table, which
+/// limits the virtual disk to 512 MiB
+#[cfg(test)]
+pub(crate) mod test_image {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::DeflateEncoder;
+
+    const CLUSTER_BITS: u32 = 16;
+    const CLUSTER_SIZE: usize = 1 << CLUSTER_BITS;
+
+    /// Converts a raw disk into a qcow2 image. Clusters which only contain
+    /// zeros are left unallocated, every other one is stored compressed if
+    /// `compress` is set.
+    pub fn build(disk: &[u8], compress: bool) -> Vec<u8> {
+        let clusters = disk.len().div_ceil(CLUSTER_SIZE);
+        assert!(clusters <= CLUSTER_SIZE / 8, "test disk is too large");
+
+        // Header, L1 table and L2 table occupy the first three clusters
+        let l1_offset = CLUSTER_SIZE;
+        let l2_offset = 2 * CLUSTER_SIZE;
+        let mut image = vec![0u8; 3 * CLUSTER_SIZE];
+
+        image[0..4].copy_from_slice(&0x5146_49fbu32.to_be_bytes());
+        image[4..8].copy_from_slice(&3u32.to_be_bytes());
+        image[20..24].copy_from_slice(&CLUSTER_BITS.to_be_bytes());
+        image[24..32].copy_from_slice(&(disk.len() as u64).to_be_bytes());
+        image[36..40].copy_from_slice(&1u32.to_be_bytes());
+        image[40..48].copy_from_slice(&(l1_offset as u64).to_be_bytes());
+        image[96..100].copy_from_slice(&4u32.to_be_bytes());
+        image[100..104].copy_from_slice(&104u32.to_be_bytes());
+        image[l1_offset..l1_offset + 8]
+            .copy_from_slice(&((l2_offset as u64) | (1 << 63)).to_be_bytes());
+
+        for index in 0..clusters {
+            let start = index * CLUSTER_SIZE;
+            let mut cluster = disk[start..disk.len().min(start + CLUSTER_SIZE)].to_vec();
+            if cluster.iter().all(|byte| *byte == 0) {
+                continue;
+            }
+            cluster.resize(CLUSTER_SIZE, 0);
+
+            let host_offset = image.len() as u64;
+            let entry = if compress {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&cluster).unwrap();
+                let compressed = encoder.finish().unwrap();
+                let sectors = compressed.len().div_ceil(512) as u64;
+                image.extend_from_slice(&compressed);
+                image.resize(image.len().next_multiple_of(512), 0);
+                let offset_bits = 62 - (CLUSTER_BITS - 8);
+                (1 << 62) | ((sectors - 1) << offset_bits) | host_offset
+            } else {
+                image.extend_from_slice(&cluster);
+                (1 << 63) | host_offset
+            };
+            let position = l2_offset + index * 8;
+            image[position..position + 8].copy_from_slice(&entry.to_be_bytes());
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn test_disk() -> Vec<u8> {
+        // Spans several clusters, with a hole in the middle
+        let mut disk = vec![0u8; 5 * 65536 + 1000];
+        for (index, byte) in disk[..100_000].iter_mut().enumerate() {
+            *byte = (index % 251) as u8;
+        }
+        disk[4 * 65536 + 10..4 * 65536 + 20].copy_from_slice(b"0123456789");
+        disk
+    }
+
+    #[test]
+    fn test_read_uncompressed_and_compressed() {
+        let disk = test_disk();
+        for compress in [false, true] {
+            let image = test_image::build(&disk, compress);
+            let mut qcow2 = Qcow2Image::open(Cursor::new(image)).unwrap();
+            assert_eq!(qcow2.size(), disk.len() as u64);
+
+            let mut all = vec![0u8; disk.len()];
+            qcow2.read_at(0, &mut all).unwrap();
+            assert_eq!(all, disk, "compress: {compress}");
+
+            // Reads which cross cluster boundaries
+            let mut buf = [0u8; 200];
+            qcow2.read_at(65436, &mut buf).unwrap();
+            assert_eq!(&buf[..], &disk[65436..65636]);
+        }
+    }
+
+    #[test]
+    fn test_read_beyond_size_fails() {
+        let disk = test_disk();
+        let mut qcow2 = Qcow2Image::open(Cursor::new(test_image::build(&disk, false))).unwrap();
+        let mut buf = [0u8; 2];
+        assert!(qcow2.read_at(disk.len() as u64 - 1, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_images() {
+        let err = Qcow2Image::open(Cursor::new(vec![0u8; 4096]))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("not in qcow2 format"));
+
+        let mut image = test_image::build(&test_disk(), false);
+        image[8..16].copy_from_slice(&4096u64.to_be_bytes());
+        let err = Qcow2Image::open(Cursor::new(image)).err().unwrap();
+        assert!(err.to_string().contains("backing files"));
+
+        let mut image = test_image::build(&test_disk(), false);
+        image[32..36].copy_from_slice(&1u32.to_be_bytes());
+        let err = Qcow2Image::open(Cursor::new(image)).err().unwrap();
+        assert!(err.to_string().contains("encrypted"));
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::rack::RackId;
+use librms::RmsApi;
+use librms::protos::rack_manager::{ReturnCode, UpdateNodeFirmwareRequest};
+use model::rack::RackFirmwareComponent;
+
+/// Path under which the files of downloaded rack firmware sets are served to RMS
+pub fn firmware_file_path(firmware_id: &str, filename: &str) -> String {
+    format!("/forge-boot-artifacts/blobs/internal/fw/rack_firmware/{firmware_id}/{filename}")
+}
+
+/// Pushes all firmware components to a rack device through RMS. Stops at the
+/// first component which fails and returns a description of the failure.
+pub async fn update_device_firmware(
+    rms_client: &dyn RmsApi,
+    rack_id: RackId,
+    firmware_id: &str,
+    device_id: &str,
+    components: &[RackFirmwareComponent],
+    activate: bool,
+) -> Result<(), String> {
+    for component in components {
+        let request = UpdateNodeFirmwareRequest {
+            metadata: None,
+            node_id: device_id.to_string(),
+            filename: firmware_file_path(firmware_id, &component.filename),
+            target: component.target.clone(),
+            activate,
+            rack_id: rack_id.to_string(),
+            ..Default::default()
+        };
+        let response = rms_client
+            .update_node_firmware(request)
+            .await
+            .map_err(|e| format!("RMS API Error for {}: {}", component.name, e))?;
+        if response.status != ReturnCode::Success as i32 {
+            return Err(format!(
+                "Updating {} failed: {}",
+                component.name, response.message
+            ));
+        }
+        tracing::info!(
+            %rack_id,
+            device_id,
+            component = %component.name,
+            "Updated rack device firmware component"
+        );
+    }
+    Ok(())
+}
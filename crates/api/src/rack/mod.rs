@@ -15,4 +15,6 @@
  * limitations under the License.
  */
 
+pub mod firmware;
+pub mod power;
 pub mod rms_client;
@@ -0,0 +1,122 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::IpAddr;
+
+use libredfish::{PowerState, Redfish, SystemPowerControl};
+use mac_address::MacAddress;
+use model::power_shelf::power_supply::PowerSupplyInventory;
+use model::rack::{RackDeviceType, RackPowerTarget};
+use sqlx::PgConnection;
+
+use crate::redfish::{RedfishAuth, RedfishClientPool};
+
+/// Looks up the IP address of the rack device with the given BMC MAC address
+pub async fn find_bmc_ip(
+    txn: &mut PgConnection,
+    bmc_mac_address: MacAddress,
+) -> Result<IpAddr, String> {
+    let interfaces = db::machine_interface::find_by_mac_address(txn, bmc_mac_address)
+        .await
+        .map_err(|e| format!("Failed to look up BMC {bmc_mac_address}: {e}"))?;
+    interfaces
+        .iter()
+        .find_map(|interface| interface.addresses.first().copied())
+        .ok_or_else(|| format!("BMC {bmc_mac_address} has no IP address assigned"))
+}
+
+/// Creates a Redfish client for the rack device BMC at the given address
+pub async fn create_bmc_client(
+    redfish_client_pool: &dyn RedfishClientPool,
+    bmc_mac_address: MacAddress,
+    ip: IpAddr,
+) -> Result<Box<dyn Redfish>, String> {
+    redfish_client_pool
+        .create_client(
+            &ip.to_string(),
+            None,
+            RedfishAuth::for_bmc_mac(bmc_mac_address),
+            true,
+        )
+        .await
+        .map_err(|e| format!("BMC {bmc_mac_address} at {ip} is not reachable: {e}"))
+}
+
+/// Requests the target power state from a rack device. Compute trays are shut
+/// down gracefully so that their operating system can stop its workloads.
+pub async fn set_device_power(
+    redfish: &dyn Redfish,
+    device_type: RackDeviceType,
+    target: RackPowerTarget,
+) -> Result<(), String> {
+    let action = match (target, device_type) {
+        (RackPowerTarget::On, _) => SystemPowerControl::On,
+        (RackPowerTarget::Off, RackDeviceType::ComputeTray) => SystemPowerControl::GracefulShutdown,
+        (RackPowerTarget::Off, _) => SystemPowerControl::ForceOff,
+    };
+    redfish
+        .power(action)
+        .await
+        .map_err(|e| format!("Power action {action} failed: {e}"))
+}
+
+/// Returns whether the device reports the target power state
+pub async fn has_power_state(redfish: &dyn Redfish, target: RackPowerTarget) -> bool {
+    let expected = match target {
+        RackPowerTarget::On => PowerState::On,
+        RackPowerTarget::Off => PowerState::Off,
+    };
+    redfish
+        .get_power_state()
+        .await
+        .is_ok_and(|power_state| power_state == expected)
+}
+
+/// Returns a description of the faults a power shelf reports, if any. A shelf
+/// is faulty if one of its present power supplies is unhealthy or if it lost
+/// redundancy.
+pub async fn power_shelf_fault(redfish: &dyn Redfish) -> Result<Option<String>, String> {
+    let power = redfish
+        .get_power_metrics()
+        .await
+        .map_err(|e| format!("Failed to read power supplies: {e}"))?;
+    let inventory = serde_json::to_value(&power)
+        .and_then(|power| PowerSupplyInventory::from_redfish_power(&power))
+        .map_err(|e| format!("Failed to parse power supplies: {e}"))?;
+
+    let mut faults: Vec<String> = inventory
+        .power_supplies
+        .iter()
+        .filter(|psu| psu.health.is_some() && !psu.is_healthy())
+        .map(|psu| {
+            format!(
+                "power supply {} is {}",
+                psu.id,
+                psu.health.as_deref().unwrap_or_default()
+            )
+        })
+        .collect();
+    if inventory
+        .redundancy
+        .as_ref()
+        .is_some_and(|redundancy| redundancy.is_lost())
+    {
+        faults.push("redundancy is lost".to_string());
+    }
+
+    Ok((!faults.is_empty()).then(|| faults.join(", ")))
+}
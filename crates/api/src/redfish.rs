@@ -625,7 +625,7 @@ pub(crate) fn redact_password(
 
 #[cfg(test)]
 pub mod test_support {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::path::Path;
     use std::sync::Mutex;
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -658,6 +658,10 @@ pub mod test_support {
         users: HashMap<String, String>,
         fw_version: Arc<String>,
         secure_boot: AtomicBool,
+        /// Serial numbers reported by the chassis of a host
+        chassis_serial_numbers: HashMap<String, String>,
+        /// Hosts for which no client can be created
+        unreachable_hosts: HashSet<String>,
     }
 
     #[derive(Debug)]
@@ -696,6 +700,25 @@ pub mod test_support {
             }
         }
 
+        /// Makes the chassis of `host` report `serial_number`
+        pub fn set_chassis_serial_number(&self, host: &str, serial_number: &str) {
+            self.state
+                .lock()
+                .unwrap()
+                .chassis_serial_numbers
+                .insert(host.to_string(), serial_number.to_string());
+        }
+
+        /// Makes creating a client for `host` fail as if its BMC was down
+        pub fn set_unreachable(&self, host: &str, unreachable: bool) {
+            let mut state = self.state.lock().unwrap();
+            if unreachable {
+                state.unreachable_hosts.insert(host.to_string());
+            } else {
+                state.unreachable_hosts.remove(host);
+            }
+        }
+
         pub fn actions_since(&self, timepoint: &RedfishSimTimepoint) -> RedfishSimActions {
             let state = self.state.lock().unwrap();
             RedfishSimActions {
@@ -1031,6 +1054,13 @@ pub mod test_support {
                 manufacturer: Some("Nvidia".to_string()),
                 model: Some("Bluefield 3 SmartNIC Main Card".to_string()),
                 name: Some("Card1".to_string()),
+                serial_number: self
+                    .state
+                    .lock()
+                    .unwrap()
+                    .chassis_serial_numbers
+                    .get(&self._host)
+                    .cloned(),
                 ..Default::default()
             })
         }
@@ -1946,6 +1976,13 @@ pub mod test_support {
             _auth: RedfishAuth,
             _initialize: bool,
         ) -> Result<Box<dyn Redfish>, RedfishClientCreationError> {
+            if self.state.lock().unwrap().unreachable_hosts.contains(host) {
+                return Err(RedfishClientCreationError::RedfishError(
+                    RedfishError::GenericError {
+                        error: format!("Connection to {host} timed out"),
+                    },
+                ));
+            }
             {
                 self.state
                     .clone()
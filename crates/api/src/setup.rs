@@ -53,6 +53,8 @@ use crate::firmware_downloader::FirmwareDownloader;
 use crate::handlers::machine_validation::apply_config_on_startup;
 use crate::ib::{self, IBFabricManager};
 use crate::ib_fabric_monitor::IbFabricMonitor;
+use crate::instance_events::hook::InstanceEventStateChangeHook;
+use crate::instance_events::notifier::InstanceEventNotifier;
 use crate::ipmitool::{IPMITool, IPMIToolImpl, IPMIToolTestImpl};
 use crate::listener::ApiListenMode;
 use crate::logging::log_limiter::LogLimiter;
@@ -63,8 +65,10 @@ use crate::logging::sqlx_query_tracing::SQLX_STATEMENTS_LOG_LEVEL;
 use crate::machine_update_manager::MachineUpdateManager;
 use crate::measured_boot::metrics_collector::MeasuredBootMetricsCollector;
 use crate::mqtt_state_change_hook::hook::MqttStateChangeHook;
+use crate::network_security_group::checker::NetworkSecurityGroupExpansionChecker;
 use crate::nvl_partition_monitor::NvlPartitionMonitor;
 use crate::nvlink::{NmxmClientPool, NmxmClientPoolImpl};
+use crate::os_image_ingestion::OsImageIngestion;
 use crate::preingestion_manager::PreingestionManager;
 use crate::redfish::RedfishClientPool;
 use crate::scout_stream::ConnectionRegistry;
@@ -85,9 +89,10 @@ use crate::state_controller::rack::handler::RackStateHandler;
 use crate::state_controller::rack::io::RackStateControllerIO;
 use crate::state_controller::spdm::handler::SpdmAttestationStateHandler;
 use crate::state_controller::spdm::io::SpdmStateControllerIO;
-use crate::state_controller::state_change_emitter::StateChangeEmitterBuilder;
+use crate::state_controller::state_change_emitter::{PublishableState, StateChangeEmitterBuilder};
 use crate::state_controller::switch::handler::SwitchStateHandler;
 use crate::state_controller::switch::io::SwitchStateControllerIO;
+use crate::webhook_state_change_hook::hook::WebhookStateChangeHook;
 use crate::{attestation, db_init, ethernet_virtualization, listener};
 
 const API_URL_KEY: &str = "api_url";
@@ -395,6 +400,7 @@ pub async fn start_api(
         kube_client_provider: Arc::new(carbide_dpf::Production {}),
         machine_state_handler_enqueuer: Enqueuer::new(db_pool),
         metric_emitter: ApiMetricsEmitter::new(&meter),
+        instance_event_notifier: InstanceEventNotifier::default(),
     });
 
     let (controllers_stop_tx, controllers_stop_rx) = oneshot::channel();
@@ -441,6 +447,7 @@ pub async fn initialize_and_start_controllers(
         nmxm_pool: shared_nmxm_pool,
         work_lock_manager_handle,
         rms_client,
+        instance_event_notifier,
         ..
     } = api_service.as_ref();
     // As soon as we get the database up, observe this version of forge so that we know when it was
@@ -584,41 +591,60 @@ pub async fn initialize_and_start_controllers(
         dpa_info = Some(Arc::new(info));
     }
 
-    // Create state change emitter with DSX Exchange Event Bus hook if enabled
-    let state_change_emitter = {
-        let mut emitter_builder = StateChangeEmitterBuilder::default();
+    // Create the hooks which publish state changes of all state controllers
+    // to the DSX Exchange Event Bus and to webhooks, if enabled
+    let mut external_hooks = ExternalStateChangeHooks::default();
 
-        if let Some(ref config) = carbide_config.dsx_exchange_event_bus
-            && config.enabled
-        {
-            let client = mqttea::MqtteaClient::new(
-                &config.mqtt_endpoint,
-                config.mqtt_broker_port,
-                "carbide-dsx-exchange-event-bus",
-                Some(mqttea::client::ClientOptions::default().with_qos(mqttea::QoS::AtMostOnce)),
-            )
-            .map_err(|e| eyre::eyre!("Failed to create DSX Exchange Event Bus MQTT client: {e}"))
-            .await?;
+    if let Some(ref config) = carbide_config.dsx_exchange_event_bus
+        && config.enabled
+    {
+        let client = mqttea::MqtteaClient::new(
+            &config.mqtt_endpoint,
+            config.mqtt_broker_port,
+            "carbide-dsx-exchange-event-bus",
+            Some(mqttea::client::ClientOptions::default().with_qos(mqttea::QoS::AtMostOnce)),
+        )
+        .map_err(|e| eyre::eyre!("Failed to create DSX Exchange Event Bus MQTT client: {e}"))
+        .await?;
 
-            client.connect().await.map_err(|e| {
-                eyre::eyre!("Failed to connect DSX Exchange Event Bus MQTT client: {e}")
-            })?;
+        client.connect().await.map_err(|e| {
+            eyre::eyre!("Failed to connect DSX Exchange Event Bus MQTT client: {e}")
+        })?;
 
-            tracing::info!(
-                "DSX Exchange Event Bus enabled, publishing to {}:{}",
-                config.mqtt_endpoint,
-                config.mqtt_broker_port
-            );
-            emitter_builder = emitter_builder.hook(Box::new(MqttStateChangeHook::new(
-                client,
-                config.publish_timeout,
-                config.queue_capacity,
-                &meter,
-            )));
-        }
+        tracing::info!(
+            "DSX Exchange Event Bus enabled, publishing to {}:{}",
+            config.mqtt_endpoint,
+            config.mqtt_broker_port
+        );
+        external_hooks.mqtt = Some(MqttStateChangeHook::new(
+            client,
+            config.publish_timeout,
+            config.queue_capacity,
+            &meter,
+        ));
+    }
 
-        emitter_builder.build()
-    };
+    if let Some(ref config) = carbide_config.state_change_webhooks
+        && config.enabled
+    {
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(|e| eyre::eyre!("Failed to create state change webhook client: {e}"))?;
+
+        tracing::info!(
+            "State change webhooks enabled, delivering to {} endpoints",
+            config.endpoints.len()
+        );
+        external_hooks.webhook = Some(WebhookStateChangeHook::new(client, config, &meter));
+    }
+
+    // The machine state controller additionally notifies instance event streams
+    let state_change_emitter = external_hooks
+        .emitter_builder()
+        .hook(Box::new(InstanceEventStateChangeHook::new(
+            instance_event_notifier.clone(),
+        )))
+        .build();
 
     let handler_services = Arc::new(CommonStateHandlerServices {
         db_pool: db_pool.clone(),
@@ -748,6 +774,7 @@ pub async fn initialize_and_start_controllers(
             sc_pool_vlan_id,
             sc_pool_vni,
         )))
+        .state_change_emitter(external_hooks.emitter_builder().build())
         .build_and_spawn()
         .expect("Unable to build NetworkSegmentController");
 
@@ -767,17 +794,25 @@ pub async fn initialize_and_start_controllers(
                     (&carbide_config.dpa_interface_state_controller.controller).into(),
                 )
                 .state_handler(Arc::new(DpaInterfaceStateHandler::new()))
+                .state_change_emitter(external_hooks.emitter_builder().build())
                 .build_and_spawn()
                 .expect("Unable to build DpaInterfaceStateController"),
         );
     }
 
     if carbide_config.spdm.enabled {
-        let Some(nras_config) = carbide_config.spdm.nras_config.clone() else {
+        let Some(mut nras_config) = carbide_config.spdm.nras_config.clone() else {
             return Err(eyre::eyre!(
                 "SPDm attestation is enabled but NRAS Config is missing!!"
             ));
         };
+        // The local verifier signs its tokens with a key derived from the site-wide root
+        if let Some(local_verifier) = nras_config.local_verifier.as_mut() {
+            let site_wide_root =
+                crate::signed_token::fetch_site_wide_root(api_service.credential_provider.as_ref())
+                    .await?;
+            local_verifier.token_secret = nras::TokenSecret::new(site_wide_root);
+        }
 
         let verifier = Arc::new(VerifierImpl::default());
 
@@ -791,6 +826,10 @@ pub async fn initialize_and_start_controllers(
                 verifier,
                 nras_config,
             )))
+            .io(Arc::new(SpdmStateControllerIO {
+                reattestation_interval: carbide_config.attestation_policy.reattestation_interval,
+            }))
+            .state_change_emitter(external_hooks.emitter_builder().build())
             .build_and_spawn()
             .expect("Unable to build SpdmStateController");
     }
@@ -803,6 +842,7 @@ pub async fn initialize_and_start_controllers(
             .services(handler_services.clone())
             .iteration_config((&carbide_config.ib_partition_state_controller.controller).into())
             .state_handler(Arc::new(IBPartitionStateHandler::default()))
+            .state_change_emitter(external_hooks.emitter_builder().build())
             .build_and_spawn()
             .expect("Unable to build IBPartitionStateController");
 
@@ -813,6 +853,7 @@ pub async fn initialize_and_start_controllers(
         .services(handler_services.clone())
         .iteration_config((&carbide_config.power_shelf_state_controller.controller).into())
         .state_handler(Arc::new(PowerShelfStateHandler::default()))
+        .state_change_emitter(external_hooks.emitter_builder().build())
         .build_and_spawn()
         .expect("Unable to build PowerShelfStateController");
 
@@ -822,6 +863,7 @@ pub async fn initialize_and_start_controllers(
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(RackStateHandler::default()))
+        .state_change_emitter(external_hooks.emitter_builder().build())
         .build_and_spawn()
         .expect("Unable to build RackStateController");
 
@@ -831,7 +873,10 @@ pub async fn initialize_and_start_controllers(
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .iteration_config((&carbide_config.switch_state_controller.controller).into())
-        .state_handler(Arc::new(SwitchStateHandler::default()))
+        .state_handler(Arc::new(SwitchStateHandler::new(Some(
+            shared_nmxm_pool.clone(),
+        ))))
+        .state_change_emitter(external_hooks.emitter_builder().build())
         .build_and_spawn()
         .expect("Unable to build SwitchStateController");
 
@@ -898,6 +943,16 @@ pub async fn initialize_and_start_controllers(
     );
     let _measured_boot_collector_handle = measured_boot_collector.start()?;
 
+    let nsg_expansion_checker = NetworkSecurityGroupExpansionChecker::new(
+        db_pool.clone(),
+        carbide_config.network_security_group.clone(),
+    );
+    let _nsg_expansion_checker_handle = nsg_expansion_checker.start()?;
+
+    let os_image_ingestion =
+        OsImageIngestion::new(db_pool.clone(), carbide_config.os_image_ingestion.clone());
+    let _os_image_ingestion_handle = os_image_ingestion.start()?;
+
     // we need to create ek_cert_status entries for all existing machines
     attestation::backfill_ek_cert_status_for_existing_machines(db_pool).await?;
 
@@ -921,6 +976,32 @@ pub async fn initialize_and_start_controllers(
         .context("error reading from stop channel, calling thread likely panicked")
 }
 
+/// Hooks which publish the state changes of all state controllers to
+/// consumers outside of carbide
+#[derive(Default)]
+struct ExternalStateChangeHooks {
+    mqtt: Option<MqttStateChangeHook>,
+    webhook: Option<WebhookStateChangeHook>,
+}
+
+impl ExternalStateChangeHooks {
+    /// Returns an emitter builder with all enabled hooks registered
+    fn emitter_builder<Id, S>(&self) -> StateChangeEmitterBuilder<Id, S>
+    where
+        Id: std::fmt::Display + Clone + Send + Sync + 'static,
+        S: PublishableState,
+    {
+        let mut builder = StateChangeEmitterBuilder::default();
+        if let Some(hook) = &self.mqtt {
+            builder = builder.hook(Box::new(hook.clone()));
+        }
+        if let Some(hook) = &self.webhook {
+            builder = builder.hook(Box::new(hook.clone()));
+        }
+        builder
+    }
+}
+
 /// Constructs a context map for bf.cfg Tera template from CarbideConfig.
 /// Used to populate deployment and runtime parameters for the DPF setup.
 fn bfcfg_context(config: &CarbideConfig) -> HashMap<String, String> {
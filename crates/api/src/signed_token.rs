@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! HMAC-signed tokens, keyed from the site-wide root secret.
+//!
+//! Used for credentials that the API hands out and later has to verify
+//! itself, like OS image download URLs and console access tokens. Every
+//! API replica derives the same key, so no token state needs to be stored.
+
+use forge_secrets::credentials::{
+    BmcCredentialType, CredentialKey, CredentialProvider, Credentials,
+};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Key used to sign and verify tokens for a single purpose
+pub struct SigningKey([u8; 32]);
+
+impl SigningKey {
+    /// Derives the signing key from the site-wide root secret using
+    /// HKDF-SHA256. `purpose` should be versioned (e.g. `"foo:v1"`), so
+    /// that tokens signed for one purpose or format are never accepted
+    /// for another.
+    pub fn derive(site_wide_root: &[u8], purpose: &str) -> Result<Self, eyre::Report> {
+        let hkdf = Hkdf::<Sha256>::new(None, site_wide_root);
+        let mut key = [0u8; 32];
+        hkdf.expand(purpose.as_bytes(), &mut key)
+            .map_err(|e| eyre::eyre!("HKDF expand failed: {e}"))?;
+        Ok(Self(key))
+    }
+
+    /// Fetches the site-wide root secret and derives the signing key from it
+    pub async fn fetch(
+        credential_provider: &dyn CredentialProvider,
+        purpose: &str,
+    ) -> Result<Self, eyre::Report> {
+        let site_wide_root = fetch_site_wide_root(credential_provider).await?;
+        Self::derive(site_wide_root.as_bytes(), purpose)
+    }
+
+    /// Appends a signature to `payload`, as `{payload}.{hex_signature}`.
+    /// The payload should not need escaping wherever the token is used.
+    pub fn sign(&self, payload: &str) -> String {
+        let signature = self.mac(payload).finalize().into_bytes();
+        format!("{payload}.{}", hex::encode(signature))
+    }
+
+    /// Checks the signature of a token produced by [`Self::sign`],
+    /// returning its payload.
+    pub fn verify<'a>(&self, token: &'a str) -> Result<&'a str, TokenError> {
+        let (payload, signature) = token.rsplit_once('.').ok_or(TokenError::Malformed)?;
+        let signature = hex::decode(signature).map_err(|_| TokenError::Malformed)?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| TokenError::InvalidSignature)?;
+        Ok(payload)
+    }
+
+    fn mac(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC can take keys of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+/// Fetches the site-wide root secret, for keys which are derived outside
+/// of [`SigningKey`]
+pub async fn fetch_site_wide_root(
+    credential_provider: &dyn CredentialProvider,
+) -> Result<String, eyre::Report> {
+    let credential_key = CredentialKey::BmcCredentials {
+        credential_type: BmcCredentialType::SiteWideRoot,
+    };
+    let credentials = credential_provider
+        .get_credentials(&credential_key)
+        .await?
+        .ok_or_else(|| eyre::eyre!("SiteWideRoot credentials not found"))?;
+    let Credentials::UsernamePassword { password, .. } = credentials;
+    Ok(password)
+}
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    #[error("token is malformed")]
+    Malformed,
+    #[error("token signature is invalid")]
+    InvalidSignature,
+    #[error("token has expired")]
+    Expired,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_scoped_to_purpose() {
+        let key = SigningKey::derive(b"site-root", "a:v1").unwrap();
+        let other_purpose = SigningKey::derive(b"site-root", "b:v1").unwrap();
+
+        let token = key.sign("payload");
+        assert_eq!(key.verify(&token), Ok("payload"));
+        assert_eq!(
+            other_purpose.verify(&token),
+            Err(TokenError::InvalidSignature)
+        );
+        assert_eq!(key.verify("payload"), Err(TokenError::Malformed));
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Functionality shared by the state handlers of devices which are only managed
+//! through their BMC, like switches and power shelves
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+use libredfish::{PowerState, Redfish};
+use mac_address::MacAddress;
+use model::machine::MachineInterfaceSnapshot;
+
+use crate::redfish::RedfishAuth;
+use crate::state_controller::common_services::CommonStateHandlerServices;
+use crate::state_controller::state_handler::{StateHandlerError, StateHandlerOutcome};
+
+/// The kind of BMC managed device. Used to describe the device in status messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BmcDeviceKind {
+    Switch,
+    PowerShelf,
+}
+
+impl BmcDeviceKind {
+    /// Name of the device kind for use within a sentence
+    pub fn name(self) -> &'static str {
+        match self {
+            BmcDeviceKind::Switch => "switch",
+            BmcDeviceKind::PowerShelf => "power shelf",
+        }
+    }
+
+    /// Name of the device kind for use at the start of a sentence
+    pub fn title(self) -> &'static str {
+        match self {
+            BmcDeviceKind::Switch => "Switch",
+            BmcDeviceKind::PowerShelf => "Power shelf",
+        }
+    }
+}
+
+/// The BMC endpoint of a device which passed validation against its expected device entry
+#[derive(Debug, Clone)]
+pub struct DeviceBmc {
+    pub mac_address: MacAddress,
+    pub ip: IpAddr,
+    pub serial_number: String,
+}
+
+/// Locates the BMC of a device among the interfaces which are associated with it.
+///
+/// `bmc_mac_address` and `serial_number` are taken from the expected device entry.
+/// Returns `Err(cause)` with a human readable cause if the BMC can not be found.
+pub fn locate_bmc(
+    kind: BmcDeviceKind,
+    interfaces: &[MachineInterfaceSnapshot],
+    bmc_mac_address: MacAddress,
+    serial_number: String,
+) -> Result<DeviceBmc, String> {
+    let Some(bmc_interface) = interfaces
+        .iter()
+        .find(|interface| interface.mac_address == bmc_mac_address)
+    else {
+        let found_macs: Vec<String> = interfaces
+            .iter()
+            .map(|interface| interface.mac_address.to_string())
+            .collect();
+        return Err(format!(
+            "Expected BMC MAC address {bmc_mac_address} is not associated with the {}. Associated MAC addresses: [{}]",
+            kind.name(),
+            found_macs.join(", ")
+        ));
+    };
+
+    let Some(ip) = bmc_interface.addresses.first().copied() else {
+        return Err(format!(
+            "BMC interface {} of the {} has no IP address assigned",
+            bmc_interface.mac_address,
+            kind.name()
+        ));
+    };
+
+    Ok(DeviceBmc {
+        mac_address: bmc_interface.mac_address,
+        ip,
+        serial_number,
+    })
+}
+
+/// Creates a Redfish client for the BMC of the device.
+///
+/// Returns `Err(cause)` if the BMC is not reachable.
+pub async fn connect(
+    kind: BmcDeviceKind,
+    bmc: &DeviceBmc,
+    services: &CommonStateHandlerServices,
+) -> Result<Box<dyn Redfish>, String> {
+    create_redfish_client(bmc, services)
+        .await
+        .map_err(|e| format!("{} BMC {} is not reachable: {e}", kind.title(), bmc.ip))
+}
+
+pub async fn create_redfish_client(
+    bmc: &DeviceBmc,
+    services: &CommonStateHandlerServices,
+) -> Result<Box<dyn Redfish>, StateHandlerError> {
+    Ok(services
+        .redfish_client_pool
+        .create_client(
+            &bmc.ip.to_string(),
+            None,
+            RedfishAuth::for_bmc_mac(bmc.mac_address),
+            true,
+        )
+        .await?)
+}
+
+/// Inventory data which is read from the BMC of every device kind
+#[derive(Debug, Clone)]
+pub struct BmcInventory {
+    pub power_state: PowerState,
+    pub serial_numbers: Vec<String>,
+    pub firmware_versions: BTreeMap<String, String>,
+}
+
+/// Reads the power state, the chassis serial numbers and the firmware versions from a BMC
+pub async fn fetch_inventory(redfish: &dyn Redfish) -> Result<BmcInventory, StateHandlerError> {
+    let power_state =
+        redfish
+            .get_power_state()
+            .await
+            .map_err(|error| StateHandlerError::RedfishError {
+                operation: "get_power_state",
+                error,
+            })?;
+
+    let chassis_ids =
+        redfish
+            .get_chassis_all()
+            .await
+            .map_err(|error| StateHandlerError::RedfishError {
+                operation: "get_chassis_all",
+                error,
+            })?;
+    let mut serial_numbers = Vec::new();
+    for chassis_id in chassis_ids.iter() {
+        let Ok(chassis) = redfish.get_chassis(chassis_id).await else {
+            continue;
+        };
+        if let Some(serial_number) = chassis.serial_number {
+            let serial_number = serial_number.trim().to_string();
+            if !serial_number.is_empty() && !serial_numbers.contains(&serial_number) {
+                serial_numbers.push(serial_number);
+            }
+        }
+    }
+
+    let inventory_ids = redfish.get_software_inventories().await.map_err(|error| {
+        StateHandlerError::RedfishError {
+            operation: "get_software_inventories",
+            error,
+        }
+    })?;
+    let mut firmware_versions = BTreeMap::new();
+    for inventory_id in inventory_ids.iter() {
+        let Ok(inventory) = redfish.get_firmware(inventory_id).await else {
+            continue;
+        };
+        if let Some(version) = inventory.version {
+            firmware_versions.insert(inventory.id, version);
+        }
+    }
+
+    Ok(BmcInventory {
+        power_state,
+        serial_numbers,
+        firmware_versions,
+    })
+}
+
+/// Checks that the BMC reports the serial number of the expected device entry.
+/// A mismatch means that the BMC MAC in the expected device entry belongs to a different device.
+pub fn verify_identity(
+    kind: BmcDeviceKind,
+    bmc: &DeviceBmc,
+    serial_numbers: &[String],
+) -> Result<(), String> {
+    if serial_numbers.contains(&bmc.serial_number) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} BMC {} reports serial numbers [{}], expected {}",
+            kind.title(),
+            bmc.ip,
+            serial_numbers.join(", "),
+            bmc.serial_number
+        ))
+    }
+}
+
+/// Returns whether the device needs to be probed again
+pub fn health_check_due(
+    last_health_check: Option<DateTime<Utc>>,
+    interval: chrono::Duration,
+) -> bool {
+    match last_health_check {
+        Some(last_check) => Utc::now() - last_check >= interval,
+        None => true,
+    }
+}
+
+/// Determines the outcome of re-probing a device which is in an error state.
+///
+/// A device which is reachable and matches its expected device entry again gets
+/// re-initialized. A device which still fails only transitions if the cause changed.
+#[track_caller]
+pub fn reprobe_outcome<S>(
+    object_id: &dyn std::fmt::Display,
+    kind: BmcDeviceKind,
+    previous_cause: &str,
+    result: Result<(), String>,
+    initializing: S,
+    error: impl FnOnce(String) -> S,
+) -> StateHandlerOutcome<S> {
+    match result {
+        Ok(()) => {
+            tracing::info!(
+                %object_id,
+                previous_cause,
+                "{} recovered from error",
+                kind.title()
+            );
+            StateHandlerOutcome::transition(initializing)
+        }
+        Err(cause) if cause != previous_cause => StateHandlerOutcome::transition(error(cause)),
+        Err(_) => StateHandlerOutcome::do_nothing(),
+    }
+}
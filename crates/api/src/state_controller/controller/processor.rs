@@ -773,7 +773,6 @@ async fn process_object<IO: StateControllerIO>(
     if let Some(next_state) = &metrics.common.next_state {
         state_change_emitter.emit(StateChangeEvent {
             object_id: &object_id,
-            #[cfg(test)]
             previous_state: metrics.common.initial_state.as_ref(),
             new_state: next_state,
             timestamp: chrono::Utc::now(),
@@ -28,11 +28,17 @@ use sqlx::PgConnection;
 use crate::state_controller::dpa_interface::context::DpaInterfaceStateHandlerContextObjects;
 use crate::state_controller::dpa_interface::metrics::DpaInterfaceMetricsEmitter;
 use crate::state_controller::io::StateControllerIO;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 /// State Controller IO implementation for dpa interfaces
 #[derive(Default, Debug)]
 pub struct DpaInterfaceStateControllerIO {}
 
+impl PublishableState for DpaInterfaceControllerState {
+    const OBJECT_TYPE: &'static str = "dpa_interface";
+    const STATE_FIELD: &'static str = "dpa_interface_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for DpaInterfaceStateControllerIO {
     type ObjectId = DpaInterfaceId;
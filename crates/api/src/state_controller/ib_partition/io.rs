@@ -29,11 +29,17 @@ use sqlx::PgConnection;
 use crate::state_controller::ib_partition::context::IBPartitionStateHandlerContextObjects;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::metrics::NoopMetricsEmitter;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 /// State Controller IO implementation for Infiniband Partitions
 #[derive(Default, Debug)]
 pub struct IBPartitionStateControllerIO {}
 
+impl PublishableState for IBPartitionControllerState {
+    const OBJECT_TYPE: &'static str = "ib_partition";
+    const STATE_FIELD: &'static str = "ib_partition_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for IBPartitionStateControllerIO {
     type ObjectId = IBPartitionId;
@@ -23,6 +23,7 @@ use std::net::IpAddr;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
+use attestation::handle_attestation_before_allocation;
 use carbide_dpf::KubeImpl;
 use carbide_uuid::machine::MachineId;
 use chrono::{DateTime, Duration, Utc};
@@ -48,18 +49,23 @@ use librms::RackManagerError;
 use librms::protos::rack_manager::NodeType as RmsNodeType;
 use machine_validation::{handle_machine_validation_requested, handle_machine_validation_state};
 use measured_boot::records::MeasurementMachineState;
+use mlxconfig::{
+    handle_mlxconfig_sync_requested, handle_mlxconfig_sync_state, next_state_after_discovery,
+};
 use model::DpuModel;
 use model::firmware::{Firmware, FirmwareComponentType, FirmwareEntry};
 use model::instance::InstanceNetworkSyncStatus;
 use model::instance::config::network::{
     DeviceLocator, InstanceInterfaceConfig, InterfaceFunctionId, NetworkDetails,
 };
+use model::instance::event::InstanceEventPayload;
 use model::instance::snapshot::InstanceSnapshot;
 use model::instance::status::SyncState;
 use model::instance::status::extension_service::{
     self, ExtensionServiceDeploymentStatus, ExtensionServicesReadiness,
     InstanceExtensionServicesStatus,
 };
+use model::instance::status::tenant::TenantState;
 use model::machine::LockdownMode::{self, Enable};
 use model::machine::infiniband::{IbConfigNotSyncedReason, ib_config_synced};
 use model::machine::nvlink::nvlink_config_synced;
@@ -105,9 +111,11 @@ use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
 
+mod attestation;
 mod dpf;
 mod helpers;
 mod machine_validation;
+mod mlxconfig;
 mod power;
 mod sku;
 use helpers::{
@@ -965,6 +973,16 @@ impl MachineStateHandler {
 
                 // Check if instance to be created.
                 if mh_snapshot.instance.is_some() {
+                    if let Some(outcome) = handle_attestation_before_allocation(
+                        mh_snapshot,
+                        ctx.services,
+                        self.host_handler.host_handler_params.attestation_enabled,
+                    )
+                    .await?
+                    {
+                        return Ok(outcome);
+                    }
+
                     // Instance is requested by user. Let's configure it.
                     let mut txn = ctx.services.db_pool.begin().await?;
 
@@ -996,6 +1014,12 @@ impl MachineStateHandler {
                     return Ok(outcome);
                 }
 
+                if let Some(outcome) =
+                    handle_mlxconfig_sync_requested(mh_snapshot, ctx.services).await?
+                {
+                    return Ok(outcome);
+                }
+
                 if host_reprovisioning_requested(mh_snapshot) {
                     let outcome = self
                         .host_upgrade
@@ -5142,14 +5166,16 @@ impl StateHandler for HostMachineStateHandler {
                         }
                     }
                 }
+                MachineState::MlxConfigSync => handle_mlxconfig_sync_state(mh_snapshot, ctx).await,
                 MachineState::Discovered {
                     skip_reboot_wait: skip_reboot,
                 } => {
-                    // Check if machine is rebooted. If yes, move to Ready state
-                    // or Measuring state, depending on if machine attestation
-                    // is enabled or not.
+                    // Check if machine is rebooted. If yes, move to Ready state,
+                    // or sync the mlxconfig profile bound to the host first.
                     if rebooted(&mh_snapshot.host_snapshot) || *skip_reboot {
-                        Ok(StateHandlerOutcome::transition(ManagedHostState::Ready))
+                        Ok(StateHandlerOutcome::transition(
+                            next_state_after_discovery(mh_snapshot, ctx.services).await?,
+                        ))
                     } else {
                         let status = trigger_reboot_if_needed(
                             &mh_snapshot.host_snapshot,
@@ -5891,12 +5917,35 @@ impl StateHandler for InstanceStateHandler {
                     // if instance is deleted before, we won't get network segment details as these
                     // details are stored in instance's network config which is deleted.
 
+                    let next_state = if self.attestation_enabled {
+                        ManagedHostState::PostAssignedMeasuring {
+                            measuring_state: MeasuringState::WaitingForMeasurements,
+                        }
+                    } else {
+                        ManagedHostState::WaitingForCleanup {
+                            cleanup_state: CleanupState::Init,
+                        }
+                    };
+
                     // Delete from database now. Once done, reboot and move to next state.
                     let mut txn = ctx.services.db_pool.begin().await?;
                     db::instance::delete(instance.id, &mut txn)
                         .await
                         .map_err(|err| StateHandlerError::GenericError(err.into()))?;
 
+                    // The instance is gone after this transition. Therefore the
+                    // final event for it can't be derived from the state change.
+                    db::instance_event::persist(
+                        &mut txn,
+                        instance.id,
+                        &instance.config.tenant.tenant_organization_id,
+                        &InstanceEventPayload::StateTransition {
+                            tenant_state: TenantState::Terminated,
+                            machine_state: next_state.to_string(),
+                        },
+                    )
+                    .await?;
+
                     release_network_segments_with_vpc_prefix(
                         &instance.config.network.interfaces,
                         &mut txn,
@@ -5907,16 +5956,6 @@ impl StateHandler for InstanceStateHandler {
                     release_vpc_dpu_loopback(mh_snapshot, self.common_pools.as_deref(), &mut txn)
                         .await?;
 
-                    let next_state = if self.attestation_enabled {
-                        ManagedHostState::PostAssignedMeasuring {
-                            measuring_state: MeasuringState::WaitingForMeasurements,
-                        }
-                    } else {
-                        ManagedHostState::WaitingForCleanup {
-                            cleanup_state: CleanupState::Init,
-                        }
-                    };
-
                     Ok(StateHandlerOutcome::transition(next_state).with_txn(txn))
                 }
                 InstanceState::DPUReprovision { .. } => {
@@ -6705,6 +6744,13 @@ impl HostUpgradeState {
                 ..
             } => ret,
             _ => {
+                // We only get to CheckingFirmwareRepeat after firmware was upgraded
+                let reattest = repeat
+                    && ctx
+                        .services
+                        .site_config
+                        .attestation_policy
+                        .reattest_on_firmware_change;
                 ret.in_transaction(&ctx.services.db_pool, move |txn| {
                     async move {
                         db::host_machine_update::clear_host_reprovisioning_request(
@@ -6718,6 +6764,13 @@ impl HostUpgradeState {
                             &machine_id,
                         )
                         .await?;
+                        if reattest {
+                            crate::attestation::policy::request_reattestation_after_firmware_change(
+                                txn,
+                                &machine_id,
+                            )
+                            .await?;
+                        }
                         Ok::<_, DatabaseError>(())
                     }
                     .boxed()
@@ -0,0 +1,93 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use model::attestation::spdm::SpdmAttestationStatus;
+use model::machine::{ManagedHostState, ManagedHostStateSnapshot, MeasuringState};
+
+use super::check_if_should_redo_measurements;
+use crate::attestation::policy::request_spdm_reattestation_if_due;
+use crate::state_controller::common_services::CommonStateHandlerServices;
+use crate::state_controller::state_handler::{StateHandlerError, StateHandlerOutcome};
+
+/// Holds a `Ready` host with a newly allocated instance back until the host was
+/// attested after the allocation, if the attestation policy asks for it. Hosts
+/// which failed SPDM attestation since are held back until they pass.
+pub(crate) async fn handle_attestation_before_allocation(
+    mh_snapshot: &ManagedHostStateSnapshot,
+    services: &mut CommonStateHandlerServices,
+    attestation_enabled: bool,
+) -> Result<Option<StateHandlerOutcome<ManagedHostState>>, StateHandlerError> {
+    let site_config = services.site_config.clone();
+    let policy = &site_config.attestation_policy;
+    let Some(instance) = mh_snapshot.instance.as_ref() else {
+        return Ok(None);
+    };
+    if !policy.reattest_before_allocation {
+        return Ok(None);
+    }
+    let machine_id = &mh_snapshot.host_snapshot.id;
+    let allocated_at = instance.config_version.timestamp();
+
+    let mut txn = services.db_pool.begin().await?;
+    if request_spdm_reattestation_if_due(&mut txn, policy, machine_id, Some(allocated_at)).await? {
+        return Ok(Some(
+            StateHandlerOutcome::wait(
+                "Waiting for SPDM attestation of the host before allocating the instance"
+                    .to_string(),
+            )
+            .with_txn(txn),
+        ));
+    }
+    let spdm_failed = db::attestation::spdm::load_details_for_machine_ids(&mut txn, &[*machine_id])
+        .await?
+        .into_iter()
+        .next()
+        .is_some_and(|details| {
+            details.machine.attestation_status == SpdmAttestationStatus::Completed
+                && details.machine.last_attestation_passed == Some(false)
+        });
+    txn.commit().await?;
+    if spdm_failed {
+        return Ok(Some(StateHandlerOutcome::wait(
+            "Host failed SPDM attestation, not allocating the instance".to_string(),
+        )));
+    }
+
+    if !attestation_enabled {
+        return Ok(None);
+    }
+    let last_measured_at = db::measured_boot::report::get_latest_tstamp_for_machine_id(
+        &mut services.db_reader,
+        *machine_id,
+    )
+    .await?;
+    if last_measured_at.is_none_or(|measured_at| measured_at < allocated_at) {
+        // Scout is asked for new measurements as long as the host is Ready
+        return Ok(Some(StateHandlerOutcome::wait(
+            "Waiting for measurements of the host before allocating the instance".to_string(),
+        )));
+    }
+    if check_if_should_redo_measurements(machine_id, &mut services.db_reader).await? {
+        return Ok(Some(StateHandlerOutcome::transition(
+            ManagedHostState::Measuring {
+                measuring_state: MeasuringState::WaitingForMeasurements,
+            },
+        )));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,141 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use health_report::HealthReport;
+use libredfish::SystemPowerControl;
+use model::machine::{MachineState, ManagedHostState, ManagedHostStateSnapshot};
+use model::mlxconfig_profile::StoredMlxConfigProfile;
+
+use crate::state_controller::common_services::CommonStateHandlerServices;
+use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
+use crate::state_controller::machine::handler::handler_host_power_control;
+use crate::state_controller::state_handler::{
+    StateHandlerContext, StateHandlerError, StateHandlerOutcome,
+};
+
+async fn find_bound_profile(
+    mh_snapshot: &ManagedHostStateSnapshot,
+    services: &mut CommonStateHandlerServices,
+) -> Result<Option<StoredMlxConfigProfile>, StateHandlerError> {
+    let host = &mh_snapshot.host_snapshot;
+    Ok(db::mlxconfig_profile::find_bound_profile(
+        &mut services.db_reader,
+        host.hw_sku.as_deref(),
+        host.instance_type_id.as_ref(),
+    )
+    .await?)
+}
+
+/// Returns the state a host that finished discovery moves to: `MlxConfigSync`
+/// if a profile is bound to the host, `Ready` otherwise
+pub(crate) async fn next_state_after_discovery(
+    mh_snapshot: &ManagedHostStateSnapshot,
+    services: &mut CommonStateHandlerServices,
+) -> Result<ManagedHostState, StateHandlerError> {
+    Ok(match find_bound_profile(mh_snapshot, services).await? {
+        Some(_) => ManagedHostState::HostInit {
+            machine_state: MachineState::MlxConfigSync,
+        },
+        None => ManagedHostState::Ready,
+    })
+}
+
+/// Moves a `Ready` host back to `MlxConfigSync` if its bound profile changed,
+/// the last sync scout reported for it did not succeed, or scout observed variable
+/// values on its devices which drifted from the profile
+pub(crate) async fn handle_mlxconfig_sync_requested(
+    mh_snapshot: &ManagedHostStateSnapshot,
+    services: &mut CommonStateHandlerServices,
+) -> Result<Option<StateHandlerOutcome<ManagedHostState>>, StateHandlerError> {
+    let Some(profile) = find_bound_profile(mh_snapshot, services).await? else {
+        return Ok(None);
+    };
+
+    let status = db::mlxconfig_profile::find_machine_status(
+        &mut services.db_reader,
+        &mh_snapshot.host_snapshot.id,
+    )
+    .await?;
+    if status.is_some_and(|status| status.is_in_sync_with(&profile)) {
+        return Ok(None);
+    }
+
+    tracing::info!(
+        machine_id = %mh_snapshot.host_snapshot.id,
+        profile_name = %profile.name,
+        profile_version = %profile.version,
+        "Host is not in sync with its mlxconfig profile"
+    );
+    Ok(Some(StateHandlerOutcome::transition(
+        ManagedHostState::HostInit {
+            machine_state: MachineState::MlxConfigSync,
+        },
+    )))
+}
+
+/// Handles `HostInit { MlxConfigSync }`: waits for scout to report the outcome of
+/// syncing the bound profile, and reboots the host if the sync changed any
+/// variables so they take effect
+pub(crate) async fn handle_mlxconfig_sync_state(
+    mh_snapshot: &ManagedHostStateSnapshot,
+    ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
+) -> Result<StateHandlerOutcome<ManagedHostState>, StateHandlerError> {
+    let host = &mh_snapshot.host_snapshot;
+    let Some(profile) = find_bound_profile(mh_snapshot, ctx.services).await? else {
+        // The profile was unbound while the host was syncing
+        return Ok(StateHandlerOutcome::transition(ManagedHostState::Ready));
+    };
+
+    let status =
+        db::mlxconfig_profile::find_machine_status(&mut ctx.services.db_reader, &host.id).await?;
+    let Some(status) = status.filter(|status| {
+        status.is_for(&profile) && status.observed_at > host.state.version.timestamp()
+    }) else {
+        return Ok(StateHandlerOutcome::wait(format!(
+            "Waiting for scout to sync mlxconfig profile {} version {}",
+            profile.name, profile.version
+        )));
+    };
+
+    if !status.synced {
+        return Ok(StateHandlerOutcome::wait(format!(
+            "Scout failed to sync mlxconfig profile {} version {}. See the {} health alert for details",
+            profile.name,
+            profile.version,
+            HealthReport::MLXCONFIG_PROFILE_SOURCE
+        )));
+    }
+
+    if status.variables_changed > 0 {
+        tracing::info!(
+            machine_id = %host.id,
+            profile_name = %profile.name,
+            variables_changed = status.variables_changed,
+            "Rebooting host to apply mlxconfig profile"
+        );
+        handler_host_power_control(mh_snapshot, ctx, SystemPowerControl::ForceRestart).await?;
+        return Ok(StateHandlerOutcome::transition(
+            ManagedHostState::HostInit {
+                machine_state: MachineState::Discovered {
+                    skip_reboot_wait: false,
+                },
+            },
+        ));
+    }
+
+    Ok(StateHandlerOutcome::transition(ManagedHostState::Ready))
+}
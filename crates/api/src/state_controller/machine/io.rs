@@ -29,9 +29,11 @@ use model::machine::{
 };
 use sqlx::PgConnection;
 
+use crate::instance_events;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::machine::context::MachineStateHandlerContextObjects;
 use crate::state_controller::machine::metrics::MachineMetricsEmitter;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 // This should be updated on each new model introdunction
 pub const CURRENT_STATE_MODEL_VERSION: i16 = 2;
@@ -42,6 +44,11 @@ pub struct MachineStateControllerIO {
     pub host_health: HostHealthConfig,
 }
 
+impl PublishableState for ManagedHostState {
+    const OBJECT_TYPE: &'static str = "machine";
+    const STATE_FIELD: &'static str = "managed_host_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for MachineStateControllerIO {
     type ObjectId = MachineId;
@@ -125,6 +132,9 @@ impl StateControllerIO for MachineStateControllerIO {
         _old_version: ConfigVersion,
         new_state: &Self::ControllerState,
     ) -> Result<(), DatabaseError> {
+        // Instance events are derived from the previous state, and therefore
+        // need to be recorded before the new state is written
+        instance_events::recorder::record_state_change(txn, object_id, new_state).await?;
         db::machine::update_state(txn, object_id, new_state).await
     }
 
@@ -165,6 +175,7 @@ impl StateControllerIO for MachineStateControllerIO {
                 MachineState::WaitingForLockdown { .. } => "waitingforlockdown",
                 MachineState::EnableIpmiOverLan => "enableipmioverlan",
                 MachineState::Measuring { .. } => "machinestatemeasuring",
+                MachineState::MlxConfigSync => "mlxconfigsync",
             }
         }
 
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+pub mod bmc_device;
 pub mod common_services;
 pub mod config;
 pub mod controller;
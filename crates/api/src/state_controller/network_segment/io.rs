@@ -28,11 +28,17 @@ use sqlx::PgConnection;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::network_segment::context::NetworkSegmentStateHandlerContextObjects;
 use crate::state_controller::network_segment::metrics::NetworkSegmentMetricsEmitter;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 /// State Controller IO implementation for network segments
 #[derive(Default, Debug)]
 pub struct NetworkSegmentStateControllerIO {}
 
+impl PublishableState for NetworkSegmentControllerState {
+    const OBJECT_TYPE: &'static str = "network_segment";
+    const STATE_FIELD: &'static str = "network_segment_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for NetworkSegmentStateControllerIO {
     type ObjectId = NetworkSegmentId;
@@ -14,19 +14,308 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::BTreeMap;
+
 use carbide_uuid::power_shelf::PowerShelfId;
-use db::power_shelf as db_power_shelf;
-use model::power_shelf::{PowerShelf, PowerShelfControllerState};
+use db::db_read::DbReader;
+use db::{
+    expected_power_shelf as db_expected_power_shelf, machine_interface as db_machine_interface,
+    power_shelf as db_power_shelf, rack as db_rack,
+};
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthProbeSuccess, HealthReport,
+};
+use libredfish::{PowerState, Redfish, SystemPowerControl};
+use model::power_shelf::power_supply::PowerSupplyInventory;
+use model::power_shelf::{PowerShelf, PowerShelfControllerState, PowerShelfStatus};
+use model::rack::RackPowerSequence;
+use sqlx::PgTransaction;
 
+use crate::state_controller::bmc_device::{self, BmcDeviceKind, BmcInventory, DeviceBmc};
 use crate::state_controller::power_shelf::context::PowerShelfStateHandlerContextObjects;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
 
+/// Source of the health reports which are generated by the power shelf state handler
+const HEALTH_REPORT_SOURCE: &str = "power-shelf-state-controller";
+
 /// The actual PowerShelf State handler
 #[derive(Debug, Default, Clone)]
 pub struct PowerShelfStateHandler {}
 
+/// Inventory and health data read from a power shelf
+#[derive(Debug, Clone)]
+struct PowerShelfInventory {
+    power_state: PowerState,
+    serial_numbers: Vec<String>,
+    firmware_versions: BTreeMap<String, String>,
+    power_supplies: PowerSupplyInventory,
+}
+
+impl PowerShelfInventory {
+    /// Returns the reason why the power supplies of the shelf are no longer
+    /// able to carry the load with redundancy, if this is the case
+    fn redundancy_lost(&self) -> Option<String> {
+        let healthy = self.power_supplies.healthy_count();
+        if let Some(redundancy) = self.power_supplies.redundancy.as_ref()
+            && redundancy.is_lost()
+        {
+            return Some(format!(
+                "Power shelf reports redundancy health {}. {healthy} of {} power supplies are healthy",
+                redundancy.health.as_deref().unwrap_or_default(),
+                self.power_supplies.power_supplies.len()
+            ));
+        }
+
+        let min_needed = self
+            .power_supplies
+            .redundancy
+            .as_ref()
+            .and_then(|redundancy| redundancy.min_num_needed)? as usize;
+        (healthy < min_needed).then(|| {
+            format!("{healthy} power supplies are healthy, at least {min_needed} are required")
+        })
+    }
+
+    fn health_report(&self) -> HealthReport {
+        let mut report = HealthReport::empty(HEALTH_REPORT_SOURCE.to_string());
+        report.observed_at = Some(chrono::Utc::now());
+
+        let power_supply_probe: health_report::HealthProbeId = "PowerSupply".parse().unwrap();
+        for psu in self.power_supplies.power_supplies.iter() {
+            if psu.is_healthy() {
+                report.successes.push(HealthProbeSuccess {
+                    id: power_supply_probe.clone(),
+                    target: Some(psu.id.clone()),
+                });
+            } else {
+                report.alerts.push(HealthProbeAlert {
+                    id: power_supply_probe.clone(),
+                    target: Some(psu.id.clone()),
+                    in_alert_since: None,
+                    message: format!(
+                        "Power supply {} (serial number {}) reports state {} and health {}",
+                        psu.name.as_deref().unwrap_or(&psu.id),
+                        psu.serial_number.as_deref().unwrap_or("unknown"),
+                        psu.state.as_deref().unwrap_or("unknown"),
+                        psu.health.as_deref().unwrap_or("unknown"),
+                    ),
+                    tenant_message: None,
+                    classifications: vec![HealthAlertClassification::hardware()],
+                });
+            }
+        }
+
+        let redundancy_probe: health_report::HealthProbeId = "PowerRedundancy".parse().unwrap();
+        match self.redundancy_lost() {
+            None => report.successes.push(HealthProbeSuccess {
+                id: redundancy_probe,
+                target: None,
+            }),
+            Some(message) => report.alerts.push(HealthProbeAlert {
+                id: redundancy_probe,
+                target: None,
+                in_alert_since: None,
+                message,
+                tenant_message: None,
+                classifications: vec![
+                    HealthAlertClassification::hardware(),
+                    HealthAlertClassification::sensor_critical(),
+                ],
+            }),
+        }
+
+        report
+    }
+
+    fn health_status(&self) -> &'static str {
+        if self.redundancy_lost().is_some() {
+            PowerShelfStatus::HEALTH_CRITICAL
+        } else if self.power_state != PowerState::On
+            || self.power_supplies.healthy_count() < self.power_supplies.power_supplies.len()
+        {
+            PowerShelfStatus::HEALTH_WARNING
+        } else {
+            PowerShelfStatus::HEALTH_OK
+        }
+    }
+
+    fn to_status(&self, shelf_name: &str, previous: Option<&PowerShelfStatus>) -> PowerShelfStatus {
+        let mut health_report = self.health_report();
+        health_report
+            .update_in_alert_since(previous.and_then(|previous| previous.health_report.as_ref()));
+
+        PowerShelfStatus {
+            shelf_name: shelf_name.to_string(),
+            power_state: self.power_state.to_string().to_lowercase(),
+            health_status: self.health_status().to_string(),
+            serial_number: self.serial_numbers.first().cloned(),
+            firmware_versions: self.firmware_versions.clone(),
+            power_supplies: self.power_supplies.power_supplies.clone(),
+            redundancy: self.power_supplies.redundancy.clone(),
+            health_report: Some(health_report),
+            last_health_check: Some(chrono::Utc::now()),
+        }
+    }
+}
+
+/// Validates the power shelf against its `ExpectedPowerShelf` entry and locates the BMC.
+///
+/// Returns `Err(cause)` with a human readable cause if the power shelf can not be
+/// matched to what was expected.
+async fn validate_power_shelf<DB>(
+    db: &mut DB,
+    power_shelf_id: &PowerShelfId,
+    state: &PowerShelf,
+) -> Result<Result<DeviceBmc, String>, StateHandlerError>
+where
+    for<'db> &'db mut DB: DbReader<'db>,
+{
+    let serial_number = state.config.name.as_str();
+    let Some(expected_power_shelf) =
+        db_expected_power_shelf::find_by_serial_number(&mut *db, serial_number).await?
+    else {
+        return Ok(Err(format!(
+            "No expected power shelf with serial number {serial_number} exists"
+        )));
+    };
+
+    let interfaces = db_machine_interface::find_by_power_shelf_id(&mut *db, power_shelf_id).await?;
+    Ok(bmc_device::locate_bmc(
+        BmcDeviceKind::PowerShelf,
+        &interfaces,
+        expected_power_shelf.bmc_mac_address,
+        expected_power_shelf.serial_number,
+    ))
+}
+
+/// Returns the power sequence of the rack owning the power shelf if it
+/// currently determines the power state of the shelf. The power policy is not
+/// applied in that case, since it would power the shelf back on while the rack
+/// is being powered off, or after it was.
+async fn overriding_rack_power_sequence<DB>(
+    db: &mut DB,
+    state: &PowerShelf,
+) -> Result<Option<RackPowerSequence>, StateHandlerError>
+where
+    for<'db> &'db mut DB: DbReader<'db>,
+{
+    let Some(rack_id) =
+        db_expected_power_shelf::find_by_serial_number(&mut *db, state.config.name.as_str())
+            .await?
+            .and_then(|expected_power_shelf| expected_power_shelf.rack_id)
+    else {
+        return Ok(None);
+    };
+    let rack = db_rack::get(&mut *db, rack_id).await?;
+    Ok(rack
+        .power_sequence
+        .filter(RackPowerSequence::overrides_power_policy))
+}
+
+impl PowerShelfStateHandler {
+    /// Reads inventory, firmware versions, power state and the power supply
+    /// status from the power shelf BMC
+    async fn fetch_inventory(
+        &self,
+        redfish: &dyn Redfish,
+    ) -> Result<PowerShelfInventory, StateHandlerError> {
+        let BmcInventory {
+            power_state,
+            serial_numbers,
+            firmware_versions,
+        } = bmc_device::fetch_inventory(redfish).await?;
+
+        let power =
+            redfish
+                .get_power_metrics()
+                .await
+                .map_err(|error| StateHandlerError::RedfishError {
+                    operation: "get_power_metrics",
+                    error,
+                })?;
+        let power_supplies = serde_json::to_value(&power)
+            .and_then(|power| PowerSupplyInventory::from_redfish_power(&power))
+            .map_err(|e| {
+                StateHandlerError::GenericError(eyre::eyre!(
+                    "Failed to parse power supplies of power shelf: {e}"
+                ))
+            })?;
+
+        Ok(PowerShelfInventory {
+            power_state,
+            serial_numbers,
+            firmware_versions,
+            power_supplies,
+        })
+    }
+
+    /// Probes the power shelf and refreshes its status.
+    ///
+    /// Returns `Err(cause)` if the power shelf is unreachable or does not match its
+    /// `ExpectedPowerShelf` entry. In that case the power shelf status is marked as critical.
+    /// The updated status is written by the returned transaction, which has to be
+    /// passed back with the outcome of the handler.
+    async fn refresh_status(
+        &self,
+        power_shelf_id: &PowerShelfId,
+        state: &mut PowerShelf,
+        ctx: &mut StateHandlerContext<'_, PowerShelfStateHandlerContextObjects>,
+    ) -> Result<(Result<PowerShelfInventory, String>, PgTransaction<'static>), StateHandlerError>
+    {
+        let result =
+            match validate_power_shelf(&mut ctx.services.db_reader, power_shelf_id, state).await? {
+                Ok(bmc) => self.probe_power_shelf(&bmc, ctx).await,
+                Err(cause) => Err(cause),
+            };
+
+        state.status = Some(match &result {
+            Ok(inventory) => inventory.to_status(&state.config.name, state.status.as_ref()),
+            Err(_) => PowerShelfStatus {
+                shelf_name: state.config.name.clone(),
+                health_status: PowerShelfStatus::HEALTH_CRITICAL.to_string(),
+                last_health_check: Some(chrono::Utc::now()),
+                ..state.status.clone().unwrap_or_default()
+            },
+        });
+        let mut txn = ctx.services.db_pool.begin().await?;
+        db_power_shelf::update(state, &mut txn).await?;
+
+        Ok((result, txn))
+    }
+
+    async fn probe_power_shelf(
+        &self,
+        bmc: &DeviceBmc,
+        ctx: &StateHandlerContext<'_, PowerShelfStateHandlerContextObjects>,
+    ) -> Result<PowerShelfInventory, String> {
+        let redfish = bmc_device::connect(BmcDeviceKind::PowerShelf, bmc, ctx.services).await?;
+        let inventory = self
+            .fetch_inventory(redfish.as_ref())
+            .await
+            .map_err(|e| format!("Failed to fetch data from power shelf BMC {}: {e}", bmc.ip))?;
+        bmc_device::verify_identity(BmcDeviceKind::PowerShelf, bmc, &inventory.serial_numbers)?;
+        Ok(inventory)
+    }
+
+    fn health_check_due(
+        state: &PowerShelf,
+        ctx: &StateHandlerContext<'_, PowerShelfStateHandlerContextObjects>,
+    ) -> bool {
+        bmc_device::health_check_due(
+            state
+                .status
+                .as_ref()
+                .and_then(|status| status.last_health_check),
+            ctx.services
+                .site_config
+                .power_shelf_state_controller
+                .health_check_interval,
+        )
+    }
+}
+
 #[async_trait::async_trait]
 impl StateHandler for PowerShelfStateHandler {
     type ObjectId = PowerShelfId;
@@ -41,80 +330,186 @@ impl StateHandler for PowerShelfStateHandler {
         controller_state: &Self::ControllerState,
         ctx: &mut StateHandlerContext<Self::ContextObjects>,
     ) -> Result<StateHandlerOutcome<PowerShelfControllerState>, StateHandlerError> {
+        if state.is_marked_as_deleted() && *controller_state != PowerShelfControllerState::Deleting
+        {
+            return Ok(StateHandlerOutcome::transition(
+                PowerShelfControllerState::Deleting,
+            ));
+        }
+
         match controller_state {
             PowerShelfControllerState::Initializing => {
-                // TODO: Implement PowerShelf initialization logic
-                // This would typically involve:
-                // 1. Validating the PowerShelf configuration
-                // 2. Allocating resources
-                // 3. Setting up the PowerShelf in the power management system
-                tracing::info!("Initializing PowerShelf");
-                let new_state = PowerShelfControllerState::FetchingData;
-                Ok(StateHandlerOutcome::transition(new_state))
+                tracing::info!(%power_shelf_id, "Initializing PowerShelf");
+                match validate_power_shelf(&mut ctx.services.db_reader, power_shelf_id, state)
+                    .await?
+                {
+                    Ok(bmc) => {
+                        tracing::info!(
+                            %power_shelf_id,
+                            bmc_ip = %bmc.ip,
+                            bmc_mac = %bmc.mac_address,
+                            "PowerShelf matches its expected power shelf entry"
+                        );
+                        Ok(StateHandlerOutcome::transition(
+                            PowerShelfControllerState::FetchingData,
+                        ))
+                    }
+                    Err(cause) => Ok(StateHandlerOutcome::transition(
+                        PowerShelfControllerState::Error { cause },
+                    )),
+                }
             }
 
             PowerShelfControllerState::FetchingData => {
-                tracing::info!("Fetching PowerShelf data");
-                // TODO: Implement PowerShelf fetching data logic
-                // This would typically involve:
-                // 1. Fetching data from the PowerShelf
-                // 2. Updating the PowerShelf status
-                let new_state = PowerShelfControllerState::Configuring;
-                Ok(StateHandlerOutcome::transition(new_state))
+                tracing::info!(%power_shelf_id, "Fetching PowerShelf data");
+                let (result, txn) = self.refresh_status(power_shelf_id, state, ctx).await?;
+                let next_state = match result {
+                    Ok(_) => PowerShelfControllerState::Configuring,
+                    Err(cause) => PowerShelfControllerState::Error { cause },
+                };
+                Ok(StateHandlerOutcome::transition(next_state).with_txn(txn))
             }
 
             PowerShelfControllerState::Configuring => {
-                tracing::info!("Configuring PowerShelf");
-                // TODO: Implement PowerShelf configuring logic
-                // This would typically involve:
-                // 1. Configuring the PowerShelf
-                // 2. Updating the PowerShelf status
-                let new_state = PowerShelfControllerState::Ready;
-                Ok(StateHandlerOutcome::transition(new_state))
+                tracing::info!(%power_shelf_id, "Configuring PowerShelf");
+                let bmc =
+                    match validate_power_shelf(&mut ctx.services.db_reader, power_shelf_id, state)
+                        .await?
+                    {
+                        Ok(bmc) => bmc,
+                        Err(cause) => {
+                            return Ok(StateHandlerOutcome::transition(
+                                PowerShelfControllerState::Error { cause },
+                            ));
+                        }
+                    };
+
+                // Apply the site power policy: The shelf outputs need to be
+                // powered on to supply the rack, unless the rack is powered off
+                let power_on = ctx
+                    .services
+                    .site_config
+                    .power_shelf_state_controller
+                    .power_policy
+                    .power_on;
+                let rack_power_sequence =
+                    overriding_rack_power_sequence(&mut ctx.services.db_reader, state).await?;
+                if let Some(sequence) = rack_power_sequence.as_ref() {
+                    tracing::info!(
+                        %power_shelf_id,
+                        rack_power_action = %sequence.action,
+                        "Not applying the power policy while the rack power sequence controls the PowerShelf"
+                    );
+                }
+                if power_on && rack_power_sequence.is_none() {
+                    let redfish = bmc_device::create_redfish_client(&bmc, ctx.services).await?;
+                    let power_state = redfish.get_power_state().await.map_err(|error| {
+                        StateHandlerError::RedfishError {
+                            operation: "get_power_state",
+                            error,
+                        }
+                    })?;
+                    if power_state != PowerState::On {
+                        tracing::info!(%power_shelf_id, %power_state, "Powering on PowerShelf");
+                        redfish
+                            .power(SystemPowerControl::On)
+                            .await
+                            .map_err(|error| StateHandlerError::RedfishError {
+                                operation: "power_on",
+                                error,
+                            })?;
+                        return Ok(StateHandlerOutcome::wait(format!(
+                            "Waiting for power shelf to power on. Current power state: {power_state}"
+                        )));
+                    }
+                }
+
+                // Re-read the inventory so that Ready starts with up-to-date data
+                let (result, txn) = self.refresh_status(power_shelf_id, state, ctx).await?;
+                let next_state = match result {
+                    Ok(_) => PowerShelfControllerState::Ready,
+                    Err(cause) => PowerShelfControllerState::Error { cause },
+                };
+                Ok(StateHandlerOutcome::transition(next_state).with_txn(txn))
             }
 
             PowerShelfControllerState::Deleting => {
-                tracing::info!("Deleting PowerShelf");
-                // TODO: Implement PowerShelf deletion logic
-                // This would typically involve:
-                // 1. Checking if the PowerShelf is in use
-                // 2. Safely shutting down the PowerShelf
-                // 3. Releasing allocated resources
-
-                // For now, just delete the PowerShelf from the database
+                tracing::info!(%power_shelf_id, "Deleting PowerShelf");
                 let mut txn = ctx.services.db_pool.begin().await?;
                 db_power_shelf::final_delete(*power_shelf_id, &mut txn).await?;
                 Ok(StateHandlerOutcome::deleted().with_txn(txn))
             }
 
             PowerShelfControllerState::Ready => {
-                tracing::info!("PowerShelf is ready");
-                if state.is_marked_as_deleted() {
-                    Ok(StateHandlerOutcome::transition(
-                        PowerShelfControllerState::Deleting,
-                    ))
-                } else {
-                    // TODO: Implement PowerShelf monitoring logic
-                    // This would typically involve:
-                    // 1. Checking PowerShelf health status
-                    // 2. Updating PowerShelf status
-                    // 3. Monitoring power consumption and efficiency
-
-                    // For now, just do nothing
-                    Ok(StateHandlerOutcome::do_nothing())
+                if !Self::health_check_due(state, ctx) {
+                    return Ok(StateHandlerOutcome::do_nothing());
                 }
+
+                let (result, txn) = self.refresh_status(power_shelf_id, state, ctx).await?;
+                let outcome = match result {
+                    Ok(inventory) => {
+                        let status = state.status.as_ref();
+                        let alerts = status
+                            .and_then(|status| status.health_report.as_ref())
+                            .map(|report| report.alerts.as_slice())
+                            .unwrap_or_default();
+                        if !alerts.is_empty() {
+                            tracing::warn!(
+                                %power_shelf_id,
+                                health_status = status.map(|status| status.health_status.as_str()),
+                                alerts = ?alerts,
+                                "PowerShelf is not healthy"
+                            );
+                        }
+
+                        // Re-apply the power policy if the shelf outputs were turned off
+                        let power_on = ctx
+                            .services
+                            .site_config
+                            .power_shelf_state_controller
+                            .power_policy
+                            .power_on;
+                        if power_on
+                            && inventory.power_state != PowerState::On
+                            && overriding_rack_power_sequence(&mut ctx.services.db_reader, state)
+                                .await?
+                                .is_none()
+                        {
+                            tracing::warn!(
+                                %power_shelf_id,
+                                power_state = %inventory.power_state,
+                                "PowerShelf is not powered on as required by the power policy"
+                            );
+                            StateHandlerOutcome::transition(PowerShelfControllerState::Configuring)
+                        } else {
+                            StateHandlerOutcome::do_nothing()
+                        }
+                    }
+                    Err(cause) => {
+                        tracing::error!(%power_shelf_id, %cause, "PowerShelf health check failed");
+                        StateHandlerOutcome::transition(PowerShelfControllerState::Error { cause })
+                    }
+                };
+                Ok(outcome.with_txn(txn))
             }
 
-            PowerShelfControllerState::Error { .. } => {
-                tracing::info!("PowerShelf is in error state");
-                if state.is_marked_as_deleted() {
-                    Ok(StateHandlerOutcome::transition(
-                        PowerShelfControllerState::Deleting,
-                    ))
-                } else {
-                    // If PowerShelf is in error state, keep it there for manual intervention
-                    Ok(StateHandlerOutcome::do_nothing())
+            PowerShelfControllerState::Error { cause } => {
+                // Periodically re-probe the power shelf. Once it is reachable and
+                // matches its expected power shelf entry again it gets re-initialized.
+                if !Self::health_check_due(state, ctx) {
+                    return Ok(StateHandlerOutcome::do_nothing());
                 }
+
+                let (result, txn) = self.refresh_status(power_shelf_id, state, ctx).await?;
+                let outcome = bmc_device::reprobe_outcome(
+                    power_shelf_id,
+                    BmcDeviceKind::PowerShelf,
+                    cause,
+                    result.map(|_| ()),
+                    PowerShelfControllerState::Initializing,
+                    |cause| PowerShelfControllerState::Error { cause },
+                );
+                Ok(outcome.with_txn(txn))
             }
         }
     }
@@ -29,11 +29,17 @@ use sqlx::PgConnection;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::metrics::NoopMetricsEmitter;
 use crate::state_controller::power_shelf::context::PowerShelfStateHandlerContextObjects;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 /// State Controller IO implementation for PowerShelves
 #[derive(Default, Debug)]
 pub struct PowerShelfStateControllerIO {}
 
+impl PublishableState for PowerShelfControllerState {
+    const OBJECT_TYPE: &'static str = "power_shelf";
+    const STATE_FIELD: &'static str = "power_shelf_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for PowerShelfStateControllerIO {
     type ObjectId = PowerShelfId;
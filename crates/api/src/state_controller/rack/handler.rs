@@ -16,21 +16,32 @@
  */
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::net::IpAddr;
 
+use carbide_uuid::machine::MachineId;
 use carbide_uuid::rack::RackId;
 use db::{expected_machine as db_expected_machine, rack as db_rack};
-use model::machine::{LoadSnapshotOptions, ManagedHostState};
+use libredfish::Redfish;
+use mac_address::MacAddress;
+use model::machine::{HostReprovisionState, LoadSnapshotOptions, ManagedHostState};
 use model::rack::{
-    Rack, RackFirmwareUpgradeState, RackMaintenanceState, RackPowerState, RackReadyState,
-    RackState, RackValidationState,
+    Rack, RackDeviceFirmwareUpgradeStatus, RackDeviceType, RackFirmwareUpgrade,
+    RackFirmwareUpgradeState, RackMaintenanceState, RackPowerSequence, RackPowerTarget,
+    RackReadyState, RackState, RackValidationState,
 };
-use sqlx::PgTransaction;
+use sqlx::{PgConnection, PgTransaction};
 
+use crate::rack;
+use crate::redfish::RedfishClientPool;
 use crate::state_controller::rack::context::RackStateHandlerContextObjects;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
 
+/// Initiator of the host reprovisioning requests of rack firmware upgrades
+const RACK_FIRMWARE_UPGRADE_INITIATOR: &str = "rack-firmware-upgrade";
+
 #[derive(Debug, Default, Clone)]
 pub struct RackStateHandler {}
 
@@ -179,23 +190,13 @@ impl StateHandler for RackStateHandler {
                     RackMaintenanceState::FirmwareUpgrade {
                         rack_firmware_upgrade,
                     } => {
-                        match rack_firmware_upgrade {
-                            RackFirmwareUpgradeState::Compute => {
-                                //TODO add code here
-                                return Ok(StateHandlerOutcome::transition(
-                                    RackState::Maintenance {
-                                        rack_maintenance: RackMaintenanceState::Completed,
-                                    },
-                                ));
-                            }
-                            RackFirmwareUpgradeState::Switch => {}
-                            RackFirmwareUpgradeState::PowerShelf => {}
-                            RackFirmwareUpgradeState::All => {
-                                // we may most likely use this for rack manager to do the entire rack
-                            }
-                        }
+                        return handle_firmware_upgrade(id, state, rack_firmware_upgrade, ctx)
+                            .await;
                     }
                     RackMaintenanceState::RackValidation { rack_validation } => {
+                        if let Some(outcome) = start_requested_maintenance(state) {
+                            return Ok(outcome);
+                        }
                         match rack_validation {
                             RackValidationState::Compute => {}
                             RackValidationState::Switch => {}
@@ -204,11 +205,10 @@ impl StateHandler for RackStateHandler {
                             RackValidationState::Topology => {}
                         }
                     }
-                    RackMaintenanceState::PowerSequence { rack_power } => match rack_power {
-                        RackPowerState::PoweringOn => {}
-                        RackPowerState::PoweringOff => {}
-                        RackPowerState::PowerReset => {}
-                    },
+                    RackMaintenanceState::PowerSequence { .. } => {
+                        // the steps of the sequence depend on the requested action
+                        return handle_power_sequence(id, state, ctx).await;
+                    }
                     RackMaintenanceState::Completed => {
                         return Ok(StateHandlerOutcome::transition(RackState::Ready {
                             rack_ready: RackReadyState::Full,
@@ -220,6 +220,9 @@ impl StateHandler for RackStateHandler {
             RackState::Ready {
                 rack_ready: ready_state,
             } => {
+                if let Some(outcome) = start_requested_maintenance(state) {
+                    return Ok(outcome);
+                }
                 match ready_state {
                     RackReadyState::Partial => {
                         // wait till rack is fully ready
@@ -236,6 +239,11 @@ impl StateHandler for RackStateHandler {
             }
             RackState::Deleting => Ok(StateHandlerOutcome::do_nothing()),
             RackState::Error { cause: log } => {
+                // a new firmware upgrade or power sequence request retries a
+                // failed one
+                if let Some(outcome) = start_requested_maintenance(state) {
+                    return Ok(outcome);
+                }
                 // try to recover / auto-remediate
                 tracing::error!("Rack {} is in error state {}", id, log);
                 Ok(StateHandlerOutcome::do_nothing())
@@ -244,3 +252,518 @@ impl StateHandler for RackStateHandler {
         }
     }
 }
+
+/// Starts a requested firmware upgrade or power sequence. A firmware upgrade
+/// takes precedence, the power sequence is started once it completed.
+fn start_requested_maintenance(state: &Rack) -> Option<StateHandlerOutcome<RackState>> {
+    start_requested_firmware_upgrade(state).or_else(|| start_requested_power_sequence(state))
+}
+
+/// Enters the firmware upgrade states if a firmware upgrade was requested via
+/// `ApplyRackFirmware` which has not been started yet
+fn start_requested_firmware_upgrade(state: &Rack) -> Option<StateHandlerOutcome<RackState>> {
+    let upgrade = state.firmware_upgrade.as_ref()?;
+    if upgrade.started_at.is_some() {
+        return None;
+    }
+    tracing::info!(
+        "Rack {} starts upgrading to firmware {}",
+        state.id,
+        upgrade.firmware_id
+    );
+    Some(StateHandlerOutcome::transition(RackState::Maintenance {
+        rack_maintenance: RackMaintenanceState::FirmwareUpgrade {
+            rack_firmware_upgrade: RackFirmwareUpgradeState::Compute,
+        },
+    }))
+}
+
+/// Applies the requested firmware upgrade to one type of device at a time.
+/// Compute trays are upgraded first, followed by switches and power shelves.
+/// The upgrade halts in the error state as soon as a device failed.
+async fn handle_firmware_upgrade(
+    id: &RackId,
+    state: &Rack,
+    phase: &RackFirmwareUpgradeState,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<StateHandlerOutcome<RackState>, StateHandlerError> {
+    let Some(mut upgrade) = state
+        .firmware_upgrade
+        .clone()
+        .filter(|upgrade| upgrade.completed_at.is_none())
+    else {
+        // no firmware upgrade was requested for the rack
+        return Ok(StateHandlerOutcome::transition(RackState::Maintenance {
+            rack_maintenance: RackMaintenanceState::Completed,
+        }));
+    };
+    if upgrade.started_at.is_none() {
+        upgrade.started_at = Some(chrono::Utc::now());
+    }
+
+    // RMS is called without holding a transaction, the reprovisioning
+    // requests of the started compute trays are written afterwards
+    let mut reprovision_requests = Vec::new();
+    let phase_done = match phase {
+        RackFirmwareUpgradeState::Compute => {
+            upgrade_compute_trays(*id, &mut upgrade, &mut reprovision_requests, ctx).await?
+        }
+        RackFirmwareUpgradeState::Switch => {
+            upgrade_rms_devices(*id, &mut upgrade, RackDeviceType::Switch, ctx).await
+        }
+        RackFirmwareUpgradeState::PowerShelf => {
+            upgrade_rms_devices(*id, &mut upgrade, RackDeviceType::PowerShelf, ctx).await
+        }
+        // we may most likely use this for rack manager to do the entire rack
+        RackFirmwareUpgradeState::All => true,
+    };
+
+    let next_state = if let Some(report) = upgrade.failure_report() {
+        tracing::error!("Rack {} firmware upgrade failed: {}", id, report);
+        Some(RackState::Error { cause: report })
+    } else if phase_done {
+        let next_phase = match phase {
+            RackFirmwareUpgradeState::Compute => Some(RackFirmwareUpgradeState::Switch),
+            RackFirmwareUpgradeState::Switch => Some(RackFirmwareUpgradeState::PowerShelf),
+            RackFirmwareUpgradeState::PowerShelf | RackFirmwareUpgradeState::All => None,
+        };
+        Some(match next_phase {
+            Some(rack_firmware_upgrade) => RackState::Maintenance {
+                rack_maintenance: RackMaintenanceState::FirmwareUpgrade {
+                    rack_firmware_upgrade,
+                },
+            },
+            None => {
+                upgrade.completed_at = Some(chrono::Utc::now());
+                RackState::Maintenance {
+                    rack_maintenance: RackMaintenanceState::Completed,
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let mut txn = ctx.services.db_pool.begin().await?;
+    for machine_id in &reprovision_requests {
+        db::host_machine_update::trigger_host_reprovisioning_request(
+            txn.as_mut(),
+            RACK_FIRMWARE_UPGRADE_INITIATOR,
+            machine_id,
+        )
+        .await?;
+    }
+    if state.firmware_upgrade.as_ref() != Some(&upgrade) {
+        db_rack::update_firmware_upgrade(&mut txn, *id, &upgrade).await?;
+    }
+
+    Ok(match next_state {
+        Some(next_state) => StateHandlerOutcome::transition(next_state),
+        None => StateHandlerOutcome::do_nothing(),
+    }
+    .with_txn(txn))
+}
+
+/// Reprovisions the compute trays of the rack in batches of at most
+/// `max_unavailable` trays. The firmware of a tray is staged through RMS
+/// before host reprovisioning is requested, and the tray is done once it
+/// returned to `Ready`. Trays which need to be reprovisioned are added to
+/// `reprovision_requests`. Returns whether all compute trays are upgraded.
+async fn upgrade_compute_trays(
+    id: RackId,
+    upgrade: &mut RackFirmwareUpgrade,
+    reprovision_requests: &mut Vec<MachineId>,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<bool, StateHandlerError> {
+    let max_unavailable = upgrade.max_unavailable;
+    let firmware_id = upgrade.firmware_id.clone();
+
+    let mut machine_ids = Vec::new();
+    for device in upgrade.devices_of_type(RackDeviceType::ComputeTray) {
+        let machine_id: MachineId = device.device_id.parse().map_err(|_| {
+            StateHandlerError::GenericError(eyre::eyre!(
+                "Invalid compute tray {} in firmware upgrade of rack {}",
+                device.device_id,
+                id
+            ))
+        })?;
+        machine_ids.push(machine_id);
+    }
+    let options = LoadSnapshotOptions {
+        include_history: false,
+        include_instance_data: false,
+        host_health_config: ctx.services.site_config.host_health,
+    };
+    let mh_snapshots =
+        db::managed_host::load_by_machine_ids(&mut ctx.services.db_reader, &machine_ids, options)
+            .await?;
+    let snapshot_of = |device_id: &str| {
+        let machine_id: MachineId = device_id.parse().ok()?;
+        mh_snapshots.get_key_value(&machine_id)
+    };
+
+    // trays which are being upgraded count against max_unavailable before
+    // any pending tray is started
+    let mut in_progress = 0;
+    for device in upgrade.devices.iter_mut().filter(|d| {
+        d.device_type == RackDeviceType::ComputeTray
+            && d.status == RackDeviceFirmwareUpgradeStatus::InProgress
+    }) {
+        let Some((machine_id, mh_snapshot)) = snapshot_of(&device.device_id) else {
+            return Err(StateHandlerError::MissingData {
+                object_id: device.device_id.clone(),
+                missing: "managed host not found",
+            });
+        };
+        match &mh_snapshot.managed_state {
+            ManagedHostState::HostReprovision {
+                reprovision_state:
+                    HostReprovisionState::FailedFirmwareUpgrade {
+                        firmware_type,
+                        reason,
+                        ..
+                    },
+                ..
+            } => device.fail(format!(
+                "Host firmware upgrade of {} failed: {}",
+                firmware_type,
+                reason.as_deref().unwrap_or("unknown reason")
+            )),
+            ManagedHostState::Failed { details, .. } => {
+                device.fail(format!("Host failed: {details}"))
+            }
+            ManagedHostState::Ready
+                if mh_snapshot
+                    .host_snapshot
+                    .host_reprovision_requested
+                    .is_none() =>
+            {
+                tracing::info!("Rack {} compute tray {} is upgraded", id, machine_id);
+                device.complete();
+            }
+            _ => in_progress += 1,
+        }
+    }
+
+    for device in upgrade.devices.iter_mut().filter(|d| {
+        d.device_type == RackDeviceType::ComputeTray
+            && d.status == RackDeviceFirmwareUpgradeStatus::Pending
+    }) {
+        if in_progress >= max_unavailable {
+            break;
+        }
+        let Some((machine_id, mh_snapshot)) = snapshot_of(&device.device_id) else {
+            return Err(StateHandlerError::MissingData {
+                object_id: device.device_id.clone(),
+                missing: "managed host not found",
+            });
+        };
+        if mh_snapshot.managed_state != ManagedHostState::Ready {
+            tracing::debug!(
+                "Rack {} waits for compute tray {} in {} state to become ready",
+                id,
+                machine_id,
+                mh_snapshot.managed_state
+            );
+            continue;
+        }
+
+        if !device.components.is_empty() {
+            let Some(rms_client) = ctx.services.rms_client.as_ref() else {
+                device.fail("RMS client not configured".to_string());
+                continue;
+            };
+            if let Err(message) = rack::firmware::update_device_firmware(
+                rms_client.as_ref(),
+                id,
+                &firmware_id,
+                &device.device_id,
+                &device.components,
+                false,
+            )
+            .await
+            {
+                device.fail(message);
+                continue;
+            }
+        }
+        tracing::info!(
+            "Rack {} requests reprovisioning of compute tray {}",
+            id,
+            machine_id
+        );
+        reprovision_requests.push(*machine_id);
+        device.start();
+        in_progress += 1;
+    }
+
+    Ok(upgrade
+        .devices_of_type(RackDeviceType::ComputeTray)
+        .all(|d| d.status == RackDeviceFirmwareUpgradeStatus::Completed))
+}
+
+/// Pushes and activates the firmware of all devices of the given type
+/// through RMS. Returns whether all of them are upgraded.
+async fn upgrade_rms_devices(
+    id: RackId,
+    upgrade: &mut RackFirmwareUpgrade,
+    device_type: RackDeviceType,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> bool {
+    let firmware_id = upgrade.firmware_id.clone();
+    for device in upgrade.devices.iter_mut().filter(|d| {
+        d.device_type == device_type && d.status == RackDeviceFirmwareUpgradeStatus::Pending
+    }) {
+        let Some(rms_client) = ctx.services.rms_client.as_ref() else {
+            device.fail("RMS client not configured".to_string());
+            continue;
+        };
+        device.start();
+        match rack::firmware::update_device_firmware(
+            rms_client.as_ref(),
+            id,
+            &firmware_id,
+            &device.device_id,
+            &device.components,
+            true,
+        )
+        .await
+        {
+            Ok(()) => device.complete(),
+            Err(message) => device.fail(message),
+        }
+    }
+
+    upgrade
+        .devices_of_type(device_type)
+        .all(|d| d.status == RackDeviceFirmwareUpgradeStatus::Completed)
+}
+
+/// Enters the power sequence states if a power sequence was requested via
+/// `SetRackPowerState` which has not been started yet
+fn start_requested_power_sequence(state: &Rack) -> Option<StateHandlerOutcome<RackState>> {
+    let sequence = state.power_sequence.as_ref()?;
+    if sequence.started_at.is_some() || !sequence.is_active() {
+        return None;
+    }
+    tracing::info!(
+        "Rack {} starts power sequence {} with {} steps",
+        state.id,
+        sequence.action,
+        sequence.steps.len()
+    );
+    Some(StateHandlerOutcome::transition(RackState::Maintenance {
+        rack_maintenance: RackMaintenanceState::PowerSequence {
+            rack_power: sequence.action.power_state(),
+        },
+    }))
+}
+
+/// Runs the requested power sequence one step at a time. The sequence is
+/// aborted into the error state if a power shelf reports a fault, a power
+/// command fails or the devices of a step don't reach the target power state
+/// within the step timeout.
+async fn handle_power_sequence(
+    id: &RackId,
+    state: &Rack,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<StateHandlerOutcome<RackState>, StateHandlerError> {
+    let Some(mut sequence) = state
+        .power_sequence
+        .clone()
+        .filter(|sequence| sequence.is_active())
+    else {
+        // no power sequence was requested for the rack
+        return Ok(StateHandlerOutcome::transition(RackState::Maintenance {
+            rack_maintenance: RackMaintenanceState::Completed,
+        }));
+    };
+    if sequence.started_at.is_none() {
+        sequence.started_at = Some(chrono::Utc::now());
+    }
+
+    // The BMC addresses are read before and the sequence is written after the
+    // Redfish calls, no transaction is held across them
+    let bmc_ips = {
+        let mut conn = ctx.services.db_pool.acquire().await?;
+        find_step_bmc_ips(&mut conn, &sequence).await
+    };
+    let next_state = match advance_power_sequence(*id, &mut sequence, &bmc_ips, ctx).await {
+        Err(cause) => {
+            tracing::error!(
+                "Rack {} power sequence {} failed: {}",
+                id,
+                sequence.action,
+                cause
+            );
+            sequence.error = Some(cause.clone());
+            Some(RackState::Error { cause })
+        }
+        Ok(()) if sequence.current_step >= sequence.steps.len() => {
+            tracing::info!("Rack {} completed power sequence {}", id, sequence.action);
+            sequence.completed_at = Some(chrono::Utc::now());
+            Some(RackState::Maintenance {
+                rack_maintenance: RackMaintenanceState::Completed,
+            })
+        }
+        Ok(()) => None,
+    };
+
+    let mut txn = ctx.services.db_pool.begin().await?;
+    if state.power_sequence.as_ref() != Some(&sequence) {
+        db_rack::update_power_sequence(&mut txn, *id, &sequence).await?;
+    }
+
+    Ok(match next_state {
+        Some(next_state) => StateHandlerOutcome::transition(next_state),
+        None => StateHandlerOutcome::do_nothing(),
+    }
+    .with_txn(txn))
+}
+
+/// Looks up the BMC addresses of the devices of the current step of the power
+/// sequence
+async fn find_step_bmc_ips(
+    txn: &mut PgConnection,
+    sequence: &RackPowerSequence,
+) -> HashMap<MacAddress, Result<IpAddr, String>> {
+    let mut bmc_ips = HashMap::new();
+    if let Some(step) = sequence.steps.get(sequence.current_step) {
+        for bmc_mac_address in &step.bmc_mac_addresses {
+            bmc_ips.insert(
+                *bmc_mac_address,
+                rack::power::find_bmc_ip(txn, *bmc_mac_address).await,
+            );
+        }
+    }
+    bmc_ips
+}
+
+/// Creates a Redfish client for a device of the current power sequence step
+async fn connect_step_bmc(
+    bmc_ips: &HashMap<MacAddress, Result<IpAddr, String>>,
+    redfish_client_pool: &dyn RedfishClientPool,
+    bmc_mac_address: MacAddress,
+) -> Result<Box<dyn Redfish>, String> {
+    let ip = bmc_ips
+        .get(&bmc_mac_address)
+        .cloned()
+        .unwrap_or_else(|| Err(format!("BMC {bmc_mac_address} was not looked up")))?;
+    rack::power::create_bmc_client(redfish_client_pool, bmc_mac_address, ip).await
+}
+
+/// Moves the current step of the power sequence forward. Power commands are
+/// issued to all devices of the step at once. Once all of them report the
+/// target power state, the sequence proceeds with the next step. After
+/// powering on devices, the next step is delayed by the inrush delay.
+///
+/// Power shelves are checked for faults before and while they are powered on.
+/// Returns the reason if the sequence needs to be aborted.
+async fn advance_power_sequence(
+    id: RackId,
+    sequence: &mut RackPowerSequence,
+    bmc_ips: &HashMap<MacAddress, Result<IpAddr, String>>,
+    ctx: &mut StateHandlerContext<'_, RackStateHandlerContextObjects>,
+) -> Result<(), String> {
+    let Some(step) = sequence.steps.get(sequence.current_step).cloned() else {
+        return Ok(());
+    };
+    let config = &ctx.services.site_config.rack_state_controller;
+    let redfish_client_pool = ctx.services.redfish_client_pool.as_ref();
+    let check_shelf_faults =
+        step.device_type == RackDeviceType::PowerShelf && step.target == RackPowerTarget::On;
+    let now = chrono::Utc::now();
+
+    let Some(step_started_at) = sequence.step_started_at else {
+        let mut clients = Vec::with_capacity(step.bmc_mac_addresses.len());
+        for bmc_mac_address in &step.bmc_mac_addresses {
+            let redfish = connect_step_bmc(bmc_ips, redfish_client_pool, *bmc_mac_address).await?;
+            clients.push((*bmc_mac_address, redfish));
+        }
+        if check_shelf_faults {
+            for (bmc_mac_address, redfish) in &clients {
+                if let Some(fault) = rack::power::power_shelf_fault(redfish.as_ref())
+                    .await
+                    .map_err(|e| format!("Power shelf {bmc_mac_address}: {e}"))?
+                {
+                    return Err(format!(
+                        "Power shelf {bmc_mac_address} reports a fault: {fault}"
+                    ));
+                }
+            }
+        }
+        for (bmc_mac_address, redfish) in &clients {
+            if rack::power::has_power_state(redfish.as_ref(), step.target).await {
+                continue;
+            }
+            rack::power::set_device_power(redfish.as_ref(), step.device_type, step.target)
+                .await
+                .map_err(|e| format!("{} {bmc_mac_address}: {e}", step.device_type))?;
+        }
+        tracing::info!(
+            "Rack {} powers {} {} devices of step {}/{}",
+            id,
+            step.target,
+            step.device_type,
+            sequence.current_step + 1,
+            sequence.steps.len()
+        );
+        sequence.step_started_at = Some(now);
+        return Ok(());
+    };
+
+    if sequence.step_verified_at.is_none() {
+        let mut pending = Vec::new();
+        for bmc_mac_address in &step.bmc_mac_addresses {
+            // BMCs which are temporarily unreachable are checked again later
+            let redfish =
+                match connect_step_bmc(bmc_ips, redfish_client_pool, *bmc_mac_address).await {
+                    Ok(redfish) => redfish,
+                    Err(message) => {
+                        tracing::debug!("Rack {} power sequence: {}", id, message);
+                        pending.push(bmc_mac_address.to_string());
+                        continue;
+                    }
+                };
+            if check_shelf_faults
+                && let Ok(Some(fault)) = rack::power::power_shelf_fault(redfish.as_ref()).await
+            {
+                return Err(format!(
+                    "Power shelf {bmc_mac_address} reports a fault: {fault}"
+                ));
+            }
+            if !rack::power::has_power_state(redfish.as_ref(), step.target).await {
+                pending.push(bmc_mac_address.to_string());
+            }
+        }
+
+        if !pending.is_empty() {
+            let timeout = config.power_sequence_step_timeout;
+            if now - step_started_at > timeout {
+                return Err(format!(
+                    "{} {} did not power {} within {} seconds",
+                    step.device_type,
+                    pending.join(", "),
+                    step.target,
+                    timeout.num_seconds()
+                ));
+            }
+            return Ok(());
+        }
+        sequence.step_verified_at = Some(now);
+    }
+
+    let is_last_step = sequence.current_step + 1 >= sequence.steps.len();
+    if step.target == RackPowerTarget::On
+        && !is_last_step
+        && sequence
+            .step_verified_at
+            .is_some_and(|verified_at| now - verified_at < config.power_sequence_inrush_delay)
+    {
+        return Ok(());
+    }
+    sequence.current_step += 1;
+    sequence.step_started_at = None;
+    sequence.step_verified_at = None;
+    Ok(())
+}
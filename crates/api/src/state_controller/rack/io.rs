@@ -29,11 +29,17 @@ use sqlx::PgConnection;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::metrics::NoopMetricsEmitter;
 use crate::state_controller::rack::context::RackStateHandlerContextObjects;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 /// State Controller IO implementation for Racks
 #[derive(Default, Debug)]
 pub struct RackStateControllerIO {}
 
+impl PublishableState for RackState {
+    const OBJECT_TYPE: &'static str = "rack";
+    const STATE_FIELD: &'static str = "rack_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for RackStateControllerIO {
     type ObjectId = RackId;
@@ -34,6 +34,7 @@ use model::attestation::spdm::{
 use model::bmc_info::BmcInfo;
 use nras::{DeviceAttestationInfo, EvidenceCertificate, RawAttestationOutcome, VerifierClient};
 
+use crate::attestation::policy::{self, AttestationKind};
 use crate::state_controller::spdm::context::SpdmStateHandlerContextObjects;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
@@ -372,6 +373,22 @@ impl StateHandler for SpdmAttestationStateHandler {
                             &SpdmAttestationStatus::Completed,
                         )
                         .await?;
+
+                        let failures = policy::spdm_failures(&next_state.devices_state);
+                        db::attestation::spdm::update_attestation_result(
+                            &mut txn,
+                            &machine_id,
+                            failures.is_empty(),
+                        )
+                        .await?;
+                        policy::apply_attestation_result(
+                            &mut txn,
+                            &ctx.services.site_config.attestation_policy,
+                            &machine_id,
+                            AttestationKind::Spdm,
+                            failures,
+                        )
+                        .await?;
                     }
 
                     // Move to next major state.
@@ -381,7 +398,19 @@ impl StateHandler for SpdmAttestationStateHandler {
                 // TODO: Set status completed
                 Ok(StateHandlerOutcome::do_nothing().with_txn(txn))
             }
-            AttestationState::Completed => Ok(StateHandlerOutcome::do_nothing()),
+            AttestationState::Completed => {
+                // Re-attestation is requested once the result is older than the policy allows,
+                // which restarts attestation in the next iteration.
+                let mut txn = ctx.services.db_pool.begin().await?;
+                policy::request_spdm_reattestation_if_due(
+                    &mut txn,
+                    &ctx.services.site_config.attestation_policy,
+                    &machine_id,
+                    None,
+                )
+                .await?;
+                Ok(StateHandlerOutcome::do_nothing().with_txn(txn))
+            }
         }
     }
 }
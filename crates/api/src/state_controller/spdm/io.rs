@@ -32,10 +32,20 @@ use sqlx::PgConnection;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::spdm::context::SpdmStateHandlerContextObjects;
 use crate::state_controller::spdm::metrics::SpdmMetricsEmitter;
+use crate::state_controller::state_change_emitter::PublishableState;
 
 /// State Controller IO implementation for dpa interfaces
 #[derive(Default, Debug)]
-pub struct SpdmStateControllerIO {}
+pub struct SpdmStateControllerIO {
+    /// Machines whose last attestation is older than this are listed again, so
+    /// that the state handler can re-attest them. Zero disables re-attestation.
+    pub reattestation_interval: chrono::Duration,
+}
+
+impl PublishableState for SpdmMachineStateSnapshot {
+    const OBJECT_TYPE: &'static str = "spdm_attestation";
+    const STATE_FIELD: &'static str = "attestation_state";
+}
 
 #[async_trait::async_trait]
 impl StateControllerIO for SpdmStateControllerIO {
@@ -54,7 +64,9 @@ impl StateControllerIO for SpdmStateControllerIO {
         &self,
         txn: &mut PgConnection,
     ) -> Result<Vec<Self::ObjectId>, DatabaseError> {
-        db::attestation::spdm::find_machine_ids_for_attestation(txn).await
+        let reattest_before = (self.reattestation_interval > chrono::Duration::zero())
+            .then(|| chrono::Utc::now() - self.reattestation_interval);
+        db::attestation::spdm::find_machine_ids_for_attestation(txn, reattest_before).await
     }
 
     /// Loads a state snapshot from the database
@@ -18,6 +18,7 @@
 //! Generic state change emitter for broadcasting state transitions to registered hooks.
 
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 /// Event emitted when a state transition occurs.
 ///
@@ -28,7 +29,6 @@ pub struct StateChangeEvent<'a, Id, S> {
     /// The ID of the object that changed state.
     pub object_id: &'a Id,
     /// The state before the transition (if known).
-    #[cfg(test)] // not used by any hooks yet, only used in tests
     pub previous_state: Option<&'a S>,
     /// The new state after the transition.
     pub new_state: &'a S,
@@ -36,6 +36,16 @@ pub struct StateChangeEvent<'a, Id, S> {
     pub timestamp: DateTime<Utc>,
 }
 
+/// A controller state whose changes can be published to consumers outside of
+/// carbide, e.g. via MQTT or webhooks.
+pub trait PublishableState: Serialize + Clone + Send + Sync + 'static {
+    /// The type of object that carries the state, e.g. `machine`.
+    /// Used in MQTT topics and to identify the object in published messages.
+    const OBJECT_TYPE: &'static str;
+    /// Name of the field which carries the state in MQTT messages
+    const STATE_FIELD: &'static str;
+}
+
 /// Trait for hooks that handle state changes.
 ///
 /// Hooks are called synchronously for each event. If a hook needs to perform
@@ -14,18 +14,252 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use carbide_uuid::switch::SwitchId;
-use db::switch as db_switch;
-use model::switch::{Switch, SwitchControllerState};
+use db::db_read::DbReader;
+use db::{
+    expected_switch as db_expected_switch, machine_interface as db_machine_interface,
+    switch as db_switch,
+};
+use libnmxm::nmxm_model::SwitchHealth;
+use libredfish::{PowerState, Redfish, SystemPowerControl};
+use model::switch::{Switch, SwitchControllerState, SwitchStatus};
+use sqlx::PgTransaction;
 
+use crate::nvlink::NmxmClientPool;
+use crate::state_controller::bmc_device::{self, BmcDeviceKind, BmcInventory, DeviceBmc};
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
 use crate::state_controller::switch::context::SwitchStateHandlerContextObjects;
 
 /// The actual Switch State handler
-#[derive(Debug, Default, Clone)]
-pub struct SwitchStateHandler {}
+#[derive(Default, Clone)]
+pub struct SwitchStateHandler {
+    /// Used to query NVLink switch health for switches with NMX-C enabled.
+    /// If not set, NMX-M health is not taken into account.
+    nmxm_client_pool: Option<Arc<dyn NmxmClientPool>>,
+}
+
+impl std::fmt::Debug for SwitchStateHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwitchStateHandler")
+            .field("nmxm_client_pool", &self.nmxm_client_pool.is_some())
+            .finish()
+    }
+}
+
+impl SwitchStateHandler {
+    pub fn new(nmxm_client_pool: Option<Arc<dyn NmxmClientPool>>) -> Self {
+        Self { nmxm_client_pool }
+    }
+}
+
+/// Inventory and health data read from a switch
+#[derive(Debug, Clone)]
+struct SwitchInventory {
+    power_state: PowerState,
+    serial_numbers: Vec<String>,
+    firmware_versions: BTreeMap<String, String>,
+    nmxm_health: Option<SwitchHealth>,
+}
+
+impl SwitchInventory {
+    fn health_status(&self) -> &'static str {
+        match (self.power_state, self.nmxm_health) {
+            (_, Some(SwitchHealth::SwitchHealthUnhealthy)) => SwitchStatus::HEALTH_CRITICAL,
+            (_, Some(SwitchHealth::SwitchHealthMissingNvlink)) => SwitchStatus::HEALTH_WARNING,
+            (PowerState::On, _) => SwitchStatus::HEALTH_OK,
+            _ => SwitchStatus::HEALTH_WARNING,
+        }
+    }
+
+    fn to_status(&self, switch_name: &str) -> SwitchStatus {
+        SwitchStatus {
+            switch_name: switch_name.to_string(),
+            power_state: self.power_state.to_string().to_lowercase(),
+            health_status: self.health_status().to_string(),
+            serial_numbers: self.serial_numbers.clone(),
+            firmware_versions: self.firmware_versions.clone(),
+            nmxm_health: self
+                .nmxm_health
+                .map(|health| nmxm_health_str(health).to_string()),
+            last_health_check: Some(chrono::Utc::now()),
+        }
+    }
+}
+
+fn nmxm_health_str(health: SwitchHealth) -> &'static str {
+    match health {
+        SwitchHealth::SwitchHealthUnknown => "unknown",
+        SwitchHealth::SwitchHealthHealthy => "healthy",
+        SwitchHealth::SwitchHealthMissingNvlink => "missing_nvlink",
+        SwitchHealth::SwitchHealthUnhealthy => "unhealthy",
+    }
+}
+
+/// Validates the switch against its `ExpectedSwitch` entry and locates the BMC.
+///
+/// Returns `Err(cause)` with a human readable cause if the switch can not be
+/// matched to what was expected.
+async fn validate_switch<DB>(
+    db: &mut DB,
+    switch_id: &SwitchId,
+    state: &Switch,
+) -> Result<Result<DeviceBmc, String>, StateHandlerError>
+where
+    for<'db> &'db mut DB: DbReader<'db>,
+{
+    let serial_number = state.config.name.as_str();
+    let Some(expected_switch) =
+        db_expected_switch::find_by_serial_number(&mut *db, serial_number).await?
+    else {
+        return Ok(Err(format!(
+            "No expected switch with serial number {serial_number} exists"
+        )));
+    };
+
+    let interfaces = db_machine_interface::find_by_switch_id(&mut *db, switch_id).await?;
+    Ok(bmc_device::locate_bmc(
+        BmcDeviceKind::Switch,
+        &interfaces,
+        expected_switch.bmc_mac_address,
+        expected_switch.serial_number,
+    ))
+}
+
+impl SwitchStateHandler {
+    /// Reads inventory, firmware versions and power state from the switch BMC,
+    /// and NVLink health from NMX-M if enabled for the switch
+    async fn fetch_inventory(
+        &self,
+        redfish: &dyn Redfish,
+        bmc: &DeviceBmc,
+        state: &Switch,
+        ctx: &StateHandlerContext<'_, SwitchStateHandlerContextObjects>,
+    ) -> Result<SwitchInventory, StateHandlerError> {
+        let BmcInventory {
+            power_state,
+            serial_numbers,
+            firmware_versions,
+        } = bmc_device::fetch_inventory(redfish).await?;
+        let nmxm_health = self.fetch_nmxm_health(bmc, state, ctx).await?;
+
+        Ok(SwitchInventory {
+            power_state,
+            serial_numbers,
+            firmware_versions,
+            nmxm_health,
+        })
+    }
+
+    async fn fetch_nmxm_health(
+        &self,
+        bmc: &DeviceBmc,
+        state: &Switch,
+        ctx: &StateHandlerContext<'_, SwitchStateHandlerContextObjects>,
+    ) -> Result<Option<SwitchHealth>, StateHandlerError> {
+        if !state.config.enable_nmxc {
+            return Ok(None);
+        }
+        let (Some(nmxm_client_pool), Some(nvlink_config)) = (
+            self.nmxm_client_pool.as_ref(),
+            ctx.services.site_config.nvlink_config.as_ref(),
+        ) else {
+            return Ok(None);
+        };
+
+        let nmxm_client = nmxm_client_pool
+            .create_client(&nvlink_config.nmx_m_endpoint, None)
+            .await
+            .map_err(|e| {
+                StateHandlerError::GenericError(eyre::eyre!("Failed to create NMX-M client: {e}"))
+            })?;
+        let switch_nodes = nmxm_client.get_switch_node(None).await.map_err(|e| {
+            StateHandlerError::GenericError(eyre::eyre!("Failed to query NMX-M switch nodes: {e}"))
+        })?;
+
+        // NMX-M identifies switch trays by name or by the serial number in their location info
+        let node = switch_nodes.into_iter().find(|node| {
+            node.name.as_deref() == Some(bmc.serial_number.as_str())
+                || node
+                    .location_info
+                    .as_ref()
+                    .and_then(|location| location.chassis_serial_number.as_deref())
+                    == Some(bmc.serial_number.as_str())
+        });
+
+        Ok(Some(
+            node.and_then(|node| node.health)
+                .unwrap_or(SwitchHealth::SwitchHealthUnknown),
+        ))
+    }
+
+    /// Probes the switch and refreshes its status.
+    ///
+    /// Returns `Err(cause)` if the switch is unreachable or does not match its
+    /// `ExpectedSwitch` entry. In that case the switch status is marked as critical.
+    /// The updated status is written by the returned transaction, which has to be
+    /// passed back with the outcome of the handler.
+    async fn refresh_status(
+        &self,
+        switch_id: &SwitchId,
+        state: &mut Switch,
+        ctx: &mut StateHandlerContext<'_, SwitchStateHandlerContextObjects>,
+    ) -> Result<(Result<SwitchInventory, String>, PgTransaction<'static>), StateHandlerError> {
+        let result = match validate_switch(&mut ctx.services.db_reader, switch_id, state).await? {
+            Ok(bmc) => self.probe_switch(&bmc, state, ctx).await,
+            Err(cause) => Err(cause),
+        };
+
+        state.status = Some(match &result {
+            Ok(inventory) => inventory.to_status(&state.config.name),
+            Err(_) => SwitchStatus {
+                switch_name: state.config.name.clone(),
+                health_status: SwitchStatus::HEALTH_CRITICAL.to_string(),
+                last_health_check: Some(chrono::Utc::now()),
+                ..state.status.clone().unwrap_or_default()
+            },
+        });
+        let mut txn = ctx.services.db_pool.begin().await?;
+        db_switch::update(state, &mut txn).await?;
+
+        Ok((result, txn))
+    }
+
+    async fn probe_switch(
+        &self,
+        bmc: &DeviceBmc,
+        state: &Switch,
+        ctx: &StateHandlerContext<'_, SwitchStateHandlerContextObjects>,
+    ) -> Result<SwitchInventory, String> {
+        let redfish = bmc_device::connect(BmcDeviceKind::Switch, bmc, ctx.services).await?;
+        let inventory = self
+            .fetch_inventory(redfish.as_ref(), bmc, state, ctx)
+            .await
+            .map_err(|e| format!("Failed to fetch data from switch BMC {}: {e}", bmc.ip))?;
+        bmc_device::verify_identity(BmcDeviceKind::Switch, bmc, &inventory.serial_numbers)?;
+        Ok(inventory)
+    }
+
+    fn health_check_due(
+        state: &Switch,
+        ctx: &StateHandlerContext<'_, SwitchStateHandlerContextObjects>,
+    ) -> bool {
+        bmc_device::health_check_due(
+            state
+                .status
+                .as_ref()
+                .and_then(|status| status.last_health_check),
+            ctx.services
+                .site_config
+                .switch_state_controller
+                .health_check_interval,
+        )
+    }
+}
 
 #[async_trait::async_trait]
 impl StateHandler for SwitchStateHandler {
@@ -41,78 +275,139 @@ impl StateHandler for SwitchStateHandler {
         controller_state: &Self::ControllerState,
         ctx: &mut StateHandlerContext<Self::ContextObjects>,
     ) -> Result<StateHandlerOutcome<SwitchControllerState>, StateHandlerError> {
+        if state.is_marked_as_deleted() && *controller_state != SwitchControllerState::Deleting {
+            return Ok(StateHandlerOutcome::transition(
+                SwitchControllerState::Deleting,
+            ));
+        }
+
         match controller_state {
             SwitchControllerState::Initializing => {
-                // TODO: Implement Switch initialization logic
-                // This would typically involve:
-                // 1. Validating the Switch configuration
-                // 2. Allocating resources
-                tracing::info!("Initializing Switch");
-                let new_state = SwitchControllerState::FetchingData;
-                Ok(StateHandlerOutcome::transition(new_state))
+                tracing::info!(%switch_id, "Initializing Switch");
+                match validate_switch(&mut ctx.services.db_reader, switch_id, state).await? {
+                    Ok(bmc) => {
+                        tracing::info!(
+                            %switch_id,
+                            bmc_ip = %bmc.ip,
+                            bmc_mac = %bmc.mac_address,
+                            "Switch matches its expected switch entry"
+                        );
+                        Ok(StateHandlerOutcome::transition(
+                            SwitchControllerState::FetchingData,
+                        ))
+                    }
+                    Err(cause) => Ok(StateHandlerOutcome::transition(
+                        SwitchControllerState::Error { cause },
+                    )),
+                }
             }
 
             SwitchControllerState::FetchingData => {
-                tracing::info!("Fetching Switch data");
-                // TODO: Implement Switch fetching data logic
-                // This would typically involve:
-                // 1. Fetching data from the Switch
-                // 2. Updating the Switch status
-                let new_state = SwitchControllerState::Configuring;
-                Ok(StateHandlerOutcome::transition(new_state))
+                tracing::info!(%switch_id, "Fetching Switch data");
+                let (result, txn) = self.refresh_status(switch_id, state, ctx).await?;
+                let next_state = match result {
+                    Ok(_) => SwitchControllerState::Configuring,
+                    Err(cause) => SwitchControllerState::Error { cause },
+                };
+                Ok(StateHandlerOutcome::transition(next_state).with_txn(txn))
             }
 
             SwitchControllerState::Configuring => {
-                tracing::info!("Configuring Switch");
-                // TODO: Implement Switch configuring logic
-                // This would typically involve:
-                // 1. Configuring the Switch
-                // 2. Updating the Switch status
-                let new_state = SwitchControllerState::Ready;
-                Ok(StateHandlerOutcome::transition(new_state))
+                tracing::info!(%switch_id, "Configuring Switch");
+                let bmc =
+                    match validate_switch(&mut ctx.services.db_reader, switch_id, state).await? {
+                        Ok(bmc) => bmc,
+                        Err(cause) => {
+                            return Ok(StateHandlerOutcome::transition(
+                                SwitchControllerState::Error { cause },
+                            ));
+                        }
+                    };
+
+                // Baseline configuration: The switch needs to be powered on to
+                // take part in the NVLink fabric
+                let redfish = bmc_device::create_redfish_client(&bmc, ctx.services).await?;
+                let power_state = redfish.get_power_state().await.map_err(|error| {
+                    StateHandlerError::RedfishError {
+                        operation: "get_power_state",
+                        error,
+                    }
+                })?;
+                if power_state != PowerState::On {
+                    tracing::info!(%switch_id, %power_state, "Powering on Switch");
+                    redfish
+                        .power(SystemPowerControl::On)
+                        .await
+                        .map_err(|error| StateHandlerError::RedfishError {
+                            operation: "power_on",
+                            error,
+                        })?;
+                    return Ok(StateHandlerOutcome::wait(format!(
+                        "Waiting for switch to power on. Current power state: {power_state}"
+                    )));
+                }
+
+                // Re-read the inventory so that Ready starts with up-to-date data
+                let (result, txn) = self.refresh_status(switch_id, state, ctx).await?;
+                let next_state = match result {
+                    Ok(_) => SwitchControllerState::Ready,
+                    Err(cause) => SwitchControllerState::Error { cause },
+                };
+                Ok(StateHandlerOutcome::transition(next_state).with_txn(txn))
             }
 
             SwitchControllerState::Deleting => {
-                tracing::info!("Deleting Switch");
-                // TODO: Implement Switch deletion logic
-                // This would typically involve:
-                // 1. Checking if the Switch is in use
-                // 2. Safely shutting down the Switch
-                // 3. Releasing allocated resources
-
-                // For now, just delete the Switch from the database
+                tracing::info!(%switch_id, "Deleting Switch");
                 let mut txn = ctx.services.db_pool.begin().await?;
                 db_switch::final_delete(*switch_id, &mut txn).await?;
                 Ok(StateHandlerOutcome::deleted().with_txn(txn))
             }
 
             SwitchControllerState::Ready => {
-                tracing::info!("Switch is ready");
-                if state.is_marked_as_deleted() {
-                    Ok(StateHandlerOutcome::transition(
-                        SwitchControllerState::Deleting,
-                    ))
-                } else {
-                    // TODO: Implement Switch monitoring logic
-                    // This would typically involve:
-                    // 1. Checking Switch health status
-                    // 2. Updating Switch status
-
-                    // For now, just do nothing
-                    Ok(StateHandlerOutcome::do_nothing())
+                if !Self::health_check_due(state, ctx) {
+                    return Ok(StateHandlerOutcome::do_nothing());
                 }
+
+                let (result, txn) = self.refresh_status(switch_id, state, ctx).await?;
+                let outcome = match result {
+                    Ok(inventory) => {
+                        let health_status = inventory.health_status();
+                        if health_status != SwitchStatus::HEALTH_OK {
+                            tracing::warn!(
+                                %switch_id,
+                                health_status,
+                                power_state = %inventory.power_state,
+                                nmxm_health = ?inventory.nmxm_health,
+                                "Switch is not healthy"
+                            );
+                        }
+                        StateHandlerOutcome::do_nothing()
+                    }
+                    Err(cause) => {
+                        tracing::error!(%switch_id, %cause, "Switch health check failed");
+                        StateHandlerOutcome::transition(SwitchControllerState::Error { cause })
+                    }
+                };
+                Ok(outcome.with_txn(txn))
             }
 
-            SwitchControllerState::Error { .. } => {
-                tracing::info!("Switch is in error state");
-                if state.is_marked_as_deleted() {
-                    Ok(StateHandlerOutcome::transition(
-                        SwitchControllerState::Deleting,
-                    ))
-                } else {
-                    // If Switch is in error state, keep it there for manual intervention
-                    Ok(StateHandlerOutcome::do_nothing())
+            SwitchControllerState::Error { cause } => {
+                // Periodically re-probe the switch. Once the switch is reachable
+                // and matches its expected switch entry again it gets re-initialized.
+                if !Self::health_check_due(state, ctx) {
+                    return Ok(StateHandlerOutcome::do_nothing());
                 }
+
+                let (result, txn) = self.refresh_status(switch_id, state, ctx).await?;
+                let outcome = bmc_device::reprobe_outcome(
+                    switch_id,
+                    BmcDeviceKind::Switch,
+                    cause,
+                    result.map(|_| ()),
+                    SwitchControllerState::Initializing,
+                    |cause| SwitchControllerState::Error { cause },
+                );
+                Ok(outcome.with_txn(txn))
             }
         }
     }
@@ -28,12 +28,18 @@ use sqlx::PgConnection;
 
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::metrics::NoopMetricsEmitter;
+use crate::state_controller::state_change_emitter::PublishableState;
 use crate::state_controller::switch::context::SwitchStateHandlerContextObjects;
 
 /// State Controller IO implementation for Switches
 #[derive(Default, Debug)]
 pub struct SwitchStateControllerIO {}
 
+impl PublishableState for SwitchControllerState {
+    const OBJECT_TYPE: &'static str = "switch";
+    const STATE_FIELD: &'static str = "switch_state";
+}
+
 #[async_trait::async_trait]
 impl StateControllerIO for SwitchStateControllerIO {
     type ObjectId = SwitchId;
@@ -34,7 +34,13 @@ pub(crate) async fn create_os_image(
     if attrs.source_url.is_empty() || attrs.digest.is_empty() {
         return Err(Status::invalid_argument("os_image url or digest is empty"));
     }
-    let image = db::os_image::create(&mut txn, &attrs)
+    // Images are only trusted once the ingestion verified them
+    let status = if api.runtime_config.os_image_ingestion.enabled {
+        OsImageStatus::Uninitialized
+    } else {
+        OsImageStatus::Ready
+    };
+    let image = db::os_image::create(&mut txn, &attrs, status)
         .await
         .map_err(|e| Status::internal(e.to_string()))?;
     txn.commit()
@@ -151,9 +157,21 @@ pub(crate) async fn update_os_image(
             "os_image update read-only attributes changed",
         ));
     }
-    let updated = db::os_image::update(&image, &mut txn, new_attrs)
+    // Changes of the filesystems, or of the credentials of an image which
+    // failed to download, need to be verified by the ingestion again
+    let restart_ingestion = api.runtime_config.os_image_ingestion.enabled
+        && (image.status == OsImageStatus::Failed
+            || new_attrs.boot_disk != image.attributes.boot_disk
+            || new_attrs.bootfs_id != image.attributes.bootfs_id
+            || new_attrs.efifs_id != image.attributes.efifs_id);
+    let mut updated = db::os_image::update(&image, &mut txn, new_attrs)
         .await
         .map_err(|e| Status::internal(e.to_string()))?;
+    if restart_ingestion {
+        updated = db::os_image::restart_ingestion(&mut txn, updated.attributes.id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+    }
 
     txn.commit()
         .await
@@ -0,0 +1,221 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use rpc::forge as rpcf;
+use rpc::forge::forge_server::Forge;
+
+use crate::auth::{AuthContext, Principal};
+use crate::tests::common;
+
+fn maintenance_request(
+    host_id: MachineId,
+    reference: &str,
+) -> tonic::Request<rpcf::MaintenanceRequest> {
+    let mut request = tonic::Request::new(rpcf::MaintenanceRequest {
+        operation: rpcf::MaintenanceOperation::Enable.into(),
+        host_id: Some(host_id),
+        reference: Some(reference.to_string()),
+    });
+    request.extensions_mut().insert(AuthContext {
+        principals: vec![
+            Principal::TrustedCertificate,
+            Principal::from_web_cookie("jdoe".to_string(), "admins".to_string()),
+        ],
+        authorization: None,
+    });
+    request
+}
+
+async fn find_entries(
+    env: &TestEnv,
+    request: rpcf::FindAuditLogEntriesRequest,
+) -> Vec<rpcf::AuditLogEntry> {
+    env.api
+        .find_audit_log_entries(tonic::Request::new(request))
+        .await
+        .unwrap()
+        .into_inner()
+        .entries
+}
+
+#[crate::sqlx_test]
+async fn test_audit_log_records_calls(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (host_id, _dpu_id) = create_managed_host(&env).await.into();
+    // Ignore the audited calls of the fixtures
+    let start_time = chrono::Utc::now();
+
+    env.api
+        .set_maintenance(maintenance_request(
+            host_id,
+            "https://jira.example.com/ABC-123",
+        ))
+        .await
+        .unwrap();
+    // The reference is too short
+    env.api
+        .set_maintenance(maintenance_request(host_id, "ABC"))
+        .await
+        .unwrap_err();
+
+    let entries = find_entries(
+        &env,
+        rpcf::FindAuditLogEntriesRequest {
+            object_id: Some(host_id.to_string()),
+            start_time: Some(start_time.into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    // The nested health override insertion does not get recorded separately
+    assert_eq!(entries.len(), 2, "Unexpected entries {entries:?}");
+
+    let failure = &entries[0];
+    assert_eq!(failure.rpc_name, "SetMaintenance");
+    assert_eq!(failure.outcome(), rpcf::AuditLogOutcome::Failure);
+    assert!(failure.error.as_ref().unwrap().contains("reference"));
+
+    let success = &entries[1];
+    assert_eq!(success.rpc_name, "SetMaintenance");
+    assert_eq!(success.outcome(), rpcf::AuditLogOutcome::Success);
+    assert_eq!(success.principal, "external-user/admins/jdoe");
+    assert_eq!(success.object_ids, vec![host_id.to_string()]);
+    assert!(success.request.contains("https://jira.example.com/ABC-123"));
+    assert!(success.error.is_none());
+    assert!(success.id < failure.id);
+
+    // Calls without authentication context are recorded as anonymous
+    env.api
+        .remove_health_report_override(tonic::Request::new(
+            rpcf::RemoveHealthReportOverrideRequest {
+                machine_id: Some(host_id),
+                source: "maintenance".to_string(),
+            },
+        ))
+        .await
+        .unwrap();
+    let entries = find_entries(
+        &env,
+        rpcf::FindAuditLogEntriesRequest {
+            principal: Some("anonymous".to_string()),
+            start_time: Some(start_time.into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].rpc_name, "RemoveHealthReportOverride");
+    assert_eq!(entries[0].outcome(), rpcf::AuditLogOutcome::Success);
+}
+
+#[crate::sqlx_test]
+async fn test_audit_log_entry_is_recorded_before_the_call(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (host_id, _dpu_id) = create_managed_host(&env).await.into();
+    let start_time = chrono::Utc::now();
+    let filter = rpcf::FindAuditLogEntriesRequest {
+        object_id: Some(host_id.to_string()),
+        start_time: Some(start_time.into()),
+        ..Default::default()
+    };
+
+    // The entry exists while the handler runs, before it changes anything
+    crate::audit_log::audited(
+        &env.api,
+        "SetMaintenance",
+        maintenance_request(host_id, "https://jira.example.com/ABC-123"),
+        |request| {
+            let env = &env;
+            let filter = filter.clone();
+            async move {
+                let entries = find_entries(env, filter).await;
+                assert_eq!(entries.len(), 1, "Unexpected entries {entries:?}");
+                assert_eq!(entries[0].outcome(), rpcf::AuditLogOutcome::Pending);
+                crate::handlers::managed_host::set_maintenance(&env.api, request).await
+            }
+        },
+    )
+    .await
+    .unwrap();
+
+    let entries = find_entries(&env, filter).await;
+    assert_eq!(entries.len(), 1, "Unexpected entries {entries:?}");
+    assert_eq!(entries[0].outcome(), rpcf::AuditLogOutcome::Success);
+}
+
+#[crate::sqlx_test]
+async fn test_find_audit_log_entries_filters(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (host_id, _dpu_id) = create_managed_host(&env).await.into();
+
+    let start_time = chrono::Utc::now();
+    for reference in [
+        "https://jira.example.com/ABC-1",
+        "https://jira.example.com/ABC-2",
+    ] {
+        env.api
+            .set_maintenance(maintenance_request(host_id, reference))
+            .await
+            .unwrap();
+    }
+
+    let entries = find_entries(
+        &env,
+        rpcf::FindAuditLogEntriesRequest {
+            principal: Some("external-user/admins/jdoe".to_string()),
+            rpc_name: Some("SetMaintenance".to_string()),
+            start_time: Some(start_time.into()),
+            limit: Some(1),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].request.contains("ABC-2"));
+
+    let entries = find_entries(
+        &env,
+        rpcf::FindAuditLogEntriesRequest {
+            principal: Some("external-user/admins/other".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(entries.is_empty());
+
+    let entries = find_entries(
+        &env,
+        rpcf::FindAuditLogEntriesRequest {
+            end_time: Some((start_time - chrono::Duration::minutes(1)).into()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(entries.is_empty());
+
+    let err = env
+        .api
+        .find_audit_log_entries(tonic::Request::new(rpcf::FindAuditLogEntriesRequest {
+            limit: Some(0),
+            ..Default::default()
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
@@ -80,11 +80,13 @@ use tracing_subscriber::EnvFilter;
 use crate::api::Api;
 use crate::api::metrics::ApiMetricsEmitter;
 use crate::cfg::file::{
-    BomValidationConfig, CarbideConfig, DpaConfig, DpaInterfaceStateControllerConfig,
-    DpuConfig as InitialDpuConfig, FirmwareGlobal, FnnConfig, IBFabricConfig, IbFabricDefinition,
-    IbPartitionStateControllerConfig, ListenMode, MachineStateControllerConfig, MachineUpdater,
-    MachineValidationConfig, MeasuredBootMetricsCollectorConfig, NetworkSecurityGroupConfig,
-    NetworkSegmentStateControllerConfig, NvLinkConfig, PowerManagerOptions,
+    AttestationPolicyConfig, BomValidationConfig, CarbideConfig, ConsoleAccessConfig, DpaConfig,
+    DpaInterfaceStateControllerConfig, DpuConfig as InitialDpuConfig, FirmwareGlobal, FnnConfig,
+    IBFabricConfig, IbFabricDefinition, IbPartitionStateControllerConfig, ListenMode,
+    MachineStateControllerConfig, MachineUpdater, MachineValidationConfig,
+    MeasuredBootMetricsCollectorConfig, NetworkSecurityGroupConfig,
+    NetworkSegmentStateControllerConfig, NvLinkConfig, OsImageDownloadConfig,
+    OsImageIngestionConfig, PowerManagerOptions, PowerShelfPowerPolicy,
     PowerShelfStateControllerConfig, RackStateControllerConfig, SiteExplorerConfig, SpdmConfig,
     SpdmStateControllerConfig, StateControllerConfig, SwitchStateControllerConfig, VmaasConfig,
     VpcPeeringPolicy, default_max_find_by_ids,
@@ -92,6 +94,8 @@ use crate::cfg::file::{
 use crate::ethernet_virtualization::{EthVirtData, SiteFabricPrefixList};
 use crate::ib::{self, IBFabricManagerImpl, IBFabricManagerType};
 use crate::ib_fabric_monitor::IbFabricMonitor;
+use crate::instance_events::hook::InstanceEventStateChangeHook;
+use crate::instance_events::notifier::InstanceEventNotifier;
 use crate::ipmitool::IPMIToolTestImpl;
 use crate::logging::level_filter::ActiveLevel;
 use crate::logging::log_limiter::LogLimiter;
@@ -117,6 +121,7 @@ use crate::state_controller::power_shelf::handler::PowerShelfStateHandler;
 use crate::state_controller::power_shelf::io::PowerShelfStateControllerIO;
 use crate::state_controller::spdm::handler::SpdmAttestationStateHandler;
 use crate::state_controller::spdm::io::SpdmStateControllerIO;
+use crate::state_controller::state_change_emitter::StateChangeEmitterBuilder;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
@@ -1079,12 +1084,19 @@ pub fn get_config() -> CarbideConfig {
         },
         power_shelf_state_controller: PowerShelfStateControllerConfig {
             controller: StateControllerConfig::default(),
+            health_check_interval: Duration::seconds(0),
+            power_policy: PowerShelfPowerPolicy::default(),
         },
         rack_state_controller: RackStateControllerConfig {
             controller: StateControllerConfig::default(),
+            firmware_upgrade_max_unavailable: 1,
+            power_sequence_compute_wave_size: 4,
+            power_sequence_inrush_delay: Duration::seconds(0),
+            power_sequence_step_timeout: Duration::minutes(5),
         },
         switch_state_controller: SwitchStateControllerConfig {
             controller: StateControllerConfig::default(),
+            health_check_interval: Duration::seconds(0),
         },
         dpu_config: InitialDpuConfig {
             dpu_nic_firmware_initial_update_enabled: true,
@@ -1110,6 +1122,9 @@ pub fn get_config() -> CarbideConfig {
             enabled: true,
             run_interval: std::time::Duration::from_secs(10),
         },
+        os_image_ingestion: OsImageIngestionConfig::default(),
+        os_image_download: OsImageDownloadConfig::default(),
+        console_access: ConsoleAccessConfig::default(),
         machine_validation_config: MachineValidationConfig {
             enabled: true,
             ..MachineValidationConfig::default()
@@ -1156,7 +1171,9 @@ pub fn get_config() -> CarbideConfig {
             enabled: true,
             nras_config: Some(nras::Config::default()),
         },
+        attestation_policy: AttestationPolicyConfig::default(),
         dsx_exchange_event_bus: None,
+        state_change_webhooks: None,
         use_onboard_nic: Arc::new(false.into()),
         dpf: crate::cfg::file::DpfConfig::default(),
         x86_pxe_boot_url_override: None,
@@ -1420,6 +1437,7 @@ pub async fn create_test_env_with_overrides(
         work_lock_manager_handle: work_lock_manager_handle.clone(),
         machine_state_handler_enqueuer: Enqueuer::new(db_pool.clone()),
         metric_emitter: ApiMetricsEmitter::new(&test_meter.meter()),
+        instance_event_notifier: InstanceEventNotifier::default(),
     });
 
     let attestation_enabled = config.attestation_enabled;
@@ -1489,6 +1507,13 @@ pub async fn create_test_env_with_overrides(
         .io(Arc::new(MachineStateControllerIO {
             host_health: config.host_health,
         }))
+        .state_change_emitter(
+            StateChangeEmitterBuilder::default()
+                .hook(Box::new(InstanceEventStateChangeHook::new(
+                    api.instance_event_notifier.clone(),
+                )))
+                .build(),
+        )
         .build_for_manual_iterations()
         .expect("Unable to build state controller");
 
@@ -1498,7 +1523,9 @@ pub async fn create_test_env_with_overrides(
         .processor_id(state_controller_id.clone())
         .services(handler_services.clone())
         .state_handler(Arc::new(spdm_swap.clone()))
-        .io(Arc::new(SpdmStateControllerIO {}))
+        .io(Arc::new(SpdmStateControllerIO {
+            reattestation_interval: config.attestation_policy.reattestation_interval,
+        }))
         .build_for_manual_iterations()
         .expect("Unable to build spdm state controller");
 
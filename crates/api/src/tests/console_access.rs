@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::instance::InstanceId;
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use forge_secrets::credentials::{
+    BmcCredentialType, CredentialKey, CredentialProvider, Credentials,
+};
+use rpc::forge::forge_server::Forge;
+
+use crate::tests::common;
+
+#[crate::sqlx_test]
+async fn test_console_access_token(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    set_site_wide_root(&env).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    for read_only in [false, true] {
+        let token = create_console_access_token(&env, tinstance.id, read_only)
+            .await
+            .unwrap();
+        assert!(token.expires_at.is_some());
+        assert_eq!(token.websocket_url, None);
+
+        let validated = validate_console_access_token(&env, &token.token)
+            .await
+            .unwrap();
+        assert_eq!(validated.instance_id, Some(tinstance.id));
+        assert_eq!(validated.machine_id, Some(mh.id));
+        assert_eq!(validated.read_only, read_only);
+    }
+
+    // A read-only token can't be turned into a read-write one
+    let token = create_console_access_token(&env, tinstance.id, true)
+        .await
+        .unwrap();
+    let err = validate_console_access_token(&env, &token.token.replacen(".ro.", ".rw.", 1))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+    // Tokens stop working once the instance is released
+    tinstance.delete().await;
+    let err = validate_console_access_token(&env, &token.token)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+    let err = create_console_access_token(&env, InstanceId::new(), false)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+}
+
+async fn set_site_wide_root(env: &TestEnv) {
+    env.test_credential_provider
+        .set_credentials(
+            &CredentialKey::BmcCredentials {
+                credential_type: BmcCredentialType::SiteWideRoot,
+            },
+            &Credentials::UsernamePassword {
+                username: "root".to_string(),
+                password: "site-wide-root".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+}
+
+async fn create_console_access_token(
+    env: &TestEnv,
+    instance_id: InstanceId,
+    read_only: bool,
+) -> Result<rpc::forge::ConsoleAccessToken, tonic::Status> {
+    env.api
+        .create_console_access_token(tonic::Request::new(
+            rpc::forge::CreateConsoleAccessTokenRequest {
+                instance_id: Some(instance_id),
+                read_only,
+            },
+        ))
+        .await
+        .map(|response| response.into_inner())
+}
+
+async fn validate_console_access_token(
+    env: &TestEnv,
+    token: &str,
+) -> Result<rpc::forge::ValidateConsoleAccessTokenResponse, tonic::Status> {
+    env.api
+        .validate_console_access_token(tonic::Request::new(
+            rpc::forge::ValidateConsoleAccessTokenRequest {
+                token: token.to_string(),
+            },
+        ))
+        .await
+        .map(|response| response.into_inner())
+}
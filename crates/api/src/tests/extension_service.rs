@@ -33,6 +33,9 @@ use crate::tests::common::api_fixtures::{TestEnv, create_managed_host, create_te
 const TEST_SERVICE_DATA: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: test\nspec:\n  containers:\n    - name: app\n      image: nginx:1.27";
 const TEST_SERVICE_DATA_VERSION_2: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: version-2\nspec:\n  containers:\n    - name: app\n      image: nginx:1.27";
 const TEST_SERVICE_DATA_VERSION_3: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: version-3\nspec:\n  containers:\n    - name: app\n      image: nginx:1.27";
+const TEST_CONTAINER_DATA: &str =
+    "image: nvcr.io/nvidia/doca/telemetry:1.0\nargs:\n  - --verbose\nenv:\n  LOG_LEVEL: info";
+const TEST_SYSTEMD_UNIT_DATA: &str = "[Unit]\nDescription=Test service\n\n[Service]\nExecStart=/usr/bin/sleep infinity\nRestart=always\n";
 
 fn create_credential() -> rpc::DpuExtensionServiceCredential {
     rpc::DpuExtensionServiceCredential {
@@ -346,6 +349,176 @@ async fn test_extension_service_create_with_credential(
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_extension_service_create_containerd_container(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool).await;
+
+    create_test_tenants(&env).await?;
+
+    let request = rpc::CreateDpuExtensionServiceRequest {
+        service_id: None,
+        service_name: "test-container".to_string(),
+        description: Some("Test service".to_string()),
+        tenant_organization_id: "best_org".to_string(),
+        service_type: rpc::DpuExtensionServiceType::ContainerdContainer.into(),
+        data: TEST_CONTAINER_DATA.to_string(),
+        credential: Some(create_credential()),
+        observability: Some(create_observability()),
+    };
+
+    let extension_service = env
+        .api
+        .create_dpu_extension_service(Request::new(request.clone()))
+        .await?
+        .into_inner();
+    assert_eq!(
+        extension_service.service_type,
+        i32::from(rpc::DpuExtensionServiceType::ContainerdContainer)
+    );
+    assert!(
+        extension_service
+            .latest_version_info
+            .as_ref()
+            .unwrap()
+            .has_credential
+    );
+
+    let Credentials::UsernamePassword { username, password } =
+        get_credentials_for_extension_service(&env, &extension_service).await?;
+    assert_eq!(
+        username,
+        "url: https://registry.test.com, username: test-username"
+    );
+    assert_eq!(password, "test-password");
+
+    // A container spec without an image is rejected
+    let create_resp = env
+        .api
+        .create_dpu_extension_service(Request::new(rpc::CreateDpuExtensionServiceRequest {
+            service_name: "test-container-2".to_string(),
+            data: "args:\n  - --verbose".to_string(),
+            credential: None,
+            ..request.clone()
+        }))
+        .await;
+    assert_eq!(
+        create_resp.unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+
+    // A pod manifest is not a valid container spec
+    let create_resp = env
+        .api
+        .create_dpu_extension_service(Request::new(rpc::CreateDpuExtensionServiceRequest {
+            service_name: "test-container-2".to_string(),
+            data: TEST_SERVICE_DATA.to_string(),
+            credential: None,
+            ..request
+        }))
+        .await;
+    assert_eq!(
+        create_resp.unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_extension_service_create_systemd_unit(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool).await;
+
+    create_test_tenants(&env).await?;
+
+    let request = rpc::CreateDpuExtensionServiceRequest {
+        service_id: None,
+        service_name: "test-unit".to_string(),
+        description: Some("Test service".to_string()),
+        tenant_organization_id: "best_org".to_string(),
+        service_type: rpc::DpuExtensionServiceType::SystemdUnit.into(),
+        data: TEST_SYSTEMD_UNIT_DATA.to_string(),
+        credential: None,
+        observability: Some(create_observability()),
+    };
+
+    let extension_service = env
+        .api
+        .create_dpu_extension_service(Request::new(request.clone()))
+        .await?
+        .into_inner();
+    assert_eq!(
+        extension_service.service_type,
+        i32::from(rpc::DpuExtensionServiceType::SystemdUnit)
+    );
+    assert!(
+        !extension_service
+            .latest_version_info
+            .as_ref()
+            .unwrap()
+            .has_credential
+    );
+
+    // Unit files are run from the local filesystem, registry credentials make no sense
+    let create_resp = env
+        .api
+        .create_dpu_extension_service(Request::new(rpc::CreateDpuExtensionServiceRequest {
+            service_name: "test-unit-2".to_string(),
+            credential: Some(create_credential()),
+            ..request.clone()
+        }))
+        .await;
+    assert_eq!(
+        create_resp.unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+
+    // A unit without ExecStart is rejected
+    let create_resp = env
+        .api
+        .create_dpu_extension_service(Request::new(rpc::CreateDpuExtensionServiceRequest {
+            service_name: "test-unit-2".to_string(),
+            data: "[Unit]\nDescription=Test\n\n[Service]\nType=oneshot\n".to_string(),
+            ..request.clone()
+        }))
+        .await;
+    assert_eq!(
+        create_resp.unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+
+    // Updating the unit file creates a new version, the same content does not
+    let update = rpc::UpdateDpuExtensionServiceRequest {
+        service_id: extension_service.service_id.clone(),
+        service_name: None,
+        description: None,
+        data: TEST_SYSTEMD_UNIT_DATA.replace("sleep infinity", "sleep 3600"),
+        credential: None,
+        observability: None,
+        if_version_ctr_match: None,
+    };
+    let updated = env
+        .api
+        .update_dpu_extension_service(Request::new(update.clone()))
+        .await?
+        .into_inner();
+    assert_eq!(updated.active_versions.len(), 2);
+
+    let update_resp = env
+        .api
+        .update_dpu_extension_service(Request::new(update))
+        .await;
+    assert_eq!(
+        update_resp.unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+
+    Ok(())
+}
+
 #[crate::sqlx_test]
 async fn test_extension_service_create_failure(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
     let env = create_test_env(db_pool).await;
@@ -0,0 +1,137 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use common::api_fixtures::{TestEnv, create_test_env};
+use rpc::forge as rpcf;
+use rpc::forge::forge_server::Forge;
+
+use crate::tests::common;
+
+async fn renew(env: &TestEnv, member_id: &str) -> Vec<String> {
+    env.api
+        .renew_health_shard_lease(tonic::Request::new(rpcf::HealthShardLeaseRequest {
+            member_id: member_id.to_string(),
+            lease_duration: Some(Duration::from_secs(30).into()),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .members
+        .into_iter()
+        .map(|member| member.member_id)
+        .collect()
+}
+
+#[crate::sqlx_test]
+async fn test_health_shard_membership(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    assert_eq!(
+        renew(&env, "carbide-hw-health-1").await,
+        ["carbide-hw-health-1"]
+    );
+    assert_eq!(
+        renew(&env, "carbide-hw-health-0").await,
+        ["carbide-hw-health-0", "carbide-hw-health-1"]
+    );
+    // Renewing doesn't create duplicate members
+    assert_eq!(
+        renew(&env, "carbide-hw-health-1").await,
+        ["carbide-hw-health-0", "carbide-hw-health-1"]
+    );
+
+    env.api
+        .release_health_shard_lease(tonic::Request::new(rpcf::HealthShardLeaseRelease {
+            member_id: "carbide-hw-health-1".to_string(),
+        }))
+        .await
+        .unwrap();
+    assert_eq!(
+        renew(&env, "carbide-hw-health-0").await,
+        ["carbide-hw-health-0"]
+    );
+
+    let err = env
+        .api
+        .renew_health_shard_lease(tonic::Request::new(rpcf::HealthShardLeaseRequest {
+            member_id: "carbide-hw-health-0".to_string(),
+            lease_duration: Some(Duration::from_secs(3600).into()),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
+
+#[crate::sqlx_test]
+async fn test_health_collector_state_handover(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let request = rpcf::HealthCollectorStateRequest {
+        endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+        collector: "logs_collector".to_string(),
+    };
+
+    let state = env
+        .api
+        .get_health_collector_state(tonic::Request::new(request.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(state.state.is_none());
+
+    for cursor in [1, 7] {
+        env.api
+            .save_health_collector_state(tonic::Request::new(rpcf::HealthCollectorState {
+                endpoint_key: request.endpoint_key.clone(),
+                collector: request.collector.clone(),
+                state: format!(
+                    r#"{{"last_seen_ids":{{"/redfish/v1/Managers/1/LogServices/SEL":{cursor}}}}}"#
+                ),
+                updated_at: None,
+            }))
+            .await
+            .unwrap();
+    }
+
+    let state = env
+        .api
+        .get_health_collector_state(tonic::Request::new(request.clone()))
+        .await
+        .unwrap()
+        .into_inner()
+        .state
+        .unwrap();
+    assert_eq!(state.endpoint_key, request.endpoint_key);
+    assert!(state.updated_at.is_some());
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&state.state).unwrap(),
+        serde_json::json!({"last_seen_ids": {"/redfish/v1/Managers/1/LogServices/SEL": 7}})
+    );
+
+    let err = env
+        .api
+        .save_health_collector_state(tonic::Request::new(rpcf::HealthCollectorState {
+            endpoint_key: request.endpoint_key.clone(),
+            collector: request.collector.clone(),
+            state: "not json".to_string(),
+            updated_at: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
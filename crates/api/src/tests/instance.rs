@@ -5903,6 +5903,84 @@ async fn test_allocate_instance_with_invalid_os_image(
     Ok(())
 }
 
+/// Allocate instance with an OS image which was not ingested yet.
+/// Expect: FailedPrecondition error indicating image is not ready.
+#[crate::sqlx_test]
+async fn test_allocate_instance_with_os_image_not_ready(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let os_image = env
+        .api
+        .create_os_image(tonic::Request::new(rpc::forge::OsImageAttributes {
+            id: Some(rpc::Uuid::from(uuid::Uuid::new_v4())),
+            source_url: "https://example.com/image.qcow2".to_string(),
+            digest: "ab".repeat(32),
+            tenant_organization_id: default_tenant_config().tenant_organization_id,
+            create_volume: false,
+            name: None,
+            description: None,
+            auth_type: None,
+            auth_token: None,
+            rootfs_id: None,
+            rootfs_label: None,
+            boot_disk: None,
+            capacity: None,
+            bootfs_id: None,
+            efifs_id: None,
+        }))
+        .await?
+        .into_inner();
+
+    let os_config = rpc::forge::OperatingSystem {
+        phone_home_enabled: false,
+        run_provisioning_instructions_on_every_boot: false,
+        user_data: None,
+        variant: Some(rpc::forge::operating_system::Variant::OsImageId(
+            os_image.attributes.unwrap().id.unwrap(),
+        )),
+    };
+
+    let err = env
+        .api
+        .allocate_instance(tonic::Request::new(rpc::forge::InstanceAllocationRequest {
+            machine_id: mh.id.into(),
+            config: Some(rpc::InstanceConfig {
+                network_security_group_id: None,
+                tenant: Some(default_tenant_config()),
+                os: Some(os_config),
+                network: Some(single_interface_network_config(segment_id)),
+                infiniband: None,
+                nvlink: None,
+                dpu_extension_services: None,
+            }),
+            instance_id: None,
+            instance_type_id: None,
+            metadata: Some(rpc::forge::Metadata {
+                name: "test-os-image-not-ready".to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            allow_unhealthy_machine: false,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    assert!(
+        err.message().contains("is not ready"),
+        "Expected error about OS image not being ready, got: {}",
+        err.message()
+    );
+
+    Ok(())
+}
+
 /// Allocate instance with non-existent IB partition ID.
 /// Expect: InvalidArgument error indicating partition is not created.
 #[crate::sqlx_test]
@@ -0,0 +1,274 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use ::rpc::forge::forge_server::Forge;
+use ::rpc::forge::instance_event::Event;
+use ::rpc::forge::instance_event_stream_request::Scope;
+use carbide_uuid::instance::InstanceId;
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use futures::StreamExt;
+use model::instance::event::InstanceEventResumeToken;
+use rpc::forge::{InstanceEvent, InstanceEventStreamRequest, TenantState};
+
+use crate::api::InstanceEventStreamType;
+use crate::tests::common;
+use crate::tests::common::api_fixtures::instance::default_tenant_config;
+
+/// How long to wait for an expected event. Streams pick up events of other
+/// transactions asynchronously.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+async fn stream_events(
+    env: &TestEnv,
+    scope: Scope,
+    resume_token: Option<String>,
+) -> InstanceEventStreamType {
+    env.api
+        .stream_instance_events(tonic::Request::new(InstanceEventStreamRequest {
+            scope: Some(scope),
+            resume_token,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+/// Reads events from the stream until an event matches the predicate
+async fn expect_event(
+    stream: &mut InstanceEventStreamType,
+    predicate: impl Fn(&Event) -> bool,
+) -> InstanceEvent {
+    tokio::time::timeout(EVENT_TIMEOUT, async {
+        loop {
+            let event = stream
+                .next()
+                .await
+                .expect("stream ended unexpectedly")
+                .unwrap();
+            if event.event.as_ref().is_some_and(&predicate) {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("Timed out waiting for instance event")
+}
+
+fn is_tenant_state(event: &Event, tenant_state: TenantState) -> bool {
+    matches!(event, Event::StateTransition(transition) if transition.tenant_state() == tenant_state)
+}
+
+async fn invoke_instance_power(env: &TestEnv, instance_id: InstanceId) {
+    env.api
+        .invoke_instance_power(tonic::Request::new(rpc::forge::InstancePowerRequest {
+            instance_id: Some(instance_id),
+            machine_id: None,
+            operation: rpc::forge::instance_power_request::Operation::PowerReset as _,
+            boot_with_custom_ipxe: false,
+            apply_updates_on_reboot: false,
+        }))
+        .await
+        .unwrap();
+}
+
+async fn phone_home(env: &TestEnv, instance_id: InstanceId) {
+    env.api
+        .update_instance_phone_home_last_contact(tonic::Request::new(
+            rpc::forge::InstancePhoneHomeLastContactRequest {
+                instance_id: Some(instance_id),
+            },
+        ))
+        .await
+        .unwrap();
+}
+
+#[crate::sqlx_test]
+async fn test_instance_lifecycle_events(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    // Replay all events of the instance from the start
+    let mut stream = stream_events(
+        &env,
+        Scope::InstanceId(tinstance.id),
+        Some(InstanceEventResumeToken::START.to_string()),
+    )
+    .await;
+
+    let event = expect_event(&mut stream, |e| matches!(e, Event::NetworkConfigApplied(_))).await;
+    assert_eq!(event.instance_id, Some(tinstance.id));
+    assert_eq!(
+        event.tenant_organization_id,
+        default_tenant_config().tenant_organization_id
+    );
+    let Some(Event::NetworkConfigApplied(applied)) = event.event else {
+        unreachable!()
+    };
+    assert_eq!(
+        applied.network_config_version,
+        tinstance
+            .rpc_instance()
+            .await
+            .inner()
+            .network_config_version
+    );
+    expect_event(&mut stream, |e| is_tenant_state(e, TenantState::Ready)).await;
+
+    invoke_instance_power(&env, tinstance.id).await;
+    let power_event = expect_event(&mut stream, |e| matches!(e, Event::PowerAction(_))).await;
+    let Some(Event::PowerAction(power_action)) = &power_event.event else {
+        unreachable!()
+    };
+    assert_eq!(
+        power_action.operation(),
+        rpc::forge::instance_power_request::Operation::PowerReset
+    );
+
+    phone_home(&env, tinstance.id).await;
+    expect_event(&mut stream, |e| matches!(e, Event::PhoneHome(_))).await;
+
+    let issue = rpc::forge::Issue {
+        category: rpc::forge::IssueCategory::Hardware as i32,
+        summary: "GPU missing".to_string(),
+        details: "nvidia-smi reports 7 GPUs".to_string(),
+    };
+    env.api
+        .release_instance(tonic::Request::new(rpc::InstanceReleaseRequest {
+            id: Some(tinstance.id),
+            issue: Some(issue.clone()),
+            is_repair_tenant: None,
+        }))
+        .await
+        .unwrap();
+    let event = expect_event(&mut stream, |e| matches!(e, Event::Release(_))).await;
+    let Some(Event::Release(release)) = event.event else {
+        unreachable!()
+    };
+    assert_eq!(release.issue, Some(issue));
+
+    // Drive the host through termination. The instance is already released,
+    // therefore this does not record another release event.
+    tinstance.delete().await;
+    expect_event(&mut stream, |e| is_tenant_state(e, TenantState::Terminated)).await;
+
+    // A client which resumes after the power action receives the missed events
+    let mut resumed_stream = stream_events(
+        &env,
+        Scope::InstanceId(tinstance.id),
+        Some(power_event.resume_token.clone()),
+    )
+    .await;
+    let event = expect_event(&mut resumed_stream, |e| {
+        !matches!(e, Event::StateTransition(_))
+    })
+    .await;
+    assert!(
+        matches!(event.event, Some(Event::PhoneHome(_))),
+        "Unexpected event {event:?}"
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_tenant_event_stream_without_resume_token(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    // Without resume token, only events which are recorded after the stream
+    // got established are delivered
+    let mut stream = stream_events(
+        &env,
+        Scope::TenantOrganizationId(default_tenant_config().tenant_organization_id),
+        None,
+    )
+    .await;
+    phone_home(&env, tinstance.id).await;
+
+    // State transitions of the provisioning process might still be delivered
+    let event = expect_event(&mut stream, |e| !matches!(e, Event::StateTransition(_))).await;
+    assert!(
+        matches!(event.event, Some(Event::PhoneHome(_))),
+        "Unexpected event {event:?}"
+    );
+    assert_eq!(event.instance_id, Some(tinstance.id));
+
+    // Events of other tenants are not delivered
+    let mut other_stream = stream_events(
+        &env,
+        Scope::TenantOrganizationId("OtherTenant".to_string()),
+        Some(InstanceEventResumeToken::START.to_string()),
+    )
+    .await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), other_stream.next())
+            .await
+            .is_err()
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_stream_instance_events_invalid_requests(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    let err = env
+        .api
+        .stream_instance_events(tonic::Request::new(InstanceEventStreamRequest {
+            scope: None,
+            resume_token: None,
+        }))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let err = env
+        .api
+        .stream_instance_events(tonic::Request::new(InstanceEventStreamRequest {
+            scope: Some(Scope::TenantOrganizationId("not a tenant!".to_string())),
+            resume_token: None,
+        }))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let err = env
+        .api
+        .stream_instance_events(tonic::Request::new(InstanceEventStreamRequest {
+            scope: Some(Scope::InstanceId(InstanceId::new())),
+            resume_token: Some("not-a-token".to_string()),
+        }))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
@@ -15,10 +15,20 @@
  * limitations under the License.
  */
 
+use std::net::IpAddr;
+
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::network::NetworkSegmentId;
-use common::api_fixtures::{TestEnv, TestManagedHost, create_test_env};
+use common::api_fixtures::{
+    TestEnv, TestEnvOverrides, TestManagedHost, create_test_env, create_test_env_with_overrides,
+    get_config,
+};
+use forge_secrets::credentials::{
+    BmcCredentialType, CredentialKey, CredentialProvider, Credentials,
+};
+use futures_util::StreamExt;
 use rpc::forge::forge_server::Forge;
+use temp_dir::TempDir;
 
 use crate::tests::common;
 use crate::tests::common::api_fixtures::create_managed_host;
@@ -159,6 +169,154 @@ async fn test_instance_always_boot_with_custom_ipxe(pool: sqlx::PgPool) {
     assert_eq!(pxe.pxe_script, "SomeRandomiPxe");
 }
 
+#[crate::sqlx_test]
+async fn test_instance_os_image_download_url(pool: sqlx::PgPool) {
+    let cache_dir = TempDir::with_prefix("test_instance_os_image_download_url").unwrap();
+    let mut config = get_config();
+    config.os_image_ingestion.enabled = false;
+    config.os_image_ingestion.cache_dir = cache_dir.path().to_path_buf();
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+    env.test_credential_provider
+        .set_credentials(
+            &CredentialKey::BmcCredentials {
+                credential_type: BmcCredentialType::SiteWideRoot,
+            },
+            &Credentials::UsernamePassword {
+                username: "root".to_string(),
+                password: "site-wide-root".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let os_image_id = uuid::Uuid::new_v4();
+    env.api
+        .create_os_image(tonic::Request::new(rpc::forge::OsImageAttributes {
+            id: Some(os_image_id.into()),
+            source_url: "https://registry.example.com/image.qcow2".to_string(),
+            digest: "ab".repeat(32),
+            tenant_organization_id: default_tenant_config().tenant_organization_id,
+            create_volume: false,
+            name: None,
+            description: None,
+            auth_type: Some("Token".to_string()),
+            auth_token: Some("registry-secret".to_string()),
+            rootfs_id: None,
+            rootfs_label: Some("cloudimg-rootfs".to_string()),
+            boot_disk: None,
+            capacity: None,
+            bootfs_id: None,
+            efifs_id: None,
+        }))
+        .await
+        .unwrap();
+
+    let mut os = default_os_config();
+    os.variant = Some(rpc::forge::operating_system::Variant::OsImageId(
+        os_image_id.into(),
+    ));
+    let config = rpc::InstanceConfig {
+        tenant: Some(default_tenant_config()),
+        os: Some(os),
+        network: Some(single_interface_network_config(segment_id)),
+        infiniband: None,
+        network_security_group_id: None,
+        dpu_extension_services: None,
+        nvlink: None,
+    };
+    mh.instance_builer(&env).config(config).build().await;
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let host_interface = mh.host().first_interface(&mut txn).await;
+    let interfaces = db::machine_interface::find_by_machine_ids(&mut txn, &[mh.id, mh.dpu_ids[0]])
+        .await
+        .unwrap();
+    let host_ip = interfaces[&mh.id][0].addresses[0];
+    let dpu_ip = interfaces[&mh.dpu_ids[0]][0].addresses[0];
+    txn.rollback().await.unwrap();
+
+    // The imaging command line only references the image through carbide-pxe
+    let pxe = host_interface
+        .get_pxe_instructions(rpc::forge::MachineArchitecture::X86)
+        .await;
+    assert!(
+        !pxe.pxe_script.contains("registry-secret")
+            && !pxe.pxe_script.contains("registry.example.com"),
+        "Actual script: {}",
+        pxe.pxe_script
+    );
+    let image_url = pxe
+        .pxe_script
+        .split_whitespace()
+        .find_map(|arg| arg.strip_prefix("image_url=${image-url}"))
+        .unwrap_or_else(|| panic!("Actual script: {}", pxe.pxe_script));
+    let (token, file_name) = image_url.split_once('/').unwrap();
+    assert_eq!(file_name, format!("{os_image_id}.qcow2"));
+
+    // The image is streamed from the ingested image store
+    let image: Vec<u8> = (0..=255).cycle().take(1 << 20).collect();
+    std::fs::write(
+        cache_dir.path().join(format!("{os_image_id}.qcow2")),
+        &image,
+    )
+    .unwrap();
+    let (total_size, content) = get_os_image_content(&env, token, host_ip, 0).await.unwrap();
+    assert_eq!(total_size, Some(image.len() as u64));
+    assert_eq!(content, image);
+
+    // Interrupted downloads can be resumed
+    let (total_size, content) = get_os_image_content(&env, token, host_ip, 1000)
+        .await
+        .unwrap();
+    assert_eq!(total_size, Some(image.len() as u64));
+    assert_eq!(content, image[1000..]);
+    let err = get_os_image_content(&env, token, host_ip, image.len() as u64)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::OutOfRange);
+
+    // The URL can't be used by other hosts
+    let err = get_os_image_content(&env, token, dpu_ip, 0)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+    // Nor for other images
+    let tampered = token.replace(&os_image_id.to_string(), &uuid::Uuid::new_v4().to_string());
+    let err = get_os_image_content(&env, &tampered, host_ip, 0)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+}
+
+// Returns the size of the image and the content starting at offset
+async fn get_os_image_content(
+    env: &TestEnv,
+    token: &str,
+    client_ip: IpAddr,
+    offset: u64,
+) -> Result<(Option<u64>, Vec<u8>), tonic::Status> {
+    let mut chunks = env
+        .api
+        .get_os_image_content(tonic::Request::new(rpc::forge::GetOsImageContentRequest {
+            token: token.to_string(),
+            client_ip: client_ip.to_string(),
+            offset,
+        }))
+        .await?
+        .into_inner();
+    let mut total_size = None;
+    let mut content = Vec::new();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        total_size = total_size.or(chunk.total_size);
+        content.extend(chunk.data);
+    }
+    Ok((total_size, content))
+}
+
 async fn invoke_instance_power(
     env: &TestEnv,
     instance_id: InstanceId,
@@ -205,6 +205,86 @@ async fn test_hardware_health_reporting(
     Ok(())
 }
 
+/// Tests that the health reports of log-parser and ssh-console's console monitor
+/// are stored separately, and don't overwrite each other's alerts.
+#[crate::sqlx_test]
+async fn test_log_parser_and_console_health_reporting(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_env(pool).await;
+
+    let (host_machine_id, _) = create_managed_host(&env).await.into();
+
+    let log_parser_health = hr(
+        "log-parser",
+        vec![],
+        vec![("LogParserFailure", None, "Seen in console log")],
+    );
+    env.api
+        .record_log_parser_health_report(Request::new(rpc::forge::HardwareHealthReport {
+            machine_id: Some(host_machine_id),
+            report: Some(log_parser_health.clone().into()),
+        }))
+        .await?;
+    let console_health = hr(
+        "ssh-console",
+        vec![],
+        vec![("ConsoleFailure", None, "Seen in console output")],
+    );
+    env.api
+        .record_console_health_report(Request::new(rpc::forge::HardwareHealthReport {
+            machine_id: Some(host_machine_id),
+            report: Some(console_health.clone().into()),
+        }))
+        .await?;
+
+    let snapshot = load_snapshot(&env, &host_machine_id).await?.host_snapshot;
+    check_reports_equal(
+        "log-parser",
+        snapshot.log_parser_health_report.unwrap(),
+        log_parser_health,
+    );
+    check_reports_equal(
+        "ssh-console",
+        snapshot.console_health_report.unwrap(),
+        console_health,
+    );
+
+    let alert_ids = |report: health_report::HealthReport| {
+        let mut ids: Vec<String> = report
+            .alerts
+            .into_iter()
+            .map(|alert| alert.id.to_string())
+            .collect();
+        ids.sort();
+        ids
+    };
+    let aggregate_health = load_health_via_find_machines_by_ids(&env, &host_machine_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        alert_ids(aggregate_health),
+        vec!["ConsoleFailure".to_string(), "LogParserFailure".to_string()]
+    );
+
+    // log-parser clearing its alert leaves the console alert in place
+    env.api
+        .record_log_parser_health_report(Request::new(rpc::forge::HardwareHealthReport {
+            machine_id: Some(host_machine_id),
+            report: Some(hr("log-parser", vec![], vec![]).into()),
+        }))
+        .await?;
+    let aggregate_health = load_health_via_find_machines_by_ids(&env, &host_machine_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        alert_ids(aggregate_health),
+        vec!["ConsoleFailure".to_string()]
+    );
+
+    Ok(())
+}
+
 #[crate::sqlx_test]
 async fn test_machine_health_aggregation(
     pool: sqlx::PgPool,
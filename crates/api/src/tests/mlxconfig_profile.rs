@@ -0,0 +1,323 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::protos::mlx_device::{
+    self as mlx_device_pb, MlxObservation, MlxObservationReport,
+    PublishMlxObservationReportRequest, SerializableMlxConfigProfile,
+};
+use carbide_uuid::machine::MachineId;
+use health_report::HealthReport;
+use libmlx::profile::serialization::SerializableProfile;
+use model::machine::{MachineState, ManagedHostState};
+use rpc::forge::forge_server::Forge;
+use tonic::Request;
+
+use crate::tests::common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+
+fn profile_pb(num_of_vfs: i64) -> SerializableMlxConfigProfile {
+    SerializableProfile::new("sriov", "mlx_generic")
+        .with_config("SRIOV_EN", true)
+        .with_config("NUM_OF_VFS", num_of_vfs)
+        .try_into()
+        .unwrap()
+}
+
+fn sku_target(sku_id: &str) -> mlx_device_pb::MlxConfigProfileTarget {
+    mlx_device_pb::MlxConfigProfileTarget {
+        target: Some(mlx_device_pb::mlx_config_profile_target::Target::SkuId(
+            sku_id.to_string(),
+        )),
+    }
+}
+
+async fn create_profile(env: &TestEnv, num_of_vfs: i64) -> mlx_device_pb::ProfileSummary {
+    env.api
+        .mlx_admin_profile_create(Request::new(mlx_device_pb::MlxAdminProfileCreateRequest {
+            serializable_profile: Some(profile_pb(num_of_vfs)),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .profile
+        .unwrap()
+}
+
+async fn bind_profile(env: &TestEnv, sku_id: &str) {
+    env.api
+        .mlx_admin_profile_bind(Request::new(mlx_device_pb::MlxAdminProfileBindRequest {
+            binding: Some(mlx_device_pb::MlxConfigProfileBinding {
+                profile_name: "sriov".to_string(),
+                target: Some(sku_target(sku_id)),
+            }),
+        }))
+        .await
+        .unwrap();
+}
+
+/// Generates a SKU from the hardware of the host and assigns it
+async fn assign_sku(pool: &sqlx::PgPool, machine_id: &MachineId) -> String {
+    let mut txn = pool.begin().await.unwrap();
+    let sku = db::sku::generate_sku_from_machine(txn.as_mut(), machine_id)
+        .await
+        .unwrap();
+    db::sku::create(&mut txn, &sku).await.unwrap();
+    db::machine::assign_sku(&mut txn, machine_id, &sku.id)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+    sku.id
+}
+
+async fn publish_sync_result(
+    env: &TestEnv,
+    machine_id: MachineId,
+    version: &str,
+    synced: bool,
+    variables_changed: u32,
+) {
+    env.api
+        .publish_mlx_observation_report(Request::new(PublishMlxObservationReportRequest {
+            report: Some(MlxObservationReport {
+                machine_id: Some(machine_id),
+                timestamp: None,
+                observations: vec![MlxObservation {
+                    device_info: Some(mlx_device_pb::MlxDeviceInfo {
+                        pci_name: "0000:3b:00.0".to_string(),
+                        ..Default::default()
+                    }),
+                    lock_status: None,
+                    profile_name: Some("sriov".to_string()),
+                    profile_synced: Some(synced),
+                    firmware_report: None,
+                    profile_version: Some(version.to_string()),
+                    profile_variables_changed: synced.then_some(variables_changed),
+                    observed_variables: vec![],
+                }],
+            }),
+        }))
+        .await
+        .unwrap();
+}
+
+async fn host_state(pool: &sqlx::PgPool, machine_id: &MachineId) -> ManagedHostState {
+    let mut txn = pool.begin().await.unwrap();
+    db::machine::find_one(txn.as_mut(), machine_id, Default::default())
+        .await
+        .unwrap()
+        .unwrap()
+        .current_state()
+        .clone()
+}
+
+#[crate::sqlx_test]
+async fn test_profile_crud(pool: sqlx::PgPool) -> Result<(), eyre::Error> {
+    let env = create_test_env(pool.clone()).await;
+
+    let created = create_profile(&env, 16).await;
+    assert_eq!(created.name, "sriov");
+    assert_eq!(created.variable_count, 2);
+    let initial_version = created.version.clone().unwrap();
+
+    let err = env
+        .api
+        .mlx_admin_profile_create(Request::new(mlx_device_pb::MlxAdminProfileCreateRequest {
+            serializable_profile: Some(profile_pb(16)),
+        }))
+        .await
+        .expect_err("Creating a duplicate profile should fail");
+    assert!(err.message().contains("sriov"), "{err:?}");
+
+    let updated = env
+        .api
+        .mlx_admin_profile_update(Request::new(mlx_device_pb::MlxAdminProfileUpdateRequest {
+            serializable_profile: Some(profile_pb(32)),
+            if_version_match: Some(initial_version.clone()),
+        }))
+        .await?
+        .into_inner()
+        .profile
+        .unwrap();
+    assert_ne!(updated.version, Some(initial_version.clone()));
+
+    // The profile moved on since initial_version
+    env.api
+        .mlx_admin_profile_update(Request::new(mlx_device_pb::MlxAdminProfileUpdateRequest {
+            serializable_profile: Some(profile_pb(64)),
+            if_version_match: Some(initial_version),
+        }))
+        .await
+        .expect_err("Updating with a stale version should fail");
+
+    let shown = env
+        .api
+        .mlx_admin_profile_show(Request::new(mlx_device_pb::MlxAdminProfileShowRequest {
+            profile_name: "sriov".to_string(),
+        }))
+        .await?
+        .into_inner()
+        .serializable_profile
+        .unwrap();
+    assert_eq!(
+        shown.config.get("NUM_OF_VFS").map(String::as_str),
+        Some("32")
+    );
+
+    let listed = env
+        .api
+        .mlx_admin_profile_list(Request::new(mlx_device_pb::MlxAdminProfileListRequest {}))
+        .await?
+        .into_inner()
+        .profiles;
+    assert!(
+        listed
+            .iter()
+            .any(|p| p.name == "sriov" && p.version == updated.version)
+    );
+
+    env.api
+        .mlx_admin_profile_delete(Request::new(mlx_device_pb::MlxAdminProfileDeleteRequest {
+            profile_name: "sriov".to_string(),
+        }))
+        .await?;
+    let err = env
+        .api
+        .mlx_admin_profile_delete(Request::new(mlx_device_pb::MlxAdminProfileDeleteRequest {
+            profile_name: "sriov".to_string(),
+        }))
+        .await
+        .expect_err("Deleting a missing profile should fail");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_profile_bindings(pool: sqlx::PgPool) -> Result<(), eyre::Error> {
+    let env = create_test_env(pool.clone()).await;
+    let (host_id, _dpu_id) = create_managed_host(&env).await.into();
+    let sku_id = assign_sku(&pool, &host_id).await;
+
+    // Binding requires the profile to exist
+    let err = env
+        .api
+        .mlx_admin_profile_bind(Request::new(mlx_device_pb::MlxAdminProfileBindRequest {
+            binding: Some(mlx_device_pb::MlxConfigProfileBinding {
+                profile_name: "sriov".to_string(),
+                target: Some(sku_target(&sku_id)),
+            }),
+        }))
+        .await
+        .expect_err("Binding a missing profile should fail");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    create_profile(&env, 16).await;
+    bind_profile(&env, &sku_id).await;
+
+    let bindings = env
+        .api
+        .mlx_admin_profile_binding_list(Request::new(
+            mlx_device_pb::MlxAdminProfileBindingListRequest {},
+        ))
+        .await?
+        .into_inner()
+        .bindings;
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0].profile_name, "sriov");
+    assert_eq!(bindings[0].target, Some(sku_target(&sku_id)));
+
+    let bound = db::mlxconfig_profile::find_bound_profile(&pool, Some(&sku_id), None).await?;
+    assert_eq!(bound.map(|p| p.name), Some("sriov".to_string()));
+
+    let err = env
+        .api
+        .mlx_admin_profile_delete(Request::new(mlx_device_pb::MlxAdminProfileDeleteRequest {
+            profile_name: "sriov".to_string(),
+        }))
+        .await
+        .expect_err("Deleting a bound profile should fail");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    env.api
+        .mlx_admin_profile_unbind(Request::new(mlx_device_pb::MlxAdminProfileUnbindRequest {
+            target: Some(sku_target(&sku_id)),
+        }))
+        .await?;
+    assert!(
+        db::mlxconfig_profile::find_bound_profile(&pool, Some(&sku_id), None)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_ready_host_syncs_bound_profile(pool: sqlx::PgPool) -> Result<(), eyre::Error> {
+    let env = create_test_env(pool.clone()).await;
+    let (host_id, _dpu_id) = create_managed_host(&env).await.into();
+    let sku_id = assign_sku(&pool, &host_id).await;
+    let version = create_profile(&env, 16).await.version.unwrap();
+    bind_profile(&env, &sku_id).await;
+
+    env.run_machine_state_controller_iteration().await;
+    assert_eq!(
+        host_state(&pool, &host_id).await,
+        ManagedHostState::HostInit {
+            machine_state: MachineState::MlxConfigSync,
+        }
+    );
+
+    // A failed sync keeps the host out of Ready and raises a health alert
+    publish_sync_result(&env, host_id, &version, false, 0).await;
+    env.run_machine_state_controller_iteration().await;
+    assert_eq!(
+        host_state(&pool, &host_id).await,
+        ManagedHostState::HostInit {
+            machine_state: MachineState::MlxConfigSync,
+        }
+    );
+    let mut txn = pool.begin().await?;
+    let host = db::machine::find_one(txn.as_mut(), &host_id, Default::default())
+        .await?
+        .unwrap();
+    let drift = &host.health_report_overrides.merges[HealthReport::MLXCONFIG_PROFILE_SOURCE];
+    assert_eq!(drift.alerts.len(), 1);
+    assert_eq!(drift.alerts[0].target.as_deref(), Some("0000:3b:00.0"));
+    txn.rollback().await?;
+
+    // A successful sync without changes lets the host return to Ready
+    publish_sync_result(&env, host_id, &version, true, 0).await;
+    env.run_machine_state_controller_iteration().await;
+    assert_eq!(host_state(&pool, &host_id).await, ManagedHostState::Ready);
+    let mut txn = pool.begin().await?;
+    let host = db::machine::find_one(txn.as_mut(), &host_id, Default::default())
+        .await?
+        .unwrap();
+    assert!(
+        !host
+            .health_report_overrides
+            .merges
+            .contains_key(HealthReport::MLXCONFIG_PROFILE_SOURCE)
+    );
+    txn.rollback().await?;
+
+    // The host stays Ready while it is in sync
+    env.run_machine_state_controller_iteration().await;
+    assert_eq!(host_state(&pool, &host_id).await, ManagedHostState::Ready);
+
+    Ok(())
+}
@@ -14,8 +14,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+mod audit_log;
 pub(crate) mod common;
 mod connected_device;
+mod console_access;
 mod create_domain;
 mod desired_firmware_versions;
 mod dns;
@@ -35,6 +37,7 @@ mod explored_endpoint_find;
 mod explored_managed_host_find;
 mod extension_service;
 mod finder;
+mod health_shard;
 mod host_bmc_firmware_test;
 mod ib_fabric_find;
 mod ib_fabric_monitor;
@@ -46,6 +49,7 @@ mod instance;
 mod instance_allocate;
 mod instance_batch_allocate;
 mod instance_config_update;
+mod instance_events;
 mod instance_find;
 mod instance_ipxe_behaviors;
 mod instance_os;
@@ -75,6 +79,7 @@ mod machine_validation;
 mod maintenance;
 #[cfg(feature = "linux-build")]
 mod measured_boot;
+mod mlxconfig_profile;
 mod mqtt_state_change_hook;
 mod network_device;
 mod network_security_group;
@@ -99,6 +104,7 @@ mod state_controller;
 mod storage;
 mod switch;
 mod switch_state_controller;
+mod tenant_dns_record;
 mod tenant_keyset_find;
 mod tenants;
 mod test_meter;
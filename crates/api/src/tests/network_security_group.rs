@@ -1882,3 +1882,258 @@ async fn test_network_security_group_get_attachments(
 
     Ok(())
 }
+
+fn vpc_reference_rule(
+    vpc_id: VpcId,
+    dst_port_end: u32,
+) -> rpc::forge::NetworkSecurityGroupRuleAttributes {
+    rpc::forge::NetworkSecurityGroupRuleAttributes {
+        id: Some("from_vpc".to_string()),
+        direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+        ipv6: false,
+        src_port_start: None,
+        src_port_end: None,
+        dst_port_start: Some(1),
+        dst_port_end: Some(dst_port_end),
+        protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+        action: rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+        priority: 100,
+        source_net: Some(
+            rpc::forge::network_security_group_rule_attributes::SourceNet::SrcVpcId(
+                vpc_id.to_string(),
+            ),
+        ),
+        destination_net: Some(
+            rpc::forge::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                "0.0.0.0/0".to_string(),
+            ),
+        ),
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_network_security_group_object_references(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    // The fixture VPC and its tenant segment
+    env.create_vpc_and_tenant_segment().await;
+    let vpc_id = crate::tests::common::api_fixtures::get_vpc_fixture_id(&env).await;
+    let vpc_tenant_org = "2829bbe3-c169-4cd9-8b2a-19a8b1618a93";
+
+    let metadata = Some(rpc::forge::Metadata {
+        name: "vpc reference".to_string(),
+        description: "".to_string(),
+        labels: vec![],
+    });
+
+    // Another tenant can't reference a VPC that it neither owns nor peers with.
+    let err = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: None,
+                tenant_organization_id: "Tenant1".to_string(),
+                metadata: metadata.clone(),
+                network_security_group_attributes: Some(
+                    rpc::forge::NetworkSecurityGroupAttributes {
+                        stateful_egress: false,
+                        rules: vec![vpc_reference_rule(vpc_id, 1)],
+                    },
+                ),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // The owner of the VPC can reference it.  The segment prefix expands
+    // to exactly as many rules as the limit allows.
+    let nsg = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: None,
+                tenant_organization_id: vpc_tenant_org.to_string(),
+                metadata: metadata.clone(),
+                network_security_group_attributes: Some(
+                    rpc::forge::NetworkSecurityGroupAttributes {
+                        stateful_egress: false,
+                        rules: vec![vpc_reference_rule(
+                            vpc_id,
+                            default_max_network_security_group_size(),
+                        )],
+                    },
+                ),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .network_security_group
+        .unwrap();
+
+    // The referenced object is stored as a reference, not as prefixes.
+    let rule = nsg.attributes.unwrap().rules.remove(0);
+    assert_eq!(
+        rule.source_net,
+        Some(
+            rpc::forge::network_security_group_rule_attributes::SourceNet::SrcVpcId(
+                vpc_id.to_string()
+            )
+        )
+    );
+
+    // A new VPC prefix would double the expanded rules and is refused.
+    let err = env
+        .api
+        .create_vpc_prefix(tonic::Request::new(rpc::forge::VpcPrefixCreationRequest {
+            id: None,
+            prefix: "192.0.2.0/25".into(),
+            name: "overflow".into(),
+            vpc_id: Some(vpc_id),
+            ..Default::default()
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_network_security_group_explain_flow(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    populate_network_security_groups(env.api.clone()).await;
+
+    // Our known fixture network security group, which
+    // denies ingress traffic on ports 80-32768.
+    let good_network_security_group_id = "fd3ab096-d811-11ef-8fe9-7be4b2483448";
+
+    let mh = site_explorer::new_host(&env, ManagedHostConfig::default())
+        .await
+        .unwrap();
+
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+
+    let instance = env
+        .api
+        .allocate_instance(tonic::Request::new(rpc::forge::InstanceAllocationRequest {
+            machine_id: mh.host_snapshot.id.into(),
+            config: Some(rpc::InstanceConfig {
+                tenant: Some(default_tenant_config()),
+                os: Some(default_os_config()),
+                network: Some(single_interface_network_config(segment_id)),
+                infiniband: None,
+                nvlink: None,
+                network_security_group_id: Some(good_network_security_group_id.into()),
+                dpu_extension_services: None,
+            }),
+            instance_id: None,
+            instance_type_id: None,
+            metadata: Some(rpc::forge::Metadata {
+                name: "newinstance".to_string(),
+                description: "desc".to_string(),
+                labels: vec![],
+            }),
+            allow_unhealthy_machine: false,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let flow = rpc::forge::ExplainNetworkSecurityGroupFlowRequest {
+        instance_id: instance.id,
+        virtual_function_id: None,
+        direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+        protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+        src_ip: "192.0.2.10".to_string(),
+        dst_ip: "10.0.0.5".to_string(),
+        src_port: Some(1000),
+        dst_port: Some(443),
+    };
+
+    // The flow matches the rule of the NSG attached to the instance.
+    let explanation = env
+        .api
+        .explain_network_security_group_flow(tonic::Request::new(flow.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        explanation.action(),
+        rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionDeny
+    );
+    assert_eq!(
+        explanation.rule_source(),
+        rpc::forge::NetworkSecurityGroupFlowRuleSource::NsgFlowRuleSourceNsg
+    );
+    assert_eq!(
+        explanation.network_security_group_source(),
+        rpc::forge::NetworkSecurityGroupSource::NsgSourceInstance
+    );
+    assert_eq!(
+        explanation.network_security_group_id.as_deref(),
+        Some(good_network_security_group_id)
+    );
+    assert_eq!(
+        explanation.matched_rule.unwrap().id.as_deref(),
+        Some(good_network_security_group_id)
+    );
+
+    // Outside of the port range of the rule, the default action applies.
+    let explanation = env
+        .api
+        .explain_network_security_group_flow(tonic::Request::new(
+            rpc::forge::ExplainNetworkSecurityGroupFlowRequest {
+                dst_port: Some(22),
+                ..flow.clone()
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        explanation.action(),
+        rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionDeny
+    );
+    assert_eq!(
+        explanation.rule_source(),
+        rpc::forge::NetworkSecurityGroupFlowRuleSource::NsgFlowRuleSourceDefault
+    );
+    assert!(explanation.matched_rule.is_none());
+
+    // Flows can't use the ANY protocol.
+    let err = env
+        .api
+        .explain_network_security_group_flow(tonic::Request::new(
+            rpc::forge::ExplainNetworkSecurityGroupFlowRequest {
+                protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+                ..flow.clone()
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // The instance has no interface on VF 1.
+    let err = env
+        .api
+        .explain_network_security_group_flow(tonic::Request::new(
+            rpc::forge::ExplainNetworkSecurityGroupFlowRequest {
+                virtual_function_id: Some(1),
+                ..flow
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    Ok(())
+}
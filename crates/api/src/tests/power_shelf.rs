@@ -299,6 +299,7 @@ async fn test_power_shelf_status_update(
         shelf_name: "Status Test Power Shelf".to_string(),
         power_state: "on".to_string(),
         health_status: "ok".to_string(),
+        ..Default::default()
     };
 
     power_shelf.status = Some(status.clone());
@@ -403,6 +404,7 @@ async fn test_power_shelf_conversion_roundtrip(
         shelf_name: "Conversion Test Power Shelf".to_string(),
         power_state: "on".to_string(),
         health_status: "ok".to_string(),
+        ..Default::default()
     };
 
     power_shelf.status = Some(status);
@@ -15,9 +15,28 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+use arc_swap::ArcSwap;
+use bmc_mock::{
+    CombinedServer, ListenerOrAddress, MockPowerState, PowerControl, PowerShelfInfo,
+    SetSystemPowerError, SystemPowerControl,
+};
 use carbide_uuid::power_shelf::PowerShelfId;
-use model::power_shelf::PowerShelfControllerState;
+use forge_secrets::credentials::{
+    BmcCredentialType, CredentialKey, CredentialProvider, Credentials,
+};
+use model::address_selection_strategy::AddressSelectionStrategy;
+use model::machine_interface_address::MachineInterfaceAssociation;
+use model::metadata::Metadata;
+use model::power_shelf::{NewPowerShelf, PowerShelfConfig, PowerShelfControllerState};
 use sqlx::PgConnection;
+use utils::HostPortPair;
+
+use crate::redfish::RedfishClientPoolImpl;
+use crate::tests::common::api_fixtures::TestEnv;
 
 /// Helper function to set power shelf controller state directly in database
 pub async fn set_power_shelf_controller_state(
@@ -46,3 +65,161 @@ pub async fn mark_power_shelf_as_deleted(
 
     Ok(())
 }
+
+/// Power control of a power shelf emulated by bmc-mock.
+/// Power commands take effect immediately.
+#[derive(Debug)]
+pub struct TestPowerShelfPowerControl {
+    power_state: Mutex<MockPowerState>,
+}
+
+impl TestPowerShelfPowerControl {
+    pub fn new(power_state: MockPowerState) -> Self {
+        Self {
+            power_state: Mutex::new(power_state),
+        }
+    }
+}
+
+impl PowerControl for TestPowerShelfPowerControl {
+    fn get_power_state(&self) -> MockPowerState {
+        *self.power_state.lock().unwrap()
+    }
+
+    fn send_power_command(
+        &self,
+        reset_type: SystemPowerControl,
+    ) -> Result<(), SetSystemPowerError> {
+        let mut power_state = self.power_state.lock().unwrap();
+        match reset_type {
+            SystemPowerControl::On | SystemPowerControl::ForceOn => {
+                *power_state = MockPowerState::On
+            }
+            SystemPowerControl::GracefulShutdown | SystemPowerControl::ForceOff => {
+                *power_state = MockPowerState::Off
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// A power shelf whose BMC is emulated by bmc-mock
+pub struct MockPowerShelf {
+    pub id: PowerShelfId,
+    pub info: PowerShelfInfo,
+    pub power_control: Arc<TestPowerShelfPowerControl>,
+    /// Redfish client pool which forwards requests for the shelf BMC to bmc-mock
+    pub redfish_client_pool: Arc<RedfishClientPoolImpl>,
+    _bmc_mock: CombinedServer,
+}
+
+/// Creates a power shelf together with its BMC interface and `ExpectedPowerShelf`
+/// entry, and starts a bmc-mock server which emulates the shelf BMC.
+///
+/// The shelf uses the serial number from `info` as name.
+pub async fn create_mock_power_shelf(
+    env: &TestEnv,
+    info: PowerShelfInfo,
+    power_state: MockPowerState,
+) -> Result<MockPowerShelf, Box<dyn std::error::Error>> {
+    let mut txn = env.pool.begin().await?;
+    let network_segment = db::network_segment::admin(&mut txn).await?;
+    let interface = db::machine_interface::create(
+        &mut txn,
+        &network_segment,
+        &info.bmc_mac_address,
+        Some(env.domain.into()),
+        true,
+        AddressSelectionStrategy::NextAvailableIp,
+    )
+    .await?;
+    let bmc_ip = *interface
+        .addresses
+        .first()
+        .expect("BMC interface should have an address");
+
+    let id = PowerShelfId::from(uuid::Uuid::new_v4());
+    db::power_shelf::create(
+        &mut txn,
+        &NewPowerShelf {
+            id,
+            config: PowerShelfConfig {
+                name: info.serial.clone(),
+                capacity: None,
+                voltage: None,
+                location: None,
+            },
+        },
+    )
+    .await?;
+    db::machine_interface::associate_interface_with_machine(
+        &interface.id,
+        MachineInterfaceAssociation::PowerShelf(id),
+        &mut txn,
+    )
+    .await?;
+    db::expected_power_shelf::create(
+        &mut txn,
+        info.bmc_mac_address,
+        "admin".to_string(),
+        "password".to_string(),
+        info.serial.clone(),
+        None,
+        Metadata::default(),
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+
+    env.test_credential_provider
+        .set_credentials(
+            &CredentialKey::BmcCredentials {
+                credential_type: BmcCredentialType::BmcRoot {
+                    bmc_mac_address: info.bmc_mac_address,
+                },
+            },
+            &Credentials::UsernamePassword {
+                username: "root".to_string(),
+                password: "power_shelf_bmc_root".to_string(),
+            },
+        )
+        .await?;
+
+    let power_control = Arc::new(TestPowerShelfPowerControl::new(power_state));
+    let router = bmc_mock::power_shelf_router(info.clone(), power_control.clone(), id.to_string());
+
+    if rustls::crypto::CryptoProvider::get_default().is_none() {
+        let _ = rustls::crypto::CryptoProvider::install_default(
+            rustls::crypto::aws_lc_rs::default_provider(),
+        );
+    }
+    let bmc_mock = CombinedServer::run(
+        "power-shelf-bmc-mock",
+        Arc::new(tokio::sync::RwLock::new(HashMap::from([(
+            bmc_ip.to_string(),
+            router,
+        )]))),
+        Some(ListenerOrAddress::Listener(TcpListener::bind(
+            "127.0.0.1:0",
+        )?)),
+        bmc_mock::tls::server_config(None::<&str>)?,
+    );
+
+    let redfish_client_pool = Arc::new(RedfishClientPoolImpl::new(
+        env.test_credential_provider.clone(),
+        libredfish::RedfishClientPool::builder().build()?,
+        Arc::new(ArcSwap::new(Arc::new(Some(HostPortPair::HostAndPort(
+            "127.0.0.1".to_string(),
+            bmc_mock.address.port(),
+        ))))),
+    ));
+
+    Ok(MockPowerShelf {
+        id,
+        info,
+        power_control,
+        redfish_client_pool,
+        _bmc_mock: bmc_mock,
+    })
+}
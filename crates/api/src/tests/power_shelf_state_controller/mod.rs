@@ -20,23 +20,33 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use bmc_mock::{MockPowerState, PowerControl, PowerShelfInfo, SystemPowerControl};
 use carbide_uuid::power_shelf::PowerShelfId;
-use db::power_shelf as db_power_shelf;
-use model::power_shelf::{PowerShelf, PowerShelfControllerState};
+use db::{power_shelf as db_power_shelf, rack as db_rack};
+use model::power_shelf::{PowerShelf, PowerShelfControllerState, PowerShelfStatus};
+use model::rack::{RackReadyState, RackState};
 use rpc::forge::forge_server::Forge;
 
 use crate::state_controller::config::IterationConfig;
 use crate::state_controller::controller::StateController;
 use crate::state_controller::power_shelf::context::PowerShelfStateHandlerContextObjects;
+use crate::state_controller::power_shelf::handler::PowerShelfStateHandler;
 use crate::state_controller::power_shelf::io::PowerShelfStateControllerIO;
+use crate::state_controller::rack::handler::RackStateHandler;
+use crate::state_controller::rack::io::RackStateControllerIO;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
 use crate::tests::common;
-use crate::tests::common::api_fixtures::create_test_env;
+use crate::tests::common::api_fixtures::site_explorer::TestRackDbBuilder;
+use crate::tests::common::api_fixtures::{TestEnv, create_test_env};
+use crate::tests::rack_state_controller::fixtures::rack::set_rack_controller_state;
 
 mod fixtures;
-use fixtures::power_shelf::{mark_power_shelf_as_deleted, set_power_shelf_controller_state};
+use fixtures::power_shelf::{
+    MockPowerShelf, create_mock_power_shelf, mark_power_shelf_as_deleted,
+    set_power_shelf_controller_state,
+};
 
 use crate::state_controller::common_services::CommonStateHandlerServices;
 
@@ -458,3 +468,304 @@ async fn test_power_shelf_deletion_with_state_controller(
 
     Ok(())
 }
+
+/// Runs the power shelf state handler against a shelf emulated by bmc-mock until
+/// the shelf reaches `target_state` or `max_iterations` are exhausted
+async fn run_mock_power_shelf_controller(
+    env: &TestEnv,
+    power_shelf: &MockPowerShelf,
+    target_state: PowerShelfControllerState,
+    max_iterations: usize,
+) -> Result<PowerShelf, Box<dyn std::error::Error>> {
+    let mut controller = StateController::<PowerShelfStateControllerIO>::builder()
+        .database(env.pool.clone(), env.api.work_lock_manager_handle.clone())
+        .meter("carbide_power_shelves", env.test_meter.meter())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(CommonStateHandlerServices {
+            redfish_client_pool: power_shelf.redfish_client_pool.clone(),
+            ..env.state_handler_services()
+        }))
+        .state_handler(Arc::new(PowerShelfStateHandler::default()))
+        .build_for_manual_iterations()?;
+
+    for _ in 0..max_iterations {
+        controller.run_single_iteration().await;
+        let shelf = db_power_shelf::find_by_id(env.pool.acquire().await?.as_mut(), &power_shelf.id)
+            .await?
+            .unwrap();
+        if shelf.controller_state.value == target_state {
+            return Ok(shelf);
+        }
+    }
+
+    let shelf = db_power_shelf::find_by_id(env.pool.acquire().await?.as_mut(), &power_shelf.id)
+        .await?
+        .unwrap();
+    panic!(
+        "Power shelf did not reach {target_state:?} after {max_iterations} iterations. Current state: {:?}",
+        shelf.controller_state.value
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_power_shelf_is_configured_and_monitored_via_redfish(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let power_shelf =
+        create_mock_power_shelf(&env, PowerShelfInfo::new(6), MockPowerState::Off).await?;
+
+    let shelf =
+        run_mock_power_shelf_controller(&env, &power_shelf, PowerShelfControllerState::Ready, 10)
+            .await?;
+
+    // The default power policy turns on the shelf outputs
+    assert!(matches!(
+        power_shelf.power_control.get_power_state(),
+        MockPowerState::On
+    ));
+
+    let status = shelf.status.unwrap();
+    assert_eq!(status.power_state, "on");
+    assert_eq!(status.health_status, PowerShelfStatus::HEALTH_OK);
+    assert_eq!(
+        status.serial_number.as_deref(),
+        Some(power_shelf.info.serial.as_str())
+    );
+    assert_eq!(
+        status.firmware_versions.get("PMC_Firmware"),
+        Some(&power_shelf.info.firmware_version)
+    );
+    assert_eq!(status.power_supplies.len(), 6);
+    assert!(status.power_supplies.iter().all(|psu| psu.is_healthy()));
+    assert_eq!(
+        status.power_supplies[3].serial_number.as_deref(),
+        Some(power_shelf.info.power_supplies[3].serial.as_str())
+    );
+    assert_eq!(status.redundancy.unwrap().min_num_needed, Some(3));
+    let health_report = status.health_report.unwrap();
+    assert!(
+        health_report.alerts.is_empty(),
+        "Unexpected alerts: {:?}",
+        health_report.alerts
+    );
+    assert!(status.last_health_check.is_some());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_power_shelf_power_policy_is_reapplied_in_ready(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let power_shelf =
+        create_mock_power_shelf(&env, PowerShelfInfo::new(4), MockPowerState::On).await?;
+    run_mock_power_shelf_controller(&env, &power_shelf, PowerShelfControllerState::Ready, 10)
+        .await?;
+
+    // Turning off the shelf outputs moves the shelf back into configuration,
+    // which turns them on again
+    power_shelf
+        .power_control
+        .send_power_command(SystemPowerControl::ForceOff)?;
+    run_mock_power_shelf_controller(
+        &env,
+        &power_shelf,
+        PowerShelfControllerState::Configuring,
+        1,
+    )
+    .await?;
+    let shelf =
+        run_mock_power_shelf_controller(&env, &power_shelf, PowerShelfControllerState::Ready, 5)
+            .await?;
+
+    assert!(matches!(
+        power_shelf.power_control.get_power_state(),
+        MockPowerState::On
+    ));
+    assert_eq!(shelf.status.unwrap().power_state, "on");
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_power_shelf_power_policy_is_suspended_while_rack_is_powered_off(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let power_shelf =
+        create_mock_power_shelf(&env, PowerShelfInfo::new(4), MockPowerState::On).await?;
+    run_mock_power_shelf_controller(&env, &power_shelf, PowerShelfControllerState::Ready, 10)
+        .await?;
+
+    // Put the shelf into a rack
+    let mut txn = pool.begin().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    let mut rack = db_rack::get(&mut *txn, rack_id).await?;
+    rack.config.expected_power_shelves = vec![power_shelf.info.bmc_mac_address];
+    db_rack::update(&mut txn, rack_id, &rack.config).await?;
+    sqlx::query("UPDATE expected_power_shelves SET rack_id = $1 WHERE bmc_mac_address = $2")
+        .bind(rack_id)
+        .bind(power_shelf.info.bmc_mac_address)
+        .execute(&mut *txn)
+        .await?;
+    set_rack_controller_state(
+        &mut txn,
+        rack_id,
+        RackState::Ready {
+            rack_ready: RackReadyState::Full,
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    env.api
+        .set_rack_power_state(tonic::Request::new(rpc::forge::SetRackPowerStateRequest {
+            rack_id: Some(rack_id),
+            action: rpc::forge::RackPowerAction::Off as i32,
+            compute_wave_size: None,
+        }))
+        .await?;
+
+    // The rack state controller powers off the shelf
+    let mut rack_controller = StateController::<RackStateControllerIO>::builder()
+        .database(pool.clone(), env.api.work_lock_manager_handle.clone())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(CommonStateHandlerServices {
+            redfish_client_pool: power_shelf.redfish_client_pool.clone(),
+            ..env.state_handler_services()
+        }))
+        .state_handler(Arc::new(RackStateHandler::default()))
+        .build_for_manual_iterations()?;
+    for _ in 0..10 {
+        rack_controller.run_single_iteration().await;
+    }
+    let sequence = db_rack::get(&pool, rack_id)
+        .await?
+        .power_sequence
+        .expect("power sequence is recorded");
+    assert!(sequence.completed_at.is_some(), "{sequence:?}");
+    assert!(matches!(
+        power_shelf.power_control.get_power_state(),
+        MockPowerState::Off
+    ));
+
+    // The power shelf state controller doesn't turn the shelf back on
+    let mut shelf_controller = StateController::<PowerShelfStateControllerIO>::builder()
+        .database(env.pool.clone(), env.api.work_lock_manager_handle.clone())
+        .meter("carbide_power_shelves", env.test_meter.meter())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(CommonStateHandlerServices {
+            redfish_client_pool: power_shelf.redfish_client_pool.clone(),
+            ..env.state_handler_services()
+        }))
+        .state_handler(Arc::new(PowerShelfStateHandler::default()))
+        .build_for_manual_iterations()?;
+    for _ in 0..5 {
+        shelf_controller.run_single_iteration().await;
+    }
+    let shelf = db_power_shelf::find_by_id(env.pool.acquire().await?.as_mut(), &power_shelf.id)
+        .await?
+        .unwrap();
+    assert_eq!(
+        shelf.controller_state.value,
+        PowerShelfControllerState::Ready
+    );
+    assert_eq!(shelf.status.unwrap().power_state, "off");
+    assert!(matches!(
+        power_shelf.power_control.get_power_state(),
+        MockPowerState::Off
+    ));
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_power_shelf_raises_power_supply_alerts(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+
+    // A single failed PSU degrades the shelf, but redundancy is still given
+    let mut info = PowerShelfInfo::new(4);
+    info.power_supplies[1].healthy = false;
+    let power_shelf = create_mock_power_shelf(&env, info, MockPowerState::On).await?;
+    let shelf =
+        run_mock_power_shelf_controller(&env, &power_shelf, PowerShelfControllerState::Ready, 10)
+            .await?;
+
+    let status = shelf.status.unwrap();
+    assert_eq!(status.health_status, PowerShelfStatus::HEALTH_WARNING);
+    let alerts = status.health_report.unwrap().alerts;
+    assert_eq!(alerts.len(), 1, "Unexpected alerts: {alerts:?}");
+    assert_eq!(alerts[0].id.as_str(), "PowerSupply");
+    assert_eq!(alerts[0].target.as_deref(), Some("1"));
+    assert!(alerts[0].in_alert_since.is_some());
+
+    // Too many failed PSUs lose redundancy
+    let mut info = PowerShelfInfo::new(4);
+    for psu in info.power_supplies.iter_mut().skip(1) {
+        psu.healthy = false;
+    }
+    let power_shelf = create_mock_power_shelf(&env, info, MockPowerState::On).await?;
+    let shelf =
+        run_mock_power_shelf_controller(&env, &power_shelf, PowerShelfControllerState::Ready, 10)
+            .await?;
+
+    let status = shelf.status.unwrap();
+    assert_eq!(status.health_status, PowerShelfStatus::HEALTH_CRITICAL);
+    let alerts = status.health_report.unwrap().alerts;
+    assert_eq!(
+        alerts
+            .iter()
+            .filter(|alert| alert.id.as_str() == "PowerSupply")
+            .count(),
+        3
+    );
+    let redundancy_alert = alerts
+        .iter()
+        .find(|alert| alert.id.as_str() == "PowerRedundancy")
+        .expect("Missing PowerRedundancy alert");
+    assert!(
+        redundancy_alert
+            .classifications
+            .contains(&health_report::HealthAlertClassification::sensor_critical())
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_power_shelf_without_expected_power_shelf_enters_error(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+
+    let power_shelf_id = common::api_fixtures::site_explorer::new_power_shelf(
+        &env,
+        Some("Unexpected Power Shelf".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    env.run_power_shelf_controller_iteration().await;
+
+    let mut txn = pool.acquire().await?;
+    let power_shelf = db_power_shelf::find_by_id(&mut txn, &power_shelf_id)
+        .await?
+        .unwrap();
+    match power_shelf.controller_state.value {
+        PowerShelfControllerState::Error { cause } => {
+            assert!(
+                cause.contains("No expected power shelf with serial number Unexpected Power Shelf"),
+                "Unexpected cause: {cause}"
+            );
+        }
+        state => panic!("Power shelf should be in error state, but is in {state:?}"),
+    }
+
+    Ok(())
+}
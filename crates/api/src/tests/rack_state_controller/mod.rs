@@ -22,21 +22,28 @@ use std::time::Duration;
 
 use carbide_uuid::rack::RackId;
 use db::rack as db_rack;
-use model::rack::{Rack, RackMaintenanceState, RackReadyState, RackState, RackValidationState};
+use model::rack::{
+    Rack, RackDeviceFirmwareUpgrade, RackDeviceFirmwareUpgradeStatus, RackDeviceType,
+    RackFirmwareUpgrade, RackFirmwareUpgradeState, RackMaintenanceState, RackPowerAction,
+    RackReadyState, RackState, RackValidationState,
+};
 use rpc::forge::RackStateHistoryRecord;
 use rpc::forge::forge_server::Forge;
 
+use crate::redfish::test_support::RedfishSimAction;
 use crate::state_controller::config::IterationConfig;
 use crate::state_controller::controller::StateController;
 use crate::state_controller::rack::context::RackStateHandlerContextObjects;
+use crate::state_controller::rack::handler::RackStateHandler;
 use crate::state_controller::rack::io::RackStateControllerIO;
 use crate::state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
-use crate::tests::common::api_fixtures::create_test_env;
 use crate::tests::common::api_fixtures::site_explorer::TestRackDbBuilder;
+use crate::tests::common::api_fixtures::{create_managed_host, create_test_env};
+use crate::tests::common::rpc_builder::DhcpDiscovery;
 
-mod fixtures;
+pub(crate) mod fixtures;
 use fixtures::rack::{mark_rack_as_deleted, set_rack_controller_state};
 
 #[derive(Debug, Default, Clone)]
@@ -95,6 +102,22 @@ impl StateHandler for TestRackStateHandler {
     }
 }
 
+async fn rack_firmware_upgrade(
+    api: &crate::api::Api,
+    rack_id: RackId,
+) -> rpc::forge::RackFirmwareUpgradeStatus {
+    api.get_rack(tonic::Request::new(rpc::forge::GetRackRequest {
+        id: Some(rack_id.to_string()),
+    }))
+    .await
+    .unwrap()
+    .into_inner()
+    .rack
+    .remove(0)
+    .firmware_upgrade
+    .unwrap()
+}
+
 fn validate_state_change_history(
     histories: &[RackStateHistoryRecord],
     expected: &Vec<&str>,
@@ -499,3 +522,313 @@ async fn test_rack_deletion_with_state_controller(
 
     Ok(())
 }
+
+#[crate::sqlx_test]
+async fn test_rack_firmware_upgrade_reprovisions_compute_trays(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let mh = create_managed_host(&env).await;
+
+    let mut txn = pool.begin().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    let mut rack = db_rack::get(&mut *txn, rack_id).await?;
+    rack.config.compute_trays = vec![mh.id];
+    db_rack::update(&mut txn, rack_id, &rack.config).await?;
+    db_rack::update_firmware_upgrade(
+        &mut txn,
+        rack_id,
+        &RackFirmwareUpgrade {
+            firmware_id: "fw-1".to_string(),
+            firmware_type: "prod".to_string(),
+            max_unavailable: 1,
+            requested_at: chrono::Utc::now(),
+            started_at: None,
+            completed_at: None,
+            devices: vec![RackDeviceFirmwareUpgrade {
+                device_id: mh.id.to_string(),
+                device_type: RackDeviceType::ComputeTray,
+                components: Vec::new(),
+                status: RackDeviceFirmwareUpgradeStatus::Pending,
+                message: None,
+                started_at: None,
+                finished_at: None,
+            }],
+        },
+    )
+    .await?;
+    set_rack_controller_state(
+        &mut txn,
+        rack_id,
+        RackState::Ready {
+            rack_ready: RackReadyState::Full,
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    let mut controller = StateController::<RackStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_millis(50),
+            processor_dispatch_interval: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .database(pool.clone(), env.api.work_lock_manager_handle.clone())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(env.state_handler_services()))
+        .state_handler(Arc::new(RackStateHandler::default()))
+        .build_for_manual_iterations()
+        .unwrap();
+
+    // Ready -> FirmwareUpgrade, then the compute tray gets reprovisioned
+    controller.run_single_iteration().await;
+    controller.run_single_iteration().await;
+
+    let upgrade = rack_firmware_upgrade(&env.api, rack_id).await;
+    assert!(upgrade.started_at.is_some());
+    assert_eq!(upgrade.devices[0].status, "InProgress");
+
+    let mut txn = pool.begin().await?;
+    assert!(
+        mh.host()
+            .db_machine(&mut txn)
+            .await
+            .host_reprovision_requested
+            .is_some()
+    );
+    // The tray finishes reprovisioning
+    db::host_machine_update::clear_host_reprovisioning_request(&mut txn, &mh.id).await?;
+    txn.commit().await?;
+
+    // Compute -> Switch -> PowerShelf -> Completed -> Ready
+    for _ in 0..4 {
+        controller.run_single_iteration().await;
+    }
+
+    let upgrade = rack_firmware_upgrade(&env.api, rack_id).await;
+    assert_eq!(upgrade.devices[0].status, "Completed");
+    assert!(upgrade.completed_at.is_some());
+
+    let rack = db_rack::get(&pool, rack_id).await?;
+    assert!(!matches!(
+        rack.controller_state.value,
+        RackState::Maintenance {
+            rack_maintenance: RackMaintenanceState::FirmwareUpgrade { .. }
+        } | RackState::Error { .. }
+    ));
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_rack_firmware_upgrade_counts_in_progress_trays_first(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let pending_mh = create_managed_host(&env).await;
+    let upgrading_mh = create_managed_host(&env).await;
+
+    let compute_tray = |machine_id: String, status| RackDeviceFirmwareUpgrade {
+        device_id: machine_id,
+        device_type: RackDeviceType::ComputeTray,
+        components: Vec::new(),
+        status,
+        message: None,
+        started_at: None,
+        finished_at: None,
+    };
+
+    let mut txn = pool.begin().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    let mut rack = db_rack::get(&mut *txn, rack_id).await?;
+    rack.config.compute_trays = vec![pending_mh.id, upgrading_mh.id];
+    db_rack::update(&mut txn, rack_id, &rack.config).await?;
+    // The pending tray is listed before the tray which is being upgraded
+    db_rack::update_firmware_upgrade(
+        &mut txn,
+        rack_id,
+        &RackFirmwareUpgrade {
+            firmware_id: "fw-1".to_string(),
+            firmware_type: "prod".to_string(),
+            max_unavailable: 1,
+            requested_at: chrono::Utc::now(),
+            started_at: Some(chrono::Utc::now()),
+            completed_at: None,
+            devices: vec![
+                compute_tray(
+                    pending_mh.id.to_string(),
+                    RackDeviceFirmwareUpgradeStatus::Pending,
+                ),
+                compute_tray(
+                    upgrading_mh.id.to_string(),
+                    RackDeviceFirmwareUpgradeStatus::InProgress,
+                ),
+            ],
+        },
+    )
+    .await?;
+    db::host_machine_update::trigger_host_reprovisioning_request(
+        &mut txn,
+        "rack-firmware-upgrade",
+        &upgrading_mh.id,
+    )
+    .await?;
+    set_rack_controller_state(
+        &mut txn,
+        rack_id,
+        RackState::Maintenance {
+            rack_maintenance: RackMaintenanceState::FirmwareUpgrade {
+                rack_firmware_upgrade: RackFirmwareUpgradeState::Compute,
+            },
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    let mut controller = StateController::<RackStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_millis(50),
+            processor_dispatch_interval: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .database(pool.clone(), env.api.work_lock_manager_handle.clone())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(env.state_handler_services()))
+        .state_handler(Arc::new(RackStateHandler::default()))
+        .build_for_manual_iterations()
+        .unwrap();
+
+    controller.run_single_iteration().await;
+
+    // The upgrading tray occupies the only slot, so the pending one waits
+    let upgrade = rack_firmware_upgrade(&env.api, rack_id).await;
+    assert_eq!(upgrade.devices[0].status, "Pending");
+    assert_eq!(upgrade.devices[1].status, "InProgress");
+
+    let mut txn = pool.begin().await?;
+    assert!(
+        pending_mh
+            .host()
+            .db_machine(&mut txn)
+            .await
+            .host_reprovision_requested
+            .is_none()
+    );
+    // The upgrading tray finishes, which frees its slot
+    db::host_machine_update::clear_host_reprovisioning_request(&mut txn, &upgrading_mh.id).await?;
+    txn.commit().await?;
+
+    controller.run_single_iteration().await;
+
+    let upgrade = rack_firmware_upgrade(&env.api, rack_id).await;
+    assert_eq!(upgrade.devices[0].status, "InProgress");
+    assert_eq!(upgrade.devices[1].status, "Completed");
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_rack_power_reset_sequences_compute_trays(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+
+    // The BMCs of the compute trays get their addresses via DHCP
+    let bmc_macs = ["b8:3f:d2:90:97:c1", "b8:3f:d2:90:97:c2"];
+    for bmc_mac in bmc_macs {
+        env.api
+            .discover_dhcp(DhcpDiscovery::builder(bmc_mac, "192.0.2.1").tonic_request())
+            .await?;
+    }
+
+    let mut txn = pool.begin().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    let mut rack = db_rack::get(&mut *txn, rack_id).await?;
+    rack.config.expected_compute_trays = bmc_macs
+        .iter()
+        .map(|mac| mac.parse())
+        .collect::<Result<_, _>>()?;
+    db_rack::update(&mut txn, rack_id, &rack.config).await?;
+    set_rack_controller_state(
+        &mut txn,
+        rack_id,
+        RackState::Ready {
+            rack_ready: RackReadyState::Full,
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    env.api
+        .set_rack_power_state(tonic::Request::new(rpc::forge::SetRackPowerStateRequest {
+            rack_id: Some(rack_id),
+            action: rpc::forge::RackPowerAction::Reset as i32,
+            compute_wave_size: Some(1),
+        }))
+        .await?;
+
+    // A second request is rejected while the sequence is pending
+    let err = env
+        .api
+        .set_rack_power_state(tonic::Request::new(rpc::forge::SetRackPowerStateRequest {
+            rack_id: Some(rack_id),
+            action: rpc::forge::RackPowerAction::Off as i32,
+            compute_wave_size: None,
+        }))
+        .await
+        .expect_err("power sequence is already pending");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    let sequence = db_rack::get(&pool, rack_id)
+        .await?
+        .power_sequence
+        .expect("power sequence is recorded");
+    assert_eq!(sequence.action, RackPowerAction::Reset);
+    assert_eq!(sequence.steps.len(), 4);
+
+    let mut controller = StateController::<RackStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_millis(50),
+            processor_dispatch_interval: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .database(pool.clone(), env.api.work_lock_manager_handle.clone())
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(env.state_handler_services()))
+        .state_handler(Arc::new(RackStateHandler::default()))
+        .build_for_manual_iterations()
+        .unwrap();
+
+    // Ready -> PowerSequence, then every step is issued and verified
+    let timepoint = env.redfish_sim.timepoint();
+    for _ in 0..10 {
+        controller.run_single_iteration().await;
+    }
+
+    let sequence = db_rack::get(&pool, rack_id)
+        .await?
+        .power_sequence
+        .expect("power sequence is recorded");
+    assert!(sequence.completed_at.is_some());
+    assert!(sequence.error.is_none());
+
+    let actions = env.redfish_sim.actions_since(&timepoint).all_hosts();
+    let count = |action: libredfish::SystemPowerControl| {
+        actions
+            .iter()
+            .filter(|a| **a == RedfishSimAction::Power(action))
+            .count()
+    };
+    assert_eq!(count(libredfish::SystemPowerControl::GracefulShutdown), 2);
+    assert_eq!(count(libredfish::SystemPowerControl::On), 2);
+
+    let rack = db_rack::get(&pool, rack_id).await?;
+    assert!(!matches!(
+        rack.controller_state.value,
+        RackState::Maintenance {
+            rack_maintenance: RackMaintenanceState::PowerSequence { .. }
+        } | RackState::Error { .. }
+    ));
+
+    Ok(())
+}
@@ -22,14 +22,18 @@ pub mod tests {
     use db::attestation::spdm::insert_devices;
     use model::attestation::spdm::{
         AttestationDeviceState, AttestationState, FetchDataDeviceStates,
-        SpdmMachineAttestationHistory, SpdmMachineStateSnapshot, VerificationDeviceStates,
+        SpdmMachineAttestationHistory, SpdmMachineStateSnapshot, SpdmObjectId,
+        VerificationDeviceStates,
     };
     use rpc::forge::forge_server::Forge;
     use rpc::forge::{AttestationData, AttestationIdsRequest, AttestationMachineList};
     use sqlx::PgConnection;
     use tonic::Request;
 
-    use crate::tests::common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+    use crate::tests::common::api_fixtures::{
+        TestEnv, TestEnvOverrides, create_managed_host, create_test_env,
+        create_test_env_with_overrides, get_config,
+    };
 
     // A simple test to test basic db functions.
     #[crate::sqlx_test]
@@ -53,7 +57,7 @@ pub mod tests {
         assert_eq!(_ids[0], machine_id);
 
         let mut txn = env.pool.begin().await.unwrap();
-        let data = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn).await?;
+        let data = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None).await?;
         assert_eq!(data.len(), 1);
         txn.commit().await.unwrap();
 
@@ -212,7 +216,7 @@ pub mod tests {
         assert_eq!(_ids[0], machine_id);
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -248,7 +252,7 @@ pub mod tests {
         );
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -405,7 +409,7 @@ pub mod tests {
         assert_eq!(_ids[0], machine_id);
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -441,7 +445,7 @@ pub mod tests {
         );
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -500,7 +504,7 @@ pub mod tests {
         txn.commit().await.unwrap();
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -530,7 +534,7 @@ pub mod tests {
         assert_eq!(_ids[0], machine_id);
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -566,7 +570,7 @@ pub mod tests {
         );
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -627,7 +631,7 @@ pub mod tests {
             .await?;
 
         let mut txn = env.pool.begin().await.unwrap();
-        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn)
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(&mut txn, None)
             .await
             .unwrap();
         txn.commit().await.unwrap();
@@ -635,4 +639,97 @@ pub mod tests {
         assert_eq!(object_ids.len(), 1);
         Ok(())
     }
+
+    // Completed attestation is restarted once it is older than the re-attestation interval
+    #[crate::sqlx_test]
+    async fn test_periodic_reattestation(pool: sqlx::PgPool) -> Result<(), eyre::Error> {
+        let mut config = get_config();
+        config.attestation_policy.reattestation_interval = chrono::Duration::hours(1);
+        let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+        let (machine_id, _dpu_id) = create_managed_host(&env).await.into();
+        let _res = env
+            .api
+            .trigger_machine_attestation(Request::new(AttestationData {
+                machine_id: Some(machine_id),
+            }))
+            .await?;
+
+        for i in 0..25 {
+            env.run_spdm_controller_iteration().await;
+            if test_device_states(
+                &[
+                    "AttestationCompleted { status: NotSupported }",
+                    "AttestationCompleted { status: Success }",
+                    "AttestationCompleted { status: Success }",
+                ],
+                &machine_id,
+                &env,
+            )
+            .await
+            {
+                break;
+            }
+            if i == 24 {
+                panic!("Attestation state machines did not complete in expected iterations");
+            }
+        }
+
+        let machine = env
+            .api
+            .find_machines_under_attestation(Request::new(AttestationMachineList {
+                machine_ids: vec![machine_id],
+            }))
+            .await?
+            .into_inner()
+            .machines
+            .remove(0);
+        assert_eq!(machine.status, "Completed");
+        assert!(machine.last_attested_at.is_some());
+        assert_eq!(machine.last_attestation_passed, Some(true));
+
+        // A fresh attestation is left alone
+        env.run_spdm_controller_iteration_no_requeue().await;
+        let mut txn = env.pool.begin().await.unwrap();
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(
+            &mut txn,
+            Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+        assert!(object_ids.is_empty());
+
+        // Age the attestation past the interval
+        sqlx::query(
+            "UPDATE spdm_machine_attestation SET last_attested_at = now() - interval '2 hours' WHERE machine_id = $1",
+        )
+        .bind(machine_id)
+        .execute(&mut *txn)
+        .await?;
+        let object_ids = db::attestation::spdm::find_machine_ids_for_attestation(
+            &mut txn,
+            Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+        txn.commit().await.unwrap();
+        assert_eq!(object_ids, vec![SpdmObjectId(machine_id, None)]);
+
+        env.run_spdm_controller_iteration_no_requeue().await;
+        let machine = env
+            .api
+            .find_machines_under_attestation(Request::new(AttestationMachineList {
+                machine_ids: vec![machine_id],
+            }))
+            .await?
+            .into_inner()
+            .machines
+            .remove(0);
+        assert_eq!(
+            machine.state,
+            format!("{:#?}", AttestationState::CheckIfAttestationSupported)
+        );
+        assert!(machine.requested_at.unwrap() > machine.started_at.unwrap());
+
+        Ok(())
+    }
 }
@@ -15,6 +15,9 @@
  * limitations under the License.
  */
 
+use axum::body::Body;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
 use rpc::forge::forge_server::Forge;
 use rpc::forge::{
     // StorageClusterAttributes,
@@ -22,10 +25,18 @@ use rpc::forge::{
     OsImageAttributes,
     OsImageStatus,
 };
+use sha2::{Digest, Sha256};
+use temp_dir::TempDir;
 use tonic::Request;
 use uuid::Uuid;
 
-use crate::tests::common::api_fixtures::{TestEnvOverrides, create_test_env_with_overrides};
+use crate::cfg::file::OsImageIngestionConfig;
+use crate::os_image_ingestion::{
+    FilesystemKind, OsImageIngestion, test_disk, test_disk::TestFilesystem, test_image,
+};
+use crate::tests::common::api_fixtures::{
+    TestEnvOverrides, create_test_env_with_overrides, get_config,
+};
 
 // TODO: Fix tests for storage pool
 /*
@@ -222,8 +233,8 @@ async fn test_create_and_delete_os_image(
     assert!(image.attributes.is_some(), "Image attributes should be set");
     assert_eq!(
         image.status,
-        OsImageStatus::ImageReady as i32,
-        "Initial status should be Ready"
+        OsImageStatus::ImageUninitialized as i32,
+        "Initial status should be Uninitialized"
     );
 
     let image_id = image.attributes.as_ref().unwrap().id.clone().unwrap();
@@ -271,8 +282,8 @@ async fn test_os_image_status_transitions(
 
     assert_eq!(
         image.status,
-        OsImageStatus::ImageReady as i32,
-        "Initial status should be Ready"
+        OsImageStatus::ImageUninitialized as i32,
+        "Initial status should be Uninitialized"
     );
 
     // Test status transition to InProgress
@@ -283,12 +294,290 @@ async fn test_os_image_status_transitions(
     let response = env.api.update_os_image(request).await;
     let updated = response.expect("Could not update OS image").into_inner();
 
-    // The status should not change unless the volume is created
+    // The status should not change unless the image is ingested
     assert_eq!(
         updated.status,
-        OsImageStatus::ImageReady as i32,
-        "Status should transition to ImageReady"
+        OsImageStatus::ImageUninitialized as i32,
+        "Status should remain ImageUninitialized"
+    );
+
+    Ok(())
+}
+
+fn image_attributes(source_url: String, digest: String) -> OsImageAttributes {
+    OsImageAttributes {
+        id: Some(rpc::Uuid {
+            value: Uuid::new_v4().to_string(),
+        }),
+        source_url,
+        digest,
+        tenant_organization_id: "test-org".to_string(),
+        create_volume: false,
+        name: Some("test-image".to_string()),
+        description: None,
+        auth_type: Some("Token".to_string()),
+        auth_token: Some("secret".to_string()),
+        rootfs_id: None,
+        rootfs_label: None,
+        boot_disk: None,
+        capacity: None,
+        bootfs_id: None,
+        efifs_id: None,
+    }
+}
+
+/// Serves the image on a local HTTP server which requires the auth token
+/// used by `image_attributes`, and returns its URL. The image is also served
+/// at `/chunked/image.qcow2`, without announcing its size.
+async fn serve_image(image: Vec<u8>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let authorized = |headers: &HeaderMap| {
+        headers.get(AUTHORIZATION).and_then(|v| v.to_str().ok()) == Some("Token secret")
+    };
+    let chunked_image = image.clone();
+    let router = axum::Router::new()
+        .route(
+            "/image.qcow2",
+            axum::routing::get(move |headers: HeaderMap| {
+                let image = image.clone();
+                async move {
+                    if !authorized(&headers) {
+                        return (StatusCode::UNAUTHORIZED, Vec::new());
+                    }
+                    (StatusCode::OK, image)
+                }
+            }),
+        )
+        .route(
+            "/chunked/image.qcow2",
+            axum::routing::get(move |headers: HeaderMap| {
+                let chunks: Vec<Result<Vec<u8>, std::io::Error>> = chunked_image
+                    .chunks(4096)
+                    .map(|chunk| Ok(chunk.to_vec()))
+                    .collect();
+                async move {
+                    if !authorized(&headers) {
+                        return (StatusCode::UNAUTHORIZED, Body::empty());
+                    }
+                    (
+                        StatusCode::OK,
+                        Body::from_stream(futures_util::stream::iter(chunks)),
+                    )
+                }
+            }),
+        );
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    format!("http://{addr}/image.qcow2")
+}
+
+#[crate::sqlx_test]
+async fn test_os_image_ingestion(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::default()).await;
+
+    let disk = test_disk::build(&[
+        TestFilesystem {
+            kind: FilesystemKind::Vfat,
+            uuid: [0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            label: "UEFI",
+        },
+        TestFilesystem {
+            kind: FilesystemKind::Ext,
+            uuid: [0x5a; 16],
+            label: "cloudimg-rootfs",
+        },
+    ]);
+    let image = test_image::build(&disk, true);
+    let digest = hex::encode(Sha256::digest(&image));
+    let url = serve_image(image).await;
+
+    let mut good = image_attributes(url.clone(), format!("sha256:{digest}"));
+    good.rootfs_id = Some("5a5a5a5a-5a5a-5a5a-5a5a-5a5a5a5a5a5a".to_string());
+    good.rootfs_label = Some("cloudimg-rootfs".to_string());
+    good.efifs_id = Some("1234-5678".to_string());
+    good.boot_disk = Some("/dev/nvme0n1".to_string());
+
+    let mut wrong_digest = image_attributes(url.clone(), "ab".repeat(32));
+    wrong_digest.rootfs_label = Some("cloudimg-rootfs".to_string());
+
+    let mut wrong_rootfs = image_attributes(url.clone(), digest.clone());
+    wrong_rootfs.rootfs_label = Some("rootfs".to_string());
+
+    let mut unauthorized = image_attributes(url.clone(), digest.clone());
+    unauthorized.auth_token = Some("wrong".to_string());
+
+    for attrs in [&good, &wrong_digest, &wrong_rootfs, &unauthorized] {
+        let image = env
+            .api
+            .create_os_image(Request::new(attrs.clone()))
+            .await?
+            .into_inner();
+        assert_eq!(image.status, OsImageStatus::ImageUninitialized as i32);
+    }
+
+    let cache_dir = TempDir::with_prefix("test_os_image_ingestion")?;
+    let ingestion = OsImageIngestion::new(
+        env.pool.clone(),
+        OsImageIngestionConfig {
+            cache_dir: cache_dir.path().to_path_buf(),
+            ..OsImageIngestionConfig::default()
+        },
+    );
+    ingestion.run_single_iteration().await?;
+
+    let get_image = |attrs: &OsImageAttributes| {
+        let id = attrs.id.clone().unwrap();
+        let api = env.api.clone();
+        async move {
+            api.get_os_image(Request::new(id))
+                .await
+                .unwrap()
+                .into_inner()
+        }
+    };
+
+    let image = get_image(&good).await;
+    assert_eq!(image.status, OsImageStatus::ImageReady as i32);
+    assert_eq!(image.status_message, None);
+    let cached = cache_dir
+        .path()
+        .join(format!("{}.qcow2", good.id.clone().unwrap().value));
+    assert!(cached.exists());
+
+    let image = get_image(&wrong_digest).await;
+    assert_eq!(image.status, OsImageStatus::ImageFailed as i32);
+    assert!(
+        image
+            .status_message
+            .as_ref()
+            .unwrap()
+            .contains("digest mismatch"),
+        "{image:?}"
+    );
+
+    let image = get_image(&wrong_rootfs).await;
+    assert_eq!(image.status, OsImageStatus::ImageFailed as i32);
+    assert!(
+        image
+            .status_message
+            .as_ref()
+            .unwrap()
+            .contains("rootfs_label"),
+        "{image:?}"
+    );
+
+    let image = get_image(&unauthorized).await;
+    assert_eq!(image.status, OsImageStatus::ImageFailed as i32);
+    assert!(
+        image.status_message.as_ref().unwrap().contains("401"),
+        "{image:?}"
+    );
+
+    // Fixing the credentials of a failed image retries the ingestion
+    let mut fixed = unauthorized.clone();
+    fixed.auth_token = Some("secret".to_string());
+    let image = env
+        .api
+        .update_os_image(Request::new(fixed.clone()))
+        .await?
+        .into_inner();
+    assert_eq!(image.status, OsImageStatus::ImageUninitialized as i32);
+    assert_eq!(image.status_message, None);
+
+    ingestion.run_single_iteration().await?;
+    assert_eq!(
+        get_image(&fixed).await.status,
+        OsImageStatus::ImageReady as i32
+    );
+
+    // Updating a ready image which doesn't change its filesystems keeps it ready
+    let mut renamed = good.clone();
+    renamed.name = Some("renamed-image".to_string());
+    let image = env
+        .api
+        .update_os_image(Request::new(renamed))
+        .await?
+        .into_inner();
+    assert_eq!(image.status, OsImageStatus::ImageReady as i32);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_os_image_ingestion_size_limit(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::default()).await;
+
+    let disk = test_disk::build(&[TestFilesystem {
+        kind: FilesystemKind::Ext,
+        uuid: [0x5a; 16],
+        label: "cloudimg-rootfs",
+    }]);
+    let image = test_image::build(&disk, true);
+    let digest = hex::encode(Sha256::digest(&image));
+    let max_image_size = image.len() as u64 - 1;
+    let url = serve_image(image).await;
+
+    // The size is either announced by the source, or only noticed while
+    // downloading the image
+    let announced = image_attributes(url.clone(), digest.clone());
+    let chunked = image_attributes(url.replace("/image.qcow2", "/chunked/image.qcow2"), digest);
+    for attrs in [&announced, &chunked] {
+        env.api.create_os_image(Request::new(attrs.clone())).await?;
+    }
+
+    let cache_dir = TempDir::with_prefix("test_os_image_ingestion_size_limit")?;
+    let ingestion = OsImageIngestion::new(
+        env.pool.clone(),
+        OsImageIngestionConfig {
+            cache_dir: cache_dir.path().to_path_buf(),
+            max_image_size,
+            ..OsImageIngestionConfig::default()
+        },
+    );
+    ingestion.run_single_iteration().await?;
+
+    for attrs in [&announced, &chunked] {
+        let image = env
+            .api
+            .get_os_image(Request::new(attrs.id.clone().unwrap()))
+            .await?
+            .into_inner();
+        assert_eq!(image.status, OsImageStatus::ImageFailed as i32);
+        assert!(
+            image
+                .status_message
+                .as_ref()
+                .unwrap()
+                .contains(&format!("exceeds the limit of {max_image_size} bytes")),
+            "{image:?}"
+        );
+    }
+    // Nothing is left behind in the cache
+    assert_eq!(std::fs::read_dir(cache_dir.path())?.count(), 0);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_os_image_ready_without_ingestion(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = get_config();
+    config.os_image_ingestion.enabled = false;
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+
+    let attrs = image_attributes(
+        "https://example.com/image.qcow2".to_string(),
+        "ab".repeat(32),
     );
+    let image = env
+        .api
+        .create_os_image(Request::new(attrs))
+        .await?
+        .into_inner();
+    assert_eq!(image.status, OsImageStatus::ImageReady as i32);
 
     Ok(())
 }
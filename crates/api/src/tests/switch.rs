@@ -269,6 +269,7 @@ async fn test_switch_status_update(pool: sqlx::PgPool) -> Result<(), Box<dyn std
         switch_name: "Status Test Switch".to_string(),
         power_state: "on".to_string(),
         health_status: "ok".to_string(),
+        ..Default::default()
     };
 
     switch.status = Some(status.clone());
@@ -365,6 +366,7 @@ async fn test_switch_conversion_roundtrip(
         switch_name: "Conversion Test Switch".to_string(),
         power_state: "on".to_string(),
         health_status: "ok".to_string(),
+        ..Default::default()
     };
 
     switch.status = Some(status);
@@ -15,10 +15,19 @@
  * limitations under the License.
  */
 
+use std::net::IpAddr;
+
 use carbide_uuid::switch::SwitchId;
+use mac_address::MacAddress;
+use model::address_selection_strategy::AddressSelectionStrategy;
+use model::machine_interface_address::MachineInterfaceAssociation;
+use model::metadata::Metadata;
 use model::switch::SwitchControllerState;
 use sqlx::PgConnection;
 
+use crate::tests::common::api_fixtures::TestEnv;
+use crate::tests::common::api_fixtures::site_explorer::new_switch;
+
 /// Helper function to set switch controller state directly in database
 pub async fn set_switch_controller_state(
     txn: &mut PgConnection,
@@ -46,3 +55,55 @@ pub async fn mark_switch_as_deleted(
 
     Ok(())
 }
+
+/// Creates a switch together with its BMC interface and `ExpectedSwitch` entry.
+/// The simulated BMC of the switch reports `serial_number` as chassis serial number.
+///
+/// Returns the ID of the switch and the IP address of its BMC.
+pub async fn create_switch_with_bmc(
+    env: &TestEnv,
+    serial_number: &str,
+    bmc_mac_address: MacAddress,
+) -> Result<(SwitchId, IpAddr), Box<dyn std::error::Error>> {
+    let switch_id = new_switch(env, Some(serial_number.to_string()), None).await?;
+
+    let mut txn = env.pool.begin().await?;
+    let network_segment = db::network_segment::admin(&mut txn).await?;
+    let interface = db::machine_interface::create(
+        &mut txn,
+        &network_segment,
+        &bmc_mac_address,
+        Some(env.domain.into()),
+        true,
+        AddressSelectionStrategy::NextAvailableIp,
+    )
+    .await?;
+    let bmc_ip = *interface
+        .addresses
+        .first()
+        .expect("BMC interface should have an address");
+    db::machine_interface::associate_interface_with_machine(
+        &interface.id,
+        MachineInterfaceAssociation::Switch(switch_id),
+        &mut txn,
+    )
+    .await?;
+    db::expected_switch::create(
+        &mut txn,
+        bmc_mac_address,
+        "admin".to_string(),
+        "password".to_string(),
+        serial_number.to_string(),
+        Metadata::default(),
+        None,
+        None,
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+
+    env.redfish_sim
+        .set_chassis_serial_number(&bmc_ip.to_string(), serial_number);
+
+    Ok((switch_id, bmc_ip))
+}
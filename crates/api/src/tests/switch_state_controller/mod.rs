@@ -22,7 +22,7 @@ use std::time::Duration;
 
 use carbide_uuid::switch::SwitchId;
 use db::switch as db_switch;
-use model::switch::{Switch, SwitchControllerState};
+use model::switch::{Switch, SwitchControllerState, SwitchStatus};
 use rpc::forge::forge_server::Forge;
 
 use crate::state_controller::common_services::CommonStateHandlerServices;
@@ -34,10 +34,12 @@ use crate::state_controller::state_handler::{
 use crate::state_controller::switch::context::SwitchStateHandlerContextObjects;
 use crate::state_controller::switch::io::SwitchStateControllerIO;
 use crate::tests::common;
-use crate::tests::common::api_fixtures::create_test_env;
+use crate::tests::common::api_fixtures::{TestEnv, create_test_env};
 
 mod fixtures;
-use fixtures::switch::{mark_switch_as_deleted, set_switch_controller_state};
+use fixtures::switch::{
+    create_switch_with_bmc, mark_switch_as_deleted, set_switch_controller_state,
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct TestSwitchStateHandler {
@@ -440,3 +442,196 @@ async fn test_switch_deletion_with_state_controller(
 
     Ok(())
 }
+
+#[crate::sqlx_test]
+async fn test_switch_without_expected_switch_enters_error(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+
+    let switch_id = common::api_fixtures::site_explorer::new_switch(
+        &env,
+        Some("Unexpected Switch".to_string()),
+        None,
+    )
+    .await?;
+
+    env.run_switch_controller_iteration().await;
+
+    let mut txn = pool.acquire().await?;
+    let switch = db_switch::find_by_id(&mut txn, &switch_id).await?.unwrap();
+    match switch.controller_state.value {
+        SwitchControllerState::Error { cause } => {
+            assert!(
+                cause.contains("No expected switch with serial number Unexpected Switch"),
+                "Unexpected cause: {cause}"
+            );
+        }
+        state => panic!("Switch should be in error state, but is in {state:?}"),
+    }
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_switch_with_unassociated_bmc_enters_error(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+
+    let switch_id = common::api_fixtures::site_explorer::new_switch(
+        &env,
+        Some("MISCABLED-SN".to_string()),
+        None,
+    )
+    .await?;
+
+    let bmc_mac_address: mac_address::MacAddress = "0a:0b:0c:0d:0e:0f".parse().unwrap();
+    let mut txn = pool.begin().await?;
+    db::expected_switch::create(
+        &mut txn,
+        bmc_mac_address,
+        "admin".to_string(),
+        "password".to_string(),
+        "MISCABLED-SN".to_string(),
+        model::metadata::Metadata::default(),
+        None,
+        None,
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+
+    env.run_switch_controller_iteration().await;
+
+    let mut txn = pool.acquire().await?;
+    let switch = db_switch::find_by_id(&mut txn, &switch_id).await?.unwrap();
+    match switch.controller_state.value {
+        SwitchControllerState::Error { cause } => {
+            assert!(
+                cause.contains(&format!(
+                    "Expected BMC MAC address {bmc_mac_address} is not associated with the switch"
+                )),
+                "Unexpected cause: {cause}"
+            );
+        }
+        state => panic!("Switch should be in error state, but is in {state:?}"),
+    }
+
+    Ok(())
+}
+
+/// Runs the switch state controller until the switch reaches `target_state`
+/// or `max_iterations` are exhausted
+async fn run_switch_controller_until_state(
+    env: &TestEnv,
+    switch_id: &SwitchId,
+    target_state: SwitchControllerState,
+    max_iterations: usize,
+) -> Result<Switch, Box<dyn std::error::Error>> {
+    for _ in 0..max_iterations {
+        env.run_switch_controller_iteration().await;
+        let switch = db_switch::find_by_id(env.pool.acquire().await?.as_mut(), switch_id)
+            .await?
+            .unwrap();
+        if switch.controller_state.value == target_state {
+            return Ok(switch);
+        }
+    }
+
+    let switch = db_switch::find_by_id(env.pool.acquire().await?.as_mut(), switch_id)
+        .await?
+        .unwrap();
+    panic!(
+        "Switch did not reach {target_state:?} after {max_iterations} iterations. Current state: {:?}",
+        switch.controller_state.value
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_switch_is_verified_and_reaches_ready(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let (switch_id, _bmc_ip) =
+        create_switch_with_bmc(&env, "SWITCH-SN-1", "0a:0b:0c:0d:0e:01".parse().unwrap()).await?;
+
+    let switch =
+        run_switch_controller_until_state(&env, &switch_id, SwitchControllerState::Ready, 10)
+            .await?;
+
+    let status = switch.status.unwrap();
+    assert_eq!(status.power_state, "on");
+    assert_eq!(status.health_status, SwitchStatus::HEALTH_OK);
+    assert_eq!(status.serial_numbers, vec!["SWITCH-SN-1".to_string()]);
+    assert!(status.nmxm_health.is_none());
+    assert!(status.last_health_check.is_some());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_switch_with_unreachable_bmc_enters_error(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let (switch_id, bmc_ip) =
+        create_switch_with_bmc(&env, "SWITCH-SN-2", "0a:0b:0c:0d:0e:02".parse().unwrap()).await?;
+    env.redfish_sim.set_unreachable(&bmc_ip.to_string(), true);
+
+    // Initializing only validates the switch against its expected switch entry
+    run_switch_controller_until_state(&env, &switch_id, SwitchControllerState::FetchingData, 5)
+        .await?;
+    env.run_switch_controller_iteration().await;
+
+    let switch = db_switch::find_by_id(pool.acquire().await?.as_mut(), &switch_id)
+        .await?
+        .unwrap();
+    match switch.controller_state.value {
+        SwitchControllerState::Error { cause } => {
+            assert!(
+                cause.contains(&format!("Switch BMC {bmc_ip} is not reachable")),
+                "Unexpected cause: {cause}"
+            );
+        }
+        state => panic!("Switch should be in error state, but is in {state:?}"),
+    }
+    let status = switch.status.unwrap();
+    assert_eq!(status.health_status, SwitchStatus::HEALTH_CRITICAL);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_switch_failing_health_check_in_ready_enters_error(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool.clone()).await;
+    let (switch_id, bmc_ip) =
+        create_switch_with_bmc(&env, "SWITCH-SN-3", "0a:0b:0c:0d:0e:03".parse().unwrap()).await?;
+    run_switch_controller_until_state(&env, &switch_id, SwitchControllerState::Ready, 10).await?;
+
+    // The test environment uses a zero health check interval, which makes the
+    // next iteration re-probe the switch
+    env.redfish_sim.set_unreachable(&bmc_ip.to_string(), true);
+    env.run_switch_controller_iteration().await;
+
+    let switch = db_switch::find_by_id(pool.acquire().await?.as_mut(), &switch_id)
+        .await?
+        .unwrap();
+    match switch.controller_state.value {
+        SwitchControllerState::Error { cause } => {
+            assert!(
+                cause.contains(&format!("Switch BMC {bmc_ip} is not reachable")),
+                "Unexpected cause: {cause}"
+            );
+        }
+        state => panic!("Switch should be in error state, but is in {state:?}"),
+    }
+    assert_eq!(
+        switch.status.unwrap().health_status,
+        SwitchStatus::HEALTH_CRITICAL
+    );
+
+    Ok(())
+}
@@ -0,0 +1,496 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::IpAddr;
+
+use carbide_uuid::domain::DomainId;
+use carbide_uuid::network::NetworkSegmentId;
+use common::api_fixtures::network_segment::FIXTURE_TENANT_NETWORK_SEGMENT_GATEWAYS;
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use db::{ObjectColumnFilter, network_segment};
+use ipnetwork::IpNetwork;
+use mac_address::MacAddress;
+use model::address_selection_strategy::AddressSelectionStrategy;
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{NetworkPrefix, NetworkSegmentCreationRequest, NetworkSegmentType};
+use rpc::protos::dns::{
+    CreateDomainRequest, CreateTenantDnsRecordRequest, DeleteTenantDnsRecordRequest,
+    DnsResourceRecord, DnsResourceRecordLookupRequest, DomainSearchQuery,
+    FindTenantDnsRecordsRequest, TenantDnsRecord, UpdateTenantDnsRecordRequest,
+};
+
+use crate::tests::common;
+use crate::tests::common::rpc_builder::VpcCreationRequest;
+
+// Tenant of the VPC created by `create_vpc_and_tenant_segment`
+const TENANT: &str = "2829bbe3-c169-4cd9-8b2a-19a8b1618a93";
+const OTHER_TENANT: &str = "other-tenant";
+
+#[crate::sqlx_test]
+async fn test_tenant_dns_record_lifecycle(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (domain_id, _segment_id) = create_tenant_domain(&env, "tenant.example", TENANT, 0).await;
+
+    let cname = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "WWW.tenant.example",
+        "CNAME",
+        "web.tenant.example",
+    )
+    .await
+    .unwrap();
+    assert_eq!(cname.name, "www.tenant.example.");
+    assert_eq!(cname.content, "web.tenant.example.");
+    let txt = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "_acme.tenant.example",
+        "TXT",
+        "token \"1\"",
+    )
+    .await
+    .unwrap();
+    let srv = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "_http._tcp.tenant.example",
+        "srv",
+        "10 5 8080 web.tenant.example",
+    )
+    .await
+    .unwrap();
+    assert_eq!(srv.record_type, "SRV");
+
+    let records = lookup(&env, "www.tenant.example.", "CNAME").await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].qtype, "CNAME");
+    assert_eq!(records[0].content, "web.tenant.example.");
+    assert_eq!(records[0].ttl, 300);
+
+    let records = lookup(&env, "_acme.tenant.example.", "TXT").await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].content, r#""token \"1\"""#);
+
+    // Names which already have a CNAME can't hold other records
+    let err = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "www.tenant.example",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert!(err.message().contains("already exists"), "{err}");
+
+    // Records need to stay inside the domain
+    let err = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "www.example.com",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    // Tenants can only manage records in their own domains
+    let err = create_record(
+        &env,
+        domain_id,
+        OTHER_TENANT,
+        "x.tenant.example",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    let err = env
+        .api
+        .update_tenant_dns_record(tonic::Request::new(UpdateTenantDnsRecordRequest {
+            id: txt.id.clone(),
+            tenant_organization_id: OTHER_TENANT.to_string(),
+            content: "hijacked".to_string(),
+            ttl: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    let err = env
+        .api
+        .delete_tenant_dns_record(tonic::Request::new(DeleteTenantDnsRecordRequest {
+            id: txt.id.clone(),
+            tenant_organization_id: OTHER_TENANT.to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    let updated = env
+        .api
+        .update_tenant_dns_record(tonic::Request::new(UpdateTenantDnsRecordRequest {
+            id: txt.id.clone(),
+            tenant_organization_id: TENANT.to_string(),
+            content: "token 2".to_string(),
+            ttl: Some(60),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.content, "token 2");
+    let records = lookup(&env, "_acme.tenant.example.", "TXT").await;
+    assert_eq!(records[0].content, r#""token 2""#);
+    assert_eq!(records[0].ttl, 60);
+
+    assert_eq!(find_records(&env, TENANT).await.len(), 3);
+    assert!(find_records(&env, OTHER_TENANT).await.is_empty());
+
+    for record in [cname, txt, srv] {
+        env.api
+            .delete_tenant_dns_record(tonic::Request::new(DeleteTenantDnsRecordRequest {
+                id: record.id,
+                tenant_organization_id: TENANT.to_string(),
+            }))
+            .await
+            .unwrap();
+    }
+    assert!(find_records(&env, TENANT).await.is_empty());
+    assert!(
+        lookup(&env, "www.tenant.example.", "CNAME")
+            .await
+            .is_empty()
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_tenant_dns_record_ownership(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    // The site domain is shared with the admin segment
+    env.create_vpc_and_tenant_segment().await;
+    let site_domain_id = find_domain(&env, "dwrt1.com").await;
+    let err = create_record(
+        &env,
+        site_domain_id,
+        TENANT,
+        "www.dwrt1.com",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    // A domain used by segments of two tenants belongs to neither of them
+    let (shared_domain_id, _) = create_tenant_domain(&env, "shared.example", TENANT, 1).await;
+    create_tenant_segment(&env, shared_domain_id, OTHER_TENANT, 2).await;
+    for tenant in [TENANT, OTHER_TENANT] {
+        let err = create_record(
+            &env,
+            shared_domain_id,
+            tenant,
+            "www.shared.example",
+            "A",
+            "192.0.2.10",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    let (domain_id, segment_id) = create_tenant_domain(&env, "tenant.example", TENANT, 3).await;
+    let (other_domain_id, _) =
+        create_tenant_domain(&env, "other.tenant.example", OTHER_TENANT, 4).await;
+
+    // Names served by the site can't be taken over
+    let mut txn = env.pool.begin().await.unwrap();
+    let segment = db::network_segment::find_by(
+        txn.as_mut(),
+        ObjectColumnFilter::One(network_segment::IdColumn, &segment_id),
+        model::network_segment::NetworkSegmentSearchConfig::default(),
+    )
+    .await
+    .unwrap()
+    .remove(0);
+    let interface = db::machine_interface::create(
+        &mut txn,
+        &segment,
+        &MacAddress::new([0x02, 0, 0, 0, 0, 0x01]),
+        Some(domain_id),
+        true,
+        AddressSelectionStrategy::NextAvailableIp,
+    )
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+    let err = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        &format!("{}.tenant.example", interface.hostname),
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
+
+    // Names of another tenant can't be taken over, even from a parent domain
+    create_record(
+        &env,
+        other_domain_id,
+        OTHER_TENANT,
+        "www.other.tenant.example",
+        "A",
+        "192.0.2.20",
+    )
+    .await
+    .unwrap();
+    let err = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "www.other.tenant.example",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
+    let err = create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "api.other.tenant.example",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    // The tenant can still add several records to its own names
+    create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "www.tenant.example",
+        "A",
+        "192.0.2.10",
+    )
+    .await
+    .unwrap();
+    create_record(
+        &env,
+        domain_id,
+        TENANT,
+        "www.tenant.example",
+        "A",
+        "192.0.2.11",
+    )
+    .await
+    .unwrap();
+    assert_eq!(lookup(&env, "www.tenant.example.", "A").await.len(), 2);
+}
+
+#[crate::sqlx_test]
+async fn test_ptr_records_for_bmc_addresses(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let (host_id, _dpu_id) = create_managed_host(&env).await.into();
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let topologies = db::machine_topology::find_by_machine_ids(&mut txn, &[host_id])
+        .await
+        .unwrap();
+    txn.rollback().await.unwrap();
+    let bmc_ip: IpAddr = topologies.get(&host_id).unwrap()[0]
+        .topology()
+        .bmc_info
+        .ip
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let IpAddr::V4(bmc_ip) = bmc_ip else {
+        panic!("Expected an IPv4 BMC address");
+    };
+    let octets = bmc_ip.octets();
+    let reverse_name = format!(
+        "{}.{}.{}.{}.in-addr.arpa.",
+        octets[3], octets[2], octets[1], octets[0]
+    );
+
+    // We are not authoritative for the reverse zone yet
+    assert!(lookup(&env, &reverse_name, "PTR").await.is_empty());
+
+    let zone = env
+        .api
+        .create_domain(tonic::Request::new(CreateDomainRequest {
+            name: "in-addr.arpa".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let records = lookup(&env, &reverse_name, "PTR").await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].qtype, "PTR");
+    assert_eq!(records[0].qname, reverse_name);
+    assert_eq!(records[0].content, format!("{host_id}.bmc.dwrt1.com."));
+    assert_eq!(records[0].domain_id, Some(zone.id.unwrap().to_string()));
+}
+
+/// Creates a domain together with a segment using it in a new VPC of the tenant
+async fn create_tenant_domain(
+    env: &TestEnv,
+    name: &str,
+    tenant: &str,
+    segment_index: usize,
+) -> (DomainId, NetworkSegmentId) {
+    let domain_id = env
+        .api
+        .create_domain(tonic::Request::new(CreateDomainRequest {
+            name: name.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .id
+        .unwrap();
+    let segment_id = create_tenant_segment(env, domain_id, tenant, segment_index).await;
+    (domain_id, segment_id)
+}
+
+/// Creates a segment with the given subdomain in a new VPC of the tenant
+async fn create_tenant_segment(
+    env: &TestEnv,
+    domain_id: DomainId,
+    tenant: &str,
+    segment_index: usize,
+) -> NetworkSegmentId {
+    let vpc = env
+        .api
+        .create_vpc(
+            VpcCreationRequest::builder(format!("{tenant} vpc {segment_index}"), tenant)
+                .tonic_request(),
+        )
+        .await
+        .unwrap()
+        .into_inner();
+
+    let network = FIXTURE_TENANT_NETWORK_SEGMENT_GATEWAYS[segment_index];
+    let segment = env
+        .api
+        .create_network_segment(tonic::Request::new(NetworkSegmentCreationRequest {
+            id: None,
+            mtu: Some(1500),
+            name: format!("{tenant} segment {segment_index}"),
+            prefixes: vec![NetworkPrefix {
+                id: None,
+                prefix: IpNetwork::new(network.network(), network.prefix())
+                    .unwrap()
+                    .to_string(),
+                gateway: Some(network.ip().to_string()),
+                reserve_first: 3,
+                free_ip_count: 0,
+                svi_ip: None,
+            }],
+            subdomain_id: Some(domain_id),
+            vpc_id: vpc.id,
+            segment_type: NetworkSegmentType::Tenant as _,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Get the segment into ready state
+    env.run_network_segment_controller_iteration().await;
+    env.run_network_segment_controller_iteration().await;
+
+    segment.id.unwrap()
+}
+
+async fn find_domain(env: &TestEnv, name: &str) -> DomainId {
+    env.api
+        .find_domain(tonic::Request::new(DomainSearchQuery {
+            id: None,
+            name: Some(name.to_string()),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .domains
+        .first()
+        .and_then(|d| d.id)
+        .unwrap()
+}
+
+async fn create_record(
+    env: &TestEnv,
+    domain_id: DomainId,
+    tenant: &str,
+    name: &str,
+    record_type: &str,
+    content: &str,
+) -> Result<TenantDnsRecord, tonic::Status> {
+    env.api
+        .create_tenant_dns_record(tonic::Request::new(CreateTenantDnsRecordRequest {
+            domain_id: Some(domain_id),
+            tenant_organization_id: tenant.to_string(),
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            content: content.to_string(),
+            ttl: None,
+        }))
+        .await
+        .map(|response| response.into_inner())
+}
+
+async fn find_records(env: &TestEnv, tenant: &str) -> Vec<TenantDnsRecord> {
+    env.api
+        .find_tenant_dns_records(tonic::Request::new(FindTenantDnsRecordsRequest {
+            tenant_organization_id: tenant.to_string(),
+            domain_id: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .records
+}
+
+async fn lookup(env: &TestEnv, qname: &str, qtype: &str) -> Vec<DnsResourceRecord> {
+    env.api
+        .lookup_record(tonic::Request::new(DnsResourceRecordLookupRequest {
+            qtype: qtype.to_string(),
+            qname: qname.to_string(),
+            zone_id: uuid::Uuid::new_v4().to_string(),
+            local: None,
+            remote: None,
+            real_remote: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .records
+}
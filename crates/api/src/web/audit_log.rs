@@ -0,0 +1,156 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::Json;
+use axum::extract::{Query as AxumQuery, State as AxumState};
+use axum::response::{Html, IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use hyper::http::StatusCode;
+use rpc::forge as forgerpc;
+use rpc::forge::forge_server::Forge;
+use serde::Deserialize;
+
+use crate::api::Api;
+
+/// The amount of entries which are shown on the page
+const PAGE_RECORD_LIMIT: u32 = 200;
+
+#[derive(Template)]
+#[template(path = "audit_log.html")]
+struct AuditLog {
+    query: QueryParams,
+    entries: Vec<AuditLogEntryDisplay>,
+    error: Option<String>,
+}
+
+struct AuditLogEntryDisplay {
+    timestamp: String,
+    principal: String,
+    rpc_name: String,
+    object_ids: Vec<String>,
+    request: String,
+    outcome: String,
+    error: String,
+}
+
+impl From<forgerpc::AuditLogEntry> for AuditLogEntryDisplay {
+    fn from(entry: forgerpc::AuditLogEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+            outcome: entry.outcome().as_str_name().to_string(),
+            principal: entry.principal,
+            rpc_name: entry.rpc_name,
+            object_ids: entry.object_ids,
+            request: entry.request,
+            error: entry.error.unwrap_or_default(),
+        }
+    }
+}
+
+/// Filters of the audit log page. Times are expected in RFC 3339 format.
+#[derive(Debug, Default, Deserialize)]
+pub struct QueryParams {
+    #[serde(default)]
+    principal: String,
+    #[serde(default)]
+    object_id: String,
+    #[serde(default)]
+    rpc_name: String,
+    #[serde(default)]
+    start_time: String,
+    #[serde(default)]
+    end_time: String,
+}
+
+impl QueryParams {
+    fn to_request(&self) -> Result<forgerpc::FindAuditLogEntriesRequest, String> {
+        let non_empty = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let parse_time = |name: &str, value: &str| {
+            non_empty(value)
+                .map(|value| {
+                    DateTime::parse_from_rfc3339(&value)
+                        .map(|time| rpc::Timestamp::from(time.with_timezone(&Utc)))
+                        .map_err(|e| format!("Invalid {name} {value}: {e}"))
+                })
+                .transpose()
+        };
+
+        Ok(forgerpc::FindAuditLogEntriesRequest {
+            principal: non_empty(&self.principal),
+            object_id: non_empty(&self.object_id),
+            rpc_name: non_empty(&self.rpc_name),
+            start_time: parse_time("start time", &self.start_time)?,
+            end_time: parse_time("end time", &self.end_time)?,
+            limit: Some(PAGE_RECORD_LIMIT),
+        })
+    }
+}
+
+/// Shows the most recent audit log entries which match the filters in the
+/// query parameters
+pub async fn show_html(
+    AxumState(state): AxumState<Arc<Api>>,
+    AxumQuery(query): AxumQuery<QueryParams>,
+) -> Response {
+    let mut tmpl = AuditLog {
+        query,
+        entries: Vec::new(),
+        error: None,
+    };
+
+    match fetch_entries(&state, &tmpl.query).await {
+        Ok(entries) => {
+            tmpl.entries = entries.entries.into_iter().map(Into::into).collect();
+        }
+        Err(err) => {
+            tracing::error!(%err, "find_audit_log_entries");
+            tmpl.error = Some(err);
+        }
+    }
+
+    (StatusCode::OK, Html(tmpl.render().unwrap())).into_response()
+}
+
+pub async fn show_json(
+    AxumState(state): AxumState<Arc<Api>>,
+    AxumQuery(query): AxumQuery<QueryParams>,
+) -> Response {
+    match fetch_entries(&state, &query).await {
+        Ok(entries) => (StatusCode::OK, Json(entries)).into_response(),
+        Err(err) => {
+            tracing::error!(%err, "find_audit_log_entries");
+            (StatusCode::BAD_REQUEST, err).into_response()
+        }
+    }
+}
+
+async fn fetch_entries(
+    api: &Api,
+    query: &QueryParams,
+) -> Result<forgerpc::AuditLogEntries, String> {
+    let request = query.to_request()?;
+    api.find_audit_log_entries(tonic::Request::new(request))
+        .await
+        .map(|response| response.into_inner())
+        .map_err(|status| format!("Failed to load audit log: {}", status.message()))
+}
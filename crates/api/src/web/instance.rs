@@ -20,7 +20,7 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::Json;
-use axum::extract::{Path as AxumPath, State as AxumState};
+use axum::extract::{Path as AxumPath, Query as AxumQuery, State as AxumState};
 use axum::response::{Html, IntoResponse, Response};
 use carbide_uuid::network::NetworkSegmentId;
 use carbide_uuid::vpc::VpcId;
@@ -28,6 +28,7 @@ use forgerpc::NetworkSegment;
 use hyper::http::StatusCode;
 use rpc::forge as forgerpc;
 use rpc::forge::forge_server::Forge;
+use serde::Deserialize;
 
 use super::filters;
 use crate::api::Api;
@@ -229,6 +230,7 @@ struct InstanceDetail {
     nvlink_config_synced: String,
     nvlink_config_version: String,
     metadata: rpc::forge::Metadata,
+    console_available: bool,
 }
 
 #[derive(Default)]
@@ -438,6 +440,7 @@ impl From<forgerpc::Instance> for InstanceDetail {
                 .map(|state| format!("{state:?}"))
                 .unwrap_or_default(),
             nvlink_config_version: instance.nvlink_config_version,
+            console_available: false,
         }
     }
 }
@@ -618,5 +621,75 @@ pub async fn detail(
         .unwrap_or_else(|_| Vec::new());
     let mut instance_detail: InstanceDetail = instance.into();
     instance_detail.interfaces = instance_detail_interfaces;
+    instance_detail.console_available = state.runtime_config.console_access.websocket_url.is_some();
     (StatusCode::OK, Html(instance_detail.render().unwrap())).into_response()
 }
+
+#[derive(Template)]
+#[template(path = "instance_console.html")]
+struct InstanceConsole {
+    id: String,
+    console_url: String,
+    read_only: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConsoleParams {
+    #[serde(default)]
+    read_only: bool,
+}
+
+/// Attaches to the instance's serial console through ssh-console's WebSocket frontend
+pub async fn console(
+    AxumState(state): AxumState<Arc<Api>>,
+    AxumPath(instance_id_string): AxumPath<String>,
+    AxumQuery(params): AxumQuery<ConsoleParams>,
+) -> Response {
+    let instance_id = match instance_id_string.parse() {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid Instance ID {instance_id_string}: {e}"),
+            )
+                .into_response();
+        }
+    };
+
+    let request = tonic::Request::new(forgerpc::CreateConsoleAccessTokenRequest {
+        instance_id: Some(instance_id),
+        read_only: params.read_only,
+    });
+    let token = match state
+        .create_console_access_token(request)
+        .await
+        .map(|response| response.into_inner())
+    {
+        Ok(token) => token,
+        Err(err) if err.code() == tonic::Code::NotFound => {
+            return super::not_found_response(instance_id_string);
+        }
+        Err(err) => {
+            tracing::error!(%err, %instance_id, "create_console_access_token");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Error creating console access token",
+            )
+                .into_response();
+        }
+    };
+    let Some(websocket_url) = token.websocket_url else {
+        return (
+            StatusCode::NOT_FOUND,
+            "The WebSocket console is not configured for this site",
+        )
+            .into_response();
+    };
+
+    let tmpl = InstanceConsole {
+        id: instance_id_string,
+        console_url: format!("{websocket_url}?token={}", token.token),
+        read_only: params.read_only,
+    };
+    (StatusCode::OK, Html(tmpl.render().unwrap())).into_response()
+}
@@ -52,6 +52,7 @@ use crate::auth::{AuthContext, Principal};
 use crate::cfg::file::CarbideConfig;
 
 mod attestation;
+mod audit_log;
 mod auth;
 mod domain;
 mod dpa;
@@ -229,6 +230,8 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
         Router::new()
             .route("/", get(root))
             .route("/static/{filename}", get(static_data))
+            .route("/audit-log", get(audit_log::show_html))
+            .route("/audit-log.json", get(audit_log::show_json))
             .route("/domain", get(domain::show_html))
             .route("/domain.json", get(domain::show_all_json))
             .route("/dpa", get(dpa::show_dpas_html))
@@ -313,6 +316,7 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
             .route("/instance", get(instance::show_html))
             .route("/instance.json", get(instance::show_all_json))
             .route("/instance/{instance_id}", get(instance::detail))
+            .route("/instance/{instance_id}/console", get(instance::console))
             .route("/instance-type", get(instance_type::show))
             .route(
                 "/instance-type/{instance_type_id}",
@@ -410,6 +414,10 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
                 "/network-security-group",
                 post(network_security_group::create),
             )
+            .route(
+                "/network-security-group/explain",
+                get(network_security_group::explain),
+            )
             .route(
                 "/network-security-group/{network_security_group_id}",
                 get(network_security_group::show_detail),
@@ -112,6 +112,71 @@ struct NetworkSecurityGroupDetailDisplay {
     propagation: Vec<NetworkSecurityGroupPropagation>,
 }
 
+#[derive(Debug, Template)]
+#[template(path = "network_security_group_explain.html")]
+struct NetworkSecurityGroupExplainDisplay {
+    instance_id: String,
+    virtual_function_id: String,
+    direction: String,
+    protocol: String,
+    src_ip: String,
+    dst_ip: String,
+    src_port: String,
+    dst_port: String,
+    error: String,
+    explanation: Option<NetworkSecurityGroupExplanation>,
+}
+
+#[derive(Debug)]
+struct NetworkSecurityGroupExplanation {
+    action: String,
+    rule_source: String,
+    network_security_group_source: String,
+    network_security_group_id: String,
+    network_security_group_version: String,
+    stateful_egress: bool,
+    matched_rule_id: String,
+    matched_rule_priority: String,
+    matched_rule: String,
+}
+
+impl From<forgerpc::ExplainNetworkSecurityGroupFlowResponse> for NetworkSecurityGroupExplanation {
+    fn from(explanation: forgerpc::ExplainNetworkSecurityGroupFlowResponse) -> Self {
+        NetworkSecurityGroupExplanation {
+            action: explanation.action().as_str_name().to_string(),
+            rule_source: explanation.rule_source().as_str_name().to_string(),
+            network_security_group_source: explanation
+                .network_security_group_source()
+                .as_str_name()
+                .to_string(),
+            network_security_group_id: explanation
+                .network_security_group_id
+                .clone()
+                .unwrap_or_default(),
+            network_security_group_version: explanation
+                .network_security_group_version
+                .clone()
+                .unwrap_or_default(),
+            stateful_egress: explanation.stateful_egress,
+            matched_rule_id: explanation
+                .matched_rule
+                .as_ref()
+                .and_then(|r| r.id.clone())
+                .unwrap_or_default(),
+            matched_rule_priority: explanation
+                .matched_rule
+                .as_ref()
+                .map(|r| r.priority.to_string())
+                .unwrap_or_default(),
+            matched_rule: explanation
+                .matched_rule
+                .as_ref()
+                .and_then(|r| serde_json::to_string_pretty(r).ok())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 /// Serde deserialization decorator to map empty Strings to None,
 fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
 where
@@ -622,3 +687,90 @@ pub async fn delete(
 
     Redirect::to("/admin/network-security-group").into_response()
 }
+
+/// Struct for deserializing a request to explain
+/// which rule applies to a flow
+#[derive(Deserialize, Debug)]
+pub struct ExplainNetworkSecurityGroupFlowParams {
+    #[serde(default)]
+    instance_id: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    virtual_function_id: Option<u32>,
+    #[serde(default)]
+    direction: String,
+    #[serde(default)]
+    protocol: String,
+    #[serde(default)]
+    src_ip: String,
+    #[serde(default)]
+    dst_ip: String,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    src_port: Option<u32>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    dst_port: Option<u32>,
+}
+
+// Handler for explaining which NSG rule applies to a flow
+pub async fn explain(
+    AxumState(api): AxumState<Arc<Api>>,
+    Query(params): Query<ExplainNetworkSecurityGroupFlowParams>,
+) -> Response {
+    let mut tmpl = NetworkSecurityGroupExplainDisplay {
+        instance_id: params.instance_id.clone(),
+        virtual_function_id: params
+            .virtual_function_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        direction: params.direction.clone(),
+        protocol: params.protocol.clone(),
+        src_ip: params.src_ip.clone(),
+        dst_ip: params.dst_ip.clone(),
+        src_port: params.src_port.map(|p| p.to_string()).unwrap_or_default(),
+        dst_port: params.dst_port.map(|p| p.to_string()).unwrap_or_default(),
+        error: String::new(),
+        explanation: None,
+    };
+
+    // Only show the form until an instance has been submitted
+    if params.instance_id.is_empty() {
+        return (StatusCode::OK, Html(tmpl.render().unwrap())).into_response();
+    }
+
+    match explain_flow(&api, params).await {
+        Ok(explanation) => tmpl.explanation = Some(explanation.into()),
+        Err(e) => tmpl.error = e.message().to_string(),
+    }
+
+    (StatusCode::OK, Html(tmpl.render().unwrap())).into_response()
+}
+
+async fn explain_flow(
+    api: &Api,
+    params: ExplainNetworkSecurityGroupFlowParams,
+) -> Result<forgerpc::ExplainNetworkSecurityGroupFlowResponse, tonic::Status> {
+    let instance_id = params
+        .instance_id
+        .parse()
+        .map_err(|_| tonic::Status::invalid_argument("invalid instance ID"))?;
+
+    api.explain_network_security_group_flow(tonic::Request::new(
+        forgerpc::ExplainNetworkSecurityGroupFlowRequest {
+            instance_id: Some(instance_id),
+            virtual_function_id: params.virtual_function_id,
+            direction: forgerpc::NetworkSecurityGroupRuleDirection::from_str_name(
+                &params.direction,
+            )
+            .unwrap_or_default()
+            .into(),
+            protocol: forgerpc::NetworkSecurityGroupRuleProtocol::from_str_name(&params.protocol)
+                .unwrap_or_default()
+                .into(),
+            src_ip: params.src_ip,
+            dst_ip: params.dst_ip,
+            src_port: params.src_port,
+            dst_port: params.dst_port,
+        },
+    ))
+    .await
+    .map(|response| response.into_inner())
+}
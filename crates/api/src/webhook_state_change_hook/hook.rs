@@ -0,0 +1,539 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Webhook hook implementation for delivering state changes.
+
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use hmac::{Hmac, Mac};
+use opentelemetry::metrics::Meter;
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use crate::cfg::file::{StateChangeWebhookEndpointConfig, StateChangeWebhooksConfig};
+use crate::state_controller::state_change_emitter::{
+    PublishableState, StateChangeEvent, StateChangeHook,
+};
+use crate::webhook_state_change_hook::message::StateChangeWebhookMessage;
+use crate::webhook_state_change_hook::metrics::WebhookHookMetrics;
+
+/// Header which carries the HMAC-SHA256 signature of the timestamp and the
+/// request body
+pub const SIGNATURE_HEADER: &str = "X-Carbide-Signature";
+
+/// Header which carries the time the request was signed at, in seconds since
+/// the Unix epoch. Receivers reject requests with old timestamps, which makes
+/// captured requests useless for replays.
+pub const TIMESTAMP_HEADER: &str = "X-Carbide-Timestamp";
+
+/// Upper bound for the backoff between delivery attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Internal queue item containing a pre-serialized webhook payload.
+struct QueuedMessage {
+    object_type: &'static str,
+    object_id: String,
+    payload: Bytes,
+}
+
+/// Errors that can occur while delivering a webhook
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("Webhook request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Webhook endpoint responded with status {0}")]
+    Status(reqwest::StatusCode),
+}
+
+/// Trait for delivering webhook requests, enabling test mocks.
+#[async_trait::async_trait]
+pub trait WebhookPublisher: Send + Sync + 'static {
+    /// POST the JSON `payload` with the given timestamp and signature to `url`.
+    async fn post(
+        &self,
+        url: &str,
+        timestamp: i64,
+        signature: &str,
+        payload: Bytes,
+    ) -> Result<(), WebhookError>;
+}
+
+#[async_trait::async_trait]
+impl WebhookPublisher for reqwest::Client {
+    async fn post(
+        &self,
+        url: &str,
+        timestamp: i64,
+        signature: &str,
+        payload: Bytes,
+    ) -> Result<(), WebhookError> {
+        let response = reqwest::Client::post(self, url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(SIGNATURE_HEADER, signature)
+            .body(payload)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(WebhookError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: WebhookPublisher> WebhookPublisher for Arc<T> {
+    async fn post(
+        &self,
+        url: &str,
+        timestamp: i64,
+        signature: &str,
+        payload: Bytes,
+    ) -> Result<(), WebhookError> {
+        T::post(self, url, timestamp, signature, payload).await
+    }
+}
+
+/// Options for delivering state change events to webhook endpoints
+#[derive(Debug, Clone)]
+struct DeliveryOptions {
+    request_timeout: Duration,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+/// Webhook hook that POSTs state changes to all configured endpoints.
+///
+/// Messages follow the schema in `state_change_webhook.schema.json`.
+///
+/// This hook maintains an internal queue per endpoint, and each endpoint is served
+/// by its own background task. A slow or unavailable endpoint therefore does not
+/// delay deliveries to the other endpoints. If the queue of an endpoint is full,
+/// events for this endpoint are dropped and a warning is logged.
+/// Failed deliveries are retried with exponential backoff. Once all attempts
+/// for an endpoint are exhausted, the event is dead-lettered: It is logged
+/// and counted in `carbide_state_change_webhook_dead_letter_count`.
+///
+/// The hook can be registered for any [`PublishableState`]. Clones of the hook
+/// share the same queues, which allows registering a single hook with the
+/// emitters of all state controllers.
+#[derive(Clone)]
+pub struct WebhookStateChangeHook {
+    endpoints: Vec<EndpointQueue>,
+    metrics: WebhookHookMetrics,
+}
+
+/// The queue of messages which still need to be delivered to an endpoint
+#[derive(Clone)]
+struct EndpointQueue {
+    url: String,
+    sender: mpsc::Sender<QueuedMessage>,
+}
+
+impl WebhookStateChangeHook {
+    /// Create a new webhook state change hook.
+    ///
+    /// Spawns a background task per endpoint to process queued events.
+    /// Emits metrics:
+    /// - `carbide_state_change_webhook_delivery_count`: Total number of delivery attempts
+    /// - `carbide_state_change_webhook_dead_letter_count`: Events that could not be delivered
+    /// - `carbide_state_change_webhook_queue_depth`: Current queue depth of all endpoints
+    pub fn new<P: WebhookPublisher>(
+        publisher: P,
+        config: &StateChangeWebhooksConfig,
+        meter: &Meter,
+    ) -> Self {
+        let publisher = Arc::new(publisher);
+        let options = DeliveryOptions {
+            request_timeout: config.request_timeout,
+            max_attempts: config.max_attempts.max(1),
+            initial_backoff: config.initial_backoff,
+        };
+
+        let channels: Vec<_> = config
+            .endpoints
+            .iter()
+            .map(|endpoint| (endpoint, mpsc::channel(config.queue_capacity)))
+            .collect();
+        let metrics = WebhookHookMetrics::new(
+            meter,
+            channels
+                .iter()
+                .map(|(_, (sender, _))| sender.downgrade())
+                .collect(),
+        );
+
+        let mut endpoints = Vec::with_capacity(channels.len());
+        for (endpoint, (sender, receiver)) in channels {
+            tokio::spawn(process_events(
+                receiver,
+                publisher.clone(),
+                endpoint.clone(),
+                options.clone(),
+                metrics.clone(),
+            ));
+            endpoints.push(EndpointQueue {
+                url: endpoint.url.clone(),
+                sender,
+            });
+        }
+
+        Self { endpoints, metrics }
+    }
+}
+
+impl<Id, S> StateChangeHook<Id, S> for WebhookStateChangeHook
+where
+    Id: Display + Clone + Send + Sync + 'static,
+    S: PublishableState,
+{
+    fn on_state_changed(&self, event: &StateChangeEvent<'_, Id, S>) {
+        // Serialize immediately to avoid cloning state
+        let message = StateChangeWebhookMessage::new(event);
+
+        match message.to_json_bytes() {
+            Ok(payload) => {
+                let payload = Bytes::from(payload);
+                for endpoint in self.endpoints.iter() {
+                    let queued = QueuedMessage {
+                        object_type: S::OBJECT_TYPE,
+                        object_id: message.object_id.clone(),
+                        payload: payload.clone(),
+                    };
+                    if let Err(e) = endpoint.sender.try_send(queued) {
+                        tracing::warn!(
+                            url = %endpoint.url,
+                            "Webhook state change event dropped (queue full): {e}"
+                        );
+                        self.metrics.record_overflow();
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    object_type = S::OBJECT_TYPE,
+                    object_id = %event.object_id,
+                    error = %e,
+                    "Failed to serialize state change message"
+                );
+                self.metrics.record_serialization_error();
+            }
+        }
+    }
+}
+
+/// Computes the value of the [`SIGNATURE_HEADER`] for a request body sent at
+/// `timestamp`. The signed material is `<timestamp>.<body>`.
+pub fn sign_payload(secret: &str, timestamp: i64, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Background task that processes the queued messages of a single endpoint
+async fn process_events<P: WebhookPublisher>(
+    mut receiver: mpsc::Receiver<QueuedMessage>,
+    publisher: P,
+    endpoint: StateChangeWebhookEndpointConfig,
+    options: DeliveryOptions,
+    metrics: WebhookHookMetrics,
+) {
+    while let Some(msg) = receiver.recv().await {
+        deliver(&publisher, &endpoint, &msg, &options, &metrics).await;
+    }
+    tracing::debug!(url = %endpoint.url, "Webhook state change hook background task stopped");
+}
+
+/// Delivers a message to a single endpoint, retrying with exponential backoff
+async fn deliver<P: WebhookPublisher>(
+    publisher: &P,
+    endpoint: &StateChangeWebhookEndpointConfig,
+    msg: &QueuedMessage,
+    options: &DeliveryOptions,
+    metrics: &WebhookHookMetrics,
+) {
+    let mut backoff = options.initial_backoff;
+
+    for attempt in 1..=options.max_attempts {
+        // Every attempt is signed anew, so that retries carry a fresh timestamp
+        let timestamp = chrono::Utc::now().timestamp();
+        let signature = sign_payload(&endpoint.hmac_secret, timestamp, &msg.payload);
+        match timeout(
+            options.request_timeout,
+            publisher.post(&endpoint.url, timestamp, &signature, msg.payload.clone()),
+        )
+        .await
+        {
+            Ok(Ok(())) => {
+                tracing::debug!(
+                    url = %endpoint.url,
+                    object_id = %msg.object_id,
+                    "Delivered state change webhook"
+                );
+                metrics.record_success();
+                return;
+            }
+            Ok(Err(e)) => {
+                tracing::warn!(
+                    url = %endpoint.url,
+                    object_id = %msg.object_id,
+                    attempt,
+                    error = %e,
+                    "Failed to deliver state change webhook"
+                );
+                metrics.record_delivery_error();
+            }
+            Err(_) => {
+                tracing::warn!(
+                    url = %endpoint.url,
+                    object_id = %msg.object_id,
+                    attempt,
+                    "State change webhook delivery timed out"
+                );
+                metrics.record_timeout();
+            }
+        }
+
+        if attempt < options.max_attempts {
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    tracing::error!(
+        url = %endpoint.url,
+        object_type = msg.object_type,
+        object_id = %msg.object_id,
+        attempts = options.max_attempts,
+        "Giving up on state change webhook delivery"
+    );
+    metrics.record_dead_letter(msg.object_type);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use carbide_uuid::machine::{MachineId, MachineIdSource, MachineType};
+    use model::machine::ManagedHostState;
+    use opentelemetry::global;
+
+    use super::*;
+
+    const SECRET: &str = "webhook-secret";
+
+    fn test_meter() -> opentelemetry::metrics::Meter {
+        global::meter("test")
+    }
+
+    fn test_config(max_attempts: u32) -> StateChangeWebhooksConfig {
+        StateChangeWebhooksConfig {
+            enabled: true,
+            endpoints: vec![StateChangeWebhookEndpointConfig {
+                url: "https://hooks.example.com/carbide".to_string(),
+                hmac_secret: SECRET.to_string(),
+            }],
+            request_timeout: Duration::from_secs(1),
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            queue_capacity: 16,
+        }
+    }
+
+    fn emit_ready_state(hook: &WebhookStateChangeHook) {
+        let id = MachineId::new(
+            MachineIdSource::ProductBoardChassisSerial,
+            [0; 32],
+            MachineType::Host,
+        );
+        let state = ManagedHostState::Ready;
+        hook.on_state_changed(&StateChangeEvent {
+            object_id: &id,
+            previous_state: None,
+            new_state: &state,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    /// Publisher which fails the first `failures` requests and records all requests.
+    struct RecordingPublisher {
+        failures: usize,
+        attempts: AtomicUsize,
+        sender: mpsc::UnboundedSender<(String, i64, String, Bytes)>,
+    }
+
+    impl RecordingPublisher {
+        fn new(failures: usize) -> (Self, mpsc::UnboundedReceiver<(String, i64, String, Bytes)>) {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            let publisher = Self {
+                failures,
+                attempts: AtomicUsize::new(0),
+                sender,
+            };
+            (publisher, receiver)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl WebhookPublisher for RecordingPublisher {
+        async fn post(
+            &self,
+            url: &str,
+            timestamp: i64,
+            signature: &str,
+            payload: Bytes,
+        ) -> Result<(), WebhookError> {
+            let _ = self
+                .sender
+                .send((url.to_string(), timestamp, signature.to_string(), payload));
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(WebhookError::Status(
+                    reqwest::StatusCode::SERVICE_UNAVAILABLE,
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_delivered_with_signature() {
+        let (publisher, mut receiver) = RecordingPublisher::new(0);
+        let hook = WebhookStateChangeHook::new(publisher, &test_config(3), &test_meter());
+
+        emit_ready_state(&hook);
+
+        let (url, timestamp, signature, payload) =
+            receiver.recv().await.expect("should receive request");
+        assert_eq!(url, "https://hooks.example.com/carbide");
+        assert!((chrono::Utc::now().timestamp() - timestamp).abs() < 60);
+
+        let signature = hex::decode(signature.strip_prefix("sha256=").unwrap()).unwrap();
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("{timestamp}.").as_bytes());
+        mac.update(&payload);
+        mac.verify_slice(&signature)
+            .expect("signature should be valid");
+
+        // The timestamp is part of the signed material
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp - 600).as_bytes());
+        mac.update(&payload);
+        assert!(mac.verify_slice(&signature).is_err());
+
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed.get("object_type").unwrap(), "machine");
+        assert_eq!(
+            parsed.get("new_state").unwrap().get("state").unwrap(),
+            "ready"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_deliveries_are_retried() {
+        let (publisher, mut receiver) = RecordingPublisher::new(2);
+        let hook = WebhookStateChangeHook::new(publisher, &test_config(5), &test_meter());
+
+        emit_ready_state(&hook);
+        drop(hook);
+
+        // 2 failed attempts, followed by the successful one
+        let mut payloads = Vec::new();
+        while let Some((_, _, _, payload)) = receiver.recv().await {
+            payloads.push(payload);
+        }
+        assert_eq!(payloads.len(), 3);
+        assert!(payloads.iter().all(|payload| payload == &payloads[0]));
+    }
+
+    /// Publisher which never answers requests to `stalled_url`
+    struct StallingPublisher {
+        stalled_url: String,
+        sender: mpsc::UnboundedSender<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl WebhookPublisher for StallingPublisher {
+        async fn post(
+            &self,
+            url: &str,
+            _timestamp: i64,
+            _signature: &str,
+            _payload: Bytes,
+        ) -> Result<(), WebhookError> {
+            if url == self.stalled_url {
+                std::future::pending::<()>().await;
+            }
+            let _ = self.sender.send(url.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stalled_endpoint_does_not_delay_other_endpoints() {
+        let mut config = test_config(5);
+        config.request_timeout = Duration::from_secs(60);
+        config.endpoints.insert(
+            0,
+            StateChangeWebhookEndpointConfig {
+                url: "https://stalled.example.com/carbide".to_string(),
+                hmac_secret: SECRET.to_string(),
+            },
+        );
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let publisher = StallingPublisher {
+            stalled_url: "https://stalled.example.com/carbide".to_string(),
+            sender,
+        };
+        let hook = WebhookStateChangeHook::new(publisher, &config, &test_meter());
+
+        emit_ready_state(&hook);
+        emit_ready_state(&hook);
+
+        for _ in 0..2 {
+            let url = timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .expect("delivery should not wait for the stalled endpoint")
+                .expect("should receive request");
+            assert_eq!(url, "https://hooks.example.com/carbide");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_undeliverable_events_are_dead_lettered() {
+        let (publisher, mut receiver) = RecordingPublisher::new(usize::MAX);
+        let hook = WebhookStateChangeHook::new(publisher, &test_config(3), &test_meter());
+
+        emit_ready_state(&hook);
+        emit_ready_state(&hook);
+        drop(hook);
+
+        // Both events are attempted `max_attempts` times. Giving up on the
+        // first event does not prevent delivery of the second one.
+        let mut count = 0;
+        while receiver.recv().await.is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 6);
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Message types for the webhook state change hook.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::state_controller::state_change_emitter::{PublishableState, StateChangeEvent};
+
+/// Webhook payload for state changes of any [`PublishableState`].
+///
+/// Implements the schema in `state_change_webhook.schema.json`. States use
+/// their native serde serialization (lowercase state names).
+#[derive(Debug, Clone, Serialize)]
+pub struct StateChangeWebhookMessage<'a, S> {
+    /// The type of object whose state changed, e.g. `machine`.
+    pub object_type: &'static str,
+    /// Unique identifier of the object whose state changed.
+    pub object_id: String,
+    /// ISO 8601 timestamp of the state change.
+    pub timestamp: DateTime<Utc>,
+    /// The state before the transition, if known.
+    pub previous_state: Option<&'a S>,
+    /// The state after the transition.
+    pub new_state: &'a S,
+}
+
+impl<'a, S: PublishableState> StateChangeWebhookMessage<'a, S> {
+    /// Create a new message from the given state change event.
+    pub fn new<Id: Display>(event: &StateChangeEvent<'a, Id, S>) -> Self {
+        Self {
+            object_type: S::OBJECT_TYPE,
+            object_id: event.object_id.to_string(),
+            timestamp: event.timestamp,
+            previous_state: event.previous_state,
+            new_state: event.new_state,
+        }
+    }
+
+    /// Serialize the message to JSON bytes for the request body.
+    pub fn to_json_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_uuid::machine::MachineId;
+    use model::machine::{InstanceState, ManagedHostState};
+
+    use super::*;
+
+    #[allow(deprecated)]
+    fn test_machine_id() -> MachineId {
+        MachineId::default()
+    }
+
+    fn to_json(
+        machine_id: &MachineId,
+        previous_state: Option<&ManagedHostState>,
+        new_state: &ManagedHostState,
+    ) -> serde_json::Value {
+        let event = StateChangeEvent {
+            object_id: machine_id,
+            previous_state,
+            new_state,
+            timestamp: Utc::now(),
+        };
+        let json = StateChangeWebhookMessage::new(&event)
+            .to_json_bytes()
+            .unwrap();
+        serde_json::from_slice(&json).unwrap()
+    }
+
+    #[test]
+    fn test_message_serialization() {
+        let machine_id = test_machine_id();
+        let previous_state = ManagedHostState::Ready;
+        let new_state = ManagedHostState::Assigned {
+            instance_state: InstanceState::Ready,
+        };
+
+        let parsed = to_json(&machine_id, Some(&previous_state), &new_state);
+
+        assert_eq!(parsed.get("object_type").unwrap(), "machine");
+        assert_eq!(
+            parsed.get("object_id").unwrap().as_str().unwrap(),
+            machine_id.to_string()
+        );
+        assert_eq!(
+            parsed.get("previous_state").unwrap().get("state").unwrap(),
+            "ready"
+        );
+        let new_state = parsed.get("new_state").unwrap();
+        assert_eq!(new_state.get("state").unwrap(), "assigned");
+        assert!(new_state.get("instance_state").is_some());
+
+        let ts = parsed.get("timestamp").unwrap().as_str().unwrap();
+        chrono::DateTime::parse_from_rfc3339(ts).expect("timestamp should be RFC 3339");
+    }
+
+    #[test]
+    fn test_unknown_previous_state_is_null() {
+        let parsed = to_json(&test_machine_id(), None, &ManagedHostState::Ready);
+        assert!(parsed.get("previous_state").unwrap().is_null());
+    }
+
+    /// The message needs to contain all fields that the published schema requires
+    #[test]
+    fn test_message_matches_schema() {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("state_change_webhook.schema.json")).unwrap();
+        let parsed = to_json(&test_machine_id(), None, &ManagedHostState::Ready);
+
+        let properties = schema.get("properties").unwrap().as_object().unwrap();
+        let message = parsed.as_object().unwrap();
+        for field in schema.get("required").unwrap().as_array().unwrap() {
+            let field = field.as_str().unwrap();
+            assert!(message.contains_key(field), "Missing field {field}");
+        }
+        for field in message.keys() {
+            assert!(properties.contains_key(field), "Undocumented field {field}");
+        }
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Metrics for the webhook state change hook.
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Meter};
+use tokio::sync::mpsc::WeakSender;
+
+/// Metrics for the webhook state change hook.
+#[derive(Clone)]
+pub struct WebhookHookMetrics {
+    /// Counter for delivery attempts, with status label for success/error.
+    delivery_count: Counter<u64>,
+    /// Counter for events which could not be delivered to an endpoint
+    /// after all retries had been exhausted.
+    dead_letter_count: Counter<u64>,
+}
+
+impl WebhookHookMetrics {
+    /// Create new metrics instruments from the given meter.
+    ///
+    /// Uses weak references to the senders of the per endpoint queues to observe
+    /// the queue depth without preventing shutdown (when a sender is dropped, its
+    /// queue depth reports 0).
+    pub fn new<T: Send + 'static>(meter: &Meter, senders: Vec<WeakSender<T>>) -> Self {
+        let queues: Vec<(WeakSender<T>, usize)> = senders
+            .into_iter()
+            .map(|sender| {
+                let max_capacity = sender.upgrade().map(|s| s.max_capacity()).unwrap_or(0);
+                (sender, max_capacity)
+            })
+            .collect();
+
+        meter
+            .u64_observable_gauge("carbide_state_change_webhook_queue_depth")
+            .with_description(
+                "Number of state change messages currently queued for webhook delivery, summed over all endpoints",
+            )
+            .with_callback(move |observer| {
+                let depth: usize = queues
+                    .iter()
+                    .map(|(sender, max_capacity)| {
+                        sender
+                            .upgrade()
+                            .map(|s| max_capacity - s.capacity())
+                            .unwrap_or(0)
+                    })
+                    .sum();
+                observer.observe(depth as u64, &[]);
+            })
+            .build();
+
+        let delivery_count = meter
+            .u64_counter("carbide_state_change_webhook_delivery_count")
+            .with_description("Total number of webhook delivery attempts")
+            .build();
+
+        let dead_letter_count = meter
+            .u64_counter("carbide_state_change_webhook_dead_letter_count")
+            .with_description(
+                "Total number of state change messages which could not be delivered to a webhook endpoint",
+            )
+            .build();
+
+        Self {
+            delivery_count,
+            dead_letter_count,
+        }
+    }
+
+    /// Record a successful delivery.
+    pub fn record_success(&self) {
+        self.delivery_count.add(1, &[KeyValue::new("status", "ok")]);
+    }
+
+    /// Record that an event was dropped due to queue overflow.
+    pub fn record_overflow(&self) {
+        self.delivery_count
+            .add(1, &[KeyValue::new("status", "overflow")]);
+    }
+
+    /// Record a delivery attempt that timed out.
+    pub fn record_timeout(&self) {
+        self.delivery_count
+            .add(1, &[KeyValue::new("status", "timeout")]);
+    }
+
+    /// Record a delivery attempt that failed.
+    pub fn record_delivery_error(&self) {
+        self.delivery_count
+            .add(1, &[KeyValue::new("status", "delivery_error")]);
+    }
+
+    /// Record a serialization failure.
+    pub fn record_serialization_error(&self) {
+        self.delivery_count
+            .add(1, &[KeyValue::new("status", "serialization_error")]);
+    }
+
+    /// Record that an event was given up on for an endpoint.
+    pub fn record_dead_letter(&self, object_type: &'static str) {
+        self.dead_letter_count
+            .add(1, &[KeyValue::new("object_type", object_type)]);
+    }
+}
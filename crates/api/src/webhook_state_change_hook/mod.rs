@@ -0,0 +1,30 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Webhook state change hook for delivering state controller transitions via HTTP.
+//!
+//! Every state change is POSTed as a JSON document to all configured endpoints.
+//! The document format is described by the JSON schema in
+//! `state_change_webhook.schema.json`. Requests carry the time they were sent
+//! at in the `X-Carbide-Timestamp` header, in seconds since the Unix epoch.
+//! `<timestamp>.<request body>` is signed using HMAC-SHA256 with the
+//! per-endpoint secret, and the signature is transmitted in the
+//! `X-Carbide-Signature` header as `sha256=<hex encoded signature>`.
+
+pub mod hook;
+pub mod message;
+pub mod metrics;
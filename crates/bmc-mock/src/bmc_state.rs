@@ -20,6 +20,7 @@ use crate::bug::InjectedBugs;
 use crate::redfish;
 use crate::redfish::chassis::ChassisState;
 use crate::redfish::computer_system::SystemState;
+use crate::redfish::event_service::EventServiceState;
 use crate::redfish::manager::ManagerState;
 use crate::redfish::update_service::UpdateServiceState;
 
@@ -31,6 +32,7 @@ pub struct BmcState {
     pub system_state: Arc<SystemState>,
     pub chassis_state: Arc<ChassisState>,
     pub update_service_state: Arc<UpdateServiceState>,
+    pub event_service_state: Arc<EventServiceState>,
     pub injected_bugs: Arc<InjectedBugs>,
 }
 
@@ -68,6 +68,7 @@ impl Bluefield3<'_> {
                     serial_number: Some(self.product_serial_number.to_string().into()),
                     sensors: None,
                     assembly: None,
+                    power: None,
                     oem: None,
                 },
                 redfish::chassis::SingleChassisConfig {
@@ -81,6 +82,7 @@ impl Bluefield3<'_> {
                     serial_number: Some("".into()),
                     sensors: None,
                     assembly: None,
+                    power: None,
                     oem: None,
                 },
                 redfish::chassis::SingleChassisConfig {
@@ -94,6 +96,7 @@ impl Bluefield3<'_> {
                     pcie_devices: Some(vec![]),
                     sensors: None,
                     assembly: None,
+                    power: None,
                     oem: None,
                 },
                 redfish::chassis::SingleChassisConfig {
@@ -110,6 +113,7 @@ impl Bluefield3<'_> {
                         Self::sensor_layout(),
                     )),
                     assembly: None,
+                    power: None,
                     oem: None,
                 },
             ],
@@ -29,3 +29,6 @@ pub mod dell_poweredge_r750;
 
 /// Support of Wiwynn GB200 NVL servers.
 pub mod wiwynn_gb200_nvl;
+
+/// Support of NVIDIA power shelves.
+pub mod nvidia_power_shelf;
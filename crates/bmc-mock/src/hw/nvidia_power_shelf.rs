@@ -0,0 +1,261 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use mac_address::MacAddress;
+
+use crate::{PowerControl, redfish};
+
+/// Chassis and system ID used by power shelves. Site explorer identifies
+/// power shelves by this chassis ID.
+pub const POWER_SHELF_ID: &str = "powershelf";
+
+pub struct NvidiaPowerShelf<'a> {
+    pub bmc_mac_address: MacAddress,
+    pub product_serial_number: Cow<'a, str>,
+    pub firmware_version: Cow<'a, str>,
+    pub power_supplies: Vec<PowerSupply<'a>>,
+}
+
+pub struct PowerSupply<'a> {
+    pub serial_number: Cow<'a, str>,
+    pub firmware_version: Cow<'a, str>,
+    pub healthy: bool,
+    pub output_watts: f64,
+}
+
+impl NvidiaPowerShelf<'_> {
+    /// Capacity of a single PSU of the shelf.
+    const PSU_CAPACITY_WATTS: f64 = 5500.0;
+
+    pub fn manager_config(&self) -> redfish::manager::Config {
+        redfish::manager::Config {
+            managers: vec![redfish::manager::SingleConfig {
+                id: "BMC_0",
+                eth_interfaces: vec![
+                    redfish::ethernet_interface::builder(
+                        &redfish::ethernet_interface::manager_resource("BMC_0", "eth0"),
+                    )
+                    .mac_address(self.bmc_mac_address)
+                    .interface_enabled(true)
+                    .build(),
+                ],
+                firmware_version: "PMC-01.04.00",
+            }],
+        }
+    }
+
+    pub fn system_config(&self, pc: Arc<dyn PowerControl>) -> redfish::computer_system::Config {
+        redfish::computer_system::Config {
+            systems: vec![redfish::computer_system::SingleSystemConfig {
+                id: POWER_SHELF_ID.into(),
+                manufacturer: Some("NVIDIA".into()),
+                model: Some("PowerShelf".into()),
+                eth_interfaces: Some(vec![]),
+                serial_number: Some(self.product_serial_number.to_string().into()),
+                boot_order_mode: redfish::computer_system::BootOrderMode::Generic,
+                power_control: Some(pc),
+                chassis: vec![POWER_SHELF_ID.into()],
+                boot_options: Some(vec![]),
+                bios_mode: redfish::computer_system::BiosMode::Generic,
+                oem: redfish::computer_system::Oem::Generic,
+                base_bios: None,
+                log_services: None,
+            }],
+        }
+    }
+
+    pub fn chassis_config(&self) -> redfish::chassis::ChassisConfig {
+        redfish::chassis::ChassisConfig {
+            chassis: vec![redfish::chassis::SingleChassisConfig {
+                id: POWER_SHELF_ID.into(),
+                chassis_type: "PowerEquipment".into(),
+                manufacturer: Some("NVIDIA".into()),
+                part_number: Some("699-1U030-0000-000".into()),
+                model: Some("PowerShelf".into()),
+                serial_number: Some(self.product_serial_number.to_string().into()),
+                network_adapters: None,
+                pcie_devices: None,
+                sensors: None,
+                assembly: None,
+                power: Some(self.power()),
+                oem: None,
+            }],
+        }
+    }
+
+    pub fn update_service_config(&self) -> redfish::update_service::UpdateServiceConfig {
+        let fw_inv_builder = |id: &str| {
+            redfish::software_inventory::builder(
+                &redfish::software_inventory::firmware_inventory_resource(id),
+            )
+        };
+        redfish::update_service::UpdateServiceConfig {
+            firmware_inventory: [fw_inv_builder("PMC_Firmware").version(&self.firmware_version)]
+                .into_iter()
+                .chain(self.power_supplies.iter().enumerate().map(|(index, psu)| {
+                    fw_inv_builder(&format!("PSU{index}_Firmware")).version(&psu.firmware_version)
+                }))
+                .map(|b| b.build())
+                .collect(),
+        }
+    }
+
+    /// The shelf runs in N+N redundancy: Half of the PSUs are able to
+    /// carry the full load.
+    fn power(&self) -> serde_json::Value {
+        let total = self.power_supplies.len();
+        let healthy = self.power_supplies.iter().filter(|psu| psu.healthy).count();
+        let min_num_needed = total.div_ceil(2);
+        let redundancy_health = if healthy == total {
+            "OK"
+        } else if healthy >= min_num_needed {
+            "Warning"
+        } else {
+            "Critical"
+        };
+
+        self.power_supplies
+            .iter()
+            .enumerate()
+            .fold(
+                redfish::power::builder(&redfish::power::chassis_resource(POWER_SHELF_ID)),
+                |builder, (index, psu)| {
+                    let (health, output_watts) = if psu.healthy {
+                        ("OK", psu.output_watts)
+                    } else {
+                        ("Critical", 0.0)
+                    };
+                    builder.add_power_supply(
+                        redfish::power::power_supply_builder(index.to_string().into())
+                            .name(&format!("PSU{index}"))
+                            .manufacturer("NVIDIA")
+                            .model("PSU-5500W")
+                            .serial_number(&psu.serial_number)
+                            .firmware_version(&psu.firmware_version)
+                            .power_capacity_watts(Self::PSU_CAPACITY_WATTS)
+                            .last_power_output_watts(output_watts)
+                            .status("Enabled", health)
+                            .build(),
+                    )
+                },
+            )
+            .add_redundancy(
+                redfish::power::redundancy_builder("0".into())
+                    .mode("N+m")
+                    .min_num_needed(min_num_needed)
+                    .max_num_supported(total)
+                    .status("Enabled", redundancy_health)
+                    .build(),
+            )
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use serde_json::Value;
+    use tower::Service;
+
+    use crate::*;
+
+    #[derive(Debug)]
+    struct TestPowerControl {}
+
+    impl PowerControl for TestPowerControl {
+        fn get_power_state(&self) -> MockPowerState {
+            MockPowerState::On
+        }
+        fn send_power_command(&self, _: SystemPowerControl) -> Result<(), SetSystemPowerError> {
+            Ok(())
+        }
+    }
+
+    async fn get_json(router: &mut axum::Router, uri: &str) -> Value {
+        let body = router
+            .call(
+                Request::builder()
+                    .uri(uri)
+                    .method(Method::GET)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .into_body();
+        serde_json::from_slice(
+            axum::body::to_bytes(body, usize::MAX)
+                .await
+                .unwrap()
+                .as_ref(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_power_shelf_power_supplies() {
+        let mut info = PowerShelfInfo::new(6);
+        info.power_supplies[2].healthy = false;
+        let mut router = power_shelf_router(
+            info.clone(),
+            Arc::new(TestPowerControl {}),
+            String::default(),
+        );
+
+        let chassis = get_json(&mut router, "/redfish/v1/Chassis/powershelf").await;
+        assert_eq!(chassis["SerialNumber"], Value::from(info.serial.as_str()));
+        assert_eq!(
+            chassis["Power"]["@odata.id"],
+            Value::from("/redfish/v1/Chassis/powershelf/Power")
+        );
+
+        let power = get_json(&mut router, "/redfish/v1/Chassis/powershelf/Power").await;
+        let Some(Value::Array(power_supplies)) = power.get("PowerSupplies") else {
+            panic!("No PowerSupplies array in {power:?}")
+        };
+        assert_eq!(power_supplies.len(), 6);
+        assert_eq!(power_supplies[0]["Status"]["Health"], Value::from("OK"));
+        assert_eq!(
+            power_supplies[2]["Status"]["Health"],
+            Value::from("Critical")
+        );
+        assert_eq!(
+            power_supplies[2]["SerialNumber"],
+            Value::from(info.power_supplies[2].serial.as_str())
+        );
+        assert_eq!(power["Redundancy"][0]["MinNumNeeded"], Value::from(3));
+        assert_eq!(
+            power["Redundancy"][0]["Status"]["Health"],
+            Value::from("Warning")
+        );
+
+        let firmware = get_json(
+            &mut router,
+            "/redfish/v1/UpdateService/FirmwareInventory/PMC_Firmware",
+        )
+        .await;
+        assert_eq!(
+            firmware["Version"],
+            Value::from(info.firmware_version.as_str())
+        );
+    }
+}
@@ -134,6 +134,7 @@ impl WiwynnGB200Nvl<'_> {
                 pcie_devices: Some(vec![]),
                 sensors: None,
                 assembly: None,
+                power: None,
                 oem: None,
             }
         };
@@ -148,6 +149,7 @@ impl WiwynnGB200Nvl<'_> {
             pcie_devices: Some(vec![]),
             sensors: None,
             assembly: None,
+            power: None,
             oem: Some(json!({
                 "Nvidia": {
                     "@odata.type": "#NvidiaChassis.v1_4_0.NvidiaCBCChassis",
@@ -172,6 +174,7 @@ impl WiwynnGB200Nvl<'_> {
                     pcie_devices: Some(vec![]),
                     sensors: None,
                     assembly: None,
+                    power: None,
                     oem: None,
                 },
                 redfish::chassis::SingleChassisConfig {
@@ -195,6 +198,7 @@ impl WiwynnGB200Nvl<'_> {
                         )
                         .build(),
                     ),
+                    power: None,
                     oem: None,
                 },
                 cbc_chassis("CBC_0"),
@@ -35,9 +35,12 @@ mod redfish;
 pub mod tls;
 
 pub use combined_server::{CombinedServer, ListenerOrAddress};
-pub use machine_info::{DpuFirmwareVersions, DpuMachineInfo, HostMachineInfo, MachineInfo};
+pub use machine_info::{
+    DpuFirmwareVersions, DpuMachineInfo, HostMachineInfo, MachineInfo, PowerShelfInfo,
+    PowerSupplyInfo,
+};
 pub use mock_machine_router::{
-    BmcCommand, SetSystemPowerError, SetSystemPowerResult, machine_router,
+    BmcCommand, SetSystemPowerError, SetSystemPowerResult, machine_router, power_shelf_router,
 };
 
 #[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
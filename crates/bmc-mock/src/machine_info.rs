@@ -55,6 +55,24 @@ pub struct DpuMachineInfo {
     pub firmware_versions: DpuFirmwareVersions,
 }
 
+/// Static information about a power shelf. Power shelves are not machines,
+/// so they are served by `power_shelf_router` instead of `machine_router`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerShelfInfo {
+    pub bmc_mac_address: MacAddress,
+    pub serial: String,
+    pub firmware_version: String,
+    pub power_supplies: Vec<PowerSupplyInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSupplyInfo {
+    pub serial: String,
+    pub firmware_version: String,
+    pub healthy: bool,
+    pub output_watts: f64,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DpuFirmwareVersions {
     pub bmc: Option<String>,
@@ -237,6 +255,45 @@ impl HostMachineInfo {
     }
 }
 
+impl PowerShelfInfo {
+    pub fn new(power_supply_count: usize) -> Self {
+        let bmc_mac_address = next_mac();
+        let serial = format!("PS{}", bmc_mac_address.to_string().replace(':', ""));
+        let power_supplies = (0..power_supply_count)
+            .map(|index| PowerSupplyInfo {
+                serial: format!("{serial}-PSU{index}"),
+                firmware_version: "02.01.05".to_string(),
+                healthy: true,
+                output_watts: 1200.0,
+            })
+            .collect();
+        Self {
+            bmc_mac_address,
+            serial,
+            firmware_version: "01.04.00".to_string(),
+            power_supplies,
+        }
+    }
+
+    pub fn nvidia_power_shelf(&self) -> hw::nvidia_power_shelf::NvidiaPowerShelf<'_> {
+        hw::nvidia_power_shelf::NvidiaPowerShelf {
+            bmc_mac_address: self.bmc_mac_address,
+            product_serial_number: Cow::Borrowed(&self.serial),
+            firmware_version: Cow::Borrowed(&self.firmware_version),
+            power_supplies: self
+                .power_supplies
+                .iter()
+                .map(|psu| hw::nvidia_power_shelf::PowerSupply {
+                    serial_number: Cow::Borrowed(&psu.serial),
+                    firmware_version: Cow::Borrowed(&psu.firmware_version),
+                    healthy: psu.healthy,
+                    output_watts: psu.output_watts,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl MachineInfo {
     pub fn oem_state(&self) -> redfish::oem::State {
         match self {
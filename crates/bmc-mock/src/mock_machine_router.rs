@@ -27,7 +27,10 @@ use crate::bmc_state::BmcState;
 use crate::bug::InjectedBugs;
 use crate::json::JsonExt;
 use crate::redfish::manager::ManagerState;
-use crate::{MachineInfo, PowerControl, SystemPowerControl, middleware_router, redfish};
+use crate::redfish::update_service::UpdateServiceConfig;
+use crate::{
+    MachineInfo, PowerControl, PowerShelfInfo, SystemPowerControl, middleware_router, redfish,
+};
 
 #[derive(Debug)]
 pub enum BmcCommand {
@@ -66,12 +69,61 @@ pub fn machine_router(
     power_control: Arc<dyn PowerControl>,
     mat_host_id: String,
 ) -> Router {
-    let system_config = machine_info.system_config(power_control);
-    let chassis_config = machine_info.chassis_config();
-    let update_service_config = machine_info.update_service_config();
     let bmc_vendor = machine_info.bmc_vendor();
-    let oem_state = machine_info.oem_state();
-    let router = Router::new()
+    let router = base_router(bmc_vendor);
+    let router = match &machine_info {
+        MachineInfo::Dpu(_) => {
+            router.add_routes(crate::redfish::oem::nvidia::bluefield::add_routes)
+        }
+        MachineInfo::Host(_) => router.add_routes(crate::redfish::oem::dell::idrac::add_routes),
+    };
+    finish_router(
+        router,
+        BmcConfig {
+            bmc_vendor,
+            oem_state: machine_info.oem_state(),
+            manager_config: machine_info.manager_config(),
+            system_config: machine_info.system_config(power_control),
+            chassis_config: machine_info.chassis_config(),
+            update_service_config: machine_info.update_service_config(),
+        },
+        mat_host_id,
+    )
+}
+
+/// Return an axum::Router that mocks the redfish service of a power shelf.
+pub fn power_shelf_router(
+    power_shelf_info: PowerShelfInfo,
+    power_control: Arc<dyn PowerControl>,
+    mat_host_id: String,
+) -> Router {
+    let power_shelf = power_shelf_info.nvidia_power_shelf();
+    let bmc_vendor = redfish::oem::BmcVendor::Nvidia;
+    finish_router(
+        base_router(bmc_vendor),
+        BmcConfig {
+            bmc_vendor,
+            oem_state: redfish::oem::State::Other,
+            manager_config: power_shelf.manager_config(),
+            system_config: power_shelf.system_config(power_control),
+            chassis_config: power_shelf.chassis_config(),
+            update_service_config: power_shelf.update_service_config(),
+        },
+        mat_host_id,
+    )
+}
+
+struct BmcConfig {
+    bmc_vendor: redfish::oem::BmcVendor,
+    oem_state: redfish::oem::State,
+    manager_config: redfish::manager::Config,
+    system_config: redfish::computer_system::Config,
+    chassis_config: redfish::chassis::ChassisConfig,
+    update_service_config: UpdateServiceConfig,
+}
+
+fn base_router(bmc_vendor: redfish::oem::BmcVendor) -> Router<BmcState> {
+    Router::new()
         // Couple routes for bug injection.
         .route(
             "/InjectedBugs",
@@ -81,33 +133,34 @@ pub fn machine_router(
         .add_routes(crate::redfish::chassis::add_routes)
         .add_routes(crate::redfish::manager::add_routes)
         .add_routes(crate::redfish::update_service::add_routes)
+        .add_routes(crate::redfish::event_service::add_routes)
         .add_routes(crate::redfish::task_service::add_routes)
         .add_routes(crate::redfish::account_service::add_routes)
-        .add_routes(|routes| crate::redfish::computer_system::add_routes(routes, bmc_vendor));
-    let router = match &machine_info {
-        MachineInfo::Dpu(_) => {
-            router.add_routes(crate::redfish::oem::nvidia::bluefield::add_routes)
-        }
-        MachineInfo::Host(_) => router.add_routes(crate::redfish::oem::dell::idrac::add_routes),
-    };
-    let manager = Arc::new(ManagerState::new(&machine_info.manager_config()));
+        .add_routes(|routes| crate::redfish::computer_system::add_routes(routes, bmc_vendor))
+}
+
+fn finish_router(router: Router<BmcState>, config: BmcConfig, mat_host_id: String) -> Router {
+    let manager = Arc::new(ManagerState::new(&config.manager_config));
     let system_state = Arc::new(crate::redfish::computer_system::SystemState::from_config(
-        system_config,
+        config.system_config,
     ));
     let chassis_state = Arc::new(crate::redfish::chassis::ChassisState::from_config(
-        chassis_config,
+        config.chassis_config,
     ));
     let update_service_state = Arc::new(
-        crate::redfish::update_service::UpdateServiceState::from_config(update_service_config),
+        crate::redfish::update_service::UpdateServiceState::from_config(
+            config.update_service_config,
+        ),
     );
     let injected_bugs = Arc::new(InjectedBugs::default());
     let router = router.with_state(BmcState {
-        bmc_vendor,
-        oem_state,
+        bmc_vendor: config.bmc_vendor,
+        oem_state: config.oem_state,
         manager,
         system_state,
         chassis_state,
         update_service_state,
+        event_service_state: Arc::default(),
         injected_bugs: injected_bugs.clone(),
     });
     let router_with_expansion = redfish::expander_router::append(router);
@@ -103,6 +103,10 @@ pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
             &redfish::assembly::chassis_resource(CHASSIS_ID).odata_id,
             get(get_chassis_assembly),
         )
+        .route(
+            &redfish::power::chassis_resource(CHASSIS_ID).odata_id,
+            get(get_chassis_power),
+        )
 }
 
 pub struct SingleChassisConfig {
@@ -116,6 +120,7 @@ pub struct SingleChassisConfig {
     pub sensors: Option<Vec<redfish::sensor::Sensor>>,
     pub chassis_type: Cow<'static, str>,
     pub assembly: Option<serde_json::Value>,
+    pub power: Option<serde_json::Value>,
     pub oem: Option<serde_json::Value>,
 }
 
@@ -219,9 +224,15 @@ async fn get_chassis(State(state): State<BmcState>, Path(chassis_id): Path<Strin
         .is_some()
         .then_some(redfish::assembly::chassis_resource(&chassis_id));
 
+    let power = config
+        .power
+        .is_some()
+        .then_some(redfish::power::chassis_resource(&chassis_id));
+
     let mut b = builder(&resource(&chassis_id))
         .chassis_type(&config.chassis_type)
         .maybe_with(ChassisBuilder::assembly, &assembly)
+        .maybe_with(ChassisBuilder::power, &power)
         .maybe_with(ChassisBuilder::pcie_devices, &pcie_devices)
         .maybe_with(ChassisBuilder::network_adapters, &network_adapters)
         .maybe_with(ChassisBuilder::sensors, &sensors)
@@ -408,6 +419,18 @@ async fn get_chassis_assembly(
         .unwrap_or_else(http::not_found)
 }
 
+async fn get_chassis_power(
+    State(state): State<BmcState>,
+    Path(chassis_id): Path<String>,
+) -> Response {
+    state
+        .chassis_state
+        .find(&chassis_id)
+        .and_then(|chassis_state| chassis_state.config.power.clone())
+        .map(|power| power.into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
 pub struct ChassisBuilder {
     value: serde_json::Value,
 }
@@ -445,6 +468,10 @@ impl ChassisBuilder {
         self.apply_patch(v.nav_property("Assembly"))
     }
 
+    pub fn power(self, v: &redfish::Resource<'_>) -> Self {
+        self.apply_patch(v.nav_property("Power"))
+    }
+
     pub fn network_adapters(self, v: &redfish::Collection<'_>) -> Self {
         self.apply_patch(v.nav_property("NetworkAdapters"))
     }
@@ -0,0 +1,427 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::borrow::Cow;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Json, Path, State};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::routing::{get, post};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::bmc_state::BmcState;
+use crate::json::{JsonExt, JsonPatch};
+use crate::{http, redfish};
+
+pub fn resource<'a>() -> redfish::Resource<'a> {
+    redfish::Resource {
+        odata_id: Cow::Borrowed("/redfish/v1/EventService"),
+        odata_type: Cow::Borrowed("#EventService.v1_7_2.EventService"),
+        id: Cow::Borrowed("EventService"),
+        name: Cow::Borrowed("Event Service"),
+    }
+}
+
+pub fn subscriptions_collection() -> redfish::Collection<'static> {
+    redfish::Collection {
+        odata_id: Cow::Borrowed("/redfish/v1/EventService/Subscriptions"),
+        odata_type: Cow::Borrowed("#EventDestinationCollection.EventDestinationCollection"),
+        name: Cow::Borrowed("Event Subscriptions Collection"),
+    }
+}
+
+pub fn subscription_resource(id: &str) -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Owned(format!("{}/{id}", subscriptions_collection().odata_id)),
+        odata_type: Cow::Borrowed("#EventDestination.v1_10_0.EventDestination"),
+        id: Cow::Owned(id.to_string()),
+        name: Cow::Borrowed("Event Subscription"),
+    }
+}
+
+pub fn submit_test_event_target() -> String {
+    format!(
+        "{}/Actions/EventService.SubmitTestEvent",
+        resource().odata_id
+    )
+}
+
+pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
+    r.route(&resource().odata_id, get(get_event_service))
+        .route(
+            &subscriptions_collection().odata_id,
+            get(get_subscriptions).post(create_subscription),
+        )
+        .route(
+            &subscription_resource("{subscription_id}").odata_id,
+            get(get_subscription).delete(delete_subscription),
+        )
+        .route(&submit_test_event_target(), post(submit_test_event))
+}
+
+#[derive(Clone, Debug)]
+struct Subscription {
+    id: String,
+    destination: String,
+    context: Option<String>,
+    protocol: String,
+}
+
+impl Subscription {
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "Destination": self.destination,
+            "Context": self.context,
+            "Protocol": self.protocol,
+            "SubscriptionType": "RedfishEvent",
+        })
+        .patch(subscription_resource(&self.id))
+    }
+}
+
+/// Subscriptions of the mocked BMC. Events are delivered to every
+/// subscription when they are submitted via `EventService.SubmitTestEvent`.
+pub struct EventServiceState {
+    subscriptions: Mutex<Vec<Subscription>>,
+    next_id: AtomicU64,
+    client: reqwest::Client,
+}
+
+impl Default for EventServiceState {
+    fn default() -> Self {
+        Self {
+            subscriptions: Mutex::default(),
+            next_id: AtomicU64::new(1),
+            // Receivers typically use self signed certificates
+            client: reqwest::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .expect("BUG: could not build event delivery client"),
+        }
+    }
+}
+
+impl EventServiceState {
+    fn subscriptions(&self) -> Vec<Subscription> {
+        self.subscriptions.lock().unwrap().clone()
+    }
+}
+
+async fn get_event_service() -> Response {
+    json!({
+        "ServiceEnabled": true,
+        "DeliveryRetryAttempts": 3,
+        "DeliveryRetryIntervalSeconds": 60,
+        "EventFormatTypes": ["Event"],
+        "RegistryPrefixes": ["Base", "EventLog", "SensorEvent"],
+        "Status": { "State": "Enabled", "Health": "OK" },
+        "Actions": {
+            "#EventService.SubmitTestEvent": {
+                "target": submit_test_event_target(),
+            }
+        },
+    })
+    .patch(resource())
+    .patch(subscriptions_collection().nav_property("Subscriptions"))
+    .into_ok_response()
+}
+
+async fn get_subscriptions(State(state): State<BmcState>) -> Response {
+    let members = state
+        .event_service_state
+        .subscriptions()
+        .iter()
+        .map(|s| subscription_resource(&s.id).entity_ref())
+        .collect::<Vec<_>>();
+    subscriptions_collection()
+        .with_members(&members)
+        .into_ok_response()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CreateSubscriptionRequest {
+    destination: String,
+    context: Option<String>,
+    protocol: Option<String>,
+}
+
+async fn create_subscription(
+    State(state): State<BmcState>,
+    Json(request): Json<CreateSubscriptionRequest>,
+) -> Response {
+    let protocol = request.protocol.unwrap_or_else(|| "Redfish".to_string());
+    if protocol != "Redfish" {
+        return json!({"error": format!("unsupported protocol {protocol}")})
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let event_service = &state.event_service_state;
+    let subscription = Subscription {
+        id: event_service
+            .next_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string(),
+        destination: request.destination,
+        context: request.context,
+        protocol,
+    };
+    event_service
+        .subscriptions
+        .lock()
+        .unwrap()
+        .push(subscription.clone());
+
+    let location = subscription_resource(&subscription.id).odata_id;
+    let mut response = subscription.to_json().into_response(StatusCode::CREATED);
+    response.headers_mut().insert(
+        "Location",
+        HeaderValue::from_str(&location).expect("BUG: invalid subscription location"),
+    );
+    response
+}
+
+async fn get_subscription(
+    State(state): State<BmcState>,
+    Path(subscription_id): Path<String>,
+) -> Response {
+    state
+        .event_service_state
+        .subscriptions()
+        .iter()
+        .find(|s| s.id == subscription_id)
+        .map(|s| s.to_json().into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
+async fn delete_subscription(
+    State(state): State<BmcState>,
+    Path(subscription_id): Path<String>,
+) -> Response {
+    let mut subscriptions = state.event_service_state.subscriptions.lock().unwrap();
+    let count = subscriptions.len();
+    subscriptions.retain(|s| s.id != subscription_id);
+    if subscriptions.len() == count {
+        return http::not_found();
+    }
+    json!({}).into_ok_response()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SubmitTestEventRequest {
+    event_type: Option<String>,
+    event_id: Option<String>,
+    message_id: Option<String>,
+    message: Option<String>,
+    #[serde(default)]
+    message_args: Vec<String>,
+    severity: Option<String>,
+    origin_of_condition: Option<String>,
+}
+
+/// Delivers the submitted event to all subscribers. Delivery happens before
+/// the response is sent, so tests don't need to wait for it.
+async fn submit_test_event(
+    State(state): State<BmcState>,
+    Json(request): Json<SubmitTestEventRequest>,
+) -> Response {
+    let event_service = &state.event_service_state;
+    let event_id = request.event_id.unwrap_or_else(|| {
+        event_service
+            .next_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string()
+    });
+    let event = json!({
+        "EventType": request.event_type.unwrap_or_else(|| "Alert".to_string()),
+        "EventId": event_id,
+        "EventTimestamp": chrono::Utc::now().to_rfc3339(),
+        "MessageId": request.message_id.unwrap_or_else(|| "Base.1.0.GeneralError".to_string()),
+        "Message": request.message.unwrap_or_default(),
+        "MessageArgs": request.message_args,
+        "Severity": request.severity.unwrap_or_else(|| "OK".to_string()),
+        "OriginOfCondition": request.origin_of_condition.map(|id| json!({"@odata.id": id})),
+    });
+
+    for subscription in event_service.subscriptions() {
+        let payload = json!({
+            "@odata.type": "#Event.v1_7_0.Event",
+            "Id": event_id,
+            "Name": "Event Array",
+            "Context": subscription.context,
+            "Events": [event],
+        });
+        let result = event_service
+            .client
+            .post(&subscription.destination)
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(error) = result {
+            tracing::warn!(
+                %error,
+                destination = %subscription.destination,
+                "bmc-mock: failed to deliver event"
+            );
+        }
+    }
+
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::extract::Json;
+    use axum::http::{Method, Request, StatusCode};
+    use axum::routing::post;
+    use serde_json::{Value, json};
+    use tokio::sync::mpsc;
+    use tower::Service;
+
+    use crate::*;
+
+    #[derive(Debug)]
+    struct TestPowerControl {}
+
+    impl PowerControl for TestPowerControl {
+        fn get_power_state(&self) -> MockPowerState {
+            MockPowerState::On
+        }
+        fn send_power_command(&self, _: SystemPowerControl) -> Result<(), SetSystemPowerError> {
+            Ok(())
+        }
+    }
+
+    async fn call(
+        router: &mut axum::Router,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let response = router
+            .call(
+                Request::builder()
+                    .uri(uri)
+                    .method(method)
+                    .header("Content-Type", "application/json")
+                    .body(body.map(|b| Body::from(b.to_string())).unwrap_or_default())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_receive_submitted_events() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+        let receiver = axum::Router::new().route(
+            "/events",
+            post(move |Json(payload): Json<Value>| {
+                let tx = tx.clone();
+                async move {
+                    tx.send(payload).unwrap();
+                    StatusCode::OK
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let receiver_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, receiver).await });
+
+        let mut router = machine_router(
+            MachineInfo::Host(HostMachineInfo::new(
+                HostHardwareType::DellPowerEdgeR750,
+                vec![DpuMachineInfo::default()],
+            )),
+            Arc::new(TestPowerControl {}),
+            String::default(),
+        );
+
+        let (status, root) = call(&mut router, Method::GET, "/redfish/v1", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            root["EventService"]["@odata.id"],
+            Value::from("/redfish/v1/EventService")
+        );
+
+        let (status, subscription) = call(
+            &mut router,
+            Method::POST,
+            "/redfish/v1/EventService/Subscriptions",
+            Some(json!({
+                "Destination": format!("http://{receiver_addr}/events"),
+                "Context": "test-context",
+                "Protocol": "Redfish",
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let subscription_id = subscription["@odata.id"].as_str().unwrap().to_string();
+
+        let (_, subscriptions) = call(
+            &mut router,
+            Method::GET,
+            "/redfish/v1/EventService/Subscriptions",
+            None,
+        )
+        .await;
+        assert_eq!(subscriptions["Members@odata.count"], Value::from(1));
+
+        let (status, _) = call(
+            &mut router,
+            Method::POST,
+            "/redfish/v1/EventService/Actions/EventService.SubmitTestEvent",
+            Some(json!({
+                "MessageId": "SensorEvent.1.0.ReadingAboveUpperCriticalThreshold",
+                "Message": "Temperature above upper critical threshold",
+                "Severity": "Critical",
+                "OriginOfCondition": "/redfish/v1/Chassis/System.Embedded.1/Sensors/Temp1",
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let payload = rx.recv().await.unwrap();
+        assert_eq!(payload["Context"], Value::from("test-context"));
+        assert_eq!(payload["Events"][0]["Severity"], Value::from("Critical"));
+        assert_eq!(
+            payload["Events"][0]["OriginOfCondition"]["@odata.id"],
+            Value::from("/redfish/v1/Chassis/System.Embedded.1/Sensors/Temp1")
+        );
+
+        let (status, _) = call(&mut router, Method::DELETE, &subscription_id, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = call(&mut router, Method::GET, &subscription_id, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -23,6 +23,7 @@ pub mod chassis;
 pub mod collection;
 pub mod computer_system;
 pub mod ethernet_interface;
+pub mod event_service;
 pub mod log_service;
 pub mod manager;
 pub mod manager_network_protocol;
@@ -30,6 +31,7 @@ pub mod network_adapter;
 pub mod network_device_function;
 pub mod oem;
 pub mod pcie_device;
+pub mod power;
 pub mod resource;
 pub mod secure_boot;
 pub mod sensor;
@@ -0,0 +1,219 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::borrow::Cow;
+
+use serde_json::json;
+
+use crate::json::{JsonExt, JsonPatch};
+use crate::redfish;
+use crate::redfish::Builder;
+
+pub fn chassis_resource(chassis_id: &str) -> redfish::Resource<'static> {
+    let odata_id = format!("/redfish/v1/Chassis/{chassis_id}/Power");
+    redfish::Resource {
+        odata_id: odata_id.into(),
+        odata_type: "#Power.v1_7_1.Power".into(),
+        id: "Power".into(),
+        name: format!("Power for {chassis_id}").into(),
+    }
+}
+
+pub fn builder(resource: &redfish::Resource) -> PowerBuilder {
+    PowerBuilder {
+        odata_id: resource.odata_id.to_string(),
+        power_supplies: vec![],
+        redundancy: vec![],
+        value: resource.json_patch(),
+    }
+}
+
+pub fn power_supply_builder(member_id: Cow<'static, str>) -> PowerSupply {
+    PowerSupply {
+        member_id,
+        value: json!({}),
+    }
+}
+
+pub fn redundancy_builder(member_id: Cow<'static, str>) -> Redundancy {
+    Redundancy {
+        member_id,
+        value: json!({}),
+    }
+}
+
+pub struct PowerBuilder {
+    odata_id: String,
+    power_supplies: Vec<PowerSupply>,
+    redundancy: Vec<Redundancy>,
+    value: serde_json::Value,
+}
+
+impl Builder for PowerBuilder {
+    fn apply_patch(self, patch: serde_json::Value) -> Self {
+        Self {
+            odata_id: self.odata_id,
+            power_supplies: self.power_supplies,
+            redundancy: self.redundancy,
+            value: self.value.patch(patch),
+        }
+    }
+}
+
+impl PowerBuilder {
+    pub fn add_power_supply(mut self, power_supply: PowerSupply) -> Self {
+        self.power_supplies.push(power_supply);
+        self
+    }
+
+    pub fn add_redundancy(mut self, redundancy: Redundancy) -> Self {
+        self.redundancy.push(redundancy);
+        self
+    }
+
+    pub fn build(self) -> serde_json::Value {
+        let odata_id = self.odata_id;
+        let power_supply_ref = |member_id: &str| format!("{odata_id}#/PowerSupplies/{member_id}");
+        let redundancy = self
+            .redundancy
+            .into_iter()
+            .map(|redundancy| {
+                json!({
+                    "@odata.id": format!("{odata_id}#/Redundancy/{}", redundancy.member_id),
+                    "MemberId": redundancy.member_id,
+                    "RedundancySet": self.power_supplies.iter().map(|psu| json!({
+                        "@odata.id": power_supply_ref(&psu.member_id)
+                    })).collect::<Vec<_>>(),
+                })
+                .patch(redundancy.value)
+            })
+            .collect::<Vec<_>>();
+        let power_supplies = self
+            .power_supplies
+            .into_iter()
+            .map(|psu| {
+                json!({
+                    "@odata.id": power_supply_ref(&psu.member_id),
+                    "MemberId": psu.member_id,
+                })
+                .patch(psu.value)
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "PowerSupplies": power_supplies,
+            "Redundancy": redundancy,
+        })
+        .patch(self.value)
+    }
+}
+
+pub struct PowerSupply {
+    member_id: Cow<'static, str>,
+    value: serde_json::Value,
+}
+
+impl Builder for PowerSupply {
+    fn apply_patch(self, patch: serde_json::Value) -> Self {
+        Self {
+            member_id: self.member_id,
+            value: self.value.patch(patch),
+        }
+    }
+}
+
+impl PowerSupply {
+    pub fn name(self, v: &str) -> Self {
+        self.add_str_field("Name", v)
+    }
+
+    pub fn serial_number(self, v: &str) -> Self {
+        self.add_str_field("SerialNumber", v)
+    }
+
+    pub fn manufacturer(self, v: &str) -> Self {
+        self.add_str_field("Manufacturer", v)
+    }
+
+    pub fn model(self, v: &str) -> Self {
+        self.add_str_field("Model", v)
+    }
+
+    pub fn firmware_version(self, v: &str) -> Self {
+        self.add_str_field("FirmwareVersion", v)
+    }
+
+    pub fn power_capacity_watts(self, v: f64) -> Self {
+        self.apply_patch(json!({ "PowerCapacityWatts": v }))
+    }
+
+    pub fn last_power_output_watts(self, v: f64) -> Self {
+        self.apply_patch(json!({ "LastPowerOutputWatts": v }))
+    }
+
+    pub fn status(self, state: &str, health: &str) -> Self {
+        self.apply_patch(json!({
+            "Status": {
+                "State": state,
+                "Health": health,
+            }
+        }))
+    }
+
+    pub fn build(self) -> Self {
+        self
+    }
+}
+
+pub struct Redundancy {
+    member_id: Cow<'static, str>,
+    value: serde_json::Value,
+}
+
+impl Builder for Redundancy {
+    fn apply_patch(self, patch: serde_json::Value) -> Self {
+        Self {
+            member_id: self.member_id,
+            value: self.value.patch(patch),
+        }
+    }
+}
+
+impl Redundancy {
+    pub fn mode(self, v: &str) -> Self {
+        self.add_str_field("Mode", v)
+    }
+
+    pub fn min_num_needed(self, v: usize) -> Self {
+        self.apply_patch(json!({ "MinNumNeeded": v }))
+    }
+
+    pub fn max_num_supported(self, v: usize) -> Self {
+        self.apply_patch(json!({ "MaxNumSupported": v }))
+    }
+
+    pub fn status(self, state: &str, health: &str) -> Self {
+        self.apply_patch(json!({
+            "Status": {
+                "State": state,
+                "Health": health,
+            }
+        }))
+    }
+
+    pub fn build(self) -> Self {
+        self
+    }
+}
@@ -58,6 +58,7 @@ async fn get_service_root(State(state): State<BmcState>) -> Response {
         .system_collection(&redfish::computer_system::collection())
         .manager_collection(&redfish::manager::collection())
         .update_service(&redfish::update_service::resource())
+        .event_service(&redfish::event_service::resource())
         .build()
         .into_ok_response()
 }
@@ -106,4 +107,8 @@ impl ServiceRootBuilder {
     pub fn update_service(self, v: &redfish::Resource<'_>) -> Self {
         self.apply_patch(v.nav_property("UpdateService"))
     }
+
+    pub fn event_service(self, v: &redfish::Resource<'_>) -> Self {
+        self.apply_patch(v.nav_property("EventService"))
+    }
 }
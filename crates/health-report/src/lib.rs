@@ -50,6 +50,9 @@ impl Default for HealthReport {
 
 impl HealthReport {
     pub const SKU_VALIDATION_SOURCE: &str = "sku-validation";
+    pub const MLXCONFIG_PROFILE_SOURCE: &str = "mlxconfig-profile";
+    pub const SPDM_ATTESTATION_SOURCE: &str = "spdm-attestation";
+    pub const MEASURED_BOOT_ATTESTATION_SOURCE: &str = "measured-boot-attestation";
 
     /// Returns a health report with no successes or errors reported
     pub fn empty(source: String) -> Self {
@@ -193,6 +196,39 @@ impl HealthReport {
         }
     }
 
+    /// Returns a health report which indicates that one or more Mellanox devices
+    /// do not match the mlxconfig profile bound to the machine.
+    ///
+    /// `drifts` holds a `(device, message)` pair for every drifted device.
+    pub fn mlxconfig_drift(drifts: Vec<(String, String)>) -> Self {
+        Self {
+            source: Self::MLXCONFIG_PROFILE_SOURCE.to_string(),
+            observed_at: Some(chrono::Utc::now()),
+            successes: vec![],
+            alerts: drifts
+                .into_iter()
+                .map(|(device, message)| HealthProbeAlert::mlxconfig_drift(device, message))
+                .collect(),
+        }
+    }
+
+    /// Returns a health report which indicates that a machine failed a periodic
+    /// or policy triggered attestation.
+    ///
+    /// `source` is the attestation source, and `failures` holds a `(target, message)`
+    /// pair for every component which failed attestation.
+    pub fn attestation_failure(source: &str, failures: Vec<(String, String)>) -> Self {
+        Self {
+            source: source.to_string(),
+            observed_at: Some(chrono::Utc::now()),
+            successes: vec![],
+            alerts: failures
+                .into_iter()
+                .map(|(target, message)| HealthProbeAlert::attestation_failure(target, message))
+                .collect(),
+        }
+    }
+
     /// Update the in_alert_since timestamps on all alerts in the reports
     /// by taking into account the timestaps in a previous report
     /// - If the alert has been reported in the previous report, the old timestamp
@@ -430,6 +466,29 @@ impl HealthProbeAlert {
             classifications: vec![HealthAlertClassification::prevent_allocations()],
         }
     }
+
+    pub fn mlxconfig_drift(device: String, message: String) -> Self {
+        Self {
+            id: HealthProbeId::mlxconfig_drift(),
+            target: Some(device),
+            in_alert_since: Some(chrono::Utc::now()),
+            message,
+            tenant_message: None,
+            classifications: vec![HealthAlertClassification::prevent_allocations()],
+        }
+    }
+
+    pub fn attestation_failure(target: String, message: String) -> Self {
+        Self {
+            id: HealthProbeId::attestation_failure(),
+            target: Some(target),
+            in_alert_since: Some(chrono::Utc::now()),
+            message,
+            tenant_message: Some("The host failed attestation".to_string()),
+            classifications: vec![HealthAlertClassification::prevent_allocations()],
+        }
+    }
+
     /// Merge a HealthProbeAlert with the report from another probe of the same type
     ///
     /// The function does not check whether the Probe ID and target are equivalent.
@@ -529,6 +588,17 @@ impl HealthProbeId {
         HealthProbeId("SkuValidation".to_string())
     }
 
+    /// The ID used when a Mellanox device does not match the mlxconfig profile
+    /// bound to the machine
+    pub fn mlxconfig_drift() -> Self {
+        HealthProbeId("MlxConfigDrift".to_string())
+    }
+
+    /// The ID used when a machine fails SPDM or measured boot attestation
+    pub fn attestation_failure() -> Self {
+        HealthProbeId("AttestationFailure".to_string())
+    }
+
     /// The ID is used to mark host under internal maintenance.
     /// This is mandatory if tenant wants to turn off the machine.
     pub fn internal_maintenance() -> Self {
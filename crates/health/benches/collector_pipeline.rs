@@ -53,6 +53,7 @@ fn event_context() -> EventContext {
         metadata: Some(EndpointMetadata::Machine(MachineData {
             machine_id: MACHINE_ID.parse().expect("valid machine id"),
             machine_serial: None,
+            rack_id: None,
         })),
     }
 }
@@ -105,6 +106,7 @@ fn build_log_event(idx: usize) -> CollectorEvent {
             (Cow::Borrowed("entry_id"), idx.to_string()),
             (Cow::Borrowed("service_id"), "logservice-1".to_string()),
         ],
+        timestamp: None,
     })
 }
 
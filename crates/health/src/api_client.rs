@@ -15,10 +15,13 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
 use std::net::IpAddr;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
+use carbide_uuid::rack::RackId;
 use forge_tls::client_config::ClientCert;
 use mac_address::MacAddress;
 use rpc::forge::{BmcRequestType, MachineSearchConfig, UserRoles};
@@ -31,6 +34,7 @@ use crate::endpoint::{
     BmcAddr, BmcCredentials, BmcEndpoint, BoxFuture, EndpointMetadata, EndpointSource, MachineData,
     SwitchData,
 };
+use crate::sharding::{CollectorStateStore, ShardMembership};
 
 #[derive(Clone)]
 pub struct ApiClientWrapper {
@@ -75,6 +79,8 @@ impl ApiClientWrapper {
 
         tracing::info!("Found {} machines", machine_ids.machine_ids.len(),);
 
+        let rack_ids = self.fetch_rack_ids().await;
+
         let mut endpoints = Vec::new();
 
         for ids_chunk in machine_ids.machine_ids.chunks(100) {
@@ -93,7 +99,7 @@ impl ApiClientWrapper {
             );
 
             for machine in machines.machines {
-                if let Some(endpoint) = self.extract_bmc_endpoint(&machine).await {
+                if let Some(endpoint) = self.extract_bmc_endpoint(&machine, &rack_ids).await {
                     endpoints.push(Arc::new(endpoint));
                 }
             }
@@ -116,6 +122,7 @@ impl ApiClientWrapper {
                             let ip = bmc.ip.as_ref()?.parse().ok()?;
                             let mac = bmc.mac.and_then(|m| MacAddress::from_str(&m).ok())?;
                             let serial = s.config?.name;
+                            let rack_id = rack_ids.get(&mac).copied();
 
                             Some(Arc::new(BmcEndpoint {
                                 addr: BmcAddr {
@@ -127,7 +134,10 @@ impl ApiClientWrapper {
                                     username: String::new(),
                                     password: String::new(),
                                 },
-                                metadata: Some(EndpointMetadata::Switch(SwitchData { serial })),
+                                metadata: Some(EndpointMetadata::Switch(SwitchData {
+                                    serial,
+                                    rack_id,
+                                })),
                             }))
                         })
                         .collect();
@@ -146,7 +156,43 @@ impl ApiClientWrapper {
         Ok(endpoints)
     }
 
-    async fn extract_bmc_endpoint(&self, machine: &rpc::forge::Machine) -> Option<BmcEndpoint> {
+    /// Returns the racks of expected machines and switches by their BMC MAC.
+    /// Rack IDs are only informational, so failures are logged and ignored.
+    async fn fetch_rack_ids(&self) -> HashMap<MacAddress, RackId> {
+        let mut rack_ids = HashMap::new();
+
+        match self.client.get_all_expected_machines().await {
+            Ok(response) => {
+                rack_ids.extend(response.expected_machines.into_iter().filter_map(|m| {
+                    Some((MacAddress::from_str(&m.bmc_mac_address).ok()?, m.rack_id?))
+                }));
+            }
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to fetch rack IDs of expected machines");
+            }
+        }
+
+        if self.nmxt_enabled {
+            match self.client.get_all_expected_switches().await {
+                Ok(response) => {
+                    rack_ids.extend(response.expected_switches.into_iter().filter_map(|s| {
+                        Some((MacAddress::from_str(&s.bmc_mac_address).ok()?, s.rack_id?))
+                    }));
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Failed to fetch rack IDs of expected switches");
+                }
+            }
+        }
+
+        rack_ids
+    }
+
+    async fn extract_bmc_endpoint(
+        &self,
+        machine: &rpc::forge::Machine,
+        rack_ids: &HashMap<MacAddress, RackId>,
+    ) -> Option<BmcEndpoint> {
         let bmc_info = machine.bmc_info.as_ref()?;
         let ip_str = bmc_info.ip.as_ref()?;
         let ip = ip_str.parse::<IpAddr>().ok()?;
@@ -169,6 +215,7 @@ impl ApiClientWrapper {
                     EndpointMetadata::Machine(MachineData {
                         machine_id,
                         machine_serial: info.dmi_data.map(|dmi| dmi.chassis_serial),
+                        rack_id: rack_ids.get(&mac).copied(),
                     })
                 }),
         })
@@ -214,6 +261,82 @@ impl ApiClientWrapper {
 
         Ok(())
     }
+
+    pub async fn renew_shard_lease(
+        &self,
+        member_id: &str,
+        lease_duration: Duration,
+    ) -> Result<Vec<String>, HealthError> {
+        let request = rpc::forge::HealthShardLeaseRequest {
+            member_id: member_id.to_string(),
+            lease_duration: Some(lease_duration.into()),
+        };
+
+        let response = self
+            .client
+            .renew_health_shard_lease(request)
+            .await
+            .map_err(HealthError::ApiInvocationError)?;
+
+        Ok(response
+            .members
+            .into_iter()
+            .map(|member| member.member_id)
+            .collect())
+    }
+
+    pub async fn release_shard_lease(&self, member_id: &str) -> Result<(), HealthError> {
+        let request = rpc::forge::HealthShardLeaseRelease {
+            member_id: member_id.to_string(),
+        };
+
+        self.client
+            .release_health_shard_lease(request)
+            .await
+            .map_err(HealthError::ApiInvocationError)?;
+
+        Ok(())
+    }
+
+    pub async fn load_collector_state(
+        &self,
+        endpoint_key: &str,
+        collector: &str,
+    ) -> Result<Option<String>, HealthError> {
+        let request = rpc::forge::HealthCollectorStateRequest {
+            endpoint_key: endpoint_key.to_string(),
+            collector: collector.to_string(),
+        };
+
+        let response = self
+            .client
+            .get_health_collector_state(request)
+            .await
+            .map_err(HealthError::ApiInvocationError)?;
+
+        Ok(response.state.map(|state| state.state))
+    }
+
+    pub async fn save_collector_state(
+        &self,
+        endpoint_key: &str,
+        collector: &str,
+        state: String,
+    ) -> Result<(), HealthError> {
+        let request = rpc::forge::HealthCollectorState {
+            endpoint_key: endpoint_key.to_string(),
+            collector: collector.to_string(),
+            state,
+            updated_at: None,
+        };
+
+        self.client
+            .save_health_collector_state(request)
+            .await
+            .map_err(HealthError::ApiInvocationError)?;
+
+        Ok(())
+    }
 }
 
 impl EndpointSource for ApiClientWrapper {
@@ -221,3 +344,36 @@ impl EndpointSource for ApiClientWrapper {
         Box::pin(self.fetch_bmc_hosts())
     }
 }
+
+impl ShardMembership for ApiClientWrapper {
+    fn renew<'a>(
+        &'a self,
+        member_id: &'a str,
+        lease_duration: Duration,
+    ) -> BoxFuture<'a, Result<Vec<String>, HealthError>> {
+        Box::pin(self.renew_shard_lease(member_id, lease_duration))
+    }
+
+    fn release<'a>(&'a self, member_id: &'a str) -> BoxFuture<'a, Result<(), HealthError>> {
+        Box::pin(self.release_shard_lease(member_id))
+    }
+}
+
+impl CollectorStateStore for ApiClientWrapper {
+    fn load<'a>(
+        &'a self,
+        endpoint_key: &'a str,
+        collector: &'a str,
+    ) -> BoxFuture<'a, Result<Option<String>, HealthError>> {
+        Box::pin(self.load_collector_state(endpoint_key, collector))
+    }
+
+    fn save<'a>(
+        &'a self,
+        endpoint_key: &'a str,
+        collector: &'a str,
+        state: String,
+    ) -> BoxFuture<'a, Result<(), HealthError>> {
+        Box::pin(self.save_collector_state(endpoint_key, collector, state))
+    }
+}
@@ -0,0 +1,707 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use dashmap::{DashMap, DashSet};
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthProbeId, HealthProbeSuccess, HealthReport,
+};
+use http::{Method, Response, StatusCode};
+use http_body_util::BodyExt;
+use hyper::Request;
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use prometheus::{IntCounterVec, Opts};
+use rustls::ServerConfig;
+use serde::Deserialize;
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+use super::redfish_severity_to_otel;
+use crate::HealthError;
+use crate::config::EventsCollectorConfig as EventsCollectorOptions;
+use crate::metrics::{MetricsManager, sanitize_unit};
+use crate::sink::{
+    CollectorEvent, DataSink, EventContext, HealthOverride, LogRecord, MetricSample,
+};
+
+/// Maximum accepted size of a single event payload
+const MAX_PAYLOAD_SIZE: usize = 1024 * 1024;
+
+/// Payload which a BMC POSTs to the destination of an `EventDestination`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EventPayload {
+    context: Option<String>,
+    #[serde(default)]
+    events: Vec<EventRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EventRecord {
+    event_type: Option<String>,
+    event_id: Option<String>,
+    event_timestamp: Option<String>,
+    message_id: Option<String>,
+    message: Option<String>,
+    #[serde(default)]
+    message_args: Vec<String>,
+    /// Deprecated in favor of `MessageSeverity`, but still sent by most BMCs
+    severity: Option<String>,
+    message_severity: Option<String>,
+    origin_of_condition: Option<ODataRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ODataRef {
+    #[serde(rename = "@odata.id")]
+    odata_id: String,
+}
+
+/// Sensor reading carried by an event of the `SensorEvent` message registry
+struct SensorReading {
+    sensor_id: String,
+    origin: Option<String>,
+    value: Option<f64>,
+    unit: String,
+}
+
+impl SensorReading {
+    /// Returns the reading as the metric which the sensor collector reports
+    /// for the same sensor, if the unit maps to a known reading type
+    fn metric_sample(&self) -> Option<MetricSample> {
+        let unit = sanitize_unit(&self.unit);
+        let metric_type = match unit.as_str() {
+            "celsius" => "temperature",
+            "volts" => "voltage",
+            "amperes" => "current",
+            "watts" => "power",
+            "rpm" => "rotational",
+            "percent" => "percent",
+            _ => return None,
+        };
+        Some(MetricSample {
+            key: self
+                .origin
+                .clone()
+                .unwrap_or_else(|| self.sensor_id.clone()),
+            name: "hw_sensor".to_string(),
+            metric_type: metric_type.to_string(),
+            unit,
+            value: self.value?,
+            labels: vec![(Cow::Borrowed("sensor_name"), self.sensor_id.clone())],
+        })
+    }
+}
+
+impl EventRecord {
+    /// Returns the sensor reading of threshold and sensor state events. The
+    /// arguments of those messages start with the sensor name, the reading
+    /// and its unit.
+    fn sensor_reading(&self) -> Option<SensorReading> {
+        let registry = self.message_id.as_deref()?.split('.').next()?;
+        if registry != "SensorEvent" {
+            return None;
+        }
+
+        let origin = self
+            .origin_of_condition
+            .as_ref()
+            .map(|origin| origin.odata_id.clone());
+        let sensor_id = origin
+            .as_deref()
+            .and_then(|origin| origin.rsplit('/').find(|segment| !segment.is_empty()))
+            .or(self.message_args.first().map(String::as_str))?
+            .to_string();
+
+        Some(SensorReading {
+            sensor_id,
+            origin,
+            value: self
+                .message_args
+                .get(1)
+                .and_then(|value| value.parse().ok()),
+            unit: self.message_args.get(2).cloned().unwrap_or_default(),
+        })
+    }
+
+    /// Returns the alert which a sensor event raises, or `None` if it reports
+    /// the sensor as healthy
+    fn sensor_alert(&self, reading: &SensorReading) -> Option<HealthProbeAlert> {
+        let severity = self
+            .message_severity
+            .as_deref()
+            .or(self.severity.as_deref())?
+            .to_lowercase();
+        let classification = if self
+            .message_id
+            .as_deref()
+            .is_some_and(|message_id| message_id.ends_with(".SensorFailure"))
+        {
+            "SensorFailure"
+        } else {
+            match severity.as_str() {
+                "warning" => "SensorWarning",
+                "critical" => "SensorCritical",
+                _ => return None,
+            }
+        };
+        let classifications = if let Ok(classification) = classification.parse() {
+            vec![classification, HealthAlertClassification::hardware()]
+        } else {
+            vec![HealthAlertClassification::hardware()]
+        };
+
+        Some(HealthProbeAlert {
+            id: sensor_probe_id(),
+            target: Some(reading.sensor_id.clone()),
+            in_alert_since: None,
+            message: self
+                .message
+                .clone()
+                .or(self.message_id.clone())
+                .unwrap_or_default(),
+            tenant_message: None,
+            classifications,
+        })
+    }
+
+    fn into_log_record(self, event_context: &EventContext) -> LogRecord {
+        let severity = self
+            .message_severity
+            .as_deref()
+            .or(self.severity.as_deref())
+            .map(redfish_severity_to_otel)
+            .unwrap_or("INFO");
+        let timestamp = self
+            .event_timestamp
+            .as_deref()
+            .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+            .map(SystemTime::from);
+
+        let mut attributes = vec![(Cow::Borrowed("type"), "bmc_event".to_string())];
+        if let Some(machine_id) = event_context.machine_id() {
+            attributes.push((Cow::Borrowed("machine_id"), machine_id.to_string()));
+        }
+        let optional_attributes = [
+            ("event_type", self.event_type),
+            ("event_id", self.event_id),
+            ("event_timestamp", self.event_timestamp),
+            ("message_id", self.message_id.clone()),
+            (
+                "origin_of_condition",
+                self.origin_of_condition.map(|origin| origin.odata_id),
+            ),
+        ];
+        for (name, value) in optional_attributes {
+            if let Some(value) = value {
+                attributes.push((Cow::Borrowed(name), value));
+            }
+        }
+        if !self.message_args.is_empty() {
+            attributes.push((Cow::Borrowed("message_args"), self.message_args.join(",")));
+        }
+
+        LogRecord {
+            body: self.message.or(self.message_id).unwrap_or_default(),
+            severity: severity.to_string(),
+            attributes,
+            timestamp,
+        }
+    }
+}
+
+fn sensor_probe_id() -> HealthProbeId {
+    HealthProbeId::from_str("BmcSensor").expect("cannot fail")
+}
+
+struct EventRoute {
+    event_context: EventContext,
+    data_sink: Option<Arc<dyn DataSink>>,
+    /// Latest health of every sensor which reported events, `None` if the
+    /// sensor is healthy
+    sensor_health: Mutex<BTreeMap<String, Option<HealthProbeAlert>>>,
+}
+
+impl EventRoute {
+    fn emit_event(&self, event: CollectorEvent) {
+        if let Some(data_sink) = &self.data_sink {
+            data_sink.handle_event(&self.event_context, &event);
+        }
+    }
+
+    /// Builds the hardware health report from the latest sensor events. It
+    /// replaces the report of the sensor collector, which is paused while
+    /// the subscription is active.
+    fn sensor_health_report(&self) -> HealthReport {
+        let sensor_health = self.sensor_health.lock().expect("lock poisoned");
+        let mut successes = Vec::new();
+        let mut alerts = Vec::new();
+        for (sensor_id, alert) in sensor_health.iter() {
+            match alert {
+                Some(alert) => alerts.push(alert.clone()),
+                None => successes.push(HealthProbeSuccess {
+                    id: sensor_probe_id(),
+                    target: Some(sensor_id.clone()),
+                }),
+            }
+        }
+
+        HealthReport {
+            source: "hardware-health".to_string(),
+            observed_at: Some(chrono::Utc::now()),
+            successes,
+            alerts,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum DispatchError {
+    #[error("invalid event payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+    #[error("event context does not belong to any subscription")]
+    UnknownContext,
+}
+
+/// Routes events received by the listener to the collector which owns the
+/// subscription. Subscriptions are identified by their `Context`, which is
+/// a random token that only the BMC and the collector know.
+#[derive(Default)]
+pub struct EventRouter {
+    routes: DashMap<String, EventRoute>,
+    /// Keys of the endpoints whose BMC has an active subscription
+    subscribed_endpoints: DashSet<String>,
+}
+
+impl EventRouter {
+    /// Registers the route of a subscription. Routes which are already
+    /// registered keep the sensor health they collected so far.
+    pub fn register(
+        &self,
+        context: String,
+        event_context: EventContext,
+        data_sink: Option<Arc<dyn DataSink>>,
+    ) {
+        self.routes.entry(context).or_insert_with(|| EventRoute {
+            event_context,
+            data_sink,
+            sensor_health: Mutex::new(BTreeMap::new()),
+        });
+    }
+
+    /// Marks the endpoint of the route as subscribed, which pauses the
+    /// polling collectors of the endpoint
+    pub fn mark_subscribed(&self, context: &str) {
+        if let Some(route) = self.routes.get(context) {
+            self.subscribed_endpoints
+                .insert(route.event_context.endpoint_key().to_string());
+        }
+    }
+
+    /// Returns whether the BMC of the endpoint delivers its events to the
+    /// listener
+    pub fn is_subscribed(&self, endpoint_key: &str) -> bool {
+        self.subscribed_endpoints.contains(endpoint_key)
+    }
+
+    pub fn deregister(&self, context: &str) {
+        if let Some((_, route)) = self.routes.remove(context) {
+            self.subscribed_endpoints
+                .remove(route.event_context.endpoint_key());
+        }
+    }
+
+    /// Forwards all events of the payload to the sink of the owning collector
+    /// and returns the amount of events
+    fn dispatch(&self, payload: &[u8]) -> Result<usize, DispatchError> {
+        let payload: EventPayload = serde_json::from_slice(payload)?;
+        let context = payload.context.ok_or(DispatchError::UnknownContext)?;
+        let route = self
+            .routes
+            .get(&context)
+            .ok_or(DispatchError::UnknownContext)?;
+
+        let count = payload.events.len();
+        let mut sensor_health_changed = false;
+        for event in payload.events {
+            if let Some(reading) = event.sensor_reading() {
+                if let Some(sample) = reading.metric_sample() {
+                    route.emit_event(CollectorEvent::Metric(sample));
+                }
+                let alert = event.sensor_alert(&reading);
+                route
+                    .sensor_health
+                    .lock()
+                    .expect("lock poisoned")
+                    .insert(reading.sensor_id, alert);
+                sensor_health_changed = true;
+            }
+
+            let record = event.into_log_record(&route.event_context);
+            route.emit_event(CollectorEvent::Log(record));
+        }
+
+        if sensor_health_changed {
+            route.emit_event(CollectorEvent::HealthOverride(HealthOverride {
+                machine_id: route.event_context.machine_id(),
+                report: Arc::new(route.sensor_health_report()),
+            }));
+        }
+        Ok(count)
+    }
+}
+
+fn load_tls_acceptor(config: &EventsCollectorOptions) -> Result<TlsAcceptor, HealthError> {
+    let cert = std::fs::read(&config.tls_cert).map_err(|e| {
+        HealthError::GenericError(format!("Failed to read {}: {e}", config.tls_cert))
+    })?;
+    let key = std::fs::read(&config.tls_key).map_err(|e| {
+        HealthError::GenericError(format!("Failed to read {}: {e}", config.tls_key))
+    })?;
+
+    let certs = rustls_pemfile::certs(&mut cert.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| HealthError::GenericError(format!("Invalid certificate: {e}")))?;
+    let key = rustls_pemfile::private_key(&mut key.as_slice())
+        .map_err(|e| HealthError::GenericError(format!("Invalid key: {e}")))?
+        .ok_or_else(|| HealthError::GenericError(format!("No key in {}", config.tls_key)))?;
+
+    let mut server_config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| HealthError::GenericError(e.to_string()))?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| HealthError::GenericError(format!("Invalid TLS config: {e}")))?;
+    server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// Runs the HTTPS listener which receives events from subscribed BMCs
+pub async fn run_event_listener(
+    config: EventsCollectorOptions,
+    event_router: Arc<EventRouter>,
+    metrics_manager: Arc<MetricsManager>,
+    metrics_prefix: String,
+) -> Result<(), HealthError> {
+    let listen_addr: SocketAddr = config.listen_addr.parse().map_err(|_| {
+        HealthError::GenericError(format!("Invalid listen address: {}", config.listen_addr))
+    })?;
+    let tls_acceptor = load_tls_acceptor(&config)?;
+
+    let received_counter = IntCounterVec::new(
+        Opts::new(
+            format!("{metrics_prefix}_redfish_events_received_total"),
+            "Count of Redfish event payloads received from BMCs",
+        ),
+        &["status"],
+    )?;
+    metrics_manager
+        .global_registry()
+        .register(Box::new(received_counter.clone()))?;
+
+    let listener = TcpListener::bind(listen_addr)
+        .await
+        .map_err(|e| HealthError::GenericError(format!("Failed to bind {listen_addr}: {e}")))?;
+
+    tracing::info!("Redfish event listener listening on {}", listen_addr);
+
+    loop {
+        let (stream, peer_addr) = match listener.accept().await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to accept event listener connection");
+                continue;
+            }
+        };
+
+        let tls_acceptor = tls_acceptor.clone();
+        let event_router = event_router.clone();
+        let received_counter = received_counter.clone();
+
+        tokio::spawn(async move {
+            let stream = match tls_acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::debug!(error = ?e, %peer_addr, "TLS handshake failed");
+                    return;
+                }
+            };
+
+            let service = service_fn(move |req| {
+                let event_router = event_router.clone();
+                let received_counter = received_counter.clone();
+                async move { serve_request(req, &event_router, &received_counter).await }
+            });
+
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!(error = ?e, %peer_addr, "event listener connection error");
+            }
+        });
+    }
+}
+
+async fn serve_request(
+    req: Request<Incoming>,
+    event_router: &EventRouter,
+    received_counter: &IntCounterVec,
+) -> Result<Response<String>, hyper::Error> {
+    let status = if req.method() != Method::POST {
+        StatusCode::METHOD_NOT_ALLOWED
+    } else {
+        let body = http_body_util::Limited::new(req.into_body(), MAX_PAYLOAD_SIZE)
+            .collect()
+            .await;
+        match body {
+            Ok(body) => match event_router.dispatch(&body.to_bytes()) {
+                Ok(_) => StatusCode::OK,
+                Err(e @ DispatchError::InvalidPayload(_)) => {
+                    tracing::warn!(error = %e, "Rejected Redfish event");
+                    StatusCode::BAD_REQUEST
+                }
+                Err(e @ DispatchError::UnknownContext) => {
+                    tracing::debug!(error = %e, "Rejected Redfish event");
+                    StatusCode::NOT_FOUND
+                }
+            },
+            Err(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        }
+    };
+
+    received_counter.with_label_values(&[status.as_str()]).inc();
+
+    Ok(Response::builder()
+        .status(status)
+        .body(String::new())
+        .expect("BUG: Response::builder error"))
+}
+
+#[cfg(test)]
+mod tests {
+    use mac_address::MacAddress;
+
+    use super::*;
+    use crate::endpoint::BmcAddr;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        records: Mutex<Vec<LogRecord>>,
+        metrics: Mutex<Vec<MetricSample>>,
+        reports: Mutex<Vec<Arc<HealthReport>>>,
+    }
+
+    impl DataSink for RecordingSink {
+        fn handle_event(&self, _context: &EventContext, event: &CollectorEvent) {
+            match event {
+                CollectorEvent::Log(record) => self.records.lock().unwrap().push(record.clone()),
+                CollectorEvent::Metric(sample) => self.metrics.lock().unwrap().push(sample.clone()),
+                CollectorEvent::HealthOverride(HealthOverride { report, .. }) => {
+                    self.reports.lock().unwrap().push(report.clone())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn sensor_event(message_id: &str, severity: &str, reading: &str) -> serde_json::Value {
+        serde_json::json!({
+            "MessageId": message_id,
+            "Message": format!("Temperature of Temp1 is {reading}"),
+            "MessageArgs": ["Temp1", reading, "Cel", "90"],
+            "MessageSeverity": severity,
+            "OriginOfCondition": {"@odata.id": "/redfish/v1/Chassis/1/Sensors/Temp1"},
+        })
+    }
+
+    fn event_context() -> EventContext {
+        EventContext {
+            endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+            addr: BmcAddr {
+                ip: "10.0.0.1".parse().expect("valid ip"),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+            },
+            collector_type: "events_collector",
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_dispatch_maps_events_to_log_records() {
+        let sink = Arc::new(RecordingSink::default());
+        let router = EventRouter::default();
+        router.register("token".to_string(), event_context(), Some(sink.clone()));
+
+        let payload = serde_json::json!({
+            "@odata.type": "#Event.v1_7_0.Event",
+            "Id": "1",
+            "Context": "token",
+            "Events": [{
+                "EventType": "Alert",
+                "EventId": "17",
+                "MessageId": "SensorEvent.1.0.ReadingAboveUpperCriticalThreshold",
+                "Message": "Temperature above upper critical threshold",
+                "MessageArgs": ["Temp1", "95"],
+                "Severity": "Critical",
+                "OriginOfCondition": {"@odata.id": "/redfish/v1/Chassis/1/Sensors/Temp1"},
+            }, {
+                "MessageId": "EventLog.1.0.LogCleared",
+            }],
+        });
+
+        let count = router
+            .dispatch(payload.to_string().as_bytes())
+            .expect("dispatch should succeed");
+        assert_eq!(count, 2);
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0].body,
+            "Temperature above upper critical threshold"
+        );
+        assert_eq!(records[0].severity, "FATAL");
+        assert!(records[0].attributes.contains(&(
+            Cow::Borrowed("origin_of_condition"),
+            "/redfish/v1/Chassis/1/Sensors/Temp1".to_string()
+        )));
+        assert!(
+            records[0]
+                .attributes
+                .contains(&(Cow::Borrowed("message_args"), "Temp1,95".to_string()))
+        );
+        assert_eq!(records[1].body, "EventLog.1.0.LogCleared");
+        assert_eq!(records[1].severity, "INFO");
+    }
+
+    #[test]
+    fn test_dispatch_rejects_unknown_context() {
+        let router = EventRouter::default();
+        router.register("token".to_string(), event_context(), None);
+
+        let payload = serde_json::json!({"Context": "other", "Events": []});
+        assert!(matches!(
+            router.dispatch(payload.to_string().as_bytes()),
+            Err(DispatchError::UnknownContext)
+        ));
+
+        router.deregister("token");
+        let payload = serde_json::json!({"Context": "token", "Events": []});
+        assert!(matches!(
+            router.dispatch(payload.to_string().as_bytes()),
+            Err(DispatchError::UnknownContext)
+        ));
+
+        assert!(matches!(
+            router.dispatch(b"not json"),
+            Err(DispatchError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_dispatch_maps_sensor_events_to_metrics_and_health() {
+        let sink = Arc::new(RecordingSink::default());
+        let router = EventRouter::default();
+        router.register("token".to_string(), event_context(), Some(sink.clone()));
+
+        let payload = serde_json::json!({
+            "Context": "token",
+            "Events": [sensor_event(
+                "SensorEvent.1.0.ReadingAboveUpperCriticalThreshold",
+                "Critical",
+                "95",
+            )],
+        });
+        router
+            .dispatch(payload.to_string().as_bytes())
+            .expect("dispatch should succeed");
+
+        {
+            let metrics = sink.metrics.lock().unwrap();
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics[0].key, "/redfish/v1/Chassis/1/Sensors/Temp1");
+            assert_eq!(metrics[0].name, "hw_sensor");
+            assert_eq!(metrics[0].metric_type, "temperature");
+            assert_eq!(metrics[0].unit, "celsius");
+            assert_eq!(metrics[0].value, 95.0);
+
+            let reports = sink.reports.lock().unwrap();
+            assert_eq!(reports.len(), 1);
+            assert_eq!(reports[0].source, "hardware-health");
+            assert!(reports[0].successes.is_empty());
+            assert_eq!(reports[0].alerts.len(), 1);
+            assert_eq!(reports[0].alerts[0].target.as_deref(), Some("Temp1"));
+            assert!(
+                reports[0].alerts[0]
+                    .classifications
+                    .contains(&HealthAlertClassification::hardware())
+            );
+            assert_eq!(sink.records.lock().unwrap().len(), 1);
+        }
+
+        // Re-registering on subscription refresh keeps the sensor health
+        router.register("token".to_string(), event_context(), Some(sink.clone()));
+        let payload = serde_json::json!({
+            "Context": "token",
+            "Events": [sensor_event(
+                "SensorEvent.1.0.ReadingNoLongerAboveUpperCriticalThreshold",
+                "OK",
+                "70",
+            )],
+        });
+        router
+            .dispatch(payload.to_string().as_bytes())
+            .expect("dispatch should succeed");
+
+        let reports = sink.reports.lock().unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports[1].alerts.is_empty());
+        assert_eq!(reports[1].successes.len(), 1);
+        assert_eq!(reports[1].successes[0].target.as_deref(), Some("Temp1"));
+        assert_eq!(sink.metrics.lock().unwrap()[1].value, 70.0);
+    }
+
+    #[test]
+    fn test_subscription_state_follows_route() {
+        let router = EventRouter::default();
+        let endpoint_key = event_context().endpoint_key().to_string();
+
+        router.mark_subscribed("token");
+        assert!(!router.is_subscribed(&endpoint_key));
+
+        router.register("token".to_string(), event_context(), None);
+        router.mark_subscribed("token");
+        assert!(router.is_subscribed(&endpoint_key));
+
+        router.deregister("token");
+        assert!(!router.is_subscribed(&endpoint_key));
+    }
+}
@@ -0,0 +1,187 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Push based collection of BMC events.
+//!
+//! The collector registers a Redfish `EventDestination` on its BMC which
+//! points to the HTTPS listener of the health service. Events received by the
+//! listener are forwarded to the data sink as [`CollectorEvent::Log`], and
+//! sensor events additionally as [`CollectorEvent::Metric`] and
+//! [`CollectorEvent::HealthOverride`]. While the subscription is active, the
+//! sensor and logs collectors of the endpoint are paused. BMCs which don't
+//! support event subscriptions keep being polled by those collectors.
+//!
+//! [`CollectorEvent::Log`]: crate::sink::CollectorEvent::Log
+//! [`CollectorEvent::Metric`]: crate::sink::CollectorEvent::Metric
+//! [`CollectorEvent::HealthOverride`]: crate::sink::CollectorEvent::HealthOverride
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use nv_redfish::core::Bmc;
+use url::Url;
+
+use crate::HealthError;
+use crate::collectors::{IterationResult, PeriodicCollector};
+use crate::endpoint::BmcEndpoint;
+use crate::sink::{DataSink, EventContext};
+
+mod listener;
+mod subscription;
+
+pub use listener::{EventRouter, run_event_listener};
+use subscription::EventServiceClient;
+
+/// Configuration for events collector
+pub struct EventsCollectorConfig {
+    pub data_sink: Option<Arc<dyn DataSink>>,
+    pub event_router: Arc<EventRouter>,
+    pub http_client: reqwest::Client,
+    pub bmc_proxy_url: Option<Url>,
+    /// URL of the event listener, as reachable by BMCs
+    pub destination: Url,
+    pub subscription_refresh_interval: Duration,
+}
+
+/// Events collector for a single BMC endpoint
+pub struct EventsCollector {
+    event_context: EventContext,
+    data_sink: Option<Arc<dyn DataSink>>,
+    event_router: Arc<EventRouter>,
+    client: EventServiceClient,
+    destination: String,
+    /// Identifies the subscription of this collector in delivered events
+    context: String,
+    subscription_uri: Option<String>,
+    last_subscription_refresh: Instant,
+    subscription_refresh_interval: Duration,
+}
+
+impl<B: Bmc + 'static> PeriodicCollector<B> for EventsCollector {
+    type Config = EventsCollectorConfig;
+
+    fn new_runner(
+        _bmc: Arc<B>,
+        endpoint: Arc<BmcEndpoint>,
+        config: Self::Config,
+    ) -> Result<Self, HealthError> {
+        let event_context = EventContext::from_endpoint(endpoint.as_ref(), "events_collector");
+        let client = EventServiceClient::new(
+            config.http_client,
+            endpoint.as_ref(),
+            config.bmc_proxy_url.as_ref(),
+        )?;
+        Ok(Self {
+            event_context,
+            data_sink: config.data_sink,
+            event_router: config.event_router,
+            client,
+            destination: config.destination.to_string(),
+            context: format!("carbide-hw-health-{:032x}", rand::random::<u128>()),
+            subscription_uri: None,
+            last_subscription_refresh: Instant::now(),
+            subscription_refresh_interval: config.subscription_refresh_interval,
+        })
+    }
+
+    async fn run_iteration(&mut self) -> Result<IterationResult, HealthError> {
+        // Without a subscription every iteration retries to subscribe
+        let refresh_triggered = self.subscription_uri.is_none()
+            || self.last_subscription_refresh.elapsed() > self.subscription_refresh_interval;
+        if refresh_triggered {
+            self.refresh_subscription().await;
+        }
+
+        Ok(IterationResult {
+            refresh_triggered,
+            entity_count: None,
+        })
+    }
+
+    async fn shutdown(&mut self) {
+        self.event_router.deregister(&self.context);
+        if let Some(subscription_uri) = &self.subscription_uri
+            && let Err(error) = self.client.delete_subscription(subscription_uri).await
+        {
+            tracing::warn!(
+                ?error,
+                endpoint = %self.event_context.endpoint_key(),
+                "Failed to remove Redfish event subscription"
+            );
+        }
+    }
+
+    fn collector_type(&self) -> &'static str {
+        "events_collector"
+    }
+}
+
+impl EventsCollector {
+    /// Ensures that the subscription of this collector exists on the BMC. If
+    /// it doesn't, the sensor and logs collectors resume polling the BMC.
+    async fn refresh_subscription(&mut self) {
+        // Registering before subscribing, since the BMC may deliver events
+        // right after the subscription got created.
+        self.event_router.register(
+            self.context.clone(),
+            self.event_context.clone(),
+            self.data_sink.clone(),
+        );
+
+        let subscription = match self
+            .client
+            .ensure_subscription(&self.destination, &self.context)
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(error) => {
+                tracing::warn!(
+                    ?error,
+                    endpoint = %self.event_context.endpoint_key(),
+                    "Failed to subscribe to Redfish events, falling back to polling"
+                );
+                None
+            }
+        };
+
+        match &subscription {
+            Some(subscription_uri) => {
+                if self.subscription_uri.is_none() {
+                    tracing::info!(
+                        endpoint = %self.event_context.endpoint_key(),
+                        %subscription_uri,
+                        "Subscribed to Redfish events"
+                    );
+                }
+                self.event_router.mark_subscribed(&self.context);
+            }
+            None => self.event_router.deregister(&self.context),
+        }
+
+        self.subscription_uri = subscription;
+        self.last_subscription_refresh = Instant::now();
+    }
+}
+
+/// Maps the Redfish severity of an event to an OpenTelemetry severity text
+fn redfish_severity_to_otel(severity: &str) -> &'static str {
+    match severity.to_lowercase().as_str() {
+        "critical" => "FATAL",
+        "warning" => "WARN",
+        _ => "INFO",
+    }
+}
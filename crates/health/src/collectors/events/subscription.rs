@@ -0,0 +1,261 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use http::header::InvalidHeaderValue;
+use http::{HeaderMap, StatusCode, header};
+use serde::Deserialize;
+use serde_json::json;
+use url::Url;
+
+use crate::HealthError;
+use crate::endpoint::{BmcCredentials, BmcEndpoint};
+
+const EVENT_SERVICE_PATH: &str = "/redfish/v1/EventService";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize)]
+struct ODataRef {
+    #[serde(rename = "@odata.id")]
+    odata_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EventService {
+    service_enabled: Option<bool>,
+    subscriptions: Option<ODataRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Collection {
+    #[serde(default)]
+    members: Vec<ODataRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EventDestination {
+    #[serde(rename = "@odata.id")]
+    odata_id: String,
+    destination: Option<String>,
+    context: Option<String>,
+}
+
+/// Manages the `EventDestination` of the health service on a single BMC.
+///
+/// Plain HTTP requests are used here since creating and deleting
+/// subscriptions is not covered by the Redfish client of the collectors.
+pub(super) struct EventServiceClient {
+    client: reqwest::Client,
+    base_url: Url,
+    headers: HeaderMap,
+    credentials: BmcCredentials,
+}
+
+impl EventServiceClient {
+    pub(super) fn new(
+        client: reqwest::Client,
+        endpoint: &BmcEndpoint,
+        bmc_proxy_url: Option<&Url>,
+    ) -> Result<Self, HealthError> {
+        let (base_url, headers) = match bmc_proxy_url {
+            Some(url) => {
+                let mut headers = HeaderMap::new();
+                headers.insert(
+                    header::FORWARDED,
+                    format!("host={}", endpoint.addr.ip).parse().map_err(
+                        |e: InvalidHeaderValue| HealthError::GenericError(e.to_string()),
+                    )?,
+                );
+                (url.clone(), headers)
+            }
+            None => (
+                endpoint
+                    .addr
+                    .to_url()
+                    .map_err(|e| HealthError::GenericError(e.to_string()))?,
+                HeaderMap::new(),
+            ),
+        };
+
+        Ok(Self {
+            client,
+            base_url,
+            headers,
+            credentials: endpoint.credentials.clone(),
+        })
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, HealthError> {
+        let url = self
+            .base_url
+            .join(path)
+            .map_err(|e| HealthError::GenericError(format!("Invalid Redfish path {path}: {e}")))?;
+        Ok(self
+            .client
+            .request(method, url)
+            .headers(self.headers.clone())
+            .basic_auth(&self.credentials.username, Some(&self.credentials.password))
+            .timeout(REQUEST_TIMEOUT))
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, HealthError> {
+        request
+            .send()
+            .await
+            .map_err(|e| HealthError::GenericError(format!("Redfish request failed: {e}")))
+    }
+
+    /// Fetches a resource. Returns `None` if the BMC doesn't implement it.
+    async fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<T>, HealthError> {
+        let response = self.send(self.request(reqwest::Method::GET, path)?).await?;
+        match response.status() {
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => Ok(None),
+            status if status.is_success() => response.json().await.map(Some).map_err(|e| {
+                HealthError::GenericError(format!("Invalid response for {path}: {e}"))
+            }),
+            status => Err(HealthError::GenericError(format!(
+                "GET {path} returned status {status}"
+            ))),
+        }
+    }
+
+    /// Makes sure that exactly one subscription with the given destination and
+    /// context exists on the BMC and returns its URI.
+    ///
+    /// Subscriptions with the same destination but a different context were
+    /// created by an earlier instance of the health service and are removed.
+    /// Returns `None` if the BMC does not support event subscriptions.
+    pub(super) async fn ensure_subscription(
+        &self,
+        destination: &str,
+        context: &str,
+    ) -> Result<Option<String>, HealthError> {
+        let Some(event_service) = self.get::<EventService>(EVENT_SERVICE_PATH).await? else {
+            return Ok(None);
+        };
+        let Some(subscriptions) = event_service.subscriptions else {
+            return Ok(None);
+        };
+        if event_service.service_enabled == Some(false) {
+            return Ok(None);
+        }
+        let Some(collection) = self.get::<Collection>(&subscriptions.odata_id).await? else {
+            return Ok(None);
+        };
+
+        let mut current = None;
+        for member in collection.members {
+            let Some(subscription) = self.get::<EventDestination>(&member.odata_id).await? else {
+                continue;
+            };
+            if subscription.destination.as_deref() != Some(destination) {
+                continue;
+            }
+            if current.is_none() && subscription.context.as_deref() == Some(context) {
+                current = Some(subscription.odata_id);
+            } else {
+                tracing::info!(
+                    subscription = %subscription.odata_id,
+                    "Removing stale Redfish event subscription"
+                );
+                self.delete_subscription(&subscription.odata_id).await?;
+            }
+        }
+
+        match current {
+            Some(uri) => Ok(Some(uri)),
+            None => self
+                .create_subscription(&subscriptions.odata_id, destination, context)
+                .await
+                .map(Some),
+        }
+    }
+
+    async fn create_subscription(
+        &self,
+        collection_path: &str,
+        destination: &str,
+        context: &str,
+    ) -> Result<String, HealthError> {
+        let request = self
+            .request(reqwest::Method::POST, collection_path)?
+            .json(&json!({
+                "Destination": destination,
+                "Context": context,
+                "Protocol": "Redfish",
+            }));
+        let response = self.send(request).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(HealthError::GenericError(format!(
+                "Creating event subscription returned status {status}"
+            )));
+        }
+
+        let location = response
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                // Some BMCs return absolute URLs
+                Url::parse(v)
+                    .map(|url| url.path().to_string())
+                    .unwrap_or_else(|_| v.to_string())
+            });
+        match location {
+            Some(location) => Ok(location),
+            None => response
+                .json::<EventDestination>()
+                .await
+                .map(|subscription| subscription.odata_id)
+                .map_err(|e| {
+                    HealthError::GenericError(format!(
+                        "Could not determine URI of created event subscription: {e}"
+                    ))
+                }),
+        }
+    }
+
+    pub(super) async fn delete_subscription(&self, uri: &str) -> Result<(), HealthError> {
+        let response = self
+            .send(self.request(reqwest::Method::DELETE, uri)?)
+            .await?;
+        match response.status() {
+            status if status.is_success() || status == StatusCode::NOT_FOUND => Ok(()),
+            status => Err(HealthError::GenericError(format!(
+                "DELETE {uri} returned status {status}"
+            ))),
+        }
+    }
+}
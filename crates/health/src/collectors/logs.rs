@@ -32,6 +32,7 @@ use tokio::sync::Mutex;
 use crate::HealthError;
 use crate::collectors::{IterationResult, PeriodicCollector};
 use crate::endpoint::{BmcEndpoint, EndpointMetadata};
+use crate::sharding::CollectorStateStore;
 use crate::sink::{CollectorEvent, DataSink, EventContext, LogRecord};
 
 /// Configuration for logs collector
@@ -40,6 +41,8 @@ pub struct LogsCollectorConfig {
     pub service_refresh_interval: Duration,
     pub log_writer: Arc<Mutex<LogFileWriter>>,
     pub data_sink: Option<Arc<dyn DataSink>>,
+    /// Receives the cursors when the endpoint moves to another replica
+    pub state_store: Option<Arc<dyn CollectorStateStore>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -242,6 +245,9 @@ pub struct LogsCollector<B: Bmc> {
     service_refresh_interval: Duration,
     log_writer: Arc<Mutex<LogFileWriter>>,
     data_sink: Option<Arc<dyn DataSink>>,
+    state_store: Option<Arc<dyn CollectorStateStore>>,
+    /// Cursors which were last stored in the state store
+    handed_over_ids: Option<HashMap<ODataId, i32>>,
 }
 
 impl<B: Bmc + 'static> PeriodicCollector<B> for LogsCollector<B> {
@@ -262,6 +268,8 @@ impl<B: Bmc + 'static> PeriodicCollector<B> for LogsCollector<B> {
             service_refresh_interval: config.service_refresh_interval,
             log_writer: config.log_writer,
             data_sink: config.data_sink,
+            state_store: config.state_store,
+            handed_over_ids: None,
         })
     }
 
@@ -269,6 +277,15 @@ impl<B: Bmc + 'static> PeriodicCollector<B> for LogsCollector<B> {
         self.run_collection_iteration().await
     }
 
+    async fn shutdown(&mut self) {
+        // The endpoint moved to another replica or was removed. The local
+        // state file is dropped once the cursors are handed over, so that it
+        // can't shadow newer cursors if the endpoint moves back later.
+        if self.state_store.is_some() && self.hand_over_state().await {
+            let _ = tokio::fs::remove_file(&self.state_file_path).await;
+        }
+    }
+
     fn collector_type(&self) -> &'static str {
         "logs_collector"
     }
@@ -294,7 +311,60 @@ impl<B: Bmc + 'static> LogsCollector<B> {
     async fn load_persistent_state(&self) -> PersistentState {
         match tokio::fs::read_to_string(&self.state_file_path).await {
             Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
-            Err(_) => PersistentState::default(),
+            Err(_) => self.load_handed_over_state().await.unwrap_or_default(),
+        }
+    }
+
+    /// Loads the cursors which were handed over by the replica which
+    /// monitored the endpoint before
+    async fn load_handed_over_state(&self) -> Option<PersistentState> {
+        let state_store = self.state_store.as_ref()?;
+        let endpoint_key = self.endpoint.addr.hash_key();
+        match state_store.load(&endpoint_key, self.collector_type()).await {
+            Ok(state) => {
+                let state = serde_json::from_str(&state?).ok()?;
+                tracing::info!(%endpoint_key, "Resuming log collection from handed over state");
+                Some(state)
+            }
+            Err(error) => {
+                tracing::warn!(?error, %endpoint_key, "Failed to load handed over log cursors");
+                None
+            }
+        }
+    }
+
+    /// Stores the cursors in the state store if they changed since the last
+    /// call. Returns whether the stored cursors are up to date.
+    async fn hand_over_state(&mut self) -> bool {
+        let (Some(state_store), Some(state)) = (&self.state_store, &self.state) else {
+            return false;
+        };
+        if self.handed_over_ids.as_ref() == Some(&state.last_seen_ids) {
+            return true;
+        }
+
+        let json = match serde_json::to_string(&PersistentStateRef {
+            last_seen_ids: &state.last_seen_ids,
+        }) {
+            Ok(json) => json,
+            Err(error) => {
+                tracing::warn!(?error, "Failed to serialize log cursors");
+                return false;
+            }
+        };
+        let endpoint_key = self.endpoint.addr.hash_key();
+        match state_store
+            .save(&endpoint_key, self.collector_type(), json)
+            .await
+        {
+            Ok(()) => {
+                self.handed_over_ids = Some(state.last_seen_ids.clone());
+                true
+            }
+            Err(error) => {
+                tracing::warn!(?error, %endpoint_key, "Failed to store log cursors for handover");
+                false
+            }
         }
     }
 
@@ -403,6 +473,7 @@ impl<B: Bmc + 'static> LogsCollector<B> {
 
         let log_count = self.collect_logs_from_services().await?;
         self.save_persistent_state().await?;
+        self.hand_over_state().await;
 
         Ok(IterationResult {
             refresh_triggered,
@@ -565,6 +636,7 @@ impl<B: Bmc + 'static> LogsCollector<B> {
                         (Cow::Borrowed("entry_id"), entry.base.id.clone()),
                         (Cow::Borrowed("service_id"), service_id.clone()),
                     ],
+                    timestamp: entry.created.as_ref().map(|dt| SystemTime::from(*dt)),
                 });
                 if let Some(sink) = &self.data_sink {
                     sink.handle_event(&self.event_context, &log_event);
@@ -15,12 +15,14 @@
  * limitations under the License.
  */
 
+mod events;
 mod firmware;
 mod logs;
 mod nmxt;
 mod runtime;
 mod sensors;
 
+pub use events::{EventRouter, EventsCollector, EventsCollectorConfig, run_event_listener};
 pub use firmware::{FirmwareCollector, FirmwareCollectorConfig};
 pub use logs::{LogFileWriter, LogsCollector, LogsCollectorConfig, create_log_file_writer};
 pub use nmxt::{NmxtCollector, NmxtCollectorConfig};
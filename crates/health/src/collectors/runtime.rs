@@ -59,6 +59,12 @@ pub trait PeriodicCollector<B: Bmc>: Send + 'static {
         &mut self,
     ) -> impl std::future::Future<Output = Result<IterationResult, HealthError>> + Send;
 
+    /// Releases state which the collector holds outside of this process,
+    /// e.g. subscriptions on the BMC. Invoked once after the collector stopped.
+    fn shutdown(&mut self) -> impl std::future::Future<Output = ()> + Send {
+        async {}
+    }
+
     /// Returns the type identifier for this collector
     fn collector_type(&self) -> &'static str;
 }
@@ -204,6 +210,7 @@ impl Collector {
                     }
                 }
             }
+            runner.shutdown().await;
         });
 
         Ok(Self {
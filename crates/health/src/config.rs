@@ -44,6 +44,10 @@ pub struct Config {
     /// Total number of shards in the StatefulSet
     pub shards_count: usize,
 
+    /// Dynamic shard membership via leases in the Carbide API. If enabled,
+    /// `shard` and `shards_count` are ignored.
+    pub shard_membership: Configurable<ShardMembershipConfig>,
+
     /// Maximum cache size per BMC, uses etags
     pub cache_size: usize,
 
@@ -61,6 +65,7 @@ impl Default for Config {
             metrics: MetricsConfig::default(),
             shard: 0,
             shards_count: 1,
+            shard_membership: Configurable::Disabled,
             cache_size: 100,
             bmc_proxy_url: None,
         }
@@ -122,6 +127,9 @@ pub struct SinksConfig {
     /// Health override sink: sends health override events to Carbide API.
     #[serde(alias = "carbide_override")]
     pub health_override: Configurable<CarbideApiConnectionConfig>,
+
+    /// OTLP sink: exports metric and log events to an OpenTelemetry collector.
+    pub otlp: Configurable<OtlpSinkConfig>,
 }
 
 impl Default for SinksConfig {
@@ -130,6 +138,7 @@ impl Default for SinksConfig {
             tracing: Configurable::Enabled(TracingSinkConfig::default()),
             prometheus: Configurable::Enabled(PrometheusSinkConfig::default()),
             health_override: Configurable::Enabled(CarbideApiConnectionConfig::default()),
+            otlp: Configurable::Disabled,
         }
     }
 }
@@ -142,6 +151,75 @@ pub struct TracingSinkConfig {}
 #[serde(default)]
 pub struct PrometheusSinkConfig {}
 
+/// Transport used to deliver OTLP exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    /// OTLP/gRPC, usually served on port 4317
+    Grpc,
+    /// OTLP/HTTP with JSON encoded payloads, usually served on port 4318
+    HttpJson,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtlpSinkConfig {
+    /// Endpoint of the OTLP receiver. For `http_json`, the signal paths
+    /// `v1/metrics` and `v1/logs` are appended to it.
+    pub endpoint: Url,
+
+    pub protocol: OtlpProtocol,
+
+    /// Maximum time a record is buffered before it gets exported.
+    #[serde(with = "humantime_serde")]
+    pub export_interval: Duration,
+
+    /// Timeout of a single export request.
+    #[serde(with = "humantime_serde")]
+    pub export_timeout: Duration,
+
+    /// Maximum number of records in a single export.
+    pub max_batch_size: usize,
+
+    /// Maximum number of records waiting for export. Further records are
+    /// dropped while the queue is full.
+    pub max_queue_size: usize,
+
+    /// How often a failed export is retried before its records are dropped.
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled on every further retry.
+    #[serde(with = "humantime_serde")]
+    pub initial_retry_backoff: Duration,
+
+    #[serde(with = "humantime_serde")]
+    pub max_retry_backoff: Duration,
+
+    /// Export sensor readings as OTLP metrics.
+    pub export_metrics: bool,
+
+    /// Export BMC log entries and events as OTLP logs.
+    pub export_logs: bool,
+}
+
+impl Default for OtlpSinkConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: Url::parse("http://localhost:4317").unwrap(),
+            protocol: OtlpProtocol::Grpc,
+            export_interval: Duration::from_secs(5),
+            export_timeout: Duration::from_secs(10),
+            max_batch_size: 1000,
+            max_queue_size: 10000,
+            max_retries: 5,
+            initial_retry_backoff: Duration::from_secs(1),
+            max_retry_backoff: Duration::from_secs(30),
+            export_metrics: true,
+            export_logs: true,
+        }
+    }
+}
+
 /// Shared Carbide API connection configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -170,6 +248,51 @@ impl Default for CarbideApiConnectionConfig {
     }
 }
 
+/// Configuration for dynamic shard membership. Replicas renew a lease in the
+/// Carbide API and BMC endpoints are assigned to all replicas with an
+/// unexpired lease via rendezvous hashing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShardMembershipConfig {
+    /// Unique and stable ID of this replica. Defaults to the `HOSTNAME`
+    /// environment variable, which is the pod name in Kubernetes.
+    pub member_id: Option<String>,
+
+    /// How long the lease of this replica stays valid without being renewed.
+    #[serde(with = "humantime_serde")]
+    pub lease_duration: Duration,
+
+    /// Interval between lease renewals, must be shorter than `lease_duration`.
+    #[serde(with = "humantime_serde")]
+    pub renew_interval: Duration,
+}
+
+impl Default for ShardMembershipConfig {
+    fn default() -> Self {
+        Self {
+            member_id: None,
+            lease_duration: Duration::from_secs(30),
+            renew_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ShardMembershipConfig {
+    /// Returns the configured member ID, or the hostname if none is configured
+    pub fn resolve_member_id(&self) -> Result<String, String> {
+        match &self.member_id {
+            Some(member_id) if !member_id.trim().is_empty() => Ok(member_id.clone()),
+            _ => std::env::var("HOSTNAME")
+                .ok()
+                .filter(|hostname| !hostname.trim().is_empty())
+                .ok_or_else(|| {
+                    "shard_membership.member_id must be set if HOSTNAME is not available"
+                        .to_string()
+                }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RateLimitConfig {
@@ -201,6 +324,9 @@ pub struct CollectorsConfig {
 
     /// Switch NMX-T collector configuration (if present, nmxt collector is enabled)
     pub nmxt: Configurable<NmxtCollectorConfig>,
+
+    /// Redfish event subscription collector configuration (if present, events collector is enabled)
+    pub events: Configurable<EventsCollectorConfig>,
 }
 
 impl Default for CollectorsConfig {
@@ -210,6 +336,7 @@ impl Default for CollectorsConfig {
             firmware: Configurable::Disabled,
             logs: Configurable::Disabled,
             nmxt: Configurable::Disabled,
+            events: Configurable::Disabled,
         }
     }
 }
@@ -317,6 +444,47 @@ impl Default for NmxtCollectorConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventsCollectorConfig {
+    /// Address of the HTTPS listener which receives events from BMCs.
+    pub listen_addr: String,
+
+    /// URL under which BMCs reach the listener, used as subscription destination.
+    pub destination_url: Url,
+
+    /// Path to the TLS certificate of the listener.
+    pub tls_cert: String,
+
+    /// Path to the TLS key of the listener.
+    pub tls_key: String,
+
+    /// Interval between checks that the subscription still exists on the BMC.
+    #[serde(with = "humantime_serde")]
+    pub subscription_refresh_interval: Duration,
+
+    /// Interval between subscription attempts on BMCs without an active
+    /// subscription. Their sensors and logs are polled in the meantime.
+    #[serde(with = "humantime_serde")]
+    pub subscription_retry_interval: Duration,
+}
+
+impl Default for EventsCollectorConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: "0.0.0.0:9443".to_string(),
+            destination_url: Url::parse(
+                "https://carbide-hw-health.forge-system.svc.cluster.local:9443/redfish/events",
+            )
+            .unwrap(),
+            tls_cert: "/var/run/secrets/spiffe.io/tls.crt".to_string(),
+            tls_key: "/var/run/secrets/spiffe.io/tls.key".to_string(),
+            subscription_refresh_interval: Duration::from_secs(1800),
+            subscription_retry_interval: Duration::from_secs(300),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct MetricsConfig {
@@ -391,6 +559,42 @@ impl Config {
 
         self.metrics_addr()?;
 
+        if let Configurable::Enabled(membership) = &self.shard_membership {
+            if !self.endpoint_sources.carbide_api.is_enabled() {
+                return Err(
+                    "shard_membership requires the carbide_api endpoint source to be enabled"
+                        .to_string(),
+                );
+            }
+            if membership.renew_interval.is_zero()
+                || membership.renew_interval >= membership.lease_duration
+            {
+                return Err(format!(
+                    "shard_membership.renew_interval ({:?}) must be greater than 0 and less than lease_duration ({:?})",
+                    membership.renew_interval, membership.lease_duration
+                ));
+            }
+        }
+
+        if let Configurable::Enabled(otlp) = &self.sinks.otlp {
+            if otlp.max_batch_size == 0 || otlp.max_batch_size > otlp.max_queue_size {
+                return Err(format!(
+                    "sinks.otlp.max_batch_size ({}) must be greater than 0 and at most max_queue_size ({})",
+                    otlp.max_batch_size, otlp.max_queue_size
+                ));
+            }
+            if otlp.export_interval.is_zero() {
+                return Err("sinks.otlp.export_interval must be greater than 0".to_string());
+            }
+        }
+
+        if let Configurable::Enabled(events) = &self.collectors.events {
+            events
+                .listen_addr
+                .parse::<SocketAddr>()
+                .map_err(|_| format!("Invalid events listen address: {}", events.listen_addr))?;
+        }
+
         Ok(())
     }
 }
@@ -498,9 +702,19 @@ mod tests {
         assert!(config.collectors.sensors.is_enabled());
         assert!(config.collectors.firmware.is_enabled());
         assert!(config.collectors.logs.is_enabled());
+        assert!(config.collectors.events.is_enabled());
         assert!(!config.sinks.tracing.is_enabled());
         assert!(config.sinks.prometheus.is_enabled());
 
+        if let Configurable::Enabled(ref otlp) = config.sinks.otlp {
+            assert_eq!(otlp.endpoint.as_str(), "http://otel-collector:4317/");
+            assert_eq!(otlp.protocol, OtlpProtocol::Grpc);
+            assert_eq!(otlp.export_interval, Duration::from_secs(5));
+            assert_eq!(otlp.max_queue_size, 10000);
+        } else {
+            panic!("otlp sink empty")
+        }
+
         if let Configurable::Enabled(ref sensors) = config.collectors.sensors {
             assert_eq!(sensors.rediscover_interval, Duration::from_secs(300));
             assert_eq!(sensors.sensor_fetch_concurrency, 10);
@@ -514,11 +728,26 @@ mod tests {
             panic!("logs empty")
         }
 
+        if let Configurable::Enabled(ref events) = config.collectors.events {
+            assert_eq!(events.listen_addr, "0.0.0.0:9443");
+            assert_eq!(events.subscription_retry_interval, Duration::from_secs(300));
+        } else {
+            panic!("events empty")
+        }
+
         assert_eq!(config.metrics.endpoint, "0.0.0.0:9009");
 
         assert_eq!(config.shard, 0);
         assert_eq!(config.shards_count, 1);
 
+        if let Configurable::Enabled(ref membership) = config.shard_membership {
+            assert_eq!(membership.member_id, None);
+            assert_eq!(membership.lease_duration, Duration::from_secs(30));
+            assert_eq!(membership.renew_interval, Duration::from_secs(10));
+        } else {
+            panic!("shard membership empty")
+        }
+
         assert_eq!(config.cache_size, 100);
     }
 
@@ -592,6 +821,8 @@ cache_size = 50
 
         assert!(!config.collectors.firmware.is_enabled());
         assert!(!config.collectors.logs.is_enabled());
+        assert!(!config.collectors.events.is_enabled());
+        assert!(!config.shard_membership.is_enabled());
 
         config.validate().expect("config should be valid");
     }
@@ -610,12 +841,41 @@ cache_size = 50
         config.shards_count = 1;
         assert!(config.validate().is_ok());
 
+        config.shard_membership = Configurable::Enabled(ShardMembershipConfig {
+            member_id: Some("carbide-hw-health-0".to_string()),
+            lease_duration: Duration::from_secs(10),
+            renew_interval: Duration::from_secs(10),
+        });
+        assert!(config.validate().is_err());
+
+        config.shard_membership = Configurable::Enabled(ShardMembershipConfig {
+            member_id: Some("carbide-hw-health-0".to_string()),
+            ..Default::default()
+        });
+        assert!(config.validate().is_ok());
+
+        config.endpoint_sources.carbide_api = Configurable::Disabled;
+        assert!(config.validate().is_err());
+        config.endpoint_sources.carbide_api =
+            Configurable::Enabled(CarbideApiConnectionConfig::default());
+        config.shard_membership = Configurable::Disabled;
+
         config.rate_limit = Configurable::Enabled(RateLimitConfig {
             bucket_burst: 200,
             bucket_replenish: Duration::from_secs(0),
             max_jitter: Duration::from_secs(0),
         });
         assert!(config.validate().is_err());
+        config.rate_limit = Configurable::Enabled(RateLimitConfig::default());
+
+        config.sinks.otlp = Configurable::Enabled(OtlpSinkConfig {
+            max_batch_size: 100,
+            max_queue_size: 10,
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
+        config.sinks.otlp = Configurable::Enabled(OtlpSinkConfig::default());
+        assert!(config.validate().is_ok());
     }
 
     #[test]
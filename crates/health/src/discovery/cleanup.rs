@@ -34,6 +34,8 @@ fn stop_collectors_for_keys(
                 "{}",
                 kind.stop_message()
             );
+            // Stopping also removes state the collector holds on the BMC,
+            // e.g. the Redfish event subscription of the events collector.
             tokio::spawn(async move {
                 collector.stop().await;
             });
@@ -57,6 +59,7 @@ pub(super) fn stop_removed_bmc_collectors(
             remaining_collectors = ctx.collectors.len(CollectorKind::Logs),
             remaining_firmware_collectors = ctx.collectors.len(CollectorKind::Firmware),
             remaining_nmxt_collectors = ctx.collectors.len(CollectorKind::Nmxt),
+            remaining_events_collectors = ctx.collectors.len(CollectorKind::Events),
             "Cleaned up removed endpoints"
         );
     }
@@ -81,6 +84,7 @@ mod tests {
         );
         maps.insert(CollectorKind::Firmware, HashMap::new());
         maps.insert(CollectorKind::Nmxt, HashMap::new());
+        maps.insert(CollectorKind::Events, HashMap::from([("d".to_string(), 5)]));
 
         let active = HashSet::from(["b".to_string()]);
 
@@ -91,6 +95,9 @@ mod tests {
             .cloned()
             .collect();
 
-        assert_eq!(removed, HashSet::from(["a".to_string(), "c".to_string()]));
+        assert_eq!(
+            removed,
+            HashSet::from(["a".to_string(), "c".to_string(), "d".to_string()])
+        );
     }
 }
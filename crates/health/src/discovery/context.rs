@@ -23,17 +23,19 @@ use nv_redfish::bmc_http::HttpBmc;
 use nv_redfish::bmc_http::reqwest::{
     BmcError, Client as ReqwestClient, ClientParams as ReqwestClientParams,
 };
-use prometheus::{Histogram, HistogramOpts};
+use prometheus::{GaugeVec, Histogram, HistogramOpts, Opts};
 
 use crate::HealthError;
-use crate::collectors::Collector;
+use crate::collectors::{Collector, EventRouter};
 use crate::config::{
-    Config, Configurable, FirmwareCollectorConfig as FirmwareCollectorOptions,
+    Config, Configurable, EventsCollectorConfig as EventsCollectorOptions,
+    FirmwareCollectorConfig as FirmwareCollectorOptions,
     LogsCollectorConfig as LogsCollectorOptions, NmxtCollectorConfig as NmxtCollectorOptions,
     SensorCollectorConfig as SensorCollectorOptions,
 };
 use crate::limiter::RateLimiter;
 use crate::metrics::{MetricsManager, operation_duration_buckets_seconds};
+use crate::sharding::CollectorStateStore;
 
 pub(crate) type BmcClient = HttpBmc<ReqwestClient>;
 
@@ -43,14 +45,16 @@ pub(super) enum CollectorKind {
     Logs,
     Firmware,
     Nmxt,
+    Events,
 }
 
 impl CollectorKind {
-    pub(super) const ALL: [CollectorKind; 4] = [
+    pub(super) const ALL: [CollectorKind; 5] = [
         CollectorKind::Sensor,
         CollectorKind::Logs,
         CollectorKind::Firmware,
         CollectorKind::Nmxt,
+        CollectorKind::Events,
     ];
 
     pub(super) fn stop_message(self) -> &'static str {
@@ -59,6 +63,9 @@ impl CollectorKind {
             CollectorKind::Logs => "Stopping logs collector for removed BMC endpoint",
             CollectorKind::Firmware => "Stopping firmware collector for removed BMC endpoint",
             CollectorKind::Nmxt => "Stopping NMX-T collector for removed BMC endpoint",
+            CollectorKind::Events => {
+                "Stopping events collector and removing its Redfish event subscription for removed BMC endpoint"
+            }
         }
     }
 }
@@ -68,6 +75,7 @@ pub(super) struct CollectorState {
     firmware: HashMap<Cow<'static, str>, Collector>,
     logs: HashMap<Cow<'static, str>, Collector>,
     nmxt: HashMap<Cow<'static, str>, Collector>,
+    events: HashMap<Cow<'static, str>, Collector>,
 }
 
 impl CollectorState {
@@ -77,6 +85,7 @@ impl CollectorState {
             firmware: HashMap::new(),
             logs: HashMap::new(),
             nmxt: HashMap::new(),
+            events: HashMap::new(),
         }
     }
 
@@ -86,6 +95,7 @@ impl CollectorState {
             CollectorKind::Logs => &self.logs,
             CollectorKind::Firmware => &self.firmware,
             CollectorKind::Nmxt => &self.nmxt,
+            CollectorKind::Events => &self.events,
         }
     }
 
@@ -95,6 +105,7 @@ impl CollectorState {
             CollectorKind::Logs => &mut self.logs,
             CollectorKind::Firmware => &mut self.firmware,
             CollectorKind::Nmxt => &mut self.nmxt,
+            CollectorKind::Events => &mut self.events,
         }
     }
 
@@ -131,6 +142,7 @@ impl CollectorState {
             .chain(self.logs.keys())
             .chain(self.firmware.keys())
             .chain(self.nmxt.keys())
+            .chain(self.events.keys())
             .filter(|key| !active_keys.contains(*key))
             .cloned()
             .collect()
@@ -141,7 +153,12 @@ pub struct DiscoveryLoopContext {
     pub(super) collectors: CollectorState,
     pub(crate) discovery_iteration_histogram: Histogram,
     pub(crate) discovery_endpoint_fetch_histogram: Histogram,
+    pub(crate) shard_assignment_gauge: GaugeVec,
+    pub(crate) shard_owner_gauge: GaugeVec,
+    pub(crate) collector_state_store: Option<Arc<dyn CollectorStateStore>>,
     pub(crate) client: ReqwestClient,
+    pub(crate) event_http_client: reqwest::Client,
+    pub(crate) event_router: Arc<EventRouter>,
     pub(crate) limiter: Arc<dyn RateLimiter>,
     pub(crate) metrics_manager: Arc<MetricsManager>,
     pub(crate) config: Arc<Config>,
@@ -149,9 +166,23 @@ pub struct DiscoveryLoopContext {
     pub(crate) logs_config: Configurable<LogsCollectorOptions>,
     pub(crate) firmware_config: Configurable<FirmwareCollectorOptions>,
     pub(crate) nmxt_config: Configurable<NmxtCollectorOptions>,
+    pub(crate) events_config: Configurable<EventsCollectorOptions>,
 }
 
 impl DiscoveryLoopContext {
+    /// Returns the router which forwards events received by the event
+    /// listener to the events collectors of this context
+    pub fn event_router(&self) -> Arc<EventRouter> {
+        self.event_router.clone()
+    }
+
+    /// Hands over collector state via the given store when endpoints move
+    /// to another replica
+    pub fn with_collector_state_store(mut self, store: Arc<dyn CollectorStateStore>) -> Self {
+        self.collector_state_store = Some(store);
+        self
+    }
+
     pub fn new(
         limiter: Arc<dyn RateLimiter>,
         metrics_manager: Arc<MetricsManager>,
@@ -179,20 +210,50 @@ impl DiscoveryLoopContext {
         )?;
         registry.register(Box::new(discovery_endpoint_fetch_histogram.clone()))?;
 
+        let shard_assignment_gauge = GaugeVec::new(
+            Opts::new(
+                format!("{metrics_prefix}_shard_assigned_endpoints"),
+                "BMC endpoints which are assigned to this replica",
+            ),
+            &["endpoint_key"],
+        )?;
+        registry.register(Box::new(shard_assignment_gauge.clone()))?;
+
+        // Every replica computes the same owners, unless their views of
+        // the members differ, which then shows up as conflicting owners.
+        let shard_owner_gauge = GaugeVec::new(
+            Opts::new(
+                format!("{metrics_prefix}_shard_endpoint_owner"),
+                "Replica which monitors each discovered BMC endpoint, as seen by this replica",
+            ),
+            &["endpoint_key", "owner"],
+        )?;
+        registry.register(Box::new(shard_owner_gauge.clone()))?;
+
         let client =
             ReqwestClient::with_params(ReqwestClientParams::new().accept_invalid_certs(true))
                 .map_err(BmcError::ReqwestError)?;
+        let event_http_client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .map_err(|e| HealthError::GenericError(format!("Failed to build HTTP client: {e}")))?;
 
         let sensors_config = config.collectors.sensors.clone();
         let logs_config = config.collectors.logs.clone();
         let firmware_config = config.collectors.firmware.clone();
         let nmxt_config = config.collectors.nmxt.clone();
+        let events_config = config.collectors.events.clone();
 
         Ok(Self {
             collectors: CollectorState::new(),
             discovery_iteration_histogram,
             discovery_endpoint_fetch_histogram,
+            shard_assignment_gauge,
+            shard_owner_gauge,
+            collector_state_store: None,
             client,
+            event_http_client,
+            event_router: Arc::new(EventRouter::default()),
             limiter,
             metrics_manager,
             config,
@@ -200,6 +261,7 @@ impl DiscoveryLoopContext {
             logs_config,
             firmware_config,
             nmxt_config,
+            events_config,
         })
     }
 }
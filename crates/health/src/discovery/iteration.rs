@@ -36,6 +36,21 @@ fn active_keys(sharded_endpoints: &[Arc<BmcEndpoint>]) -> HashSet<Cow<'static, s
         .collect()
 }
 
+/// Returns the owner of each endpoint, leaving out endpoints without one
+fn endpoint_owners(
+    shard_manager: &ShardManager,
+    endpoints: &[Arc<BmcEndpoint>],
+) -> Vec<(Cow<'static, str>, String)> {
+    endpoints
+        .iter()
+        .filter_map(|e| {
+            let key = e.addr.hash_key();
+            let owner = shard_manager.owner(&key)?;
+            Some((key, owner))
+        })
+        .collect()
+}
+
 pub async fn run_discovery_iteration(
     endpoint_source: Arc<dyn EndpointSource>,
     shard_manager: &ShardManager,
@@ -65,10 +80,15 @@ pub async fn run_discovery_iteration(
         .collect();
 
     if sharded_endpoints.is_empty() {
-        tracing::warn!("No endpoints assigned to this shard");
+        tracing::warn!(
+            member_id = shard_manager.member_id(),
+            "No endpoints assigned to this shard"
+        );
     } else {
         tracing::info!(
             endpoint_count = sharded_endpoints.len(),
+            member_id = shard_manager.member_id(),
+            shard_members = shard_manager.members().len(),
             "Discovered and sharded BMC endpoints"
         );
     }
@@ -80,6 +100,20 @@ pub async fn run_discovery_iteration(
     let active_endpoints = active_keys(&sharded_endpoints);
     stop_removed_bmc_collectors(ctx, &active_endpoints);
 
+    ctx.shard_assignment_gauge.reset();
+    for key in &active_endpoints {
+        ctx.shard_assignment_gauge
+            .with_label_values(&[key.as_ref()])
+            .set(1.0);
+    }
+
+    ctx.shard_owner_gauge.reset();
+    for (key, owner) in endpoint_owners(shard_manager, &endpoints) {
+        ctx.shard_owner_gauge
+            .with_label_values(&[key.as_ref(), owner.as_str()])
+            .set(1.0);
+    }
+
     let iteration_duration = iteration_start.elapsed();
     ctx.discovery_iteration_histogram
         .observe(iteration_duration.as_secs_f64());
@@ -115,6 +149,7 @@ mod tests {
             metadata: if switch {
                 Some(EndpointMetadata::Switch(SwitchData {
                     serial: format!("serial-{mac}"),
+                    rack_id: None,
                 }))
             } else {
                 None
@@ -134,4 +169,24 @@ mod tests {
             HashSet::from([ep1.addr.hash_key(), ep2.addr.hash_key()])
         );
     }
+
+    #[test]
+    fn test_endpoint_owners() {
+        let ep1 = endpoint(MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(), false);
+        let ep2 = endpoint(MacAddress::from_str("11:22:33:44:55:66").unwrap(), true);
+        let endpoints = [ep1.clone(), ep2.clone()];
+
+        // Without members, nobody owns an endpoint
+        let manager = ShardManager::with_member_id("member-0".to_string());
+        assert!(endpoint_owners(&manager, &endpoints).is_empty());
+
+        // Endpoints owned by other members are included as well
+        manager.update_members(vec!["member-0".to_string(), "member-1".to_string()]);
+        let owners = endpoint_owners(&manager, &endpoints);
+        assert_eq!(owners.len(), 2);
+        for (ep, (key, owner)) in endpoints.iter().zip(&owners) {
+            assert_eq!(*key, ep.addr.hash_key());
+            assert_eq!(Some(owner), manager.owner(key).as_ref());
+        }
+    }
 }
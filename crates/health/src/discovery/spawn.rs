@@ -21,9 +21,9 @@ use std::sync::Arc;
 use super::context::{BmcClient, CollectorKind, DiscoveryLoopContext};
 use crate::HealthError;
 use crate::collectors::{
-    Collector, FirmwareCollector, FirmwareCollectorConfig, LogsCollector, LogsCollectorConfig,
-    NmxtCollector, NmxtCollectorConfig, SensorCollector, SensorCollectorConfig,
-    create_log_file_writer,
+    Collector, EventsCollector, EventsCollectorConfig, FirmwareCollector, FirmwareCollectorConfig,
+    LogsCollector, LogsCollectorConfig, NmxtCollector, NmxtCollectorConfig, SensorCollector,
+    SensorCollectorConfig, create_log_file_writer,
 };
 use crate::config::Configurable;
 use crate::endpoint::{BmcEndpoint, EndpointMetadata};
@@ -33,6 +33,23 @@ fn logs_state_file_path(template: &str, endpoint_id: &str) -> PathBuf {
     PathBuf::from(template.replace("{machine_id}", endpoint_id))
 }
 
+/// Stops the polling collectors of an endpoint whose BMC delivers its events
+/// to the event listener. They are started again once the subscription is lost.
+fn pause_polling_collectors(ctx: &mut DiscoveryLoopContext, key: &str) {
+    for kind in [CollectorKind::Sensor, CollectorKind::Logs] {
+        if let Some(collector) = ctx.collectors.map_mut_for_kind(kind).remove(key) {
+            tracing::info!(
+                endpoint_key = %key,
+                collector = ?kind,
+                "Pausing polling collector for BMC endpoint with event subscription"
+            );
+            tokio::spawn(async move {
+                collector.stop().await;
+            });
+        }
+    }
+}
+
 pub(super) async fn spawn_collectors_for_endpoint(
     ctx: &mut DiscoveryLoopContext,
     endpoint: &Arc<BmcEndpoint>,
@@ -41,7 +58,13 @@ pub(super) async fn spawn_collectors_for_endpoint(
 ) -> Result<(), HealthError> {
     let key = endpoint.addr.hash_key();
     let endpoint_arc = endpoint.clone();
+    let subscribed = ctx.event_router.is_subscribed(&key);
+    if subscribed {
+        pause_polling_collectors(ctx, &key);
+    }
+
     if let Configurable::Enabled(sensor_cfg) = &ctx.sensors_config
+        && !subscribed
         && !ctx.collectors.contains(CollectorKind::Sensor, &key)
     {
         let collector_registry = Arc::new(ctx.metrics_manager.create_collector_registry(
@@ -82,6 +105,7 @@ pub(super) async fn spawn_collectors_for_endpoint(
     }
 
     if let Configurable::Enabled(logs_cfg) = &ctx.logs_config
+        && !subscribed
         && !ctx.collectors.contains(CollectorKind::Logs, &key)
     {
         let endpoint_id = endpoint.log_identity().into_owned();
@@ -121,6 +145,7 @@ pub(super) async fn spawn_collectors_for_endpoint(
                     service_refresh_interval: logs_cfg.state_refresh_interval,
                     log_writer,
                     data_sink: data_sink.clone(),
+                    state_store: ctx.collector_state_store.clone(),
                 },
                 collector_registry,
                 ctx.client.clone(),
@@ -183,6 +208,48 @@ pub(super) async fn spawn_collectors_for_endpoint(
         }
     }
 
+    if let Configurable::Enabled(events_cfg) = &ctx.events_config
+        && !ctx.collectors.contains(CollectorKind::Events, &key)
+    {
+        let collector_registry = Arc::new(ctx.metrics_manager.create_collector_registry(
+            format!("events_collector_{}", endpoint.addr.hash_key()),
+            metrics_prefix,
+        )?);
+        match Collector::start::<EventsCollector>(
+            endpoint_arc.clone(),
+            ctx.limiter.clone(),
+            events_cfg.subscription_retry_interval,
+            EventsCollectorConfig {
+                data_sink: data_sink.clone(),
+                event_router: ctx.event_router.clone(),
+                http_client: ctx.event_http_client.clone(),
+                bmc_proxy_url: ctx.config.bmc_proxy_url.clone(),
+                destination: events_cfg.destination_url.clone(),
+                subscription_refresh_interval: events_cfg.subscription_refresh_interval,
+            },
+            collector_registry,
+            ctx.client.clone(),
+            &ctx.config,
+        ) {
+            Ok(collector) => {
+                ctx.collectors
+                    .insert(CollectorKind::Events, key.clone(), collector);
+                tracing::info!(
+                    endpoint_key = %key,
+                    total_events_collectors = ctx.collectors.len(CollectorKind::Events),
+                    "Started events collection for BMC endpoint"
+                );
+            }
+            Err(error) => {
+                tracing::error!(
+                    ?error,
+                    "Could not start events collector for: {:?}",
+                    endpoint.addr
+                )
+            }
+        }
+    }
+
     if let Configurable::Enabled(nmxt_cfg) = &ctx.nmxt_config
         && !ctx.collectors.contains(CollectorKind::Nmxt, &key)
         && matches!(endpoint.metadata, Some(EndpointMetadata::Switch(_)))
@@ -277,6 +344,7 @@ mod tests {
             },
             metadata: Some(EndpointMetadata::Switch(SwitchData {
                 serial: "switch-serial-1".to_string(),
+                rack_id: None,
             })),
         };
 
@@ -290,6 +358,7 @@ mod tests {
         config.collectors.logs = Configurable::Disabled;
         config.collectors.firmware = Configurable::Disabled;
         config.collectors.nmxt = Configurable::Disabled;
+        config.collectors.events = Configurable::Disabled;
 
         let limiter: Arc<dyn RateLimiter> = Arc::new(NoopLimiter);
         let metrics_manager = Arc::new(MetricsManager::new());
@@ -320,5 +389,6 @@ mod tests {
         assert_eq!(ctx.collectors.len(CollectorKind::Logs), 0);
         assert_eq!(ctx.collectors.len(CollectorKind::Firmware), 0);
         assert_eq!(ctx.collectors.len(CollectorKind::Nmxt), 0);
+        assert_eq!(ctx.collectors.len(CollectorKind::Events), 0);
     }
 }
@@ -22,6 +22,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
 use mac_address::MacAddress;
 use url::Url;
 
@@ -56,11 +57,13 @@ pub enum EndpointMetadata {
 pub struct MachineData {
     pub machine_id: MachineId,
     pub machine_serial: Option<String>,
+    pub rack_id: Option<RackId>,
 }
 
 #[derive(Clone, Debug)]
 pub struct SwitchData {
     pub serial: String,
+    pub rack_id: Option<RackId>,
 }
 
 #[derive(Clone)]
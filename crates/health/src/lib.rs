@@ -37,12 +37,15 @@ pub use config::Config;
 pub use discovery::{DiscoveryIterationStats, DiscoveryLoopContext};
 
 use crate::api_client::ApiClientWrapper;
+use crate::collectors::run_event_listener;
 use crate::config::Configurable;
 use crate::endpoint::{CompositeEndpointSource, EndpointSource, StaticEndpointSource};
 use crate::limiter::{BucketLimiter, NoopLimiter, RateLimiter};
 use crate::metrics::{MetricsManager, run_metrics_server};
-use crate::sharding::ShardManager;
-use crate::sink::{CompositeDataSink, DataSink, HealthOverrideSink, PrometheusSink, TracingSink};
+use crate::sharding::{ShardManager, ShardMembership, run_membership_loop};
+use crate::sink::{
+    CompositeDataSink, DataSink, HealthOverrideSink, OtlpSink, PrometheusSink, TracingSink,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum HealthError {
@@ -91,10 +94,12 @@ impl<B: nv_redfish::core::Bmc + 'static> From<nv_redfish::Error<B>> for HealthEr
 
 struct EndpointWiring {
     source: Arc<dyn EndpointSource>,
+    api_client: Option<Arc<ApiClientWrapper>>,
 }
 
 fn build_endpoint_wiring(config: &Config) -> Result<EndpointWiring, HealthError> {
     let mut sources: Vec<Arc<dyn EndpointSource>> = Vec::new();
+    let mut api_client = None;
 
     if !config.endpoint_sources.static_bmc_endpoints.is_empty() {
         let static_source = StaticEndpointSource::from_config(
@@ -104,14 +109,15 @@ fn build_endpoint_wiring(config: &Config) -> Result<EndpointWiring, HealthError>
     }
 
     if let Configurable::Enabled(ref source_cfg) = config.endpoint_sources.carbide_api {
-        let api_client = Arc::new(ApiClientWrapper::new(
+        let client = Arc::new(ApiClientWrapper::new(
             source_cfg.root_ca.clone(),
             source_cfg.client_cert.clone(),
             source_cfg.client_key.clone(),
             &source_cfg.api_url,
             config.collectors.nmxt.is_enabled(),
         ));
-        sources.push(api_client as Arc<dyn EndpointSource>);
+        sources.push(client.clone() as Arc<dyn EndpointSource>);
+        api_client = Some(client);
     }
 
     let composite_source = CompositeEndpointSource::new(sources);
@@ -124,6 +130,7 @@ fn build_endpoint_wiring(config: &Config) -> Result<EndpointWiring, HealthError>
 
     Ok(EndpointWiring {
         source: Arc::new(composite_source),
+        api_client,
     })
 }
 
@@ -139,7 +146,7 @@ fn build_data_sink(
 
     if let Configurable::Enabled(_) = &config.sinks.prometheus {
         sinks.push(Arc::new(PrometheusSink::new(
-            metrics_manager,
+            metrics_manager.clone(),
             &config.metrics.prefix,
         )?));
     }
@@ -148,6 +155,14 @@ fn build_data_sink(
         sinks.push(Arc::new(HealthOverrideSink::new(sink_cfg)?));
     }
 
+    if let Configurable::Enabled(ref sink_cfg) = config.sinks.otlp {
+        sinks.push(Arc::new(OtlpSink::new(
+            sink_cfg,
+            metrics_manager,
+            &config.metrics.prefix,
+        )?));
+    }
+
     let data_sink = match sinks.len() {
         0 => None,
         1 => Some(sinks.pop().expect("len() == 1 guarantees one element")),
@@ -190,33 +205,79 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
 
     let EndpointWiring {
         source: endpoint_source,
+        api_client,
     } = build_endpoint_wiring(&config)?;
 
     let data_sink = build_data_sink(&config, metrics_manager.clone())?;
 
     let config_arc = Arc::new(config);
 
+    let limiter: Arc<dyn RateLimiter> =
+        if let Configurable::Enabled(rate_limit) = &config_arc.rate_limit {
+            Arc::new(BucketLimiter::new(
+                rate_limit.bucket_burst,
+                rate_limit.bucket_replenish,
+                rate_limit.max_jitter,
+            ))
+        } else {
+            Arc::new(NoopLimiter)
+        };
+    let mut ctx = DiscoveryLoopContext::new(limiter, metrics_manager.clone(), config_arc.clone())?;
+
+    let (shard_manager, shard_membership) = match &config_arc.shard_membership {
+        Configurable::Enabled(membership_cfg) => {
+            let api_client = api_client.ok_or_else(|| {
+                HealthError::GenericError(
+                    "shard_membership requires the carbide_api endpoint source".to_string(),
+                )
+            })?;
+            ctx = ctx.with_collector_state_store(api_client.clone());
+            (
+                Arc::new(ShardManager::with_member_id(
+                    membership_cfg.resolve_member_id()?,
+                )),
+                Some((
+                    api_client as Arc<dyn ShardMembership>,
+                    membership_cfg.clone(),
+                )),
+            )
+        }
+        Configurable::Disabled => (
+            Arc::new(ShardManager::new(config_arc.shard, config_arc.shards_count)),
+            None,
+        ),
+    };
+
+    let join_membership = shard_membership
+        .clone()
+        .map(|(membership, membership_cfg)| {
+            tokio::spawn(run_membership_loop(
+                shard_manager.clone(),
+                membership,
+                membership_cfg,
+                metrics_manager.clone(),
+                config_arc.metrics.prefix.clone(),
+            ))
+        });
+
+    let join_events_listener = match &config_arc.collectors.events {
+        Configurable::Enabled(events_cfg) => Some(tokio::spawn(run_event_listener(
+            events_cfg.clone(),
+            ctx.event_router(),
+            metrics_manager.clone(),
+            config_arc.metrics.prefix.clone(),
+        ))),
+        Configurable::Disabled => None,
+    };
+
     let join_discovery: tokio::task::JoinHandle<Result<(), HealthError>> = tokio::spawn({
         let config = config_arc.clone();
-        let shard_manager = ShardManager::new(config.shard, config.shards_count);
-        let limiter: Arc<dyn RateLimiter> =
-            if let Configurable::Enabled(rate_limit) = &config.rate_limit {
-                Arc::new(BucketLimiter::new(
-                    rate_limit.bucket_burst,
-                    rate_limit.bucket_replenish,
-                    rate_limit.max_jitter,
-                ))
-            } else {
-                Arc::new(NoopLimiter)
-            };
-        let metrics_manager = metrics_manager.clone();
+        let shard_manager = shard_manager.clone();
         let active_endpoints_gauge = active_endpoints_gauge.clone();
         let discovery_endpoints_gauge = discovery_endpoints_gauge.clone();
         let endpoint_source = endpoint_source.clone();
         let data_sink = data_sink.clone();
 
-        let mut ctx = DiscoveryLoopContext::new(limiter, metrics_manager, config.clone())?;
-
         async move {
             loop {
                 let stats = discovery::run_discovery_iteration(
@@ -236,20 +297,62 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
                     .set(stats.sharded_endpoints as f64);
                 active_endpoints_gauge.set(stats.active_monitors as f64);
 
-                tokio::time::sleep(
-                    config
-                        .collectors
-                        .sensors
-                        .as_option()
-                        .map(|s| s.rediscover_interval)
-                        .unwrap_or(Duration::from_secs(300)),
-                )
-                .await;
+                let rediscover_interval = config
+                    .collectors
+                    .sensors
+                    .as_option()
+                    .map(|s| s.rediscover_interval)
+                    .unwrap_or(Duration::from_secs(300));
+                tokio::select! {
+                    _ = tokio::time::sleep(rediscover_interval) => {}
+                    _ = shard_manager.membership_changed() => {
+                        tracing::info!("Shard membership changed, reassigning BMC endpoints");
+                    }
+                }
             }
         }
     });
 
+    let join_events_listener = async {
+        match join_events_listener {
+            Some(handle) => handle.await,
+            None => std::future::pending().await,
+        }
+    };
+    let join_membership = async {
+        match join_membership {
+            Some(handle) => handle.await,
+            None => std::future::pending().await,
+        }
+    };
+
     tokio::select! {
+        res = join_membership => {
+            match res {
+                Ok(Ok(_)) => {
+                    tracing::error!("Shard membership loop shutdown");
+                }
+                Ok(Err(e)) => {
+                    tracing::error!(error=?e, "Shard membership loop failed");
+                }
+                Err(e) => {
+                    tracing::error!(error=?e, "Shard membership loop join error");
+                }
+            }
+        }
+        res = join_events_listener => {
+            match res {
+                Ok(Ok(_)) => {
+                    tracing::error!("Redfish event listener shutdown");
+                }
+                Ok(Err(e)) => {
+                    tracing::error!(error=?e, "Redfish event listener failed");
+                }
+                Err(e) => {
+                    tracing::error!(error=?e, "Redfish event listener join error");
+                }
+            }
+        }
         res = join_listener => {
             match res {
                 Ok(Ok(_)) => {
@@ -278,5 +381,11 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
         }
     };
 
+    if let Some((membership, _)) = shard_membership
+        && let Err(error) = membership.release(shard_manager.member_id()).await
+    {
+        tracing::warn!(?error, "Failed to release shard lease");
+    }
+
     Ok(())
 }
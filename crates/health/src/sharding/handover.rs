@@ -0,0 +1,38 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::HealthError;
+use crate::endpoint::BoxFuture;
+
+/// Storage for collector state which needs to survive a move of a BMC
+/// endpoint to another replica, e.g. the cursors of the logs collector.
+///
+/// The state is stored as opaque JSON per endpoint and collector type.
+pub trait CollectorStateStore: Send + Sync {
+    fn load<'a>(
+        &'a self,
+        endpoint_key: &'a str,
+        collector: &'a str,
+    ) -> BoxFuture<'a, Result<Option<String>, HealthError>>;
+
+    fn save<'a>(
+        &'a self,
+        endpoint_key: &'a str,
+        collector: &'a str,
+        state: String,
+    ) -> BoxFuture<'a, Result<(), HealthError>>;
+}
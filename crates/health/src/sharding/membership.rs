@@ -0,0 +1,150 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use prometheus::{GaugeVec, IntCounter, Opts};
+
+use super::ShardManager;
+use crate::HealthError;
+use crate::config::ShardMembershipConfig;
+use crate::endpoint::BoxFuture;
+use crate::metrics::MetricsManager;
+
+/// Source of the members which share the BMC endpoints
+pub trait ShardMembership: Send + Sync {
+    /// Renews the lease of the member and returns the IDs of all members with
+    /// an unexpired lease, including the renewed one
+    fn renew<'a>(
+        &'a self,
+        member_id: &'a str,
+        lease_duration: Duration,
+    ) -> BoxFuture<'a, Result<Vec<String>, HealthError>>;
+
+    /// Gives up the lease of the member, so that its endpoints are taken over
+    /// without waiting for the lease to expire
+    fn release<'a>(&'a self, member_id: &'a str) -> BoxFuture<'a, Result<(), HealthError>>;
+}
+
+struct MembershipMetrics {
+    members: GaugeVec,
+    changes: IntCounter,
+    renewal_failures: IntCounter,
+}
+
+impl MembershipMetrics {
+    fn new(metrics_manager: &MetricsManager, metrics_prefix: &str) -> Result<Self, HealthError> {
+        let registry = metrics_manager.global_registry();
+
+        let members = GaugeVec::new(
+            Opts::new(
+                format!("{metrics_prefix}_shard_members"),
+                "Members which currently share the BMC endpoints, as seen by this replica",
+            ),
+            &["member_id", "is_self"],
+        )?;
+        registry.register(Box::new(members.clone()))?;
+
+        let changes = IntCounter::new(
+            format!("{metrics_prefix}_shard_membership_changes_total"),
+            "Number of observed changes of the shard members",
+        )?;
+        registry.register(Box::new(changes.clone()))?;
+
+        let renewal_failures = IntCounter::new(
+            format!("{metrics_prefix}_shard_lease_renewal_failures_total"),
+            "Number of failed renewals of the shard lease of this replica",
+        )?;
+        registry.register(Box::new(renewal_failures.clone()))?;
+
+        Ok(Self {
+            members,
+            changes,
+            renewal_failures,
+        })
+    }
+
+    fn set_members(&self, shard_manager: &ShardManager) {
+        self.members.reset();
+        for member in shard_manager.members().iter() {
+            let is_self = if member == shard_manager.member_id() {
+                "true"
+            } else {
+                "false"
+            };
+            self.members.with_label_values(&[member, is_self]).set(1.0);
+        }
+    }
+}
+
+/// Keeps the lease of this replica alive and updates the members of the
+/// shard manager whenever they change.
+///
+/// If the lease can't be renewed before it expires, the other replicas take
+/// over all endpoints. This replica then gives up its endpoints as well until
+/// the next successful renewal, so that BMCs are not polled twice.
+pub async fn run_membership_loop(
+    shard_manager: Arc<ShardManager>,
+    membership: Arc<dyn ShardMembership>,
+    config: ShardMembershipConfig,
+    metrics_manager: Arc<MetricsManager>,
+    metrics_prefix: String,
+) -> Result<(), HealthError> {
+    let metrics = MembershipMetrics::new(&metrics_manager, &metrics_prefix)?;
+    let member_id = shard_manager.member_id().to_string();
+
+    let mut last_renewal: Option<Instant> = None;
+    let mut interval = tokio::time::interval(config.renew_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let members = match membership.renew(&member_id, config.lease_duration).await {
+            Ok(members) => {
+                last_renewal = Some(Instant::now());
+                members
+            }
+            Err(error) => {
+                metrics.renewal_failures.inc();
+                tracing::warn!(?error, %member_id, "Failed to renew shard lease");
+
+                let expired =
+                    last_renewal.is_none_or(|renewal| renewal.elapsed() >= config.lease_duration);
+                if !expired || shard_manager.members().is_empty() {
+                    continue;
+                }
+                tracing::error!(
+                    %member_id,
+                    "Shard lease expired, releasing all BMC endpoints until it is renewed"
+                );
+                Vec::new()
+            }
+        };
+
+        if shard_manager.update_members(members) {
+            metrics.changes.inc();
+            metrics.set_members(&shard_manager);
+            tracing::info!(
+                %member_id,
+                members = ?shard_manager.members(),
+                "Shard membership changed"
+            );
+        }
+    }
+}
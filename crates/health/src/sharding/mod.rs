@@ -0,0 +1,300 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Assignment of BMC endpoints to the replicas of the health service.
+//!
+//! Endpoints are assigned via rendezvous hashing: every replica computes a
+//! score for each pair of member and endpoint, and the member with the highest
+//! score monitors the endpoint. If a member joins or leaves, only the endpoints
+//! it gains or loses move, which is roughly 1/N of all endpoints.
+//!
+//! Members are either static (`shard`/`shards_count`) or discovered via
+//! leases in the Carbide API, see [`run_membership_loop`].
+
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::Notify;
+
+use crate::endpoint::BmcAddr;
+
+mod handover;
+mod membership;
+
+pub use handover::CollectorStateStore;
+pub use membership::{ShardMembership, run_membership_loop};
+
+pub struct ShardManager {
+    member_id: String,
+    members: RwLock<Arc<[String]>>,
+    membership_changed: Notify,
+}
+
+impl ShardManager {
+    /// Creates a manager with a static set of `shards_count` members, where
+    /// this instance is the member with the ordinal `shard`
+    pub fn new(shard: usize, shards_count: usize) -> Self {
+        let members: Vec<String> = (0..shards_count).map(|shard| shard.to_string()).collect();
+        Self {
+            member_id: shard.to_string(),
+            members: RwLock::new(Arc::from(members)),
+            membership_changed: Notify::new(),
+        }
+    }
+
+    /// Creates a manager for a dynamic set of members, which is updated via
+    /// [`ShardManager::update_members`]. No endpoints are assigned to this
+    /// instance until the first update.
+    pub fn with_member_id(member_id: String) -> Self {
+        Self {
+            member_id,
+            members: RwLock::new(Arc::from([])),
+            membership_changed: Notify::new(),
+        }
+    }
+
+    pub fn member_id(&self) -> &str {
+        &self.member_id
+    }
+
+    /// Returns the current members, ordered by their ID
+    pub fn members(&self) -> Arc<[String]> {
+        self.members
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replaces the current members. Returns whether the members changed.
+    pub fn update_members(&self, mut members: Vec<String>) -> bool {
+        members.sort();
+        members.dedup();
+
+        let mut current = self.members.write().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref() == members.as_slice() {
+            return false;
+        }
+        *current = Arc::from(members);
+        drop(current);
+
+        self.membership_changed.notify_one();
+        true
+    }
+
+    /// Waits until the members changed since the last call
+    pub async fn membership_changed(&self) {
+        self.membership_changed.notified().await
+    }
+
+    /// Check if this shard should monitor a BMC endpoint.
+    pub fn should_monitor(&self, endpoint: &BmcAddr) -> bool {
+        self.should_monitor_key(&endpoint.hash_key())
+    }
+
+    pub fn should_monitor_key(&self, key: &str) -> bool {
+        self.owner(key)
+            .is_some_and(|owner| owner == self.member_id.as_str())
+    }
+
+    /// Returns the ID of the member which monitors the endpoint with the given key
+    pub fn owner(&self, key: &str) -> Option<String> {
+        let members = self.members();
+        members
+            .iter()
+            .max_by_key(|member| rendezvous_score(member, key))
+            .cloned()
+    }
+}
+
+/// Score of a member for an endpoint key. The score is derived from FNV-1a
+/// 64-bit, followed by the SplitMix64 finalizer since FNV alone distributes
+/// similar short inputs such as MAC addresses poorly.
+fn rendezvous_score(member: &str, key: &str) -> u64 {
+    const FNV_PRIME: u64 = 1099511628211;
+    const FNV_OFFSET_BASIS: u64 = 14695981039346656037;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    // The separator can't be part of valid UTF-8, which makes the
+    // concatenation unambiguous
+    for byte in member.bytes().chain([0xff]).chain(key.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58476d1ce4e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use mac_address::MacAddress;
+
+    use super::*;
+
+    #[test]
+    fn test_single_shard() {
+        let manager = ShardManager::new(0, 1);
+        let endpoint = BmcAddr {
+            ip: "10.0.0.1".parse().unwrap(),
+            port: Some(443),
+            mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+        };
+        assert!(manager.should_monitor(&endpoint));
+    }
+
+    #[test]
+    fn test_consistent_hashing() {
+        let endpoint1 = BmcAddr {
+            ip: "10.0.0.1".parse().unwrap(),
+            port: Some(443),
+            mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+        };
+        let endpoint2 = BmcAddr {
+            ip: "10.0.0.2".parse().unwrap(),
+            port: Some(443),
+            mac: MacAddress::from_str("42:9e:b2:bd:9d:dd").unwrap(),
+        };
+
+        let manager0 = ShardManager::new(0, 3);
+        let manager1 = ShardManager::new(1, 3);
+        let manager2 = ShardManager::new(2, 3);
+
+        // Each endpoint should be assigned to exactly one pod
+        let mut count1 = 0;
+        let mut count2 = 0;
+        if manager0.should_monitor(&endpoint1) {
+            count1 += 1;
+        }
+        if manager1.should_monitor(&endpoint1) {
+            count1 += 1;
+        }
+        if manager2.should_monitor(&endpoint1) {
+            count1 += 1;
+        }
+        assert_eq!(
+            count1, 1,
+            "endpoint1 should be monitored by exactly one pod"
+        );
+
+        if manager0.should_monitor(&endpoint2) {
+            count2 += 1;
+        }
+        if manager1.should_monitor(&endpoint2) {
+            count2 += 1;
+        }
+        if manager2.should_monitor(&endpoint2) {
+            count2 += 1;
+        }
+        assert_eq!(
+            count2, 1,
+            "endpoint2 should be monitored by exactly one pod"
+        );
+    }
+
+    #[test]
+    fn test_should_monitor_key_distribution() {
+        let key1 = "AA:BB:CC:DD:EE:FF";
+        let key2 = "11:22:33:44:55:66";
+
+        // Each key should be assigned to exactly one pod/shard
+        for key in [key1, key2] {
+            let mut count = 0;
+            for shard in 0..3 {
+                let manager = ShardManager::new(shard, 3);
+                if manager.should_monitor_key(key) {
+                    count += 1;
+                }
+            }
+            assert_eq!(
+                count, 1,
+                "Key {} should be assigned to exactly one shard",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_should_monitor_key_consistency() {
+        let manager = ShardManager::new(0, 3);
+        let key = "AA:BB:CC:DD:EE:FF";
+        assert_eq!(
+            manager.should_monitor_key(key),
+            manager.should_monitor_key(key)
+        );
+    }
+
+    #[test]
+    fn test_dynamic_membership() {
+        let manager = ShardManager::with_member_id("carbide-hw-health-b".to_string());
+        let key = "AA:BB:CC:DD:EE:FF";
+        assert!(!manager.should_monitor_key(key));
+
+        assert!(manager.update_members(vec!["carbide-hw-health-b".to_string()]));
+        assert!(!manager.update_members(vec!["carbide-hw-health-b".to_string()]));
+        assert!(manager.should_monitor_key(key));
+        assert_eq!(manager.owner(key).as_deref(), Some("carbide-hw-health-b"));
+    }
+
+    #[test]
+    fn test_membership_change_moves_few_endpoints() {
+        let keys: Vec<String> = (0..1000u32)
+            .map(|i| {
+                let [a, b, c, d] = i.to_be_bytes();
+                format!("42:9e:{a:02x}:{b:02x}:{c:02x}:{d:02x}")
+            })
+            .collect();
+        let manager = ShardManager::with_member_id("member-0".to_string());
+        let members: Vec<String> = (0..4).map(|i| format!("member-{i}")).collect();
+
+        manager.update_members(members.clone());
+        let before: Vec<Option<String>> = keys.iter().map(|key| manager.owner(key)).collect();
+        for member in &members {
+            let count = before
+                .iter()
+                .filter(|owner| owner.as_ref() == Some(member))
+                .count();
+            assert!(
+                (150..350).contains(&count),
+                "{member} owns {count} of 1000 endpoints"
+            );
+        }
+
+        let mut grown = members.clone();
+        grown.push("member-4".to_string());
+        manager.update_members(grown);
+        let moved = keys
+            .iter()
+            .zip(&before)
+            .filter(|(key, owner)| manager.owner(key) != **owner)
+            .collect::<Vec<_>>();
+        // Only endpoints which are taken over by the new member move
+        assert!(
+            moved
+                .iter()
+                .all(|(key, _)| manager.owner(key).as_deref() == Some("member-4"))
+        );
+        assert!(
+            (100..300).contains(&moved.len()),
+            "{} of 1000 endpoints moved",
+            moved.len()
+        );
+    }
+}
@@ -16,8 +16,10 @@
  */
 
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
 
 use crate::endpoint::{BmcAddr, BmcEndpoint, EndpointMetadata};
 use crate::metrics::MetricLabel;
@@ -57,6 +59,14 @@ impl EventContext {
             _ => None,
         }
     }
+
+    pub fn rack_id(&self) -> Option<RackId> {
+        match &self.metadata {
+            Some(EndpointMetadata::Machine(machine)) => machine.rack_id,
+            Some(EndpointMetadata::Switch(switch)) => switch.rack_id,
+            None => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -74,6 +84,8 @@ pub struct LogRecord {
     pub body: String,
     pub severity: String,
     pub attributes: Vec<MetricLabel>,
+    /// Time the record was created on the BMC, if known
+    pub timestamp: Option<SystemTime>,
 }
 
 #[derive(Clone, Debug)]
@@ -18,6 +18,7 @@
 mod composite;
 mod events;
 mod health_override;
+mod otlp;
 mod prometheus;
 mod tracing;
 
@@ -26,6 +27,7 @@ pub use events::{
     CollectorEvent, EventContext, FirmwareInfo, HealthOverride, LogRecord, MetricSample,
 };
 pub use health_override::HealthOverrideSink;
+pub use otlp::OtlpSink;
 pub use prometheus::PrometheusSink;
 pub use tracing::TracingSink;
 
@@ -44,8 +46,9 @@ mod tests {
 
     use super::{
         CollectorEvent, CompositeDataSink, DataSink, EventContext, LogRecord, MetricSample,
-        PrometheusSink,
+        OtlpSink, PrometheusSink,
     };
+    use crate::config::OtlpSinkConfig;
     use crate::endpoint::{BmcAddr, EndpointMetadata, MachineData};
     use crate::metrics::MetricsManager;
 
@@ -122,6 +125,7 @@ mod tests {
                     .parse()
                     .expect("valid machine id"),
                 machine_serial: None,
+                rack_id: None,
             })),
         };
 
@@ -129,6 +133,7 @@ mod tests {
             body: "ignored by prometheus sink".to_string(),
             severity: "INFO".to_string(),
             attributes: Vec::new(),
+            timestamp: None,
         });
         sink.handle_event(&context, &log_event);
 
@@ -172,6 +177,7 @@ mod tests {
                     .parse()
                     .expect("valid machine id"),
                 machine_serial: None,
+                rack_id: None,
             })),
         };
 
@@ -214,4 +220,57 @@ mod tests {
         assert!(!second_export.contains("sensor=\"temp1\""));
         assert!(second_export.contains("sensor=\"temp2\""));
     }
+
+    #[tokio::test]
+    async fn test_otlp_sink_drops_records_when_queue_is_full() {
+        let metrics_manager = Arc::new(MetricsManager::new());
+        let config = OtlpSinkConfig {
+            max_batch_size: 1,
+            max_queue_size: 1,
+            export_logs: false,
+            ..Default::default()
+        };
+        let sink = OtlpSink::new(&config, metrics_manager.clone(), "test_sink")
+            .expect("sink should initialize");
+
+        let context = EventContext {
+            endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+            addr: BmcAddr {
+                ip: "10.0.0.1".parse().expect("valid ip"),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+            },
+            collector_type: "sensor_collector",
+            metadata: None,
+        };
+
+        // The exporter task doesn't run before the test yields, so only the
+        // first record fits into the queue
+        for value in 0..3 {
+            let event = CollectorEvent::Metric(MetricSample {
+                key: format!("s{value}"),
+                name: "hw_sensor".to_string(),
+                metric_type: "temperature".to_string(),
+                unit: "celsius".to_string(),
+                value: value as f64,
+                labels: Vec::new(),
+            });
+            sink.handle_event(&context, &event);
+        }
+        let log_event = CollectorEvent::Log(LogRecord {
+            body: "not exported".to_string(),
+            severity: "INFO".to_string(),
+            attributes: Vec::new(),
+            timestamp: None,
+        });
+        sink.handle_event(&context, &log_event);
+
+        let export = metrics_manager
+            .export_all()
+            .expect("metrics export should work");
+        assert!(export.contains(
+            "test_sink_otlp_sink_records_total{signal=\"metrics\",outcome=\"dropped\"} 2"
+        ));
+        assert!(!export.contains("signal=\"logs\""));
+    }
 }
@@ -0,0 +1,543 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Export of collector metrics and logs via OTLP.
+//!
+//! Events are queued in a bounded channel and exported in batches by a
+//! background task. Records are grouped by the BMC endpoint they originate
+//! from, which is described by the OTLP resource. While the receiver is slow
+//! or unavailable, exports are retried and the queue fills up. Further events
+//! are then dropped instead of blocking the collectors.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::logs::v1::logs_service_client::LogsServiceClient;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_client::MetricsServiceClient;
+use opentelemetry_proto::tonic::common::v1::{AnyValue, InstrumentationScope, KeyValue, any_value};
+use opentelemetry_proto::tonic::logs::v1::{LogRecord as OtlpLogRecord, ResourceLogs, ScopeLogs};
+use opentelemetry_proto::tonic::metrics::v1::{
+    Gauge, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, metric, number_data_point,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prometheus::{IntCounterVec, Opts};
+use tokio::sync::mpsc;
+
+use super::{CollectorEvent, DataSink, EventContext, LogRecord, MetricSample};
+use crate::HealthError;
+use crate::config::{OtlpProtocol, OtlpSinkConfig};
+use crate::metrics::MetricsManager;
+
+const SERVICE_NAME: &str = "carbide-hw-health";
+
+/// gRPC status codes which indicate a transient failure, as listed in the
+/// OTLP specification
+const RETRYABLE_GRPC_CODES: [tonic::Code; 6] = [
+    tonic::Code::Cancelled,
+    tonic::Code::DeadlineExceeded,
+    tonic::Code::Aborted,
+    tonic::Code::OutOfRange,
+    tonic::Code::Unavailable,
+    tonic::Code::DataLoss,
+];
+
+/// Describes the resource and instrumentation scope of a record
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct RecordOrigin {
+    resource_attributes: Vec<(&'static str, String)>,
+    collector_type: &'static str,
+}
+
+impl RecordOrigin {
+    fn from_context(context: &EventContext) -> Self {
+        let mut resource_attributes = vec![
+            ("service.name", SERVICE_NAME.to_string()),
+            ("bmc.mac", context.addr.mac.to_string()),
+            ("bmc.ip", context.addr.ip.to_string()),
+        ];
+        if let Some(machine_id) = context.machine_id() {
+            resource_attributes.push(("machine.id", machine_id.to_string()));
+        }
+        if let Some(serial) = context.switch_serial() {
+            resource_attributes.push(("switch.serial", serial.to_string()));
+        }
+        if let Some(rack_id) = context.rack_id() {
+            resource_attributes.push(("rack.id", rack_id.to_string()));
+        }
+
+        Self {
+            resource_attributes,
+            collector_type: context.collector_type,
+        }
+    }
+
+    fn resource(&self) -> Resource {
+        Resource {
+            attributes: self
+                .resource_attributes
+                .iter()
+                .map(|(key, value)| string_attribute(key, value.clone()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn scope(&self) -> InstrumentationScope {
+        InstrumentationScope {
+            name: self.collector_type.to_string(),
+            version: carbide_version::v!(build_version).to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+enum QueuedRecord {
+    Metric(MetricSample),
+    Log(LogRecord),
+}
+
+struct QueuedEvent {
+    origin: RecordOrigin,
+    observed_at: SystemTime,
+    record: QueuedRecord,
+}
+
+#[derive(Clone)]
+struct SinkMetrics {
+    records: IntCounterVec,
+}
+
+impl SinkMetrics {
+    fn new(metrics_manager: &MetricsManager, metrics_prefix: &str) -> Result<Self, HealthError> {
+        let records = IntCounterVec::new(
+            Opts::new(
+                format!("{metrics_prefix}_otlp_sink_records_total"),
+                "Number of records handled by the OTLP sink, by outcome",
+            ),
+            &["signal", "outcome"],
+        )?;
+        metrics_manager
+            .global_registry()
+            .register(Box::new(records.clone()))?;
+        Ok(Self { records })
+    }
+
+    fn record(&self, signal: &str, outcome: &str, count: usize) {
+        self.records
+            .with_label_values(&[signal, outcome])
+            .inc_by(count as u64);
+    }
+}
+
+pub struct OtlpSink {
+    sender: mpsc::Sender<QueuedEvent>,
+    metrics: SinkMetrics,
+    export_metrics: bool,
+    export_logs: bool,
+}
+
+impl OtlpSink {
+    pub fn new(
+        config: &OtlpSinkConfig,
+        metrics_manager: Arc<MetricsManager>,
+        metrics_prefix: &str,
+    ) -> Result<Self, HealthError> {
+        let handle = tokio::runtime::Handle::try_current().map_err(|error| {
+            HealthError::GenericError(format!("OTLP sink requires active Tokio runtime: {error}"))
+        })?;
+
+        let metrics = SinkMetrics::new(&metrics_manager, metrics_prefix)?;
+        let exporter = Exporter::new(config.clone(), metrics.clone(), metrics_prefix)?;
+        let (sender, receiver) = mpsc::channel(config.max_queue_size);
+        handle.spawn(exporter.run(receiver));
+
+        Ok(Self {
+            sender,
+            metrics,
+            export_metrics: config.export_metrics,
+            export_logs: config.export_logs,
+        })
+    }
+
+    fn enqueue(&self, context: &EventContext, signal: &'static str, record: QueuedRecord) {
+        let event = QueuedEvent {
+            origin: RecordOrigin::from_context(context),
+            observed_at: SystemTime::now(),
+            record,
+        };
+        match self.sender.try_send(event) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.metrics.record(signal, "dropped", 1);
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                self.metrics.record(signal, "dropped", 1);
+                tracing::warn!("OTLP exporter stopped, dropping record");
+            }
+        }
+    }
+}
+
+impl DataSink for OtlpSink {
+    fn handle_event(&self, context: &EventContext, event: &CollectorEvent) {
+        match event {
+            CollectorEvent::Metric(sample) if self.export_metrics => {
+                self.enqueue(context, "metrics", QueuedRecord::Metric(sample.clone()));
+            }
+            CollectorEvent::Log(record) if self.export_logs => {
+                self.enqueue(context, "logs", QueuedRecord::Log(record.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ExportError {
+    /// The export may succeed if it is retried
+    Transient(String),
+    Permanent(String),
+}
+
+struct Exporter {
+    config: OtlpSinkConfig,
+    metrics: SinkMetrics,
+    metric_prefix: String,
+    http_client: reqwest::Client,
+}
+
+impl Exporter {
+    fn new(
+        config: OtlpSinkConfig,
+        metrics: SinkMetrics,
+        metric_prefix: &str,
+    ) -> Result<Self, HealthError> {
+        let http_client = reqwest::Client::builder()
+            .timeout(config.export_timeout)
+            .build()
+            .map_err(|e| HealthError::GenericError(format!("Failed to build HTTP client: {e}")))?;
+        Ok(Self {
+            config,
+            metrics,
+            metric_prefix: metric_prefix.to_string(),
+            http_client,
+        })
+    }
+
+    async fn run(self, mut receiver: mpsc::Receiver<QueuedEvent>) {
+        let mut batch = Vec::with_capacity(self.config.max_batch_size);
+        let mut interval = tokio::time::interval(self.config.export_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = receiver.recv() => {
+                    let Some(event) = event else {
+                        self.export(std::mem::take(&mut batch)).await;
+                        return;
+                    };
+                    batch.push(event);
+                    if batch.len() >= self.config.max_batch_size {
+                        self.export(std::mem::take(&mut batch)).await;
+                    }
+                }
+                _ = interval.tick() => {
+                    if !batch.is_empty() {
+                        self.export(std::mem::take(&mut batch)).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn export(&self, batch: Vec<QueuedEvent>) {
+        let (metric_count, logs_request, metrics_request) =
+            build_requests(&self.metric_prefix, batch);
+        let log_count = batch_log_count(&logs_request);
+
+        if let Some(request) = metrics_request {
+            let outcome = self
+                .with_retries("metrics", || self.send_metrics(request.clone()))
+                .await;
+            self.metrics.record("metrics", outcome, metric_count);
+        }
+        if let Some(request) = logs_request {
+            let outcome = self
+                .with_retries("logs", || self.send_logs(request.clone()))
+                .await;
+            self.metrics.record("logs", outcome, log_count);
+        }
+    }
+
+    /// Sends a request until it succeeds, fails permanently or the retries
+    /// are exhausted. Returns the outcome for the sink metrics.
+    async fn with_retries<F, Fut>(&self, signal: &str, send: F) -> &'static str
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<(), ExportError>>,
+    {
+        let mut backoff = self.config.initial_retry_backoff;
+        let mut attempt = 0;
+        loop {
+            let error = match send().await {
+                Ok(()) => return "exported",
+                Err(ExportError::Permanent(error)) => error,
+                Err(ExportError::Transient(error)) if attempt < self.config.max_retries => {
+                    attempt += 1;
+                    tracing::debug!(
+                        signal,
+                        attempt,
+                        %error,
+                        "OTLP export failed, retrying in {backoff:?}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.config.max_retry_backoff);
+                    continue;
+                }
+                Err(ExportError::Transient(error)) => error,
+            };
+            tracing::warn!(signal, attempts = attempt + 1, %error, "OTLP export failed");
+            return "failed";
+        }
+    }
+
+    async fn send_metrics(&self, request: ExportMetricsServiceRequest) -> Result<(), ExportError> {
+        match self.config.protocol {
+            OtlpProtocol::Grpc => {
+                // Exports happen at most once per batch, so a connection per
+                // export is cheap and never leaves a broken channel behind
+                let mut client = MetricsServiceClient::connect(self.config.endpoint.to_string())
+                    .await
+                    .map_err(|e| ExportError::Transient(e.to_string()))?;
+                let response =
+                    tokio::time::timeout(self.config.export_timeout, client.export(request))
+                        .await
+                        .map_err(|_| ExportError::Transient("export timed out".to_string()))?;
+                response.map(|_| ()).map_err(|status| {
+                    grpc_error(status.code() as i32, status.message().to_string())
+                })
+            }
+            OtlpProtocol::HttpJson => self.send_http("v1/metrics", &request).await,
+        }
+    }
+
+    async fn send_logs(&self, request: ExportLogsServiceRequest) -> Result<(), ExportError> {
+        match self.config.protocol {
+            OtlpProtocol::Grpc => {
+                let mut client = LogsServiceClient::connect(self.config.endpoint.to_string())
+                    .await
+                    .map_err(|e| ExportError::Transient(e.to_string()))?;
+                let response =
+                    tokio::time::timeout(self.config.export_timeout, client.export(request))
+                        .await
+                        .map_err(|_| ExportError::Transient("export timed out".to_string()))?;
+                response.map(|_| ()).map_err(|status| {
+                    grpc_error(status.code() as i32, status.message().to_string())
+                })
+            }
+            OtlpProtocol::HttpJson => self.send_http("v1/logs", &request).await,
+        }
+    }
+
+    async fn send_http<T: serde::Serialize>(
+        &self,
+        path: &str,
+        request: &T,
+    ) -> Result<(), ExportError> {
+        let url = self
+            .config
+            .endpoint
+            .join(path)
+            .map_err(|e| ExportError::Permanent(format!("Invalid OTLP endpoint: {e}")))?;
+        let response = self
+            .http_client
+            .post(url)
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| ExportError::Transient(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let message = format!("OTLP receiver returned status {status}");
+        match status.as_u16() {
+            429 | 502 | 503 | 504 => Err(ExportError::Transient(message)),
+            _ => Err(ExportError::Permanent(message)),
+        }
+    }
+}
+
+fn grpc_error(code: i32, message: String) -> ExportError {
+    let code = tonic::Code::from_i32(code);
+    let message = format!("OTLP receiver returned {code:?}: {message}");
+    if RETRYABLE_GRPC_CODES.contains(&code) {
+        ExportError::Transient(message)
+    } else {
+        ExportError::Permanent(message)
+    }
+}
+
+fn batch_log_count(request: &Option<ExportLogsServiceRequest>) -> usize {
+    request
+        .iter()
+        .flat_map(|request| &request.resource_logs)
+        .flat_map(|resource_logs| &resource_logs.scope_logs)
+        .map(|scope_logs| scope_logs.log_records.len())
+        .sum()
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_nanos()
+        .try_into()
+        .unwrap_or(u64::MAX)
+}
+
+fn string_attribute(key: &str, value: String) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+/// Maps the severity text of a [`LogRecord`] to the OTLP severity number
+fn severity_number(severity: &str) -> i32 {
+    match severity {
+        "TRACE" => 1,
+        "DEBUG" => 5,
+        "INFO" => 9,
+        "WARN" => 13,
+        "ERROR" => 17,
+        "FATAL" => 21,
+        _ => 0,
+    }
+}
+
+fn metric_from_sample(
+    metric_prefix: &str,
+    sample: MetricSample,
+    observed_at: SystemTime,
+) -> Metric {
+    let data_point = NumberDataPoint {
+        attributes: sample
+            .labels
+            .into_iter()
+            .map(|(key, value)| string_attribute(&key, value))
+            .collect(),
+        time_unix_nano: unix_nanos(observed_at),
+        value: Some(number_data_point::Value::AsDouble(sample.value)),
+        ..Default::default()
+    };
+    Metric {
+        name: format!("{metric_prefix}_{}_{}", sample.name, sample.metric_type),
+        unit: sample.unit,
+        data: Some(metric::Data::Gauge(Gauge {
+            data_points: vec![data_point],
+        })),
+        ..Default::default()
+    }
+}
+
+fn log_from_record(record: LogRecord, observed_at: SystemTime) -> OtlpLogRecord {
+    OtlpLogRecord {
+        time_unix_nano: record.timestamp.map(unix_nanos).unwrap_or(0),
+        observed_time_unix_nano: unix_nanos(observed_at),
+        severity_number: severity_number(&record.severity),
+        severity_text: record.severity,
+        body: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(record.body)),
+        }),
+        attributes: record
+            .attributes
+            .into_iter()
+            .map(|(key, value)| string_attribute(&key, value))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Groups a batch by resource and scope. Returns the number of metric data
+/// points and the export requests for metrics and logs, if the batch
+/// contains any.
+fn build_requests(
+    metric_prefix: &str,
+    batch: Vec<QueuedEvent>,
+) -> (
+    usize,
+    Option<ExportLogsServiceRequest>,
+    Option<ExportMetricsServiceRequest>,
+) {
+    let mut metrics: BTreeMap<RecordOrigin, Vec<Metric>> = BTreeMap::new();
+    let mut logs: BTreeMap<RecordOrigin, Vec<OtlpLogRecord>> = BTreeMap::new();
+    let mut metric_count = 0;
+
+    for event in batch {
+        match event.record {
+            QueuedRecord::Metric(sample) => {
+                metric_count += 1;
+                metrics
+                    .entry(event.origin)
+                    .or_default()
+                    .push(metric_from_sample(metric_prefix, sample, event.observed_at));
+            }
+            QueuedRecord::Log(record) => {
+                logs.entry(event.origin)
+                    .or_default()
+                    .push(log_from_record(record, event.observed_at));
+            }
+        }
+    }
+
+    let logs_request = (!logs.is_empty()).then(|| ExportLogsServiceRequest {
+        resource_logs: logs
+            .into_iter()
+            .map(|(origin, log_records)| ResourceLogs {
+                resource: Some(origin.resource()),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(origin.scope()),
+                    log_records,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+            .collect(),
+    });
+    let metrics_request = (!metrics.is_empty()).then(|| ExportMetricsServiceRequest {
+        resource_metrics: metrics
+            .into_iter()
+            .map(|(origin, metrics)| ResourceMetrics {
+                resource: Some(origin.resource()),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(origin.scope()),
+                    metrics,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+            .collect(),
+    });
+
+    (metric_count, logs_request, metrics_request)
+}
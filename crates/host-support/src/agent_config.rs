@@ -138,18 +138,34 @@ pub struct MachineConfig {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct MetadataServiceConfig {
     pub address: String,
+    /// Whether clients need to fetch a session token with `PUT /api/token`
+    /// before they can read instance metadata
+    #[serde(default)]
+    pub session_tokens: SessionTokenMode,
 }
 
 impl Default for MetadataServiceConfig {
     fn default() -> Self {
         Self {
             address: INSTANCE_METADATA_SERVICE_ADDRESS.to_string(),
+            session_tokens: SessionTokenMode::default(),
         }
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionTokenMode {
+    /// Requests without token are served. Requests with an invalid token are rejected.
+    #[default]
+    Optional,
+    /// Every request needs a valid token
+    Required,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TelemetryConfig {
@@ -316,6 +332,7 @@ interface-id = "91609f10-c91d-470d-a260-6293ea0c1200"
 
 [metadata-service]
 address = "0.0.0.0:7777"
+session-tokens = "required"
 
 [telemetry]
 metrics-address = "0.0.0.0:8888"
@@ -350,6 +367,10 @@ addresses = ["168.254.169.254/30"]
         assert!(config.machine.is_fake_dpu);
 
         assert_eq!(config.metadata_service.address, "0.0.0.0:7777");
+        assert_eq!(
+            config.metadata_service.session_tokens,
+            SessionTokenMode::Required
+        );
         assert_eq!(config.telemetry.metrics_address, "0.0.0.0:8888");
 
         assert_eq!(config.hbn.root_dir, PathBuf::from("/tmp/hbn-root"));
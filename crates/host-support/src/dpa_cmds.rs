@@ -18,6 +18,7 @@
 use std::borrow::Cow;
 
 use libmlx::firmware::config::FirmwareFlasherProfile;
+use libmlx::profile::serialization::SerializableProfile;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -35,6 +36,14 @@ pub enum OpCode<'a> {
     ApplyFirmware {
         profile: Option<Box<Cow<'a, FirmwareFlasherProfile>>>,
     },
+    // SyncProfile syncs an mlxconfig profile stored in carbide-api to the
+    // device, if the device is supported by the profile's registry. The
+    // version is reported back so carbide-api can tell which revision
+    // of the profile the device was synced to.
+    SyncProfile {
+        profile: Box<SerializableProfile>,
+        version: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -15,7 +15,6 @@
  * limitations under the License.
  */
 
-use std::process::Command;
 use std::str::FromStr;
 
 use mac_address::MacAddress;
@@ -25,6 +24,8 @@ use tracing::{debug, warn};
 
 use crate::device::filters::DeviceFilter;
 use crate::device::info::MlxDeviceInfo;
+use crate::runner::backend::{CommandBackend, SystemBackend};
+use crate::runner::command_builder::CommandSpec;
 
 // DevicesXml represents the root XML structure
 // from mlxfwmanager output.
@@ -92,12 +93,15 @@ struct MacsXml {
 
 // discover_devices finds all devices using mlxfwmanager.
 pub fn discover_devices() -> Result<Vec<MlxDeviceInfo>, String> {
+    discover_devices_with(&SystemBackend)
+}
+
+// discover_devices_with finds all devices using mlxfwmanager,
+// executed through the provided backend.
+pub fn discover_devices_with(backend: &dyn CommandBackend) -> Result<Vec<MlxDeviceInfo>, String> {
     debug!("Running mlxfwmanager to discover devices");
 
-    let output = Command::new("mlxfwmanager")
-        .args(["--query-format", "xml"])
-        .output()
-        .map_err(|e| format!("failed to build cmd: {e}"))?;
+    let output = run_mlxfwmanager(backend, &["--query-format", "xml"])?;
 
     // In cases where DPUs are returned, it looks like DPUs that are
     // currently in lockdown won't return data to mlxfwmanager. The
@@ -125,12 +129,18 @@ pub fn discover_devices() -> Result<Vec<MlxDeviceInfo>, String> {
 // The actual XML returned is still "devices", but will only
 // contain the target device.
 pub fn discover_device(device: &str) -> Result<MlxDeviceInfo, String> {
+    discover_device_with(&SystemBackend, device)
+}
+
+// discover_device_with loads a specific device using mlxfwmanager,
+// executed through the provided backend.
+pub fn discover_device_with(
+    backend: &dyn CommandBackend,
+    device: &str,
+) -> Result<MlxDeviceInfo, String> {
     debug!("Running mlxfwmanager to discover device: {device}");
 
-    let output = Command::new("mlxfwmanager")
-        .args(["--dev", device, "--query-format", "xml"])
-        .output()
-        .map_err(|e| format!("failed to build cmd: {e}"))?;
+    let output = run_mlxfwmanager(backend, &["--dev", device, "--query-format", "xml"])?;
 
     // In cases where DPUs are returned, it looks like DPUs that are
     // currently in lockdown won't return data to mlxfwmanager. The
@@ -162,6 +172,18 @@ pub fn discover_device(device: &str) -> Result<MlxDeviceInfo, String> {
     Ok(devices.into_iter().next().unwrap())
 }
 
+// run_mlxfwmanager runs mlxfwmanager with the given arguments.
+fn run_mlxfwmanager(
+    backend: &dyn CommandBackend,
+    args: &[&str],
+) -> Result<std::process::Output, String> {
+    let spec = CommandSpec::new("mlxfwmanager").args(args.iter().copied());
+    backend
+        .run(&spec, None)
+        .map_err(|e| format!("failed to build cmd: {e}"))?
+        .ok_or_else(|| "mlxfwmanager timed out".to_string())
+}
+
 // discover_devices_with_filters finds devices that match
 // the specified filters.
 pub fn discover_devices_with_filters(filter: DeviceFilter) -> Result<Vec<MlxDeviceInfo>, String> {
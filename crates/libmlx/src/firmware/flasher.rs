@@ -30,15 +30,18 @@
 // profile with a FirmwareFlasherProfile.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use tracing;
 
+use crate::device::info::MlxDeviceInfo;
 use crate::firmware::config::{FirmwareFlasherProfile, FirmwareSpec, FlashSpec};
 use crate::firmware::error::{FirmwareError, FirmwareResult};
 use crate::firmware::reset::{DEFAULT_RESET_LEVEL, MlxFwResetRunner};
 use crate::firmware::result::FirmwareFlashReport;
 use crate::lockdown::runner::FlintRunner;
 use crate::runner::applier::MlxConfigApplier;
+use crate::runner::backend::{CommandBackend, SystemBackend};
 use crate::runner::exec_options::ExecOptions;
 
 // FirmwareFlasher manages the firmware flash lifecycle for Mellanox NICs.
@@ -54,6 +57,9 @@ pub struct FirmwareFlasher {
     firmware_spec: FirmwareSpec,
     // dry_run enables dry-run mode across all underlying operations.
     dry_run: bool,
+    // backend, if set, executes all underlying commands instead of
+    // the tools found on the system (e.g. an MlxSimulator).
+    backend: Option<Arc<dyn CommandBackend>>,
 }
 
 impl FirmwareFlasher {
@@ -62,9 +68,28 @@ impl FirmwareFlasher {
     // psid match the provided FirmwareSpec. Returns an error if the device
     // cannot be found or if the identity doesn't match.
     pub fn new(device_id: impl Into<String>, spec: &FirmwareSpec) -> FirmwareResult<Self> {
-        let device_id = device_id.into();
+        Self::validated(device_id.into(), spec, None)
+    }
+
+    // with_backend is the same as new, except all commands (including
+    // the initial device discovery) are executed through the provided
+    // backend instead of the tools installed on the system.
+    pub fn with_backend(
+        device_id: impl Into<String>,
+        spec: &FirmwareSpec,
+        backend: Arc<dyn CommandBackend>,
+    ) -> FirmwareResult<Self> {
+        Self::validated(device_id.into(), spec, Some(backend))
+    }
 
-        let device_info = crate::device::discovery::discover_device(&device_id).map_err(|e| {
+    // validated discovers the device and validates its identity
+    // against the FirmwareSpec, on behalf of new and with_backend.
+    fn validated(
+        device_id: String,
+        spec: &FirmwareSpec,
+        backend: Option<Arc<dyn CommandBackend>>,
+    ) -> FirmwareResult<Self> {
+        let device_info = discover(backend.as_deref(), &device_id).map_err(|e| {
             FirmwareError::ConfigError(format!("Failed to discover device '{}': {e}", device_id))
         })?;
 
@@ -90,6 +115,7 @@ impl FirmwareFlasher {
             device_id,
             firmware_spec: spec.clone(),
             dry_run: false,
+            backend,
         })
     }
 
@@ -125,7 +151,10 @@ impl FirmwareFlasher {
         if let Some(device_conf) = spec.build_device_conf_source()? {
             tracing::info!(source = %device_conf.description(), "Applying device config");
 
-            let exec_options = ExecOptions::new().with_dry_run(self.dry_run);
+            let mut exec_options = ExecOptions::new().with_dry_run(self.dry_run);
+            if let Some(backend) = &self.backend {
+                exec_options = exec_options.with_backend(backend.clone());
+            }
             let applier = MlxConfigApplier::with_options(&self.device_id, exec_options);
 
             let conf_path = device_conf.resolve(&cache_dir).await?;
@@ -141,11 +170,7 @@ impl FirmwareFlasher {
 
         tracing::info!(device = %self.device_id, "Burning firmware via flint");
 
-        let flint = if self.dry_run {
-            FlintRunner::with_path("flint").with_dry_run(true)
-        } else {
-            FlintRunner::new().map_err(FirmwareError::FlintError)?
-        };
+        let flint = self.flint_runner()?;
 
         match flint.burn(&self.device_id, &firmware_path) {
             Ok(output) => {
@@ -207,11 +232,7 @@ impl FirmwareFlasher {
             "Verifying firmware image"
         );
 
-        let flint = if self.dry_run {
-            FlintRunner::with_path("flint").with_dry_run(true)
-        } else {
-            FlintRunner::new().map_err(FirmwareError::FlintError)?
-        };
+        let flint = self.flint_runner()?;
 
         match flint.verify_image(&self.device_id, &image_path) {
             Ok(output) => {
@@ -245,13 +266,12 @@ impl FirmwareFlasher {
             return Ok(Some(expected.clone()));
         }
 
-        let device_info =
-            crate::device::discovery::discover_device(&self.device_id).map_err(|e| {
-                FirmwareError::VerificationFailed(format!(
-                    "Failed to query device '{}': {e}",
-                    self.device_id
-                ))
-            })?;
+        let device_info = discover(self.backend.as_deref(), &self.device_id).map_err(|e| {
+            FirmwareError::VerificationFailed(format!(
+                "Failed to query device '{}': {e}",
+                self.device_id
+            ))
+        })?;
 
         let installed = device_info
             .fw_version_current
@@ -291,11 +311,7 @@ impl FirmwareFlasher {
             "Resetting device via mlxfwreset"
         );
 
-        let runner = if self.dry_run {
-            MlxFwResetRunner::with_path("mlxfwreset").with_dry_run(true)
-        } else {
-            MlxFwResetRunner::new()?
-        };
+        let runner = self.reset_runner()?;
 
         match runner.reset(&self.device_id, level) {
             Ok(output) => {
@@ -311,6 +327,33 @@ impl FirmwareFlasher {
         }
     }
 
+    // flint_runner builds the FlintRunner for the configured mode,
+    // preferring dry-run, then the configured backend, and finally
+    // the flint executable found on the system.
+    fn flint_runner(&self) -> FirmwareResult<FlintRunner> {
+        if self.dry_run {
+            return Ok(FlintRunner::with_path("flint").with_dry_run(true));
+        }
+        match &self.backend {
+            Some(backend) => Ok(FlintRunner::with_path("flint").with_backend(backend.clone())),
+            None => FlintRunner::new().map_err(FirmwareError::FlintError),
+        }
+    }
+
+    // reset_runner builds the MlxFwResetRunner, following the
+    // same rules as flint_runner.
+    fn reset_runner(&self) -> FirmwareResult<MlxFwResetRunner> {
+        if self.dry_run {
+            return Ok(MlxFwResetRunner::with_path("mlxfwreset").with_dry_run(true));
+        }
+        match &self.backend {
+            Some(backend) => {
+                Ok(MlxFwResetRunner::with_path("mlxfwreset").with_backend(backend.clone()))
+            }
+            None => MlxFwResetRunner::new(),
+        }
+    }
+
     // apply executes the full firmware lifecycle from a FirmwareFlasherProfile:
     //
     //   1. Flash firmware (burn via flint) — Err = burn failed, caller retries
@@ -363,7 +406,7 @@ impl FirmwareFlasher {
 
         // Step 4: Verify firmware version (if enabled).
         let (observed_version, verified_version) = if options.verify_version {
-            let observed = match crate::device::discovery::discover_device(&self.device_id) {
+            let observed = match discover(self.backend.as_deref(), &self.device_id) {
                 Ok(info) => info.fw_version_current,
                 Err(e) => {
                     tracing::error!(
@@ -434,3 +477,12 @@ impl FirmwareFlasher {
         Ok(report)
     }
 }
+
+// discover queries a device via mlxfwmanager, using the backend
+// if one is set, or the system otherwise.
+fn discover(
+    backend: Option<&dyn CommandBackend>,
+    device_id: &str,
+) -> Result<MlxDeviceInfo, String> {
+    crate::device::discovery::discover_device_with(backend.unwrap_or(&SystemBackend), device_id)
+}
@@ -16,10 +16,13 @@
  */
 
 use std::process::{Command, Stdio};
+use std::sync::Arc;
 
 use tracing;
 
 use crate::firmware::error::{FirmwareError, FirmwareResult};
+use crate::runner::backend::{CommandBackend, SystemBackend};
+use crate::runner::command_builder::CommandSpec;
 
 // DEFAULT_RESET_LEVEL is the default reset level for mlxfwreset, which
 // corresponds to a full NIC reset (driver restart + firmware reset).
@@ -32,6 +35,8 @@ pub struct MlxFwResetRunner {
     mlxfwreset_path: String,
     // dry_run determines whether to perform dry-run operations.
     dry_run: bool,
+    // backend executes the mlxfwreset commands.
+    backend: Arc<dyn CommandBackend>,
 }
 
 impl MlxFwResetRunner {
@@ -42,6 +47,7 @@ impl MlxFwResetRunner {
         Ok(Self {
             mlxfwreset_path: path,
             dry_run: false,
+            backend: Arc::new(SystemBackend),
         })
     }
 
@@ -51,6 +57,7 @@ impl MlxFwResetRunner {
         Self {
             mlxfwreset_path: path.into(),
             dry_run: false,
+            backend: Arc::new(SystemBackend),
         }
     }
 
@@ -60,6 +67,13 @@ impl MlxFwResetRunner {
         self
     }
 
+    // with_backend sets the backend used to execute mlxfwreset,
+    // e.g. an MlxSimulator in tests.
+    pub fn with_backend(mut self, backend: Arc<dyn CommandBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
     // find_mlxfwreset attempts to find the mlxfwreset executable
     // in common installation locations.
     fn find_mlxfwreset() -> FirmwareResult<String> {
@@ -105,9 +119,13 @@ impl MlxFwResetRunner {
 
         tracing::debug!(cmd = %self.build_command(&args), "Executing mlxfwreset");
 
-        let output = Command::new(&self.mlxfwreset_path)
-            .args(args)
-            .output()
+        let spec = CommandSpec::new(&self.mlxfwreset_path).args(args);
+        let output = self
+            .backend
+            .run(&spec, None)
+            .and_then(|output| {
+                output.ok_or_else(|| std::io::Error::from(std::io::ErrorKind::TimedOut))
+            })
             .map_err(|e| {
                 FirmwareError::ResetFailed(format!("Failed to execute mlxfwreset: {e}"))
             })?;
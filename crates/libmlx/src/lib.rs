@@ -22,4 +22,5 @@ pub mod lockdown;
 pub mod profile;
 pub mod registry;
 pub mod runner;
+pub mod simulator;
 pub mod variables;
@@ -16,9 +16,12 @@
  */
 
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
+use std::sync::Arc;
 
 use crate::lockdown::error::{MlxError, MlxResult};
+use crate::runner::backend::{CommandBackend, SystemBackend};
+use crate::runner::command_builder::CommandSpec;
 
 // FlintRunner is a wrapper for executing flint commands.
 pub struct FlintRunner {
@@ -26,6 +29,8 @@ pub struct FlintRunner {
     flint_path: String,
     // dry_run determines whether to perform dry-run operations.
     dry_run: bool,
+    // backend executes the flint commands.
+    backend: Arc<dyn CommandBackend>,
 }
 
 impl FlintRunner {
@@ -35,6 +40,7 @@ impl FlintRunner {
         Ok(Self {
             flint_path,
             dry_run: false,
+            backend: Arc::new(SystemBackend),
         })
     }
 
@@ -43,6 +49,7 @@ impl FlintRunner {
         Self {
             flint_path: path.into(),
             dry_run: false,
+            backend: Arc::new(SystemBackend),
         }
     }
 
@@ -52,6 +59,13 @@ impl FlintRunner {
         self
     }
 
+    // with_backend sets the backend used to execute flint commands,
+    // e.g. an MlxSimulator in tests.
+    pub fn with_backend(mut self, backend: Arc<dyn CommandBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
     // find_flint attempts to find the flint executable in common locations.
     fn find_flint() -> MlxResult<String> {
         let common_paths = [
@@ -81,6 +95,14 @@ impl FlintRunner {
         format!("{} {}", self.flint_path, args.join(" "))
     }
 
+    // run executes flint with the given arguments via the backend.
+    fn run(&self, args: &[&str]) -> std::io::Result<Output> {
+        let spec = CommandSpec::new(&self.flint_path).args(args.iter().copied());
+        self.backend
+            .run(&spec, None)?
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::TimedOut))
+    }
+
     // query_device queries device information and hardware access status.
     pub fn query_device(&self, device_id: &str) -> MlxResult<String> {
         let args = ["-d", device_id, "q"];
@@ -89,9 +111,8 @@ impl FlintRunner {
             return Err(MlxError::DryRun(self.build_command(&args)));
         }
 
-        let output = Command::new(&self.flint_path)
-            .args(args)
-            .output()
+        let output = self
+            .run(&args)
             .map_err(|e| MlxError::CommandFailed(format!("Failed to execute query: {e}")))?;
 
         if !output.status.success() {
@@ -128,9 +149,8 @@ impl FlintRunner {
             return Err(MlxError::DryRun(self.build_command(&args)));
         }
 
-        let output = Command::new(&self.flint_path)
-            .args(args)
-            .output()
+        let output = self
+            .run(&args)
             .map_err(|e| MlxError::CommandFailed(format!("Failed to execute enable: {e}")))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -162,9 +182,8 @@ impl FlintRunner {
             return Err(MlxError::DryRun(self.build_command(&args)));
         }
 
-        let output = Command::new(&self.flint_path)
-            .args(args)
-            .output()
+        let output = self
+            .run(&args)
             .map_err(|e| MlxError::CommandFailed(format!("Failed to execute disable: {e}")))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -195,9 +214,8 @@ impl FlintRunner {
             return Err(MlxError::DryRun(self.build_command(&args)));
         }
 
-        let output = Command::new(&self.flint_path)
-            .args(args)
-            .output()
+        let output = self
+            .run(&args)
             .map_err(|e| MlxError::CommandFailed(format!("Failed to execute set_key: {e}")))?;
 
         if !output.status.success() {
@@ -234,9 +252,8 @@ impl FlintRunner {
             return Err(MlxError::DryRun(self.build_command(&args)));
         }
 
-        let output = Command::new(&self.flint_path)
-            .args(args)
-            .output()
+        let output = self
+            .run(&args)
             .map_err(|e| MlxError::CommandFailed(format!("Failed to execute burn: {e}")))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
@@ -278,12 +295,9 @@ impl FlintRunner {
             return Err(MlxError::DryRun(self.build_command(&args)));
         }
 
-        let output = Command::new(&self.flint_path)
-            .args(args)
-            .output()
-            .map_err(|e| {
-                MlxError::CommandFailed(format!("Failed to execute verify with image: {e}"))
-            })?;
+        let output = self.run(&args).map_err(|e| {
+            MlxError::CommandFailed(format!("Failed to execute verify with image: {e}"))
+        })?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -0,0 +1,87 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/backend.rs
+// CommandBackend is the seam between libmlx and the Mellanox tools it
+// drives (mlxconfig, flint, mlxfwreset and mlxfwmanager). Production
+// code uses SystemBackend, which spawns the real processes, while tests
+// and machine-a-tron plug in the stateful simulator from the
+// simulator module instead.
+
+use std::process::{Output, Stdio};
+use std::time::Duration;
+
+use wait_timeout::ChildExt;
+
+use crate::runner::command_builder::CommandSpec;
+
+// CommandBackend runs a single command and returns its raw output,
+// leaving exit code interpretation to the caller (each tool wrapper
+// has its own rules for what counts as success).
+pub trait CommandBackend: Send + Sync + std::fmt::Debug {
+    // run executes the command, waiting at most `timeout` for it to
+    // finish. Returns Ok(None) if the command timed out.
+    fn run(&self, spec: &CommandSpec, timeout: Option<Duration>)
+    -> std::io::Result<Option<Output>>;
+}
+
+// SystemBackend executes commands as real child processes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemBackend;
+
+impl CommandBackend for SystemBackend {
+    fn run(
+        &self,
+        spec: &CommandSpec,
+        timeout: Option<Duration>,
+    ) -> std::io::Result<Option<Output>> {
+        let mut child = spec
+            .to_command()
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let Some(timeout) = timeout else {
+            return child.wait_with_output().map(Some);
+        };
+
+        match child.wait_timeout(timeout)? {
+            Some(status) => {
+                let mut stdout = Vec::new();
+                if let Some(mut pipe) = child.stdout.take() {
+                    std::io::Read::read_to_end(&mut pipe, &mut stdout)?;
+                }
+                let mut stderr = Vec::new();
+                if let Some(mut pipe) = child.stderr.take() {
+                    std::io::Read::read_to_end(&mut pipe, &mut stderr)?;
+                }
+                Ok(Some(Output {
+                    status,
+                    stdout,
+                    stderr,
+                }))
+            }
+            None => {
+                // Timed out, so kill the process and wait
+                // for it to be cleaned up.
+                let _ = child.kill();
+                let _ = child.wait();
+                Ok(None)
+            }
+        }
+    }
+}
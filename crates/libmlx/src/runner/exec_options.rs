@@ -21,8 +21,11 @@
 // allowing us to set various things for how we want to interact
 // (or not) with mlxconfig, including timeout and retry behavior.
 
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::runner::backend::{CommandBackend, SystemBackend};
+
 // DESTRUCTIVE_VARIABLES are variables that may potentially require
 // confirmation before modification (and will be enforced if the
 // runner is configured with confirm_destructive: true).
@@ -67,6 +70,11 @@ pub struct ExecOptions {
     // confirm_destructive will make it so the runner requires
     // confirmation for destructive variables.
     pub confirm_destructive: bool,
+
+    // backend is what actually executes the mlxconfig commands,
+    // which is the real system by default, but can be swapped
+    // out for a simulator in tests.
+    pub backend: Arc<dyn CommandBackend>,
 }
 
 impl Default for ExecOptions {
@@ -83,6 +91,7 @@ impl Default for ExecOptions {
             verbose: false,
             log_json_output: false,
             confirm_destructive: false,
+            backend: Arc::new(SystemBackend),
         }
     }
 }
@@ -152,6 +161,13 @@ impl ExecOptions {
         self.confirm_destructive = confirm_destructive;
         self
     }
+
+    // Sets the backend used to execute commands, e.g. an MlxSimulator
+    // instead of the real mlxconfig binary.
+    pub fn with_backend(mut self, backend: Arc<dyn CommandBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
 }
 
 // Checks if a given variable is considered a "destructive" variable
@@ -23,12 +23,11 @@
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Output, Stdio};
+use std::process::Output;
 use std::time::{Duration, Instant};
 
 use backon::{BlockingRetryable, ExponentialBuilder};
 use uuid::Uuid;
-use wait_timeout::ChildExt;
 
 use crate::runner::command_builder::CommandSpec;
 use crate::runner::error::MlxRunnerError;
@@ -113,23 +112,42 @@ impl<'a> CommandExecutor<'a> {
     // This is called by the retry logic for each attempt.
     fn execute_single_attempt(&self, command_spec: &CommandSpec) -> Result<Output, MlxRunnerError> {
         let start_time = Instant::now();
-        let mut command = command_spec.to_command();
+        if self.options.verbose
+            && let Some(timeout) = self.options.timeout
+        {
+            println!(
+                "[TIMEOUT] Waiting for command with timeout: {}",
+                format_duration(timeout)
+            );
+        }
 
-        // Spawn the child process
-        let child = command
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
+        let output = self
+            .options
+            .backend
+            .run(command_spec, self.options.timeout)
             .map_err(MlxRunnerError::Io)?;
 
-        // Apply timeout if configured
-        let output = if let Some(timeout) = self.options.timeout {
-            self.execute_with_timeout(child, timeout, start_time, command_spec)?
-        } else {
-            // No timeout - just wait for completion
-            child.wait_with_output().map_err(MlxRunnerError::Io)?
+        let execution_time = start_time.elapsed();
+        let Some(output) = output else {
+            if self.options.verbose {
+                println!(
+                    "[TIMEOUT] Command timed out after {}, killing process",
+                    format_duration(execution_time)
+                );
+            }
+            return Err(MlxRunnerError::Timeout {
+                command: command_spec.to_string(),
+                duration: self.options.timeout.unwrap_or_default(),
+            });
         };
 
+        if self.options.verbose {
+            println!(
+                "[TIMEOUT] Command completed in {}",
+                format_duration(execution_time)
+            );
+        }
+
         // Check if command succeeded
         if output.status.success() {
             Ok(output)
@@ -141,81 +159,6 @@ impl<'a> CommandExecutor<'a> {
         }
     }
 
-    // Executes a child process with the specified timeout using wait-timeout crate.
-    // Returns the output if successful, or kills the process and returns a timeout error.
-    fn execute_with_timeout(
-        &self,
-        mut child: Child,
-        timeout: Duration,
-        start_time: Instant,
-        command_spec: &CommandSpec,
-    ) -> Result<Output, MlxRunnerError> {
-        if self.options.verbose {
-            println!(
-                "[TIMEOUT] Waiting for command with timeout: {}",
-                format_duration(timeout)
-            );
-        }
-
-        // Wait for the child process with timeout
-        match child.wait_timeout(timeout).map_err(MlxRunnerError::Io)? {
-            Some(status) => {
-                // Process completed within timeout
-                let execution_time = start_time.elapsed();
-                if self.options.verbose {
-                    println!(
-                        "[TIMEOUT] Command completed in {}",
-                        format_duration(execution_time)
-                    );
-                }
-
-                // Collect stdout and stderr
-                let stdout = if let Some(mut stdout) = child.stdout.take() {
-                    let mut buf = Vec::new();
-                    std::io::Read::read_to_end(&mut stdout, &mut buf)
-                        .map_err(MlxRunnerError::Io)?;
-                    buf
-                } else {
-                    Vec::new()
-                };
-
-                let stderr = if let Some(mut stderr) = child.stderr.take() {
-                    let mut buf = Vec::new();
-                    std::io::Read::read_to_end(&mut stderr, &mut buf)
-                        .map_err(MlxRunnerError::Io)?;
-                    buf
-                } else {
-                    Vec::new()
-                };
-
-                Ok(Output {
-                    status,
-                    stdout,
-                    stderr,
-                })
-            }
-            None => {
-                // Process timed out - kill it and return timeout error
-                let execution_time = start_time.elapsed();
-                if self.options.verbose {
-                    println!(
-                        "[TIMEOUT] Command timed out after {}, killing process",
-                        format_duration(execution_time)
-                    );
-                }
-
-                // Kill the child process
-                let _ = child.kill();
-                let _ = child.wait(); // Wait for process to be cleaned up
-
-                Err(MlxRunnerError::Timeout {
-                    command: command_spec.to_string(),
-                    duration: timeout,
-                })
-            }
-        }
-    }
-
     // Determines whether an error should trigger a retry or is permanent.
     // Currently treats I/O errors and command execution failures as transient,
     // but treats specific errors like VariableNotFound as permanent.
@@ -16,6 +16,7 @@
  */
 
 pub mod applier;
+pub mod backend;
 pub mod command_builder;
 pub mod error;
 pub mod exec_options;
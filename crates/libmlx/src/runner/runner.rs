@@ -432,7 +432,11 @@ impl MlxConfigRunner {
         }
 
         // Discover the device to get its info.
-        let device_info = crate::device::discovery::discover_device(&self.device).map_err(|e| {
+        let device_info = crate::device::discovery::discover_device_with(
+            self.options.backend.as_ref(),
+            &self.device,
+        )
+        .map_err(|e| {
             MlxRunnerError::GenericError(format!(
                 "Failed to discover device '{}': {}",
                 self.device, e
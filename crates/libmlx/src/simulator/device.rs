@@ -0,0 +1,532 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/simulator/device.rs
+// SimulatedDevice is the state the MlxSimulator keeps for a single
+// Mellanox device: its identity (as reported by mlxfwmanager), its
+// mlxconfig variables with default, current and next (pending) values,
+// its flint lockdown state, and its firmware version (including any
+// firmware which has been burned, but not yet activated by a reset).
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::device::filters::{DeviceField, MatchMode};
+use crate::variables::registry::MlxVariableRegistry;
+use crate::variables::spec::MlxVariableSpec;
+use crate::variables::variable::MlxConfigVariable;
+
+// normalize_device_id strips the PCI domain (if present), so that
+// "0000:b4:00.0" and "b4:00.0" refer to the same device, the same
+// way convert_pci_name_to_address does for mlxfwmanager output.
+pub(crate) fn normalize_device_id(device: &str) -> String {
+    device
+        .strip_prefix("0000:")
+        .unwrap_or(device)
+        .to_lowercase()
+}
+
+// SimValue is a single simulated variable value.
+#[derive(Debug, Clone, PartialEq)]
+enum SimValue {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+}
+
+impl SimValue {
+    // display returns the value in the same format mlxconfig
+    // accepts for `set`, which is also how tests compare values.
+    fn display(&self) -> String {
+        match self {
+            SimValue::Bool(b) => b.to_string(),
+            SimValue::Int(i) => i.to_string(),
+            SimValue::Text(s) => s.clone(),
+        }
+    }
+}
+
+// SlotKind is how a single (scalar, or single array index)
+// variable slot is parsed from `set` and rendered as JSON.
+#[derive(Debug, Clone)]
+enum SlotKind {
+    Boolean,
+    Integer,
+    Enum(Vec<String>),
+    Hex,
+    Text,
+}
+
+impl SlotKind {
+    // from_spec returns the slot kind and the number of array
+    // indices for a variable spec (None for scalar variables).
+    fn from_spec(spec: &MlxVariableSpec) -> (Self, Option<usize>) {
+        match spec {
+            MlxVariableSpec::Boolean => (SlotKind::Boolean, None),
+            MlxVariableSpec::Integer | MlxVariableSpec::Preset { .. } => (SlotKind::Integer, None),
+            MlxVariableSpec::Enum { options } => (SlotKind::Enum(options.clone()), None),
+            MlxVariableSpec::Binary | MlxVariableSpec::Bytes | MlxVariableSpec::Opaque => {
+                (SlotKind::Hex, None)
+            }
+            MlxVariableSpec::String | MlxVariableSpec::Array => (SlotKind::Text, None),
+            MlxVariableSpec::BooleanArray { size } => (SlotKind::Boolean, Some(*size)),
+            MlxVariableSpec::IntegerArray { size } => (SlotKind::Integer, Some(*size)),
+            MlxVariableSpec::EnumArray { options, size } => {
+                (SlotKind::Enum(options.clone()), Some(*size))
+            }
+            MlxVariableSpec::BinaryArray { size } => (SlotKind::Hex, Some(*size)),
+        }
+    }
+
+    // default_value is the factory default for a fresh device.
+    fn default_value(&self) -> SimValue {
+        match self {
+            SlotKind::Boolean => SimValue::Bool(false),
+            SlotKind::Integer => SimValue::Int(0),
+            SlotKind::Enum(options) => SimValue::Text(options.first().cloned().unwrap_or_default()),
+            SlotKind::Hex => SimValue::Text("0x00".to_string()),
+            SlotKind::Text => SimValue::Text(String::new()),
+        }
+    }
+
+    // parse parses a value as given to `mlxconfig set`.
+    fn parse(&self, raw: &str) -> Result<SimValue, String> {
+        let raw = raw.trim();
+        match self {
+            SlotKind::Boolean => match raw.to_lowercase().as_str() {
+                "true" | "1" => Ok(SimValue::Bool(true)),
+                "false" | "0" => Ok(SimValue::Bool(false)),
+                _ => Err(format!("invalid boolean value '{raw}'")),
+            },
+            SlotKind::Integer => raw
+                .parse::<i64>()
+                .map(SimValue::Int)
+                .map_err(|_| format!("invalid integer value '{raw}'")),
+            SlotKind::Enum(options) => options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(raw))
+                .map(|option| SimValue::Text(option.clone()))
+                .ok_or_else(|| format!("invalid value '{raw}', expected one of {options:?}")),
+            SlotKind::Hex => {
+                let digits = raw
+                    .strip_prefix("0x")
+                    .or_else(|| raw.strip_prefix("0X"))
+                    .unwrap_or(raw);
+                hex::decode(digits)
+                    .map(|bytes| SimValue::Text(format!("0x{}", hex::encode(bytes))))
+                    .map_err(|_| format!("invalid hex value '{raw}'"))
+            }
+            SlotKind::Text => Ok(SimValue::Text(raw.to_string())),
+        }
+    }
+
+    // to_json renders a value the way `mlxconfig -j` does,
+    // including the "(n)" suffixes on booleans and enums.
+    fn to_json(&self, value: &SimValue) -> serde_json::Value {
+        match (self, value) {
+            (SlotKind::Boolean, SimValue::Bool(true)) => "True(1)".into(),
+            (SlotKind::Boolean, SimValue::Bool(false)) => "False(0)".into(),
+            (SlotKind::Enum(options), SimValue::Text(option)) => {
+                let index = options.iter().position(|o| o == option).unwrap_or(0);
+                format!("{option}({index})").into()
+            }
+            (_, SimValue::Int(i)) => (*i).into(),
+            (_, other) => other.display().into(),
+        }
+    }
+}
+
+// SimulatedSlot is a single scalar variable, or a single
+// index of an array variable (e.g. "MODULE_SPLIT_M0[3]").
+#[derive(Debug, Clone)]
+struct SimulatedSlot {
+    kind: SlotKind,
+    read_only: bool,
+    default: SimValue,
+    current: SimValue,
+    next: SimValue,
+}
+
+// SimulatedDevice is a single simulated Mellanox device.
+#[derive(Debug, Clone)]
+pub struct SimulatedDevice {
+    // pci_name is the PCI address of the device, as
+    // reported by mlxfwmanager (e.g. "0000:b4:00.0").
+    pci_name: String,
+    device_type: String,
+    part_number: String,
+    psid: String,
+    description: String,
+    base_mac: String,
+    // fw_version is the running firmware version.
+    fw_version: String,
+    // pending_fw_version is the version of firmware which
+    // has been burned, but not activated by a reset yet.
+    pending_fw_version: Option<String>,
+    // burned_image is the file name of the last image
+    // burned, which is what `flint verify` checks against.
+    burned_image: Option<String>,
+    // lock_key is the key the device is locked with,
+    // or None if hardware access is enabled.
+    lock_key: Option<String>,
+    // slots are all variable slots, keyed by the name
+    // mlxconfig uses in its JSON output.
+    slots: BTreeMap<String, SimulatedSlot>,
+    // arrays maps array variable names to their size, so
+    // a query for "NAME" can be expanded to all indices.
+    arrays: BTreeMap<String, usize>,
+    // applied_configs are all config files given
+    // to `mlxconfig apply`, in order.
+    applied_configs: Vec<PathBuf>,
+    // reset_count is the number of mlxfwresets performed.
+    reset_count: u32,
+}
+
+impl SimulatedDevice {
+    // new creates a device with no variables, and a generic
+    // ConnectX-7 identity, which can be overridden with the
+    // with_* builders.
+    pub fn new(pci_name: impl Into<String>) -> Self {
+        let pci_name = pci_name.into();
+        let pci_name = if pci_name.starts_with("0000:") || pci_name.starts_with('/') {
+            pci_name
+        } else {
+            format!("0000:{pci_name}")
+        };
+        Self {
+            pci_name,
+            device_type: "ConnectX7".to_string(),
+            part_number: "MCX75310AAS-NEA_Ax".to_string(),
+            psid: "MT_0000000834".to_string(),
+            description: "Simulated NVIDIA ConnectX-7 adapter".to_string(),
+            base_mac: "b83fd2000000".to_string(),
+            fw_version: "28.39.1002".to_string(),
+            pending_fw_version: None,
+            burned_image: None,
+            lock_key: None,
+            slots: BTreeMap::new(),
+            arrays: BTreeMap::new(),
+            applied_configs: Vec::new(),
+            reset_count: 0,
+        }
+    }
+
+    // from_registry creates a device with all variables from the
+    // registry, and whose device type and part number satisfy the
+    // registry's exact-match filters, if it has any.
+    pub fn from_registry(pci_name: impl Into<String>, registry: &MlxVariableRegistry) -> Self {
+        let mut device = Self::new(pci_name).with_registry(registry);
+        for filter in registry.filters.iter().flat_map(|set| set.filters.iter()) {
+            if filter.match_mode != MatchMode::Exact {
+                continue;
+            }
+            let Some(value) = filter.values.first() else {
+                continue;
+            };
+            match filter.field {
+                DeviceField::DeviceType => device.device_type = value.clone(),
+                DeviceField::PartNumber => device.part_number = value.clone(),
+                DeviceField::FirmwareVersion => device.fw_version = value.clone(),
+                DeviceField::Description => device.description = value.clone(),
+                _ => {}
+            }
+        }
+        device
+    }
+
+    // with_registry adds all variables from the registry, with
+    // every value set to its default.
+    pub fn with_registry(mut self, registry: &MlxVariableRegistry) -> Self {
+        for variable in &registry.variables {
+            self.add_variable(variable);
+        }
+        self
+    }
+
+    // with_device_type sets the device type (e.g. "BlueField3").
+    pub fn with_device_type(mut self, device_type: impl Into<String>) -> Self {
+        self.device_type = device_type.into();
+        self
+    }
+
+    // with_part_number sets the part number.
+    pub fn with_part_number(mut self, part_number: impl Into<String>) -> Self {
+        self.part_number = part_number.into();
+        self
+    }
+
+    // with_psid sets the PSID.
+    pub fn with_psid(mut self, psid: impl Into<String>) -> Self {
+        self.psid = psid.into();
+        self
+    }
+
+    // with_description sets the device description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    // with_base_mac sets the base MAC, in the colon-less
+    // format mlxfwmanager reports (e.g. "b83fd2000000").
+    pub fn with_base_mac(mut self, base_mac: impl Into<String>) -> Self {
+        self.base_mac = base_mac.into();
+        self
+    }
+
+    // with_fw_version sets the running firmware version.
+    pub fn with_fw_version(mut self, version: impl Into<String>) -> Self {
+        self.fw_version = version.into();
+        self
+    }
+
+    // with_lock_key starts the device out locked with the given key.
+    pub fn with_lock_key(mut self, key: impl Into<String>) -> Self {
+        self.lock_key = Some(key.into());
+        self
+    }
+
+    // add_variable adds a variable (all indices, for arrays),
+    // with every value set to its default.
+    pub fn add_variable(&mut self, variable: &MlxConfigVariable) {
+        let (kind, size) = SlotKind::from_spec(&variable.spec);
+        let names = match size {
+            Some(size) => {
+                self.arrays.insert(variable.name.clone(), size);
+                (0..size)
+                    .map(|i| format!("{}[{i}]", variable.name))
+                    .collect()
+            }
+            None => vec![variable.name.clone()],
+        };
+        for name in names {
+            let default = kind.default_value();
+            self.slots.insert(
+                name,
+                SimulatedSlot {
+                    kind: kind.clone(),
+                    read_only: variable.read_only,
+                    current: default.clone(),
+                    next: default.clone(),
+                    default,
+                },
+            );
+        }
+    }
+
+    // set_value sets both the current and next value of a variable
+    // (or a single array index, e.g. "NAME[2]"), as if the device
+    // had already been configured and reset.
+    pub fn set_value(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let slot = self
+            .slots
+            .get_mut(name)
+            .ok_or_else(|| format!("unknown variable '{name}'"))?;
+        let value = slot.kind.parse(value)?;
+        slot.current = value.clone();
+        slot.next = value;
+        Ok(())
+    }
+
+    // set_fw_version sets the running firmware version, and
+    // clears any pending firmware.
+    pub fn set_fw_version(&mut self, version: impl Into<String>) {
+        self.fw_version = version.into();
+        self.pending_fw_version = None;
+    }
+
+    // set_pending_fw_version stages firmware which will become the
+    // running version on the next reset, as if it had been burned.
+    pub fn set_pending_fw_version(&mut self, version: impl Into<String>) {
+        self.pending_fw_version = Some(version.into());
+    }
+
+    // pci_name returns the PCI name as mlxfwmanager reports it.
+    pub fn pci_name(&self) -> &str {
+        &self.pci_name
+    }
+
+    // device_type returns the device type.
+    pub fn device_type(&self) -> &str {
+        &self.device_type
+    }
+
+    // part_number returns the part number.
+    pub fn part_number(&self) -> &str {
+        &self.part_number
+    }
+
+    // psid returns the PSID.
+    pub fn psid(&self) -> &str {
+        &self.psid
+    }
+
+    // description returns the device description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    // base_mac returns the base MAC.
+    pub fn base_mac(&self) -> &str {
+        &self.base_mac
+    }
+
+    // fw_version returns the running firmware version.
+    pub fn fw_version(&self) -> &str {
+        &self.fw_version
+    }
+
+    // pending_fw_version returns the version burned but not
+    // yet activated, if any.
+    pub fn pending_fw_version(&self) -> Option<&str> {
+        self.pending_fw_version.as_deref()
+    }
+
+    // is_locked returns whether hardware access is disabled.
+    pub fn is_locked(&self) -> bool {
+        self.lock_key.is_some()
+    }
+
+    // current_value returns the running value of a variable slot.
+    pub fn current_value(&self, name: &str) -> Option<String> {
+        self.slots.get(name).map(|slot| slot.current.display())
+    }
+
+    // next_value returns the value a variable slot will
+    // have after the next reset.
+    pub fn next_value(&self, name: &str) -> Option<String> {
+        self.slots.get(name).map(|slot| slot.next.display())
+    }
+
+    // has_pending_changes returns whether any variable has a next
+    // value which differs from its current value.
+    pub fn has_pending_changes(&self) -> bool {
+        self.slots.values().any(|slot| slot.next != slot.current)
+    }
+
+    // applied_configs returns all files given to `mlxconfig apply`.
+    pub fn applied_configs(&self) -> &[PathBuf] {
+        &self.applied_configs
+    }
+
+    // reset_count returns the number of mlxfwresets performed.
+    pub fn reset_count(&self) -> u32 {
+        self.reset_count
+    }
+
+    // expand_query_names expands a queried name into the slot
+    // names it covers (all indices for an array base name).
+    pub(crate) fn expand_query_names(&self, name: &str) -> Option<Vec<String>> {
+        if let Some(size) = self.arrays.get(name) {
+            return Some((0..*size).map(|i| format!("{name}[{i}]")).collect());
+        }
+        self.slots
+            .contains_key(name)
+            .then(|| vec![name.to_string()])
+    }
+
+    // all_slot_names returns every slot name, which is
+    // what a query without any variables returns.
+    pub(crate) fn all_slot_names(&self) -> Vec<String> {
+        self.slots.keys().cloned().collect()
+    }
+
+    // slot_json renders a single slot as an mlxconfig JSON
+    // tlv_configuration entry.
+    pub(crate) fn slot_json(&self, name: &str) -> Option<serde_json::Value> {
+        let slot = self.slots.get(name)?;
+        Some(serde_json::json!({
+            "current_value": slot.kind.to_json(&slot.current),
+            "default_value": slot.kind.to_json(&slot.default),
+            "modified": slot.next != slot.default,
+            "next_value": slot.kind.to_json(&slot.next),
+            "read_only": slot.read_only,
+        }))
+    }
+
+    // stage_assignments validates and then applies a set of
+    // `NAME=value` assignments to next values. Nothing is
+    // changed if any assignment is invalid.
+    pub(crate) fn stage_assignments(&mut self, assignments: &[String]) -> Result<(), String> {
+        let mut staged = Vec::with_capacity(assignments.len());
+        for assignment in assignments {
+            let (name, raw) = assignment
+                .split_once('=')
+                .ok_or_else(|| format!("invalid assignment '{assignment}'"))?;
+            let slot = self
+                .slots
+                .get(name)
+                .ok_or_else(|| format!("Unknown Parameter: {name}"))?;
+            if slot.read_only {
+                return Err(format!("The Parameter {name} is read only"));
+            }
+            staged.push((name.to_string(), slot.kind.parse(raw)?));
+        }
+        for (name, value) in staged {
+            if let Some(slot) = self.slots.get_mut(&name) {
+                slot.next = value;
+            }
+        }
+        Ok(())
+    }
+
+    // reset_to_defaults sets all next values back to their defaults.
+    pub(crate) fn reset_to_defaults(&mut self) {
+        for slot in self.slots.values_mut() {
+            slot.next = slot.default.clone();
+        }
+    }
+
+    // record_applied_config records a file given to `mlxconfig apply`.
+    pub(crate) fn record_applied_config(&mut self, path: PathBuf) {
+        self.applied_configs.push(path);
+    }
+
+    // lock_key returns the key the device is locked with.
+    pub(crate) fn lock_key(&self) -> Option<&str> {
+        self.lock_key.as_deref()
+    }
+
+    // set_lock_key locks (Some) or unlocks (None) the device.
+    pub(crate) fn set_lock_key(&mut self, key: Option<String>) {
+        self.lock_key = key;
+    }
+
+    // burned_image returns the file name of the last burned image.
+    pub(crate) fn burned_image(&self) -> Option<&str> {
+        self.burned_image.as_deref()
+    }
+
+    // record_burn records a burned image, and its version as the
+    // pending firmware version.
+    pub(crate) fn record_burn(&mut self, image: String, version: String) {
+        self.burned_image = Some(image);
+        self.pending_fw_version = Some(version);
+    }
+
+    // activate is what mlxfwreset does: pending firmware and
+    // next variable values become current.
+    pub(crate) fn activate(&mut self) {
+        if let Some(version) = self.pending_fw_version.take() {
+            self.fw_version = version;
+        }
+        for slot in self.slots.values_mut() {
+            slot.current = slot.next.clone();
+        }
+        self.reset_count += 1;
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/simulator/failure.rs
+// Scripted failure modes for the MlxSimulator, which let tests make
+// specific operations fail (optionally only for a given device, and
+// optionally only a fixed number of times) to exercise retry and
+// error handling paths.
+
+// SimOperation identifies an operation the simulator knows how
+// to emulate, and is what failure rules are matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimOperation {
+    // Discover is an mlxfwmanager device query.
+    Discover,
+    // Query is an `mlxconfig q`.
+    Query,
+    // Set is an `mlxconfig set`.
+    Set,
+    // Apply is an `mlxconfig apply`.
+    Apply,
+    // ResetConfig is an `mlxconfig reset`.
+    ResetConfig,
+    // LockQuery is a `flint q`.
+    LockQuery,
+    // Unlock is a `flint hw_access enable`.
+    Unlock,
+    // Lock is a `flint hw_access disable`.
+    Lock,
+    // SetKey is a `flint set_key`.
+    SetKey,
+    // Burn is a `flint burn`.
+    Burn,
+    // VerifyImage is a `flint verify`.
+    VerifyImage,
+    // FwReset is an `mlxfwreset reset`.
+    FwReset,
+}
+
+// SimFailure is how a matched operation fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimFailure {
+    // CommandFailed exits with code 1 and the given stderr.
+    CommandFailed(String),
+    // Timeout never completes, which the backend
+    // reports as a timed out command.
+    Timeout,
+    // PermissionDenied exits with code 1 and a
+    // permission denied error.
+    PermissionDenied,
+    // DeviceNotFound exits with code 1 and an
+    // error saying the device can't be opened.
+    DeviceNotFound,
+}
+
+// FailureRule is a single scripted failure.
+#[derive(Debug, Clone)]
+pub struct FailureRule {
+    // operation is the operation this rule applies to.
+    pub operation: SimOperation,
+    // device limits the rule to a single device, or
+    // applies it to all devices if None.
+    pub device: Option<String>,
+    // remaining is the number of times the rule will
+    // still fire, or forever if None.
+    pub remaining: Option<u32>,
+    // failure is what happens when the rule fires.
+    pub failure: SimFailure,
+}
+
+impl FailureRule {
+    // new creates a rule which fails every matching
+    // operation, on every device.
+    pub fn new(operation: SimOperation, failure: SimFailure) -> Self {
+        Self {
+            operation,
+            device: None,
+            remaining: None,
+            failure,
+        }
+    }
+
+    // on_device limits the rule to a single device.
+    pub fn on_device(mut self, device: impl Into<String>) -> Self {
+        self.device = Some(device.into());
+        self
+    }
+
+    // times limits the rule to firing `count` times, after
+    // which the operation succeeds again.
+    pub fn times(mut self, count: u32) -> Self {
+        self.remaining = Some(count);
+        self
+    }
+
+    // matches returns whether this rule applies to the given
+    // operation against the given (normalized) device.
+    pub(crate) fn matches(&self, operation: SimOperation, device: Option<&str>) -> bool {
+        if self.operation != operation || self.remaining == Some(0) {
+            return false;
+        }
+        match (&self.device, device) {
+            (None, _) => true,
+            (Some(expected), Some(device)) => {
+                crate::simulator::device::normalize_device_id(expected) == device
+            }
+            (Some(_), None) => false,
+        }
+    }
+}
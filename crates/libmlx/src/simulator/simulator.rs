@@ -0,0 +1,636 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// src/simulator/simulator.rs
+// MlxSimulator is a stateful CommandBackend which emulates the Mellanox
+// tools libmlx drives (mlxconfig, flint, mlxfwreset and mlxfwmanager)
+// against a set of SimulatedDevices, so that the runner, lockdown and
+// firmware flows can be exercised without any hardware (in tests, and
+// in machine-a-tron). It's cheap to clone, and all clones share the
+// same devices, so a test can hand a clone to the code under test and
+// then inspect device state afterwards.
+//
+// The emulation intentionally follows the same output conventions the
+// real tools have (and which our wrappers parse), e.g. mlxconfig JSON
+// booleans like "True(1)", flint reporting "HW access is disabled" for
+// locked devices, and mlxfwmanager exiting with 1 when a device can't
+// be read because it's locked.
+
+use std::collections::HashMap;
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Output};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use crate::runner::backend::CommandBackend;
+use crate::runner::command_builder::CommandSpec;
+use crate::simulator::device::{SimulatedDevice, normalize_device_id};
+use crate::simulator::failure::{FailureRule, SimFailure, SimOperation};
+
+// OPTIONS_WITH_VALUES are the command line options (across all of
+// the emulated tools) which take a value.
+const OPTIONS_WITH_VALUES: &[&str] = &[
+    "-d",
+    "-j",
+    "-i",
+    "--dev",
+    "--device",
+    "--level",
+    "--query-format",
+];
+
+// MlxSimulator is the simulated backend; see the module comment.
+#[derive(Debug, Clone, Default)]
+pub struct MlxSimulator {
+    state: Arc<Mutex<SimulatorState>>,
+}
+
+// SimulatorState is the state shared by all clones of a simulator.
+#[derive(Debug, Default)]
+struct SimulatorState {
+    devices: Vec<SimulatedDevice>,
+    // images maps firmware image file names to the
+    // firmware version they contain.
+    images: HashMap<String, String>,
+    failures: Vec<FailureRule>,
+    // history is every command run, in order.
+    history: Vec<String>,
+}
+
+impl MlxSimulator {
+    // new creates a simulator without any devices.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // with_device adds a device (builder pattern).
+    pub fn with_device(self, device: SimulatedDevice) -> Self {
+        self.add_device(device);
+        self
+    }
+
+    // with_firmware_image registers a firmware image (builder pattern).
+    pub fn with_firmware_image(self, image: impl AsRef<Path>, version: impl Into<String>) -> Self {
+        self.register_firmware_image(image, version);
+        self
+    }
+
+    // add_device adds a device, replacing any existing
+    // device with the same PCI name.
+    pub fn add_device(&self, device: SimulatedDevice) {
+        let mut state = self.state();
+        let id = normalize_device_id(device.pci_name());
+        state
+            .devices
+            .retain(|existing| normalize_device_id(existing.pci_name()) != id);
+        state.devices.push(device);
+    }
+
+    // register_firmware_image tells the simulator which firmware
+    // version an image contains, which is what a `flint burn` of
+    // that image will leave pending on the device. Images are
+    // matched by file name.
+    pub fn register_firmware_image(&self, image: impl AsRef<Path>, version: impl Into<String>) {
+        self.state()
+            .images
+            .insert(image_name(image.as_ref()), version.into());
+    }
+
+    // inject_failure adds a scripted failure.
+    pub fn inject_failure(&self, rule: FailureRule) {
+        self.state().failures.push(rule);
+    }
+
+    // clear_failures removes all scripted failures.
+    pub fn clear_failures(&self) {
+        self.state().failures.clear();
+    }
+
+    // device returns a snapshot of a device's current state.
+    pub fn device(&self, device_id: &str) -> Option<SimulatedDevice> {
+        self.state().find_device(device_id).cloned()
+    }
+
+    // devices returns a snapshot of all devices.
+    pub fn devices(&self) -> Vec<SimulatedDevice> {
+        self.state().devices.clone()
+    }
+
+    // update_device runs `f` against a device, for changing state
+    // directly (e.g. simulating an out-of-band firmware update).
+    pub fn update_device<R>(
+        &self,
+        device_id: &str,
+        f: impl FnOnce(&mut SimulatedDevice) -> R,
+    ) -> Option<R> {
+        self.state().find_device(device_id).map(f)
+    }
+
+    // power_cycle activates pending firmware and mlxconfig
+    // values on all devices, which is what a host reboot does.
+    pub fn power_cycle(&self) {
+        for device in self.state().devices.iter_mut() {
+            device.activate();
+        }
+    }
+
+    // commands returns every command run against the
+    // simulator so far, in order.
+    pub fn commands(&self) -> Vec<String> {
+        self.state().history.clone()
+    }
+
+    // backend returns the simulator as a shareable CommandBackend,
+    // for ExecOptions::with_backend and friends.
+    pub fn backend(&self) -> Arc<dyn CommandBackend> {
+        Arc::new(self.clone())
+    }
+
+    fn state(&self) -> MutexGuard<'_, SimulatorState> {
+        // A panic while holding the lock (e.g. a failed assertion
+        // in a test callback) shouldn't poison all other users.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CommandBackend for MlxSimulator {
+    fn run(
+        &self,
+        spec: &CommandSpec,
+        _timeout: Option<Duration>,
+    ) -> std::io::Result<Option<Output>> {
+        let mut state = self.state();
+        state.history.push(spec.to_string());
+
+        let program = Path::new(&spec.program)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let invocation = Invocation::parse(&spec.args);
+
+        let result = match program.as_str() {
+            "mlxconfig" => state.mlxconfig(&invocation)?,
+            "flint" => state.flint(&invocation),
+            "mlxfwreset" => state.mlxfwreset(&invocation),
+            "mlxfwmanager" => state.mlxfwmanager(&invocation),
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("{}: command not found", spec.program),
+                ));
+            }
+        };
+
+        Ok(result.into_output())
+    }
+}
+
+// Invocation is a parsed command line.
+#[derive(Debug, Default)]
+struct Invocation {
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+    positionals: Vec<String>,
+}
+
+impl Invocation {
+    fn parse(args: &[String]) -> Self {
+        let mut invocation = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if OPTIONS_WITH_VALUES.contains(&arg.as_str()) {
+                let value = args.next().cloned().unwrap_or_default();
+                invocation.options.insert(arg.clone(), value);
+            } else if arg.starts_with('-') {
+                invocation.flags.push(arg.clone());
+            } else {
+                invocation.positionals.push(arg.clone());
+            }
+        }
+        invocation
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn positional(&self, index: usize) -> Option<&str> {
+        self.positionals.get(index).map(String::as_str)
+    }
+}
+
+// SimResult is the outcome of an emulated command.
+#[derive(Debug)]
+enum SimResult {
+    Exit {
+        code: i32,
+        stdout: String,
+        stderr: String,
+    },
+    Timeout,
+}
+
+impl SimResult {
+    fn ok(stdout: impl Into<String>) -> Self {
+        SimResult::Exit {
+            code: 0,
+            stdout: stdout.into(),
+            stderr: String::new(),
+        }
+    }
+
+    fn error(stderr: impl Into<String>) -> Self {
+        SimResult::Exit {
+            code: 1,
+            stdout: String::new(),
+            stderr: stderr.into(),
+        }
+    }
+
+    fn device_not_found(device: &str) -> Self {
+        Self::error(format!("-E- Cannot open device {device}: No such device"))
+    }
+
+    fn hw_access_disabled(device: &str) -> Self {
+        Self::error(format!(
+            "-E- Failed to open device {device}: HW access is disabled on the device."
+        ))
+    }
+
+    fn from_failure(failure: SimFailure, device: &str) -> Self {
+        match failure {
+            SimFailure::CommandFailed(stderr) => Self::error(stderr),
+            SimFailure::Timeout => SimResult::Timeout,
+            SimFailure::PermissionDenied => Self::error("-E- Permission denied"),
+            SimFailure::DeviceNotFound => Self::device_not_found(device),
+        }
+    }
+
+    fn into_output(self) -> Option<Output> {
+        match self {
+            SimResult::Exit {
+                code,
+                stdout,
+                stderr,
+            } => Some(Output {
+                status: ExitStatus::from_raw(code << 8),
+                stdout: stdout.into_bytes(),
+                stderr: stderr.into_bytes(),
+            }),
+            SimResult::Timeout => None,
+        }
+    }
+}
+
+impl SimulatorState {
+    fn find_device(&mut self, device_id: &str) -> Option<&mut SimulatedDevice> {
+        let id = normalize_device_id(device_id);
+        self.devices
+            .iter_mut()
+            .find(|device| normalize_device_id(device.pci_name()) == id)
+    }
+
+    // take_failure returns the failure for the first rule matching
+    // the operation and device (if any), consuming one of its uses.
+    fn take_failure(
+        &mut self,
+        operation: SimOperation,
+        device: Option<&str>,
+    ) -> Option<SimFailure> {
+        let device = device.map(normalize_device_id);
+        let rule = self
+            .failures
+            .iter_mut()
+            .find(|rule| rule.matches(operation, device.as_deref()))?;
+        if let Some(remaining) = rule.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        Some(rule.failure.clone())
+    }
+
+    // mlxconfig emulates `mlxconfig -d <dev> [-e -j <file>] q [VARS..]`,
+    // `mlxconfig -d <dev> --yes set VAR=val..`, `.. apply <file>` and
+    // `.. reset`. Writing the query JSON file is the only real I/O,
+    // so it's the only thing that can return an io::Error.
+    fn mlxconfig(&mut self, invocation: &Invocation) -> std::io::Result<SimResult> {
+        let Some(device_id) = invocation.option("-d") else {
+            return Ok(SimResult::error("-E- missing device, use -d <device>"));
+        };
+        let operation = match invocation.positional(0) {
+            Some("q" | "query") => SimOperation::Query,
+            Some("s" | "set") => SimOperation::Set,
+            Some("a" | "apply") => SimOperation::Apply,
+            Some("r" | "reset") => SimOperation::ResetConfig,
+            other => {
+                return Ok(SimResult::error(format!(
+                    "-E- Unknown command: {}",
+                    other.unwrap_or_default()
+                )));
+            }
+        };
+        if let Some(failure) = self.take_failure(operation, Some(device_id)) {
+            return Ok(SimResult::from_failure(failure, device_id));
+        }
+        let Some(device) = self.find_device(device_id) else {
+            return Ok(SimResult::device_not_found(device_id));
+        };
+        if device.is_locked() {
+            return Ok(SimResult::hw_access_disabled(device_id));
+        }
+        let params = &invocation.positionals[1..];
+
+        Ok(match operation {
+            SimOperation::Query => {
+                let mut names = Vec::new();
+                if params.is_empty() {
+                    names = device.all_slot_names();
+                }
+                for param in params {
+                    match device.expand_query_names(param) {
+                        Some(expanded) => names.extend(expanded),
+                        None => {
+                            return Ok(SimResult::error(format!("-E- Unknown Parameter: {param}")));
+                        }
+                    }
+                }
+
+                let tlv_configuration: serde_json::Map<String, serde_json::Value> = names
+                    .into_iter()
+                    .filter_map(|name| device.slot_json(&name).map(|json| (name, json)))
+                    .collect();
+                let response = serde_json::json!({
+                    "Device #1": {
+                        "description": device.description(),
+                        "device": device_id,
+                        "device_type": device.device_type(),
+                        "name": device.part_number(),
+                        "tlv_configuration": tlv_configuration,
+                    }
+                });
+                if let Some(path) = invocation.option("-j") {
+                    std::fs::write(path, serde_json::to_string_pretty(&response)?)?;
+                }
+                SimResult::ok(format!(
+                    "Device #1:\n----------\n\nDevice type:    {}\nName:           {}\nDevice:         {device_id}\n",
+                    device.device_type(),
+                    device.part_number()
+                ))
+            }
+            SimOperation::Set => match device.stage_assignments(params) {
+                Ok(()) => SimResult::ok(
+                    "Apply new Configuration? (y/n) [n] : y\nApplying... Done!\n-I- Please reboot machine to load new configurations.\n",
+                ),
+                Err(e) => SimResult::error(format!("-E- {e}")),
+            },
+            SimOperation::Apply => match params.first() {
+                Some(path) => {
+                    device.record_applied_config(PathBuf::from(path));
+                    SimResult::ok(
+                        "Applying... Done!\n-I- Please reboot machine to load new configurations.\n",
+                    )
+                }
+                None => SimResult::error("-E- missing configuration file"),
+            },
+            _ => {
+                device.reset_to_defaults();
+                SimResult::ok(
+                    "Reset configuration for device? (y/n) [n] : y\nApplying... Done!\n-I- Please reboot machine to load new configurations.\n",
+                )
+            }
+        })
+    }
+
+    // flint emulates `flint -d <dev> q`, `.. hw_access enable|disable <key>`,
+    // `.. set_key <key>`, `.. -y -i <image> burn` and `.. -i <image> verify`.
+    fn flint(&mut self, invocation: &Invocation) -> SimResult {
+        let Some(device_id) = invocation.option("-d") else {
+            return SimResult::error("-E- missing device, use -d <device>");
+        };
+        let operation = match (invocation.positional(0), invocation.positional(1)) {
+            (Some("q" | "query"), _) => SimOperation::LockQuery,
+            (Some("hw_access"), Some("enable")) => SimOperation::Unlock,
+            (Some("hw_access"), Some("disable")) => SimOperation::Lock,
+            (Some("set_key"), _) => SimOperation::SetKey,
+            (Some("b" | "burn"), _) => SimOperation::Burn,
+            (Some("v" | "verify"), _) => SimOperation::VerifyImage,
+            (other, _) => {
+                return SimResult::error(format!(
+                    "-E- Unknown command: {}",
+                    other.unwrap_or_default()
+                ));
+            }
+        };
+        if let Some(failure) = self.take_failure(operation, Some(device_id)) {
+            return SimResult::from_failure(failure, device_id);
+        }
+        let image = invocation
+            .option("-i")
+            .map(|path| image_name(Path::new(path)));
+        let image_version = image
+            .as_ref()
+            .and_then(|name| self.images.get(name).cloned());
+        let Some(device) = self.find_device(device_id) else {
+            return SimResult::device_not_found(device_id);
+        };
+
+        match operation {
+            SimOperation::LockQuery => {
+                if device.is_locked() {
+                    return SimResult::hw_access_disabled(device_id);
+                }
+                SimResult::ok(format!(
+                    "Image type:            FS4\nFW Version:            {}\nPSID:                  {}\n",
+                    device.fw_version(),
+                    device.psid()
+                ))
+            }
+            SimOperation::Unlock => {
+                let key = invocation.positional(2).unwrap_or_default();
+                match device.lock_key() {
+                    None => SimResult::ok("-I- HW access already enabled\n"),
+                    Some(lock_key) if lock_key.eq_ignore_ascii_case(key) => {
+                        device.set_lock_key(None);
+                        SimResult::ok("-I- HW access enabled\n")
+                    }
+                    Some(_) => SimResult::error("-E- Failed to enable HW access: wrong key"),
+                }
+            }
+            SimOperation::Lock | SimOperation::SetKey => {
+                let key = invocation
+                    .positional(if operation == SimOperation::Lock {
+                        2
+                    } else {
+                        1
+                    })
+                    .unwrap_or_default();
+                if device.is_locked() {
+                    return if operation == SimOperation::Lock {
+                        SimResult::ok("-I- HW access already disabled\n")
+                    } else {
+                        SimResult::hw_access_disabled(device_id)
+                    };
+                }
+                device.set_lock_key(Some(key.to_string()));
+                SimResult::ok("-I- HW access disabled\n")
+            }
+            SimOperation::Burn => {
+                if device.is_locked() {
+                    return SimResult::hw_access_disabled(device_id);
+                }
+                let (Some(image), Some(version)) = (image, image_version) else {
+                    return SimResult::error(
+                        "-E- Unknown firmware image (not registered with the simulator)",
+                    );
+                };
+                device.record_burn(image, version);
+                SimResult::ok(
+                    "Burning FW image without signatures - OK\nRestoring signature - OK\n-I- To load new FW run mlxfwreset or reboot machine.\n",
+                )
+            }
+            _ => {
+                if device.is_locked() {
+                    return SimResult::hw_access_disabled(device_id);
+                }
+                let burned = image.is_some() && device.burned_image() == image.as_deref();
+                let running = image_version.as_deref() == Some(device.fw_version());
+                if burned || running {
+                    SimResult::ok("-I- FW image verification succeeded. Image is bootable.\n")
+                } else {
+                    SimResult::error(
+                        "-E- FW image verification failed: image does not match the device firmware",
+                    )
+                }
+            }
+        }
+    }
+
+    // mlxfwreset emulates `mlxfwreset --device <dev> --level <n> reset -y`,
+    // which activates pending firmware and pending mlxconfig values.
+    fn mlxfwreset(&mut self, invocation: &Invocation) -> SimResult {
+        let Some(device_id) = invocation.option("--device") else {
+            return SimResult::error("-E- missing device, use --device <device>");
+        };
+        if invocation.positional(0) != Some("reset") {
+            return SimResult::error("-E- Unknown command, expected reset");
+        }
+        if let Some(failure) = self.take_failure(SimOperation::FwReset, Some(device_id)) {
+            return SimResult::from_failure(failure, device_id);
+        }
+        let Some(device) = self.find_device(device_id) else {
+            return SimResult::device_not_found(device_id);
+        };
+        if device.is_locked() {
+            return SimResult::hw_access_disabled(device_id);
+        }
+        device.activate();
+        SimResult::ok(
+            "-I- Sending Reset Command To Fw             -Done\n-I- FW was loaded successfully.\n",
+        )
+    }
+
+    // mlxfwmanager emulates `mlxfwmanager [--dev <dev>] --query-format xml`.
+    // Locked devices are reported with empty fields, and make the
+    // command exit with 1, which is what the real mlxfwmanager does.
+    fn mlxfwmanager(&mut self, invocation: &Invocation) -> SimResult {
+        let device_id = invocation.option("--dev");
+        if let Some(failure) = self.take_failure(SimOperation::Discover, device_id) {
+            return SimResult::from_failure(failure, device_id.unwrap_or_default());
+        }
+        let devices: Vec<&SimulatedDevice> = match device_id {
+            Some(device_id) => match self.find_device(device_id) {
+                Some(device) => vec![&*device],
+                None => {
+                    return SimResult::Exit {
+                        code: 2,
+                        stdout: String::new(),
+                        stderr: format!("-E- Device {device_id} not found"),
+                    };
+                }
+            },
+            None => self.devices.iter().collect(),
+        };
+
+        let mut xml = String::from("<Devices>\n");
+        let mut any_locked = false;
+        for device in devices {
+            any_locked |= device.is_locked();
+            xml.push_str(&device_xml(device));
+        }
+        xml.push_str("</Devices>\n");
+
+        SimResult::Exit {
+            code: i32::from(any_locked),
+            stdout: xml,
+            stderr: String::new(),
+        }
+    }
+}
+
+// device_xml renders a single device the way mlxfwmanager does.
+fn device_xml(device: &SimulatedDevice) -> String {
+    if device.is_locked() {
+        return format!(
+            concat!(
+                "  <Device pciName=\"{}\" type=\"{}\" psid=\"\" partNumber=\"--\">\n",
+                "    <Versions>\n",
+                "      <FW current=\"--\" available=\"\"/>\n",
+                "    </Versions>\n",
+                "    <MACs Base_Mac=\"N/A\" />\n",
+                "    <Status>Failed to open device</Status>\n",
+                "    <Description></Description>\n",
+                "  </Device>\n",
+            ),
+            xml_escape(device.pci_name()),
+            xml_escape(device.device_type()),
+        );
+    }
+    format!(
+        concat!(
+            "  <Device pciName=\"{}\" type=\"{}\" psid=\"{}\" partNumber=\"{}\">\n",
+            "    <Versions>\n",
+            "      <FW current=\"{}\" available=\"N/A\"/>\n",
+            "    </Versions>\n",
+            "    <MACs Base_Mac=\"{}\" />\n",
+            "    <Status>No matching image found</Status>\n",
+            "    <Description>{}</Description>\n",
+            "  </Device>\n",
+        ),
+        xml_escape(device.pci_name()),
+        xml_escape(device.device_type()),
+        xml_escape(device.psid()),
+        xml_escape(device.part_number()),
+        xml_escape(device.fw_version()),
+        xml_escape(device.base_mac()),
+        xml_escape(device.description()),
+    )
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// image_name is the key firmware images are registered under.
+fn image_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
@@ -0,0 +1,8 @@
+mod simulator {
+    mod common;
+    mod test_device;
+    mod test_failures;
+    mod test_firmware;
+    mod test_lockdown;
+    mod test_runner;
+}
@@ -0,0 +1,88 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/simulator/common/mod.rs
+// Shared helpers for the simulator tests.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use libmlx::firmware::config::FirmwareSpec;
+use libmlx::registry::registries;
+use libmlx::runner::backend::CommandBackend;
+use libmlx::runner::exec_options::ExecOptions;
+use libmlx::runner::runner::MlxConfigRunner;
+use libmlx::simulator::device::SimulatedDevice;
+use libmlx::simulator::simulator::MlxSimulator;
+use libmlx::variables::registry::MlxVariableRegistry;
+
+pub const DEVICE: &str = "b4:00.0";
+pub const OTHER_DEVICE: &str = "dc:00.0";
+pub const LOCK_KEY: &str = "deadbeef";
+pub const PART_NUMBER: &str = "900-9D3B4-00CV-TA0";
+pub const PSID: &str = "MT_0000000884";
+
+// generic_registry returns the embedded mlx_generic registry,
+// which is limited to BlueField2/BlueField3 devices.
+pub fn generic_registry() -> MlxVariableRegistry {
+    registries::get("mlx_generic")
+        .expect("mlx_generic registry should be embedded")
+        .clone()
+}
+
+// bluefield3 creates a simulated BlueField-3 SuperNIC with
+// all mlx_generic variables at their defaults.
+pub fn bluefield3(pci_name: &str) -> SimulatedDevice {
+    SimulatedDevice::from_registry(pci_name, &generic_registry())
+        .with_device_type("BlueField3")
+        .with_part_number(PART_NUMBER)
+        .with_psid(PSID)
+        .with_fw_version("32.42.1000")
+}
+
+// simulator creates a simulator with a single BlueField-3.
+pub fn simulator() -> MlxSimulator {
+    MlxSimulator::new().with_device(bluefield3(DEVICE))
+}
+
+// exec_options returns options which execute against the
+// simulator, with a single fast retry.
+pub fn exec_options(backend: Arc<dyn CommandBackend>) -> ExecOptions {
+    ExecOptions::new()
+        .with_retries(1)
+        .with_retry_delay(Duration::from_millis(1))
+        .with_max_retry_delay(Duration::from_millis(1))
+        .with_backend(backend)
+}
+
+// runner creates an mlx_generic runner for DEVICE.
+pub fn runner(sim: &MlxSimulator) -> MlxConfigRunner {
+    MlxConfigRunner::with_options(
+        DEVICE.to_string(),
+        generic_registry(),
+        exec_options(sim.backend()),
+    )
+}
+
+// firmware_spec returns the spec matching the bluefield3 device.
+pub fn firmware_spec(version: &str) -> FirmwareSpec {
+    FirmwareSpec {
+        part_number: PART_NUMBER.to_string(),
+        psid: PSID.to_string(),
+        version: version.to_string(),
+    }
+}
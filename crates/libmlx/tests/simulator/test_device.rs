@@ -0,0 +1,95 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/simulator/test_device.rs
+// Tests for simulated devices and mlxfwmanager discovery.
+
+use libmlx::device::discovery::{discover_device_with, discover_devices_with};
+use libmlx::simulator::device::SimulatedDevice;
+use libmlx::simulator::simulator::MlxSimulator;
+
+use super::common;
+
+#[test]
+fn test_from_registry_satisfies_registry_filters() {
+    let registry = common::generic_registry();
+    let device = SimulatedDevice::from_registry(common::DEVICE, &registry);
+
+    assert_eq!(device.device_type(), "BlueField2");
+    assert_eq!(device.pci_name(), "0000:b4:00.0");
+    assert_eq!(device.current_value("SRIOV_EN").as_deref(), Some("false"));
+    assert_eq!(device.next_value("NUM_OF_VFS").as_deref(), Some("0"));
+    assert!(!device.is_locked());
+    assert!(!device.has_pending_changes());
+}
+
+#[test]
+fn test_set_value_validates_against_spec() {
+    let mut device = common::bluefield3(common::DEVICE);
+
+    assert!(device.set_value("NUM_OF_VFS", "16").is_ok());
+    assert_eq!(device.current_value("NUM_OF_VFS").as_deref(), Some("16"));
+    assert!(device.set_value("NUM_OF_VFS", "sixteen").is_err());
+    assert!(device.set_value("NOT_A_VARIABLE", "1").is_err());
+}
+
+#[test]
+fn test_discover_devices() {
+    let sim = common::simulator().with_device(
+        SimulatedDevice::new(common::OTHER_DEVICE)
+            .with_base_mac("c470bd31eb46")
+            .with_description("BlueField-3 SuperNIC; 400GbE & NDR"),
+    );
+
+    let devices = discover_devices_with(&sim).unwrap();
+    assert_eq!(devices.len(), 2);
+
+    let bf3 = devices
+        .iter()
+        .find(|d| d.pci_name == common::DEVICE)
+        .unwrap();
+    assert_eq!(bf3.device_type, "BlueField3");
+    assert_eq!(bf3.part_number.as_deref(), Some(common::PART_NUMBER));
+    assert_eq!(bf3.psid.as_deref(), Some(common::PSID));
+    assert_eq!(bf3.fw_version_current.as_deref(), Some("32.42.1000"));
+
+    let other = discover_device_with(&sim, common::OTHER_DEVICE).unwrap();
+    assert_eq!(other.device_type, "ConnectX7");
+    assert!(other.base_mac.is_some());
+    assert_eq!(
+        other.device_description.as_deref(),
+        Some("BlueField-3 SuperNIC; 400GbE & NDR")
+    );
+}
+
+#[test]
+fn test_discover_locked_device_has_no_identity() {
+    let sim = MlxSimulator::new()
+        .with_device(common::bluefield3(common::DEVICE).with_lock_key(common::LOCK_KEY));
+
+    let device = discover_device_with(&sim, common::DEVICE).unwrap();
+    assert_eq!(device.device_type, "BlueField3");
+    assert_eq!(device.psid, None);
+    assert_eq!(device.part_number, None);
+    assert_eq!(device.fw_version_current, None);
+}
+
+#[test]
+fn test_discover_unknown_device() {
+    let sim = common::simulator();
+    assert!(discover_device_with(&sim, common::OTHER_DEVICE).is_err());
+}
@@ -0,0 +1,153 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/simulator/test_failures.rs
+// Tests for scripted failure modes, and how the runner,
+// lockdown and firmware flows handle them.
+
+use libmlx::device::discovery::discover_device_with;
+use libmlx::firmware::config::{FirmwareFlasherProfile, FlashOptions, FlashSpec};
+use libmlx::firmware::flasher::FirmwareFlasher;
+use libmlx::lockdown::error::MlxError;
+use libmlx::lockdown::runner::FlintRunner;
+use libmlx::runner::error::MlxRunnerError;
+use libmlx::simulator::failure::{FailureRule, SimFailure, SimOperation};
+
+use super::common;
+
+#[test]
+fn test_transient_timeout_is_retried() {
+    let sim = common::simulator();
+    sim.inject_failure(FailureRule::new(SimOperation::Query, SimFailure::Timeout).times(1));
+
+    let result = common::runner(&sim).query(&["SRIOV_EN"]).unwrap();
+    assert_eq!(result.variable_count(), 1);
+
+    let queries = sim
+        .commands()
+        .iter()
+        .filter(|command| command.starts_with("mlxconfig") && command.contains(" q "))
+        .count();
+    assert_eq!(queries, 2);
+}
+
+#[test]
+fn test_persistent_timeout_fails() {
+    let sim = common::simulator();
+    sim.inject_failure(FailureRule::new(SimOperation::Query, SimFailure::Timeout));
+
+    assert!(matches!(
+        common::runner(&sim).query(&["SRIOV_EN"]),
+        Err(MlxRunnerError::Timeout { .. })
+    ));
+}
+
+#[test]
+fn test_failed_set_leaves_device_unchanged() {
+    let sim = common::simulator();
+    sim.inject_failure(FailureRule::new(
+        SimOperation::Set,
+        SimFailure::CommandFailed("-E- Failed to set configuration".to_string()),
+    ));
+
+    assert!(matches!(
+        common::runner(&sim).sync(&[("SRIOV_EN", "true")]),
+        Err(MlxRunnerError::CommandExecution { .. })
+    ));
+    let device = sim.device(common::DEVICE).unwrap();
+    assert_eq!(device.next_value("SRIOV_EN").as_deref(), Some("false"));
+
+    // Once the failure is cleared, the same sync goes through.
+    sim.clear_failures();
+    let result = common::runner(&sim).sync(&[("SRIOV_EN", "true")]).unwrap();
+    assert_eq!(result.variables_changed, 1);
+}
+
+#[test]
+fn test_failure_scoped_to_device() {
+    let sim = common::simulator().with_device(common::bluefield3(common::OTHER_DEVICE));
+    sim.inject_failure(
+        FailureRule::new(SimOperation::Discover, SimFailure::DeviceNotFound)
+            .on_device(format!("0000:{}", common::DEVICE)),
+    );
+
+    assert!(discover_device_with(&sim, common::DEVICE).is_err());
+    assert!(discover_device_with(&sim, common::OTHER_DEVICE).is_ok());
+}
+
+#[test]
+fn test_flint_error_mapping() {
+    let sim = common::simulator();
+    let flint = FlintRunner::with_path("flint").with_backend(sim.backend());
+
+    sim.inject_failure(
+        FailureRule::new(SimOperation::LockQuery, SimFailure::PermissionDenied).times(1),
+    );
+    assert!(matches!(
+        flint.query_device(common::DEVICE),
+        Err(MlxError::PermissionDenied)
+    ));
+
+    sim.inject_failure(
+        FailureRule::new(SimOperation::LockQuery, SimFailure::DeviceNotFound).times(1),
+    );
+    assert!(matches!(
+        flint.query_device(common::DEVICE),
+        Err(MlxError::DeviceNotFound(_))
+    ));
+
+    assert_eq!(flint.query_device(common::DEVICE).unwrap(), "unlocked");
+}
+
+#[tokio::test]
+async fn test_failed_reset_is_captured_in_report() {
+    let sim = common::simulator();
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("fw.bin");
+    std::fs::write(&image, b"fw").unwrap();
+    sim.register_firmware_image(&image, "32.43.1014");
+    sim.inject_failure(FailureRule::new(
+        SimOperation::FwReset,
+        SimFailure::CommandFailed("-E- Reset failed".to_string()),
+    ));
+
+    let profile = FirmwareFlasherProfile {
+        firmware_spec: common::firmware_spec("32.43.1014"),
+        flash_spec: FlashSpec {
+            firmware_url: image.to_string_lossy().to_string(),
+            firmware_credentials: None,
+            device_conf_url: None,
+            device_conf_credentials: None,
+            verify_from_cache: false,
+            cache_dir: Some(dir.path().join("cache")),
+        },
+        flash_options: FlashOptions {
+            verify_version: true,
+            reset: true,
+            ..Default::default()
+        },
+    };
+    let flasher =
+        FirmwareFlasher::with_backend(common::DEVICE, &profile.firmware_spec, sim.backend())
+            .unwrap();
+    let report = flasher.apply(&profile).await.unwrap();
+
+    assert!(report.flashed);
+    assert_eq!(report.reset, Some(false));
+    assert_eq!(report.verified_version, Some(false));
+    assert_eq!(report.observed_version.as_deref(), Some("32.42.1000"));
+}
@@ -0,0 +1,148 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/simulator/test_firmware.rs
+// Tests for the FirmwareFlasher flash/reset/verify lifecycle
+// against the simulator.
+
+use std::path::PathBuf;
+
+use libmlx::firmware::config::{FirmwareFlasherProfile, FlashOptions, FlashSpec};
+use libmlx::firmware::flasher::FirmwareFlasher;
+use libmlx::simulator::simulator::MlxSimulator;
+
+use super::common;
+
+const NEW_VERSION: &str = "32.43.1014";
+
+// write_image writes a fake firmware image to a temp directory,
+// and registers it with the simulator as NEW_VERSION.
+fn write_image(sim: &MlxSimulator, dir: &tempfile::TempDir) -> PathBuf {
+    let path = dir.path().join("fw-bf3-32.43.1014.bin");
+    std::fs::write(&path, b"not really firmware").unwrap();
+    sim.register_firmware_image(&path, NEW_VERSION);
+    path
+}
+
+fn flash_spec(image: &std::path::Path, dir: &tempfile::TempDir) -> FlashSpec {
+    FlashSpec {
+        firmware_url: image.to_string_lossy().to_string(),
+        firmware_credentials: None,
+        device_conf_url: None,
+        device_conf_credentials: None,
+        verify_from_cache: false,
+        cache_dir: Some(dir.path().join("cache")),
+    }
+}
+
+#[test]
+fn test_flasher_validates_device_identity() {
+    let sim = common::simulator();
+
+    assert!(
+        FirmwareFlasher::with_backend(
+            common::DEVICE,
+            &common::firmware_spec(NEW_VERSION),
+            sim.backend()
+        )
+        .is_ok()
+    );
+
+    let mut wrong_psid = common::firmware_spec(NEW_VERSION);
+    wrong_psid.psid = "MT_0000000000".to_string();
+    assert!(FirmwareFlasher::with_backend(common::DEVICE, &wrong_psid, sim.backend()).is_err());
+}
+
+#[tokio::test]
+async fn test_flash_is_pending_until_reset() {
+    let sim = common::simulator();
+    let dir = tempfile::tempdir().unwrap();
+    let image = write_image(&sim, &dir);
+    let flasher = FirmwareFlasher::with_backend(
+        common::DEVICE,
+        &common::firmware_spec(NEW_VERSION),
+        sim.backend(),
+    )
+    .unwrap();
+
+    flasher.flash(&flash_spec(&image, &dir)).await.unwrap();
+    let device = sim.device(common::DEVICE).unwrap();
+    assert_eq!(device.pending_fw_version(), Some(NEW_VERSION));
+    assert_eq!(device.fw_version(), "32.42.1000");
+    assert!(flasher.verify_version().is_err());
+
+    flasher.reset().unwrap();
+    assert_eq!(
+        flasher.verify_version().unwrap().as_deref(),
+        Some(NEW_VERSION)
+    );
+    assert!(
+        flasher
+            .verify_image(&flash_spec(&image, &dir))
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn test_apply_full_lifecycle() {
+    let sim = common::simulator();
+    let dir = tempfile::tempdir().unwrap();
+    let image = write_image(&sim, &dir);
+    let profile = FirmwareFlasherProfile {
+        firmware_spec: common::firmware_spec(NEW_VERSION),
+        flash_spec: flash_spec(&image, &dir),
+        flash_options: FlashOptions {
+            verify_image: true,
+            verify_version: true,
+            reset: true,
+            ..Default::default()
+        },
+    };
+
+    let flasher =
+        FirmwareFlasher::with_backend(common::DEVICE, &profile.firmware_spec, sim.backend())
+            .unwrap();
+    let report = flasher.apply(&profile).await.unwrap();
+
+    assert!(report.flashed);
+    assert_eq!(report.reset, Some(true));
+    assert_eq!(report.verified_image, Some(true));
+    assert_eq!(report.verified_version, Some(true));
+    assert_eq!(report.observed_version.as_deref(), Some(NEW_VERSION));
+    assert_eq!(sim.device(common::DEVICE).unwrap().reset_count(), 1);
+}
+
+#[tokio::test]
+async fn test_flash_unregistered_image_fails() {
+    let sim = common::simulator();
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join("unknown.bin");
+    std::fs::write(&image, b"???").unwrap();
+
+    let flasher = FirmwareFlasher::with_backend(
+        common::DEVICE,
+        &common::firmware_spec(NEW_VERSION),
+        sim.backend(),
+    )
+    .unwrap();
+    assert!(flasher.flash(&flash_spec(&image, &dir)).await.is_err());
+    assert_eq!(
+        sim.device(common::DEVICE).unwrap().pending_fw_version(),
+        None
+    );
+}
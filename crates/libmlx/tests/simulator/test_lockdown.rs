@@ -0,0 +1,110 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/simulator/test_lockdown.rs
+// Tests for flint lockdown flows against the simulator.
+
+use libmlx::lockdown::error::MlxError;
+use libmlx::lockdown::lockdown::{LockStatus, LockdownManager};
+use libmlx::lockdown::runner::FlintRunner;
+use libmlx::simulator::simulator::MlxSimulator;
+
+use super::common;
+
+fn manager(sim: &MlxSimulator) -> LockdownManager {
+    LockdownManager::with_runner(FlintRunner::with_path("flint").with_backend(sim.backend()))
+}
+
+#[test]
+fn test_lock_and_unlock() {
+    let sim = common::simulator();
+    let manager = manager(&sim);
+
+    assert_eq!(
+        manager.get_status(common::DEVICE).unwrap(),
+        LockStatus::Unlocked
+    );
+
+    manager
+        .lock_device(common::DEVICE, common::LOCK_KEY)
+        .unwrap();
+    assert_eq!(
+        manager.get_status(common::DEVICE).unwrap(),
+        LockStatus::Locked
+    );
+    assert!(sim.device(common::DEVICE).unwrap().is_locked());
+
+    manager
+        .unlock_device(common::DEVICE, common::LOCK_KEY)
+        .unwrap();
+    assert_eq!(
+        manager.get_status(common::DEVICE).unwrap(),
+        LockStatus::Unlocked
+    );
+}
+
+#[test]
+fn test_already_locked_and_unlocked() {
+    let sim = common::simulator();
+    let manager = manager(&sim);
+
+    assert!(matches!(
+        manager.unlock_device(common::DEVICE, common::LOCK_KEY),
+        Err(MlxError::AlreadyUnlocked)
+    ));
+
+    manager
+        .set_device_key(common::DEVICE, common::LOCK_KEY)
+        .unwrap();
+    assert!(matches!(
+        manager.lock_device(common::DEVICE, common::LOCK_KEY),
+        Err(MlxError::AlreadyLocked)
+    ));
+}
+
+#[test]
+fn test_unlock_with_wrong_key() {
+    let sim = common::simulator();
+    let manager = manager(&sim);
+
+    manager
+        .lock_device(common::DEVICE, common::LOCK_KEY)
+        .unwrap();
+    assert!(matches!(
+        manager.unlock_device(common::DEVICE, "0badc0de"),
+        Err(MlxError::CommandFailed(_))
+    ));
+    assert!(sim.device(common::DEVICE).unwrap().is_locked());
+}
+
+#[test]
+fn test_locked_device_rejects_mlxconfig() {
+    let sim = common::simulator();
+    let manager = manager(&sim);
+    let runner = common::runner(&sim);
+
+    manager
+        .lock_device(common::DEVICE, common::LOCK_KEY)
+        .unwrap();
+    assert!(runner.sync(&[("SRIOV_EN", "true")]).is_err());
+
+    manager
+        .unlock_device(common::DEVICE, common::LOCK_KEY)
+        .unwrap();
+    let result = runner.sync(&[("SRIOV_EN", "true")]).unwrap();
+    assert_eq!(result.variables_changed, 1);
+}
@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// tests/simulator/test_runner.rs
+// Tests for MlxConfigRunner query, sync and compare flows
+// against the simulator.
+
+use libmlx::firmware::reset::MlxFwResetRunner;
+use libmlx::runner::runner::MlxConfigRunner;
+use libmlx::simulator::device::SimulatedDevice;
+use libmlx::simulator::simulator::MlxSimulator;
+
+use super::common;
+
+#[test]
+fn test_query_returns_device_values() {
+    let sim = common::simulator();
+    sim.update_device(common::DEVICE, |device| {
+        device.set_value("NUM_OF_VFS", "8").unwrap();
+    });
+
+    let result = common::runner(&sim)
+        .query(&["SRIOV_EN", "NUM_OF_VFS"])
+        .unwrap();
+    assert_eq!(result.variable_count(), 2);
+    assert_eq!(
+        result.device_info.device_type.as_deref(),
+        Some("BlueField3")
+    );
+
+    let sriov_en = result.get_variable("SRIOV_EN").unwrap();
+    assert_eq!(sriov_en.current_value.to_display_string(), "false");
+    assert!(!sriov_en.modified);
+
+    let num_of_vfs = result.get_variable("NUM_OF_VFS").unwrap();
+    assert_eq!(num_of_vfs.current_value.to_display_string(), "8");
+    assert_eq!(num_of_vfs.default_value.to_display_string(), "0");
+    assert!(num_of_vfs.modified);
+}
+
+#[test]
+fn test_query_all() {
+    let sim = common::simulator();
+    let result = common::runner(&sim).query_all().unwrap();
+    assert_eq!(
+        result.variable_count(),
+        common::generic_registry().variables.len()
+    );
+}
+
+#[test]
+fn test_sync_stages_pending_values_until_reset() {
+    let sim = common::simulator();
+    let runner = common::runner(&sim);
+    let desired = [("SRIOV_EN", "true"), ("NUM_OF_VFS", "16")];
+
+    let result = runner.sync(&desired).unwrap();
+    assert_eq!(result.variables_checked, 2);
+    assert_eq!(result.variables_changed, 2);
+
+    // The new values are pending until the device is reset.
+    let device = sim.device(common::DEVICE).unwrap();
+    assert_eq!(device.next_value("SRIOV_EN").as_deref(), Some("true"));
+    assert_eq!(device.current_value("SRIOV_EN").as_deref(), Some("false"));
+    assert!(device.has_pending_changes());
+
+    // Syncing again compares against next values, so there's nothing to do.
+    let result = runner.sync(&desired).unwrap();
+    assert_eq!(result.variables_changed, 0);
+
+    MlxFwResetRunner::with_path("mlxfwreset")
+        .with_backend(sim.backend())
+        .reset(common::DEVICE, 3)
+        .unwrap();
+
+    let device = sim.device(common::DEVICE).unwrap();
+    assert_eq!(device.current_value("SRIOV_EN").as_deref(), Some("true"));
+    assert_eq!(device.current_value("NUM_OF_VFS").as_deref(), Some("16"));
+    assert!(!device.has_pending_changes());
+    assert_eq!(device.reset_count(), 1);
+}
+
+#[test]
+fn test_compare_reports_planned_changes() {
+    let sim = common::simulator();
+    sim.update_device(common::DEVICE, |device| {
+        device.set_value("NUM_OF_VFS", "8").unwrap();
+    });
+
+    let result = common::runner(&sim)
+        .compare(&[("SRIOV_EN", "false"), ("NUM_OF_VFS", "16")])
+        .unwrap();
+    assert_eq!(result.variables_checked, 2);
+    assert_eq!(result.variables_needing_change, 1);
+    assert_eq!(result.planned_changes[0].variable_name, "NUM_OF_VFS");
+    assert_eq!(
+        result.planned_changes[0].current_value.to_display_string(),
+        "8"
+    );
+
+    // compare never changes anything.
+    assert!(
+        !sim.commands()
+            .iter()
+            .any(|command| command.contains(" set "))
+    );
+}
+
+#[test]
+fn test_registry_filter_mismatch() {
+    let sim = MlxSimulator::new().with_device(
+        SimulatedDevice::new(common::DEVICE).with_registry(&common::generic_registry()),
+    );
+
+    let runner = MlxConfigRunner::with_options(
+        common::DEVICE.to_string(),
+        common::generic_registry(),
+        common::exec_options(sim.backend()),
+    );
+    assert!(runner.query(&["SRIOV_EN"]).is_err());
+}
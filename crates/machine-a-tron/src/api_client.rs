@@ -27,6 +27,9 @@ use rpc::forge::{
     PxeInstructions, SetDynamicConfigRequest,
 };
 use rpc::protos::forge_api_client::ForgeApiClient;
+use rpc::protos::mlx_device::{
+    MlxObservation, MlxObservationReport, PublishMlxObservationReportRequest,
+};
 
 use crate::MachineConfig;
 
@@ -512,6 +515,24 @@ impl ApiClient {
             .map(|_| ())
     }
 
+    pub async fn publish_mlx_observation_report(
+        &self,
+        machine_id: &MachineId,
+        observations: Vec<MlxObservation>,
+    ) -> ClientApiResult<()> {
+        self.0
+            .publish_mlx_observation_report(PublishMlxObservationReportRequest {
+                report: Some(MlxObservationReport {
+                    machine_id: Some(*machine_id),
+                    timestamp: Some(chrono::Utc::now().into()),
+                    observations,
+                }),
+            })
+            .await
+            .map_err(ClientApiError::InvocationError)
+            .map(|_| ())
+    }
+
     pub async fn get_pxe_instructions(
         &self,
         arch: rpc::forge::MachineArchitecture,
@@ -45,6 +45,7 @@ use crate::machine_utils::{
     PxeError, PxeResponse, forge_agent_control, get_fac_action, get_validation_id,
     send_pxe_boot_request,
 };
+use crate::mlx_devices::MlxDevices;
 use crate::{PersistedDpuMachine, PersistedHostMachine, dhcp_wrapper};
 
 /// MachineStateMachine (yo dawg) models the state machine of a machine endpoint
@@ -65,6 +66,7 @@ pub struct MachineStateMachine {
     config: Arc<MachineConfig>,
     app_context: Arc<MachineATronContext>,
     dpu_dhcp_relay: Option<DpuDhcpRelay>,
+    mlx_devices: MlxDevices,
 }
 
 #[derive(Debug, Clone)]
@@ -241,6 +243,7 @@ impl MachineStateMachine {
             app_context,
             dpu_dhcp_relay,
             mat_host_id,
+            mlx_devices: MlxDevices::default(),
         }
     }
 
@@ -269,6 +272,7 @@ impl MachineStateMachine {
             app_context,
             dpu_dhcp_relay,
             mat_host_id,
+            mlx_devices: MlxDevices::default(),
         }
     }
 
@@ -626,6 +630,8 @@ impl MachineStateMachine {
         else {
             // No machine_discovery_result means scout has not yet run this boot. Run discovery now.
             tracing::trace!("Running initial discovery after boot");
+            // Pending mlxconfig values and firmware take effect on reboot
+            self.mlx_devices.power_cycle();
             let machine_discovery_result = match self
                 .run_machine_discovery(&machine_up_state.machine_dhcp_info)
                 .await
@@ -688,6 +694,17 @@ impl MachineStateMachine {
                     .cleanup_complete(machine_id)
                     .await?;
             }
+            Action::MlxAction => {
+                let Some(data) = control_response.data.as_ref() else {
+                    tracing::error!("Got MlxAction without any DpaCommands");
+                    return Ok(NextState::SleepFor(self.config.scout_run_interval));
+                };
+                let observations = self.mlx_devices.handle_mlx_action(data);
+                self.app_context
+                    .api_client()
+                    .publish_mlx_observation_report(machine_id, observations)
+                    .await?;
+            }
             Action::Noop => {}
             _ => {
                 tracing::warn!(
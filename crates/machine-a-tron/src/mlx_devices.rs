@@ -0,0 +1,231 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::borrow::Cow;
+
+use carbide_host_support::dpa_cmds::{DpaCommand, OpCode};
+use libmlx::device::discovery::discover_device_with;
+use libmlx::device::info::MlxDeviceInfo;
+use libmlx::firmware::config::FirmwareFlasherProfile;
+use libmlx::lockdown::lockdown::LockdownManager;
+use libmlx::lockdown::runner::FlintRunner;
+use libmlx::profile::serialization::SerializableProfile;
+use libmlx::registry::registries;
+use libmlx::runner::exec_options::ExecOptions;
+use libmlx::simulator::device::SimulatedDevice;
+use libmlx::simulator::simulator::MlxSimulator;
+use libmlx::variables::registry::MlxVariableRegistry;
+use rpc::forge::forge_agent_control_response::ForgeAgentControlExtraInfo;
+use rpc::protos::mlx_device::{FirmwareFlashReport, LockStatus, MlxObservation};
+
+/// The registry used for devices which are first seen through a command that doesn't carry one
+const DEFAULT_REGISTRY: &str = "mlx_generic";
+
+/// The simulated Mellanox NICs of a mock host, which play the part of the devices scout
+/// configures when carbide-api sends it an `MlxAction`.
+///
+/// Devices are created the first time carbide-api sends a command for them, and pending
+/// mlxconfig values and firmware are activated whenever the host reboots.
+#[derive(Debug, Clone, Default)]
+pub struct MlxDevices {
+    simulator: MlxSimulator,
+}
+
+impl MlxDevices {
+    /// Activates pending mlxconfig values and firmware, like a reboot of the host does
+    pub fn power_cycle(&self) {
+        self.simulator.power_cycle();
+    }
+
+    /// Runs the DpaCommands of an `MlxAction` against the simulated devices, the same way scout
+    /// does against real ones, and returns the observations to report back to carbide-api
+    pub fn handle_mlx_action(&self, data: &ForgeAgentControlExtraInfo) -> Vec<MlxObservation> {
+        let mut observations = Vec::new();
+        for kv in &data.pair {
+            let dpa_cmd: DpaCommand<'_> = match serde_json::from_str(&kv.value) {
+                Ok(cmd) => cmd,
+                Err(e) => {
+                    tracing::error!(device = %kv.key, "Failed to decode DpaCommand: {e}");
+                    continue;
+                }
+            };
+            if let Some(observation) = self.run_command(&kv.key, dpa_cmd.op) {
+                observations.push(observation);
+            }
+        }
+        observations
+    }
+
+    fn run_command(&self, pci_name: &str, op: OpCode<'_>) -> Option<MlxObservation> {
+        let observation = match op {
+            OpCode::Noop => return None,
+            OpCode::Lock { key } => {
+                let device_info = self.device_info(pci_name, None)?;
+                if let Err(e) = self.lockdown_manager().lock_device(pci_name, &key) {
+                    tracing::warn!(device = %pci_name, "Failed to lock device: {e}");
+                    return None;
+                }
+                MlxObservation {
+                    device_info: Some(device_info.into()),
+                    lock_status: Some(LockStatus::Locked.into()),
+                    ..Default::default()
+                }
+            }
+            OpCode::Unlock { key } => {
+                let device_info = self.device_info(pci_name, None)?;
+                if let Err(e) = self.lockdown_manager().unlock_device(pci_name, &key) {
+                    tracing::warn!(device = %pci_name, "Failed to unlock device: {e}");
+                    return None;
+                }
+                MlxObservation {
+                    device_info: Some(device_info.into()),
+                    lock_status: Some(LockStatus::Unlocked.into()),
+                    ..Default::default()
+                }
+            }
+            OpCode::ApplyProfile { profile_str } => MlxObservation {
+                device_info: Some(self.device_info(pci_name, None)?.into()),
+                profile_name: Some(profile_str),
+                profile_synced: Some(true),
+                ..Default::default()
+            },
+            OpCode::ApplyFirmware { profile } => {
+                let device_info = self.device_info(pci_name, None)?;
+                let firmware_report = match profile {
+                    Some(profile) => self.apply_firmware(pci_name, &profile),
+                    None => FirmwareFlashReport {
+                        flashed: true,
+                        ..Default::default()
+                    },
+                };
+                MlxObservation {
+                    device_info: Some(device_info.into()),
+                    firmware_report: Some(firmware_report),
+                    ..Default::default()
+                }
+            }
+            OpCode::SyncProfile { profile, version } => {
+                let profile_name = profile.name.clone();
+                let (device_info, synced, variables_changed) =
+                    self.sync_profile(pci_name, *profile)?;
+                MlxObservation {
+                    device_info: Some(device_info.into()),
+                    profile_name: Some(profile_name),
+                    profile_synced: Some(synced),
+                    profile_version: Some(version),
+                    profile_variables_changed: variables_changed,
+                    ..Default::default()
+                }
+            }
+        };
+        Some(observation)
+    }
+
+    /// Syncs a profile to the device through libmlx, returning the device and whether it's now
+    /// synced, along with the number of variables changed
+    fn sync_profile(
+        &self,
+        pci_name: &str,
+        profile: SerializableProfile,
+    ) -> Option<(MlxDeviceInfo, bool, Option<u32>)> {
+        let profile_name = profile.name.clone();
+        let profile = match profile.into_profile() {
+            Ok(profile) => profile,
+            Err(e) => {
+                tracing::error!(device = %pci_name, %profile_name, "Invalid mlxconfig profile: {e}");
+                let device_info = self.device_info(pci_name, None)?;
+                return Some((device_info, false, None));
+            }
+        };
+
+        let device_info = self.device_info(pci_name, Some(&profile.registry))?;
+        if !profile.registry.matches_device(&device_info) {
+            // Nothing in the profile applies to this device
+            return Some((device_info, true, Some(0)));
+        }
+
+        let options = ExecOptions::new().with_backend(self.simulator.backend());
+        match profile.sync(pci_name, Some(options)) {
+            Ok(result) => Some((device_info, true, Some(result.variables_changed as u32))),
+            Err(e) => {
+                tracing::error!(device = %pci_name, %profile_name, "Failed to sync mlxconfig profile: {e}");
+                Some((device_info, false, None))
+            }
+        }
+    }
+
+    /// Installs the firmware of the profile on the device. There's no image to burn in
+    /// machine-a-tron, so the version is set directly.
+    fn apply_firmware(
+        &self,
+        pci_name: &str,
+        profile: &Cow<'_, FirmwareFlasherProfile>,
+    ) -> FirmwareFlashReport {
+        let expected = profile.firmware_spec.version.clone();
+        let options = &profile.flash_options;
+        self.simulator.update_device(pci_name, |device| {
+            if options.reset {
+                device.set_fw_version(expected.clone());
+            } else {
+                device.set_pending_fw_version(expected.clone());
+            }
+        });
+
+        let observed = self
+            .simulator
+            .device(pci_name)
+            .map(|device| device.fw_version().to_string());
+        FirmwareFlashReport {
+            flashed: true,
+            reset: options.reset.then_some(true),
+            verified_image: options.verify_image.then_some(true),
+            verified_version: options
+                .verify_version
+                .then(|| observed.as_deref() == Some(expected.as_str())),
+            observed_version: observed,
+            expected_version: Some(expected),
+        }
+    }
+
+    /// Discovers the device, creating it first if it doesn't exist yet
+    fn device_info(
+        &self,
+        pci_name: &str,
+        registry: Option<&MlxVariableRegistry>,
+    ) -> Option<MlxDeviceInfo> {
+        if self.simulator.device(pci_name).is_none() {
+            let device = match registry.or_else(|| registries::get(DEFAULT_REGISTRY)) {
+                Some(registry) => SimulatedDevice::from_registry(pci_name, registry),
+                None => SimulatedDevice::new(pci_name),
+            };
+            self.simulator.add_device(device);
+        }
+
+        match discover_device_with(&self.simulator, pci_name) {
+            Ok(device_info) => Some(device_info),
+            Err(e) => {
+                tracing::error!(device = %pci_name, "Failed to discover simulated device: {e}");
+                None
+            }
+        }
+    }
+
+    fn lockdown_manager(&self) -> LockdownManager {
+        LockdownManager::with_runner(
+            FlintRunner::with_path("flint").with_backend(self.simulator.backend()),
+        )
+    }
+}
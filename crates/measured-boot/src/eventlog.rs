@@ -0,0 +1,1229 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*!
+ *  Native parsing of the binary TCG PC Client event log (as found in
+ *  /sys/kernel/security/tpm0/binary_bios_measurements), in both the
+ *  crypto-agile and the legacy SHA1 formats. A parsed log can be
+ *  replayed to compute the PCR values it implies, for verifying the
+ *  values a machine quoted, and diffed event by event against the log
+ *  a measurement bundle was made from.
+*/
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use carbide_uuid::measured_boot::MeasurementBundleId;
+#[cfg(feature = "cli")]
+use rpc::admin_cli::ToTable;
+use rpc::protos::measured_boot::{
+    DiffMeasurementReportResponse, EventLogDifferenceKindPb, EventLogDifferencePb,
+    EventLogReplayPb, EventLogReplayStatusPb, PcrReplayPb,
+};
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use super::pcr::{PcrRegisterValue, PcrSet};
+
+// The signature at the start of the Spec ID event, which is the first
+// event of a crypto-agile log, and lists the digests every following
+// event carries.
+const SPEC_ID_SIGNATURE: &[u8] = b"Spec ID Event03\0";
+
+// The signature of the EV_NO_ACTION event recording the locality the
+// platform started from, which is the initial value of PCR0.
+const STARTUP_LOCALITY_SIGNATURE: &[u8] = b"StartupLocality\0";
+
+/// HashAlgorithm is a PCR bank algorithm that event logs can be
+/// replayed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    // The order algorithms are picked in when comparing two
+    // logs, strongest (and most commonly used) first.
+    const PREFERENCE: [HashAlgorithm; 4] = [Self::Sha256, Self::Sha384, Self::Sha512, Self::Sha1];
+
+    /// from_tcg_id returns the algorithm for a TPM_ALG_ID.
+    pub fn from_tcg_id(id: u16) -> Option<Self> {
+        match id {
+            0x0004 => Some(Self::Sha1),
+            0x000b => Some(Self::Sha256),
+            0x000c => Some(Self::Sha384),
+            0x000d => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// tcg_id returns the TPM_ALG_ID of the algorithm.
+    pub fn tcg_id(&self) -> u16 {
+        match self {
+            Self::Sha1 => 0x0004,
+            Self::Sha256 => 0x000b,
+            Self::Sha384 => 0x000c,
+            Self::Sha512 => 0x000d,
+        }
+    }
+
+    /// from_digest_len returns the algorithm producing digests of
+    /// the given length (in bytes), which is how the algorithm of
+    /// the PCR values in a report is worked out.
+    pub fn from_digest_len(len: usize) -> Option<Self> {
+        Self::PREFERENCE
+            .into_iter()
+            .find(|algorithm| algorithm.digest_len() == len)
+    }
+
+    pub fn digest_len(&self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha256 => 32,
+            Self::Sha384 => 48,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// extend returns the new value of a PCR currently holding
+    /// `current` after extending `digest` into it.
+    pub fn extend(&self, current: &[u8], digest: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha1 => Sha1::new()
+                .chain_update(current)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+            Self::Sha256 => Sha256::new()
+                .chain_update(current)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+            Self::Sha384 => Sha384::new()
+                .chain_update(current)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+            Self::Sha512 => Sha512::new()
+                .chain_update(current)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha384 => "sha384",
+            Self::Sha512 => "sha512",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// EventType is the type of a TCG event, as defined by the TCG PC
+/// Client Platform Firmware Profile. Unknown types are kept as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventType(pub u32);
+
+impl EventType {
+    pub const PREBOOT_CERT: Self = Self(0x0000_0000);
+    pub const POST_CODE: Self = Self(0x0000_0001);
+    pub const NO_ACTION: Self = Self(0x0000_0003);
+    pub const SEPARATOR: Self = Self(0x0000_0004);
+    pub const ACTION: Self = Self(0x0000_0005);
+    pub const EVENT_TAG: Self = Self(0x0000_0006);
+    pub const S_CRTM_CONTENTS: Self = Self(0x0000_0007);
+    pub const S_CRTM_VERSION: Self = Self(0x0000_0008);
+    pub const CPU_MICROCODE: Self = Self(0x0000_0009);
+    pub const PLATFORM_CONFIG_FLAGS: Self = Self(0x0000_000a);
+    pub const TABLE_OF_DEVICES: Self = Self(0x0000_000b);
+    pub const COMPACT_HASH: Self = Self(0x0000_000c);
+    pub const IPL: Self = Self(0x0000_000d);
+    pub const IPL_PARTITION_DATA: Self = Self(0x0000_000e);
+    pub const NONHOST_CODE: Self = Self(0x0000_000f);
+    pub const NONHOST_CONFIG: Self = Self(0x0000_0010);
+    pub const NONHOST_INFO: Self = Self(0x0000_0011);
+    pub const OMIT_BOOT_DEVICE_EVENTS: Self = Self(0x0000_0012);
+    pub const POST_CODE2: Self = Self(0x0000_0013);
+    pub const EFI_VARIABLE_DRIVER_CONFIG: Self = Self(0x8000_0001);
+    pub const EFI_VARIABLE_BOOT: Self = Self(0x8000_0002);
+    pub const EFI_BOOT_SERVICES_APPLICATION: Self = Self(0x8000_0003);
+    pub const EFI_BOOT_SERVICES_DRIVER: Self = Self(0x8000_0004);
+    pub const EFI_RUNTIME_SERVICES_DRIVER: Self = Self(0x8000_0005);
+    pub const EFI_GPT_EVENT: Self = Self(0x8000_0006);
+    pub const EFI_ACTION: Self = Self(0x8000_0007);
+    pub const EFI_PLATFORM_FIRMWARE_BLOB: Self = Self(0x8000_0008);
+    pub const EFI_HANDOFF_TABLES: Self = Self(0x8000_0009);
+    pub const EFI_PLATFORM_FIRMWARE_BLOB2: Self = Self(0x8000_000a);
+    pub const EFI_HANDOFF_TABLES2: Self = Self(0x8000_000b);
+    pub const EFI_VARIABLE_BOOT2: Self = Self(0x8000_000c);
+    pub const EFI_GPT_EVENT2: Self = Self(0x8000_000d);
+    pub const EFI_HCRTM_EVENT: Self = Self(0x8000_0010);
+    pub const EFI_VARIABLE_AUTHORITY: Self = Self(0x8000_00e0);
+    pub const EFI_SPDM_FIRMWARE_BLOB: Self = Self(0x8000_00e1);
+    pub const EFI_SPDM_FIRMWARE_CONFIG: Self = Self(0x8000_00e2);
+
+    /// name returns the spec name of the event type (e.g.
+    /// EV_SEPARATOR), if it's a known one.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match *self {
+            Self::PREBOOT_CERT => "EV_PREBOOT_CERT",
+            Self::POST_CODE => "EV_POST_CODE",
+            Self::NO_ACTION => "EV_NO_ACTION",
+            Self::SEPARATOR => "EV_SEPARATOR",
+            Self::ACTION => "EV_ACTION",
+            Self::EVENT_TAG => "EV_EVENT_TAG",
+            Self::S_CRTM_CONTENTS => "EV_S_CRTM_CONTENTS",
+            Self::S_CRTM_VERSION => "EV_S_CRTM_VERSION",
+            Self::CPU_MICROCODE => "EV_CPU_MICROCODE",
+            Self::PLATFORM_CONFIG_FLAGS => "EV_PLATFORM_CONFIG_FLAGS",
+            Self::TABLE_OF_DEVICES => "EV_TABLE_OF_DEVICES",
+            Self::COMPACT_HASH => "EV_COMPACT_HASH",
+            Self::IPL => "EV_IPL",
+            Self::IPL_PARTITION_DATA => "EV_IPL_PARTITION_DATA",
+            Self::NONHOST_CODE => "EV_NONHOST_CODE",
+            Self::NONHOST_CONFIG => "EV_NONHOST_CONFIG",
+            Self::NONHOST_INFO => "EV_NONHOST_INFO",
+            Self::OMIT_BOOT_DEVICE_EVENTS => "EV_OMIT_BOOT_DEVICE_EVENTS",
+            Self::POST_CODE2 => "EV_POST_CODE2",
+            Self::EFI_VARIABLE_DRIVER_CONFIG => "EV_EFI_VARIABLE_DRIVER_CONFIG",
+            Self::EFI_VARIABLE_BOOT => "EV_EFI_VARIABLE_BOOT",
+            Self::EFI_BOOT_SERVICES_APPLICATION => "EV_EFI_BOOT_SERVICES_APPLICATION",
+            Self::EFI_BOOT_SERVICES_DRIVER => "EV_EFI_BOOT_SERVICES_DRIVER",
+            Self::EFI_RUNTIME_SERVICES_DRIVER => "EV_EFI_RUNTIME_SERVICES_DRIVER",
+            Self::EFI_GPT_EVENT => "EV_EFI_GPT_EVENT",
+            Self::EFI_ACTION => "EV_EFI_ACTION",
+            Self::EFI_PLATFORM_FIRMWARE_BLOB => "EV_EFI_PLATFORM_FIRMWARE_BLOB",
+            Self::EFI_HANDOFF_TABLES => "EV_EFI_HANDOFF_TABLES",
+            Self::EFI_PLATFORM_FIRMWARE_BLOB2 => "EV_EFI_PLATFORM_FIRMWARE_BLOB2",
+            Self::EFI_HANDOFF_TABLES2 => "EV_EFI_HANDOFF_TABLES2",
+            Self::EFI_VARIABLE_BOOT2 => "EV_EFI_VARIABLE_BOOT2",
+            Self::EFI_GPT_EVENT2 => "EV_EFI_GPT_EVENT2",
+            Self::EFI_HCRTM_EVENT => "EV_EFI_HCRTM_EVENT",
+            Self::EFI_VARIABLE_AUTHORITY => "EV_EFI_VARIABLE_AUTHORITY",
+            Self::EFI_SPDM_FIRMWARE_BLOB => "EV_EFI_SPDM_FIRMWARE_BLOB",
+            Self::EFI_SPDM_FIRMWARE_CONFIG => "EV_EFI_SPDM_FIRMWARE_CONFIG",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "0x{:08x}", self.0),
+        }
+    }
+}
+
+/// EventDigest is a single digest of an event, for one of
+/// the algorithms the log was recorded with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventDigest {
+    pub algorithm_id: u16,
+    pub digest: Vec<u8>,
+}
+
+/// TcgEvent is a single event from the event log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcgEvent {
+    // index is the position of the event in the log,
+    // starting from 0.
+    pub index: usize,
+    pub pcr_index: u32,
+    pub event_type: EventType,
+    pub digests: Vec<EventDigest>,
+    pub data: Vec<u8>,
+}
+
+impl TcgEvent {
+    /// digest returns the digest of the event for the given algorithm,
+    /// if the log was recorded with it.
+    pub fn digest(&self, algorithm: HashAlgorithm) -> Option<&[u8]> {
+        self.digests
+            .iter()
+            .find(|digest| digest.algorithm_id == algorithm.tcg_id())
+            .map(|digest| digest.digest.as_slice())
+    }
+
+    /// is_measurement returns whether the event was extended into its
+    /// PCR. EV_NO_ACTION events are informational only.
+    pub fn is_measurement(&self) -> bool {
+        self.event_type != EventType::NO_ACTION
+    }
+
+    /// description returns a short human-friendly description of what
+    /// was measured (e.g. "shim", or "Secure Boot db"), decoded from the
+    /// event data where the event type allows for it. It doubles as the
+    /// identity of the event when diffing two logs.
+    pub fn description(&self) -> String {
+        match self.event_type {
+            EventType::EFI_VARIABLE_DRIVER_CONFIG
+            | EventType::EFI_VARIABLE_BOOT
+            | EventType::EFI_VARIABLE_BOOT2 => match efi_variable_name(&self.data) {
+                Some(name) => describe_efi_variable(&name),
+                None => "EFI variable".to_string(),
+            },
+            EventType::EFI_VARIABLE_AUTHORITY => match efi_variable_name(&self.data) {
+                Some(name) => format!("Secure Boot authority from {name}"),
+                None => "Secure Boot authority".to_string(),
+            },
+            EventType::EFI_BOOT_SERVICES_APPLICATION
+            | EventType::EFI_BOOT_SERVICES_DRIVER
+            | EventType::EFI_RUNTIME_SERVICES_DRIVER => match image_file_path(&self.data) {
+                Some(path) => describe_image(&path),
+                None if self.event_type == EventType::EFI_BOOT_SERVICES_APPLICATION => {
+                    "EFI application".to_string()
+                }
+                None => "EFI driver".to_string(),
+            },
+            EventType::EFI_PLATFORM_FIRMWARE_BLOB2 | EventType::EFI_HANDOFF_TABLES2 => {
+                // Both start with a length-prefixed description.
+                self.data
+                    .split_first()
+                    .and_then(|(len, rest)| rest.get(..*len as usize))
+                    .and_then(printable_text)
+                    .unwrap_or_else(|| self.event_type.to_string())
+            }
+            EventType::IPL => match printable_text(&self.data) {
+                Some(text) => describe_ipl(&text),
+                None => "boot loader measurement".to_string(),
+            },
+            EventType::SEPARATOR => "separator".to_string(),
+            EventType::S_CRTM_VERSION => "CRTM version".to_string(),
+            EventType::S_CRTM_CONTENTS => "CRTM contents".to_string(),
+            EventType::CPU_MICROCODE => "CPU microcode".to_string(),
+            EventType::PLATFORM_CONFIG_FLAGS => "platform configuration flags".to_string(),
+            EventType::EFI_PLATFORM_FIRMWARE_BLOB => "platform firmware blob".to_string(),
+            EventType::EFI_HANDOFF_TABLES => "handoff tables".to_string(),
+            EventType::EFI_GPT_EVENT | EventType::EFI_GPT_EVENT2 => {
+                "GPT partition table".to_string()
+            }
+            // Actions (e.g. "Exit Boot Services Invocation"), POST codes and
+            // the like carry a plain string.
+            _ => printable_text(&self.data).unwrap_or_else(|| self.event_type.to_string()),
+        }
+    }
+
+    // startup_locality returns the locality of a StartupLocality
+    // event, which sets the initial value of PCR0.
+    fn startup_locality(&self) -> Option<u8> {
+        if self.event_type != EventType::NO_ACTION || self.pcr_index != 0 {
+            return None;
+        }
+        self.data
+            .strip_prefix(STARTUP_LOCALITY_SIGNATURE)
+            .and_then(|rest| rest.first().copied())
+    }
+}
+
+impl fmt::Display for TcgEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} PCR{} {} \"{}\"",
+            self.index,
+            self.pcr_index,
+            self.event_type,
+            self.description()
+        )?;
+        for digest in &self.digests {
+            match HashAlgorithm::from_tcg_id(digest.algorithm_id) {
+                Some(algorithm) => write!(f, " {algorithm}:{}", hex::encode(&digest.digest))?,
+                None => write!(
+                    f,
+                    " 0x{:04x}:{}",
+                    digest.algorithm_id,
+                    hex::encode(&digest.digest)
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// EventLog is a parsed TCG PC Client event log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventLog {
+    // digest_sizes maps the TPM_ALG_ID of every algorithm the
+    // log was recorded with to its digest size.
+    pub digest_sizes: BTreeMap<u16, u16>,
+    pub events: Vec<TcgEvent>,
+}
+
+impl EventLog {
+    /// parse parses a binary event log, in either the crypto-agile
+    /// format or the legacy SHA1-only format.
+    pub fn parse(data: &[u8]) -> super::Result<Self> {
+        let mut reader = Reader::new(data);
+
+        // The first event is always in the legacy format. For a
+        // crypto-agile log it's the Spec ID event, which describes
+        // the digests of every event after it.
+        let first = read_legacy_event(&mut reader, 0)?;
+        let spec_id = parse_spec_id_event(&first)?;
+        let digest_sizes = match &spec_id {
+            Some(digest_sizes) => digest_sizes.clone(),
+            None => BTreeMap::from([(HashAlgorithm::Sha1.tcg_id(), 20)]),
+        };
+
+        let mut events = vec![first];
+        // Firmware pads the memory region holding the log, which can
+        // end up at the end of a log read from somewhere other than
+        // securityfs.
+        while !reader.remaining().iter().all(|b| *b == 0x00 || *b == 0xff) {
+            let index = events.len();
+            let event = match spec_id {
+                Some(_) => read_event2(&mut reader, index, &digest_sizes)?,
+                None => read_legacy_event(&mut reader, index)?,
+            };
+            events.push(event);
+        }
+
+        Ok(Self {
+            digest_sizes,
+            events,
+        })
+    }
+
+    /// algorithms returns the algorithms the log can be replayed with.
+    pub fn algorithms(&self) -> Vec<HashAlgorithm> {
+        self.digest_sizes
+            .keys()
+            .filter_map(|id| HashAlgorithm::from_tcg_id(*id))
+            .collect()
+    }
+
+    pub fn has_algorithm(&self, algorithm: HashAlgorithm) -> bool {
+        self.digest_sizes.contains_key(&algorithm.tcg_id())
+    }
+
+    /// measured_pcrs returns the PCRs the log has events for.
+    pub fn measured_pcrs(&self) -> BTreeSet<u32> {
+        self.events
+            .iter()
+            .filter(|event| event.is_measurement())
+            .map(|event| event.pcr_index)
+            .collect()
+    }
+
+    /// measurements returns the events extended into the given PCR,
+    /// in the order they were extended.
+    pub fn measurements(&self, pcr_index: u32) -> Vec<&TcgEvent> {
+        self.events
+            .iter()
+            .filter(|event| event.pcr_index == pcr_index && event.is_measurement())
+            .collect()
+    }
+
+    /// replay computes the value of every PCR the log has events for,
+    /// by extending the event digests for `algorithm` in order.
+    pub fn replay(&self, algorithm: HashAlgorithm) -> BTreeMap<u32, Vec<u8>> {
+        let mut pcrs: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+        for event in &self.events {
+            if let Some(locality) = event.startup_locality() {
+                let mut initial = vec![0; algorithm.digest_len()];
+                initial[algorithm.digest_len() - 1] = locality;
+                pcrs.insert(event.pcr_index, initial);
+                continue;
+            }
+            if !event.is_measurement() {
+                continue;
+            }
+            let Some(digest) = event.digest(algorithm) else {
+                continue;
+            };
+            let current = pcrs
+                .entry(event.pcr_index)
+                .or_insert_with(|| initial_pcr_value(event.pcr_index, algorithm));
+            *current = algorithm.extend(current, digest);
+        }
+        pcrs
+    }
+
+    /// verify replays the log and compares the result with PCR values
+    /// quoted by a machine. The algorithm is picked based on the length
+    /// of the quoted values. PCRs without any events in the log (e.g.
+    /// ones only the OS extends) aren't covered by it, and are skipped.
+    pub fn verify(&self, quoted: &[PcrRegisterValue]) -> super::Result<Vec<PcrReplay>> {
+        let Some(first) = quoted.first() else {
+            return Ok(Vec::new());
+        };
+        let algorithm =
+            HashAlgorithm::from_digest_len(first.sha_any.len() / 2).ok_or_else(|| {
+                super::Error::Parse(format!(
+                    "no hash algorithm produces PCR values like {}",
+                    first.sha_any
+                ))
+            })?;
+        if !self.has_algorithm(algorithm) {
+            return Err(super::Error::Parse(format!(
+                "event log has no {algorithm} digests to replay"
+            )));
+        }
+
+        let replayed = self.replay(algorithm);
+        Ok(quoted
+            .iter()
+            .filter_map(|value| {
+                let pcr = replayed.get(&u32::try_from(value.pcr_register).ok()?)?;
+                Some(PcrReplay {
+                    pcr_register: value.pcr_register,
+                    quoted: value.sha_any.to_lowercase(),
+                    replayed: hex::encode(pcr),
+                })
+            })
+            .collect())
+    }
+
+    /// replay_against sums up verifying the log against the PCR
+    /// values quoted by a machine, so that the outcome can be stored
+    /// with the report the values went into.
+    pub fn replay_against(&self, quoted: &[PcrRegisterValue]) -> EventLogReplay {
+        match self.verify(quoted) {
+            Ok(replays) => {
+                let mismatched: Vec<String> = replays
+                    .iter()
+                    .filter(|replay| !replay.matches())
+                    .map(|replay| format!("PCR{}", replay.pcr_register))
+                    .collect();
+                if mismatched.is_empty() {
+                    EventLogReplay {
+                        status: EventLogReplayStatus::Matched,
+                        message: None,
+                    }
+                } else {
+                    EventLogReplay {
+                        status: EventLogReplayStatus::Mismatched,
+                        message: Some(format!(
+                            "replay does not match the quote for {}",
+                            mismatched.join(", ")
+                        )),
+                    }
+                }
+            }
+            Err(e) => EventLogReplay {
+                status: EventLogReplayStatus::Failed,
+                message: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// diff compares this log with the one of a `baseline` (e.g. the log
+    /// a bundle was made from) event by event, PCR by PCR, optionally
+    /// limited to a set of PCRs. Events are matched up by their type and
+    /// description, so a new shim shows up as "shim changed" rather than
+    /// a PCR4 mismatch.
+    pub fn diff(
+        &self,
+        baseline: &EventLog,
+        pcr_set: Option<&PcrSet>,
+    ) -> super::Result<Vec<EventDifference>> {
+        let algorithm = HashAlgorithm::PREFERENCE
+            .into_iter()
+            .find(|algorithm| self.has_algorithm(*algorithm) && baseline.has_algorithm(*algorithm))
+            .ok_or_else(|| {
+                super::Error::Parse("event logs have no hash algorithm in common".to_string())
+            })?;
+
+        let mut pcr_indexes = self.measured_pcrs();
+        pcr_indexes.extend(baseline.measured_pcrs());
+        if let Some(pcr_set) = pcr_set {
+            pcr_indexes.retain(|pcr| {
+                pcr_set
+                    .iter()
+                    .any(|selected| u32::try_from(*selected).ok() == Some(*pcr))
+            });
+        }
+
+        let mut differences = Vec::new();
+        for pcr_index in pcr_indexes {
+            differences.extend(diff_events(
+                pcr_index,
+                &baseline.measurements(pcr_index),
+                &self.measurements(pcr_index),
+                algorithm,
+            ));
+        }
+        Ok(differences)
+    }
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in &self.events {
+            writeln!(f, "{event}")?;
+        }
+        Ok(())
+    }
+}
+
+/// PcrReplay is the value of a PCR computed by replaying the event
+/// log, next to the value the machine quoted for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PcrReplay {
+    pub pcr_register: i16,
+    pub quoted: String,
+    pub replayed: String,
+}
+
+impl PcrReplay {
+    pub fn matches(&self) -> bool {
+        self.quoted.eq_ignore_ascii_case(&self.replayed)
+    }
+}
+
+impl From<PcrReplay> for PcrReplayPb {
+    fn from(val: PcrReplay) -> Self {
+        Self {
+            pcr_register: val.pcr_register as i32,
+            quoted: val.quoted,
+            replayed: val.replayed,
+        }
+    }
+}
+
+impl From<PcrReplayPb> for PcrReplay {
+    fn from(msg: PcrReplayPb) -> Self {
+        Self {
+            pcr_register: msg.pcr_register as i16,
+            quoted: msg.quoted,
+            replayed: msg.replayed,
+        }
+    }
+}
+
+/// EventLogReplayStatus is an enum in the database, and is the
+/// outcome of replaying the event log of a report against the
+/// PCR values the machine quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "measurement_event_log_replay", rename_all = "lowercase")
+)]
+pub enum EventLogReplayStatus {
+    // Matched means every PCR covered by the log replays to
+    // the quoted value.
+    Matched,
+    // Mismatched means at least one PCR doesn't, so the log
+    // can't be trusted to explain the measurements.
+    Mismatched,
+    // Failed means the log couldn't be replayed at all (e.g. it
+    // has no digests of the quoted algorithm).
+    Failed,
+}
+
+impl fmt::Display for EventLogReplayStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Self::Matched => "matched",
+            Self::Mismatched => "mismatched",
+            Self::Failed => "failed",
+        };
+        write!(f, "{status}")
+    }
+}
+
+impl From<EventLogReplayStatus> for EventLogReplayStatusPb {
+    fn from(val: EventLogReplayStatus) -> Self {
+        match val {
+            EventLogReplayStatus::Matched => Self::Matched,
+            EventLogReplayStatus::Mismatched => Self::Mismatched,
+            EventLogReplayStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<EventLogReplayStatusPb> for EventLogReplayStatus {
+    fn from(msg: EventLogReplayStatusPb) -> Self {
+        match msg {
+            EventLogReplayStatusPb::Matched => Self::Matched,
+            EventLogReplayStatusPb::Mismatched => Self::Mismatched,
+            EventLogReplayStatusPb::Failed => Self::Failed,
+        }
+    }
+}
+
+/// EventLogReplay is the stored outcome of replaying the event log
+/// a machine submitted with a report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventLogReplay {
+    pub status: EventLogReplayStatus,
+    // message says which PCRs mismatched, or why
+    // the replay failed.
+    pub message: Option<String>,
+}
+
+impl EventLogReplay {
+    pub fn matches(&self) -> bool {
+        self.status == EventLogReplayStatus::Matched
+    }
+}
+
+impl fmt::Display for EventLogReplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {message}", self.status),
+            None => write!(f, "{}", self.status),
+        }
+    }
+}
+
+impl From<EventLogReplay> for EventLogReplayPb {
+    fn from(val: EventLogReplay) -> Self {
+        Self {
+            status: EventLogReplayStatusPb::from(val.status) as i32,
+            message: val.message,
+        }
+    }
+}
+
+impl From<EventLogReplayPb> for EventLogReplay {
+    fn from(msg: EventLogReplayPb) -> Self {
+        Self {
+            status: msg.status().into(),
+            message: msg.message,
+        }
+    }
+}
+
+/// EventDifferenceKind is how an event differs from the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EventDifferenceKind {
+    // Added events are only in the log being compared.
+    Added,
+    // Removed events are only in the baseline log.
+    Removed,
+    // Changed events are in both logs, with different digests.
+    Changed,
+}
+
+impl fmt::Display for EventDifferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+impl From<EventDifferenceKind> for EventLogDifferenceKindPb {
+    fn from(val: EventDifferenceKind) -> Self {
+        match val {
+            EventDifferenceKind::Added => Self::Added,
+            EventDifferenceKind::Removed => Self::Removed,
+            EventDifferenceKind::Changed => Self::Changed,
+        }
+    }
+}
+
+impl From<EventLogDifferenceKindPb> for EventDifferenceKind {
+    fn from(msg: EventLogDifferenceKindPb) -> Self {
+        match msg {
+            EventLogDifferenceKindPb::Added => Self::Added,
+            EventLogDifferenceKindPb::Removed => Self::Removed,
+            EventLogDifferenceKindPb::Changed => Self::Changed,
+        }
+    }
+}
+
+/// EventDifference is a single event that differs between a
+/// log and its baseline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventDifference {
+    pub pcr_register: i16,
+    pub kind: EventDifferenceKind,
+    pub event_type: String,
+    pub description: String,
+    // expected_digest is the digest of the event in the
+    // baseline, and unset for added events.
+    pub expected_digest: Option<String>,
+    // actual_digest is the digest of the event in the log
+    // being compared, and unset for removed events.
+    pub actual_digest: Option<String>,
+}
+
+impl fmt::Display for EventDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PCR{}: {} {}",
+            self.pcr_register, self.description, self.kind
+        )
+    }
+}
+
+impl From<EventDifference> for EventLogDifferencePb {
+    fn from(val: EventDifference) -> Self {
+        Self {
+            pcr_register: val.pcr_register as i32,
+            kind: EventLogDifferenceKindPb::from(val.kind) as i32,
+            event_type: val.event_type,
+            description: val.description,
+            expected_digest: val.expected_digest,
+            actual_digest: val.actual_digest,
+        }
+    }
+}
+
+impl From<EventLogDifferencePb> for EventDifference {
+    fn from(msg: EventLogDifferencePb) -> Self {
+        Self {
+            pcr_register: msg.pcr_register as i16,
+            kind: msg.kind().into(),
+            event_type: msg.event_type,
+            description: msg.description,
+            expected_digest: msg.expected_digest,
+            actual_digest: msg.actual_digest,
+        }
+    }
+}
+
+/// MeasurementReportDiff is the result of diffing the event log of
+/// a report against the event log of a bundle, along with the replay
+/// of the report's event log against the PCR values it quoted.
+#[derive(Debug, Clone, Serialize)]
+pub struct MeasurementReportDiff {
+    pub bundle_id: Option<MeasurementBundleId>,
+    pub pcr_replays: Vec<PcrReplay>,
+    pub differences: Vec<EventDifference>,
+    // event_log_replay is the outcome of the replay stored
+    // when the machine submitted the report.
+    pub event_log_replay: Option<EventLogReplay>,
+}
+
+impl From<MeasurementReportDiff> for DiffMeasurementReportResponse {
+    fn from(val: MeasurementReportDiff) -> Self {
+        Self {
+            bundle_id: val.bundle_id,
+            pcr_replays: val.pcr_replays.into_iter().map(Into::into).collect(),
+            differences: val.differences.into_iter().map(Into::into).collect(),
+            event_log_replay: val.event_log_replay.map(Into::into),
+        }
+    }
+}
+
+impl From<DiffMeasurementReportResponse> for MeasurementReportDiff {
+    fn from(msg: DiffMeasurementReportResponse) -> Self {
+        Self {
+            bundle_id: msg.bundle_id,
+            pcr_replays: msg.pcr_replays.into_iter().map(Into::into).collect(),
+            differences: msg.differences.into_iter().map(Into::into).collect(),
+            event_log_replay: msg.event_log_replay.map(Into::into),
+        }
+    }
+}
+
+// When `report diff <report-id>` gets called, and the output format is
+// the default table view, this gets used to print a pretty table.
+#[cfg(feature = "cli")]
+impl ToTable for MeasurementReportDiff {
+    fn into_table(self) -> eyre::Result<String> {
+        let mut table = prettytable::Table::new();
+        let mut replay_table = prettytable::Table::new();
+        replay_table.add_row(prettytable::row![
+            "pcr_register",
+            "quoted",
+            "replayed",
+            "match"
+        ]);
+        for replay in self.pcr_replays.iter() {
+            replay_table.add_row(prettytable::row![
+                replay.pcr_register,
+                replay.quoted,
+                replay.replayed,
+                replay.matches()
+            ]);
+        }
+        let mut differences_table = prettytable::Table::new();
+        differences_table.add_row(prettytable::row![
+            "pcr_register",
+            "difference",
+            "event_type",
+            "expected",
+            "actual"
+        ]);
+        for difference in self.differences.iter() {
+            differences_table.add_row(prettytable::row![
+                difference.pcr_register,
+                format!("{} {}", difference.description, difference.kind),
+                difference.event_type,
+                difference.expected_digest.as_deref().unwrap_or("-"),
+                difference.actual_digest.as_deref().unwrap_or("-")
+            ]);
+        }
+        table.add_row(prettytable::row![
+            "bundle_id",
+            self.bundle_id
+                .map(|bundle_id| bundle_id.to_string())
+                .unwrap_or_default()
+        ]);
+        table.add_row(prettytable::row![
+            "event_log_replay",
+            self.event_log_replay
+                .map(|replay| replay.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        ]);
+        table.add_row(prettytable::row!["pcr_replays", replay_table]);
+        table.add_row(prettytable::row!["differences", differences_table]);
+        Ok(table.to_string())
+    }
+}
+
+// diff_events diffs the events of a single PCR, lining them up by
+// their type and description with a longest common subsequence.
+fn diff_events(
+    pcr_index: u32,
+    expected: &[&TcgEvent],
+    actual: &[&TcgEvent],
+    algorithm: HashAlgorithm,
+) -> Vec<EventDifference> {
+    let expected_keys: Vec<(EventType, String)> = expected
+        .iter()
+        .map(|event| (event.event_type, event.description()))
+        .collect();
+    let actual_keys: Vec<(EventType, String)> = actual
+        .iter()
+        .map(|event| (event.event_type, event.description()))
+        .collect();
+
+    // lengths[i][j] is the length of the longest common
+    // subsequence of expected[i..] and actual[j..].
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected_keys[i] == actual_keys[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let difference = |kind: EventDifferenceKind,
+                      key: &(EventType, String),
+                      expected: Option<&TcgEvent>,
+                      actual: Option<&TcgEvent>| {
+        EventDifference {
+            pcr_register: pcr_index as i16,
+            kind,
+            event_type: key.0.to_string(),
+            description: key.1.clone(),
+            expected_digest: expected
+                .and_then(|event| event.digest(algorithm))
+                .map(hex::encode),
+            actual_digest: actual
+                .and_then(|event| event.digest(algorithm))
+                .map(hex::encode),
+        }
+    };
+
+    let mut differences = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected_keys[i] == actual_keys[j] {
+            if expected[i].digest(algorithm) != actual[j].digest(algorithm) {
+                differences.push(difference(
+                    EventDifferenceKind::Changed,
+                    &actual_keys[j],
+                    Some(expected[i]),
+                    Some(actual[j]),
+                ));
+            }
+            i += 1;
+            j += 1;
+        } else if j < actual.len()
+            && (i == expected.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+        {
+            differences.push(difference(
+                EventDifferenceKind::Added,
+                &actual_keys[j],
+                None,
+                Some(actual[j]),
+            ));
+            j += 1;
+        } else {
+            differences.push(difference(
+                EventDifferenceKind::Removed,
+                &expected_keys[i],
+                Some(expected[i]),
+                None,
+            ));
+            i += 1;
+        }
+    }
+    differences
+}
+
+// initial_pcr_value returns the value of a PCR after a reset. The
+// PCRs reserved for the dynamic root of trust (17-22) start out as
+// all ones, and everything else as all zeros.
+fn initial_pcr_value(pcr_index: u32, algorithm: HashAlgorithm) -> Vec<u8> {
+    let fill = if (17..=22).contains(&pcr_index) {
+        0xff
+    } else {
+        0x00
+    };
+    vec![fill; algorithm.digest_len()]
+}
+
+// read_legacy_event reads a TCG_PCClientPCREvent, which has a
+// single SHA1 digest.
+fn read_legacy_event(reader: &mut Reader<'_>, index: usize) -> super::Result<TcgEvent> {
+    let pcr_index = reader.u32()?;
+    let event_type = EventType(reader.u32()?);
+    let digest = reader.bytes(20)?.to_vec();
+    let size = reader.u32()? as usize;
+    let data = reader.bytes(size)?.to_vec();
+    Ok(TcgEvent {
+        index,
+        pcr_index,
+        event_type,
+        digests: vec![EventDigest {
+            algorithm_id: HashAlgorithm::Sha1.tcg_id(),
+            digest,
+        }],
+        data,
+    })
+}
+
+// read_event2 reads a crypto-agile TCG_PCR_EVENT2, whose digests
+// are sized according to the Spec ID event.
+fn read_event2(
+    reader: &mut Reader<'_>,
+    index: usize,
+    digest_sizes: &BTreeMap<u16, u16>,
+) -> super::Result<TcgEvent> {
+    let pcr_index = reader.u32()?;
+    let event_type = EventType(reader.u32()?);
+    let count = reader.u32()?;
+    let mut digests = Vec::new();
+    for _ in 0..count {
+        let algorithm_id = reader.u16()?;
+        let size = digest_sizes.get(&algorithm_id).ok_or_else(|| {
+            super::Error::Parse(format!(
+                "event {index} has a digest for algorithm 0x{algorithm_id:04x}, which the Spec ID event doesn't list"
+            ))
+        })?;
+        let digest = reader.bytes(*size as usize)?.to_vec();
+        digests.push(EventDigest {
+            algorithm_id,
+            digest,
+        });
+    }
+    let size = reader.u32()? as usize;
+    let data = reader.bytes(size)?.to_vec();
+    Ok(TcgEvent {
+        index,
+        pcr_index,
+        event_type,
+        digests,
+        data,
+    })
+}
+
+// parse_spec_id_event returns the digest sizes listed in the Spec ID
+// event, or None if the first event isn't one (i.e. the log is in the
+// legacy SHA1 format).
+fn parse_spec_id_event(event: &TcgEvent) -> super::Result<Option<BTreeMap<u16, u16>>> {
+    if event.event_type != EventType::NO_ACTION {
+        return Ok(None);
+    }
+    let Some(spec_id) = event.data.strip_prefix(SPEC_ID_SIGNATURE) else {
+        return Ok(None);
+    };
+
+    let mut reader = Reader::new(spec_id);
+    // platformClass, followed by the spec version (minor, major
+    // and errata) and uintnSize.
+    reader.bytes(8)?;
+    let count = reader.u32()?;
+    let mut digest_sizes = BTreeMap::new();
+    for _ in 0..count {
+        let algorithm_id = reader.u16()?;
+        let size = reader.u16()?;
+        digest_sizes.insert(algorithm_id, size);
+    }
+    if digest_sizes.is_empty() {
+        return Err(super::Error::Parse(
+            "Spec ID event lists no algorithms".to_string(),
+        ));
+    }
+    Ok(Some(digest_sizes))
+}
+
+// efi_variable_name returns the name of the variable in a
+// UEFI_VARIABLE_DATA structure.
+fn efi_variable_name(data: &[u8]) -> Option<String> {
+    let mut reader = Reader::new(data);
+    // VariableName, which is actually the vendor GUID.
+    reader.bytes(16).ok()?;
+    let name_len = usize::try_from(reader.u64().ok()?).ok()?;
+    let _data_len = reader.u64().ok()?;
+    let name = reader.bytes(name_len.checked_mul(2)?).ok()?;
+    Some(utf16_string(name))
+}
+
+// image_file_path returns the file path from the device path in a
+// UEFI_IMAGE_LOAD_EVENT structure, if it has one.
+fn image_file_path(data: &[u8]) -> Option<String> {
+    let mut reader = Reader::new(data);
+    // ImageLocationInMemory, ImageLengthInMemory and
+    // ImageLinkTimeAddress.
+    reader.bytes(24).ok()?;
+    let path_len = usize::try_from(reader.u64().ok()?).ok()?;
+    let mut path = Reader::new(reader.bytes(path_len).ok()?);
+
+    let mut file_path = String::new();
+    while let (Ok(node_type), Ok(sub_type), Ok(len)) = (path.u8(), path.u8(), path.u16()) {
+        // 0x7f is the end of the device path.
+        if node_type == 0x7f || len < 4 {
+            break;
+        }
+        let Ok(node) = path.bytes(len as usize - 4) else {
+            break;
+        };
+        // A media (0x04) file path (0x04) node.
+        if node_type == 0x04 && sub_type == 0x04 {
+            file_path.push_str(&utf16_string(node));
+        }
+    }
+    (!file_path.is_empty()).then_some(file_path)
+}
+
+// describe_efi_variable names the well-known Secure Boot variables
+// the way operators know them.
+fn describe_efi_variable(name: &str) -> String {
+    match name {
+        "SecureBoot" => "Secure Boot state".to_string(),
+        "PK" | "KEK" | "db" | "dbx" | "dbt" | "dbr" => format!("Secure Boot {name}"),
+        _ => format!("EFI variable {name}"),
+    }
+}
+
+// describe_image names an EFI image after its file, calling out
+// the boot loaders every host goes through.
+fn describe_image(path: &str) -> String {
+    let file_name = path
+        .rsplit(['\\', '/'])
+        .next()
+        .unwrap_or(path)
+        .to_lowercase();
+    if file_name.starts_with("shim") {
+        "shim".to_string()
+    } else if file_name.starts_with("grub") {
+        "GRUB".to_string()
+    } else if file_name.starts_with("mm") {
+        "MOK manager".to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+// describe_ipl describes what the boot loader measured. GRUB measures
+// every command it runs and the kernel command line, which are keyed by
+// the command alone so an edited command shows up as changed.
+fn describe_ipl(text: &str) -> String {
+    if let Some(command) = text.strip_prefix("grub_cmd: ") {
+        let name = command.split_whitespace().next().unwrap_or_default();
+        format!("GRUB command {name}")
+    } else if text.starts_with("kernel_cmdline: ") {
+        "kernel command line".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+// printable_text returns the data as a string, if it's (NUL
+// terminated) printable text.
+fn printable_text(data: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(data).ok()?.trim_end_matches('\0');
+    if text.is_empty() || text.chars().any(|c| c.is_control()) {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+// utf16_string decodes NUL terminated UTF-16LE, as used by UEFI.
+fn utf16_string(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+// Reader reads the little-endian structures of the event log,
+// failing rather than panicking on truncated input.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn remaining(&self) -> &'a [u8] {
+        &self.data[self.offset..]
+    }
+
+    fn bytes(&mut self, len: usize) -> super::Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| {
+                super::Error::Parse(format!("event log truncated at offset {}", self.offset))
+            })?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> super::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> super::Result<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> super::Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> super::Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+}
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 pub mod bundle;
+pub mod eventlog;
 pub mod journal;
 pub mod machine;
 pub mod pcr;
@@ -32,6 +32,7 @@ use rpc::errors::RpcDataConversionError;
 use rpc::protos::measured_boot::MeasurementReportPb;
 use serde::Serialize;
 
+use super::eventlog::EventLogReplay;
 use super::pcr::PcrRegisterValue;
 use super::records::MeasurementReportValueRecord;
 
@@ -45,6 +46,9 @@ pub struct MeasurementReport {
     pub machine_id: MachineId,
     pub ts: chrono::DateTime<Utc>,
     pub values: Vec<MeasurementReportValueRecord>,
+    // event_log_replay is the outcome of replaying the event log
+    // the machine submitted with the report, if it submitted one.
+    pub event_log_replay: Option<EventLogReplay>,
 }
 
 impl MeasurementReport {
@@ -82,6 +86,7 @@ impl From<MeasurementReport> for MeasurementReportPb {
                 .map(|value| value.clone().into())
                 .collect(),
             ts: Some(val.ts.into()),
+            event_log_replay: val.event_log_replay.map(Into::into),
         }
     }
 }
@@ -113,6 +118,7 @@ impl TryFrom<MeasurementReportPb> for MeasurementReport {
             machine_id: MachineId::from_str(&msg.machine_id)?,
             values: values?,
             ts: chrono::DateTime::<chrono::Utc>::try_from(msg.ts.unwrap())?,
+            event_log_replay: msg.event_log_replay.map(Into::into),
         })
     }
 }
@@ -134,6 +140,12 @@ impl ToTable for MeasurementReport {
         table.add_row(prettytable::row!["report_id", self.report_id]);
         table.add_row(prettytable::row!["machine_id", self.machine_id]);
         table.add_row(prettytable::row!["created_ts", self.ts]);
+        table.add_row(prettytable::row![
+            "event_log_replay",
+            self.event_log_replay
+                .map(|replay| replay.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        ]);
         table.add_row(prettytable::row!["values", values_table]);
         Ok(table.to_string())
     }
@@ -18,24 +18,36 @@
 // these are not visible outside of this crate
 mod client;
 mod keystore;
+mod local;
 mod parser;
+mod rim;
+mod spdm;
 
 // re-exports
 use std::collections as stdcol;
+use std::path::PathBuf;
 
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
 pub use client::{NrasVerifierClient, VerifierClient};
 pub use keystore::{KeyStore, NrasKeyStore};
+pub use local::{LocalKeyStore, LocalVerifierClient};
 pub use parser::Parser;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
+    #[serde(default)]
     pub nras_url: String,
+    #[serde(default)]
     pub nras_gpu_url_suffix: String,
+    #[serde(default)]
     pub nras_jwks_url: String,
     pub validate_jwt_expiry: bool,
+    // when set, evidence is appraised locally instead of being sent
+    // to NRAS, e.g. for air-gapped sites which can't reach it
+    #[serde(default)]
+    pub local_verifier: Option<LocalVerifierConfig>,
 }
 
 impl Default for Config {
@@ -45,10 +57,52 @@ impl Default for Config {
             nras_gpu_url_suffix: Default::default(),
             nras_jwks_url: Default::default(),
             validate_jwt_expiry: true,
+            local_verifier: None,
         }
     }
 }
 
+/// Configuration of the local verifier.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LocalVerifierConfig {
+    /// PEM files holding the root certificates device certificate
+    /// chains have to chain up to.
+    pub root_certificates: Vec<PathBuf>,
+    /// Directory holding the imported RIMs (SWID tag XML files) the
+    /// device measurements are compared against. RIMs are picked up
+    /// as they're imported, without a restart.
+    pub rim_directory: PathBuf,
+    /// Secret the key signing the verifier tokens is derived from,
+    /// usually the site-wide root. It is set at startup rather than
+    /// read from the configuration file.
+    #[serde(skip)]
+    pub token_secret: TokenSecret,
+}
+
+/// Secret material which is kept out of debug output.
+#[derive(Clone, Default)]
+pub struct TokenSecret(Vec<u8>);
+
+impl TokenSecret {
+    pub fn new(secret: impl Into<Vec<u8>>) -> TokenSecret {
+        TokenSecret(secret.into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Debug for TokenSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenSecret(..)")
+    }
+}
+
 #[derive(Clone, Debug, thiserror::Error, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NrasError {
     #[error("Error talking to NRAS: {0}")]
@@ -65,6 +119,8 @@ pub enum NrasError {
     DecodingKeyNotFound(String),
     #[error("Error forming JWK decoding key: {0}")]
     Jwk(String),
+    #[error("Error in local verifier configuration: {0}")]
+    Configuration(String),
 }
 
 impl From<reqwest::Error> for NrasError {
@@ -0,0 +1,509 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// A verifier which appraises GPU evidence locally, for sites which
+// can't reach NRAS. It checks the device certificate chain against the
+// configured roots, the evidence signature and nonce, and compares the
+// measurements with the imported RIMs. The outcome is returned in the
+// same shape NRAS uses, so that it goes through the regular Parser.
+
+use std::collections as stdcol;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256, Sha384};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::FromDer;
+use {jsonwebtoken as jst, serde_json as sj};
+
+use crate::rim::ReferenceIntegrityManifest;
+use crate::spdm::MeasurementEvidence;
+use crate::{
+    DeviceAttestationInfo, EvidenceCertificate, KeyStore, LocalVerifierConfig, NrasError,
+    RawAttestationOutcome, VerifierClient,
+};
+
+const LOCAL_VERIFIER_KID: &str = "nras-local-verifier";
+const TOKEN_KEY_INFO: &str = "nras-local-verifier:v1";
+const TOKEN_ALGORITHM: jst::Algorithm = jst::Algorithm::HS384;
+const TOKEN_LIFETIME_SECS: u64 = 3600;
+const NONCE_LEN: usize = 32;
+
+#[derive(Debug)]
+pub struct LocalVerifierClient {
+    config: LocalVerifierConfig,
+}
+
+impl LocalVerifierClient {
+    pub fn new_with_config(config: &crate::Config) -> LocalVerifierClient {
+        LocalVerifierClient {
+            config: config.local_verifier.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl VerifierClient for LocalVerifierClient {
+    async fn attest_gpu(
+        &self,
+        device_attestation_info: &DeviceAttestationInfo,
+    ) -> Result<RawAttestationOutcome, NrasError> {
+        // roots and RIMs are loaded on every attestation, so that newly
+        // imported RIMs are picked up right away
+        let roots = load_root_certificates(&self.config)?;
+        let rims = ReferenceIntegrityManifest::load_directory(&self.config.rim_directory)?;
+        let signing_key = jst::EncodingKey::from_secret(&token_key(&self.config)?);
+        let nonce = expected_nonce(&device_attestation_info.nonce)?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        let mut attestation_passed = !device_attestation_info.ec.is_empty();
+        let mut submods = sj::Map::new();
+        let mut devices_outcome = stdcol::HashMap::new();
+
+        for (i, evidence_certificate) in device_attestation_info.ec.iter().enumerate() {
+            let appraisal = appraise_gpu(evidence_certificate, &nonce, &roots, &rims);
+            attestation_passed &= appraisal.passed();
+
+            let mut claims = appraisal.into_claims();
+            claims.insert("sub".to_string(), "NVIDIA-GPU-ATTESTATION".into());
+            claims.insert(
+                "eat_nonce".to_string(),
+                device_attestation_info.nonce.clone().into(),
+            );
+            claims.insert(
+                "x-nvidia-gpu-vbios-version".to_string(),
+                evidence_certificate.firmware_version.clone().into(),
+            );
+            let device_token = sign_token(claims, now, &signing_key)?;
+
+            let device_name = format!("GPU-{}", i);
+            submods.insert(
+                device_name.clone(),
+                sj::json!([
+                    "DIGEST",
+                    [
+                        "SHA-256",
+                        hex::encode(Sha256::digest(device_token.as_bytes()))
+                    ]
+                ]),
+            );
+            devices_outcome.insert(device_name, device_token);
+        }
+
+        let mut claims = sj::Map::new();
+        claims.insert("sub".to_string(), "NVIDIA-PLATFORM-ATTESTATION".into());
+        claims.insert(
+            "eat_nonce".to_string(),
+            device_attestation_info.nonce.clone().into(),
+        );
+        claims.insert(
+            "x-nvidia-overall-att-result".to_string(),
+            attestation_passed.into(),
+        );
+        claims.insert("submods".to_string(), submods.into());
+        let overall_token = sign_token(claims, now, &signing_key)?;
+
+        Ok(RawAttestationOutcome {
+            overall_outcome: ("JWT".to_string(), overall_token),
+            devices_outcome,
+        })
+    }
+
+    async fn attest_dpu(
+        &self,
+        _device_attestation_info: &DeviceAttestationInfo,
+    ) -> Result<RawAttestationOutcome, NrasError> {
+        Err(NrasError::NotImplemented)
+    }
+    async fn attest_cx7(
+        &self,
+        _device_attestation_info: &DeviceAttestationInfo,
+    ) -> Result<RawAttestationOutcome, NrasError> {
+        Err(NrasError::NotImplemented)
+    }
+}
+
+// KeyStore for the tokens minted by the LocalVerifierClient
+#[derive(Debug)]
+pub struct LocalKeyStore {
+    key: jst::DecodingKey,
+}
+
+impl KeyStore for LocalKeyStore {
+    fn find_key(&self, kid: &str) -> Option<jst::DecodingKey> {
+        (kid == LOCAL_VERIFIER_KID).then(|| self.key.clone())
+    }
+}
+
+impl LocalKeyStore {
+    pub fn new_with_config(config: &crate::Config) -> Result<LocalKeyStore, NrasError> {
+        let local_config = config.local_verifier.clone().unwrap_or_default();
+        Ok(LocalKeyStore {
+            key: jst::DecodingKey::from_secret(&token_key(&local_config)?),
+        })
+    }
+}
+
+// The outcome of appraising the evidence of a single GPU
+#[derive(Debug, Default)]
+struct GpuAppraisal {
+    cert_chain_valid: bool,
+    report_parsed: bool,
+    nonce_match: bool,
+    signature_verified: bool,
+    rim_fetched: bool,
+    measurements_match: bool,
+    mismatched_measurements: Vec<u8>,
+    errors: Vec<String>,
+}
+
+impl GpuAppraisal {
+    fn passed(&self) -> bool {
+        self.cert_chain_valid
+            && self.report_parsed
+            && self.nonce_match
+            && self.signature_verified
+            && self.rim_fetched
+            && self.measurements_match
+    }
+
+    // into_claims maps the appraisal onto the claims NRAS reports for GPUs
+    fn into_claims(self) -> sj::Map<String, sj::Value> {
+        let mut claims = sj::Map::new();
+        claims.insert(
+            "measres".to_string(),
+            if self.passed() {
+                "comparison-successful"
+            } else {
+                "comparison-fail"
+            }
+            .into(),
+        );
+        claims.insert(
+            "x-nvidia-cert-status".to_string(),
+            if self.cert_chain_valid {
+                "valid"
+            } else {
+                "invalid"
+            }
+            .into(),
+        );
+        claims.insert(
+            "x-nvidia-gpu-attestation-report-parsed".to_string(),
+            self.report_parsed.into(),
+        );
+        claims.insert(
+            "x-nvidia-gpu-attestation-report-nonce-match".to_string(),
+            self.nonce_match.into(),
+        );
+        claims.insert(
+            "x-nvidia-gpu-attestation-report-signature-verified".to_string(),
+            self.signature_verified.into(),
+        );
+        claims.insert(
+            "x-nvidia-gpu-vbios-rim-fetched".to_string(),
+            self.rim_fetched.into(),
+        );
+        if !self.mismatched_measurements.is_empty() {
+            claims.insert(
+                "x-nvidia-mismatch-measurement-records".to_string(),
+                self.mismatched_measurements.into(),
+            );
+        }
+        if !self.errors.is_empty() {
+            claims.insert("x-local-verifier-errors".to_string(), self.errors.into());
+        }
+        claims
+    }
+}
+
+fn appraise_gpu(
+    evidence_certificate: &EvidenceCertificate,
+    nonce: &[u8],
+    roots: &[Vec<u8>],
+    rims: &[ReferenceIntegrityManifest],
+) -> GpuAppraisal {
+    let mut appraisal = GpuAppraisal::default();
+
+    let leaf_public_key = match verify_certificate_chain(&evidence_certificate.certificate, roots) {
+        Ok(public_key) => {
+            appraisal.cert_chain_valid = true;
+            Some(public_key)
+        }
+        Err(e) => {
+            appraisal.errors.push(e);
+            None
+        }
+    };
+
+    let evidence = match STANDARD
+        .decode(&evidence_certificate.evidence)
+        .map_err(|e| format!("evidence is not base64: {}", e))
+        .and_then(|evidence| MeasurementEvidence::parse(&evidence))
+    {
+        Ok(evidence) => {
+            appraisal.report_parsed = true;
+            evidence
+        }
+        Err(e) => {
+            appraisal.errors.push(e);
+            return appraisal;
+        }
+    };
+
+    appraisal.nonce_match = evidence.request_nonce == nonce;
+    if !appraisal.nonce_match {
+        appraisal
+            .errors
+            .push("evidence nonce does not match the requested nonce".to_string());
+    }
+
+    if let Some(public_key) = leaf_public_key {
+        match evidence.verify_signature(&public_key) {
+            Ok(()) => appraisal.signature_verified = true,
+            Err(e) => appraisal.errors.push(e),
+        }
+    }
+
+    match golden_measurements(&evidence_certificate.firmware_version, rims) {
+        Ok(golden_measurements) => {
+            appraisal.rim_fetched = true;
+            appraisal.mismatched_measurements =
+                compare_measurements(&evidence, &golden_measurements);
+            appraisal.measurements_match = appraisal.mismatched_measurements.is_empty();
+        }
+        Err(e) => appraisal.errors.push(e),
+    }
+
+    appraisal
+}
+
+// verify_certificate_chain checks that the device certificate chain,
+// which starts with the leaf, is valid and chains up to one of the
+// roots. It returns the public key of the leaf.
+fn verify_certificate_chain(certificate: &str, roots: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    let pem = STANDARD
+        .decode(certificate)
+        .map_err(|e| format!("device certificate is not base64: {}", e))?;
+    let chain = parse_pem_certificates(&pem)?;
+    let certificates = chain
+        .iter()
+        .map(|der| {
+            X509Certificate::from_der(der)
+                .map(|(_, certificate)| certificate)
+                .map_err(|e| format!("invalid device certificate: {}", e))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let Some(leaf) = certificates.first() else {
+        return Err("device certificate chain is empty".to_string());
+    };
+
+    for (i, certificate) in certificates.iter().enumerate() {
+        if !certificate.validity().is_valid() {
+            return Err(format!(
+                "certificate {} is not within its validity period",
+                certificate.subject()
+            ));
+        }
+        if let Some(issuer) = certificates.get(i + 1) {
+            if !issuer.is_ca() {
+                return Err(format!("certificate {} is not a CA", issuer.subject()));
+            }
+            certificate
+                .verify_signature(Some(issuer.public_key()))
+                .map_err(|e| {
+                    format!(
+                        "certificate {} is not signed by {}: {}",
+                        certificate.subject(),
+                        issuer.subject(),
+                        e
+                    )
+                })?;
+        }
+    }
+
+    // the chain either ends with one of the roots, or with a
+    // certificate issued by one of them
+    let last_der = &chain[chain.len() - 1];
+    let last = &certificates[certificates.len() - 1];
+    let anchored = roots.iter().any(|root_der| {
+        if root_der == last_der {
+            return true;
+        }
+        X509Certificate::from_der(root_der).is_ok_and(|(_, root)| {
+            root.subject().as_raw() == last.issuer().as_raw()
+                && last.verify_signature(Some(root.public_key())).is_ok()
+        })
+    });
+    if !anchored {
+        return Err(format!(
+            "certificate chain of {} does not chain up to a trusted root",
+            leaf.subject()
+        ));
+    }
+
+    Ok(leaf.public_key().subject_public_key.data.to_vec())
+}
+
+// golden_measurements merges the active measurements of every RIM for
+// the firmware version, by index.
+fn golden_measurements(
+    firmware_version: &str,
+    rims: &[ReferenceIntegrityManifest],
+) -> Result<stdcol::BTreeMap<u32, Vec<String>>, String> {
+    let rims: Vec<&ReferenceIntegrityManifest> = rims
+        .iter()
+        .filter(|rim| rim.version.eq_ignore_ascii_case(firmware_version))
+        .collect();
+    if rims.is_empty() {
+        return Err(format!(
+            "no RIM imported for firmware version {}",
+            firmware_version
+        ));
+    }
+
+    let mut golden_measurements = stdcol::BTreeMap::<u32, Vec<String>>::new();
+    for rim in rims {
+        for measurement in rim.measurements.iter().filter(|m| m.active) {
+            match golden_measurements.get(&measurement.index) {
+                Some(alternatives) if alternatives != &measurement.alternatives => {
+                    return Err(format!(
+                        "RIMs for firmware version {} disagree on measurement {} ({})",
+                        firmware_version, measurement.index, rim.name
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    golden_measurements.insert(measurement.index, measurement.alternatives.clone());
+                }
+            }
+        }
+    }
+    Ok(golden_measurements)
+}
+
+// compare_measurements returns the indexes of the measurement blocks
+// which don't match any of their golden values. RIM measurement
+// indexes are zero based, whereas block indexes start at one.
+fn compare_measurements(
+    evidence: &MeasurementEvidence,
+    golden_measurements: &stdcol::BTreeMap<u32, Vec<String>>,
+) -> Vec<u8> {
+    golden_measurements
+        .iter()
+        .filter_map(|(index, alternatives)| {
+            let block_index = u8::try_from(index + 1).ok()?;
+            let matches = evidence
+                .block(block_index)
+                .is_some_and(|block| alternatives.contains(&hex::encode(&block.value)));
+            (!matches).then_some(block_index)
+        })
+        .collect()
+}
+
+// expected_nonce turns the nonce of the request, which is a UUID, into
+// the 32 byte nonce the device was asked to sign.
+fn expected_nonce(nonce: &str) -> Result<Vec<u8>, NrasError> {
+    let mut bytes = hex::decode(nonce.replace('-', ""))
+        .map_err(|e| NrasError::Serde(format!("Error decoding nonce {}: {}", nonce, e)))?;
+    if bytes.len() > NONCE_LEN {
+        return Err(NrasError::Serde(format!(
+            "Error decoding nonce {}: longer than {} bytes",
+            nonce, NONCE_LEN
+        )));
+    }
+    bytes.resize(NONCE_LEN, 0);
+    Ok(bytes)
+}
+
+// load_root_certificates reads the DER encoding of the configured roots
+fn load_root_certificates(config: &LocalVerifierConfig) -> Result<Vec<Vec<u8>>, NrasError> {
+    let mut roots = Vec::new();
+    for path in config.root_certificates.iter() {
+        let pem = std::fs::read(path).map_err(|e| {
+            NrasError::Configuration(format!(
+                "Error reading root certificate {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        roots.extend(parse_pem_certificates(&pem).map_err(|e| {
+            NrasError::Configuration(format!(
+                "Error parsing root certificate {}: {}",
+                path.display(),
+                e
+            ))
+        })?);
+    }
+    if roots.is_empty() {
+        return Err(NrasError::Configuration(
+            "No root certificates configured".to_string(),
+        ));
+    }
+    Ok(roots)
+}
+
+fn parse_pem_certificates(pem: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    Pem::iter_from_buffer(pem)
+        .map(|pem| {
+            pem.map(|pem| pem.contents)
+                .map_err(|e| format!("invalid PEM: {}", e))
+        })
+        .collect()
+}
+
+// token_key derives the HMAC key the local verifier tokens are signed
+// with from the configured secret, using HKDF-SHA384. The outcome is
+// stored between state controller runs, so the key has to be the same
+// across restarts and replicas, while not being computable from
+// anything public like the root certificates.
+fn token_key(config: &LocalVerifierConfig) -> Result<Vec<u8>, NrasError> {
+    if config.token_secret.is_empty() {
+        return Err(NrasError::Configuration(
+            "No token secret configured".to_string(),
+        ));
+    }
+    let hkdf = Hkdf::<Sha384>::new(None, config.token_secret.as_bytes());
+    let mut key = [0u8; 48];
+    hkdf.expand(TOKEN_KEY_INFO.as_bytes(), &mut key)
+        .map_err(|e| NrasError::Configuration(format!("HKDF expand failed: {}", e)))?;
+    Ok(key.to_vec())
+}
+
+fn sign_token(
+    mut claims: sj::Map<String, sj::Value>,
+    now: u64,
+    signing_key: &jst::EncodingKey,
+) -> Result<String, NrasError> {
+    claims.insert("iss".to_string(), LOCAL_VERIFIER_KID.into());
+    claims.insert("iat".to_string(), now.into());
+    claims.insert("nbf".to_string(), now.into());
+    claims.insert("exp".to_string(), (now + TOKEN_LIFETIME_SECS).into());
+
+    let mut header = jst::Header::new(TOKEN_ALGORITHM);
+    header.kid = Some(LOCAL_VERIFIER_KID.to_string());
+    jst::encode(&header, &claims, signing_key)
+        .map_err(|e| NrasError::Jwt(format!("Error signing local verifier token: {}", e)))
+}
@@ -0,0 +1,171 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Parsing of Reference Integrity Manifests (RIMs), the SWID tags which
+// list the golden measurements of a firmware version.
+//
+// RIMs are trusted by virtue of having been imported by the operator;
+// their XML signatures are not checked.
+
+use std::collections as stdcol;
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+
+use crate::NrasError;
+
+#[derive(Debug)]
+pub(crate) struct GoldenMeasurement {
+    pub index: u32,
+    pub active: bool,
+    // the hex encoded values the measurement may have
+    pub alternatives: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ReferenceIntegrityManifest {
+    pub name: String,
+    pub version: String,
+    pub measurements: Vec<GoldenMeasurement>,
+}
+
+impl ReferenceIntegrityManifest {
+    pub fn parse(xml: &str) -> Result<ReferenceIntegrityManifest, String> {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        let mut identity: Option<(String, String)> = None;
+        let mut measurements = Vec::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(element)) | Ok(Event::Empty(element)) => {
+                    match element.local_name().as_ref() {
+                        b"SoftwareIdentity" => {
+                            let attributes = attributes(&element)?;
+                            identity = Some((
+                                attributes.get("name").cloned().unwrap_or_default(),
+                                attributes
+                                    .get("version")
+                                    .cloned()
+                                    .ok_or_else(|| "SoftwareIdentity has no version".to_string())?,
+                            ));
+                        }
+                        b"Resource" => {
+                            let attributes = attributes(&element)?;
+                            if attributes.get("type").map(String::as_str) == Some("Measurement") {
+                                measurements.push(golden_measurement(&attributes)?);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => return Err(format!("invalid XML: {}", e)),
+            }
+        }
+
+        let (name, version) = identity.ok_or_else(|| "no SoftwareIdentity found".to_string())?;
+        Ok(ReferenceIntegrityManifest {
+            name,
+            version,
+            measurements,
+        })
+    }
+
+    // load_directory loads every RIM in a directory
+    pub fn load_directory(dir: &Path) -> Result<Vec<ReferenceIntegrityManifest>, NrasError> {
+        let entries = std::fs::read_dir(dir).map_err(|e| {
+            NrasError::Configuration(format!(
+                "Error reading RIM directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let mut rims = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| {
+                    NrasError::Configuration(format!(
+                        "Error reading RIM directory {}: {}",
+                        dir.display(),
+                        e
+                    ))
+                })?
+                .path();
+            if !path.is_file() {
+                continue;
+            }
+            let xml = std::fs::read_to_string(&path).map_err(|e| {
+                NrasError::Configuration(format!("Error reading RIM {}: {}", path.display(), e))
+            })?;
+            let rim = ReferenceIntegrityManifest::parse(&xml).map_err(|e| {
+                NrasError::Configuration(format!("Error parsing RIM {}: {}", path.display(), e))
+            })?;
+            rims.push(rim);
+        }
+        Ok(rims)
+    }
+}
+
+// attributes returns the attributes of an element by their local
+// name, e.g. "Hash0" for "SHA384:Hash0".
+fn attributes(element: &BytesStart<'_>) -> Result<stdcol::HashMap<String, String>, String> {
+    element
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.map_err(|e| format!("invalid XML attribute: {}", e))?;
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| format!("invalid XML attribute value: {}", e))?
+                .into_owned();
+            Ok((key, value))
+        })
+        .collect()
+}
+
+fn golden_measurement(
+    attributes: &stdcol::HashMap<String, String>,
+) -> Result<GoldenMeasurement, String> {
+    let index = attributes
+        .get("index")
+        .and_then(|index| index.parse::<u32>().ok())
+        .ok_or_else(|| "measurement has no valid index".to_string())?;
+    let active = attributes
+        .get("active")
+        .is_some_and(|active| active.eq_ignore_ascii_case("true"));
+    let alternative_count = attributes
+        .get("alternatives")
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(1);
+
+    let alternatives = (0..alternative_count)
+        .map(|n| {
+            attributes
+                .get(&format!("Hash{}", n))
+                .map(|hash| hash.to_lowercase())
+                .ok_or_else(|| format!("measurement {} is missing Hash{}", index, n))
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+
+    Ok(GoldenMeasurement {
+        index,
+        active,
+        alternatives,
+    })
+}
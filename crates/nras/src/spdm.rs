@@ -0,0 +1,182 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Parsing of the SPDM evidence collected from devices through the BMC,
+// which is the GET_MEASUREMENTS request followed by the signed
+// MEASUREMENTS response.
+
+use ring::signature as rs;
+use sha2::{Digest, Sha384};
+
+const GET_MEASUREMENTS: u8 = 0xe0;
+const MEASUREMENTS: u8 = 0x60;
+const SIGNATURE_REQUESTED: u8 = 0x01;
+const NONCE_LEN: usize = 32;
+// r and s of an ECDSA P-384 signature
+const P384_SIGNATURE_LEN: usize = 96;
+const MEASUREMENTS_SIGNING_CONTEXT: &[u8] = b"responder-measurements signing";
+
+#[derive(Debug)]
+pub(crate) struct MeasurementBlock {
+    pub index: u8,
+    pub value: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MeasurementEvidence {
+    pub version: u8,
+    pub request_nonce: Vec<u8>,
+    pub blocks: Vec<MeasurementBlock>,
+    // everything the signature covers, i.e. the request and the
+    // response up to the signature
+    signed_data: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl MeasurementEvidence {
+    pub fn parse(evidence: &[u8]) -> Result<MeasurementEvidence, String> {
+        let mut reader = Reader::new(evidence);
+
+        // GET_MEASUREMENTS request
+        let version = reader.u8()?;
+        if reader.u8()? != GET_MEASUREMENTS {
+            return Err("evidence does not start with a GET_MEASUREMENTS request".to_string());
+        }
+        let attributes = reader.u8()?;
+        let _operation = reader.u8()?;
+        if attributes & SIGNATURE_REQUESTED == 0 {
+            return Err("GET_MEASUREMENTS request did not ask for a signature".to_string());
+        }
+        let request_nonce = reader.bytes(NONCE_LEN)?.to_vec();
+        if version >= 0x11 {
+            let _slot_id = reader.u8()?;
+        }
+
+        // MEASUREMENTS response
+        if reader.u8()? != version {
+            return Err("MEASUREMENTS response version does not match the request".to_string());
+        }
+        if reader.u8()? != MEASUREMENTS {
+            return Err("evidence does not contain a MEASUREMENTS response".to_string());
+        }
+        let _param1 = reader.u8()?;
+        let _param2 = reader.u8()?;
+        let block_count = reader.u8()?;
+        let record_len = reader.u24()?;
+        let mut record = Reader::new(reader.bytes(record_len)?);
+        let mut blocks = Vec::new();
+        for _ in 0..block_count {
+            blocks.push(read_measurement_block(&mut record)?);
+        }
+        let _response_nonce = reader.bytes(NONCE_LEN)?;
+        let opaque_len = reader.u16()? as usize;
+        let _opaque_data = reader.bytes(opaque_len)?;
+        if version >= 0x13 {
+            let _requester_context = reader.bytes(8)?;
+        }
+
+        let signed_len = reader.offset;
+        Ok(MeasurementEvidence {
+            version,
+            request_nonce,
+            blocks,
+            signed_data: evidence[..signed_len].to_vec(),
+            signature: evidence[signed_len..].to_vec(),
+        })
+    }
+
+    pub fn block(&self, index: u8) -> Option<&MeasurementBlock> {
+        self.blocks.iter().find(|block| block.index == index)
+    }
+
+    // verify_signature checks the ECDSA P-384 signature of the response
+    // against the public key of the device certificate.
+    pub fn verify_signature(&self, public_key: &[u8]) -> Result<(), String> {
+        if self.signature.len() != P384_SIGNATURE_LEN {
+            return Err(format!(
+                "unsupported signature length {} (only ECDSA P-384 is supported)",
+                self.signature.len()
+            ));
+        }
+
+        // SPDM 1.2 and later sign the hash of the transcript, prefixed
+        // with what's being signed, rather than the transcript itself.
+        let message = if self.version >= 0x12 {
+            let spdm_prefix = format!("dmtf-spdm-v{}.{}.*", self.version >> 4, self.version & 0x0f);
+            let mut message = spdm_prefix.repeat(4).into_bytes();
+            message.resize(message.len() + 36 - MEASUREMENTS_SIGNING_CONTEXT.len(), 0);
+            message.extend(MEASUREMENTS_SIGNING_CONTEXT);
+            message.extend(Sha384::digest(&self.signed_data));
+            message
+        } else {
+            self.signed_data.clone()
+        };
+
+        rs::UnparsedPublicKey::new(&rs::ECDSA_P384_SHA384_FIXED, public_key)
+            .verify(&message, &self.signature)
+            .map_err(|_| "measurement signature does not verify".to_string())
+    }
+}
+
+// read_measurement_block reads a measurement block, which holds a
+// DMTF measurement.
+fn read_measurement_block(reader: &mut Reader<'_>) -> Result<MeasurementBlock, String> {
+    let index = reader.u8()?;
+    let _specification = reader.u8()?;
+    let size = reader.u16()? as usize;
+    let mut measurement = Reader::new(reader.bytes(size)?);
+    let _value_type = measurement.u8()?;
+    let value_size = measurement.u16()? as usize;
+    let value = measurement.bytes(value_size)?.to_vec();
+    Ok(MeasurementBlock { index, value })
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data, offset: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| format!("evidence truncated at offset {}", self.offset))?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u24(&mut self) -> Result<usize, String> {
+        let bytes = self.bytes(3)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) as usize)
+    }
+}
@@ -34,6 +34,7 @@ async fn nras_comm_failure_returns_comm_err() {
         nras_gpu_url_suffix: "invalid_urls_suffix".to_string(),
         nras_jwks_url: "invalid_jwks_url".to_string(),
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
     // execute
     let client = nras::NrasVerifierClient::new_with_config(&config);
@@ -65,6 +66,7 @@ async fn nras_response_parsing_failure_returns_parsing_err() {
         nras_gpu_url_suffix: String::new(),
         nras_jwks_url: String::new(),
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
 
     // execute
@@ -90,6 +92,7 @@ async fn nras_response_not_200_returns_comm_error() {
         nras_gpu_url_suffix: String::new(),
         nras_jwks_url: String::new(),
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
 
     // execute
@@ -117,6 +120,7 @@ async fn keystore_comm_failure_returns_comm_error() {
         nras_gpu_url_suffix: String::new(),
         nras_jwks_url: url,
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
 
     // execute
@@ -145,6 +149,7 @@ async fn keystore_status_code_not_200_returns_comm_error() {
         nras_gpu_url_suffix: String::new(),
         nras_jwks_url: url,
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
 
     // execute
@@ -173,6 +178,7 @@ async fn keystore_parsing_error_returns_jwk_err() {
         nras_gpu_url_suffix: String::new(),
         nras_jwks_url: url,
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
 
     // execute
@@ -200,6 +206,7 @@ fn parser_malformed_jwt_returns_jwt_error() {
 
     let config = nras::Config {
         validate_jwt_expiry: false,
+        local_verifier: None,
         ..Default::default()
     };
 
@@ -230,6 +237,7 @@ fn parser_malformed_devices_jwt_returns_jwt_error() {
 
     let config = nras::Config {
         validate_jwt_expiry: false,
+        local_verifier: None,
         ..Default::default()
     };
 
@@ -260,6 +268,7 @@ fn parser_decoding_key_not_found_returns_decoding_key_error() {
 
     let config = nras::Config {
         validate_jwt_expiry: false,
+        local_verifier: None,
         ..Default::default()
     };
 
@@ -289,6 +298,7 @@ fn parser_submod_has_missing_device_returns_verifier_error() {
 
     let config = nras::Config {
         validate_jwt_expiry: false,
+        local_verifier: None,
         ..Default::default()
     };
 
@@ -328,6 +338,7 @@ async fn happy_path_one_gpu_returns_processed_attestation_outcome() {
         nras_gpu_url_suffix: String::new(),
         nras_jwks_url: url_keystore,
         validate_jwt_expiry: false,
+        local_verifier: None,
     };
 
     let client = nras::NrasVerifierClient::new_with_config(&config);
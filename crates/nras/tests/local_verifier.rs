@@ -0,0 +1,215 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::PathBuf;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use nras::{
+    DeviceAttestationInfo, EvidenceCertificate, LocalKeyStore, LocalVerifierClient, NrasError,
+    ProcessedAttestationOutcome, TokenSecret, VerifierClient,
+};
+
+// the evidence in tests/data was signed over this nonce, and measured
+// with the firmware version of the RIM in tests/data/rims
+const NONCE: &str = "5f4a1c2e-8d3b-4e6f-9a7c-1b2d3e4f5a6b";
+const FIRMWARE_VERSION: &str = "96.00.9F.00.01";
+const GPU_EVIDENCE: &str = include_str!("data/gpu_evidence.b64");
+const GPU_CERT_CHAIN: &str = include_str!("data/gpu_cert_chain.pem");
+
+fn data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name)
+}
+
+fn local_config(root_certificate: &str) -> nras::Config {
+    nras::Config {
+        validate_jwt_expiry: true,
+        local_verifier: Some(nras::LocalVerifierConfig {
+            root_certificates: vec![data_path(root_certificate)],
+            rim_directory: data_path("rims"),
+            token_secret: TokenSecret::new("site-root"),
+        }),
+        ..Default::default()
+    }
+}
+
+fn attestation_info(evidence: &str, firmware_version: &str, nonce: &str) -> DeviceAttestationInfo {
+    DeviceAttestationInfo {
+        ec: vec![EvidenceCertificate {
+            evidence: evidence.trim().to_string(),
+            certificate: nras::certificate_to_base64(GPU_CERT_CHAIN),
+            firmware_version: firmware_version.to_string(),
+        }],
+        nonce: nonce.to_string(),
+        ..Default::default()
+    }
+}
+
+// attest runs the evidence through the local verifier, and then parses
+// the outcome the way the state controller does
+async fn attest(
+    config: &nras::Config,
+    info: &DeviceAttestationInfo,
+) -> Result<ProcessedAttestationOutcome, NrasError> {
+    let client = LocalVerifierClient::new_with_config(config);
+    let raw_outcome = client.attest_gpu(info).await?;
+    let keystore = LocalKeyStore::new_with_config(config)?;
+    nras::Parser::new_with_config(config).parse_attestation_outcome(&raw_outcome, &keystore)
+}
+
+#[tokio::test]
+async fn local_verifier_passes_valid_evidence() {
+    let config = local_config("root_ca.pem");
+    let info = attestation_info(GPU_EVIDENCE, FIRMWARE_VERSION, NONCE);
+
+    let outcome = attest(&config, &info).await.expect("attestation failed");
+
+    assert!(outcome.attestation_passed);
+    let claims = outcome.devices.get("GPU-0").expect("GPU-0 not found");
+    assert_eq!(claims["measres"], "comparison-successful");
+    assert_eq!(claims["x-nvidia-cert-status"], "valid");
+    assert_eq!(
+        claims["x-nvidia-gpu-attestation-report-nonce-match"],
+        "true"
+    );
+    assert_eq!(
+        claims["x-nvidia-gpu-attestation-report-signature-verified"],
+        "true"
+    );
+    assert_eq!(claims["x-nvidia-gpu-vbios-version"], FIRMWARE_VERSION);
+    assert!(!claims.contains_key("x-nvidia-mismatch-measurement-records"));
+}
+
+#[tokio::test]
+async fn local_verifier_rejects_untrusted_root() {
+    let config = local_config("untrusted_root_ca.pem");
+    let info = attestation_info(GPU_EVIDENCE, FIRMWARE_VERSION, NONCE);
+
+    let outcome = attest(&config, &info).await.expect("attestation failed");
+
+    assert!(!outcome.attestation_passed);
+    let claims = &outcome.devices["GPU-0"];
+    assert_eq!(claims["x-nvidia-cert-status"], "invalid");
+    assert_eq!(
+        claims["x-nvidia-gpu-attestation-report-signature-verified"],
+        "false"
+    );
+}
+
+#[tokio::test]
+async fn local_verifier_rejects_tampered_evidence() {
+    let config = local_config("root_ca.pem");
+    // flip a bit of the first measurement value
+    let mut evidence = STANDARD.decode(GPU_EVIDENCE.trim()).unwrap();
+    evidence[60] ^= 0x01;
+    let info = attestation_info(&STANDARD.encode(evidence), FIRMWARE_VERSION, NONCE);
+
+    let outcome = attest(&config, &info).await.expect("attestation failed");
+
+    assert!(!outcome.attestation_passed);
+    let claims = &outcome.devices["GPU-0"];
+    assert_eq!(claims["measres"], "comparison-fail");
+    assert_eq!(
+        claims["x-nvidia-gpu-attestation-report-signature-verified"],
+        "false"
+    );
+    assert_eq!(claims["x-nvidia-mismatch-measurement-records"], "[1]");
+}
+
+#[tokio::test]
+async fn local_verifier_rejects_wrong_nonce() {
+    let config = local_config("root_ca.pem");
+    let info = attestation_info(
+        GPU_EVIDENCE,
+        FIRMWARE_VERSION,
+        "0d9e4c47-6a1b-4f0c-8e2d-3c5b7a9f1e20",
+    );
+
+    let outcome = attest(&config, &info).await.expect("attestation failed");
+
+    assert!(!outcome.attestation_passed);
+    assert_eq!(
+        outcome.devices["GPU-0"]["x-nvidia-gpu-attestation-report-nonce-match"],
+        "false"
+    );
+}
+
+#[tokio::test]
+async fn local_verifier_rejects_firmware_without_rim() {
+    let config = local_config("root_ca.pem");
+    let info = attestation_info(GPU_EVIDENCE, "96.00.9F.00.02", NONCE);
+
+    let outcome = attest(&config, &info).await.expect("attestation failed");
+
+    assert!(!outcome.attestation_passed);
+    assert_eq!(
+        outcome.devices["GPU-0"]["x-nvidia-gpu-vbios-rim-fetched"],
+        "false"
+    );
+}
+
+#[tokio::test]
+async fn local_verifier_missing_root_returns_config_err() {
+    let config = local_config("missing_root_ca.pem");
+    let info = attestation_info(GPU_EVIDENCE, FIRMWARE_VERSION, NONCE);
+
+    let client = LocalVerifierClient::new_with_config(&config);
+    let actual_err = client
+        .attest_gpu(&info)
+        .await
+        .expect_err("Expected NrasError to be returned");
+    assert!(matches!(actual_err, NrasError::Configuration(_)));
+}
+
+#[tokio::test]
+async fn local_verifier_missing_token_secret_returns_config_err() {
+    let mut config = local_config("root_ca.pem");
+    config.local_verifier.as_mut().unwrap().token_secret = TokenSecret::default();
+    let info = attestation_info(GPU_EVIDENCE, FIRMWARE_VERSION, NONCE);
+
+    let client = LocalVerifierClient::new_with_config(&config);
+    let actual_err = client
+        .attest_gpu(&info)
+        .await
+        .expect_err("Expected NrasError to be returned");
+    assert!(matches!(actual_err, NrasError::Configuration(_)));
+
+    let actual_err =
+        LocalKeyStore::new_with_config(&config).expect_err("Expected NrasError to be returned");
+    assert!(matches!(actual_err, NrasError::Configuration(_)));
+}
+
+#[tokio::test]
+async fn local_verifier_rejects_tokens_signed_with_other_secret() {
+    let config = local_config("root_ca.pem");
+    let info = attestation_info(GPU_EVIDENCE, FIRMWARE_VERSION, NONCE);
+    let raw_outcome = LocalVerifierClient::new_with_config(&config)
+        .attest_gpu(&info)
+        .await
+        .expect("attestation failed");
+
+    // same roots, but another site
+    let mut other_config = config.clone();
+    other_config.local_verifier.as_mut().unwrap().token_secret = TokenSecret::new("other-root");
+    let keystore = LocalKeyStore::new_with_config(&other_config).unwrap();
+
+    nras::Parser::new_with_config(&other_config)
+        .parse_attestation_outcome(&raw_outcome, &keystore)
+        .expect_err("Expected NrasError to be returned");
+}
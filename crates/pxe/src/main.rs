@@ -104,6 +104,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .merge(routes::ipxe::get_router("/api/v0/pxe"))
         .merge(routes::cloud_init::get_router("/api/v0/cloud-init"))
         .merge(routes::tls::get_router("/api/v0/tls"))
+        .merge(routes::os_image::get_router("/api/v0/os-image"))
         .route_layer(axum::middleware::from_fn(middleware::logging::logger))
         .layer(map_response(middleware::fix_content_length_header))
         .layer(middleware::metrics::MetricLayer::default())
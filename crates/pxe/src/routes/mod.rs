@@ -14,6 +14,8 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::net::IpAddr;
+
 use ::rpc::forge as rpc;
 use ::rpc::forge_tls_client::{self, ApiConfig, ForgeClientConfig};
 use carbide_uuid::machine::MachineInterfaceId;
@@ -21,6 +23,7 @@ use carbide_uuid::machine::MachineInterfaceId;
 pub(crate) mod cloud_init;
 pub(crate) mod ipxe;
 pub(crate) mod metrics;
+pub(crate) mod os_image;
 pub(crate) mod tls;
 
 pub struct RpcContext;
@@ -52,4 +55,26 @@ impl RpcContext {
                 )
             })
     }
+
+    async fn get_os_image_content(
+        token: String,
+        client_ip: IpAddr,
+        offset: u64,
+        url: &str,
+        client_config: &ForgeClientConfig,
+    ) -> Result<tonic::Streaming<rpc::OsImageContentChunk>, tonic::Status> {
+        let api_config = ApiConfig::new(url, client_config);
+        let mut client = forge_tls_client::ForgeTlsClient::retry_build(&api_config)
+            .await
+            .map_err(|err| tonic::Status::unavailable(err.to_string()))?;
+        let request = tonic::Request::new(rpc::GetOsImageContentRequest {
+            token,
+            client_ip: client_ip.to_string(),
+            offset,
+        });
+        client
+            .get_os_image_content(request)
+            .await
+            .map(|response| response.into_inner())
+    }
 }
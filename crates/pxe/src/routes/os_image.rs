@@ -0,0 +1,244 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::Router;
+use axum::body::{Body, Bytes};
+use axum::extract::{Path as UrlPath, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum_client_ip::ClientIp;
+use forge_tls::client_config::ClientCert;
+use http_body::Frame;
+use rpc::forge::OsImageContentChunk;
+use rpc::forge_tls_client::ForgeClientConfig;
+use tokio::sync::mpsc;
+
+use crate::common::AppState;
+use crate::routes::RpcContext;
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    eprintln!("{message}");
+    (status, message).into_response()
+}
+
+// Hosts resuming an interrupted download ask for the rest of the image with
+// `bytes=N-`. Any other range is answered with the whole image.
+fn requested_offset(headers: &HeaderMap) -> u64 {
+    headers
+        .get(header::RANGE)
+        .and_then(|range| range.to_str().ok())
+        .and_then(|range| range.strip_prefix("bytes="))
+        .and_then(|range| range.strip_suffix('-'))
+        .and_then(|offset| offset.parse().ok())
+        .unwrap_or(0)
+}
+
+fn image_response(offset: u64, total_size: Option<u64>, body: Body) -> Response {
+    let mut response = Response::builder()
+        .header(
+            header::CONTENT_TYPE,
+            mime::APPLICATION_OCTET_STREAM.as_ref(),
+        )
+        .header(header::ACCEPT_RANGES, "bytes");
+    match total_size {
+        Some(total_size) if offset > 0 && offset >= total_size => {
+            return error_response(
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                format!("Offset {offset} is beyond the end of the OS image"),
+            );
+        }
+        Some(total_size) => {
+            response = response.header(header::CONTENT_LENGTH, total_size - offset);
+            if offset > 0 {
+                response = response.status(StatusCode::PARTIAL_CONTENT).header(
+                    header::CONTENT_RANGE,
+                    format!("bytes {offset}-{}/{total_size}", total_size - 1),
+                );
+            }
+        }
+        None if offset > 0 => {
+            return error_response(
+                StatusCode::BAD_GATEWAY,
+                "Size of OS image is unknown, download can't be resumed".to_string(),
+            );
+        }
+        None => {}
+    }
+    response.body(body).unwrap_or_else(|err| {
+        error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Error building OS image response: {err}"),
+        )
+    })
+}
+
+/// Response body which passes the image through as carbide-api streams it
+struct ImageBody {
+    chunks: mpsc::Receiver<Result<Bytes, tonic::Status>>,
+}
+
+impl ImageBody {
+    fn new(mut content: tonic::Streaming<OsImageContentChunk>) -> Self {
+        // Only a couple of chunks are buffered, so that a slow host applies
+        // backpressure to carbide-api
+        let (sender, chunks) = mpsc::channel(2);
+        tokio::spawn(async move {
+            loop {
+                let chunk = match content.message().await {
+                    Ok(Some(chunk)) => Ok(Bytes::from(chunk.data)),
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let failed = chunk.is_err();
+                if sender.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self { chunks }
+    }
+}
+
+impl http_body::Body for ImageBody {
+    type Data = Bytes;
+    type Error = tonic::Status;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.chunks
+            .poll_recv(cx)
+            .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+}
+
+// The URL is handed out by carbide-api as part of the PXE instructions for
+// hosts being provisioned with an OS image. The file name is only there so
+// that the downloaded file gets a meaningful name.
+async fn download(
+    UrlPath((token, _file_name)): UrlPath<(String, String)>,
+    ClientIp(client_ip): ClientIp,
+    headers: HeaderMap,
+    state: State<AppState>,
+) -> Response {
+    let offset = requested_offset(&headers);
+    let content = RpcContext::get_os_image_content(
+        token,
+        client_ip,
+        offset,
+        &state.runtime_config.internal_api_url,
+        &ForgeClientConfig::new(
+            state.runtime_config.forge_root_ca_path.clone(),
+            Some(ClientCert {
+                cert_path: state.runtime_config.server_cert_path.clone(),
+                key_path: state.runtime_config.server_key_path.clone(),
+            }),
+        ),
+    )
+    .await;
+
+    // The first chunk carries the size of the image and no data
+    let (content, total_size) = match content {
+        Ok(mut content) => match content.message().await {
+            Ok(first) => (content, first.and_then(|chunk| chunk.total_size)),
+            Err(status) => return rpc_error_response(client_ip, status),
+        },
+        Err(status) => return rpc_error_response(client_ip, status),
+    };
+
+    image_response(offset, total_size, Body::new(ImageBody::new(content)))
+}
+
+fn rpc_error_response(client_ip: std::net::IpAddr, status: tonic::Status) -> Response {
+    let status_code = match status.code() {
+        tonic::Code::PermissionDenied | tonic::Code::Unauthenticated => StatusCode::FORBIDDEN,
+        tonic::Code::NotFound => StatusCode::NOT_FOUND,
+        tonic::Code::InvalidArgument => StatusCode::BAD_REQUEST,
+        tonic::Code::OutOfRange => StatusCode::RANGE_NOT_SATISFIABLE,
+        _ => StatusCode::BAD_GATEWAY,
+    };
+    error_response(
+        status_code,
+        format!(
+            "Error downloading OS image for {client_ip}: {}",
+            status.message()
+        ),
+    )
+}
+
+pub fn get_router(path_prefix: &str) -> Router<AppState> {
+    Router::new().route(
+        format!("{}/{}", path_prefix, "{token}/{file_name}").as_str(),
+        get(download),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested_offset() {
+        let range = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RANGE, value.parse().unwrap());
+            headers
+        };
+
+        assert_eq!(requested_offset(&HeaderMap::new()), 0);
+        assert_eq!(requested_offset(&range("bytes=4-")), 4);
+        assert_eq!(requested_offset(&range("bytes=0-")), 0);
+        // Only open ended ranges are used for resuming
+        assert_eq!(requested_offset(&range("bytes=4-8")), 0);
+        assert_eq!(requested_offset(&range("bytes=-4")), 0);
+        assert_eq!(requested_offset(&range("items=4-")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_image_response() {
+        let response = image_response(0, Some(10), Body::from("0123456789"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "10");
+        assert!(!response.headers().contains_key(header::CONTENT_RANGE));
+
+        // Resuming a download
+        let response = image_response(4, Some(10), Body::from("456789"));
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "6");
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 4-9/10");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"456789");
+
+        let response = image_response(0, None, Body::from("0123456789"));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(header::CONTENT_LENGTH));
+
+        let response = image_response(4, None, Body::from("456789"));
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+
+        let response = image_response(10, Some(10), Body::empty());
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    }
+}
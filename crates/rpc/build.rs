@@ -575,6 +575,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.NetworkSecurityGroupPropagationObjectStatus",
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            "forge.NetworkSecurityGroupFlowRuleSource",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.ExplainNetworkSecurityGroupFlowResponse",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
         .type_attribute("Sku", "#[derive(serde::Serialize, serde::Deserialize)]")
         .field_attribute("Sku.schema_version", "#[serde(default)]")
         .field_attribute("Sku.associated_machine_ids", "#[serde(default)]")
@@ -742,6 +750,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.RouteServer",
             "#[derive(serde::Serialize)]",
         )
+        .type_attribute("forge.AuditLogEntries", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.AuditLogEntry", "#[derive(serde::Serialize)]")
         .build_server(true)
         .build_client(true)
         .protoc_arg("--experimental_allow_proto3_optional")
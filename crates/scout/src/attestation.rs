@@ -16,7 +16,6 @@
  */
 
 use std::ffi::CString;
-use std::process::Command;
 use std::str::FromStr;
 use std::vec::Vec;
 
@@ -339,28 +338,18 @@ pub(crate) fn create_quote_request(
     Ok(request)
 }
 
-pub(crate) fn get_tpm_eventlog() -> Option<Vec<u8>> {
-    let output_res = Command::new("sh")
-        .arg("-c")
-        .arg("tpm2_eventlog /sys/kernel/security/tpm0/binary_bios_measurements")
-        .output();
+const TPM_EVENTLOG_PATH: &str = "/sys/kernel/security/tpm0/binary_bios_measurements";
 
-    let output = match output_res {
-        Ok(output) => output,
+/// get_tpm_eventlog reads the raw binary TCG event log exposed by the
+/// kernel. The API parses and replays it natively, so there's no need
+/// to shell out to tpm2_eventlog here.
+pub(crate) fn get_tpm_eventlog() -> Option<Vec<u8>> {
+    match std::fs::read(TPM_EVENTLOG_PATH) {
+        Ok(eventlog) => Some(eventlog),
         Err(e) => {
-            tracing::error!("Could not retrieve TPM Event Log {0}", e.to_string());
-            return None;
+            tracing::error!("Could not retrieve TPM Event Log from {TPM_EVENTLOG_PATH}: {e}");
+            None
         }
-    };
-
-    if !output.status.success() {
-        tracing::error!(
-            "Error retrieving TPM Event Log: {0}",
-            String::from_utf8(output.stderr).unwrap_or("<could not parse stderr log>".to_string())
-        );
-        None
-    } else {
-        Some(output.stdout)
     }
 }
 
@@ -506,12 +506,16 @@ async fn handle_mlxreport_action(
             OpCode::Noop => (),
             OpCode::Lock { key } => match mlx_device::lock_device(&dev_pci_name, &key) {
                 Ok(()) => {
+                    let observed_variables = mlx_device::query_observed_variables(&dev);
                     let obs = MlxObservation {
                         device_info: Some(dev.into()),
                         lock_status: Some(LockStatus::Locked.into()),
                         profile_name: None,
                         profile_synced: None,
                         firmware_report: None,
+                        profile_version: None,
+                        profile_variables_changed: None,
+                        observed_variables,
                     };
                     report.observations.push(obs);
                 }
@@ -558,12 +562,16 @@ async fn handle_mlxreport_action(
                     }
                 };
 
+                let observed_variables = mlx_device::query_observed_variables(&dev);
                 let obs = MlxObservation {
                     device_info: Some(dev.into()),
                     lock_status: None,
                     profile_name: None,
                     profile_synced: None,
                     firmware_report,
+                    profile_version: None,
+                    profile_variables_changed: None,
+                    observed_variables,
                 };
                 report.observations.push(obs);
             }
@@ -571,23 +579,73 @@ async fn handle_mlxreport_action(
                 // XXX TODO XXX
                 // Call appropriate mlx routine to apply profile and handle errors
                 // XXX TODO XXX
+                let observed_variables = mlx_device::query_observed_variables(&dev);
                 let obs = MlxObservation {
                     device_info: Some(dev.into()),
                     lock_status: None,
                     profile_name: Some(profile_str),
                     profile_synced: Some(true),
                     firmware_report: None,
+                    profile_version: None,
+                    profile_variables_changed: None,
+                    observed_variables,
+                };
+                report.observations.push(obs);
+            }
+            // SyncProfile syncs the mlxconfig profile bound to this host. A failed
+            // sync is still reported (with profile_synced=false), so carbide-api can
+            // raise a drift alert for the device. Devices the profile doesn't apply
+            // to are skipped without an observation.
+            OpCode::SyncProfile { profile, version } => {
+                let profile_name = profile.name.clone();
+                let (profile_synced, variables_changed) =
+                    match mlx_device::sync_stored_profile(&dev, *profile) {
+                        Ok(Some(variables_changed)) => (true, Some(variables_changed)),
+                        Ok(None) => {
+                            // Nothing in the profile applies to this device, which
+                            // leaves it trivially in sync.
+                            tracing::info!(
+                                device = %dev_pci_name,
+                                %profile_name,
+                                "device not supported by profile registry, nothing to sync"
+                            );
+                            (true, Some(0))
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                device = %dev_pci_name,
+                                %profile_name,
+                                "failed to sync mlxconfig profile: {e}"
+                            );
+                            (false, None)
+                        }
+                    };
+
+                let obs = MlxObservation {
+                    device_info: Some(dev.into()),
+                    lock_status: None,
+                    profile_name: Some(profile_name),
+                    profile_synced: Some(profile_synced),
+                    firmware_report: None,
+                    profile_version: Some(version),
+                    profile_variables_changed: variables_changed,
+                    // The sync result is all carbide-api needs here
+                    observed_variables: Vec::new(),
                 };
                 report.observations.push(obs);
             }
             OpCode::Unlock { key } => match mlx_device::unlock_device(&dev_pci_name, &key) {
                 Ok(()) => {
+                    let observed_variables = mlx_device::query_observed_variables(&dev);
                     let obs = MlxObservation {
                         device_info: Some(dev.into()),
                         lock_status: Some(LockStatus::Unlocked.into()),
                         profile_name: None,
                         profile_synced: None,
                         firmware_report: None,
+                        profile_version: None,
+                        profile_variables_changed: None,
+                        observed_variables,
                     };
                     report.observations.push(obs);
                 }
@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 
+use std::collections::HashSet;
+
 use ::rpc::protos::mlx_device::{
     FirmwareFlashReport as FirmwareFlashReportPb, MlxDeviceReport as MlxDeviceReportPb,
     PublishMlxDeviceReportRequest, PublishMlxDeviceReportResponse,
@@ -22,6 +24,7 @@ use ::rpc::protos::mlx_device::{
 };
 use carbide_uuid::machine::MachineId;
 use libmlx::device::discovery;
+use libmlx::device::info::MlxDeviceInfo;
 use libmlx::device::report::MlxDeviceReport;
 use libmlx::firmware::config::FirmwareFlasherProfile;
 use libmlx::firmware::flasher::FirmwareFlasher;
@@ -110,6 +113,77 @@ pub fn unlock_device(device_address: &str, key: &str) -> MlxResult<()> {
     Ok(())
 }
 
+// sync_stored_profile syncs a profile that carbide-api sent down as part of
+// an MlxAction to the given device. Devices which aren't supported by the
+// registry backing the profile are left alone, and Ok(None) is returned,
+// since there is nothing to report for them. Otherwise the number of variables
+// which only take their profile value after a reboot is returned: the ones the
+// sync changed, and the ones with an earlier change that is still pending.
+pub fn sync_stored_profile(
+    device_info: &MlxDeviceInfo,
+    serializable_profile: SerializableProfile,
+) -> Result<Option<u32>, MlxProfileError> {
+    let profile = serializable_profile.into_profile()?;
+    if !profile.registry.matches_device(device_info) {
+        return Ok(None);
+    }
+
+    let sync_result = profile.sync(&device_info.pci_name, None)?;
+    let pending_reboot = profile
+        .config_values
+        .iter()
+        .filter(|desired| {
+            sync_result
+                .changes_applied
+                .iter()
+                .any(|change| change.variable_name == desired.name())
+                || sync_result
+                    .query_result
+                    .get_variable(desired.name())
+                    .is_some_and(|queried| queried.current_value.value != desired.value)
+        })
+        .count();
+    Ok(Some(pending_reboot as u32))
+}
+
+// query_observed_variables queries the mlxconfig variables of every registry
+// supporting the device, so carbide-api can compare the current and pending
+// values against the profile bound to the host. Registries which fail to be
+// queried are logged and skipped.
+pub fn query_observed_variables(
+    device_info: &MlxDeviceInfo,
+) -> Vec<mlx_device_pb::QueriedVariable> {
+    let mut seen = HashSet::new();
+    let mut observed = Vec::new();
+    for registry in registries::get_registries_for_device(device_info) {
+        let runner = MlxConfigRunner::new(device_info.pci_name.clone(), registry.clone());
+        let query_result = match runner.query_all() {
+            Ok(query_result) => query_result,
+            Err(e) => {
+                tracing::warn!(
+                    device = %device_info.pci_name,
+                    registry_name = %registry.name,
+                    "failed to query mlxconfig variables: {e}"
+                );
+                continue;
+            }
+        };
+        for variable in query_result.variables {
+            if !seen.insert(variable.name().to_string()) {
+                continue;
+            }
+            match variable.try_into() {
+                Ok(variable_pb) => observed.push(variable_pb),
+                Err(e) => tracing::warn!(
+                    device = %device_info.pci_name,
+                    "failed to serialize mlxconfig variable: {e}"
+                ),
+            }
+        }
+    }
+    observed
+}
+
 pub fn handle_profile_sync(
     request: mlx_device_pb::MlxDeviceProfileSyncRequest,
 ) -> mlx_device_pb::MlxDeviceProfileSyncResponse {
@@ -24,6 +24,7 @@ use carbide_uuid::machine::MachineId;
 use chrono::{DateTime, Utc};
 use futures_util::FutureExt;
 use opentelemetry::KeyValue;
+use rpc::forge_api_client::ForgeApiClient;
 use russh::ChannelMsg;
 use tokio::net::TcpStream;
 use tokio::sync::{MutexGuard, broadcast, mpsc, oneshot};
@@ -37,6 +38,7 @@ use crate::bmc::message_proxy::{
 };
 use crate::config::Config;
 use crate::console_logger;
+use crate::console_monitor::{self, ConsoleRules};
 use crate::shutdown_handle::ShutdownHandle;
 use crate::ssh_server::ServerMetrics;
 
@@ -46,6 +48,8 @@ use crate::ssh_server::ServerMetrics;
 pub fn spawn(
     connection_details: ConnectionDetails,
     config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    console_rules: Option<Arc<ConsoleRules>>,
     metrics: Arc<BmcPoolMetrics>,
 ) -> ClientHandle {
     // Shutdown handle for the retry loop that is retrying this connection
@@ -72,6 +76,8 @@ pub fn spawn(
     let bmc_client = BmcClient {
         connection_details,
         config,
+        forge_api_client,
+        console_rules,
         connection_state: connection_state.clone(),
         broadcast_to_frontend_tx: broadcast_to_frontend_tx.clone(),
         shutdown_rx,
@@ -95,6 +101,8 @@ pub fn spawn(
 struct BmcClient {
     connection_details: ConnectionDetails,
     config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    console_rules: Option<Arc<ConsoleRules>>,
     connection_state: Arc<AtomicConnectionState>,
     shutdown_rx: oneshot::Receiver<()>,
     broadcast_to_frontend_tx: broadcast::Sender<ToFrontendMessage>,
@@ -119,6 +127,17 @@ impl BmcClient {
             None
         };
 
+        // And to watch it for health problems, if configured.
+        let monitor_handle = self.console_rules.clone().map(|console_rules| {
+            console_monitor::spawn(
+                machine_id,
+                self.broadcast_to_frontend_tx.subscribe(),
+                console_rules,
+                self.config.clone(),
+                self.forge_api_client.clone(),
+            )
+        });
+
         // Keep track of when we were last disconnected, for relaying status
         let last_disconnect_time: Arc<RwLock<Option<DateTime<Utc>>>> = Default::default();
 
@@ -275,11 +294,14 @@ impl BmcClient {
             }
         }
 
-        // Clean up: Shut down message relay and logger
+        // Clean up: Shut down message relay, logger and monitor
         bmc_message_relay.shutdown_and_wait().await;
         if let Some(logger_handle) = logger_handle {
             logger_handle.shutdown_and_wait().await;
         }
+        if let Some(monitor_handle) = monitor_handle {
+            monitor_handle.shutdown_and_wait().await;
+        }
     }
 }
 
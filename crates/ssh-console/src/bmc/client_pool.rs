@@ -36,11 +36,17 @@ use crate::bmc::client_pool::GetConnectionError::InvalidMachineId;
 use crate::bmc::connection::State;
 use crate::bmc::{client, connection};
 use crate::config::Config;
+use crate::console_monitor::ConsoleRules;
 use crate::shutdown_handle::{ReadyHandle, ShutdownHandle};
 use crate::ssh_server::ServerMetrics;
 
 /// Spawn a background task that connects to all BMC's in the environment, reconnecting if they fail.
-pub fn spawn(config: Arc<Config>, forge_api_client: ForgeApiClient, meter: &Meter) -> Handle {
+pub fn spawn(
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    console_rules: Option<Arc<ConsoleRules>>,
+    meter: &Meter,
+) -> Handle {
     let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
     let (ready_tx, ready_rx) = oneshot::channel();
     let members: Arc<RwLock<HashMap<MachineId, ClientHandle>>> = Default::default();
@@ -50,6 +56,7 @@ pub fn spawn(config: Arc<Config>, forge_api_client: ForgeApiClient, meter: &Mete
             shutdown_rx,
             config,
             forge_api_client,
+            console_rules,
             metrics: Arc::new(BmcPoolMetrics::new(meter, members.clone())),
         }
         .run_loop(ready_tx),
@@ -190,6 +197,8 @@ struct BmcPool {
     shutdown_rx: oneshot::Receiver<()>,
     config: Arc<Config>,
     forge_api_client: ForgeApiClient,
+    /// Rules for console health monitoring, if enabled
+    console_rules: Option<Arc<ConsoleRules>>,
     metrics: Arc<BmcPoolMetrics>,
 }
 
@@ -412,6 +421,8 @@ impl BmcPool {
                 let bmc_session_handle = client::spawn(
                     connection_details,
                     self.config.clone(),
+                    self.forge_api_client.clone(),
+                    self.console_rules.clone(),
                     self.metrics.clone(),
                 );
                 guard.insert(machine_id, bmc_session_handle);
@@ -40,6 +40,8 @@ pub struct Config {
     pub listen_address: SocketAddr,
     #[serde(default = "Defaults::metrics_address")]
     pub metrics_address: SocketAddr,
+    #[serde(default)]
+    pub websocket_listen_address: Option<SocketAddr>,
     #[serde(
         rename = "carbide_url",
         default = "Defaults::carbide_uri",
@@ -113,6 +115,34 @@ pub struct Config {
     pub log_rotate_max_size: Size,
     #[serde(default = "Defaults::log_rotate_max_rotated_files")]
     pub log_rotate_max_rotated_files: usize,
+    #[serde(default = "Defaults::session_recording_enabled")]
+    pub session_recording_enabled: bool,
+    #[serde(default = "Defaults::session_recordings_path")]
+    pub session_recordings_path: PathBuf,
+    #[serde(default = "Defaults::session_recording_max_size")]
+    pub session_recording_max_size: Size,
+    #[serde(
+        default = "Defaults::session_recording_retention",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub session_recording_retention: Duration,
+    #[serde(default)]
+    pub console_health_monitoring_enabled: bool,
+    #[serde(default)]
+    pub console_health_rules_path: Option<PathBuf>,
+    #[serde(
+        default = "Defaults::console_health_report_interval",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub console_health_report_interval: Duration,
+    #[serde(
+        default = "Defaults::console_health_alert_expiry",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub console_health_alert_expiry: Duration,
     #[serde(default = "Defaults::cert_authorization")]
     pub openssh_certificate_authorization: CertAuthorization,
 }
@@ -198,6 +228,7 @@ impl Config {
         let Self {
             listen_address,
             metrics_address,
+            websocket_listen_address: _,
             authorized_keys_path: _,
             override_bmcs: _,
             host_key_path,
@@ -222,6 +253,14 @@ impl Config {
             successful_connection_minimum_duration,
             log_rotate_max_size,
             log_rotate_max_rotated_files,
+            session_recording_enabled,
+            session_recordings_path,
+            session_recording_max_size,
+            session_recording_retention,
+            console_health_monitoring_enabled,
+            console_health_rules_path: _,
+            console_health_report_interval,
+            console_health_alert_expiry,
             openssh_certificate_authorization,
         } = self;
         let api_poll_interval = format!("{}s", api_poll_interval.as_secs());
@@ -237,6 +276,15 @@ impl Config {
             .with_base(size::Base::Base2)
             .with_style(size::Style::Abbreviated)
             .to_string();
+        let session_recording_max_size = session_recording_max_size
+            .format()
+            .with_base(size::Base::Base2)
+            .with_style(size::Style::Abbreviated)
+            .to_string();
+        let session_recording_retention = format!("{}s", session_recording_retention.as_secs());
+        let console_health_report_interval =
+            format!("{}s", console_health_report_interval.as_secs());
+        let console_health_alert_expiry = format!("{}s", console_health_alert_expiry.as_secs());
 
         let cert_authorization_strategy = {
             let mut value = String::new();
@@ -273,6 +321,11 @@ listen_address = {listen_address:?}
 ## Address to listen on for prometheus metrics requests (HTTP)
 metrics_address = {metrics_address:?}
 
+## Optional: Address to listen on for WebSocket console connections (HTTP), used by browser-based
+## consoles. Clients authenticate with a short-lived console access token minted by carbide-api,
+## passed as the `token` query parameter to `/console`. Disabled if unset.
+# websocket_listen_address = "0.0.0.0:8080"
+
 ## Address for carbide-api
 carbide_url = {carbide_uri:?}
 
@@ -351,6 +404,34 @@ log_rotate_max_size = {log_rotate_max_size:?}
 ## When rotating console logs, how many old logs should we keep? (e.g. 3 means we keep .log, .log.0, .log.1, and .log.2)
 log_rotate_max_rotated_files = {log_rotate_max_rotated_files}
 
+## Whether to record each interactive session (output, input and timing) in asciicast v2 format.
+## Recordings are served to carbide-api under /recordings on the websocket_listen_address.
+session_recording_enabled = {session_recording_enabled}
+
+## Where to write session recordings, in a subdirectory per machine_id or instance_id
+session_recordings_path = {session_recordings_path:?}
+
+## Stop recording a session once its recording reaches this size
+session_recording_max_size = {session_recording_max_size:?}
+
+## How long to keep session recordings before deleting them
+session_recording_retention = {session_recording_retention:?}
+
+## Whether to match console output against health rules (kernel panics, MCE/AER errors, GPU Xid
+## errors, UEFI hangs, login prompts), and report matches to carbide-api as health alerts. Reports are
+## recorded through RecordConsoleHealthReport, separately from log-parser's reports.
+console_health_monitoring_enabled = {console_health_monitoring_enabled}
+
+## Optional: TOML file with `[[rules]]` to match instead of the built-in console health rules.
+# console_health_rules_path = <path>
+
+## Minimum time between health reports for a single machine. Matches in between are batched.
+console_health_report_interval = {console_health_report_interval:?}
+
+## How long a console health alert stays raised after its last match, unless a later match (like a
+## login prompt after a kernel panic) clears it sooner.
+console_health_alert_expiry = {console_health_alert_expiry:?}
+
 ## Configure how the role is extracted from an SSH certificate
 [openssh_certificate_authorization]
 ## How should roles be extracted from SSH certs? (Currently supported: "key_id")
@@ -445,6 +526,7 @@ impl Default for Config {
         Self {
             listen_address: Defaults::listen_address(),
             metrics_address: Defaults::metrics_address(),
+            websocket_listen_address: None,
             host_key_path: Defaults::host_key_path(),
             carbide_uri: Defaults::carbide_uri(),
             forge_root_ca_path: Defaults::root_ca_path(),
@@ -457,6 +539,14 @@ impl Default for Config {
                 Defaults::successful_connection_minimum_duration(),
             log_rotate_max_size: Defaults::log_rotate_max_size(),
             log_rotate_max_rotated_files: Defaults::log_rotate_max_rotated_files(),
+            session_recording_enabled: Defaults::session_recording_enabled(),
+            session_recordings_path: Defaults::session_recordings_path(),
+            session_recording_max_size: Defaults::session_recording_max_size(),
+            session_recording_retention: Defaults::session_recording_retention(),
+            console_health_monitoring_enabled: false,
+            console_health_rules_path: None,
+            console_health_report_interval: Defaults::console_health_report_interval(),
+            console_health_alert_expiry: Defaults::console_health_alert_expiry(),
             reconnect_interval_base: Defaults::reconnect_interval_base(),
             reconnect_interval_max: Defaults::reconnect_interval_max(),
             dpus: Defaults::dpus(),
@@ -573,6 +663,30 @@ impl Defaults {
         4
     }
 
+    pub fn session_recording_enabled() -> bool {
+        true
+    }
+
+    pub fn session_recordings_path() -> PathBuf {
+        "/var/log/console-sessions".into()
+    }
+
+    pub fn session_recording_max_size() -> Size {
+        Size::from_mebibytes(100)
+    }
+
+    pub fn session_recording_retention() -> Duration {
+        Duration::from_secs(30 * 24 * 3600)
+    }
+
+    pub fn console_health_report_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn console_health_alert_expiry() -> Duration {
+        Duration::from_secs(24 * 3600)
+    }
+
     pub fn cert_authorization() -> CertAuthorization {
         CertAuthorization {
             strategy: vec![CertAuthorizationStrategy::KeyId],
@@ -685,6 +799,51 @@ mod tests {
         assert_eq!(default, roundtripped);
     }
 
+    #[test]
+    fn test_session_recording_config() {
+        let config = indoc! {r#"
+        session_recordings_path = "/tmp/recordings"
+        session_recording_max_size = "1 MiB"
+        session_recording_retention = "7d"
+        "#};
+
+        let config = toml::from_str::<Config>(config).expect("Couldn't parse config toml");
+        assert!(config.session_recording_enabled);
+        assert_eq!(
+            config.session_recordings_path,
+            PathBuf::from("/tmp/recordings")
+        );
+        assert_eq!(config.session_recording_max_size, Size::from_mebibytes(1));
+        assert_eq!(
+            config.session_recording_retention,
+            Duration::from_secs(7 * 24 * 3600)
+        );
+    }
+
+    #[test]
+    fn test_console_health_config() {
+        let config = indoc! {r#"
+        console_health_monitoring_enabled = true
+        console_health_rules_path = "/etc/ssh-console/console_rules.toml"
+        console_health_report_interval = "5m"
+        "#};
+
+        let config = toml::from_str::<Config>(config).expect("Couldn't parse config toml");
+        assert!(config.console_health_monitoring_enabled);
+        assert_eq!(
+            config.console_health_rules_path,
+            Some(PathBuf::from("/etc/ssh-console/console_rules.toml"))
+        );
+        assert_eq!(
+            config.console_health_report_interval,
+            Duration::from_secs(300)
+        );
+        assert_eq!(
+            config.console_health_alert_expiry,
+            Defaults::console_health_alert_expiry()
+        );
+    }
+
     #[test]
     fn test_authz_partial_config() {
         let partial_config = indoc! {r#"
@@ -0,0 +1,392 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Watches each console's output for notable events (kernel panics, hardware errors, firmware
+//! hangs, login prompts), and reports them to carbide-api as health alerts.
+//!
+//! Reports go through `RecordConsoleHealthReport`, which carbide-api stores separately from
+//! log-parser's report, so both can process the same machine's console. Repeated matches of the
+//! same alert are de-duplicated, and reports for a machine are sent at most once per
+//! `console_health_report_interval`.
+
+mod rules;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, TimeDelta, Utc};
+use health_report::{HealthProbeAlert, HealthProbeId, HealthProbeSuccess, HealthReport};
+use rpc::forge::HardwareHealthReport;
+use rpc::forge_api_client::ForgeApiClient;
+use rules::{ConsoleMatcher, RuleMatch};
+pub use rules::{ConsoleRules, RulesError};
+use russh::ChannelMsg;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+use crate::config::Config;
+use crate::shutdown_handle::ShutdownHandle;
+
+/// Source of the health reports sent by ssh-console
+pub static HEALTH_REPORT_SOURCE: &str = "ssh-console";
+
+/// Matched lines are truncated to this length in alert messages
+const MAX_MESSAGE_LINE_LENGTH: usize = 256;
+
+/// Load the configured rules, or the built-in ones if no rules file is configured.
+pub fn load_rules(config: &Config) -> Result<ConsoleRules, RulesError> {
+    match &config.console_health_rules_path {
+        Some(path) => ConsoleRules::load(path),
+        None => ConsoleRules::compile(rules::default_rules()),
+    }
+}
+
+/// Spawn a background task which matches all output from a BMC against the rules, and reports
+/// matches for the machine to carbide-api.
+pub fn spawn(
+    machine_id: MachineId,
+    message_rx: broadcast::Receiver<ToFrontendMessage>,
+    rules: Arc<ConsoleRules>,
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+) -> ConsoleMonitorHandle {
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let console_monitor = ConsoleMonitor {
+        machine_id,
+        rules,
+        config,
+        forge_api_client,
+    };
+
+    let join_handle = tokio::spawn(console_monitor.run(shutdown_rx, message_rx));
+
+    ConsoleMonitorHandle {
+        shutdown_tx,
+        join_handle,
+    }
+}
+
+pub struct ConsoleMonitorHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for ConsoleMonitorHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+struct ConsoleMonitor {
+    machine_id: MachineId,
+    rules: Arc<ConsoleRules>,
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+}
+
+impl ConsoleMonitor {
+    async fn run(
+        self,
+        mut shutdown_rx: oneshot::Receiver<()>,
+        mut message_rx: broadcast::Receiver<ToFrontendMessage>,
+    ) {
+        let mut matcher = ConsoleMatcher::new(&self.rules);
+        let mut health = MachineHealth::default();
+        let alert_expiry =
+            TimeDelta::from_std(self.config.console_health_alert_expiry).unwrap_or(TimeDelta::MAX);
+
+        // Reports are only sent on ticks, which is what limits how often they're sent.
+        let mut report_interval = tokio::time::interval(
+            self.config
+                .console_health_report_interval
+                .max(Duration::from_secs(1)),
+        );
+        report_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    break;
+                }
+
+                _ = report_interval.tick() => {
+                    health.expire(Utc::now(), alert_expiry);
+                    if let Some(report) = health.take_report(Utc::now())
+                        && let Err(error) = self.send_report(report).await
+                    {
+                        tracing::warn!(machine_id=%self.machine_id, %error, "error sending console health report, will retry");
+                        health.changed = true;
+                    }
+                }
+
+                res = message_rx.recv() => match res {
+                    Ok(msg) => {
+                        let msg = Arc::<ChannelMsg>::from(msg);
+                        if let ChannelMsg::Data { data } = msg.as_ref() {
+                            let now = Utc::now();
+                            for rule_match in matcher.feed(data.as_ref()) {
+                                tracing::debug!(machine_id=%self.machine_id, rule=%rule_match.rule.id, line=rule_match.line, "console output matched health rule");
+                                health.record(&rule_match, now);
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        tracing::warn!(machine_id=%self.machine_id, "console monitor is lagged by {count} messages, matches may be missed");
+                    }
+                },
+            }
+        }
+
+        tracing::debug!(machine_id=%self.machine_id, "shutting down console monitor");
+    }
+
+    async fn send_report(&self, report: HealthReport) -> Result<(), tonic::Status> {
+        self.forge_api_client
+            .record_console_health_report(HardwareHealthReport {
+                machine_id: Some(self.machine_id),
+                report: Some(report.into()),
+            })
+            .await
+    }
+}
+
+type ProbeKey = (HealthProbeId, Option<String>);
+
+/// The health of a single machine, as seen on its console.
+#[derive(Default)]
+struct MachineHealth {
+    alerts: BTreeMap<ProbeKey, ActiveAlert>,
+    successes: BTreeMap<ProbeKey, HealthProbeSuccess>,
+    /// Whether the alerts or successes changed since the last report
+    changed: bool,
+}
+
+struct ActiveAlert {
+    alert: HealthProbeAlert,
+    last_seen: DateTime<Utc>,
+}
+
+impl MachineHealth {
+    fn record(&mut self, rule_match: &RuleMatch, now: DateTime<Utc>) {
+        let rule = rule_match.rule;
+        let len_before = self.alerts.len();
+        self.alerts.retain(|(id, _), _| !rule.clears.contains(id));
+        self.changed |= self.alerts.len() != len_before;
+
+        let key = (rule.id.clone(), rule_match.target.clone());
+        if !rule.alert {
+            self.changed |= self
+                .successes
+                .insert(
+                    key.clone(),
+                    HealthProbeSuccess {
+                        id: rule.id.clone(),
+                        target: rule_match.target.clone(),
+                    },
+                )
+                .is_none();
+            self.changed |= self.alerts.remove(&key).is_some();
+            return;
+        }
+
+        // A repeated alert only keeps the alert raised: It's reported with its first message.
+        if let Some(active) = self.alerts.get_mut(&key) {
+            active.last_seen = now;
+            return;
+        }
+        self.successes.remove(&key);
+        self.alerts.insert(
+            key,
+            ActiveAlert {
+                alert: HealthProbeAlert {
+                    id: rule.id.clone(),
+                    target: rule_match.target.clone(),
+                    in_alert_since: Some(now),
+                    message: format!(
+                        "{}: {}",
+                        rule.message,
+                        truncate(&rule_match.line, MAX_MESSAGE_LINE_LENGTH)
+                    ),
+                    tenant_message: None,
+                    classifications: rule.classifications.clone(),
+                },
+                last_seen: now,
+            },
+        );
+        self.changed = true;
+    }
+
+    /// Drop alerts which haven't matched for longer than `expiry`
+    fn expire(&mut self, now: DateTime<Utc>, expiry: TimeDelta) {
+        let len_before = self.alerts.len();
+        self.alerts
+            .retain(|_, active| now.signed_duration_since(active.last_seen) < expiry);
+        self.changed |= self.alerts.len() != len_before;
+    }
+
+    /// Returns a report of all current alerts and successes, if they changed since the last one.
+    fn take_report(&mut self, now: DateTime<Utc>) -> Option<HealthReport> {
+        if !std::mem::take(&mut self.changed) {
+            return None;
+        }
+        Some(HealthReport {
+            source: HEALTH_REPORT_SOURCE.to_string(),
+            observed_at: Some(now),
+            successes: self.successes.values().cloned().collect(),
+            alerts: self
+                .alerts
+                .values()
+                .map(|active| active.alert.clone())
+                .collect(),
+        })
+    }
+}
+
+fn truncate(line: &str, max_len: usize) -> &str {
+    match line.char_indices().nth(max_len) {
+        Some((index, _)) => &line[..index],
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_log(health: &mut MachineHealth, rules: &ConsoleRules, log: &str, now: DateTime<Utc>) {
+        let mut matcher = ConsoleMatcher::new(rules);
+        for rule_match in matcher.feed(log.as_bytes()) {
+            health.record(&rule_match, now);
+        }
+    }
+
+    fn alert_ids(report: &HealthReport) -> Vec<(String, Option<String>)> {
+        report
+            .alerts
+            .iter()
+            .map(|alert| (alert.id.to_string(), alert.target.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_repeated_alerts_are_deduplicated() {
+        let rules = ConsoleRules::compile(rules::default_rules()).unwrap();
+        let mut health = MachineHealth::default();
+        let start = Utc::now();
+
+        record_log(
+            &mut health,
+            &rules,
+            include_str!("test_data/hardware_errors.log"),
+            start,
+        );
+        let report = health.take_report(start).expect("alerts were raised");
+        assert_eq!(report.source, HEALTH_REPORT_SOURCE);
+        assert_eq!(
+            alert_ids(&report),
+            vec![
+                ("GpuXid".to_string(), Some("0000:3b:00".to_string())),
+                ("MachineCheckException".to_string(), None),
+                ("PcieAerError".to_string(), Some("0000:41:00.0".to_string())),
+            ]
+        );
+        let xid = &report.alerts[0];
+        assert!(xid.message.starts_with("GPU Xid error: "));
+        assert!(xid.message.contains("79, pid=20711"));
+        assert_eq!(xid.in_alert_since, Some(start));
+
+        // Seeing the same errors again doesn't produce another report
+        let later = start + TimeDelta::minutes(5);
+        record_log(
+            &mut health,
+            &rules,
+            include_str!("test_data/hardware_errors.log"),
+            later,
+        );
+        assert!(health.take_report(later).is_none());
+    }
+
+    #[test]
+    fn test_login_prompt_clears_boot_failures() {
+        let rules = ConsoleRules::compile(rules::default_rules()).unwrap();
+        let mut health = MachineHealth::default();
+        let now = Utc::now();
+
+        record_log(
+            &mut health,
+            &rules,
+            include_str!("test_data/kernel_panic.log"),
+            now,
+        );
+        record_log(
+            &mut health,
+            &rules,
+            include_str!("test_data/hardware_errors.log"),
+            now,
+        );
+        let report = health.take_report(now).unwrap();
+        assert!(report.alerts.iter().any(|a| a.id.as_str() == "KernelPanic"));
+
+        record_log(
+            &mut health,
+            &rules,
+            include_str!("test_data/login.log"),
+            now,
+        );
+        let report = health.take_report(now).unwrap();
+        assert!(!report.alerts.iter().any(|a| a.id.as_str() == "KernelPanic"));
+        // Hardware errors aren't cleared by a reboot
+        assert_eq!(report.alerts.len(), 3);
+        assert_eq!(report.successes.len(), 1);
+        assert_eq!(report.successes[0].id.as_str(), "LoginPrompt");
+    }
+
+    #[test]
+    fn test_alerts_expire() {
+        let rules = ConsoleRules::compile(rules::default_rules()).unwrap();
+        let mut health = MachineHealth::default();
+        let now = Utc::now();
+
+        record_log(
+            &mut health,
+            &rules,
+            include_str!("test_data/uefi_hang.log"),
+            now,
+        );
+        assert_eq!(health.take_report(now).unwrap().alerts.len(), 1);
+
+        health.expire(now + TimeDelta::minutes(59), TimeDelta::hours(1));
+        assert!(health.take_report(now).is_none());
+
+        health.expire(now + TimeDelta::hours(1), TimeDelta::hours(1));
+        let report = health.take_report(now).unwrap();
+        assert!(report.alerts.is_empty());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("ééééé", 3), "ééé");
+    }
+}
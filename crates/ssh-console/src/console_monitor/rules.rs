@@ -0,0 +1,383 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Rules which recognize notable events in console output, and the matcher which applies them to a
+//! console's byte stream.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use health_report::{HealthAlertClassification, HealthProbeId};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Lines longer than this are matched (and then discarded) in pieces, so that a console printing
+/// without newlines can't grow the buffer without bound.
+const MAX_LINE_LENGTH: usize = 4096;
+
+/// A rule as written in a rules file. See [`default_rules`] for examples.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsoleRule {
+    /// Health probe ID reported when the rule matches
+    pub id: String,
+    /// Regex matched against each line of console output, with ANSI escapes and the line ending
+    /// removed. If it has a capture group named `target`, its value is used as the alert target.
+    pub pattern: String,
+    /// Alert message. The matching line is appended to it.
+    pub message: String,
+    /// Whether a match raises an alert. Rules which don't are reported as successes, and are
+    /// useful for clearing alerts with `clears`.
+    #[serde(default = "default_alert")]
+    pub alert: bool,
+    /// Whether the pattern is a prompt. Prompts aren't followed by a newline, so they're also
+    /// matched against the incomplete last line.
+    #[serde(default)]
+    pub prompt: bool,
+    /// Classifications for raised alerts
+    #[serde(default)]
+    pub classifications: Vec<String>,
+    /// IDs of rules whose alerts are cleared when this rule matches
+    #[serde(default)]
+    pub clears: Vec<String>,
+}
+
+fn default_alert() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct RulesFile {
+    rules: Vec<ConsoleRule>,
+}
+
+/// Built-in rules, used unless `console_health_rules_path` is configured.
+pub fn default_rules() -> Vec<ConsoleRule> {
+    let hardware = || {
+        vec![
+            "SerialConsole".to_string(),
+            HealthAlertClassification::hardware().to_string(),
+        ]
+    };
+    vec![
+        ConsoleRule {
+            id: "KernelPanic".to_string(),
+            pattern: r"Kernel panic - not syncing".to_string(),
+            message: "Kernel panic".to_string(),
+            alert: true,
+            prompt: false,
+            classifications: vec!["SerialConsole".to_string()],
+            clears: vec![],
+        },
+        ConsoleRule {
+            id: "MachineCheckException".to_string(),
+            pattern: r"mce: \[Hardware Error\]|Machine check events logged".to_string(),
+            message: "Machine check exception".to_string(),
+            alert: true,
+            prompt: false,
+            classifications: hardware(),
+            clears: vec![],
+        },
+        ConsoleRule {
+            id: "PcieAerError".to_string(),
+            pattern: r"AER: (?:Multiple )?Uncorrected \((?:Fatal|Non-Fatal)\) error received: (?P<target>[0-9a-fA-F:.]+)"
+                .to_string(),
+            message: "Uncorrected PCIe AER error".to_string(),
+            alert: true,
+            prompt: false,
+            classifications: hardware(),
+            clears: vec![],
+        },
+        ConsoleRule {
+            id: "GpuXid".to_string(),
+            pattern: r"NVRM: Xid \(PCI:(?P<target>[0-9a-fA-F:.]+)\): \d+".to_string(),
+            message: "GPU Xid error".to_string(),
+            alert: true,
+            prompt: false,
+            classifications: hardware(),
+            clears: vec![],
+        },
+        ConsoleRule {
+            id: "UefiHang".to_string(),
+            pattern: r"!!!! X64 Exception Type|Synchronous Exception at 0x|ASSERT_EFI_ERROR|ASSERT \[\w+\]|No bootable option or device was found"
+                .to_string(),
+            message: "UEFI firmware stopped booting".to_string(),
+            alert: true,
+            prompt: false,
+            classifications: vec!["SerialConsole".to_string()],
+            clears: vec![],
+        },
+        ConsoleRule {
+            id: "LoginPrompt".to_string(),
+            pattern: r"^\S+ login:\s*$".to_string(),
+            message: "Login prompt".to_string(),
+            alert: false,
+            prompt: true,
+            classifications: vec![],
+            // Reaching a login prompt means the machine booted again
+            clears: vec!["KernelPanic".to_string(), "UefiHang".to_string()],
+        },
+    ]
+}
+
+/// A rule, ready for matching
+#[derive(Debug)]
+pub struct CompiledRule {
+    pub id: HealthProbeId,
+    pub message: String,
+    pub alert: bool,
+    pub classifications: Vec<HealthAlertClassification>,
+    pub clears: Vec<HealthProbeId>,
+    prompt: bool,
+    regex: Regex,
+}
+
+/// The set of rules applied to every console
+#[derive(Debug)]
+pub struct ConsoleRules(Vec<CompiledRule>);
+
+impl ConsoleRules {
+    pub fn compile(rules: Vec<ConsoleRule>) -> Result<Self, RulesError> {
+        rules
+            .into_iter()
+            .map(|rule| {
+                let invalid = |error: String| RulesError::InvalidRule {
+                    id: rule.id.clone(),
+                    error,
+                };
+                Ok(CompiledRule {
+                    id: HealthProbeId::from_str(&rule.id).map_err(|e| invalid(e.to_string()))?,
+                    regex: Regex::new(&rule.pattern).map_err(|e| invalid(e.to_string()))?,
+                    classifications: rule
+                        .classifications
+                        .iter()
+                        .map(|c| c.parse::<HealthAlertClassification>())
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| invalid(e.to_string()))?,
+                    clears: rule
+                        .clears
+                        .iter()
+                        .map(|id| id.parse::<HealthProbeId>())
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| invalid(e.to_string()))?,
+                    message: rule.message,
+                    alert: rule.alert,
+                    prompt: rule.prompt,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Load rules from a TOML file with a `[[rules]]` entry per rule
+    pub fn load(path: &Path) -> Result<Self, RulesError> {
+        let contents = std::fs::read_to_string(path).map_err(|error| RulesError::Reading {
+            path: path.display().to_string(),
+            error,
+        })?;
+        let file: RulesFile = toml::from_str(&contents).map_err(|error| RulesError::Parsing {
+            path: path.display().to_string(),
+            error,
+        })?;
+        Self::compile(file.rules)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RulesError {
+    #[error("Error reading console health rules at {path}: {error}")]
+    Reading { path: String, error: std::io::Error },
+    #[error("Error parsing console health rules at {path}: {error}")]
+    Parsing {
+        path: String,
+        error: toml::de::Error,
+    },
+    #[error("Invalid console health rule {id}: {error}")]
+    InvalidRule { id: String, error: String },
+}
+
+/// A line of console output which matched a rule
+#[derive(Debug)]
+pub struct RuleMatch<'a> {
+    pub rule: &'a CompiledRule,
+    pub target: Option<String>,
+    pub line: String,
+}
+
+/// Splits a console's output into lines and matches them against the rules.
+pub struct ConsoleMatcher<'a> {
+    rules: &'a ConsoleRules,
+    line: Vec<u8>,
+    // Prompt rules which already matched the incomplete line, so they don't match it again as more
+    // of it arrives.
+    matched_prompts: Vec<usize>,
+}
+
+impl<'a> ConsoleMatcher<'a> {
+    pub fn new(rules: &'a ConsoleRules) -> Self {
+        Self {
+            rules,
+            line: Vec::new(),
+            matched_prompts: Vec::new(),
+        }
+    }
+
+    /// Feed console output to the matcher, returning the rules matched by any lines it completes
+    /// (or prompts it ends with.)
+    pub fn feed(&mut self, data: &[u8]) -> Vec<RuleMatch<'a>> {
+        let mut matches = Vec::new();
+        for &byte in data {
+            if byte == b'\n' {
+                self.finish_line(&mut matches);
+            } else {
+                self.line.push(byte);
+                if self.line.len() >= MAX_LINE_LENGTH {
+                    self.finish_line(&mut matches);
+                }
+            }
+        }
+
+        if !self.line.is_empty() {
+            let line = clean_line(&self.line);
+            for (index, rule) in self.rules.0.iter().enumerate() {
+                if rule.prompt
+                    && !self.matched_prompts.contains(&index)
+                    && let Some(rule_match) = match_rule(rule, &line)
+                {
+                    self.matched_prompts.push(index);
+                    matches.push(rule_match);
+                }
+            }
+        }
+        matches
+    }
+
+    fn finish_line(&mut self, matches: &mut Vec<RuleMatch<'a>>) {
+        let line = clean_line(&self.line);
+        for (index, rule) in self.rules.0.iter().enumerate() {
+            if self.matched_prompts.contains(&index) {
+                continue;
+            }
+            matches.extend(match_rule(rule, &line));
+        }
+        self.line.clear();
+        self.matched_prompts.clear();
+    }
+}
+
+fn clean_line(line: &[u8]) -> String {
+    let stripped = strip_ansi_escapes::strip(line);
+    String::from_utf8_lossy(&stripped)
+        .trim_end_matches('\r')
+        .to_string()
+}
+
+fn match_rule<'a>(rule: &'a CompiledRule, line: &str) -> Option<RuleMatch<'a>> {
+    let captures = rule.regex.captures(line)?;
+    Some(RuleMatch {
+        rule,
+        target: captures.name("target").map(|m| m.as_str().to_string()),
+        line: line.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched_ids(rules: &ConsoleRules, log: &str) -> Vec<(String, Option<String>)> {
+        let mut matcher = ConsoleMatcher::new(rules);
+        matcher
+            .feed(log.as_bytes())
+            .into_iter()
+            .map(|m| (m.rule.id.to_string(), m.target))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_rules_against_captured_logs() {
+        let rules = ConsoleRules::compile(default_rules()).unwrap();
+
+        assert_eq!(
+            matched_ids(&rules, include_str!("test_data/kernel_panic.log")),
+            vec![
+                ("KernelPanic".to_string(), None),
+                ("KernelPanic".to_string(), None)
+            ]
+        );
+        assert_eq!(
+            matched_ids(&rules, include_str!("test_data/hardware_errors.log")),
+            vec![
+                ("MachineCheckException".to_string(), None),
+                ("PcieAerError".to_string(), Some("0000:41:00.0".to_string())),
+                ("GpuXid".to_string(), Some("0000:3b:00".to_string())),
+                ("GpuXid".to_string(), Some("0000:3b:00".to_string())),
+            ]
+        );
+        assert_eq!(
+            matched_ids(&rules, include_str!("test_data/uefi_hang.log")),
+            vec![("UefiHang".to_string(), None)]
+        );
+        assert_eq!(
+            matched_ids(&rules, include_str!("test_data/login.log")),
+            vec![("LoginPrompt".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn test_prompt_matches_once_across_chunks() {
+        let rules = ConsoleRules::compile(default_rules()).unwrap();
+        let mut matcher = ConsoleMatcher::new(&rules);
+
+        assert!(matcher.feed(b"\x1b[0mhost-1 lo").is_empty());
+        let matches = matcher.feed(b"gin: ");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, "host-1 login:");
+
+        // The user typing a username and pressing enter doesn't match the prompt again
+        assert!(matcher.feed(b"root").is_empty());
+        assert!(matcher.feed(b"\r\n").is_empty());
+    }
+
+    #[test]
+    fn test_load_rules_file() {
+        let rules: RulesFile = toml::from_str(
+            r#"
+            [[rules]]
+            id = "OomKill"
+            pattern = "Out of memory: Killed process"
+            message = "Out of memory"
+            classifications = ["SerialConsole"]
+            "#,
+        )
+        .unwrap();
+        let rules = ConsoleRules::compile(rules.rules).unwrap();
+        assert!(rules.0[0].alert);
+        assert_eq!(
+            matched_ids(
+                &rules,
+                "[ 1.0] Out of memory: Killed process 123 (stress)\n"
+            ),
+            vec![("OomKill".to_string(), None)]
+        );
+
+        let invalid = ConsoleRules::compile(vec![ConsoleRule {
+            pattern: "(".to_string(),
+            ..default_rules().remove(0)
+        }]);
+        assert!(matches!(invalid, Err(RulesError::InvalidRule { .. })));
+    }
+}
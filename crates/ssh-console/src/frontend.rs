@@ -38,6 +38,8 @@ use crate::bmc::connection::Kind;
 use crate::bmc::message_proxy;
 use crate::bmc::message_proxy::{ExecReply, ToBmcMessage};
 use crate::config::Config;
+use crate::session_recorder;
+use crate::session_recorder::{SessionEventSender, SessionMetadata};
 use crate::shutdown_handle::ShutdownHandle;
 use crate::ssh_cert_parsing::{certificate_contains_role, get_user_from_certificate};
 use crate::ssh_server::ServerMetrics;
@@ -85,6 +87,9 @@ pub struct Handler {
     bmc_connection_store: BmcConnectionStore,
     /// The machine_id or instance_id the user is attempting to log into. Used as the username in the ssh command line (ie. ssh machine_id@ssh-console)
     authenticated_machine_string: Option<String>,
+    /// The user from the client's openssh certificate, if they authenticated with one. Recorded in
+    /// session recordings.
+    authenticated_user: Option<String>,
     per_client_state: HashMap<ChannelId, PerClientState>,
     metrics: Arc<ServerMetrics>,
     last_auth_failure: Option<AuthFailureReason>,
@@ -96,6 +101,16 @@ struct PerClientState {
     bmc_connection: BmcConnectionSubscription,
     // Option so that it can be taken with .take() when we get a shell_request or exec_request
     client_channel: Option<Channel<Msg>>,
+    // Terminal details from the pty_request, if any, for the session recording header
+    pty: Option<PtyInfo>,
+    // Set once a shell_request starts recording the session
+    session_recording: Option<SessionEventSender>,
+}
+
+struct PtyInfo {
+    term: String,
+    col_width: u32,
+    row_height: u32,
 }
 
 impl Handler {
@@ -112,6 +127,7 @@ impl Handler {
             forge_api_client,
             bmc_connection_store,
             authenticated_machine_string: None,
+            authenticated_user: None,
             per_client_state: HashMap::new(),
             metrics,
             last_auth_failure: Default::default(),
@@ -209,6 +225,8 @@ impl russh::server::Handler for Handler {
             PerClientState {
                 bmc_connection,
                 client_channel: Some(channel),
+                pty: None,
+                session_recording: None,
             },
         );
 
@@ -302,6 +320,7 @@ impl russh::server::Handler for Handler {
             );
         }
         self.authenticated_machine_string = Some(machine_string.to_owned());
+        self.authenticated_user = user;
         Ok(Auth::Accept)
     }
 
@@ -374,6 +393,9 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "data");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            if let Some(session_recording) = &client_state.session_recording {
+                session_recording.input(data);
+            }
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
@@ -414,15 +436,22 @@ impl russh::server::Handler for Handler {
     async fn pty_request(
         &mut self,
         channel: ChannelId,
-        _term: &str,
-        _col_width: u32,
-        _row_height: u32,
+        term: &str,
+        col_width: u32,
+        row_height: u32,
         _pix_width: u32,
         _pix_height: u32,
         _modes: &[(Pty, u32)],
         session: &mut Session,
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "pty_request");
+        if let Some(client_state) = self.per_client_state.get_mut(&channel) {
+            client_state.pty = Some(PtyInfo {
+                term: term.to_owned(),
+                col_width,
+                row_height,
+            });
+        }
         session.channel_success(channel)?;
         Ok(())
     }
@@ -434,6 +463,9 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "shell_request");
         let peer_addr = self.peer_addr.clone();
+        let machine_string = self.authenticated_machine_string.clone();
+        let user = self.authenticated_user.clone();
+        let config = self.config.clone();
         let Some(client_state) = self.get_client_state_or_report_error(session, channel_id) else {
             return Ok(());
         };
@@ -458,14 +490,44 @@ impl russh::server::Handler for Handler {
             return Ok(());
         };
         let machine_id = client_state.bmc_connection.machine_id;
-        let Some(from_bmc_rx) = client_state
+        let Some(to_frontend_msg_tx) = client_state
             .bmc_connection
             .to_frontend_msg_weak_tx
             .upgrade()
-            .map(|tx| tx.subscribe())
         else {
             return Err(HandlerError::BmcDisconnectedBeforeSubscribe { machine_id })?;
         };
+        let from_bmc_rx = to_frontend_msg_tx.subscribe();
+
+        // Record the session, starting from the same point in the BMC output as the user sees it.
+        let recorder_handle = if config.session_recording_enabled {
+            let (term, width, height) = match &client_state.pty {
+                Some(pty) => (Some(pty.term.clone()), pty.col_width, pty.row_height),
+                None => (
+                    None,
+                    session_recorder::DEFAULT_WIDTH,
+                    session_recorder::DEFAULT_HEIGHT,
+                ),
+            };
+            let recorder_handle = session_recorder::spawn(
+                SessionMetadata {
+                    machine_string: machine_string.unwrap_or_else(|| machine_id.to_string()),
+                    machine_id,
+                    user,
+                    peer_addr: peer_addr.clone(),
+                    term,
+                    width,
+                    height,
+                },
+                to_frontend_msg_tx.subscribe(),
+                config,
+            );
+            client_state.session_recording = Some(recorder_handle.event_sender());
+            Some(recorder_handle)
+        } else {
+            None
+        };
+        drop(to_frontend_msg_tx);
 
         // Output the banner with instructions
         let banner = match client_state.bmc_connection.kind {
@@ -486,6 +548,9 @@ impl russh::server::Handler for Handler {
             .await
             .ok();
         if let Ok(pending_line) = pending_line_reply_rx.await {
+            if let Some(session_recording) = &client_state.session_recording {
+                session_recording.output(&pending_line);
+            }
             channel_tx.data(pending_line.as_slice()).await.ok();
         }
 
@@ -505,6 +570,9 @@ impl russh::server::Handler for Handler {
                     }
                 }
                 proxy_handle.shutdown_and_wait().await;
+                if let Some(recorder_handle) = recorder_handle {
+                    recorder_handle.shutdown_and_wait().await;
+                }
             }
         });
 
@@ -521,6 +589,7 @@ impl russh::server::Handler for Handler {
         let Some(PerClientState {
             client_channel,
             bmc_connection,
+            ..
         }) = self.get_client_state_or_report_error(session, channel_id)
         else {
             return Ok(());
@@ -598,6 +667,9 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "window_change_request");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            if let Some(session_recording) = &client_state.session_recording {
+                session_recording.resize(col_width, row_height);
+            }
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
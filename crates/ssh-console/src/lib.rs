@@ -20,9 +20,12 @@ mod io_util;
 mod metrics;
 mod ssh_cert_parsing;
 mod ssh_server;
+mod websocket_frontend;
 
 mod console_logger;
+mod console_monitor;
 mod frontend;
+mod session_recorder;
 
 // pub mods are only ones used by main.rs and integration tests
 pub mod config;
@@ -38,6 +41,7 @@ use tokio::task::JoinHandle;
 use crate::config::Config;
 use crate::metrics::MetricsState;
 use crate::shutdown_handle::{ReadyHandle, ShutdownHandle};
+use crate::ssh_server::ServerMetrics;
 
 pub static POWER_RESET_COMMAND: &str = "power reset";
 
@@ -47,34 +51,70 @@ pub async fn spawn(config: Config) -> Result<SpawnHandle, SpawnError> {
     let config = Arc::new(config);
     let metrics = Arc::new(MetricsState::new());
     let forge_api_client = config.make_forge_api_client();
+    let console_rules = if config.console_health_monitoring_enabled {
+        Some(Arc::new(console_monitor::load_rules(&config)?))
+    } else {
+        None
+    };
 
     // 1) Start BMC client pool
-    let mut bmc_client_pool =
-        bmc::client_pool::spawn(config.clone(), forge_api_client.clone(), &metrics.meter);
+    let mut bmc_client_pool = bmc::client_pool::spawn(
+        config.clone(),
+        forge_api_client.clone(),
+        console_rules,
+        &metrics.meter,
+    );
     bmc_client_pool
         .wait_until_ready()
         .await
         .map_err(|_| SpawnError::ClientPoolUnknownFailure)?;
 
     // 2) Start SSH server itself
+    let server_metrics = Arc::new(ServerMetrics::new(&metrics.meter, &config));
     let server = ssh_server::spawn(
         config.clone(),
         forge_api_client.clone(),
         bmc_client_pool.connection_store(),
-        &metrics.meter,
+        server_metrics.clone(),
     )
     .await?;
 
-    // 3) Start metrics server
+    // 3) Start the WebSocket frontend, if enabled
+    let websocket_frontend = match config.websocket_listen_address {
+        Some(listen_address) => Some(
+            websocket_frontend::spawn(
+                listen_address,
+                config.clone(),
+                forge_api_client.clone(),
+                bmc_client_pool.connection_store(),
+                server_metrics,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    // 4) Start metrics server
     let metrics_handle = metrics::spawn(config.clone(), metrics).await?;
 
-    // 4) Wait for a shutdown signal, then shut down the above
+    // 5) Periodically delete expired session recordings
+    let recording_pruner = config
+        .session_recording_enabled
+        .then(|| session_recorder::spawn_pruner(config.clone()));
+
+    // 6) Wait for a shutdown signal, then shut down the above
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let join_handle = tokio::spawn(async move {
         shutdown_rx.await.ok();
         metrics_handle.shutdown_and_wait().await;
+        if let Some(recording_pruner) = recording_pruner {
+            recording_pruner.shutdown_and_wait().await;
+        }
         bmc_client_pool.shutdown_and_wait().await;
         server.shutdown_and_wait().await;
+        if let Some(websocket_frontend) = websocket_frontend {
+            websocket_frontend.shutdown_and_wait().await;
+        }
     });
 
     Ok(SpawnHandle {
@@ -85,10 +125,14 @@ pub async fn spawn(config: Config) -> Result<SpawnHandle, SpawnError> {
 
 #[derive(thiserror::Error, Debug)]
 pub enum SpawnError {
+    #[error("Error loading console health rules: {0}")]
+    ConsoleRules(#[from] console_monitor::RulesError),
     #[error("Unknown failure spawning BMC client pool")]
     ClientPoolUnknownFailure,
     #[error("Error spawning SSH server: {0}")]
     SshServerSpawn(#[from] ssh_server::SpawnError),
+    #[error("Error spawning WebSocket frontend: {0}")]
+    WebsocketFrontendSpawn(#[from] websocket_frontend::SpawnError),
     #[error("Error spawning metrics server: {0}")]
     MetricsSpawn(#[from] metrics::SpawnError),
 }
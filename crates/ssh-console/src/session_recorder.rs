@@ -0,0 +1,595 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records interactive console sessions in [asciicast v2] format, one file per session.
+//!
+//! Unlike [`crate::console_logger`], which logs everything a BMC prints regardless of who is
+//! connected, a session recording captures a single user's interactive session: BMC output, the
+//! user's input, and terminal resizes, all with timing information. Recordings are written to
+//! `{session_recordings_path}/{machine_or_instance_id}/{started_at}_{session_id}.cast`.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use russh::ChannelMsg;
+use serde::Serialize;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+use crate::config::Config;
+use crate::shutdown_handle::ShutdownHandle;
+
+static RECORDING_EXTENSION: &str = "cast";
+
+/// How often to look for recordings older than `session_recording_retention`
+static PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Terminal size to declare in the recording header if the client did not request a pty.
+pub const DEFAULT_WIDTH: u32 = 80;
+pub const DEFAULT_HEIGHT: u32 = 24;
+
+/// Details about the session being recorded, written to the asciicast header.
+pub struct SessionMetadata {
+    /// The machine_id or instance_id the user logged into. Recordings are grouped by this.
+    pub machine_string: String,
+    pub machine_id: MachineId,
+    /// The user extracted from the client's openssh certificate, if they logged in with one.
+    pub user: Option<String>,
+    pub peer_addr: String,
+    pub term: Option<String>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Spawn a background task which records a single interactive session. BMC output is read from
+/// `output_rx`, and input/resize events are sent via [`SessionRecorderHandle::event_sender`].
+pub fn spawn(
+    metadata: SessionMetadata,
+    output_rx: broadcast::Receiver<ToFrontendMessage>,
+    config: Arc<Config>,
+) -> SessionRecorderHandle {
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let session_recorder = SessionRecorder::new(&config, metadata);
+
+    let join_handle = tokio::spawn(session_recorder.run(shutdown_rx, output_rx, event_rx));
+
+    SessionRecorderHandle {
+        shutdown_tx,
+        join_handle,
+        event_tx,
+    }
+}
+
+pub struct SessionRecorderHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+    event_tx: mpsc::UnboundedSender<SessionEvent>,
+}
+
+impl SessionRecorderHandle {
+    pub fn event_sender(&self) -> SessionEventSender {
+        SessionEventSender(self.event_tx.clone())
+    }
+}
+
+impl ShutdownHandle<()> for SessionRecorderHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+/// Sends events which don't come from the BMC's broadcast channel to a session recorder. Sending
+/// is best-effort: if the recorder has stopped, events are dropped.
+#[derive(Clone)]
+pub struct SessionEventSender(mpsc::UnboundedSender<SessionEvent>);
+
+impl SessionEventSender {
+    pub fn output(&self, data: &[u8]) {
+        self.0.send(SessionEvent::Output(data.to_vec())).ok();
+    }
+
+    pub fn input(&self, data: &[u8]) {
+        self.0.send(SessionEvent::Input(data.to_vec())).ok();
+    }
+
+    pub fn resize(&self, col_width: u32, row_height: u32) {
+        self.0
+            .send(SessionEvent::Resize {
+                col_width,
+                row_height,
+            })
+            .ok();
+    }
+}
+
+enum SessionEvent {
+    Output(Vec<u8>),
+    Input(Vec<u8>),
+    Resize { col_width: u32, row_height: u32 },
+}
+
+#[derive(Serialize)]
+struct AsciicastHeader<'a> {
+    version: u8,
+    width: u32,
+    height: u32,
+    timestamp: i64,
+    title: &'a str,
+    env: HashMap<&'static str, &'a str>,
+    // Fields below are not part of the asciicast spec, players ignore them.
+    machine_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<&'a str>,
+    peer_addr: &'a str,
+}
+
+struct SessionRecorder {
+    metadata: SessionMetadata,
+    recording_path: PathBuf,
+    max_size: u64,
+}
+
+impl SessionRecorder {
+    fn new(config: &Config, metadata: SessionMetadata) -> Self {
+        // machine_string is a machine_id or instance_id we already found a BMC connection for, but
+        // it's still user input: don't let it pick a directory outside of the recordings path.
+        let directory = if is_safe_path_component(&metadata.machine_string) {
+            metadata.machine_string.clone()
+        } else {
+            metadata.machine_id.to_string()
+        };
+        let file_name = format!(
+            "{}_{}.{RECORDING_EXTENSION}",
+            Utc::now().format("%Y%m%dT%H%M%SZ"),
+            Uuid::new_v4()
+        );
+
+        Self {
+            recording_path: config
+                .session_recordings_path
+                .join(directory)
+                .join(file_name),
+            max_size: config.session_recording_max_size.bytes() as _,
+            metadata,
+        }
+    }
+
+    async fn run(
+        self,
+        mut shutdown_rx: oneshot::Receiver<()>,
+        mut output_rx: broadcast::Receiver<ToFrontendMessage>,
+        mut event_rx: mpsc::UnboundedReceiver<SessionEvent>,
+    ) {
+        let machine_id = self.metadata.machine_id;
+        let mut writer = match self.open().await {
+            Ok(writer) => writer,
+            Err(error) => {
+                tracing::error!(path = self.recording_path.display().to_string(), %machine_id, %error, "could not open session recording for writing");
+                return;
+            }
+        };
+        tracing::info!(
+            path = self.recording_path.display().to_string(),
+            %machine_id,
+            peer_addr = self.metadata.peer_addr,
+            "recording session"
+        );
+
+        loop {
+            let result = tokio::select! {
+                _ = &mut shutdown_rx => {
+                    break;
+                }
+
+                res = output_rx.recv() => match res {
+                    Ok(msg) => match Arc::<ChannelMsg>::from(msg).as_ref() {
+                        ChannelMsg::Data { data } | ChannelMsg::ExtendedData { data, .. } => {
+                            writer.write_data("o", data.as_ref()).await
+                        }
+                        _ => Ok(()),
+                    },
+                    Err(broadcast::error::RecvError::Closed) => {
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        tracing::warn!(%machine_id, "session recorder is lagged by {count} messages, data may be missing from recording");
+                        writer.write_event("m", &format!("recorder lagged by {count} messages, output may be missing")).await
+                    }
+                },
+
+                Some(event) = event_rx.recv() => match event {
+                    SessionEvent::Output(data) => writer.write_data("o", &data).await,
+                    SessionEvent::Input(data) => writer.write_data("i", &data).await,
+                    SessionEvent::Resize { col_width, row_height } => {
+                        writer.write_event("r", &format!("{col_width}x{row_height}")).await
+                    }
+                },
+            };
+
+            if let Err(error) = result {
+                tracing::error!(path = self.recording_path.display().to_string(), %machine_id, %error, "error writing session recording, stopping recording");
+                break;
+            }
+        }
+
+        tracing::debug!(%machine_id, "shutting down session recorder");
+        writer.file.flush().await.ok();
+    }
+
+    async fn open(&self) -> io::Result<CastWriter> {
+        if let Some(parent) = self.recording_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&self.recording_path)
+            .await?;
+
+        let header = AsciicastHeader {
+            version: 2,
+            width: self.metadata.width,
+            height: self.metadata.height,
+            timestamp: Utc::now().timestamp(),
+            title: &self.metadata.machine_string,
+            env: self
+                .metadata
+                .term
+                .as_deref()
+                .map(|term| HashMap::from([("TERM", term)]))
+                .unwrap_or_default(),
+            machine_id: self.metadata.machine_id.to_string(),
+            user: self.metadata.user.as_deref(),
+            peer_addr: &self.metadata.peer_addr,
+        };
+        let mut line = serde_json::to_vec(&header).map_err(io::Error::other)?;
+        line.push(b'\n');
+        file.write_all(&line).await?;
+
+        Ok(CastWriter {
+            file,
+            started_at: Instant::now(),
+            byte_count: line.len() as u64,
+            max_size: self.max_size,
+            truncated: false,
+            input_utf8: Utf8Decoder::default(),
+            output_utf8: Utf8Decoder::default(),
+        })
+    }
+}
+
+/// Writes asciicast events to a recording file, up to a maximum size.
+struct CastWriter {
+    file: tokio::fs::File,
+    started_at: Instant,
+    byte_count: u64,
+    max_size: u64,
+    truncated: bool,
+    input_utf8: Utf8Decoder,
+    output_utf8: Utf8Decoder,
+}
+
+impl CastWriter {
+    async fn write_data(&mut self, code: &'static str, data: &[u8]) -> io::Result<()> {
+        let text = match code {
+            "i" => self.input_utf8.decode(data),
+            _ => self.output_utf8.decode(data),
+        };
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.write_event(code, &text).await
+    }
+
+    async fn write_event(&mut self, code: &'static str, data: &str) -> io::Result<()> {
+        if self.truncated {
+            return Ok(());
+        }
+
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        let mut line = serde_json::to_vec(&(elapsed, code, data)).map_err(io::Error::other)?;
+        line.push(b'\n');
+
+        if self.byte_count + line.len() as u64 > self.max_size {
+            // Leave a marker so whoever is replaying knows why the recording ends here.
+            self.truncated = true;
+            line = serde_json::to_vec(&(
+                elapsed,
+                "m",
+                "recording truncated: session_recording_max_size reached",
+            ))
+            .map_err(io::Error::other)?;
+            line.push(b'\n');
+        }
+
+        self.byte_count += line.len() as u64;
+        self.file.write_all(&line).await
+    }
+}
+
+/// asciicast events are JSON strings, but BMC output arrives in arbitrary chunks which can split a
+/// multi-byte UTF-8 sequence. Hold on to an incomplete trailing sequence until the next chunk
+/// arrives, and replace anything actually invalid.
+#[derive(Default)]
+struct Utf8Decoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    fn decode(&mut self, data: &[u8]) -> String {
+        self.pending.extend_from_slice(data);
+        let complete_len = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // error_len() is None when the input ends in the middle of a valid sequence
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        let rest = self.pending.split_off(complete_len);
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+        text
+    }
+}
+
+/// Whether `s` can be used as a single path component under the recordings directory.
+fn is_safe_path_component(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !s.starts_with('.')
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct RecordingInfo {
+    /// The machine_id or instance_id the session logged into
+    pub machine: String,
+    pub name: String,
+    pub size_bytes: u64,
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub modified: DateTime<Utc>,
+}
+
+fn serialize_rfc3339<S>(t: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&t.to_rfc3339())
+}
+
+/// List the recordings under `root`, optionally only those for a single machine or instance.
+/// Sorted by machine, then oldest first.
+pub async fn list_recordings(root: &Path, machine: Option<&str>) -> io::Result<Vec<RecordingInfo>> {
+    let machines = match machine {
+        Some(machine) if is_safe_path_component(machine) => vec![machine.to_string()],
+        Some(_) => return Ok(vec![]),
+        None => {
+            let mut machines = vec![];
+            let Some(mut entries) = read_dir_if_exists(root).await? else {
+                return Ok(vec![]);
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_dir()
+                    && let Some(name) = entry.file_name().to_str()
+                {
+                    machines.push(name.to_string());
+                }
+            }
+            machines
+        }
+    };
+
+    let mut recordings = vec![];
+    for machine in machines {
+        let Some(mut entries) = read_dir_if_exists(&root.join(&machine)).await? else {
+            continue;
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(RECORDING_EXTENSION) {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let metadata = entry.metadata().await?;
+            recordings.push(RecordingInfo {
+                machine: machine.clone(),
+                name,
+                size_bytes: metadata.len(),
+                modified: metadata.modified()?.into(),
+            });
+        }
+    }
+
+    recordings.sort_by(|a, b| (&a.machine, &a.name).cmp(&(&b.machine, &b.name)));
+    Ok(recordings)
+}
+
+/// Resolve a recording by machine and file name, as returned by [`list_recordings`]. Returns None
+/// for names which could point outside of `root` or at something other than a recording.
+pub fn recording_path(root: &Path, machine: &str, name: &str) -> Option<PathBuf> {
+    if !is_safe_path_component(machine) || !is_safe_path_component(name) {
+        return None;
+    }
+    let path = root.join(machine).join(name);
+    (path.extension().and_then(|e| e.to_str()) == Some(RECORDING_EXTENSION)).then_some(path)
+}
+
+async fn read_dir_if_exists(path: &Path) -> io::Result<Option<tokio::fs::ReadDir>> {
+    match tokio::fs::read_dir(path).await {
+        Ok(entries) => Ok(Some(entries)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Spawn a background task which periodically deletes recordings older than
+/// `session_recording_retention`.
+pub fn spawn_pruner(config: Arc<Config>) -> PrunerHandle {
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+    let join_handle = tokio::spawn(async move {
+        loop {
+            match prune_recordings(
+                &config.session_recordings_path,
+                config.session_recording_retention,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("pruned {count} expired session recordings"),
+                Err(error) => tracing::error!(%error, "error pruning session recordings"),
+            }
+
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                _ = tokio::time::sleep(PRUNE_INTERVAL) => {}
+            }
+        }
+    });
+
+    PrunerHandle {
+        shutdown_tx,
+        join_handle,
+    }
+}
+
+pub struct PrunerHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for PrunerHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+/// Delete recordings last modified more than `retention` ago, removing machine directories left
+/// empty. Returns how many recordings were deleted.
+async fn prune_recordings(root: &Path, retention: Duration) -> io::Result<usize> {
+    let Some(cutoff) = SystemTime::now().checked_sub(retention) else {
+        return Ok(0);
+    };
+
+    let mut pruned = 0;
+    for recording in list_recordings(root, None).await? {
+        if SystemTime::from(recording.modified) >= cutoff {
+            continue;
+        }
+        let path = root.join(&recording.machine).join(&recording.name);
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => pruned += 1,
+            Err(error) => {
+                tracing::warn!(path = path.display().to_string(), %error, "could not delete expired session recording");
+                continue;
+            }
+        }
+        // Fails if there are other recordings left for this machine, which is fine.
+        tokio::fs::remove_dir(root.join(&recording.machine))
+            .await
+            .ok();
+    }
+
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod tests {
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_utf8_decoder_split_sequence() {
+        let mut decoder = Utf8Decoder::default();
+        let bytes = "a→b".as_bytes();
+        // Split in the middle of the 3-byte arrow
+        assert_eq!(decoder.decode(&bytes[..2]), "a");
+        assert_eq!(decoder.decode(&bytes[2..3]), "");
+        assert_eq!(decoder.decode(&bytes[3..]), "→b");
+    }
+
+    #[test]
+    fn test_utf8_decoder_invalid_bytes() {
+        let mut decoder = Utf8Decoder::default();
+        assert_eq!(decoder.decode(b"a\xffb"), "a\u{fffd}b");
+        assert!(decoder.pending.is_empty());
+    }
+
+    #[test]
+    fn test_recording_path_rejects_traversal() {
+        let root = Path::new("/recordings");
+        assert_eq!(
+            recording_path(root, "machine", "20260101T000000Z_x.cast"),
+            Some(PathBuf::from("/recordings/machine/20260101T000000Z_x.cast"))
+        );
+        assert_eq!(recording_path(root, "..", "x.cast"), None);
+        assert_eq!(recording_path(root, "machine", "../x.cast"), None);
+        assert_eq!(recording_path(root, "machine", "x.log"), None);
+        assert_eq!(recording_path(root, "a/b", "x.cast"), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_prune_recordings() {
+        let root = TempDir::new().unwrap();
+        let old_dir = root.path().join("old-machine");
+        let new_dir = root.path().join("new-machine");
+        std::fs::create_dir_all(&old_dir).unwrap();
+        std::fs::create_dir_all(&new_dir).unwrap();
+        std::fs::write(new_dir.join("2.cast"), "{}\n").unwrap();
+        std::fs::write(new_dir.join("ignored.txt"), "").unwrap();
+        let old_file = std::fs::File::create(old_dir.join("1.cast")).unwrap();
+        old_file
+            .set_modified(SystemTime::now() - Duration::from_secs(7200))
+            .unwrap();
+
+        let recordings = list_recordings(root.path(), None).await.unwrap();
+        assert_eq!(
+            recordings
+                .iter()
+                .map(|r| (r.machine.as_str(), r.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("new-machine", "2.cast"), ("old-machine", "1.cast")]
+        );
+        assert_eq!(recordings[0].size_bytes, 3);
+
+        let recordings = list_recordings(root.path(), Some("old-machine"))
+            .await
+            .unwrap();
+        assert_eq!(recordings.len(), 1);
+
+        let pruned = prune_recordings(root.path(), Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(pruned, 1);
+        assert!(!old_dir.exists());
+        assert!(new_dir.join("2.cast").exists());
+    }
+}
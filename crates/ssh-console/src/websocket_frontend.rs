@@ -0,0 +1,646 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Browser-facing serial console frontend.
+//!
+//! Clients connect to `GET /console?token=<token>` with a console access token minted by
+//! carbide-api, which names the instance to attach to and whether the client may type into it. Any
+//! number of read-only viewers can attach to a console, but only one client at a time may write to
+//! it: read-write tokens are downgraded to read-only while another writer is attached.
+//!
+//! BMC output is sent to the client as binary messages. Binary messages from the client are
+//! forwarded to the BMC as input, and text messages carry JSON control messages, like
+//! `{"type": "resize", "cols": 80, "rows": 24}`.
+//!
+//! Session recordings are served to carbide-api, which proxies them to authorized users. Each
+//! request carries a short-lived recording access token minted by carbide-api as a bearer token:
+//! - `GET /recordings`: list all recordings as JSON
+//! - `GET /recordings/{machine}`: list recordings for a machine_id or instance_id as JSON
+//! - `GET /recordings/{machine}/{name}`: fetch a recording (asciicast v2)
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::Json;
+use axum::Router;
+use axum::body::Body;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use futures_util::{SinkExt, StreamExt};
+use rpc::forge::{ValidateConsoleAccessTokenRequest, ValidateConsoleRecordingAccessTokenRequest};
+use rpc::forge_api_client::ForgeApiClient;
+use russh::ChannelMsg;
+use serde::Deserialize;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_util::io::ReaderStream;
+use tonic::Code;
+
+use crate::bmc::client::BmcConnectionSubscription;
+use crate::bmc::client_pool::BmcConnectionStore;
+use crate::bmc::message_proxy::{ToBmcMessage, ToFrontendMessage};
+use crate::config::Config;
+use crate::session_recorder;
+use crate::session_recorder::SessionMetadata;
+use crate::shutdown_handle::ShutdownHandle;
+use crate::ssh_server::ServerMetrics;
+
+static BANNER_WEBSOCKET: &str = "\
++------------------------------------------------------------------------------+\r\n\
+|                NVIDIA Carbide Serial Console (beta)                          |\r\n\
++------------------------------------------------------------------------------+\r\n\
+";
+
+static READ_ONLY_NOTICE: &str = "--- Read-only session: input is ignored ---\r\n";
+
+static WRITER_ATTACHED_NOTICE: &str =
+    "--- Another session has write access to this console: input is ignored ---\r\n";
+
+/// Run the WebSocket console frontend in the background, listening on `listen_address`.
+pub async fn spawn(
+    listen_address: SocketAddr,
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    bmc_connection_store: BmcConnectionStore,
+    metrics: Arc<ServerMetrics>,
+) -> Result<Handle, SpawnError> {
+    let listener =
+        TcpListener::bind(listen_address)
+            .await
+            .map_err(|error| SpawnError::Listening {
+                addr: listen_address,
+                error,
+            })?;
+    tracing::info!("websocket frontend listening on {}", listen_address);
+
+    let router = Router::new()
+        .route("/console", get(console))
+        .route("/recordings", get(list_all_recordings))
+        .route("/recordings/{machine}", get(list_machine_recordings))
+        .route("/recordings/{machine}/{name}", get(get_recording))
+        .with_state(Arc::new(FrontendState {
+            config,
+            forge_api_client,
+            bmc_connection_store,
+            metrics,
+            writers: WriterLocks::default(),
+        }));
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let join_handle = tokio::spawn(async move {
+        // Stop accepting connections on shutdown. Sessions which are already attached end when
+        // their client or BMC disconnects, the same as SSH sessions.
+        tokio::select! {
+            result = axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            ) => {
+                if let Err(error) = result {
+                    tracing::error!(%error, "websocket frontend exited with error");
+                }
+            }
+            _ = shutdown_rx => {
+                tracing::info!("websocket frontend shutting down");
+            }
+        }
+    });
+
+    Ok(Handle {
+        shutdown_tx,
+        join_handle,
+    })
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SpawnError {
+    #[error("Error listening on {addr}: {error}")]
+    Listening {
+        addr: SocketAddr,
+        error: std::io::Error,
+    },
+}
+
+pub struct Handle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for Handle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+struct FrontendState {
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    bmc_connection_store: BmcConnectionStore,
+    metrics: Arc<ServerMetrics>,
+    writers: WriterLocks,
+}
+
+#[derive(Deserialize)]
+struct ConsoleParams {
+    token: String,
+}
+
+/// Control messages sent by the client as text messages
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ControlMessage {
+    Resize { cols: u32, rows: u32 },
+}
+
+/// Validate the token and find the BMC connection before upgrading, so that failures can be
+/// reported with a proper HTTP status.
+async fn console(
+    State(state): State<Arc<FrontendState>>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Query(params): Query<ConsoleParams>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let peer_addr = peer_addr.to_string();
+
+    let validated = match state
+        .forge_api_client
+        .validate_console_access_token(ValidateConsoleAccessTokenRequest {
+            token: params.token,
+        })
+        .await
+    {
+        Ok(validated) => validated,
+        Err(status)
+            if matches!(
+                status.code(),
+                Code::PermissionDenied | Code::InvalidArgument
+            ) =>
+        {
+            tracing::warn!(peer_addr, error = %status.message(), "console access token rejected");
+            state.metrics.client_auth_failures_total.add(
+                1,
+                &[opentelemetry::KeyValue::new(
+                    "auth_type",
+                    "console_access_token",
+                )],
+            );
+            return (StatusCode::FORBIDDEN, "Invalid console access token").into_response();
+        }
+        Err(status) => {
+            tracing::error!(peer_addr, %status, "error validating console access token");
+            return (
+                StatusCode::BAD_GATEWAY,
+                "Error validating console access token",
+            )
+                .into_response();
+        }
+    };
+    let (Some(instance_id), Some(machine_id)) = (validated.instance_id, validated.machine_id)
+    else {
+        tracing::error!(
+            peer_addr,
+            "carbide-api returned an incomplete console access token"
+        );
+        return (
+            StatusCode::BAD_GATEWAY,
+            "Error validating console access token",
+        )
+            .into_response();
+    };
+
+    let bmc_connection = match state
+        .bmc_connection_store
+        .get_connection(
+            &machine_id.to_string(),
+            &state.config,
+            &state.forge_api_client,
+            state.metrics.clone(),
+        )
+        .await
+    {
+        Ok(bmc_connection) => bmc_connection,
+        Err(error) => {
+            tracing::warn!(peer_addr, %machine_id, %error, "could not get BMC connection");
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Console for instance {instance_id} is not available"),
+            )
+                .into_response();
+        }
+    };
+
+    tracing::info!(
+        peer_addr,
+        %instance_id,
+        %machine_id,
+        read_only = validated.read_only,
+        "websocket console connection"
+    );
+    ws.on_upgrade(move |socket| {
+        run_session(
+            socket,
+            state,
+            ConsoleSession {
+                instance_id,
+                bmc_connection,
+                wants_write: !validated.read_only,
+                peer_addr,
+            },
+        )
+    })
+}
+
+struct ConsoleSession {
+    instance_id: InstanceId,
+    bmc_connection: BmcConnectionSubscription,
+    wants_write: bool,
+    peer_addr: String,
+}
+
+async fn run_session(socket: WebSocket, state: Arc<FrontendState>, session: ConsoleSession) {
+    let ConsoleSession {
+        instance_id,
+        bmc_connection,
+        wants_write,
+        peer_addr,
+    } = session;
+    let machine_id = bmc_connection.machine_id;
+    let (mut to_client, mut from_client) = socket.split();
+
+    let Some(to_frontend_msg_tx) = bmc_connection.to_frontend_msg_weak_tx.upgrade() else {
+        tracing::warn!(
+            peer_addr,
+            %machine_id,
+            "BMC connection dropped before we could subscribe to messages"
+        );
+        to_client
+            .send(Message::Text(
+                "ssh-console error: BMC disconnected\r\n".into(),
+            ))
+            .await
+            .ok();
+        return;
+    };
+    let mut from_bmc_rx = to_frontend_msg_tx.subscribe();
+
+    // Record the session, starting from the same point in the BMC output as the user sees it.
+    let recorder_handle = state.config.session_recording_enabled.then(|| {
+        session_recorder::spawn(
+            SessionMetadata {
+                machine_string: instance_id.to_string(),
+                machine_id,
+                user: None,
+                peer_addr: peer_addr.clone(),
+                term: None,
+                width: session_recorder::DEFAULT_WIDTH,
+                height: session_recorder::DEFAULT_HEIGHT,
+            },
+            to_frontend_msg_tx.subscribe(),
+            state.config.clone(),
+        )
+    });
+    let session_recording = recorder_handle
+        .as_ref()
+        .map(|recorder_handle| recorder_handle.event_sender());
+    drop(to_frontend_msg_tx);
+
+    // Held for as long as this session may write to the console
+    let writer_guard = if wants_write {
+        state.writers.try_acquire(machine_id)
+    } else {
+        None
+    };
+
+    let mut banner = BANNER_WEBSOCKET.to_string();
+    if !wants_write {
+        banner.push_str(READ_ONLY_NOTICE);
+    } else if writer_guard.is_none() {
+        banner.push_str(WRITER_ATTACHED_NOTICE);
+    }
+    to_client.send(Message::Text(banner.into())).await.ok();
+
+    // Tell the backend to return any "pending line": data since the last newline
+    let (pending_line_reply_tx, pending_line_reply_rx) = oneshot::channel();
+    bmc_connection
+        .to_bmc_msg_tx
+        .send(ToBmcMessage::EchoConnectionMessage {
+            reply_tx: pending_line_reply_tx,
+        })
+        .await
+        .ok();
+    if let Ok(pending_line) = pending_line_reply_rx.await {
+        if let Some(session_recording) = &session_recording {
+            session_recording.output(&pending_line);
+        }
+        to_client
+            .send(Message::Binary(pending_line.into()))
+            .await
+            .ok();
+    }
+
+    loop {
+        tokio::select! {
+            res = from_bmc_rx.recv() => {
+                let Ok(msg) = res else {
+                    tracing::debug!(peer_addr, "BMC connection closed");
+                    break;
+                };
+                let Some(data) = output_data(msg) else {
+                    continue;
+                };
+                if let Err(error) = to_client.send(Message::Binary(data.into())).await {
+                    tracing::debug!(
+                        peer_addr,
+                        %error,
+                        "error sending message to websocket client, likely disconnected"
+                    );
+                    break;
+                }
+            }
+
+            frame = from_client.next() => {
+                let msg = match frame {
+                    Some(Ok(Message::Close(_))) | None => {
+                        tracing::debug!(peer_addr, "websocket client disconnected");
+                        break;
+                    }
+                    Some(Err(error)) => {
+                        tracing::debug!(peer_addr, %error, "error reading from websocket client");
+                        break;
+                    }
+                    Some(Ok(Message::Binary(data))) => {
+                        if let Some(session_recording) = &session_recording {
+                            session_recording.input(&data);
+                        }
+                        ToBmcMessage::ChannelMsg(ChannelMsg::Data {
+                            data: data.to_vec().into(),
+                        })
+                    }
+                    Some(Ok(Message::Text(text))) => {
+                        match serde_json::from_str::<ControlMessage>(text.as_str()) {
+                            Ok(ControlMessage::Resize { cols, rows }) => {
+                                if let Some(session_recording) = &session_recording {
+                                    session_recording.resize(cols, rows);
+                                }
+                                ToBmcMessage::ChannelMsg(ChannelMsg::WindowChange {
+                                    col_width: cols,
+                                    row_height: rows,
+                                    pix_width: 0,
+                                    pix_height: 0,
+                                })
+                            }
+                            Err(error) => {
+                                tracing::debug!(peer_addr, %error, "ignoring invalid control message");
+                                continue;
+                            }
+                        }
+                    }
+                    // axum replies to pings itself
+                    Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+                };
+
+                // Viewers can watch the console, but not type into it or resize it
+                if writer_guard.is_none() {
+                    continue;
+                }
+                if bmc_connection.to_bmc_msg_tx.send(msg).await.is_err() {
+                    tracing::debug!(peer_addr, "BMC connection closed when writing client input");
+                    break;
+                }
+            }
+        }
+    }
+
+    to_client.close().await.ok();
+    drop(writer_guard);
+    if let Some(recorder_handle) = recorder_handle {
+        recorder_handle.shutdown_and_wait().await;
+    }
+    tracing::info!(peer_addr, %instance_id, "end websocket console connection");
+}
+
+/// Checks the recording access token carbide-api passes as a bearer token, returning the response
+/// to send if it's missing or invalid.
+async fn authorize_recordings(
+    state: &FrontendState,
+    headers: &HeaderMap,
+    peer_addr: SocketAddr,
+) -> Result<(), Response> {
+    if !state.config.session_recording_enabled {
+        return Err((StatusCode::NOT_FOUND, "Session recording is disabled").into_response());
+    }
+
+    let Some(token) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return Err((StatusCode::UNAUTHORIZED, "Missing recording access token").into_response());
+    };
+
+    match state
+        .forge_api_client
+        .validate_console_recording_access_token(ValidateConsoleRecordingAccessTokenRequest {
+            token: token.to_string(),
+        })
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(status)
+            if matches!(
+                status.code(),
+                Code::PermissionDenied | Code::InvalidArgument
+            ) =>
+        {
+            tracing::warn!(%peer_addr, error = %status.message(), "recording access token rejected");
+            state.metrics.client_auth_failures_total.add(
+                1,
+                &[opentelemetry::KeyValue::new(
+                    "auth_type",
+                    "recording_access_token",
+                )],
+            );
+            Err((StatusCode::FORBIDDEN, "Invalid recording access token").into_response())
+        }
+        Err(status) => {
+            tracing::error!(%peer_addr, %status, "error validating recording access token");
+            Err((
+                StatusCode::BAD_GATEWAY,
+                "Error validating recording access token",
+            )
+                .into_response())
+        }
+    }
+}
+
+async fn list_all_recordings(
+    State(state): State<Arc<FrontendState>>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    list_recordings(&state, &headers, peer_addr, None).await
+}
+
+async fn list_machine_recordings(
+    State(state): State<Arc<FrontendState>>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(machine): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    list_recordings(&state, &headers, peer_addr, Some(&machine)).await
+}
+
+async fn list_recordings(
+    state: &FrontendState,
+    headers: &HeaderMap,
+    peer_addr: SocketAddr,
+    machine: Option<&str>,
+) -> Response {
+    if let Err(response) = authorize_recordings(state, headers, peer_addr).await {
+        return response;
+    }
+
+    match session_recorder::list_recordings(&state.config.session_recordings_path, machine).await {
+        Ok(recordings) => Json(recordings).into_response(),
+        Err(error) => {
+            tracing::error!(%error, "error listing session recordings");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Error listing recordings: {error}"),
+            )
+                .into_response()
+        }
+    }
+}
+
+async fn get_recording(
+    State(state): State<Arc<FrontendState>>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path((machine, name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(response) = authorize_recordings(&state, &headers, peer_addr).await {
+        return response;
+    }
+
+    let Some(recording_path) =
+        session_recorder::recording_path(&state.config.session_recordings_path, &machine, &name)
+    else {
+        return (StatusCode::BAD_REQUEST, "Invalid recording name").into_response();
+    };
+    match tokio::fs::File::open(&recording_path).await {
+        Ok(file) => (
+            [(CONTENT_TYPE, "application/x-asciicast")],
+            Body::from_stream(ReaderStream::new(file)),
+        )
+            .into_response(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            (StatusCode::NOT_FOUND, "Recording not found").into_response()
+        }
+        Err(error) => {
+            tracing::error!(%error, path = recording_path.display().to_string(), "error reading session recording");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Error reading recording: {error}"),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Returns the bytes to send to the client for a message from the BMC, if any.
+fn output_data(msg: ToFrontendMessage) -> Option<Vec<u8>> {
+    match Arc::<ChannelMsg>::from(msg).as_ref() {
+        ChannelMsg::Data { data } | ChannelMsg::ExtendedData { data, .. } => Some(data.to_vec()),
+        _ => None,
+    }
+}
+
+/// Tracks which machines have a client attached with write access.
+#[derive(Clone, Default)]
+struct WriterLocks(Arc<Mutex<HashSet<MachineId>>>);
+
+impl WriterLocks {
+    /// Returns a guard granting write access to the machine's console, or None if another client
+    /// already has it.
+    fn try_acquire(&self, machine_id: MachineId) -> Option<WriterGuard> {
+        self.0
+            .lock()
+            .expect("lock poisoned")
+            .insert(machine_id)
+            .then(|| WriterGuard {
+                locks: self.clone(),
+                machine_id,
+            })
+    }
+}
+
+struct WriterGuard {
+    locks: WriterLocks,
+    machine_id: MachineId,
+}
+
+impl Drop for WriterGuard {
+    fn drop(&mut self) {
+        self.locks
+            .0
+            .lock()
+            .expect("lock poisoned")
+            .remove(&self.machine_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_control_message() {
+        assert_eq!(
+            serde_json::from_str::<ControlMessage>(r#"{"type":"resize","cols":120,"rows":40}"#)
+                .unwrap(),
+            ControlMessage::Resize {
+                cols: 120,
+                rows: 40
+            }
+        );
+        assert!(serde_json::from_str::<ControlMessage>(r#"{"type":"paste"}"#).is_err());
+        assert!(serde_json::from_str::<ControlMessage>("ls -l").is_err());
+    }
+
+    #[test]
+    fn test_single_writer_per_machine() {
+        let writers = WriterLocks::default();
+        let machine_id: MachineId = "fm100hteau2jdt69qg575qld4lj05me09u2qp7ei38uv7volvprkck9enkg"
+            .parse()
+            .unwrap();
+
+        let guard = writers.try_acquire(machine_id).expect("first writer");
+        assert!(writers.try_acquire(machine_id).is_none());
+
+        // Access is released when the writer disconnects
+        drop(guard);
+        assert!(writers.try_acquire(machine_id).is_some());
+    }
+}
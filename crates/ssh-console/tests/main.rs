@@ -156,6 +156,41 @@ async fn test_ssh_console() -> eyre::Result<()> {
         );
     }
 
+    // Each interactive session should have been recorded, grouped by the machine_id or
+    // instance_id used to log in.
+    let recordings_path = handle.recordings_dir.path();
+    for mock_host in env.mock_hosts.iter() {
+        for machine_string in [
+            mock_host.machine_id.to_string(),
+            mock_host.instance_id.to_string(),
+        ] {
+            let machine_recordings_path = recordings_path.join(&machine_string);
+            let recording_path = std::fs::read_dir(&machine_recordings_path)
+                .with_context(|| {
+                    format!(
+                        "no session recordings at {}",
+                        machine_recordings_path.display()
+                    )
+                })?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .find(|path| path.extension().is_some_and(|ext| ext == "cast"))
+                .ok_or_else(|| {
+                    eyre::eyre!("no .cast files in {}", machine_recordings_path.display())
+                })?;
+
+            let recording = std::fs::read_to_string(&recording_path).with_context(|| {
+                format!("error reading recording at {}", recording_path.display())
+            })?;
+            let header: serde_json::Value =
+                serde_json::from_str(recording.lines().next().unwrap_or_default())
+                    .with_context(|| format!("invalid header in {}", recording_path.display()))?;
+            assert_eq!(header["version"], 2);
+            assert_eq!(header["title"], machine_string.as_str());
+            assert_eq!(header["machine_id"], mock_host.machine_id.to_string());
+        }
+    }
+
     Ok(())
 }
 
@@ -58,10 +58,13 @@ pub async fn spawn(
     };
 
     let logs_dir = TempDir::new().context("error creating temp dir for console logs")?;
+    let recordings_dir =
+        TempDir::new().context("error creating temp dir for session recordings")?;
 
     let config = ssh_console::config::Config {
         listen_address,
         metrics_address,
+        websocket_listen_address: None,
         carbide_uri: format!("https://localhost:{carbide_port}")
             .try_into()
             .expect("Invalid URI?"),
@@ -98,6 +101,14 @@ pub async fn spawn(
             .unwrap_or(Duration::ZERO),
         log_rotate_max_rotated_files: 3,
         log_rotate_max_size: Size::from_kib(10),
+        session_recording_enabled: true,
+        session_recordings_path: recordings_dir.path().to_path_buf(),
+        session_recording_max_size: Defaults::session_recording_max_size(),
+        session_recording_retention: Defaults::session_recording_retention(),
+        console_health_monitoring_enabled: false,
+        console_health_rules_path: None,
+        console_health_report_interval: Defaults::console_health_report_interval(),
+        console_health_alert_expiry: Defaults::console_health_alert_expiry(),
         hosts: true,
         openssh_certificate_authorization: ssh_console::config::Defaults::cert_authorization(),
     };
@@ -107,8 +118,9 @@ pub async fn spawn(
     Ok(NewSshConsoleHandle {
         addr: listen_address,
         metrics_address,
-        // Make sure the logs dir doesn't drop.
+        // Make sure the logs and recordings dirs don't drop.
         logs_dir,
+        recordings_dir,
         spawn_handle,
     })
 }
@@ -117,6 +129,7 @@ pub struct NewSshConsoleHandle {
     pub addr: SocketAddr,
     pub metrics_address: SocketAddr,
     pub logs_dir: TempDir,
+    pub recordings_dir: TempDir,
     pub spawn_handle: ssh_console::SpawnHandle,
 }
 
@@ -28,6 +28,17 @@ impl UuidSubtype for DomainIdMarker {
 /// an Infiniband domain ID.
 pub type DomainId = TypedUuid<DomainIdMarker>;
 
+/// Marker type for TenantDnsRecordId.
+pub struct TenantDnsRecordIdMarker;
+
+impl UuidSubtype for TenantDnsRecordIdMarker {
+    const TYPE_NAME: &'static str = "TenantDnsRecordId";
+}
+
+/// TenantDnsRecordId is a strongly typed UUID specific to
+/// a DNS record managed by a tenant inside one of its domains.
+pub type TenantDnsRecordId = TypedUuid<TenantDnsRecordIdMarker>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,3 +47,10 @@ mod tests {
     // ensuring TYPE_NAME and DB_COLUMN_NAME test correctly.
     typed_uuid_tests!(DomainId, "DomainId", "id");
 }
+
+#[cfg(test)]
+mod tenant_dns_record_id_tests {
+    use super::*;
+    use crate::typed_uuid_tests;
+    typed_uuid_tests!(TenantDnsRecordId, "TenantDnsRecordId", "id");
+}